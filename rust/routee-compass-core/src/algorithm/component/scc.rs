@@ -148,10 +148,8 @@ pub fn largest_strongly_connected_component(graph: &Graph) -> Result<Vec<VertexI
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        model::property::{edge::Edge, vertex::Vertex},
-        util::compact_ordered_hash_map::CompactOrderedHashMap,
-    };
+    use crate::model::property::{edge::Edge, vertex::Vertex};
+    use crate::test_util::graph_fixture::graph_from_edges;
 
     fn build_mock_graph() -> Graph {
         let vertices = vec![
@@ -177,24 +175,7 @@ mod tests {
             Edge::new(11, 3, 1, 10.0),
             Edge::new(12, 4, 4, 10.0),
         ];
-
-        // Create the adjacency and reverse adjacency lists.
-        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
-        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
-
-        for edge in &edges {
-            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
-            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
-        }
-
-        // Construct the Graph instance.
-
-        Graph {
-            adj: adj.into_boxed_slice(),
-            rev: rev.into_boxed_slice(),
-            edges: edges.into_boxed_slice(),
-            vertices: vertices.into_boxed_slice(),
-        }
+        graph_from_edges(vertices, edges)
     }
 
     #[test]
@@ -1,65 +1,194 @@
+use crate::algorithm::search::branch_cost::{BranchCost, ScalarBranchCost};
 use crate::algorithm::search::direction::Direction;
 use crate::algorithm::search::edge_traversal::EdgeTraversal;
 use crate::algorithm::search::search_error::SearchError;
 use crate::algorithm::search::search_instance::SearchInstance;
+use crate::algorithm::search::search_metadata::SearchMetadata;
 use crate::algorithm::search::search_result::SearchResult;
 use crate::algorithm::search::search_tree_branch::SearchTreeBranch;
 use crate::model::road_network::edge_id::EdgeId;
 use crate::model::road_network::vertex_id::VertexId;
 use crate::model::unit::as_f64::AsF64;
-use crate::model::unit::cost::ReverseCost;
 use crate::model::unit::Cost;
 use crate::util::priority_queue::InternalPriorityQueue;
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+/// number of iterations between checks of the search instance's cancellation flag
+const CANCELLATION_CHECK_INTERVAL: u64 = 256;
+
 /// run an A* Search over the given directed graph model. traverses links
 /// from the source, via the provided direction, to the target. uses the
 /// provided traversal model for state updates and link costs. estimates
 /// the distance to the destination (the a* heuristic) using the provided
-/// cost estimate function.
+/// cost estimate function. any edges in the optional avoid set are treated
+/// as impassable for this search only. if the search instance sets a
+/// `max_edges` limit, labels whose branch would exceed that many edges are
+/// pruned and counted in the search metadata, and likewise for labels whose
+/// cost would exceed a `max_cost` limit. if the search instance has a
+/// cancellation flag, it is checked periodically and a set flag ends the
+/// search with a [`SearchError::QueryCancelled`] error, discarding the
+/// partial search tree.
+///
+/// a weight factor above one inflates the heuristic (weighted A*), which
+/// settles fewer vertices but may return a route costing up to that factor
+/// times the optimal cost. the factor is reported as the suboptimality bound
+/// of the search metadata.
+///
+/// ties are broken deterministically so that the same query always yields the
+/// same route. when two frontier vertices have equal cost, the vertex with
+/// the lower VertexId is expanded first. when two edges reach an unexpanded
+/// vertex at equal cost, the edge with the lower EdgeId is kept in the tree,
+/// unless the search instance sets `prefer_fewer_edges`, in which case the
+/// edge completing the branch with fewer edges is kept first. this picks the
+/// simpler of two equal-cost routes and never changes the cost of the route.
 pub fn run_a_star(
     source: VertexId,
     target: Option<VertexId>,
     direction: &Direction,
     weight_factor: Option<Cost>,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    run_a_star_from_arrival(
+        source,
+        target,
+        direction,
+        weight_factor,
+        avoid_edges,
+        None,
+        si,
+    )
+}
+
+/// runs an A* search as [`run_a_star`] does, but continues a trip that
+/// arrived at the source by the provided edge traversal. the search begins
+/// with the result state of that traversal instead of the initial state of
+/// the state model, and treats its edge as the previous edge of the first
+/// expansion, so turn restrictions, turn costs and u-turn checks apply at
+/// the source. used to continue a trip from where an earlier search ended,
+/// such as the legs of a route through waypoints.
+///
+/// # Arguments
+///
+/// * `source` - vertex where the search begins
+/// * `target` - optional vertex where the search ends
+/// * `direction` - direction of the search
+/// * `weight_factor` - optional inflation of the heuristic
+/// * `avoid_edges` - optional edges to treat as impassable
+/// * `arrival` - traversal of the edge the trip arrived at the source by, if any
+/// * `si` - the search instance
+///
+/// # Returns
+///
+/// the search tree rooted at the source
+pub fn run_a_star_from_arrival(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    weight_factor: Option<Cost>,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    arrival: Option<&EdgeTraversal>,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    let mut branch_cost = ScalarBranchCost {
+        weight_factor,
+        max_cost: si.max_cost,
+    };
+    let mut result = run_a_star_with_cost(
+        source,
+        target,
+        direction,
+        avoid_edges,
+        arrival,
+        &mut branch_cost,
+        si,
+    )?;
+    result.metadata.suboptimality_bound = weight_factor
+        .filter(|w| target.is_some() && *w > Cost::ONE)
+        .map(|w| w.as_f64());
+    Ok(result)
+}
+
+/// runs the search loop of [`run_a_star_from_arrival`], comparing branches with
+/// the provided [`BranchCost`] instead of their scalar cost. the search keeps
+/// the lowest-cost branch into each vertex and expands the frontier vertex
+/// with the highest priority first, with the same deterministic tie-breaking,
+/// `max_edges` pruning and cancellation as [`run_a_star`]. branches outside
+/// the bound of the branch cost are dropped, so a bounded search with a
+/// target ends with [`SearchError::NoPathExists`] once no branch within the
+/// bound remains, and the search ends early when the branch cost asks to
+/// stop as a vertex is settled.
+///
+/// # Arguments
+///
+/// * `source` - vertex where the search begins
+/// * `target` - optional vertex where the search ends
+/// * `direction` - direction of the search
+/// * `avoid_edges` - optional edges to treat as impassable
+/// * `arrival` - traversal of the edge the trip arrived at the source by, if any
+/// * `branch_cost` - costs and prioritizes the branches of the search
+/// * `si` - the search instance
+///
+/// # Returns
+///
+/// the search tree rooted at the source
+pub fn run_a_star_with_cost<B: BranchCost>(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    arrival: Option<&EdgeTraversal>,
+    branch_cost: &mut B,
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
-    if target.map_or(false, |t| t == source) {
+    if target.is_some_and(|t| t == source) {
         return Ok(SearchResult::default());
     }
 
     // context for the search (graph, search functions, frontier priority queue)
-    let mut costs: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
-    let mut traversal_costs: HashMap<VertexId, Cost> = HashMap::new();
+    let mut costs: InternalPriorityQueue<VertexId, B::Priority> = InternalPriorityQueue::default();
+    let mut traversal_costs: HashMap<VertexId, B::Cost> = HashMap::new();
     let mut solution: HashMap<VertexId, SearchTreeBranch> = HashMap::new();
+    let mut edge_counts: HashMap<VertexId, usize> = HashMap::new();
 
     // setup initial search state
-    traversal_costs.insert(source, Cost::ZERO);
-    let initial_state = si.state_model.initial_state()?;
-    let origin_cost = match target {
-        None => Cost::ZERO,
-        Some(target) => {
-            let cost_est = si.estimate_traversal_cost(source, target, &initial_state)?;
-            Cost::new(cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64())
-        }
+    let origin_cost = branch_cost.origin_cost();
+    edge_counts.insert(source, 0);
+    let initial_state = match arrival {
+        Some(et) => et.result_state.clone(),
+        None => si.state_model.initial_state()?,
     };
-    costs.push(source, origin_cost.into());
+    let origin_priority = branch_cost.priority(&origin_cost, source, target, &initial_state, si)?;
+    traversal_costs.insert(source, origin_cost);
+    costs.push(source, origin_priority);
 
     let start_time = Instant::now();
     let mut iterations = 0;
+    let mut metadata = SearchMetadata {
+        peak_open_set_size: costs.len(),
+        ..Default::default()
+    };
 
     loop {
         si.termination_model
             .test(&start_time, solution.len(), iterations)?;
+        if iterations % CANCELLATION_CHECK_INTERVAL == 0 && si.is_cancelled() {
+            log::debug!("search cancelled after {} iterations", iterations);
+            return Err(SearchError::QueryCancelled(iterations));
+        }
 
-        let current_vertex_id = match advance_search(&mut costs, source, target)? {
-            None => break,
-            Some(id) => id,
-        };
+        let current_vertex_id =
+            match advance_search(&mut costs, source, target, &metadata, &start_time)? {
+                None => break,
+                Some(id) => id,
+            };
+        metadata.vertices_settled += 1;
 
-        let last_edge_id = get_last_traversed_edge_id(&current_vertex_id, &source, &solution)?;
+        let last_edge_id = get_last_traversed_edge_id(&current_vertex_id, &source, &solution)?
+            .or(arrival.map(|et| et.edge_id));
         let last_edge = match last_edge_id {
             Some(id) => Some(si.directed_graph.get_edge(id)?),
             None => None,
@@ -82,33 +211,87 @@ pub fn run_a_star(
                 .clone()
         };
 
+        let current_gscore = traversal_costs
+            .get(&current_vertex_id)
+            .ok_or_else(|| {
+                SearchError::InternalSearchError(format!(
+                    "expected vertex id {} missing from search costs",
+                    current_vertex_id
+                ))
+            })?
+            .clone();
+        if branch_cost.on_settled(current_vertex_id, &current_gscore) {
+            break;
+        }
+        let next_edge_count = edge_counts.get(&current_vertex_id).unwrap_or(&0) + 1;
+
         // visit all neighbors of this source vertex
         let incident_edge_iterator = direction.get_incident_edges(&current_vertex_id, si)?;
         for edge_id in incident_edge_iterator {
+            if avoid_edges.is_some_and(|avoid| avoid.contains(edge_id)) {
+                continue;
+            }
             let e = si.directed_graph.get_edge(*edge_id)?;
 
             let terminal_vertex_id = direction.terminal_vertex_id(e);
             let key_vertex_id = direction.tree_key_vertex_id(e);
 
-            let valid_frontier =
-                si.frontier_model
-                    .valid_frontier(e, &current_state, last_edge, &si.state_model)?;
+            let valid_frontier = si.frontier_model.valid_frontier_in_direction(
+                e,
+                &current_state,
+                last_edge,
+                direction,
+                &si.state_model,
+            )?;
             if !valid_frontier {
+                metadata.frontier_rejections += 1;
                 continue;
             }
             let et =
                 direction.perform_edge_traversal(*edge_id, last_edge_id, &current_state, si)?;
-            let current_gscore = traversal_costs
-                .get(&terminal_vertex_id)
-                .unwrap_or(&Cost::INFINITY)
-                .to_owned();
-            let tentative_gscore = current_gscore + et.total_cost();
-            let existing_gscore = traversal_costs
-                .get(&key_vertex_id)
-                .unwrap_or(&Cost::INFINITY)
-                .to_owned();
-            if tentative_gscore < existing_gscore {
+            metadata.edges_relaxed += 1;
+            let valid_traversal =
+                si.frontier_model
+                    .valid_traversal(e, &et.result_state, &si.state_model)?;
+            if !valid_traversal {
+                metadata.frontier_rejections += 1;
+                continue;
+            }
+            let tentative_gscore = branch_cost.extend(&current_gscore, &et, si)?;
+            if !branch_cost.within_bound(&tentative_gscore) {
+                metadata.labels_pruned_by_max_cost += 1;
+                continue;
+            }
+            let existing_gscore = traversal_costs.get(&key_vertex_id);
+            // on a cost tie, prefer fewer edges when requested, then the lower
+            // incoming EdgeId, but only for vertices still in the frontier, as
+            // the tree below an expanded vertex was built from the state of its
+            // current branch
+            let wins_tie = existing_gscore.is_some_and(|existing| tentative_gscore == *existing)
+                && costs.get(&key_vertex_id).is_some()
+                && solution.get(&key_vertex_id).is_some_and(|branch| {
+                    let existing_edge_count = edge_counts.get(&key_vertex_id).unwrap_or(&0);
+                    match next_edge_count.cmp(existing_edge_count) {
+                        Ordering::Less if si.prefer_fewer_edges => true,
+                        Ordering::Greater if si.prefer_fewer_edges => false,
+                        _ => *edge_id < branch.edge_traversal.edge_id,
+                    }
+                });
+            let improves = existing_gscore.is_none_or(|existing| tentative_gscore < *existing);
+            if improves || wins_tie {
+                if si.max_edges.is_some_and(|max| next_edge_count > max) {
+                    metadata.labels_pruned_by_max_edges += 1;
+                    continue;
+                }
+                let priority = branch_cost.priority(
+                    &tentative_gscore,
+                    key_vertex_id,
+                    target,
+                    &current_state,
+                    si,
+                )?;
                 traversal_costs.insert(key_vertex_id, tentative_gscore);
+                edge_counts.insert(key_vertex_id, next_edge_count);
 
                 // update solution
                 let traversal = SearchTreeBranch {
@@ -116,25 +299,18 @@ pub fn run_a_star(
                     edge_traversal: et,
                 };
                 solution.insert(key_vertex_id, traversal);
-
-                let dst_h_cost = match target {
-                    None => Cost::ZERO,
-                    Some(target_v) => {
-                        let cost_est =
-                            si.estimate_traversal_cost(key_vertex_id, target_v, &current_state)?;
-                        Cost::new(cost_est.as_f64() * weight_factor.unwrap_or(Cost::ONE).as_f64())
-                    }
-                };
-                let f_score_value = tentative_gscore + dst_h_cost;
-                costs.push_increase(key_vertex_id, f_score_value.into());
+                costs.push_increase(key_vertex_id, priority);
+                metadata.peak_open_set_size = metadata.peak_open_set_size.max(costs.len());
             }
         }
         iterations += 1;
     }
+    metadata.runtime = start_time.elapsed();
     log::debug!(
-        "search iterations: {}, size of search tree: {}",
+        "search iterations: {}, size of search tree: {}, search metadata: {:?}",
         iterations,
-        solution.len()
+        solution.len(),
+        metadata
     );
 
     #[cfg(debug_assertions)]
@@ -147,6 +323,7 @@ pub fn run_a_star(
         flamegraph.visit_root(&costs);
         flamegraph.visit_root(&traversal_costs);
         flamegraph.visit_root(&solution);
+        flamegraph.visit_root(&edge_counts);
         let output = flamegraph.finish_and_write_flame_graph();
 
         let search_name = match target {
@@ -170,14 +347,16 @@ pub fn run_a_star(
         flamegraph_file.write_all(output.as_bytes()).unwrap();
     }
 
-    let result = SearchResult::new(solution, iterations);
+    let result = SearchResult::new(solution, iterations, metadata);
     Ok(result)
 }
 
 /// convenience method when origin and destination are specified using
 /// edge ids instead of vertex ids. invokes a vertex-oriented search
 /// from the out-vertex of the source edge to the in-vertex of the
-/// target edge. composes the result with the source and target.
+/// target edge. composes the result with the source and target. any
+/// `max_edges` limit applies to the vertex-oriented search and does not
+/// count the source and target edges.
 ///
 /// not tested.
 pub fn run_a_star_edge_oriented(
@@ -185,6 +364,7 @@ pub fn run_a_star_edge_oriented(
     target: Option<EdgeId>,
     direction: &Direction,
     weight_factor: Option<Cost>,
+    avoid_edges: Option<&HashSet<EdgeId>>,
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
     // 1. guard against edge conditions (src==dst, src.dst_v == dst.src_v)
@@ -206,13 +386,15 @@ pub fn run_a_star_edge_oriented(
             let SearchResult {
                 mut tree,
                 iterations,
-            } = run_a_star(e1_dst, None, direction, weight_factor, si)?;
+                metadata,
+            } = run_a_star(e1_dst, None, direction, weight_factor, avoid_edges, si)?;
             if !tree.contains_key(&e1_dst) {
                 tree.extend([(e1_dst, src_branch)]);
             }
             let updated = SearchResult {
                 tree,
                 iterations: iterations + 1,
+                metadata,
             };
             Ok(updated)
         }
@@ -244,6 +426,7 @@ pub fn run_a_star_edge_oriented(
                 let result = SearchResult {
                     tree,
                     iterations: 1,
+                    metadata: SearchMetadata::default(),
                 };
                 return Ok(result);
             } else {
@@ -251,10 +434,18 @@ pub fn run_a_star_edge_oriented(
                 let SearchResult {
                     mut tree,
                     iterations,
-                } = run_a_star(e1_dst, Some(e2_src), direction, weight_factor, si)?;
+                    metadata,
+                } = run_a_star(
+                    e1_dst,
+                    Some(e2_src),
+                    direction,
+                    weight_factor,
+                    avoid_edges,
+                    si,
+                )?;
 
                 if tree.is_empty() {
-                    return Err(SearchError::NoPathExists(e1_dst, e2_src));
+                    return Err(SearchError::NoPathExists(e1_dst, e2_src, metadata));
                 }
 
                 let final_state = &tree
@@ -285,6 +476,7 @@ pub fn run_a_star_edge_oriented(
                 let result = SearchResult {
                     tree,
                     iterations: iterations + 2,
+                    metadata,
                 };
                 Ok(result)
             }
@@ -302,18 +494,32 @@ pub fn run_a_star_edge_oriented(
 /// * `cost`   - queue of priority-ranked vertices for exploration
 /// * `source` - search source vertex
 /// * `target` - optional search destination
+/// * `metadata` - metadata of the search so far, reported when no path exists
+/// * `start_time` - when the search began
 ///
 /// # Results
 /// The next vertex to search. None if the queue has been exhausted in a search with no
 /// destination, or we have reached our destination.
 /// An error if no path exists for a search that includes a destination.
-fn advance_search(
-    cost: &mut InternalPriorityQueue<VertexId, ReverseCost>,
+fn advance_search<P: Ord>(
+    cost: &mut InternalPriorityQueue<VertexId, P>,
     source: VertexId,
     target: Option<VertexId>,
+    metadata: &SearchMetadata,
+    start_time: &Instant,
 ) -> Result<Option<VertexId>, SearchError> {
     match (cost.pop(), target) {
-        (None, Some(target_vertex_id)) => Err(SearchError::NoPathExists(source, target_vertex_id)),
+        (None, Some(target_vertex_id)) => {
+            let metadata = SearchMetadata {
+                runtime: start_time.elapsed(),
+                ..*metadata
+            };
+            Err(SearchError::NoPathExists(
+                source,
+                target_vertex_id,
+                metadata,
+            ))
+        }
         (None, None) => Ok(None),
         (Some((current_v, _)), Some(target_v)) if current_v == target_v => Ok(None),
         (Some((current_vertex_id, _)), _) => Ok(Some(current_vertex_id)),
@@ -362,12 +568,13 @@ fn get_last_traversed_edge_id(
 mod tests {
     use super::*;
     use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::cost_estimate_function::{
+        CostEstimateFunction, TraversalModelCostEstimate, ZeroCostEstimate,
+    };
+    use crate::algorithm::search::search_algorithm::SearchAlgorithm;
     use crate::algorithm::search::MinSearchTree;
-    use crate::model::access::default::no_access_model::NoAccessModel;
-    use crate::model::cost::cost_aggregation::CostAggregation;
-    use crate::model::cost::cost_model::CostModel;
-    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
-    use crate::model::frontier::default::no_restriction::NoRestriction;
+    use crate::model::frontier::frontier_model::FrontierModel;
+    use crate::model::frontier::frontier_model_error::FrontierModelError;
     use crate::model::property::edge::Edge;
     use crate::model::property::vertex::Vertex;
     use crate::model::road_network::edge_id::EdgeId;
@@ -375,45 +582,79 @@ mod tests {
     use crate::model::state::state_feature::StateFeature;
     use crate::model::state::state_model::StateModel;
     use crate::model::termination::termination_model::TerminationModel;
+    use crate::model::traversal::cached_traversal_model::TraversalCache;
     use crate::model::traversal::default::distance_traversal_model::DistanceTraversalModel;
+    use crate::model::traversal::state::state_variable::StateVar;
+    use crate::model::traversal::traversal_model::TraversalModel;
     use crate::model::unit::{Distance, DistanceUnit};
-    use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+    use crate::test_util::graph_fixture::{graph_from_edges, graph_from_links, two_way_links};
+    use crate::test_util::search_instance_builder::{distance_cost_model, SearchInstanceBuilder};
+    use crate::util::geo::haversine::haversine_distance_meters;
     use rayon::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
 
     fn build_mock_graph() -> Graph {
-        let vertices = vec![
-            Vertex::new(0, 0.0, 0.0),
-            Vertex::new(1, 0.0, 0.0),
-            Vertex::new(2, 0.0, 0.0),
-            Vertex::new(3, 0.0, 0.0),
-        ];
-
-        let edges = vec![
-            Edge::new(0, 0, 1, 10.0),
-            Edge::new(1, 1, 0, 10.0),
-            Edge::new(2, 1, 2, 2.0),
-            Edge::new(3, 2, 1, 2.0),
-            Edge::new(4, 2, 3, 1.0),
-            Edge::new(5, 3, 2, 1.0),
-            Edge::new(6, 3, 0, 2.0),
-            Edge::new(7, 0, 3, 2.0),
-        ];
-
-        let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
-        let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+        graph_from_links(
+            4,
+            &[
+                (0, 1, 10.0),
+                (1, 0, 10.0),
+                (1, 2, 2.0),
+                (2, 1, 2.0),
+                (2, 3, 1.0),
+                (3, 2, 1.0),
+                (3, 0, 2.0),
+                (0, 3, 2.0),
+            ],
+        )
+    }
 
-        for edge in &edges {
-            adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
-            rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
-        }
+    /// a distance search whose cost is the distance in kilometers
+    fn build_mock_search_instance(graph: Graph) -> SearchInstance {
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(vec![(
+                    String::from("distance"),
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Kilometers,
+                        initial: Distance::new(0.0),
+                    },
+                )])
+                .unwrap(),
+        );
+        SearchInstanceBuilder::new(graph)
+            .state_model(state_model.clone())
+            .cost_model(distance_cost_model(state_model))
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build()
+    }
 
-        Graph {
-            adj: adj.into_boxed_slice(),
-            rev: rev.into_boxed_slice(),
-            edges: edges.into_boxed_slice(),
-            vertices: vertices.into_boxed_slice(),
-        }
+    /// 3x3 grid where vertex `r * 3 + c` is at row `r` and column `c`. every
+    /// pair of neighbors is connected in both directions. the perimeter path
+    /// from (0) through the bottom row to (2) costs 1 per edge, while the top
+    /// row and every edge touching the center vertex (4) costs 10 per edge.
+    /// (0) -10- (1) -10- (2)
+    ///  1        10       1
+    /// (3) -10- (4) -10- (5)
+    ///  1        10       1
+    /// (6) -1-- (7) -1-- (8)
+    fn build_grid_graph() -> Graph {
+        let links = [
+            (0, 1, 10.0),
+            (1, 2, 10.0),
+            (3, 4, 10.0),
+            (4, 5, 10.0),
+            (6, 7, 1.0),
+            (7, 8, 1.0),
+            (0, 3, 1.0),
+            (3, 6, 1.0),
+            (1, 4, 10.0),
+            (4, 7, 10.0),
+            (2, 5, 1.0),
+            (5, 8, 1.0),
+        ];
+        graph_from_links(9, &two_way_links(&links))
     }
 
     #[test]
@@ -465,45 +706,14 @@ mod tests {
 
         // setup the graph, traversal model, and a* heuristic to be shared across the queries in parallel
         // these live in the "driver" process and are passed as read-only memory to each executor process
-        let state_model = Arc::new(
-            StateModel::empty()
-                .extend(vec![(
-                    String::from("distance"),
-                    StateFeature::Distance {
-                        distance_unit: DistanceUnit::Kilometers,
-                        initial: Distance::new(0.0),
-                    },
-                )])
-                .unwrap(),
-        );
-        let cost_model = CostModel::new(
-            // vec![(String::from("distance"), 0usize)],
-            Arc::new(HashMap::from([(String::from("distance"), 1.0)])),
-            Arc::new(HashMap::from([(
-                String::from("distance"),
-                VehicleCostRate::Raw,
-            )])),
-            Arc::new(HashMap::new()),
-            CostAggregation::Sum,
-            state_model.clone(),
-        )
-        .unwrap();
-        let si = SearchInstance {
-            directed_graph: Arc::new(build_mock_graph()),
-            state_model: state_model.clone(),
-            traversal_model: Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
-            access_model: Arc::new(NoAccessModel {}),
-            cost_model,
-            frontier_model: Arc::new(NoRestriction {}),
-            termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 20 }),
-        };
+        let si = build_mock_search_instance(build_mock_graph());
 
         // execute the route search
         let result: Vec<Result<MinSearchTree, SearchError>> = queries
             .clone()
             .into_par_iter()
             .map(|(o, d, _expected)| {
-                run_a_star(o, Some(d), &Direction::Forward, None, &si)
+                run_a_star(o, Some(d), &Direction::Forward, None, None, &si)
                     .map(|search_result| search_result.tree)
             })
             .collect();
@@ -520,4 +730,696 @@ mod tests {
             );
         }
     }
+
+    /// builds a graph with a short and a long path between vertices 1 and 3:
+    /// (0) -[0]-> (1) -[1]-> (2) -[2]-> (3) -[5]-> (5)
+    ///             |                     ^
+    ///             +--[3]--> (4) --[4]---+
+    fn build_detour_graph() -> Graph {
+        graph_from_links(
+            6,
+            &[
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (2, 3, 1.0),
+                (1, 4, 5.0),
+                (4, 3, 5.0),
+                (3, 5, 1.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_avoid_edges_forces_detour() {
+        let si = build_mock_search_instance(build_detour_graph());
+        let alg = SearchAlgorithm::Dijkstra;
+
+        let result = alg
+            .run_edge_oriented(EdgeId(0), Some(EdgeId(5)), &Direction::Forward, None, &si)
+            .unwrap();
+        let route: Vec<EdgeId> = result.routes[0].iter().map(|et| et.edge_id).collect();
+        assert_eq!(route, vec![EdgeId(0), EdgeId(1), EdgeId(2), EdgeId(5)]);
+
+        // closing edge 2 forces the search onto the longer path through vertex 4
+        let avoid = HashSet::from([EdgeId(2)]);
+        let result = alg
+            .run_edge_oriented(
+                EdgeId(0),
+                Some(EdgeId(5)),
+                &Direction::Forward,
+                Some(&avoid),
+                &si,
+            )
+            .unwrap();
+        let route: Vec<EdgeId> = result.routes[0].iter().map(|et| et.edge_id).collect();
+        assert_eq!(route, vec![EdgeId(0), EdgeId(3), EdgeId(4), EdgeId(5)]);
+        assert!(route.iter().all(|edge_id| !avoid.contains(edge_id)));
+    }
+
+    #[test]
+    fn test_search_metadata_without_route() {
+        // a search with no destination produces a tree but no route. every
+        // vertex in the box world is settled and each has two out-edges.
+        let si = build_mock_search_instance(build_mock_graph());
+        let result = run_a_star(VertexId(0), None, &Direction::Forward, None, None, &si).unwrap();
+        assert_eq!(result.metadata.vertices_settled, 4);
+        assert_eq!(result.metadata.edges_relaxed, 8);
+        assert!(result.metadata.peak_open_set_size >= 1);
+        assert!(result.metadata.peak_open_set_size <= 4);
+    }
+
+    #[test]
+    fn test_search_metadata_without_path() {
+        // closing edges 2 and 5 leaves no way into vertex 2 of the box world
+        let si = build_mock_search_instance(build_mock_graph());
+        let avoid = HashSet::from([EdgeId(2), EdgeId(5)]);
+        let result = run_a_star(
+            VertexId(0),
+            Some(VertexId(2)),
+            &Direction::Forward,
+            None,
+            Some(&avoid),
+            &si,
+        );
+        match result {
+            Err(SearchError::NoPathExists(VertexId(0), VertexId(2), metadata)) => {
+                assert!(metadata.vertices_settled > 0);
+                assert!(metadata.edges_relaxed > 0);
+            }
+            other => panic!("expected no path with metadata, found {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_max_edges_limits_route_length() {
+        let mut si = build_mock_search_instance(build_grid_graph());
+        let route_vertices = |si: &SearchInstance| {
+            let result = run_a_star(
+                VertexId(0),
+                Some(VertexId(2)),
+                &Direction::Forward,
+                None,
+                None,
+                si,
+            )
+            .unwrap();
+            let route = vertex_oriented_route(VertexId(0), VertexId(2), &result.tree).unwrap();
+            let vertices = route
+                .iter()
+                .map(|et| si.directed_graph.dst_vertex_id(et.edge_id).unwrap().0)
+                .collect::<Vec<_>>();
+            (vertices, result.metadata)
+        };
+
+        // without a limit, the cheap perimeter route uses 6 edges
+        let (unlimited, metadata) = route_vertices(&si);
+        assert_eq!(unlimited, vec![3, 6, 7, 8, 5, 2]);
+        assert!(!metadata.max_edges_binding());
+
+        // a limit of 6 edges does not change the result
+        si.max_edges = Some(6);
+        let (loose, metadata) = route_vertices(&si);
+        assert_eq!(loose, unlimited);
+        assert!(!metadata.max_edges_binding());
+
+        // a limit of 4 edges forces the more expensive route along the top row
+        si.max_edges = Some(4);
+        let (capped, metadata) = route_vertices(&si);
+        assert_eq!(capped, vec![1, 2]);
+        assert!(capped.len() <= 4);
+        assert!(metadata.max_edges_binding());
+    }
+
+    #[test]
+    fn test_max_cost_bounds_search() {
+        let mut si = build_mock_search_instance(build_grid_graph());
+        let run = |si: &SearchInstance| {
+            run_a_star(
+                VertexId(0),
+                Some(VertexId(2)),
+                &Direction::Forward,
+                None,
+                None,
+                si,
+            )
+        };
+        let unbounded = run(&si).unwrap();
+        let route = vertex_oriented_route(VertexId(0), VertexId(2), &unbounded.tree).unwrap();
+        let route_cost: Cost = route.iter().map(|et| et.total_cost()).sum();
+
+        // a max cost equal to the route cost still finds it
+        si.max_cost = Some(route_cost);
+        let bounded = run(&si).unwrap();
+        assert!(bounded.tree.contains_key(&VertexId(2)));
+
+        // a lower max cost stops the search before the destination
+        si.max_cost = Some(Cost::new(route_cost.as_f64() * 0.9));
+        match run(&si) {
+            Err(SearchError::NoPathExists(_, _, metadata)) => {
+                assert!(metadata.max_cost_binding())
+            }
+            other => panic!(
+                "expected no path within the max cost, found {:?}",
+                other.err()
+            ),
+        }
+    }
+
+    #[test]
+    fn test_successors() {
+        // vertex 0 in the box world has out-edges 0 (to 1, 10 units) and 7 (to 3, 2 units).
+        // edge distances are in meters while the distance cost is in kilometers.
+        let si = build_mock_search_instance(build_mock_graph());
+        let state = si.state_model.initial_state().unwrap();
+        let mut successors = si
+            .successors(VertexId(0), None, &state, &Direction::Forward)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        successors.sort_by_key(|(edge_id, _, _)| edge_id.0);
+        let summary = successors
+            .iter()
+            .map(|(e, v, et)| (*e, *v, et.total_cost()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (EdgeId(0), VertexId(1), Cost::new(0.01)),
+                (EdgeId(7), VertexId(3), Cost::new(0.002)),
+            ]
+        );
+
+        // in the reverse direction, vertex 0 is reached from vertices 1 and 3
+        let mut predecessors = si
+            .successors(VertexId(0), None, &state, &Direction::Reverse)
+            .unwrap()
+            .map(|r| r.map(|(e, v, _)| (e, v)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        predecessors.sort_by_key(|(edge_id, _)| edge_id.0);
+        assert_eq!(
+            predecessors,
+            vec![(EdgeId(1), VertexId(1)), (EdgeId(6), VertexId(3))]
+        );
+    }
+
+    #[test]
+    fn test_equal_cost_routes_are_deterministic() {
+        // a 3x3 lattice of unit-length edges has six shortest paths from one
+        // corner to the opposite corner, all with a cost of 4
+        //  0 - 1 - 2
+        //  |   |   |
+        //  3 - 4 - 5
+        //  |   |   |
+        //  6 - 7 - 8
+        let build_lattice = || {
+            let links = [
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (6, 7, 1.0),
+                (7, 8, 1.0),
+                (0, 3, 1.0),
+                (3, 6, 1.0),
+                (1, 4, 1.0),
+                (4, 7, 1.0),
+                (2, 5, 1.0),
+                (5, 8, 1.0),
+            ];
+            graph_from_links(9, &two_way_links(&links))
+        };
+
+        let routes = (0..20)
+            .map(|_| {
+                let si = build_mock_search_instance(build_lattice());
+                let result = run_a_star(
+                    VertexId(0),
+                    Some(VertexId(8)),
+                    &Direction::Forward,
+                    None,
+                    None,
+                    &si,
+                )
+                .unwrap();
+                let route = vertex_oriented_route(VertexId(0), VertexId(8), &result.tree).unwrap();
+                route.iter().map(|et| et.edge_id).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert!(routes.iter().all(|route| *route == routes[0]));
+        // each vertex keeps its lowest EdgeId approach, which runs down the left
+        // column and along the bottom row
+        assert_eq!(
+            routes[0],
+            vec![EdgeId(12), EdgeId(14), EdgeId(8), EdgeId(10)]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_search_returns_promptly() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let si = SearchInstanceBuilder::new(build_mock_graph())
+            .cancellation(cancelled.clone())
+            .build();
+
+        // an unset flag has no effect on the search
+        let result = run_a_star(VertexId(0), None, &Direction::Forward, None, None, &si);
+        assert!(result.is_ok());
+
+        cancelled.store(true, Ordering::Relaxed);
+        let result = run_a_star(VertexId(0), None, &Direction::Forward, None, None, &si);
+        assert!(matches!(result, Err(SearchError::QueryCancelled(0))));
+    }
+
+    /// an n by n grid of vertices spaced 0.001 degrees apart, with every pair
+    /// of neighbors connected in both directions by an edge whose distance
+    /// is the haversine distance between them, so the straight-line
+    /// heuristic is admissible.
+    fn build_medium_grid(n: usize) -> Graph {
+        let vertices = (0..n * n)
+            .map(|i| Vertex::new(i, (i % n) as f32 * 0.001, (i / n) as f32 * 0.001))
+            .collect::<Vec<_>>();
+        let mut edges = vec![];
+        for r in 0..n {
+            for c in 0..n {
+                let v = r * n + c;
+                let mut neighbors = vec![];
+                if c + 1 < n {
+                    neighbors.push(v + 1);
+                }
+                if r + 1 < n {
+                    neighbors.push(v + n);
+                }
+                for u in neighbors {
+                    let (a, b) = (&vertices[v], &vertices[u]);
+                    let distance = haversine_distance_meters(a.x(), a.y(), b.x(), b.y())
+                        .unwrap()
+                        .as_f64();
+                    edges.push(Edge::new(edges.len(), v, u, distance));
+                    edges.push(Edge::new(edges.len(), u, v, distance));
+                }
+            }
+        }
+        graph_from_edges(vertices, edges)
+    }
+
+    #[test]
+    fn test_weighted_heuristic_settles_fewer_vertices() {
+        let n = 30;
+        let si = SearchInstanceBuilder::new(build_medium_grid(n))
+            .termination_model(TerminationModel::IterationsLimit { limit: 10_000 })
+            .build();
+        let source = VertexId(0);
+        let target = VertexId(n * n - 1);
+        let search = |weight: f64| {
+            let result = run_a_star(
+                source,
+                Some(target),
+                &Direction::Forward,
+                Some(Cost::new(weight)),
+                None,
+                &si,
+            )
+            .unwrap();
+            let route = vertex_oriented_route(source, target, &result.tree).unwrap();
+            let cost: Cost = route.iter().map(|et| et.total_cost()).sum();
+            (result.metadata, cost.as_f64())
+        };
+
+        let (exact, exact_cost) = search(1.0);
+        let (weighted, weighted_cost) = search(1.5);
+        assert!(!exact.is_approximate());
+        assert_eq!(weighted.suboptimality_bound, Some(1.5));
+        assert!(
+            weighted.vertices_settled < exact.vertices_settled,
+            "weighted search settled {} vertices, exact search settled {}",
+            weighted.vertices_settled,
+            exact.vertices_settled
+        );
+        assert!(weighted_cost >= exact_cost - 1e-6);
+        assert!(weighted_cost <= 1.5 * exact_cost);
+    }
+
+    #[test]
+    fn test_injected_cost_estimate_function() {
+        let n = 20;
+        let search = |cost_estimate: Arc<dyn CostEstimateFunction>| {
+            let si = SearchInstanceBuilder::new(build_medium_grid(n))
+                .termination_model(TerminationModel::IterationsLimit { limit: 10_000 })
+                .cost_estimate(cost_estimate)
+                .build();
+            let source = VertexId(0);
+            let target = VertexId(n * n - 1);
+            let result =
+                run_a_star(source, Some(target), &Direction::Forward, None, None, &si).unwrap();
+            let route = vertex_oriented_route(source, target, &result.tree).unwrap();
+            let cost: Cost = route.iter().map(|et| et.total_cost()).sum();
+            (result.metadata.vertices_settled, cost.as_f64())
+        };
+
+        // without an estimate, A* explores like Dijkstra and finds the same
+        // cost as the default straight-line estimate, but settles more vertices
+        let (default_settled, default_cost) = search(Arc::new(TraversalModelCostEstimate));
+        let (zero_settled, zero_cost) = search(Arc::new(ZeroCostEstimate));
+        assert!((default_cost - zero_cost).abs() < 1e-6);
+        assert!(
+            default_settled < zero_settled,
+            "default estimate settled {} vertices, zero estimate settled {}",
+            default_settled,
+            zero_settled
+        );
+    }
+
+    #[test]
+    fn test_route_through_vias_sums_legs() {
+        let si = build_mock_search_instance(build_grid_graph());
+        let alg = SearchAlgorithm::Dijkstra;
+        let leg_cost = |src: usize, dst: usize| -> f64 {
+            let result = alg
+                .run_vertex_oriented(
+                    VertexId(src),
+                    Some(VertexId(dst)),
+                    &Direction::Forward,
+                    None,
+                    &si,
+                )
+                .unwrap();
+            result.routes[0]
+                .iter()
+                .map(|et| et.total_cost())
+                .sum::<Cost>()
+                .as_f64()
+        };
+
+        // corner to corner through the other two corners
+        let result = alg
+            .run_via(
+                VertexId(0),
+                &[VertexId(6), VertexId(2)],
+                VertexId(8),
+                None,
+                &si,
+            )
+            .unwrap();
+        assert_eq!(result.trees.len(), 3);
+        let route = &result.routes[0];
+        let total_cost: Cost = route.iter().map(|et| et.total_cost()).sum();
+        let expected = leg_cost(0, 6) + leg_cost(6, 2) + leg_cost(2, 8);
+        assert!((total_cost.as_f64() - expected).abs() < 1e-12);
+        assert!(expected > leg_cost(0, 8));
+
+        // consecutive edges connect, and the distance keeps growing across legs
+        for pair in route.windows(2) {
+            let prev = si.directed_graph.get_edge(pair[0].edge_id).unwrap();
+            let next = si.directed_graph.get_edge(pair[1].edge_id).unwrap();
+            assert_eq!(prev.dst_vertex_id, next.src_vertex_id);
+        }
+        let final_state = &route.last().unwrap().result_state;
+        let distance = si
+            .state_model
+            .get_distance(final_state, &"distance".into(), &DistanceUnit::Meters)
+            .unwrap();
+        assert!((distance.as_f64() - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unreachable_via_leg_names_the_leg() {
+        let si = build_mock_search_instance(build_grid_graph());
+        // close both edges into the destination so that the last leg fails
+        let into_destination = si
+            .directed_graph
+            .edges
+            .iter()
+            .filter(|e| e.dst_vertex_id == VertexId(8))
+            .map(|e| e.edge_id)
+            .collect::<HashSet<_>>();
+        let result = SearchAlgorithm::Dijkstra.run_via(
+            VertexId(0),
+            &[VertexId(6), VertexId(2)],
+            VertexId(8),
+            Some(&into_destination),
+            &si,
+        );
+        assert!(matches!(
+            result,
+            Err(SearchError::WaypointLegFailed(
+                2,
+                VertexId(2),
+                VertexId(8),
+                _
+            ))
+        ));
+    }
+
+    /// rejects any edge that reverses the previous edge
+    struct NoUTurnFrontierModel;
+
+    impl FrontierModel for NoUTurnFrontierModel {
+        fn valid_frontier(
+            &self,
+            edge: &Edge,
+            _state: &[StateVar],
+            previous_edge: Option<&Edge>,
+            _state_model: &StateModel,
+        ) -> Result<bool, FrontierModelError> {
+            Ok(!previous_edge.is_some_and(|prev| {
+                prev.src_vertex_id == edge.dst_vertex_id && prev.dst_vertex_id == edge.src_vertex_id
+            }))
+        }
+    }
+
+    #[test]
+    fn test_via_leg_continues_from_previous_edge() {
+        // a round trip from (0) through (1). turning back at (1) would be a
+        // u-turn, so the second leg must take the loop (1) -> (2) -> (0).
+        let graph = graph_from_links(4, &[(0, 1, 1.0), (1, 0, 1.0), (1, 2, 1.0), (2, 0, 1.0)]);
+        let si = SearchInstanceBuilder::new(graph)
+            .frontier_model(Arc::new(NoUTurnFrontierModel))
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        let result = SearchAlgorithm::Dijkstra
+            .run_via(VertexId(0), &[VertexId(1)], VertexId(0), None, &si)
+            .unwrap();
+        let route = result.routes[0]
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(0), EdgeId(2), EdgeId(3)]);
+    }
+
+    #[test]
+    fn test_edge_offsets_charge_partial_edges() {
+        // the line (0) -[0]-> (1) -[1]-> (2) -[2]-> (3) with lengths 1, 2 and 3
+        let graph = graph_from_links(4, &[(0, 1, 1.0), (1, 2, 2.0), (2, 3, 3.0)]);
+        let si = SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        let alg = SearchAlgorithm::Dijkstra;
+        let route_cost = |o: usize, o_offset: f64, d: usize, d_offset: f64| {
+            let result = alg
+                .run_between_edge_offsets(EdgeId(o), o_offset, EdgeId(d), d_offset, None, &si)
+                .unwrap();
+            let route = &result.routes[0];
+            let edge_ids = route.iter().map(|et| et.edge_id.0).collect::<Vec<_>>();
+            let cost = route.iter().map(|et| et.total_cost()).sum::<Cost>();
+            (edge_ids, cost.as_f64())
+        };
+
+        assert_eq!(route_cost(0, 0.0, 2, 1.0), (vec![0, 1, 2], 6.0));
+        // half of the origin edge and half of the destination edge are skipped
+        assert_eq!(route_cost(0, 0.5, 2, 0.5), (vec![0, 1, 2], 4.0));
+        // adjacent edges need no search
+        assert_eq!(route_cost(0, 0.5, 1, 0.5), (vec![0, 1], 1.5));
+        // a trip along a single edge charges the part between the offsets
+        assert_eq!(route_cost(1, 0.25, 1, 0.75), (vec![1], 1.0));
+
+        // returning to an earlier point on the same edge needs a loop, which
+        // this one-way line does not have
+        let backward = alg.run_between_edge_offsets(EdgeId(1), 0.75, EdgeId(1), 0.25, None, &si);
+        assert!(matches!(backward, Err(SearchError::NoPathExists(..))));
+        let out_of_range = alg.run_between_edge_offsets(EdgeId(0), 1.5, EdgeId(2), 1.0, None, &si);
+        assert!(matches!(out_of_range, Err(SearchError::BuildError(_))));
+    }
+
+    #[test]
+    fn test_edge_offsets_bypass_the_traversal_cache() {
+        let graph = graph_from_links(4, &[(0, 1, 1.0), (1, 2, 2.0), (2, 3, 3.0)]);
+        let cache = TraversalCache::default();
+        let distance = Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters));
+        let state_model = StateModel::empty()
+            .extend(distance.state_features())
+            .unwrap();
+        let si = SearchInstanceBuilder::new(graph)
+            .traversal_model(cache.wrap(distance, &state_model).unwrap())
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        let route_cost = |o_offset: f64, d_offset: f64| {
+            let result = SearchAlgorithm::Dijkstra
+                .run_between_edge_offsets(EdgeId(0), o_offset, EdgeId(2), d_offset, None, &si)
+                .unwrap();
+            result.routes[0]
+                .iter()
+                .map(|et| et.total_cost())
+                .sum::<Cost>()
+                .as_f64()
+        };
+
+        // a search between vertices fills the cache with every edge of the line
+        SearchAlgorithm::Dijkstra
+            .run_vertex_oriented(
+                VertexId(0),
+                Some(VertexId(3)),
+                &Direction::Forward,
+                None,
+                &si,
+            )
+            .unwrap();
+        assert_eq!(cache.len(), 3);
+        // partial edges are not served the full edge traversal, nor stored
+        assert_eq!(route_cost(0.5, 0.5), 4.0);
+        assert_eq!(route_cost(0.0, 1.0), 6.0);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_required_edge_is_checked_and_continued_from() {
+        let graph = graph_from_links(4, &[(0, 1, 1.0), (1, 0, 1.0), (1, 2, 1.0), (2, 0, 1.0)]);
+        let si = SearchInstanceBuilder::new(graph)
+            .frontier_model(Arc::new(NoUTurnFrontierModel))
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        // after the required edge (0) -> (1), the route may not turn back
+        let result = SearchAlgorithm::Dijkstra
+            .run_required_edges(VertexId(0), &[EdgeId(0)], VertexId(0), None, &si)
+            .unwrap();
+        let route = result.routes[0]
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(0), EdgeId(2), EdgeId(3)]);
+
+        // a required edge that u-turns on the previous required edge is rejected
+        let rejected = SearchAlgorithm::Dijkstra.run_required_edges(
+            VertexId(0),
+            &[EdgeId(0), EdgeId(1)],
+            VertexId(0),
+            None,
+            &si,
+        );
+        match rejected {
+            Err(SearchError::RequiredEdgeSegmentFailed(1, VertexId(1), VertexId(1), e)) => {
+                assert!(matches!(*e, SearchError::RequiredEdgeRejected(EdgeId(1))))
+            }
+            other => panic!("expected a rejected required edge, found {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_route_through_required_edge() {
+        let si = build_mock_search_instance(build_grid_graph());
+        // the center edge from vertex 4 to 5 is far from the fastest route along the edge of the grid
+        let required = si
+            .directed_graph
+            .edges
+            .iter()
+            .find(|e| e.src_vertex_id == VertexId(4) && e.dst_vertex_id == VertexId(5))
+            .map(|e| e.edge_id)
+            .unwrap();
+        let result = SearchAlgorithm::Dijkstra
+            .run_required_edges(VertexId(0), &[required], VertexId(8), None, &si)
+            .unwrap();
+        assert_eq!(result.trees.len(), 2);
+        let route = &result.routes[0];
+        assert!(route.iter().any(|et| et.edge_id == required));
+        for pair in route.windows(2) {
+            let prev = si.directed_graph.get_edge(pair[0].edge_id).unwrap();
+            let next = si.directed_graph.get_edge(pair[1].edge_id).unwrap();
+            assert_eq!(prev.dst_vertex_id, next.src_vertex_id);
+        }
+        let first = si.directed_graph.get_edge(route[0].edge_id).unwrap();
+        let last = si
+            .directed_graph
+            .get_edge(route.last().unwrap().edge_id)
+            .unwrap();
+        assert_eq!(first.src_vertex_id, VertexId(0));
+        assert_eq!(last.dst_vertex_id, VertexId(8));
+        // 0 -> 3 -> 4, across the required edge, then 5 -> 8
+        let distance = si
+            .state_model
+            .get_distance(
+                &route.last().unwrap().result_state,
+                &"distance".into(),
+                &DistanceUnit::Meters,
+            )
+            .unwrap();
+        assert!((distance.as_f64() - 22.0).abs() < 1e-9);
+
+        // when the start of the required edge cannot be reached, the first segment fails
+        let into_start = si
+            .directed_graph
+            .edges
+            .iter()
+            .filter(|e| e.dst_vertex_id == VertexId(4))
+            .map(|e| e.edge_id)
+            .collect::<HashSet<_>>();
+        let failed = SearchAlgorithm::Dijkstra.run_required_edges(
+            VertexId(0),
+            &[required],
+            VertexId(8),
+            Some(&into_start),
+            &si,
+        );
+        assert!(matches!(
+            failed,
+            Err(SearchError::RequiredEdgeSegmentFailed(
+                0,
+                VertexId(0),
+                VertexId(4),
+                _
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_prefer_fewer_edges_among_equal_cost_routes() {
+        // two routes from (0) to (3) that each cost 4. the route through (1)
+        // has two edges, the route through (2), (4) and (5) has four edges
+        // with lower EdgeIds, so it wins the default tie-break.
+        let graph = graph_from_links(
+            6,
+            &[
+                (0, 2, 1.0),
+                (2, 4, 1.0),
+                (4, 5, 1.0),
+                (5, 3, 1.0),
+                (0, 1, 2.0),
+                (1, 3, 2.0),
+            ],
+        );
+        let mut si = build_mock_search_instance(graph);
+        let route = |si: &SearchInstance| {
+            let result = run_a_star(
+                VertexId(0),
+                Some(VertexId(3)),
+                &Direction::Forward,
+                None,
+                None,
+                si,
+            )
+            .unwrap();
+            let route = vertex_oriented_route(VertexId(0), VertexId(3), &result.tree).unwrap();
+            let cost: Cost = route.iter().map(|et| et.total_cost()).sum();
+            let edge_ids = route.iter().map(|et| et.edge_id.0).collect::<Vec<_>>();
+            (cost, edge_ids)
+        };
+
+        let (default_cost, default_route) = route(&si);
+        assert_eq!(default_route, vec![0, 1, 2, 3]);
+
+        si.prefer_fewer_edges = true;
+        let (fewer_cost, fewer_route) = route(&si);
+        assert_eq!(fewer_route, vec![4, 5]);
+        assert_eq!(default_cost, fewer_cost);
+    }
 }
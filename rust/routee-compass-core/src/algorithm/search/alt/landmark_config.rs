@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// configures ALT (A*, landmarks, triangle inequality) preprocessing.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LandmarkConfig {
+    /// number of landmark vertices to select
+    pub count: usize,
+    /// optional CSV file used to cache the landmark distance tables between runs.
+    /// the file is read if it exists and matches the graph and its edge distances,
+    /// otherwise the tables are computed and written to this location.
+    pub cache_file: Option<PathBuf>,
+}
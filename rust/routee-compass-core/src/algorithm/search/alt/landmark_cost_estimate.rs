@@ -0,0 +1,55 @@
+use super::landmark_heuristic::LandmarkHeuristic;
+use crate::algorithm::search::cost_estimate_function::{
+    CostEstimateFunction, TraversalModelCostEstimate,
+};
+use crate::algorithm::search::{search_error::SearchError, search_instance::SearchInstance};
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::unit::{as_f64::AsF64, Cost};
+use crate::util::geo::haversine;
+use std::sync::Arc;
+
+/// tightens the traversal model estimate with ALT landmark lower bounds.
+/// the traversal models estimate from the straight-line haversine distance,
+/// and their estimated cost grows in proportion to it, so the estimate is
+/// scaled by the ratio of the landmark bound to the straight-line distance
+/// whenever the bound is larger. the bound never exceeds the shortest path
+/// distance, so the scaled estimate remains admissible.
+pub struct LandmarkCostEstimate {
+    landmarks: Arc<LandmarkHeuristic>,
+}
+
+impl LandmarkCostEstimate {
+    pub fn new(landmarks: Arc<LandmarkHeuristic>) -> LandmarkCostEstimate {
+        LandmarkCostEstimate { landmarks }
+    }
+}
+
+impl CostEstimateFunction for LandmarkCostEstimate {
+    fn estimate_cost(
+        &self,
+        src: VertexId,
+        dst: VertexId,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        let straight_line_cost = TraversalModelCostEstimate.estimate_cost(src, dst, state, si)?;
+
+        // reverse searches estimate toward their target against the edge
+        // direction, so only the bound that holds both ways is admissible
+        let forward = self.landmarks.distance_lower_bound(src, dst);
+        let reverse = self.landmarks.distance_lower_bound(dst, src);
+        let bound = if forward < reverse { forward } else { reverse };
+
+        let src_vertex = si.directed_graph.get_vertex(src)?;
+        let dst_vertex = si.directed_graph.get_vertex(dst)?;
+        let straight_line =
+            haversine::coord_distance_meters(&src_vertex.coordinate, &dst_vertex.coordinate)
+                .map_err(SearchError::InternalSearchError)?;
+        if straight_line.as_f64() <= 0.0 || bound <= straight_line {
+            return Ok(straight_line_cost);
+        }
+        let ratio = bound.as_f64() / straight_line.as_f64();
+        Ok(Cost::new(straight_line_cost.as_f64() * ratio))
+    }
+}
@@ -0,0 +1,16 @@
+use crate::model::road_network::graph_error::GraphError;
+use std::path::PathBuf;
+
+#[derive(thiserror::Error, Debug)]
+pub enum LandmarkError {
+    #[error("landmark count must be positive")]
+    NoLandmarks,
+    #[error("cannot select landmarks from an empty graph")]
+    EmptyGraph,
+    #[error(transparent)]
+    GraphError(#[from] GraphError),
+    #[error("failure reading or writing landmark cache file {0}: {1}")]
+    CacheIOError(PathBuf, String),
+    #[error("landmark cache file {0} does not match this graph: {1}")]
+    InvalidCache(PathBuf, String),
+}
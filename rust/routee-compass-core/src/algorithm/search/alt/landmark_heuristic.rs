@@ -0,0 +1,486 @@
+use super::{landmark_config::LandmarkConfig, landmark_error::LandmarkError};
+use crate::model::road_network::{graph::Graph, vertex_id::VertexId};
+use crate::model::unit::{as_f64::AsF64, cost::ReverseCost, Cost, Distance};
+use crate::util::{fingerprint::fingerprint, priority_queue::InternalPriorityQueue};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// ALT (A*, landmarks, triangle inequality) lower bounds on shortest path
+/// distance. for a landmark `L` and any vertices `v` and `t`, the triangle
+/// inequality gives
+///
+/// `d(v, t) >= d(L, t) - d(L, v)` and `d(v, t) >= d(v, L) - d(t, L)`
+///
+/// so the largest of these differences over all landmarks is an admissible
+/// estimate of the distance from `v` to `t`. distances are in meters, the
+/// internal distance unit of the graph.
+pub struct LandmarkHeuristic {
+    landmarks: Vec<VertexId>,
+    /// shortest path distance from each landmark to every vertex
+    from_landmark: Vec<Box<[f64]>>,
+    /// shortest path distance from every vertex to each landmark
+    to_landmark: Vec<Box<[f64]>>,
+    edge_distance_fingerprint: u64,
+}
+
+/// a row of the landmark cache file
+#[derive(Serialize, Deserialize)]
+struct LandmarkCacheRow {
+    edge_distance_fingerprint: u64,
+    landmark_vertex_id: VertexId,
+    vertex_id: VertexId,
+    from_landmark: f64,
+    to_landmark: f64,
+}
+
+impl LandmarkHeuristic {
+    /// selects landmarks and computes their distance tables. landmarks are
+    /// chosen greedily, each one being the vertex farthest from all previously
+    /// selected landmarks, starting from the vertex farthest from vertex 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the road network
+    /// * `count` - number of landmarks to select, fewer if the graph runs out of distinct vertices
+    ///
+    /// # Returns
+    ///
+    /// The landmark heuristic, or an error if the graph cannot be searched.
+    pub fn build(graph: &Graph, count: usize) -> Result<LandmarkHeuristic, LandmarkError> {
+        if count == 0 {
+            return Err(LandmarkError::NoLandmarks);
+        }
+        let n_vertices = graph.n_vertices();
+        if n_vertices == 0 {
+            return Err(LandmarkError::EmptyGraph);
+        }
+
+        let seed_distances = shortest_distances(graph, VertexId(0), false)?;
+        let mut next = farthest_vertex(&seed_distances, &[]);
+        let mut min_distance = vec![f64::INFINITY; n_vertices];
+        let mut landmarks = vec![];
+        let mut from_landmark = vec![];
+        let mut to_landmark = vec![];
+        while let Some(landmark) = next {
+            let from = shortest_distances(graph, landmark, false)?;
+            let to = shortest_distances(graph, landmark, true)?;
+            for (idx, d) in min_distance.iter_mut().enumerate() {
+                *d = d.min(from[idx].min(to[idx]));
+            }
+            landmarks.push(landmark);
+            from_landmark.push(from);
+            to_landmark.push(to);
+            if landmarks.len() == count {
+                break;
+            }
+            next = farthest_vertex(&min_distance, &landmarks);
+        }
+        log::debug!("selected {} ALT landmarks", landmarks.len());
+
+        Ok(LandmarkHeuristic {
+            landmarks,
+            from_landmark,
+            to_landmark,
+            edge_distance_fingerprint: edge_distance_fingerprint(graph),
+        })
+    }
+
+    /// reads the landmark tables from the configured cache file if it exists
+    /// and was built from the same edge distances. otherwise, builds the tables and writes them to
+    /// the cache file, if one is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the road network
+    /// * `config` - landmark count and optional cache file
+    ///
+    /// # Returns
+    ///
+    /// The landmark heuristic, or an error if it could not be built or cached.
+    pub fn load_or_build(
+        graph: &Graph,
+        config: &LandmarkConfig,
+    ) -> Result<LandmarkHeuristic, LandmarkError> {
+        if let Some(cache_file) = &config.cache_file {
+            if cache_file.is_file() {
+                match LandmarkHeuristic::read_cache(cache_file, graph, config.count) {
+                    Ok(heuristic) => {
+                        log::info!("loaded ALT landmarks from {:?}", cache_file);
+                        return Ok(heuristic);
+                    }
+                    Err(LandmarkError::InvalidCache(path, msg)) => {
+                        log::warn!(
+                            "rebuilding ALT landmarks, cache {:?} is stale: {}",
+                            path,
+                            msg
+                        );
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        let heuristic = LandmarkHeuristic::build(graph, config.count)?;
+        if let Some(cache_file) = &config.cache_file {
+            heuristic.write_cache(cache_file)?;
+            log::info!("wrote ALT landmarks to {:?}", cache_file);
+        }
+        Ok(heuristic)
+    }
+
+    /// the selected landmark vertices
+    pub fn landmarks(&self) -> &[VertexId] {
+        &self.landmarks
+    }
+
+    /// computes a lower bound on the shortest path distance from `src` to `dst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - start of the path
+    /// * `dst` - end of the path
+    ///
+    /// # Returns
+    ///
+    /// A distance in meters that never exceeds the true shortest path distance.
+    pub fn distance_lower_bound(&self, src: VertexId, dst: VertexId) -> Distance {
+        let mut bound: f64 = 0.0;
+        for (from, to) in self.from_landmark.iter().zip(self.to_landmark.iter()) {
+            if let (Some(from_src), Some(from_dst)) = (from.get(src.0), from.get(dst.0)) {
+                if from_src.is_finite() && from_dst.is_finite() {
+                    bound = bound.max(from_dst - from_src);
+                }
+            }
+            if let (Some(to_src), Some(to_dst)) = (to.get(src.0), to.get(dst.0)) {
+                if to_src.is_finite() && to_dst.is_finite() {
+                    bound = bound.max(to_src - to_dst);
+                }
+            }
+        }
+        Distance::new(bound)
+    }
+
+    /// writes the landmark tables to a CSV file with one row per landmark and
+    /// vertex, each holding a fingerprint of the edge distances they were
+    /// built from.
+    pub fn write_cache(&self, path: &Path) -> Result<(), LandmarkError> {
+        let io_err = |e: csv::Error| LandmarkError::CacheIOError(path.to_path_buf(), e.to_string());
+        let mut writer = csv::Writer::from_path(path).map_err(io_err)?;
+        for (idx, landmark) in self.landmarks.iter().enumerate() {
+            let from = &self.from_landmark[idx];
+            let to = &self.to_landmark[idx];
+            for vertex_idx in 0..from.len() {
+                let row = LandmarkCacheRow {
+                    edge_distance_fingerprint: self.edge_distance_fingerprint,
+                    landmark_vertex_id: *landmark,
+                    vertex_id: VertexId(vertex_idx),
+                    from_landmark: from[vertex_idx],
+                    to_landmark: to[vertex_idx],
+                };
+                writer.serialize(row).map_err(io_err)?;
+            }
+        }
+        writer
+            .flush()
+            .map_err(|e| LandmarkError::CacheIOError(path.to_path_buf(), e.to_string()))?;
+        Ok(())
+    }
+
+    /// reads landmark tables written by [`LandmarkHeuristic::write_cache`],
+    /// confirming that they were built from the edge distances of this graph
+    /// and cover every vertex of it.
+    pub fn read_cache(
+        path: &Path,
+        graph: &Graph,
+        count: usize,
+    ) -> Result<LandmarkHeuristic, LandmarkError> {
+        let invalid = |msg: String| LandmarkError::InvalidCache(path.to_path_buf(), msg);
+        let n_vertices = graph.n_vertices();
+        let fingerprint = edge_distance_fingerprint(graph);
+        let mut reader = csv::Reader::from_path(path)
+            .map_err(|e| LandmarkError::CacheIOError(path.to_path_buf(), e.to_string()))?;
+
+        let mut landmarks: Vec<VertexId> = vec![];
+        let mut from_landmark: Vec<Vec<f64>> = vec![];
+        let mut to_landmark: Vec<Vec<f64>> = vec![];
+        for row in reader.deserialize::<LandmarkCacheRow>() {
+            let row = row.map_err(|e| invalid(e.to_string()))?;
+            if row.edge_distance_fingerprint != fingerprint {
+                return Err(invalid(String::from(
+                    "edge distances have changed since the landmarks were built",
+                )));
+            }
+            if landmarks.last() != Some(&row.landmark_vertex_id) {
+                landmarks.push(row.landmark_vertex_id);
+                from_landmark.push(Vec::with_capacity(n_vertices));
+                to_landmark.push(Vec::with_capacity(n_vertices));
+            }
+            let idx = landmarks.len() - 1;
+            if row.vertex_id.0 != from_landmark[idx].len() {
+                return Err(invalid(format!(
+                    "expected vertex {} for landmark {}, found {}",
+                    from_landmark[idx].len(),
+                    row.landmark_vertex_id,
+                    row.vertex_id
+                )));
+            }
+            from_landmark[idx].push(row.from_landmark);
+            to_landmark[idx].push(row.to_landmark);
+        }
+
+        if landmarks.is_empty() || landmarks.len() > count {
+            return Err(invalid(format!(
+                "expected up to {} landmarks, found {}",
+                count,
+                landmarks.len()
+            )));
+        }
+        if let Some(table) = from_landmark.iter().find(|t| t.len() != n_vertices) {
+            return Err(invalid(format!(
+                "expected {} vertices per landmark, found {}",
+                n_vertices,
+                table.len()
+            )));
+        }
+
+        Ok(LandmarkHeuristic {
+            landmarks,
+            from_landmark: from_landmark
+                .into_iter()
+                .map(|t| t.into_boxed_slice())
+                .collect(),
+            to_landmark: to_landmark
+                .into_iter()
+                .map(|t| t.into_boxed_slice())
+                .collect(),
+            edge_distance_fingerprint: fingerprint,
+        })
+    }
+}
+
+/// runs Dijkstra's algorithm over edge distances from a source vertex. when
+/// `reverse` is true, edges are followed backward so the result holds the
+/// distance from every vertex to the source. unreachable vertices have an
+/// infinite distance.
+fn shortest_distances(
+    graph: &Graph,
+    source: VertexId,
+    reverse: bool,
+) -> Result<Box<[f64]>, LandmarkError> {
+    let mut distances = vec![f64::INFINITY; graph.n_vertices()];
+    distances[source.0] = 0.0;
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    queue.push(source, Cost::ZERO.into());
+
+    while let Some((vertex_id, _)) = queue.pop() {
+        let distance = distances[vertex_id.0];
+        let edge_ids = if reverse {
+            graph.in_edges_iter(vertex_id)?.cloned().collect::<Vec<_>>()
+        } else {
+            graph
+                .out_edges_iter(vertex_id)?
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        for edge_id in edge_ids {
+            let edge = graph.get_edge(edge_id)?;
+            let next_vertex_id = if reverse {
+                edge.src_vertex_id
+            } else {
+                edge.dst_vertex_id
+            };
+            let next_distance = distance + edge.distance.as_f64();
+            if next_distance < distances[next_vertex_id.0] {
+                distances[next_vertex_id.0] = next_distance;
+                queue.push_increase(next_vertex_id, Cost::new(next_distance).into());
+            }
+        }
+    }
+    Ok(distances.into_boxed_slice())
+}
+
+/// a fingerprint of the endpoints and distance of every edge, used to detect
+/// a stale cache
+fn edge_distance_fingerprint(graph: &Graph) -> u64 {
+    let edges = graph.edges.iter().flat_map(|edge| {
+        [
+            edge.src_vertex_id.0 as u64,
+            edge.dst_vertex_id.0 as u64,
+            edge.distance.as_f64().to_bits(),
+        ]
+    });
+    fingerprint(std::iter::once(graph.n_edges() as u64).chain(edges))
+}
+
+/// finds the vertex with the largest distance that is not already a landmark.
+/// unreachable vertices are preferred so that every component gets a landmark.
+/// returns None when no remaining vertex is a positive distance away.
+fn farthest_vertex(distances: &[f64], landmarks: &[VertexId]) -> Option<VertexId> {
+    let mut best: Option<(VertexId, f64)> = None;
+    for (idx, distance) in distances.iter().enumerate() {
+        let vertex_id = VertexId(idx);
+        if *distance <= 0.0 || landmarks.contains(&vertex_id) {
+            continue;
+        }
+        match best {
+            Some((_, best_distance)) if *distance <= best_distance => {}
+            _ => best = Some((vertex_id, *distance)),
+        }
+    }
+    best.map(|(vertex_id, _)| vertex_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::alt::landmark_cost_estimate::LandmarkCostEstimate;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::search_instance::SearchInstance;
+    use crate::model::property::edge::Edge;
+    use crate::model::property::vertex::Vertex;
+    use crate::test_util::graph_fixture::graph_from_edges;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+    use std::sync::Arc;
+
+    const GRID_SIZE: usize = 10;
+
+    /// a bidirectional grid with vertices 0.001 degrees apart. each edge is
+    /// 250 meters, roughly twice the straight-line distance, so haversine
+    /// underestimates the remaining distance.
+    fn build_grid_graph() -> Graph {
+        let vertices = (0..GRID_SIZE * GRID_SIZE)
+            .map(|i| {
+                let (row, col) = (i / GRID_SIZE, i % GRID_SIZE);
+                Vertex::new(i, col as f32 * 0.001, row as f32 * 0.001)
+            })
+            .collect::<Vec<_>>();
+        let mut links = vec![];
+        for row in 0..GRID_SIZE {
+            for col in 0..GRID_SIZE {
+                let v = row * GRID_SIZE + col;
+                if col + 1 < GRID_SIZE {
+                    links.push((v, v + 1));
+                }
+                if row + 1 < GRID_SIZE {
+                    links.push((v, v + GRID_SIZE));
+                }
+            }
+        }
+        let edges = links
+            .iter()
+            .flat_map(|(a, b)| [(*a, *b), (*b, *a)])
+            .enumerate()
+            .map(|(idx, (src, dst))| Edge::new(idx, src, dst, 250.0))
+            .collect::<Vec<_>>();
+        graph_from_edges(vertices, edges)
+    }
+
+    fn build_search_instance(
+        graph: Arc<Graph>,
+        landmarks: Option<Arc<LandmarkHeuristic>>,
+    ) -> SearchInstance {
+        let builder = SearchInstanceBuilder::new(graph);
+        match landmarks {
+            Some(landmarks) => builder
+                .cost_estimate(Arc::new(LandmarkCostEstimate::new(landmarks)))
+                .build(),
+            None => builder.build(),
+        }
+    }
+
+    #[test]
+    fn test_lower_bound_is_admissible() {
+        let graph = build_grid_graph();
+        let heuristic = LandmarkHeuristic::build(&graph, 4).unwrap();
+        assert_eq!(heuristic.landmarks().len(), 4);
+        for src in 0..graph.n_vertices() {
+            let exact = shortest_distances(&graph, VertexId(src), false).unwrap();
+            for (dst, exact_distance) in exact.iter().enumerate() {
+                let bound = heuristic.distance_lower_bound(VertexId(src), VertexId(dst));
+                assert!(bound.as_f64() <= exact_distance + 1e-9);
+            }
+        }
+        // the bound is exact between a landmark and any other vertex
+        let landmark = heuristic.landmarks()[0];
+        let bound = heuristic.distance_lower_bound(landmark, VertexId(55));
+        let exact = shortest_distances(&graph, landmark, false).unwrap()[55];
+        assert_eq!(bound.as_f64(), exact);
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let graph = build_grid_graph();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_file = cache_dir.path().join("landmarks.csv");
+        let config = LandmarkConfig {
+            count: 3,
+            cache_file: Some(cache_file.clone()),
+        };
+        let built = LandmarkHeuristic::load_or_build(&graph, &config).unwrap();
+        assert!(cache_file.is_file());
+        let cached = LandmarkHeuristic::load_or_build(&graph, &config).unwrap();
+        assert_eq!(built.landmarks(), cached.landmarks());
+        assert_eq!(
+            built.distance_lower_bound(VertexId(3), VertexId(97)),
+            cached.distance_lower_bound(VertexId(3), VertexId(97))
+        );
+
+        // a cache built for more landmarks than requested is stale
+        let fewer = LandmarkHeuristic::read_cache(&cache_file, &graph, 2);
+        assert!(matches!(fewer, Err(LandmarkError::InvalidCache(_, _))));
+
+        // so is a cache built before an edge distance changed
+        let mut changed = build_grid_graph();
+        changed.edges[0].distance = Distance::new(500.0);
+        let stale = LandmarkHeuristic::read_cache(&cache_file, &changed, 3);
+        assert!(matches!(stale, Err(LandmarkError::InvalidCache(_, _))));
+    }
+
+    /// compares the work done by A* using the haversine heuristic alone
+    /// against A* with landmark lower bounds across corner-to-corner queries.
+    #[test]
+    fn test_landmarks_settle_fewer_vertices_than_haversine() {
+        let graph = Arc::new(build_grid_graph());
+        let heuristic = Arc::new(LandmarkHeuristic::build(&graph, 4).unwrap());
+        let haversine_si = build_search_instance(graph.clone(), None);
+        let alt_si = build_search_instance(graph.clone(), Some(heuristic));
+
+        let last = GRID_SIZE * GRID_SIZE - 1;
+        let queries = [(0, last), (last, 0), (GRID_SIZE - 1, last - GRID_SIZE + 1)];
+        let mut haversine_settled = 0;
+        let mut alt_settled = 0;
+        for (src, dst) in queries {
+            let run = |si: &SearchInstance| {
+                let result = run_a_star(
+                    VertexId(src),
+                    Some(VertexId(dst)),
+                    &Direction::Forward,
+                    None,
+                    None,
+                    si,
+                )
+                .unwrap();
+                let branch = result.tree.get(&VertexId(dst)).unwrap();
+                let distance = branch.edge_traversal.result_state[0].0;
+                (distance, result.metadata.vertices_settled)
+            };
+            let (haversine_distance, h) = run(&haversine_si);
+            let (alt_distance, a) = run(&alt_si);
+            // both heuristics are admissible, so both routes are optimal
+            assert_eq!(haversine_distance, alt_distance);
+            haversine_settled += h;
+            alt_settled += a;
+        }
+        log::info!(
+            "vertices settled: haversine {}, landmarks {}",
+            haversine_settled,
+            alt_settled
+        );
+        assert!(
+            alt_settled < haversine_settled,
+            "landmarks settled {} vertices, haversine settled {}",
+            alt_settled,
+            haversine_settled
+        );
+    }
+}
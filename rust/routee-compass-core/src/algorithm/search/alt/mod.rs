@@ -0,0 +1,4 @@
+pub mod landmark_config;
+pub mod landmark_cost_estimate;
+pub mod landmark_error;
+pub mod landmark_heuristic;
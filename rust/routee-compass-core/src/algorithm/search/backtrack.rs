@@ -1,7 +1,10 @@
 use super::{
-    edge_traversal::EdgeTraversal, search_error::SearchError, search_tree_branch::SearchTreeBranch,
+    edge_traversal::EdgeTraversal, search_error::SearchError, search_instance::SearchInstance,
+    search_tree_branch::SearchTreeBranch,
 };
 use crate::model::road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId};
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::unit::{Cost, CostUnit, UnitError};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -53,3 +56,314 @@ pub fn edge_oriented_route(
         .map_err(SearchError::GraphError)?;
     vertex_oriented_route(o_v, d_v, solution)
 }
+
+/// walks a forward route from its destination back to its origin, producing
+/// the edge traversals in reverse order. the state is recomputed from the
+/// initial state at the destination using reverse traversals, so each
+/// traversal's result state accumulates toward the origin rather than being
+/// a copy of the forward state. each consecutive pair of edges is confirmed
+/// to be connected using the reverse adjacency of the graph.
+///
+/// # Arguments
+///
+/// * `route` - a route in forward (origin to destination) order
+/// * `si` - the search assets used to recompute each traversal
+///
+/// # Returns
+///
+/// the route in destination to origin order, or an error if the route is
+/// disconnected or a traversal fails
+pub fn reversed_route(
+    route: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    for pair in route.windows(2) {
+        let (prev, next) = (pair[0].edge_id, pair[1].edge_id);
+        let junction = si.directed_graph.src_vertex_id(next)?;
+        let connected = si
+            .directed_graph
+            .in_edges_iter(junction)?
+            .any(|e| *e == prev);
+        if !connected {
+            return Err(SearchError::DisconnectedRoute(prev, next));
+        }
+    }
+
+    let mut state = si.state_model.initial_state()?;
+    let mut result: Vec<EdgeTraversal> = Vec::with_capacity(route.len());
+    let mut next_edge_id: Option<EdgeId> = None;
+    for forward in route.iter().rev() {
+        let et = EdgeTraversal::reverse_traversal(forward.edge_id, next_edge_id, &state, si)?;
+        state = et.result_state.clone();
+        next_edge_id = Some(et.edge_id);
+        result.push(et);
+    }
+    Ok(result)
+}
+
+/// the result of recomputing a fixed sequence of edges, see [`score_route`].
+#[derive(Clone, Debug)]
+pub struct RouteScore {
+    /// the traversal of each edge, in route order
+    pub route: Vec<EdgeTraversal>,
+    /// the search state after traversing the final edge
+    pub final_state: Vec<StateVar>,
+    /// sum of the access and traversal costs of every edge
+    pub total_cost: Cost,
+    /// unit of the total cost, when the cost model produces costs in a unit
+    pub cost_unit: Option<CostUnit>,
+}
+
+impl RouteScore {
+    /// the difference in total cost between this route and another,
+    /// confirming first that both costs are in the same unit. routes scored
+    /// by cost models without a unit are compared as-is.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the score to subtract from this score
+    ///
+    /// # Returns
+    ///
+    /// this total cost minus the other total cost, or an error if the
+    /// costs are in different units
+    pub fn cost_difference(&self, other: &RouteScore) -> Result<Cost, UnitError> {
+        if let (Some(unit), Some(other_unit)) = (&self.cost_unit, &other.cost_unit) {
+            unit.ensure_compatible(other_unit)?;
+        }
+        Ok(self.total_cost - other.total_cost)
+    }
+}
+
+/// scores a fixed sequence of edges under the models of a search instance
+/// without running a search, so the same physical path can be compared
+/// across traversal, access and cost models. each edge is accessed from
+/// the previous edge and traversed in order, starting from the initial state.
+///
+/// # Arguments
+///
+/// * `edge_ids` - the route in forward (origin to destination) order
+/// * `si` - the search assets whose models score the route
+///
+/// # Returns
+///
+/// the traversals, final state and total cost of the route, or an error if
+/// the route is empty, two consecutive edges are not connected in the
+/// forward adjacency of the graph, or a traversal fails
+pub fn score_route(edge_ids: &[EdgeId], si: &SearchInstance) -> Result<RouteScore, SearchError> {
+    if edge_ids.is_empty() {
+        return Err(SearchError::BuildError(String::from(
+            "cannot score an empty route",
+        )));
+    }
+    for pair in edge_ids.windows(2) {
+        let (prev, next) = (pair[0], pair[1]);
+        let junction = si.directed_graph.dst_vertex_id(prev)?;
+        let connected = si
+            .directed_graph
+            .out_edges_iter(junction)?
+            .any(|e| *e == next);
+        if !connected {
+            return Err(SearchError::DisconnectedRoute(prev, next));
+        }
+    }
+
+    let mut state = si.state_model.initial_state()?;
+    let mut route: Vec<EdgeTraversal> = Vec::with_capacity(edge_ids.len());
+    let mut prev_edge_id: Option<EdgeId> = None;
+    for edge_id in edge_ids.iter() {
+        let et = EdgeTraversal::forward_traversal(*edge_id, prev_edge_id, &state, si)?;
+        state = et.result_state.clone();
+        prev_edge_id = Some(et.edge_id);
+        route.push(et);
+    }
+    let total_cost = route.iter().map(|e| e.total_cost()).sum();
+    Ok(RouteScore {
+        route,
+        final_state: state,
+        total_cost,
+        cost_unit: si.cost_model.cost_unit(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::{a_star::a_star_algorithm::run_a_star, direction::Direction};
+    use crate::model::cost::cost_aggregation::CostAggregation;
+    use crate::model::cost::cost_model::CostModel;
+    use crate::model::cost::vehicle::vehicle_cost_rate::VehicleCostRate;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::model::unit::as_f64::AsF64;
+    use crate::model::unit::{Distance, DistanceUnit, TimeUnit};
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+
+    /// (0) -1-> (1) -2-> (2) -3-> (3), with a 10-meter shortcut (0) -> (3)
+    /// and each edge paired with its reverse
+    fn build_search_instance() -> SearchInstance {
+        let graph = graph_from_links(
+            4,
+            &[
+                (0, 1, 1.0),
+                (1, 2, 2.0),
+                (2, 3, 3.0),
+                (0, 3, 10.0),
+                (1, 0, 1.0),
+                (2, 1, 2.0),
+                (3, 2, 3.0),
+            ],
+        );
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 100 })
+            .build()
+    }
+
+    fn total_cost(route: &[EdgeTraversal]) -> Cost {
+        route.iter().map(|e| e.total_cost()).sum()
+    }
+
+    #[test]
+    fn test_reversed_route_has_same_total_cost() {
+        let si = build_search_instance();
+        let tree = run_a_star(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            None,
+            None,
+            &si,
+        )
+        .unwrap()
+        .tree;
+        let forward = vertex_oriented_route(VertexId(0), VertexId(3), &tree).unwrap();
+        assert_eq!(
+            forward.iter().map(|e| e.edge_id).collect::<Vec<_>>(),
+            vec![EdgeId(0), EdgeId(1), EdgeId(2)]
+        );
+
+        let reversed = reversed_route(&forward, &si).unwrap();
+        assert_eq!(
+            reversed.iter().map(|e| e.edge_id).collect::<Vec<_>>(),
+            vec![EdgeId(2), EdgeId(1), EdgeId(0)]
+        );
+        assert_eq!(total_cost(&reversed), total_cost(&forward));
+
+        // state accumulates from the destination, so the first reversed edge
+        // carries only its own distance
+        let first_distance = si
+            .state_model
+            .get_distance(
+                &reversed[0].result_state,
+                &"distance".into(),
+                &DistanceUnit::Meters,
+            )
+            .unwrap();
+        assert_eq!(first_distance, Distance::new(3.0));
+    }
+
+    #[test]
+    fn test_reversed_route_rejects_disconnected_route() {
+        let si = build_search_instance();
+        let tree = run_a_star(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            None,
+            None,
+            &si,
+        )
+        .unwrap()
+        .tree;
+        let mut route = vertex_oriented_route(VertexId(0), VertexId(3), &tree).unwrap();
+        route.remove(1);
+        let result = reversed_route(&route, &si);
+        assert!(matches!(
+            result,
+            Err(SearchError::DisconnectedRoute(EdgeId(0), EdgeId(2)))
+        ));
+    }
+
+    /// the route (0) -[0]-> (1) -[1]-> (2) -[2]-> (3)
+    fn forward_route(si: &SearchInstance) -> Vec<EdgeTraversal> {
+        let tree = run_a_star(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            None,
+            None,
+            si,
+        )
+        .unwrap()
+        .tree;
+        vertex_oriented_route(VertexId(0), VertexId(3), &tree).unwrap()
+    }
+
+    fn route_distance(route: &[EdgeTraversal], si: &SearchInstance) -> f64 {
+        let last = route.last().unwrap();
+        si.state_model
+            .get_distance(
+                &last.result_state,
+                &"distance".into(),
+                &DistanceUnit::Meters,
+            )
+            .unwrap()
+            .as_f64()
+    }
+
+    #[test]
+    fn test_score_route_under_different_cost_models() {
+        let si = build_search_instance();
+        let route = [EdgeId(0), EdgeId(1), EdgeId(2)];
+        let score = score_route(&route, &si).unwrap();
+        assert_eq!(score.total_cost, total_cost(&forward_route(&si)));
+        assert_eq!(score.total_cost, Cost::new(6.0));
+        assert_eq!(route_distance(&score.route, &si), 6.0);
+
+        // the same path under a model that weighs distance twice as heavily
+        let base = build_search_instance();
+        let cost_model = CostModel::new(
+            Arc::new(HashMap::from([(String::from("distance"), 2.0)])),
+            Arc::new(HashMap::from([(
+                String::from("distance"),
+                VehicleCostRate::Raw,
+            )])),
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            base.state_model.clone(),
+        )
+        .unwrap();
+        let weighted = SearchInstance { cost_model, ..base };
+        let weighted_score = score_route(&route, &weighted).unwrap();
+        assert_eq!(weighted_score.total_cost, Cost::new(12.0));
+        assert_eq!(weighted_score.final_state, score.final_state);
+
+        // doubled distances are no longer in meters, so only the raw score has a unit
+        assert_eq!(
+            score.cost_unit,
+            Some(CostUnit::Distance(DistanceUnit::Meters))
+        );
+        assert_eq!(weighted_score.cost_unit, None);
+        let difference = weighted_score.cost_difference(&score).unwrap();
+        assert_eq!(difference, Cost::new(6.0));
+        let in_seconds = RouteScore {
+            cost_unit: Some(CostUnit::Time(TimeUnit::Seconds)),
+            ..score.clone()
+        };
+        assert!(matches!(
+            in_seconds.cost_difference(&score),
+            Err(UnitError::IncompatibleCostUnits(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_score_route_rejects_disconnected_route() {
+        let si = build_search_instance();
+        let result = score_route(&[EdgeId(0), EdgeId(2)], &si);
+        assert!(matches!(
+            result,
+            Err(SearchError::DisconnectedRoute(EdgeId(0), EdgeId(2)))
+        ));
+        assert!(score_route(&[], &si).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    edge_traversal::EdgeTraversal, search_error::SearchError, search_instance::SearchInstance,
+    search_priority::SearchPriority,
+};
+use crate::model::{
+    road_network::vertex_id::VertexId,
+    traversal::state::state_variable::StateVar,
+    unit::{as_f64::AsF64, Cost},
+};
+use allocative::Allocative;
+
+/// compares the branches of a search. the search keeps the lowest-cost branch
+/// into each vertex and expands the frontier vertex with the highest priority
+/// first, so this decides both which routes win and the order of the search.
+pub trait BranchCost {
+    /// the cost accumulated along a branch
+    type Cost: Clone + Ord + Allocative;
+    /// the frontier priority of a vertex, where greater priorities pop first
+    type Priority: Ord + Allocative;
+
+    /// the cost of the empty branch at the source of the search.
+    fn origin_cost(&self) -> Self::Cost;
+
+    /// extends the cost of a branch with the traversal of one more edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost` - cost of the branch up to the traversed edge
+    /// * `traversal` - the traversal of the edge, holding the resulting state
+    /// * `si` - the search instance
+    ///
+    /// # Returns
+    ///
+    /// the cost of the extended branch, or an error if a model fails
+    fn extend(
+        &self,
+        cost: &Self::Cost,
+        traversal: &EdgeTraversal,
+        si: &SearchInstance,
+    ) -> Result<Self::Cost, SearchError>;
+
+    /// the priority of a frontier vertex reached with the given branch cost.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost` - cost of the branch into the vertex
+    /// * `vertex_id` - the frontier vertex
+    /// * `target` - optional destination of the search
+    /// * `state` - search state used to estimate the remaining cost
+    /// * `si` - the search instance
+    ///
+    /// # Returns
+    ///
+    /// the priority of the vertex, or an error if a model fails
+    fn priority(
+        &self,
+        cost: &Self::Cost,
+        vertex_id: VertexId,
+        target: Option<VertexId>,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<Self::Priority, SearchError>;
+
+    /// true if a branch of this cost may still be extended. the search drops
+    /// any branch outside the bound, so a bounded search never expands a
+    /// vertex beyond it. branches are unbounded by default.
+    fn within_bound(&self, _cost: &Self::Cost) -> bool {
+        true
+    }
+
+    /// called as each vertex is removed from the open set, before it is
+    /// expanded. returning true ends the search there, which lets a search
+    /// stop once it has settled the vertices it needs. never ends the search
+    /// by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_id` - the settled vertex
+    /// * `cost` - cost of the branch into the settled vertex
+    ///
+    /// # Returns
+    ///
+    /// true if the search should end
+    fn on_settled(&mut self, _vertex_id: VertexId, _cost: &Self::Cost) -> bool {
+        false
+    }
+}
+
+/// the A* branch cost, which sums the scalar cost of each edge traversal and
+/// prioritizes a vertex by its cost plus the estimated cost remaining to the
+/// target, inflated by an optional weight factor. an optional max cost bounds
+/// the search to branches costing no more than it.
+pub struct ScalarBranchCost {
+    pub weight_factor: Option<Cost>,
+    pub max_cost: Option<Cost>,
+}
+
+impl BranchCost for ScalarBranchCost {
+    type Cost = Cost;
+    type Priority = SearchPriority;
+
+    fn origin_cost(&self) -> Cost {
+        Cost::ZERO
+    }
+
+    fn extend(
+        &self,
+        cost: &Cost,
+        traversal: &EdgeTraversal,
+        _si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        Ok(*cost + traversal.total_cost())
+    }
+
+    fn priority(
+        &self,
+        cost: &Cost,
+        vertex_id: VertexId,
+        target: Option<VertexId>,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<SearchPriority, SearchError> {
+        let h_cost = match target {
+            None => Cost::ZERO,
+            Some(target_v) => {
+                let cost_est = si.estimate_traversal_cost(vertex_id, target_v, state)?;
+                let weight = self.weight_factor.unwrap_or(Cost::ONE);
+                Cost::new(cost_est.as_f64() * weight.as_f64())
+            }
+        };
+        Ok(SearchPriority::new(*cost + h_cost, vertex_id))
+    }
+
+    fn within_bound(&self, cost: &Cost) -> bool {
+        self.max_cost.is_none_or(|max_cost| *cost <= max_cost)
+    }
+}
@@ -0,0 +1,963 @@
+use super::{
+    contraction_hierarchy_config::ContractionHierarchyConfig,
+    contraction_hierarchy_error::ContractionHierarchyError,
+};
+use crate::algorithm::search::{
+    direction::Direction, edge_traversal::EdgeTraversal,
+    search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+    search_instance::SearchInstance, search_metadata::SearchMetadata,
+    search_tree_branch::SearchTreeBranch,
+};
+use crate::model::road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId};
+use crate::model::unit::{as_f64::AsF64, cost::ReverseCost, Cost};
+use crate::util::{fingerprint::fingerprint, priority_queue::InternalPriorityQueue};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// maximum number of vertices settled by each witness search. a witness
+/// search that gives up early adds a shortcut that may be unnecessary, which
+/// costs query time but never correctness.
+const WITNESS_SETTLE_LIMIT: usize = 500;
+
+/// an edge of the hierarchy, either an edge of the road network or a shortcut
+/// that replaces a path through a contracted vertex
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+struct HierarchyEdge {
+    src_vertex_id: VertexId,
+    dst_vertex_id: VertexId,
+    cost: f64,
+    kind: HierarchyEdgeKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case", tag = "type")]
+enum HierarchyEdgeKind {
+    Original {
+        edge_id: EdgeId,
+    },
+    /// indices of the two hierarchy edges which this shortcut joins
+    Shortcut {
+        first: usize,
+        second: usize,
+    },
+}
+
+/// the contents of the cache file
+#[derive(Serialize, Deserialize)]
+struct ContractionHierarchyCache {
+    edge_cost_fingerprint: u64,
+    rank: Vec<usize>,
+    edges: Vec<HierarchyEdge>,
+}
+
+/// adjacency of the vertices that remain during contraction, keeping the
+/// cheapest hierarchy edge between each pair of vertices
+type Overlay = Vec<BTreeMap<VertexId, (f64, usize)>>;
+
+/// a contraction hierarchy over the road network for a fixed set of edge
+/// costs. vertices are contracted one at a time, adding shortcut edges so
+/// that shortest path costs among the remaining vertices are preserved. a
+/// query runs a bidirectional Dijkstra search that only moves toward vertices
+/// contracted later, which settles far fewer vertices than A* on large graphs.
+///
+/// the hierarchy is only valid for the edge costs it was built with. costs
+/// are computed once per edge from the initial search state, so the traversal
+/// and cost models must not depend on the state, time of day, or previous
+/// edge, and frontier and access models are not applied. rebuild the
+/// hierarchy whenever speeds or cost weights change.
+pub struct ContractionHierarchy {
+    /// contraction order of each vertex
+    rank: Box<[usize]>,
+    edges: Vec<HierarchyEdge>,
+    /// for each vertex, hierarchy edges leading to a vertex of higher rank
+    upward: Vec<Vec<usize>>,
+    /// for each vertex, hierarchy edges arriving from a vertex of higher rank
+    downward: Vec<Vec<usize>>,
+    edge_cost_fingerprint: u64,
+}
+
+impl ContractionHierarchy {
+    /// computes the cost of traversing each edge of the graph from the initial
+    /// search state, using the traversal and cost models of the search instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `si` - search assets for the traversal and cost models of the hierarchy
+    ///
+    /// # Returns
+    ///
+    /// The cost of each edge indexed by EdgeId, or an error if an edge could not be traversed.
+    pub fn edge_costs(si: &SearchInstance) -> Result<Vec<f64>, ContractionHierarchyError> {
+        let initial_state = si
+            .state_model
+            .initial_state()
+            .map_err(|e| ContractionHierarchyError::EdgeCostError(e.to_string()))?;
+        (0..si.directed_graph.n_edges())
+            .map(|idx| {
+                let et = EdgeTraversal::forward_traversal(EdgeId(idx), None, &initial_state, si)?;
+                Ok(et.total_cost().as_f64())
+            })
+            .collect()
+    }
+
+    /// contracts every vertex of the graph. vertices are ordered by edge
+    /// difference, the number of shortcuts added less the number of edges
+    /// removed, plus the number of neighbors already contracted, which spreads
+    /// contraction evenly across the graph. priorities are updated lazily.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the road network
+    /// * `edge_costs` - the non-negative cost of each edge, indexed by EdgeId, where an infinite cost is impassable
+    ///
+    /// # Returns
+    ///
+    /// The contraction hierarchy, or an error if the edge costs are invalid.
+    pub fn build(
+        graph: &Graph,
+        edge_costs: &[f64],
+    ) -> Result<ContractionHierarchy, ContractionHierarchyError> {
+        let n_vertices = graph.n_vertices();
+        if n_vertices == 0 {
+            return Err(ContractionHierarchyError::EmptyGraph);
+        }
+        if edge_costs.len() != graph.n_edges() {
+            return Err(ContractionHierarchyError::EdgeCostCountMismatch(
+                graph.n_edges(),
+                edge_costs.len(),
+            ));
+        }
+
+        let mut edges: Vec<HierarchyEdge> = vec![];
+        let mut out_overlay: Overlay = vec![BTreeMap::new(); n_vertices];
+        let mut in_overlay: Overlay = vec![BTreeMap::new(); n_vertices];
+        for edge in graph.edges.iter() {
+            let cost = edge_costs[edge.edge_id.0];
+            if cost < 0.0 {
+                return Err(ContractionHierarchyError::NegativeEdgeCost(
+                    edge.edge_id,
+                    cost,
+                ));
+            }
+            // self loops are never part of a shortest path
+            if !cost.is_finite() || edge.src_vertex_id == edge.dst_vertex_id {
+                continue;
+            }
+            let idx = edges.len();
+            edges.push(HierarchyEdge {
+                src_vertex_id: edge.src_vertex_id,
+                dst_vertex_id: edge.dst_vertex_id,
+                cost,
+                kind: HierarchyEdgeKind::Original {
+                    edge_id: edge.edge_id,
+                },
+            });
+            add_overlay_edge(&mut out_overlay, &mut in_overlay, &edges[idx], idx);
+        }
+
+        let mut contracted = vec![false; n_vertices];
+        let mut contracted_neighbors = vec![0_i64; n_vertices];
+        let mut rank = vec![0; n_vertices];
+        let mut queue: InternalPriorityQueue<VertexId, Reverse<(i64, VertexId)>> =
+            InternalPriorityQueue::default();
+        for idx in 0..n_vertices {
+            let v = VertexId(idx);
+            let p = contraction_priority(
+                v,
+                &out_overlay,
+                &in_overlay,
+                &contracted,
+                &contracted_neighbors,
+            );
+            queue.push(v, Reverse((p, v)));
+        }
+
+        let mut next_rank = 0;
+        while let Some((v, _)) = queue.pop() {
+            // lazy update: contracting neighbors may have raised this priority
+            let updated = contraction_priority(
+                v,
+                &out_overlay,
+                &in_overlay,
+                &contracted,
+                &contracted_neighbors,
+            );
+            if let Some((_, Reverse((next_priority, _)))) = queue.peek() {
+                if updated > *next_priority {
+                    queue.push(v, Reverse((updated, v)));
+                    continue;
+                }
+            }
+
+            for (src, dst, cost, first, second) in
+                find_shortcuts(v, &out_overlay, &in_overlay, &contracted)
+            {
+                let idx = edges.len();
+                edges.push(HierarchyEdge {
+                    src_vertex_id: src,
+                    dst_vertex_id: dst,
+                    cost,
+                    kind: HierarchyEdgeKind::Shortcut { first, second },
+                });
+                add_overlay_edge(&mut out_overlay, &mut in_overlay, &edges[idx], idx);
+            }
+            let neighbors = out_overlay[v.0]
+                .keys()
+                .chain(in_overlay[v.0].keys())
+                .cloned()
+                .collect::<Vec<_>>();
+            for neighbor in neighbors {
+                contracted_neighbors[neighbor.0] += 1;
+            }
+            contracted[v.0] = true;
+            rank[v.0] = next_rank;
+            next_rank += 1;
+        }
+
+        let n_shortcuts = edges
+            .iter()
+            .filter(|e| matches!(e.kind, HierarchyEdgeKind::Shortcut { .. }))
+            .count();
+        log::debug!(
+            "contracted {} vertices, adding {} shortcuts",
+            n_vertices,
+            n_shortcuts
+        );
+
+        let fingerprint = edge_cost_fingerprint(edge_costs);
+        Ok(ContractionHierarchy::from_parts(
+            rank.into_boxed_slice(),
+            edges,
+            fingerprint,
+        ))
+    }
+
+    /// reads the hierarchy from the configured cache file if it exists and was
+    /// built from the same edge costs. otherwise, contracts the graph and writes
+    /// the hierarchy to the cache file, if one is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `si` - search assets providing the graph and the edge cost models
+    /// * `config` - optional cache file
+    ///
+    /// # Returns
+    ///
+    /// The contraction hierarchy, or an error if it could not be built or cached.
+    pub fn load_or_build(
+        si: &SearchInstance,
+        config: &ContractionHierarchyConfig,
+    ) -> Result<ContractionHierarchy, ContractionHierarchyError> {
+        let edge_costs = ContractionHierarchy::edge_costs(si)?;
+        ContractionHierarchy::load_or_build_from_costs(&si.directed_graph, &edge_costs, config)
+    }
+
+    /// returns the hierarchy held by `loaded` if it was built from the edge
+    /// costs of this search instance, otherwise loads or builds the hierarchy
+    /// and keeps it in `loaded` for later queries with the same edge costs.
+    /// queries that arrive while the hierarchy is built wait for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `si` - search assets providing the graph and the edge cost models
+    /// * `config` - optional cache file
+    /// * `loaded` - the hierarchy of a previous query, if any
+    ///
+    /// # Returns
+    ///
+    /// The contraction hierarchy, or an error if it could not be built or cached.
+    pub fn load_shared(
+        si: &SearchInstance,
+        config: &ContractionHierarchyConfig,
+        loaded: &Mutex<Option<Arc<ContractionHierarchy>>>,
+    ) -> Result<Arc<ContractionHierarchy>, ContractionHierarchyError> {
+        let edge_costs = ContractionHierarchy::edge_costs(si)?;
+        let fingerprint = edge_cost_fingerprint(&edge_costs);
+        // the lock only guards a cache, so a panic while holding it leaves nothing inconsistent
+        let mut loaded = loaded.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(hierarchy) = loaded.as_ref() {
+            if hierarchy.edge_cost_fingerprint == fingerprint {
+                return Ok(hierarchy.clone());
+            }
+        }
+        let hierarchy = Arc::new(ContractionHierarchy::load_or_build_from_costs(
+            &si.directed_graph,
+            &edge_costs,
+            config,
+        )?);
+        *loaded = Some(hierarchy.clone());
+        Ok(hierarchy)
+    }
+
+    fn load_or_build_from_costs(
+        graph: &Graph,
+        edge_costs: &[f64],
+        config: &ContractionHierarchyConfig,
+    ) -> Result<ContractionHierarchy, ContractionHierarchyError> {
+        if let Some(cache_file) = &config.cache_file {
+            if cache_file.is_file() {
+                match ContractionHierarchy::read_cache(cache_file, graph, edge_costs) {
+                    Ok(hierarchy) => {
+                        log::info!("loaded contraction hierarchy from {:?}", cache_file);
+                        return Ok(hierarchy);
+                    }
+                    Err(ContractionHierarchyError::InvalidCache(path, msg)) => {
+                        log::warn!(
+                            "rebuilding contraction hierarchy, cache {:?} is stale: {}",
+                            path,
+                            msg
+                        );
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        let hierarchy = ContractionHierarchy::build(graph, edge_costs)?;
+        if let Some(cache_file) = &config.cache_file {
+            hierarchy.write_cache(cache_file)?;
+            log::info!("wrote contraction hierarchy to {:?}", cache_file);
+        }
+        Ok(hierarchy)
+    }
+
+    /// finds the least-cost route between two vertices with a bidirectional
+    /// search over the hierarchy. the cost matches a Dijkstra search over the
+    /// road network with the same edge costs.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - start of the route
+    /// * `target` - end of the route
+    ///
+    /// # Returns
+    ///
+    /// The route cost and the road network edges of the route, or an error
+    /// if no route exists.
+    pub fn route(
+        &self,
+        source: VertexId,
+        target: VertexId,
+    ) -> Result<(Cost, Vec<EdgeId>), ContractionHierarchyError> {
+        let (cost, route, _) = self.route_counting_settled(source, target)?;
+        Ok((cost, route))
+    }
+
+    /// [`ContractionHierarchy::route`], also returning the number of vertices
+    /// settled by the two searches
+    fn route_counting_settled(
+        &self,
+        source: VertexId,
+        target: VertexId,
+    ) -> Result<(Cost, Vec<EdgeId>, u64), ContractionHierarchyError> {
+        for vertex_id in [source, target] {
+            if vertex_id.0 >= self.rank.len() {
+                return Err(ContractionHierarchyError::VertexNotFound(vertex_id));
+            }
+        }
+        if source == target {
+            return Ok((Cost::ZERO, vec![], 0));
+        }
+
+        // cost and the hierarchy edge used to reach each vertex in each direction
+        let mut forward: HashMap<VertexId, (f64, Option<usize>)> =
+            HashMap::from([(source, (0.0, None))]);
+        let mut backward: HashMap<VertexId, (f64, Option<usize>)> =
+            HashMap::from([(target, (0.0, None))]);
+        let mut forward_queue: InternalPriorityQueue<VertexId, ReverseCost> =
+            InternalPriorityQueue::default();
+        let mut backward_queue: InternalPriorityQueue<VertexId, ReverseCost> =
+            InternalPriorityQueue::default();
+        forward_queue.push(source, Cost::ZERO.into());
+        backward_queue.push(target, Cost::ZERO.into());
+
+        let mut best: Option<(f64, VertexId)> = None;
+        let mut settled = 0;
+        loop {
+            let forward_min = forward_queue.peek().map(|(v, _)| forward[v].0);
+            let backward_min = backward_queue.peek().map(|(v, _)| backward[v].0);
+            let (is_forward, min_cost) = match (forward_min, backward_min) {
+                (None, None) => break,
+                (Some(f), None) => (true, f),
+                (None, Some(b)) => (false, b),
+                (Some(f), Some(b)) if f <= b => (true, f),
+                (Some(_), Some(b)) => (false, b),
+            };
+            // the cheapest unsettled vertex in either direction cannot improve the route
+            if best.is_some_and(|(best_cost, _)| min_cost >= best_cost) {
+                break;
+            }
+
+            let (queue, labels, other_labels, adjacency) = if is_forward {
+                (&mut forward_queue, &mut forward, &backward, &self.upward)
+            } else {
+                (&mut backward_queue, &mut backward, &forward, &self.downward)
+            };
+            let (vertex_id, _) = match queue.pop() {
+                Some(next) => next,
+                None => break,
+            };
+            settled += 1;
+            let cost = labels[&vertex_id].0;
+            if let Some((other_cost, _)) = other_labels.get(&vertex_id) {
+                if best.is_none_or(|(best_cost, _)| cost + other_cost < best_cost) {
+                    best = Some((cost + other_cost, vertex_id));
+                }
+            }
+            for edge_idx in adjacency[vertex_id.0].iter() {
+                let edge = &self.edges[*edge_idx];
+                let next_vertex_id = if is_forward {
+                    edge.dst_vertex_id
+                } else {
+                    edge.src_vertex_id
+                };
+                let next_cost = cost + edge.cost;
+                let improved = labels
+                    .get(&next_vertex_id)
+                    .is_none_or(|(existing, _)| next_cost < *existing);
+                if improved {
+                    labels.insert(next_vertex_id, (next_cost, Some(*edge_idx)));
+                    queue.push_increase(next_vertex_id, Cost::new(next_cost).into());
+                }
+            }
+        }
+
+        let (best_cost, meeting_vertex_id) =
+            best.ok_or(ContractionHierarchyError::NoPathExists(source, target))?;
+
+        // walk each search tree out from the vertex where the searches met
+        let mut hierarchy_route = vec![];
+        let mut vertex_id = meeting_vertex_id;
+        while let Some((_, Some(edge_idx))) = forward.get(&vertex_id) {
+            hierarchy_route.push(*edge_idx);
+            vertex_id = self.edges[*edge_idx].src_vertex_id;
+        }
+        hierarchy_route.reverse();
+        let mut vertex_id = meeting_vertex_id;
+        while let Some((_, Some(edge_idx))) = backward.get(&vertex_id) {
+            hierarchy_route.push(*edge_idx);
+            vertex_id = self.edges[*edge_idx].dst_vertex_id;
+        }
+
+        let route = hierarchy_route
+            .into_iter()
+            .flat_map(|edge_idx| self.unpack(edge_idx))
+            .collect();
+        Ok((Cost::new(best_cost), route, settled))
+    }
+
+    /// writes the hierarchy to a JSON file, along with a fingerprint of the
+    /// edge costs it was built from.
+    pub fn write_cache(&self, path: &Path) -> Result<(), ContractionHierarchyError> {
+        let io_err = |msg: String| ContractionHierarchyError::CacheIOError(path.to_path_buf(), msg);
+        let cache = ContractionHierarchyCache {
+            edge_cost_fingerprint: self.edge_cost_fingerprint,
+            rank: self.rank.to_vec(),
+            edges: self.edges.clone(),
+        };
+        let file = File::create(path).map_err(|e| io_err(e.to_string()))?;
+        serde_json::to_writer(BufWriter::new(file), &cache).map_err(|e| io_err(e.to_string()))?;
+        Ok(())
+    }
+
+    /// reads a hierarchy written by [`ContractionHierarchy::write_cache`],
+    /// confirming that it was built for this graph and these edge costs.
+    pub fn read_cache(
+        path: &Path,
+        graph: &Graph,
+        edge_costs: &[f64],
+    ) -> Result<ContractionHierarchy, ContractionHierarchyError> {
+        let invalid =
+            |msg: String| ContractionHierarchyError::InvalidCache(path.to_path_buf(), msg);
+        let file = File::open(path).map_err(|e| {
+            ContractionHierarchyError::CacheIOError(path.to_path_buf(), e.to_string())
+        })?;
+        let cache: ContractionHierarchyCache =
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| invalid(e.to_string()))?;
+
+        if cache.edge_cost_fingerprint != edge_cost_fingerprint(edge_costs) {
+            return Err(invalid(String::from(
+                "edge costs have changed since the hierarchy was built",
+            )));
+        }
+        if cache.rank.len() != graph.n_vertices() {
+            return Err(invalid(format!(
+                "expected {} vertices, found {}",
+                graph.n_vertices(),
+                cache.rank.len()
+            )));
+        }
+        for (idx, edge) in cache.edges.iter().enumerate() {
+            let valid = match edge.kind {
+                HierarchyEdgeKind::Original { edge_id } => edge_id.0 < graph.n_edges(),
+                HierarchyEdgeKind::Shortcut { first, second } => first < idx && second < idx,
+            };
+            let in_range =
+                edge.src_vertex_id.0 < cache.rank.len() && edge.dst_vertex_id.0 < cache.rank.len();
+            if !valid || !in_range {
+                return Err(invalid(format!("hierarchy edge {} is malformed", idx)));
+            }
+        }
+
+        Ok(ContractionHierarchy::from_parts(
+            cache.rank.into_boxed_slice(),
+            cache.edges,
+            cache.edge_cost_fingerprint,
+        ))
+    }
+
+    /// indexes the hierarchy edges by the direction of rank they travel
+    fn from_parts(
+        rank: Box<[usize]>,
+        edges: Vec<HierarchyEdge>,
+        edge_cost_fingerprint: u64,
+    ) -> ContractionHierarchy {
+        let mut upward = vec![vec![]; rank.len()];
+        let mut downward = vec![vec![]; rank.len()];
+        for (idx, edge) in edges.iter().enumerate() {
+            if rank[edge.src_vertex_id.0] < rank[edge.dst_vertex_id.0] {
+                upward[edge.src_vertex_id.0].push(idx);
+            } else {
+                downward[edge.dst_vertex_id.0].push(idx);
+            }
+        }
+        ContractionHierarchy {
+            rank,
+            edges,
+            upward,
+            downward,
+            edge_cost_fingerprint,
+        }
+    }
+
+    /// expands a hierarchy edge into the road network edges it represents
+    fn unpack(&self, edge_idx: usize) -> Vec<EdgeId> {
+        let mut result = vec![];
+        let mut stack = vec![edge_idx];
+        while let Some(idx) = stack.pop() {
+            match self.edges[idx].kind {
+                HierarchyEdgeKind::Original { edge_id } => result.push(edge_id),
+                HierarchyEdgeKind::Shortcut { first, second } => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// runs a search over a contraction hierarchy of the road network, loading
+/// or building the hierarchy for the edge costs of this query when the
+/// hierarchy in `loaded` does not match them. the route found over the
+/// hierarchy is traversed from the initial state to produce its edge
+/// traversals, and the tree holds only the edges of the route.
+///
+/// # Arguments
+///
+/// * `source` - start of the route
+/// * `target` - end of the route, which is required
+/// * `direction` - only forward searches are supported
+/// * `avoid_edges` - must be empty, as the hierarchy cannot skip edges
+/// * `config` - optional cache file for the hierarchy
+/// * `loaded` - the hierarchy of a previous query, if any
+/// * `si` - the search instance
+///
+/// # Returns
+///
+/// The route and its tree, or an error if no route exists or the query
+/// cannot be answered with a contraction hierarchy.
+pub fn run(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    config: &ContractionHierarchyConfig,
+    loaded: &Mutex<Option<Arc<ContractionHierarchy>>>,
+    si: &SearchInstance,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    let target = target.ok_or_else(|| {
+        SearchError::BuildError(String::from(
+            "contraction hierarchy search requires a destination",
+        ))
+    })?;
+    if matches!(direction, Direction::Reverse) {
+        return Err(SearchError::BuildError(String::from(
+            "contraction hierarchy search only runs in the forward direction",
+        )));
+    }
+    if avoid_edges.is_some_and(|avoid| !avoid.is_empty()) {
+        return Err(SearchError::BuildError(String::from(
+            "contraction hierarchy search cannot avoid edges",
+        )));
+    }
+
+    let start_time = Instant::now();
+    let hierarchy = ContractionHierarchy::load_shared(si, config, loaded)
+        .map_err(|e| SearchError::BuildError(e.to_string()))?;
+    let (edge_ids, settled) = match hierarchy.route_counting_settled(source, target) {
+        Ok((_, edge_ids, settled)) => (edge_ids, settled),
+        Err(ContractionHierarchyError::NoPathExists(src, dst)) => {
+            let metadata = SearchMetadata {
+                runtime: start_time.elapsed(),
+                ..Default::default()
+            };
+            return Err(SearchError::NoPathExists(src, dst, metadata));
+        }
+        Err(e) => return Err(SearchError::BuildError(e.to_string())),
+    };
+
+    let initial_state = si.state_model.initial_state()?;
+    let mut route: Vec<EdgeTraversal> = vec![];
+    let mut tree = HashMap::new();
+    for edge_id in edge_ids {
+        let prev = route.last();
+        let traversal = EdgeTraversal::forward_traversal(
+            edge_id,
+            prev.map(|et| et.edge_id),
+            prev.map_or(&initial_state, |et| &et.result_state),
+            si,
+        )?;
+        let edge = si.directed_graph.get_edge(edge_id)?;
+        tree.insert(
+            edge.dst_vertex_id,
+            SearchTreeBranch {
+                terminal_vertex: edge.src_vertex_id,
+                edge_traversal: traversal.clone(),
+            },
+        );
+        route.push(traversal);
+    }
+    Ok(SearchAlgorithmResult {
+        trees: vec![tree],
+        routes: vec![route],
+        iterations: settled,
+        metadata: SearchMetadata {
+            vertices_settled: settled,
+            runtime: start_time.elapsed(),
+            ..Default::default()
+        },
+    })
+}
+
+/// the edge difference of contracting a vertex, the number of shortcuts added
+/// less the number of edges removed, plus its count of contracted neighbors.
+/// vertices with the lowest priority are contracted first.
+fn contraction_priority(
+    v: VertexId,
+    out_overlay: &Overlay,
+    in_overlay: &Overlay,
+    contracted: &[bool],
+    contracted_neighbors: &[i64],
+) -> i64 {
+    let shortcuts = find_shortcuts(v, out_overlay, in_overlay, contracted).len() as i64;
+    let removed = out_overlay[v.0]
+        .keys()
+        .chain(in_overlay[v.0].keys())
+        .filter(|n| !contracted[n.0])
+        .count() as i64;
+    shortcuts - removed + contracted_neighbors[v.0]
+}
+
+/// records a hierarchy edge in the overlay if it is the cheapest edge so far
+/// between its vertices
+fn add_overlay_edge(
+    out_overlay: &mut Overlay,
+    in_overlay: &mut Overlay,
+    edge: &HierarchyEdge,
+    idx: usize,
+) {
+    let (src, dst) = (edge.src_vertex_id, edge.dst_vertex_id);
+    let cheaper = out_overlay[src.0]
+        .get(&dst)
+        .is_none_or(|(existing, _)| edge.cost < *existing);
+    if cheaper {
+        out_overlay[src.0].insert(dst, (edge.cost, idx));
+        in_overlay[dst.0].insert(src, (edge.cost, idx));
+    }
+}
+
+/// finds the shortcuts needed to contract a vertex. for each pair of
+/// remaining neighbors `u -> v -> x`, a shortcut is needed unless a witness
+/// search from `u` that avoids `v` finds a path to `x` costing no more.
+///
+/// # Returns
+///
+/// Each shortcut as its source, destination, cost, and the indices of the
+/// two hierarchy edges it joins.
+fn find_shortcuts(
+    v: VertexId,
+    out_overlay: &Overlay,
+    in_overlay: &Overlay,
+    contracted: &[bool],
+) -> Vec<(VertexId, VertexId, f64, usize, usize)> {
+    let mut shortcuts = vec![];
+    let outgoing = out_overlay[v.0]
+        .iter()
+        .filter(|(x, _)| !contracted[x.0])
+        .collect::<Vec<_>>();
+    if outgoing.is_empty() {
+        return shortcuts;
+    }
+    for (u, (in_cost, in_idx)) in in_overlay[v.0].iter() {
+        if contracted[u.0] {
+            continue;
+        }
+        let max_cost = outgoing
+            .iter()
+            .filter(|(x, _)| *x != u)
+            .map(|(_, (out_cost, _))| in_cost + out_cost)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if max_cost == f64::NEG_INFINITY {
+            continue;
+        }
+        let witness = witness_costs(*u, v, max_cost, out_overlay, contracted);
+        for (x, (out_cost, out_idx)) in outgoing.iter() {
+            if *x == u {
+                continue;
+            }
+            let via_cost = in_cost + out_cost;
+            let has_witness = witness
+                .get(x)
+                .is_some_and(|witness_cost| *witness_cost <= via_cost);
+            if !has_witness {
+                shortcuts.push((*u, **x, via_cost, *in_idx, *out_idx));
+            }
+        }
+    }
+    shortcuts
+}
+
+/// runs a bounded Dijkstra search from `source` over the remaining vertices,
+/// skipping `excluded`. every cost in the result is the cost of a real path.
+fn witness_costs(
+    source: VertexId,
+    excluded: VertexId,
+    max_cost: f64,
+    out_overlay: &Overlay,
+    contracted: &[bool],
+) -> HashMap<VertexId, f64> {
+    let mut costs: HashMap<VertexId, f64> = HashMap::from([(source, 0.0)]);
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    queue.push(source, Cost::ZERO.into());
+    let mut settled = 0;
+    while let Some((vertex_id, _)) = queue.pop() {
+        let cost = costs[&vertex_id];
+        if cost > max_cost || settled >= WITNESS_SETTLE_LIMIT {
+            break;
+        }
+        settled += 1;
+        for (next_vertex_id, (edge_cost, _)) in out_overlay[vertex_id.0].iter() {
+            if *next_vertex_id == excluded || contracted[next_vertex_id.0] {
+                continue;
+            }
+            let next_cost = cost + edge_cost;
+            if costs.get(next_vertex_id).is_none_or(|c| next_cost < *c) {
+                costs.insert(*next_vertex_id, next_cost);
+                queue.push_increase(*next_vertex_id, Cost::new(next_cost).into());
+            }
+        }
+    }
+    costs
+}
+
+/// a fingerprint of the edge costs, used to detect a stale cache
+fn edge_cost_fingerprint(edge_costs: &[f64]) -> u64 {
+    fingerprint(
+        std::iter::once(edge_costs.len() as u64).chain(edge_costs.iter().map(|c| c.to_bits())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::algorithm::search::search_algorithm::SearchAlgorithm;
+    use crate::model::property::edge::Edge;
+    use crate::model::property::vertex::Vertex;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::test_util::graph_fixture::graph_from_edges;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const GRID_SIZE: usize = 15;
+
+    /// a grid with random edge lengths between 50 and 550 meters where about
+    /// one link in five is one-way, so some vertex pairs are unreachable
+    fn build_random_grid_graph(seed: u64) -> Graph {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vertices = (0..GRID_SIZE * GRID_SIZE)
+            .map(|i| Vertex::new(i, 0.0, 0.0))
+            .collect::<Vec<_>>();
+        let mut links = vec![];
+        for row in 0..GRID_SIZE {
+            for col in 0..GRID_SIZE {
+                let v = row * GRID_SIZE + col;
+                if col + 1 < GRID_SIZE {
+                    links.push((v, v + 1));
+                }
+                if row + 1 < GRID_SIZE {
+                    links.push((v, v + GRID_SIZE));
+                }
+            }
+        }
+        let mut pairs = vec![];
+        for (a, b) in links {
+            match rng.gen_range(0..10) {
+                0 => pairs.push((a, b)),
+                1 => pairs.push((b, a)),
+                _ => pairs.extend([(a, b), (b, a)]),
+            }
+        }
+        let edges = pairs
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (src, dst))| Edge::new(idx, src, dst, rng.gen_range(50.0..550.0)))
+            .collect::<Vec<_>>();
+        graph_from_edges(vertices, edges)
+    }
+
+    fn build_search_instance(graph: Graph) -> SearchInstance {
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 100_000 })
+            .build()
+    }
+
+    #[test]
+    fn test_routes_match_dijkstra() {
+        let si = build_search_instance(build_random_grid_graph(42));
+        let edge_costs = ContractionHierarchy::edge_costs(&si).unwrap();
+        let hierarchy = ContractionHierarchy::build(&si.directed_graph, &edge_costs).unwrap();
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let n_vertices = si.directed_graph.n_vertices();
+        let mut n_routes = 0;
+        for _ in 0..300 {
+            let src = VertexId(rng.gen_range(0..n_vertices));
+            let dst = VertexId(rng.gen_range(0..n_vertices));
+            let dijkstra = run_a_star(
+                src,
+                Some(dst),
+                &Direction::Forward,
+                Some(Cost::ZERO),
+                None,
+                &si,
+            )
+            .and_then(|result| vertex_oriented_route(src, dst, &result.tree));
+            let ch = hierarchy.route(src, dst);
+            match (dijkstra, ch) {
+                (Ok(route), Ok((cost, edge_ids))) => {
+                    let dijkstra_cost =
+                        route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+                    assert!(
+                        (cost.as_f64() - dijkstra_cost).abs() < 1e-6,
+                        "route {} -> {} costs {} with dijkstra but {} with the hierarchy",
+                        src,
+                        dst,
+                        dijkstra_cost,
+                        cost
+                    );
+                    // the unpacked route is a connected path with the same cost
+                    let mut vertex_id = src;
+                    let mut unpacked_cost = 0.0;
+                    for edge_id in edge_ids.iter() {
+                        let edge = si.directed_graph.get_edge(*edge_id).unwrap();
+                        assert_eq!(edge.src_vertex_id, vertex_id);
+                        vertex_id = edge.dst_vertex_id;
+                        unpacked_cost += edge_costs[edge_id.0];
+                    }
+                    assert_eq!(vertex_id, dst);
+                    assert!((unpacked_cost - dijkstra_cost).abs() < 1e-6);
+                    n_routes += 1;
+                }
+                (
+                    Err(SearchError::NoPathExists(..)),
+                    Err(ContractionHierarchyError::NoPathExists(_, _)),
+                ) => {}
+                (dijkstra, ch) => panic!(
+                    "route {} -> {} disagrees: dijkstra {:?}, hierarchy {:?}",
+                    src,
+                    dst,
+                    dijkstra.map(|r| r.len()),
+                    ch
+                ),
+            }
+        }
+        assert!(n_routes > 200, "only {} queries found a route", n_routes);
+    }
+
+    #[test]
+    fn test_cache_is_invalidated_when_costs_change() {
+        let si = build_search_instance(build_random_grid_graph(3));
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_file = cache_dir.path().join("contraction_hierarchy.json");
+        let config = ContractionHierarchyConfig {
+            cache_file: Some(cache_file.clone()),
+        };
+        let built = ContractionHierarchy::load_or_build(&si, &config).unwrap();
+        assert!(cache_file.is_file());
+        let cached = ContractionHierarchy::load_or_build(&si, &config).unwrap();
+        let last = VertexId(GRID_SIZE * GRID_SIZE - 1);
+        assert_eq!(
+            built.route(VertexId(0), last).unwrap(),
+            cached.route(VertexId(0), last).unwrap()
+        );
+
+        // a change to any edge cost, such as a new speed, makes the cache stale
+        let mut edge_costs = ContractionHierarchy::edge_costs(&si).unwrap();
+        edge_costs[0] *= 2.0;
+        let stale = ContractionHierarchy::read_cache(&cache_file, &si.directed_graph, &edge_costs);
+        assert!(matches!(
+            stale,
+            Err(ContractionHierarchyError::InvalidCache(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_search_algorithm_reuses_hierarchy() {
+        let si = build_search_instance(build_random_grid_graph(11));
+        let algorithm: SearchAlgorithm =
+            serde_json::from_value(serde_json::json!({ "type": "contraction_hierarchy" })).unwrap();
+        let last = VertexId(GRID_SIZE * GRID_SIZE - 1);
+        let dijkstra = SearchAlgorithm::Dijkstra
+            .run_vertex_oriented(VertexId(0), Some(last), &Direction::Forward, None, &si)
+            .unwrap();
+        let result = algorithm
+            .run_vertex_oriented(VertexId(0), Some(last), &Direction::Forward, None, &si)
+            .unwrap();
+        let route_cost =
+            |route: &[EdgeTraversal]| route.iter().map(|et| et.total_cost().as_f64()).sum::<f64>();
+        assert!((route_cost(&result.routes[0]) - route_cost(&dijkstra.routes[0])).abs() < 1e-6);
+        assert_eq!(
+            vertex_oriented_route(VertexId(0), last, &result.trees[0])
+                .unwrap()
+                .len(),
+            result.routes[0].len()
+        );
+        assert!(result.metadata.vertices_settled > 0);
+
+        // a second query reuses the hierarchy built by the first
+        let loaded = match &algorithm {
+            SearchAlgorithm::ContractionHierarchy { loaded, .. } => {
+                loaded.lock().unwrap().clone().unwrap()
+            }
+            _ => panic!("expected a contraction hierarchy"),
+        };
+        algorithm
+            .run_vertex_oriented(last, Some(VertexId(0)), &Direction::Forward, None, &si)
+            .unwrap();
+        match &algorithm {
+            SearchAlgorithm::ContractionHierarchy { loaded: next, .. } => {
+                assert!(Arc::ptr_eq(&loaded, next.lock().unwrap().as_ref().unwrap()))
+            }
+            _ => panic!("expected a contraction hierarchy"),
+        }
+    }
+}
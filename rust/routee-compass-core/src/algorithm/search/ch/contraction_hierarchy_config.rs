@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// configures contraction hierarchy preprocessing.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ContractionHierarchyConfig {
+    /// optional JSON file used to cache the hierarchy between runs. the file
+    /// is read if it exists and was built from the same edge costs, otherwise
+    /// the hierarchy is contracted and written to this location.
+    pub cache_file: Option<PathBuf>,
+}
@@ -0,0 +1,31 @@
+use crate::algorithm::search::search_error::SearchError;
+use crate::model::road_network::{edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId};
+use std::path::PathBuf;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ContractionHierarchyError {
+    #[error("cannot contract an empty graph")]
+    EmptyGraph,
+    #[error("expected a cost for each of the {0} edges in the graph, found {1}")]
+    EdgeCostCountMismatch(usize, usize),
+    #[error("edge {0} has cost {1}, contraction requires non-negative edge costs")]
+    NegativeEdgeCost(EdgeId, f64),
+    #[error("vertex {0} is not in the contraction hierarchy")]
+    VertexNotFound(VertexId),
+    #[error("no path exists between vertices {0} and {1}")]
+    NoPathExists(VertexId, VertexId),
+    #[error(transparent)]
+    GraphError(#[from] GraphError),
+    #[error("failure computing edge costs: {0}")]
+    EdgeCostError(String),
+    #[error("failure reading or writing contraction hierarchy cache file {0}: {1}")]
+    CacheIOError(PathBuf, String),
+    #[error("contraction hierarchy cache file {0} does not match these edge costs: {1}")]
+    InvalidCache(PathBuf, String),
+}
+
+impl From<SearchError> for ContractionHierarchyError {
+    fn from(value: SearchError) -> Self {
+        ContractionHierarchyError::EdgeCostError(value.to_string())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod contraction_hierarchy;
+pub mod contraction_hierarchy_config;
+pub mod contraction_hierarchy_error;
@@ -0,0 +1,74 @@
+use super::{search_error::SearchError, search_instance::SearchInstance};
+use crate::model::{
+    road_network::vertex_id::VertexId, traversal::state::state_variable::StateVar, unit::Cost,
+};
+
+/// estimates the cost remaining from a vertex to the destination of a search,
+/// which guides A* toward the destination. an estimate that never exceeds the
+/// true remaining cost keeps the routes found by A* optimal.
+pub trait CostEstimateFunction: Send + Sync {
+    /// estimates the cost of reaching `dst` from `src`.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - vertex the estimate begins at
+    /// * `dst` - destination of the search
+    /// * `state` - search state at `src`
+    /// * `si` - the search instance, providing the graph and the models of the query
+    ///
+    /// # Returns
+    ///
+    /// the estimated cost, or an error if a vertex is missing or a model fails
+    fn estimate_cost(
+        &self,
+        src: VertexId,
+        dst: VertexId,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError>;
+}
+
+/// the default estimate, which asks the traversal model to approximate the
+/// state change between the two vertices and costs the result. traversal
+/// models approximate from the straight-line haversine distance, see
+/// [`super::alt::landmark_cost_estimate::LandmarkCostEstimate`] for a tighter
+/// estimate from ALT landmarks.
+pub struct TraversalModelCostEstimate;
+
+impl CostEstimateFunction for TraversalModelCostEstimate {
+    fn estimate_cost(
+        &self,
+        src: VertexId,
+        dst: VertexId,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        let src_vertex = si.directed_graph.get_vertex(src)?;
+        let dst_vertex = si.directed_graph.get_vertex(dst)?;
+        let mut dst_state = state.to_vec();
+
+        si.traversal_model.estimate_traversal(
+            (src_vertex, dst_vertex),
+            &mut dst_state,
+            &si.state_model,
+        )?;
+        let cost_estimate = si.cost_model.cost_estimate(state, &dst_state)?;
+        Ok(cost_estimate)
+    }
+}
+
+/// estimates zero remaining cost everywhere, which makes A* explore like
+/// Dijkstra's algorithm. always admissible, for models whose estimate is not.
+pub struct ZeroCostEstimate;
+
+impl CostEstimateFunction for ZeroCostEstimate {
+    fn estimate_cost(
+        &self,
+        _src: VertexId,
+        _dst: VertexId,
+        _state: &[StateVar],
+        _si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        Ok(Cost::ZERO)
+    }
+}
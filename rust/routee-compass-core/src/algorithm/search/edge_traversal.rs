@@ -1,9 +1,11 @@
 use super::search_error::SearchError;
 use super::search_instance::SearchInstance;
+use crate::model::property::{edge::Edge, vertex::Vertex};
 use crate::model::road_network::edge_id::EdgeId;
 use crate::model::traversal::state::state_variable::StateVar;
 use crate::model::traversal::state::traversal_state::TraversalState;
-use crate::model::unit::Cost;
+use crate::model::unit::as_f64::AsF64;
+use crate::model::unit::{Cost, Distance};
 use allocative::Allocative;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -54,14 +56,82 @@ impl EdgeTraversal {
         prev_state: &[StateVar],
         si: &SearchInstance,
     ) -> Result<EdgeTraversal, SearchError> {
-        let mut result_state = prev_state.to_vec();
-        let mut access_cost = Cost::ZERO;
-
         // find this traversal in the graph
         let traversal_trajectory = si
             .directed_graph
             .edge_triplet_attrs(next_edge_id)
             .map_err(SearchError::GraphError)?;
+        EdgeTraversal::forward_traversal_of(
+            traversal_trajectory,
+            prev_edge_id_opt,
+            None,
+            prev_state,
+            si,
+        )
+    }
+
+    /// traverses only a fraction of an edge, such as the remainder of an
+    /// origin edge after a query offset or the leading part of a destination
+    /// edge. the traversal models see a copy of the edge whose distance is
+    /// scaled by the fraction, so distance and any quantity derived from it,
+    /// such as time at the edge speed, are charged proportionally. the copy
+    /// keeps the edge id of the full edge, so the traversal model is called
+    /// through [`crate::model::traversal::traversal_model::TraversalModel::traverse_partial_edge`], which keeps partial
+    /// traversals out of any cache of full edge traversals.
+    ///
+    /// # Arguments
+    ///
+    /// * `next_edge_id`     - the edge to partially traverse
+    /// * `prev_edge_id_opt` - the previously traversed edge, if exists, for access costs
+    /// * `fraction`         - the portion of the edge to traverse, in the range [0, 1]
+    /// * `prev_state`       - the state before traversal
+    /// * `si`               - the search assets for this query
+    ///
+    /// # Returns
+    ///
+    /// An edge traversal summarizing the costs and result state of traversing part of the next edge.
+    pub fn partial_forward_traversal(
+        next_edge_id: EdgeId,
+        prev_edge_id_opt: Option<EdgeId>,
+        fraction: f64,
+        prev_state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<EdgeTraversal, SearchError> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(SearchError::BuildError(format!(
+                "edge fraction must be in the range [0, 1], found {}",
+                fraction
+            )));
+        }
+        let (v2, e2, v3) = si
+            .directed_graph
+            .edge_triplet_attrs(next_edge_id)
+            .map_err(SearchError::GraphError)?;
+        let partial_edge = Edge {
+            distance: Distance::new(e2.distance.as_f64() * fraction),
+            ..*e2
+        };
+        EdgeTraversal::forward_traversal_of(
+            (v2, &partial_edge, v3),
+            prev_edge_id_opt,
+            Some(fraction),
+            prev_state,
+            si,
+        )
+    }
+
+    /// forward traversal of an edge given its trajectory in the graph, where
+    /// `fraction` is set when only part of the edge is traversed
+    fn forward_traversal_of(
+        traversal_trajectory: (&Vertex, &Edge, &Vertex),
+        prev_edge_id_opt: Option<EdgeId>,
+        fraction: Option<f64>,
+        prev_state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<EdgeTraversal, SearchError> {
+        let mut result_state = prev_state.to_vec();
+        let mut access_cost = Cost::ZERO;
+        let next_edge_id = traversal_trajectory.1.edge_id;
 
         // perform access traversal for (v2)-[next]->(v3)
         // access cost for (v1)-[prev]->(v2)-[next]->(v3)
@@ -88,16 +158,29 @@ impl EdgeTraversal {
             access_cost = access_cost + ac;
         }
 
-        si.traversal_model
-            .traverse_edge(traversal_trajectory, &mut result_state, &si.state_model)
-            .map_err(SearchError::TraversalModelFailure)?;
+        // the traversal cost covers only the state change of the edge itself,
+        // so a road class factor never scales the access cost
+        let access_state = result_state.clone();
+        match fraction {
+            None => si.traversal_model.traverse_edge(
+                traversal_trajectory,
+                &mut result_state,
+                &si.state_model,
+            ),
+            Some(fraction) => si.traversal_model.traverse_partial_edge(
+                traversal_trajectory,
+                fraction,
+                &mut result_state,
+                &si.state_model,
+            ),
+        }
+        .map_err(SearchError::TraversalModelFailure)?;
 
         let (_, edge, _) = traversal_trajectory;
-        let total_cost = si
+        let traversal_cost = si
             .cost_model
-            .traversal_cost(edge, prev_state, &result_state)
+            .traversal_cost(edge, &access_state, &result_state)
             .map_err(SearchError::CostError)?;
-        let traversal_cost = total_cost - access_cost;
 
         let result = EdgeTraversal {
             edge_id: next_edge_id,
@@ -167,16 +250,16 @@ impl EdgeTraversal {
             access_cost = access_cost + ac;
         }
 
+        let access_state = result_state.clone();
         si.traversal_model
             .traverse_edge(traversal_trajectory, &mut result_state, &si.state_model)
             .map_err(SearchError::TraversalModelFailure)?;
 
         let (_, edge, _) = traversal_trajectory;
-        let total_cost = si
+        let traversal_cost = si
             .cost_model
-            .traversal_cost(edge, prev_state, &result_state)
+            .traversal_cost(edge, &access_state, &result_state)
             .map_err(SearchError::CostError)?;
-        let traversal_cost = total_cost - access_cost;
 
         let result = EdgeTraversal {
             edge_id: prev_edge_id,
@@ -188,3 +271,67 @@ impl EdgeTraversal {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::access::access_model::AccessModel;
+    use crate::model::access::access_model_error::AccessModelError;
+    use crate::model::cost::road_class_bias::RoadClassBias;
+    use crate::model::state::{state_feature::StateFeature, state_model::StateModel};
+    use crate::model::unit::DistanceUnit;
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::{
+        distance_cost_model, distance_state_model, SearchInstanceBuilder,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// adds 10 meters of distance to access any edge, such as a detour to turn
+    struct DetourAccessModel;
+
+    impl AccessModel for DetourAccessModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+
+        fn access_edge(
+            &self,
+            _traversal: (&Vertex, &Edge, &Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), AccessModelError> {
+            state_model.add_distance(
+                state,
+                &"distance".into(),
+                &Distance::new(10.0),
+                &DistanceUnit::Meters,
+            )?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_road_class_factor_does_not_scale_access_cost() {
+        let graph = graph_from_links(3, &[(0, 1, 100.0), (1, 2, 100.0)]);
+        let state_model = distance_state_model();
+        let mut cost_model = distance_cost_model(state_model.clone());
+        // the second edge is of a road class whose cost is doubled
+        let road_classes = Arc::new(vec![0u8, 1].into_boxed_slice());
+        let bias = RoadClassBias::new(road_classes, HashMap::from([(1, 2.0)])).unwrap();
+        cost_model.road_class_bias = Some(bias);
+        let si = SearchInstanceBuilder::new(graph)
+            .state_model(state_model.clone())
+            .cost_model(cost_model)
+            .access_model(Arc::new(DetourAccessModel))
+            .build();
+
+        let initial = state_model.initial_state().unwrap();
+        let et =
+            EdgeTraversal::forward_traversal(EdgeId(1), Some(EdgeId(0)), &initial, &si).unwrap();
+        assert_eq!(et.access_cost, Cost::new(10.0));
+        assert_eq!(et.traversal_cost, Cost::new(200.0));
+        // the traversal state holds the distance of both, unscaled
+        assert_eq!(et.result_state[0].0, 110.0);
+    }
+}
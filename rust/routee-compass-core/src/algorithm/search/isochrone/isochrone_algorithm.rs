@@ -0,0 +1,371 @@
+use super::isochrone_result::{IsochroneResult, ReachableEdge, ReachableVertex};
+use crate::algorithm::search::a_star::a_star_algorithm::run_a_star_with_cost;
+use crate::algorithm::search::branch_cost::ScalarBranchCost;
+use crate::algorithm::search::direction::Direction;
+use crate::algorithm::search::search_error::SearchError;
+use crate::algorithm::search::search_instance::SearchInstance;
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::unit::as_f64::AsF64;
+use crate::model::unit::cost::ReverseCost;
+use crate::model::unit::Cost;
+use crate::util::priority_queue::InternalPriorityQueue;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// runs a Dijkstra expansion from the source vertex, collecting every vertex
+/// whose accumulated cost is within the budget. edge costs come from the
+/// traversal, access and cost models of the search instance, and edges
+/// rejected by the frontier model are not expanded.
+///
+/// a forward isochrone finds every vertex reachable from the source. a reverse
+/// isochrone expands over incoming edges, traversing each edge backward at the
+/// same cost, and finds every vertex that can reach the source, such as the
+/// catchment area of a facility. on a directed graph these generally differ.
+///
+/// # Arguments
+///
+/// * `source` - the origin of a forward isochrone or the destination of a reverse isochrone
+/// * `budget` - the maximum accumulated cost of any reachable vertex
+/// * `direction` - whether to expand over outgoing or incoming edges
+/// * `si` - the search assets for this query
+///
+/// # Returns
+///
+/// The reachable vertices and the tree edges used to reach them, plus any
+/// edges leaving a reachable vertex that cross the budget boundary, recorded
+/// with the fraction of the edge that can be reached.
+pub fn run_isochrone(
+    source: VertexId,
+    budget: Cost,
+    direction: &Direction,
+    si: &SearchInstance,
+) -> Result<IsochroneResult, SearchError> {
+    if budget < Cost::ZERO {
+        return Err(SearchError::BuildError(format!(
+            "isochrone budget must be non-negative, found {}",
+            budget
+        )));
+    }
+
+    let mut queue: InternalPriorityQueue<VertexId, ReverseCost> = InternalPriorityQueue::default();
+    let mut costs: HashMap<VertexId, Cost> = HashMap::from([(source, Cost::ZERO)]);
+    let mut states: HashMap<VertexId, Vec<StateVar>> =
+        HashMap::from([(source, si.state_model.initial_state()?)]);
+    let mut tree_edges: HashMap<VertexId, (EdgeId, VertexId)> = HashMap::new();
+    let mut settled: HashSet<VertexId> = HashSet::new();
+    let mut boundary_edges: Vec<ReachableEdge> = vec![];
+    queue.push(source, Cost::ZERO.into());
+
+    let start_time = Instant::now();
+    let mut iterations: u64 = 0;
+    while let Some((vertex_id, _)) = queue.pop() {
+        si.termination_model
+            .test(&start_time, settled.len(), iterations)?;
+        settled.insert(vertex_id);
+
+        let cost = *costs.get(&vertex_id).ok_or_else(|| {
+            SearchError::InternalSearchError(format!(
+                "expected vertex id {} missing from isochrone costs",
+                vertex_id
+            ))
+        })?;
+        let state = states.remove(&vertex_id).ok_or_else(|| {
+            SearchError::InternalSearchError(format!(
+                "expected vertex id {} missing from isochrone states",
+                vertex_id
+            ))
+        })?;
+        let last_edge_id = tree_edges.get(&vertex_id).map(|(edge_id, _)| *edge_id);
+
+        for successor in si.successors(vertex_id, last_edge_id, &state, direction)? {
+            let (edge_id, next_vertex_id, et) = successor?;
+            let edge_cost = et.total_cost();
+            let next_cost = cost + edge_cost;
+            if next_cost > budget {
+                // only part of this edge is reachable before the budget runs out
+                let fraction = (budget - cost).as_f64() / edge_cost.as_f64();
+                let (src_vertex_id, dst_vertex_id) =
+                    edge_endpoints(direction, vertex_id, next_vertex_id);
+                boundary_edges.push(ReachableEdge {
+                    edge_id,
+                    src_vertex_id,
+                    dst_vertex_id,
+                    start_cost: cost,
+                    end_cost: next_cost,
+                    fraction,
+                });
+                continue;
+            }
+            if settled.contains(&next_vertex_id) {
+                continue;
+            }
+            let existing_cost = costs.get(&next_vertex_id).unwrap_or(&Cost::INFINITY);
+            if next_cost < *existing_cost {
+                costs.insert(next_vertex_id, next_cost);
+                states.insert(next_vertex_id, et.result_state);
+                tree_edges.insert(next_vertex_id, (edge_id, vertex_id));
+                queue.push_increase(next_vertex_id, next_cost.into());
+            }
+        }
+        iterations += 1;
+    }
+    log::debug!(
+        "isochrone settled {} vertices in {} iterations",
+        settled.len(),
+        iterations
+    );
+
+    let mut vertices = costs
+        .iter()
+        .map(|(vertex_id, cost)| ReachableVertex {
+            vertex_id: *vertex_id,
+            cost: *cost,
+        })
+        .collect::<Vec<_>>();
+    vertices.sort_by(|a, b| a.cost.cmp(&b.cost).then(a.vertex_id.0.cmp(&b.vertex_id.0)));
+
+    let mut edges = tree_edges
+        .iter()
+        .map(|(child_vertex_id, (edge_id, parent_vertex_id))| {
+            let (src_vertex_id, dst_vertex_id) =
+                edge_endpoints(direction, *parent_vertex_id, *child_vertex_id);
+            ReachableEdge {
+                edge_id: *edge_id,
+                src_vertex_id,
+                dst_vertex_id,
+                start_cost: costs.get(parent_vertex_id).cloned().unwrap_or(Cost::ZERO),
+                end_cost: costs.get(child_vertex_id).cloned().unwrap_or(Cost::ZERO),
+                fraction: 1.0,
+            }
+        })
+        .collect::<Vec<_>>();
+    edges.extend(boundary_edges);
+    edges.sort_by_key(|e| e.edge_id.0);
+
+    Ok(IsochroneResult {
+        budget,
+        vertices,
+        edges,
+    })
+}
+
+/// answers whether the target can be reached from the source within the
+/// budget, without building a route. runs [`run_a_star_with_cost`] bounded
+/// by the budget, which returns as soon as the target is settled and fails
+/// with [`SearchError::NoPathExists`] once no branch within the budget
+/// remains. the route is never backtracked, which makes this suited to
+/// filtering large sets of candidate trips.
+///
+/// # Arguments
+///
+/// * `source` - the origin of the trip
+/// * `target` - the destination of the trip
+/// * `budget` - the maximum accumulated cost of a feasible trip, inclusive
+/// * `si` - the search assets for this query
+///
+/// # Returns
+///
+/// true if the target is reachable within the budget
+pub fn is_reachable(
+    source: VertexId,
+    target: VertexId,
+    budget: Cost,
+    si: &SearchInstance,
+) -> Result<bool, SearchError> {
+    if budget < Cost::ZERO {
+        return Err(SearchError::BuildError(format!(
+            "reachability budget must be non-negative, found {}",
+            budget
+        )));
+    }
+    let mut branch_cost = ScalarBranchCost {
+        weight_factor: None,
+        max_cost: Some(budget),
+    };
+    let result = run_a_star_with_cost(
+        source,
+        Some(target),
+        &Direction::Forward,
+        None,
+        None,
+        &mut branch_cost,
+        si,
+    );
+    match result {
+        Ok(_) => Ok(true),
+        Err(SearchError::NoPathExists(..)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// orders the vertices of an expanded edge by the edge's own direction. a
+/// reverse search expands an edge from its destination to its source.
+fn edge_endpoints(
+    direction: &Direction,
+    expanded_vertex_id: VertexId,
+    next_vertex_id: VertexId,
+) -> (VertexId, VertexId) {
+    match direction {
+        Direction::Forward => (expanded_vertex_id, next_vertex_id),
+        Direction::Reverse => (next_vertex_id, expanded_vertex_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::graph::Graph;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+
+    /// a line graph (0) -[0]-> (1) -[1]-> (2) -[2]-> (3) with a branch
+    /// (0) -[3]-> (4). distances are in meters.
+    fn build_line_graph() -> Graph {
+        graph_from_links(5, &[(0, 1, 5.0), (1, 2, 4.0), (2, 3, 10.0), (0, 4, 20.0)])
+    }
+
+    fn build_search_instance(graph: Graph) -> SearchInstance {
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build()
+    }
+
+    #[test]
+    fn test_isochrone_with_straddling_edge() {
+        // with a budget of 12, vertices 0, 1 and 2 are reachable at 0, 5 and 9.
+        // edge 2 starts at cost 9 and ends at cost 19, so 3 of its 10 units
+        // are within budget. edge 3 from the origin is 12/20 reachable.
+        let si = build_search_instance(build_line_graph());
+        let result = run_isochrone(VertexId(0), Cost::new(12.0), &Direction::Forward, &si).unwrap();
+
+        let vertices = result
+            .vertices
+            .iter()
+            .map(|v| (v.vertex_id, v.cost))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vertices,
+            vec![
+                (VertexId(0), Cost::new(0.0)),
+                (VertexId(1), Cost::new(5.0)),
+                (VertexId(2), Cost::new(9.0)),
+            ]
+        );
+
+        let edges = result
+            .edges
+            .iter()
+            .map(|e| (e.edge_id, e.is_partial()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                (EdgeId(0), false),
+                (EdgeId(1), false),
+                (EdgeId(2), true),
+                (EdgeId(3), true)
+            ]
+        );
+        let straddling = &result.edges[2];
+        assert_eq!(straddling.start_cost, Cost::new(9.0));
+        assert_eq!(straddling.end_cost, Cost::new(19.0));
+        assert!((straddling.fraction - 0.3).abs() < 1e-9);
+        assert!((result.edges[3].fraction - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_isochrone_budget_is_inclusive() {
+        // a vertex whose accumulated cost equals the budget is reachable
+        let si = build_search_instance(build_line_graph());
+        let result = run_isochrone(VertexId(0), Cost::new(9.0), &Direction::Forward, &si).unwrap();
+        let reachable = result
+            .vertices
+            .iter()
+            .map(|v| v.vertex_id)
+            .collect::<Vec<_>>();
+        assert_eq!(reachable, vec![VertexId(0), VertexId(1), VertexId(2)]);
+    }
+
+    #[test]
+    fn test_reachable_within_budget() {
+        let si = build_search_instance(build_line_graph());
+        // vertex 2 is 9 meters from the origin, and the budget is inclusive
+        assert!(is_reachable(VertexId(0), VertexId(2), Cost::new(9.0), &si).unwrap());
+        // vertex 3 is 19 meters from the origin
+        assert!(!is_reachable(VertexId(0), VertexId(3), Cost::new(12.0), &si).unwrap());
+        // the line graph is one-way
+        assert!(!is_reachable(VertexId(2), VertexId(0), Cost::new(100.0), &si).unwrap());
+        assert!(is_reachable(VertexId(1), VertexId(1), Cost::ZERO, &si).unwrap());
+    }
+
+    #[test]
+    fn test_isochrone_serializes() {
+        let si = build_search_instance(build_line_graph());
+        let result = run_isochrone(VertexId(0), Cost::new(12.0), &Direction::Forward, &si).unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        let decoded: IsochroneResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_reverse_isochrone_follows_incoming_edges() {
+        // the line graph is one-way, so nothing can reach vertex 0 but vertex 2
+        // can be reached from vertices 0 and 1
+        let si = build_search_instance(build_line_graph());
+        let forward =
+            run_isochrone(VertexId(2), Cost::new(12.0), &Direction::Forward, &si).unwrap();
+        let reverse =
+            run_isochrone(VertexId(2), Cost::new(12.0), &Direction::Reverse, &si).unwrap();
+
+        let forward_vertices = forward
+            .vertices
+            .iter()
+            .map(|v| (v.vertex_id, v.cost))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            forward_vertices,
+            vec![
+                (VertexId(2), Cost::new(0.0)),
+                (VertexId(3), Cost::new(10.0))
+            ]
+        );
+        let reverse_vertices = reverse
+            .vertices
+            .iter()
+            .map(|v| (v.vertex_id, v.cost))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reverse_vertices,
+            vec![
+                (VertexId(2), Cost::new(0.0)),
+                (VertexId(1), Cost::new(4.0)),
+                (VertexId(0), Cost::new(9.0)),
+            ]
+        );
+
+        // reverse edges keep their own orientation
+        let reverse_edges = reverse
+            .edges
+            .iter()
+            .map(|e| (e.edge_id, e.src_vertex_id, e.dst_vertex_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reverse_edges,
+            vec![
+                (EdgeId(0), VertexId(0), VertexId(1)),
+                (EdgeId(1), VertexId(1), VertexId(2)),
+            ]
+        );
+
+        let from_origin = run_isochrone(VertexId(0), Cost::new(12.0), &Direction::Reverse, &si)
+            .unwrap()
+            .vertices
+            .iter()
+            .map(|v| v.vertex_id)
+            .collect::<Vec<_>>();
+        assert_eq!(from_origin, vec![VertexId(0)]);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::model::{
+    road_network::{edge_id::EdgeId, vertex_id::VertexId},
+    unit::Cost,
+};
+use serde::{Deserialize, Serialize};
+
+/// the subgraph reachable from an origin within a cost budget.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IsochroneResult {
+    /// the cost budget used to build this isochrone
+    pub budget: Cost,
+    /// every vertex reachable within the budget
+    pub vertices: Vec<ReachableVertex>,
+    /// the edges used to reach each vertex along with any edges that cross the
+    /// budget boundary
+    pub edges: Vec<ReachableEdge>,
+}
+
+/// a vertex reachable within the budget along with the minimum accumulated
+/// cost to reach it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ReachableVertex {
+    pub vertex_id: VertexId,
+    pub cost: Cost,
+}
+
+/// an edge departing from a reachable vertex. `fraction` is 1.0 for edges that
+/// are fully traversable within the budget, and the portion of the edge
+/// reachable before the budget runs out for edges that straddle the boundary.
+/// in a reverse isochrone, edges are expanded from their destination vertex,
+/// so the costs and reachable fraction are measured from `dst_vertex_id`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ReachableEdge {
+    pub edge_id: EdgeId,
+    pub src_vertex_id: VertexId,
+    pub dst_vertex_id: VertexId,
+    /// accumulated cost at the vertex the edge was expanded from
+    pub start_cost: Cost,
+    /// accumulated cost after fully traversing the edge
+    pub end_cost: Cost,
+    pub fraction: f64,
+}
+
+impl ReachableEdge {
+    /// true if only part of this edge can be reached within the budget
+    pub fn is_partial(&self) -> bool {
+        self.fraction < 1.0
+    }
+}
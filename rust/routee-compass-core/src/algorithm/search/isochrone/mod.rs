@@ -0,0 +1,2 @@
+pub mod isochrone_algorithm;
+pub mod isochrone_result;
@@ -0,0 +1,246 @@
+use super::k_nearest_result::{KNearestResult, NearestTarget};
+use crate::algorithm::search::a_star::a_star_algorithm::run_a_star_with_cost;
+use crate::algorithm::search::backtrack;
+use crate::algorithm::search::branch_cost::{BranchCost, ScalarBranchCost};
+use crate::algorithm::search::direction::Direction;
+use crate::algorithm::search::edge_traversal::EdgeTraversal;
+use crate::algorithm::search::search_error::SearchError;
+use crate::algorithm::search::search_instance::SearchInstance;
+use crate::algorithm::search::search_priority::SearchPriority;
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::unit::Cost;
+use std::collections::HashSet;
+
+/// runs a Dijkstra search from the source vertex that stops once the k
+/// lowest-cost targets in the candidate set are settled. vertices are settled
+/// in order of accumulated cost, so when the k-th target is settled every
+/// unsettled target costs at least as much and the search can end there,
+/// without computing the cost to every candidate. the search also ends when
+/// every candidate is settled or the frontier is exhausted. the search runs
+/// through [`run_a_star_with_cost`], so it honors the avoided edges,
+/// `max_edges` limit and cancellation flag of the query and reports its
+/// search metadata.
+///
+/// a reverse search finds the k targets that reach the source most cheaply,
+/// and each route is returned in forward order, from the target to the source.
+///
+/// # Arguments
+///
+/// * `source` - the origin of a forward search or the destination of a reverse search
+/// * `targets` - the candidate targets, such as the vertices of charging stations
+/// * `k` - the maximum number of targets to return
+/// * `direction` - whether to expand over outgoing or incoming edges
+/// * `avoid_edges` - optional edges to treat as impassable
+/// * `si` - the search assets for this query
+///
+/// # Returns
+///
+/// up to k reachable targets ordered by cost, each with its route
+pub fn run_k_nearest(
+    source: VertexId,
+    targets: &HashSet<VertexId>,
+    k: usize,
+    direction: &Direction,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    si: &SearchInstance,
+) -> Result<KNearestResult, SearchError> {
+    if k == 0 {
+        return Err(SearchError::BuildError(String::from(
+            "k nearest search requires k greater than zero",
+        )));
+    }
+    let mut branch_cost = KNearestBranchCost {
+        scalar: ScalarBranchCost {
+            weight_factor: None,
+            max_cost: None,
+        },
+        targets,
+        n_wanted: k.min(targets.len()),
+        found: vec![],
+    };
+    let result = run_a_star_with_cost(
+        source,
+        None,
+        direction,
+        avoid_edges,
+        None,
+        &mut branch_cost,
+        si,
+    )?;
+    log::debug!(
+        "k nearest search found {} of {} targets after settling {} vertices",
+        branch_cost.found.len(),
+        k,
+        result.metadata.vertices_settled
+    );
+
+    let targets = branch_cost
+        .found
+        .into_iter()
+        .map(|(vertex_id, cost)| {
+            let mut route = backtrack::vertex_oriented_route(source, vertex_id, &result.tree)?;
+            if matches!(direction, Direction::Reverse) {
+                // a reverse tree backtracks from the target toward the source,
+                // which is already the order of travel
+                route.reverse();
+            }
+            Ok(NearestTarget {
+                vertex_id,
+                cost,
+                route,
+            })
+        })
+        .collect::<Result<Vec<_>, SearchError>>()?;
+    Ok(KNearestResult {
+        targets,
+        metadata: result.metadata,
+    })
+}
+
+/// the Dijkstra branch cost, which records each candidate target as it is
+/// settled and ends the search once enough targets are found.
+struct KNearestBranchCost<'a> {
+    scalar: ScalarBranchCost,
+    targets: &'a HashSet<VertexId>,
+    n_wanted: usize,
+    found: Vec<(VertexId, Cost)>,
+}
+
+impl BranchCost for KNearestBranchCost<'_> {
+    type Cost = Cost;
+    type Priority = SearchPriority;
+
+    fn origin_cost(&self) -> Cost {
+        self.scalar.origin_cost()
+    }
+
+    fn extend(
+        &self,
+        cost: &Cost,
+        traversal: &EdgeTraversal,
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        self.scalar.extend(cost, traversal, si)
+    }
+
+    fn priority(
+        &self,
+        cost: &Cost,
+        vertex_id: VertexId,
+        target: Option<VertexId>,
+        state: &[StateVar],
+        si: &SearchInstance,
+    ) -> Result<SearchPriority, SearchError> {
+        self.scalar.priority(cost, vertex_id, target, state, si)
+    }
+
+    fn on_settled(&mut self, vertex_id: VertexId, cost: &Cost) -> bool {
+        if self.targets.contains(&vertex_id) {
+            self.found.push((vertex_id, *cost));
+        }
+        self.found.len() == self.n_wanted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::edge_id::EdgeId;
+    use crate::model::road_network::graph::Graph;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+
+    /// a line graph (0) -[0]-> (1) -[1]-> (2) -[2]-> (3) -[3]-> (4) -[4]-> (5)
+    /// with unit-length edges and a spur (0) -[5]-> (6) of length 10.
+    fn build_graph() -> Graph {
+        graph_from_links(
+            7,
+            &[
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (2, 3, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (0, 6, 10.0),
+            ],
+        )
+    }
+
+    fn build_search_instance(graph: Graph) -> SearchInstance {
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build()
+    }
+
+    #[test]
+    fn test_returns_k_cheapest_targets_with_routes() {
+        let si = build_search_instance(build_graph());
+        let targets = HashSet::from([VertexId(6), VertexId(4), VertexId(2)]);
+        let result =
+            run_k_nearest(VertexId(0), &targets, 2, &Direction::Forward, None, &si).unwrap();
+        let found = result
+            .targets
+            .iter()
+            .map(|t| (t.vertex_id, t.cost))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![(VertexId(2), Cost::new(2.0)), (VertexId(4), Cost::new(4.0))]
+        );
+        let route = result.targets[1]
+            .route
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(0), EdgeId(1), EdgeId(2), EdgeId(3)]);
+    }
+
+    #[test]
+    fn test_stops_expanding_at_kth_target() {
+        // the second target is settled at cost 4 after vertices 0 through 4.
+        // vertex 5 and the spur to vertex 6 cost more, so they are never settled.
+        let si = build_search_instance(build_graph());
+        let targets = HashSet::from([VertexId(2), VertexId(4), VertexId(5), VertexId(6)]);
+        let result =
+            run_k_nearest(VertexId(0), &targets, 2, &Direction::Forward, None, &si).unwrap();
+        assert_eq!(result.targets.len(), 2);
+        assert_eq!(result.metadata.vertices_settled, 5);
+
+        let all = run_k_nearest(VertexId(0), &targets, 4, &Direction::Forward, None, &si).unwrap();
+        assert_eq!(all.targets.len(), 4);
+        assert_eq!(all.metadata.vertices_settled, 7);
+    }
+
+    #[test]
+    fn test_reverse_routes_run_from_target_to_source() {
+        // vertices 1 and 2 can both reach vertex 4, and vertex 2 is closer
+        let si = build_search_instance(build_graph());
+        let targets = HashSet::from([VertexId(1), VertexId(2)]);
+        let result =
+            run_k_nearest(VertexId(4), &targets, 1, &Direction::Reverse, None, &si).unwrap();
+        assert_eq!(result.targets[0].vertex_id, VertexId(2));
+        let route = result.targets[0]
+            .route
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(2), EdgeId(3)]);
+    }
+
+    #[test]
+    fn test_returns_fewer_when_fewer_are_reachable() {
+        let si = build_search_instance(build_graph());
+        let targets = HashSet::from([VertexId(3), VertexId(0)]);
+        let result =
+            run_k_nearest(VertexId(2), &targets, 2, &Direction::Forward, None, &si).unwrap();
+        let found = result
+            .targets
+            .iter()
+            .map(|t| t.vertex_id)
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![VertexId(3)]);
+    }
+}
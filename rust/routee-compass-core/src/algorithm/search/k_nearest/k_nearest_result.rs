@@ -0,0 +1,23 @@
+use crate::algorithm::search::edge_traversal::EdgeTraversal;
+use crate::algorithm::search::search_metadata::SearchMetadata;
+use crate::model::{road_network::vertex_id::VertexId, unit::Cost};
+use serde::Serialize;
+
+/// the k lowest-cost targets reachable from an origin, ordered by cost.
+/// fewer than k targets are returned when fewer are reachable.
+#[derive(Serialize, Clone, Debug)]
+pub struct KNearestResult {
+    pub targets: Vec<NearestTarget>,
+    /// counters describing the work of the search, which stops early once
+    /// the k-th target is settled
+    pub metadata: SearchMetadata,
+}
+
+/// a reachable target with the minimum cost to reach it and the route that
+/// achieves that cost.
+#[derive(Serialize, Clone, Debug)]
+pub struct NearestTarget {
+    pub vertex_id: VertexId,
+    pub cost: Cost,
+    pub route: Vec<EdgeTraversal>,
+}
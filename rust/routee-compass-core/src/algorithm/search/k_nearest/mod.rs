@@ -0,0 +1,2 @@
+pub mod k_nearest_algorithm;
+pub mod k_nearest_result;
@@ -6,10 +6,13 @@ use crate::{
         search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
         search_instance::SearchInstance,
     },
-    model::{road_network::vertex_id::VertexId, unit::cost::ReverseCost},
+    model::{
+        road_network::{edge_id::EdgeId, vertex_id::VertexId},
+        unit::cost::ReverseCost,
+    },
     util::priority_queue::InternalPriorityQueue,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// generates a set of k-shortest paths using the single-via path algorithm.
 pub fn run(
@@ -17,6 +20,7 @@ pub fn run(
     target: VertexId,
     k: usize,
     similarity: &RouteSimilarityFunction,
+    avoid_edges: Option<&HashSet<EdgeId>>,
     si: &SearchInstance,
     underlying: &SearchAlgorithm,
 ) -> Result<SearchAlgorithmResult, SearchError> {
@@ -25,12 +29,26 @@ pub fn run(
         trees: fwd_trees,
         routes: _,
         iterations: fwd_iterations,
-    } = underlying.run_vertex_oriented(source, Some(target), &Direction::Forward, si)?;
+        metadata: fwd_metadata,
+    } = underlying.run_vertex_oriented(
+        source,
+        Some(target),
+        &Direction::Forward,
+        avoid_edges,
+        si,
+    )?;
     let SearchAlgorithmResult {
         trees: rev_trees,
         routes: _,
         iterations: rev_iterations,
-    } = underlying.run_vertex_oriented(target, Some(source), &Direction::Reverse, si)?;
+        metadata: rev_metadata,
+    } = underlying.run_vertex_oriented(
+        target,
+        Some(source),
+        &Direction::Reverse,
+        avoid_edges,
+        si,
+    )?;
     if fwd_trees.len() != 1 {
         Err(SearchError::InternalSearchError(format!(
             "ksp solver fwd trees count should be exactly 1, found {}",
@@ -129,6 +147,7 @@ pub fn run(
         trees: vec![fwd_tree.clone(), rev_tree.clone()], // todo: figure out how to avoid this clone
         routes: solution,
         iterations: fwd_iterations + rev_iterations + ksp_it, // todo: figure out how to report individually
+        metadata: fwd_metadata.combine(&rev_metadata),
     };
     Ok(result)
 }
@@ -1,2 +1,4 @@
 pub mod ksp_single_via_paths;
+pub mod revisit_penalty;
 pub mod route_similarity_function;
+pub mod yens_algorithm;
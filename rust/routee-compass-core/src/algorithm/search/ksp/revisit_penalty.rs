@@ -0,0 +1,75 @@
+use crate::{
+    algorithm::search::{
+        edge_traversal::EdgeTraversal, search_error::SearchError, search_instance::SearchInstance,
+    },
+    model::{
+        road_network::vertex_id::VertexId,
+        unit::{as_f64::AsF64, Cost},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// discourages alternative routes that loop back near recently found routes.
+/// a candidate route is ranked by its cost plus `penalty` for each of its
+/// interior vertices used by any of the last `window` accepted routes. the
+/// penalty only affects the order in which candidates are accepted, the
+/// cost reported for a route is unchanged.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RevisitPenalty {
+    /// number of most recently accepted routes whose vertices are penalized
+    pub window: usize,
+    /// cost added per revisited vertex
+    pub penalty: Cost,
+}
+
+impl RevisitPenalty {
+    /// computes the cost used to rank a candidate route against the routes
+    /// accepted so far. the origin and destination are shared by every
+    /// route and are never penalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost` - the cost of the candidate route
+    /// * `candidate` - the candidate route
+    /// * `accepted` - the accepted routes, in the order they were accepted
+    /// * `si` - the search instance for these routes
+    ///
+    /// # Returns
+    ///
+    /// the penalized cost of the candidate
+    pub fn penalized_cost(
+        &self,
+        cost: Cost,
+        candidate: &[EdgeTraversal],
+        accepted: &[Vec<EdgeTraversal>],
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        let window_start = accepted.len().saturating_sub(self.window);
+        let mut recent: HashSet<VertexId> = HashSet::new();
+        for route in accepted[window_start..].iter() {
+            recent.extend(interior_vertices(route, si)?);
+        }
+        let revisits = interior_vertices(candidate, si)?
+            .iter()
+            .filter(|v| recent.contains(v))
+            .count();
+        Ok(cost + Cost::new(self.penalty.as_f64() * revisits as f64))
+    }
+}
+
+/// the vertices a route passes through, excluding its origin and destination
+fn interior_vertices(
+    route: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<Vec<VertexId>, SearchError> {
+    let n_interior = route.len().saturating_sub(1);
+    route[0..n_interior]
+        .iter()
+        .map(|et| {
+            si.directed_graph
+                .dst_vertex_id(et.edge_id)
+                .map_err(SearchError::GraphError)
+        })
+        .collect()
+}
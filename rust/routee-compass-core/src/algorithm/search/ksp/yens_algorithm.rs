@@ -0,0 +1,366 @@
+use super::revisit_penalty::RevisitPenalty;
+use crate::{
+    algorithm::search::{
+        direction::Direction, edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        search_instance::SearchInstance,
+    },
+    model::{
+        road_network::{edge_id::EdgeId, vertex_id::VertexId},
+        unit::Cost,
+    },
+};
+use std::collections::HashSet;
+
+/// limits the pool of candidate routes of Yen's algorithm and how the
+/// candidates are ranked
+#[derive(Clone, Copy, Default)]
+pub struct CandidateOptions<'a> {
+    /// optional limit on the size of the candidate pool. when exceeded, the
+    /// most expensive candidates are discarded.
+    pub max_candidates: Option<usize>,
+    /// optional penalty that ranks candidates reusing the vertices of
+    /// recently accepted routes as more expensive
+    pub revisit_penalty: Option<&'a RevisitPenalty>,
+}
+
+/// generates up to k shortest loopless paths using Yen's algorithm. each
+/// spur path is found by running the underlying search with the edges of
+/// earlier routes that share the same root path, and every edge touching a
+/// root path vertex, added to the avoid set.
+///
+/// # Arguments
+///
+/// * `source` - search source vertex
+/// * `target` - search destination vertex
+/// * `k` - maximum number of routes to return
+/// * `candidate_options` - optional limit on the candidate pool and penalty for ranking candidates
+/// * `avoid_edges` - edges that no route may use
+/// * `si` - the search assets for this query
+/// * `underlying` - the search algorithm used to find the shortest path and each spur path
+///
+/// # Returns
+///
+/// The routes ordered by increasing cost, or by increasing penalized cost
+/// when a revisit penalty is set, along with the tree of the initial
+/// shortest path search.
+pub fn run(
+    source: VertexId,
+    target: VertexId,
+    k: usize,
+    candidate_options: CandidateOptions,
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    let SearchAlgorithmResult {
+        trees,
+        routes: tsp_routes,
+        mut iterations,
+        mut metadata,
+    } = underlying.run_vertex_oriented(
+        source,
+        Some(target),
+        &Direction::Forward,
+        avoid_edges,
+        si,
+    )?;
+    let tsp = tsp_routes.into_iter().next().ok_or_else(|| {
+        SearchError::InternalSearchError(String::from(
+            "yen's ksp solver expected a shortest path route",
+        ))
+    })?;
+
+    let mut solution: Vec<Vec<EdgeTraversal>> = vec![tsp];
+    let mut seen: HashSet<Vec<EdgeId>> = HashSet::from([edge_ids(&solution[0])]);
+    let mut candidates: Vec<(Cost, Vec<EdgeTraversal>)> = vec![];
+
+    while solution.len() < k {
+        let prev_route = solution.last().ok_or_else(|| {
+            SearchError::InternalSearchError(String::from("yen's ksp solution is empty"))
+        })?;
+        let prev_edges = edge_ids(prev_route);
+
+        for spur_idx in 0..prev_edges.len() {
+            let root_edges = &prev_edges[0..spur_idx];
+            let spur_vertex = si.directed_graph.src_vertex_id(prev_edges[spur_idx])?;
+
+            // block the next edge of every accepted route sharing this root path
+            let mut spur_avoid: HashSet<EdgeId> = avoid_edges.cloned().unwrap_or_default();
+            for route in solution.iter() {
+                let route_edges = edge_ids(route);
+                if route_edges.len() > spur_idx && route_edges[0..spur_idx] == *root_edges {
+                    spur_avoid.insert(route_edges[spur_idx]);
+                }
+            }
+            // block every root path vertex other than the spur vertex so that
+            // the combined route is loopless
+            for edge_id in root_edges {
+                let root_vertex = si.directed_graph.src_vertex_id(*edge_id)?;
+                spur_avoid.extend(si.directed_graph.out_edges_iter(root_vertex)?);
+                spur_avoid.extend(si.directed_graph.in_edges_iter(root_vertex)?);
+            }
+
+            let spur_result = underlying.run_vertex_oriented(
+                spur_vertex,
+                Some(target),
+                &Direction::Forward,
+                Some(&spur_avoid),
+                si,
+            );
+            let spur_routes = match spur_result {
+                Err(SearchError::NoPathExists(..)) => continue,
+                Err(e) => return Err(e),
+                Ok(result) => {
+                    iterations += result.iterations;
+                    metadata = metadata.combine(&result.metadata);
+                    result.routes
+                }
+            };
+
+            for spur_route in spur_routes {
+                let candidate_edges = root_edges
+                    .iter()
+                    .cloned()
+                    .chain(edge_ids(&spur_route))
+                    .collect::<Vec<_>>();
+                if seen.insert(candidate_edges.clone()) {
+                    // re-traverse the full route so that state accumulates from the origin
+                    let candidate = traverse_route(&candidate_edges, si)?;
+                    let cost = route_cost(&candidate);
+                    candidates.push((cost, candidate));
+                }
+            }
+        }
+
+        // keep the candidate pool sorted by descending rank so the cheapest is at the
+        // end. penalties depend on the accepted routes, so they are recomputed each round.
+        let mut ranked = match candidate_options.revisit_penalty {
+            None => candidates,
+            Some(p) => candidates
+                .into_iter()
+                .map(|(_, route)| {
+                    let rank = p.penalized_cost(route_cost(&route), &route, &solution, si)?;
+                    Ok((rank, route))
+                })
+                .collect::<Result<Vec<_>, SearchError>>()?,
+        };
+        ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+        if let Some(limit) = candidate_options.max_candidates {
+            let excess = ranked.len().saturating_sub(limit);
+            ranked.drain(0..excess);
+        }
+        candidates = ranked;
+        match candidates.pop() {
+            None => {
+                log::debug!("yen's ksp candidate pool is empty, quitting");
+                break;
+            }
+            Some((_, route)) => solution.push(route),
+        }
+    }
+
+    log::debug!("yen's ksp found {} routes", solution.len());
+
+    let result = SearchAlgorithmResult {
+        trees,
+        routes: solution,
+        iterations,
+        metadata,
+    };
+    Ok(result)
+}
+
+fn edge_ids(route: &[EdgeTraversal]) -> Vec<EdgeId> {
+    route.iter().map(|et| et.edge_id).collect()
+}
+
+fn route_cost(route: &[EdgeTraversal]) -> Cost {
+    route.iter().map(|et| et.total_cost()).sum()
+}
+
+/// traverses a sequence of edges from the initial search state
+fn traverse_route(
+    edges: &[EdgeId],
+    si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    let mut state = si.state_model.initial_state()?;
+    let mut prev_edge_id: Option<EdgeId> = None;
+    let mut route = Vec::with_capacity(edges.len());
+    for edge_id in edges {
+        let et = EdgeTraversal::forward_traversal(*edge_id, prev_edge_id, &state, si)?;
+        state = et.result_state.clone();
+        prev_edge_id = Some(*edge_id);
+        route.push(et);
+    }
+    Ok(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::graph::Graph;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+
+    /// the example graph from the Yen's algorithm literature, with vertices
+    /// C=0, D=1, E=2, F=3, G=4, H=5 and distances in meters
+    fn build_yen_graph() -> Graph {
+        graph_from_links(
+            6,
+            &[
+                (0, 1, 3.0),
+                (0, 2, 2.0),
+                (1, 3, 4.0),
+                (2, 1, 1.0),
+                (2, 3, 2.0),
+                (2, 4, 3.0),
+                (3, 4, 2.0),
+                (3, 5, 1.0),
+                (4, 5, 2.0),
+            ],
+        )
+    }
+
+    fn build_search_instance(graph: Graph) -> SearchInstance {
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build()
+    }
+
+    #[test]
+    fn test_yens_k_shortest_paths() {
+        let si = build_search_instance(build_yen_graph());
+        let result = run(
+            VertexId(0),
+            VertexId(5),
+            3,
+            CandidateOptions::default(),
+            None,
+            &si,
+            &SearchAlgorithm::Dijkstra,
+        )
+        .unwrap();
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| edge_ids(r))
+            .collect::<Vec<_>>();
+        let costs = result
+            .routes
+            .iter()
+            .map(|r| route_cost(r))
+            .collect::<Vec<_>>();
+
+        // C-E-F-H (5), C-E-G-H (7), then either C-D-F-H or C-E-D-F-H (8)
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0], vec![EdgeId(1), EdgeId(4), EdgeId(7)]);
+        assert_eq!(routes[1], vec![EdgeId(1), EdgeId(5), EdgeId(8)]);
+        assert!(
+            routes[2] == vec![EdgeId(0), EdgeId(2), EdgeId(7)]
+                || routes[2] == vec![EdgeId(1), EdgeId(3), EdgeId(2), EdgeId(7)]
+        );
+        assert_eq!(costs, vec![Cost::new(5.0), Cost::new(7.0), Cost::new(8.0)]);
+
+        // routes are distinct and never revisit a vertex
+        let distinct = routes.iter().collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), routes.len());
+        for route in routes.iter() {
+            let mut visited = HashSet::from([VertexId(0)]);
+            for edge_id in route {
+                let dst = si.directed_graph.dst_vertex_id(*edge_id).unwrap();
+                assert!(visited.insert(dst), "route {:?} contains a loop", route);
+            }
+        }
+    }
+
+    #[test]
+    fn test_yens_respects_avoid_edges_and_k() {
+        // avoiding C-E leaves only routes that begin with C-D
+        let si = build_search_instance(build_yen_graph());
+        let avoid = HashSet::from([EdgeId(1)]);
+        let result = run(
+            VertexId(0),
+            VertexId(5),
+            5,
+            CandidateOptions {
+                max_candidates: Some(1),
+                revisit_penalty: None,
+            },
+            Some(&avoid),
+            &si,
+            &SearchAlgorithm::Dijkstra,
+        )
+        .unwrap();
+        let routes = result
+            .routes
+            .iter()
+            .map(|r| edge_ids(r))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            routes,
+            vec![
+                vec![EdgeId(0), EdgeId(2), EdgeId(7)],
+                vec![EdgeId(0), EdgeId(2), EdgeId(6), EdgeId(8)],
+            ]
+        );
+    }
+
+    /// a main route 0-1-2-3, a near duplicate 0-1-4-3 that leaves it only to
+    /// skip vertex 2, and a spatially distinct route 0-5-3
+    fn build_near_duplicate_graph() -> Graph {
+        graph_from_links(
+            6,
+            &[
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (2, 3, 1.0),
+                (1, 4, 1.5),
+                (4, 3, 1.0),
+                (0, 5, 2.0),
+                (5, 3, 2.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_revisit_penalty_favors_distinct_routes() {
+        let si = build_search_instance(build_near_duplicate_graph());
+        let second_route = |revisit_penalty: Option<&RevisitPenalty>| {
+            let result = run(
+                VertexId(0),
+                VertexId(3),
+                2,
+                CandidateOptions {
+                    max_candidates: None,
+                    revisit_penalty,
+                },
+                None,
+                &si,
+                &SearchAlgorithm::Dijkstra,
+            )
+            .unwrap();
+            assert_eq!(
+                edge_ids(&result.routes[0]),
+                vec![EdgeId(0), EdgeId(1), EdgeId(2)]
+            );
+            (edge_ids(&result.routes[1]), route_cost(&result.routes[1]))
+        };
+
+        // without a penalty, the cheaper near duplicate is the second route
+        let (route, cost) = second_route(None);
+        assert_eq!(route, vec![EdgeId(0), EdgeId(3), EdgeId(4)]);
+        assert_eq!(cost, Cost::new(3.5));
+
+        // the penalty for reusing vertex 1 ranks the near duplicate below the
+        // distinct route, which is returned with its true cost
+        let penalty = RevisitPenalty {
+            window: 1,
+            penalty: Cost::new(1.0),
+        };
+        let (route, cost) = second_route(Some(&penalty));
+        assert_eq!(route, vec![EdgeId(5), EdgeId(6)]);
+        assert_eq!(cost, Cost::new(4.0));
+    }
+}
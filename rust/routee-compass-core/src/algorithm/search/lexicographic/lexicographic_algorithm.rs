@@ -0,0 +1,289 @@
+use super::lexicographic_priority::{LexicographicCost, LexicographicPriority};
+use crate::algorithm::search::a_star::a_star_algorithm::run_a_star_with_cost;
+use crate::algorithm::search::branch_cost::BranchCost;
+use crate::algorithm::search::direction::Direction;
+use crate::algorithm::search::edge_traversal::EdgeTraversal;
+use crate::algorithm::search::search_error::SearchError;
+use crate::algorithm::search::search_instance::SearchInstance;
+use crate::algorithm::search::search_result::SearchResult;
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::traversal::state::state_variable::StateVar;
+use crate::model::unit::Cost;
+use std::collections::HashSet;
+
+/// run a label-setting search that minimizes a list of state features in
+/// strict priority order instead of a single scalar cost. a branch is better
+/// than another if it accumulates less of the first feature, and later
+/// features only break ties on the earlier ones. for example, the features
+/// `["tolls", "time"]` find the fastest route among those with the fewest
+/// toll roads, no matter how much slower it is.
+///
+/// the search runs [`run_a_star_with_cost`] with the lexicographic
+/// comparison in place of the scalar cost, so the open set is ordered by the
+/// same comparison used to relax edges. the accumulated features are read from the search state, so
+/// each feature must be non-decreasing along a route for the result to be
+/// optimal. the scalar cost model is still applied to each edge traversal
+/// so that routes report their usual costs, but it does not affect the
+/// search order. no a* heuristic is used.
+///
+/// # Arguments
+///
+/// * `source`      - search source vertex
+/// * `target`      - optional search destination
+/// * `direction`   - direction of the search
+/// * `features`    - state features to minimize, in priority order
+/// * `avoid_edges` - edges treated as impassable for this search only
+/// * `si`          - search instance
+///
+/// # Returns
+///
+/// the search tree, or an error if a feature is missing from the state model
+pub fn run_lexicographic(
+    source: VertexId,
+    target: Option<VertexId>,
+    direction: &Direction,
+    features: &[String],
+    avoid_edges: Option<&HashSet<EdgeId>>,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    if features.is_empty() {
+        return Err(SearchError::BuildError(String::from(
+            "lexicographic search requires at least one feature",
+        )));
+    }
+    for feature in features.iter() {
+        if !si.state_model.contains_key(feature) {
+            return Err(SearchError::BuildError(format!(
+                "lexicographic feature '{}' is not in the state model, found: {}",
+                feature,
+                si.state_model.get_names()
+            )));
+        }
+    }
+    let initial_state = si.state_model.initial_state()?;
+    let mut branch_cost = LexicographicBranchCost {
+        features,
+        initial_state: &initial_state,
+    };
+    run_a_star_with_cost(
+        source,
+        target,
+        direction,
+        avoid_edges,
+        None,
+        &mut branch_cost,
+        si,
+    )
+}
+
+/// costs a branch by the amount of each lexicographic feature accumulated
+/// along it, with no a* heuristic.
+struct LexicographicBranchCost<'a> {
+    features: &'a [String],
+    initial_state: &'a [StateVar],
+}
+
+impl BranchCost for LexicographicBranchCost<'_> {
+    type Cost = LexicographicCost;
+    type Priority = LexicographicPriority;
+
+    fn origin_cost(&self) -> LexicographicCost {
+        LexicographicCost(vec![Cost::ZERO; self.features.len()])
+    }
+
+    fn extend(
+        &self,
+        _cost: &LexicographicCost,
+        traversal: &EdgeTraversal,
+        si: &SearchInstance,
+    ) -> Result<LexicographicCost, SearchError> {
+        lexicographic_cost(
+            self.features,
+            self.initial_state,
+            &traversal.result_state,
+            si,
+        )
+    }
+
+    fn priority(
+        &self,
+        cost: &LexicographicCost,
+        vertex_id: VertexId,
+        _target: Option<VertexId>,
+        _state: &[StateVar],
+        _si: &SearchInstance,
+    ) -> Result<LexicographicPriority, SearchError> {
+        Ok(LexicographicPriority::new(cost.clone(), vertex_id))
+    }
+}
+
+/// reads the amount of each feature accumulated since the initial state.
+fn lexicographic_cost(
+    features: &[String],
+    initial_state: &[StateVar],
+    state: &[StateVar],
+    si: &SearchInstance,
+) -> Result<LexicographicCost, SearchError> {
+    let costs = features
+        .iter()
+        .map(|feature| {
+            let delta = si.state_model.get_delta(initial_state, state, feature)?;
+            Ok(Cost::new(delta.0))
+        })
+        .collect::<Result<Vec<_>, SearchError>>()?;
+    Ok(LexicographicCost(costs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::a_star::a_star_algorithm::run_a_star;
+    use crate::algorithm::search::backtrack::vertex_oriented_route;
+    use crate::model::property::edge::Edge;
+    use crate::model::property::vertex::Vertex;
+    use crate::model::state::custom_feature_format::CustomFeatureFormat;
+    use crate::model::state::state_feature::StateFeature;
+    use crate::model::state::state_model::StateModel;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::model::traversal::traversal_model::TraversalModel;
+    use crate::model::traversal::traversal_model_error::TraversalModelError;
+    use crate::model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT};
+    use crate::test_util::graph_fixture::graph_from_links;
+    use crate::test_util::search_instance_builder::{raw_cost_model, SearchInstanceBuilder};
+    use std::sync::Arc;
+
+    /// accumulates distance and a count of toll edges traversed
+    struct TollTraversalModel {
+        toll_edges: HashSet<EdgeId>,
+    }
+
+    impl TraversalModel for TollTraversalModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &DistanceUnit::Meters);
+            state_model.add_distance(
+                state,
+                &"distance".into(),
+                &distance,
+                &DistanceUnit::Meters,
+            )?;
+            if self.toll_edges.contains(&edge.edge_id) {
+                state_model.accumulate_custom_u64(state, &"tolls".into(), &1)?;
+            }
+            Ok(())
+        }
+
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVar>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+    }
+
+    /// a 1-meter toll road (0) -> (1), and a 20-meter toll-free
+    /// detour (0) -> (2) -> (1)
+    fn build_search_instance() -> SearchInstance {
+        let graph = graph_from_links(3, &[(0, 1, 1.0), (0, 2, 10.0), (2, 1, 10.0)]);
+        let state_model = Arc::new(
+            StateModel::empty()
+                .extend(vec![
+                    (
+                        String::from("distance"),
+                        StateFeature::Distance {
+                            distance_unit: DistanceUnit::Meters,
+                            initial: Distance::new(0.0),
+                        },
+                    ),
+                    (
+                        String::from("tolls"),
+                        StateFeature::Custom {
+                            r#type: String::from("tolls"),
+                            unit: String::from("count"),
+                            format: CustomFeatureFormat::UnsignedInteger { initial: 0 },
+                            accumulation: Default::default(),
+                        },
+                    ),
+                ])
+                .unwrap(),
+        );
+        // a weighted sum where one toll road is worth one meter of driving
+        let cost_model = raw_cost_model(&["distance", "tolls"], state_model.clone());
+        SearchInstanceBuilder::new(graph)
+            .state_model(state_model)
+            .cost_model(cost_model)
+            .traversal_model(Arc::new(TollTraversalModel {
+                toll_edges: HashSet::from([EdgeId(0)]),
+            }))
+            .termination_model(TerminationModel::IterationsLimit { limit: 100 })
+            .build()
+    }
+
+    #[test]
+    fn test_lexicographic_differs_from_weighted_sum() {
+        let si = build_search_instance();
+
+        let weighted = run_a_star(
+            VertexId(0),
+            Some(VertexId(1)),
+            &Direction::Forward,
+            None,
+            None,
+            &si,
+        )
+        .unwrap()
+        .tree;
+        let weighted_route = vertex_oriented_route(VertexId(0), VertexId(1), &weighted).unwrap();
+        assert_eq!(
+            weighted_route.iter().map(|e| e.edge_id).collect::<Vec<_>>(),
+            vec![EdgeId(0)]
+        );
+
+        let features = vec![String::from("tolls"), String::from("distance")];
+        let lexicographic = run_lexicographic(
+            VertexId(0),
+            Some(VertexId(1)),
+            &Direction::Forward,
+            &features,
+            None,
+            &si,
+        )
+        .unwrap()
+        .tree;
+        let lexicographic_route =
+            vertex_oriented_route(VertexId(0), VertexId(1), &lexicographic).unwrap();
+        assert_eq!(
+            lexicographic_route
+                .iter()
+                .map(|e| e.edge_id)
+                .collect::<Vec<_>>(),
+            vec![EdgeId(1), EdgeId(2)]
+        );
+    }
+
+    #[test]
+    fn test_lexicographic_rejects_unknown_feature() {
+        let si = build_search_instance();
+        let result = run_lexicographic(
+            VertexId(0),
+            Some(VertexId(1)),
+            &Direction::Forward,
+            &[String::from("energy")],
+            None,
+            &si,
+        );
+        assert!(matches!(result, Err(SearchError::BuildError(_))));
+    }
+}
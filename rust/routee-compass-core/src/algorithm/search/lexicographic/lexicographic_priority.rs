@@ -0,0 +1,55 @@
+use crate::model::{road_network::vertex_id::VertexId, unit::Cost};
+use allocative::Allocative;
+use std::cmp::Reverse;
+
+/// accumulated value of each objective feature along a branch, in the
+/// configured priority order. keys are compared lexicographically, so a
+/// lower value of the first feature always wins, and later features only
+/// break ties on earlier ones.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Allocative)]
+pub struct LexicographicCost(pub Vec<Cost>);
+
+/// priority of a vertex in a lexicographic search frontier. the vertex with
+/// the lowest [`LexicographicCost`] is popped first, and ties are broken by
+/// popping the lower VertexId first, matching SearchPriority.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct LexicographicPriority {
+    cost: Reverse<LexicographicCost>,
+    vertex_id: Reverse<VertexId>,
+}
+
+impl LexicographicPriority {
+    pub fn new(cost: LexicographicCost, vertex_id: VertexId) -> LexicographicPriority {
+        LexicographicPriority {
+            cost: Reverse(cost),
+            vertex_id: Reverse(vertex_id),
+        }
+    }
+}
+
+impl Allocative for LexicographicPriority {
+    fn visit<'a, 'b: 'a>(&self, visitor: &'a mut allocative::Visitor<'b>) {
+        let _visitor = visitor.enter_self_sized::<Self>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::priority_queue::InternalPriorityQueue;
+
+    #[test]
+    fn test_earlier_features_dominate() {
+        let mut queue: InternalPriorityQueue<VertexId, LexicographicPriority> =
+            InternalPriorityQueue::default();
+        for (vertex_id, key) in [(0, [1.0, 1.0]), (1, [0.0, 100.0]), (2, [0.0, 50.0])] {
+            let cost = LexicographicCost(key.iter().map(|c| Cost::new(*c)).collect());
+            queue.push(
+                VertexId(vertex_id),
+                LexicographicPriority::new(cost, VertexId(vertex_id)),
+            );
+        }
+        let order = std::iter::from_fn(|| queue.pop().map(|(v, _)| v)).collect::<Vec<_>>();
+        assert_eq!(order, vec![VertexId(2), VertexId(1), VertexId(0)]);
+    }
+}
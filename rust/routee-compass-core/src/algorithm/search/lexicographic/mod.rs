@@ -0,0 +1,2 @@
+pub mod lexicographic_algorithm;
+pub mod lexicographic_priority;
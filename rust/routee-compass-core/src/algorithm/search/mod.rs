@@ -3,14 +3,24 @@ use crate::model::road_network::vertex_id::VertexId;
 use std::collections::HashMap;
 
 pub mod a_star;
+pub mod alt;
 pub mod backtrack;
+pub mod branch_cost;
+pub mod ch;
+pub mod cost_estimate_function;
 pub mod direction;
 pub mod edge_traversal;
+pub mod isochrone;
+pub mod k_nearest;
 pub mod ksp;
+pub mod lexicographic;
 pub mod search_algorithm;
 pub mod search_algorithm_result;
 pub mod search_error;
 pub mod search_instance;
+pub mod search_metadata;
+pub mod search_outcome;
+pub mod search_priority;
 pub mod search_result;
 pub mod search_tree_branch;
 
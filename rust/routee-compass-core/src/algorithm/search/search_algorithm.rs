@@ -1,17 +1,23 @@
 use super::backtrack;
+use super::ch::{contraction_hierarchy, contraction_hierarchy_config::ContractionHierarchyConfig};
 use super::edge_traversal::EdgeTraversal;
 use super::ksp::ksp_single_via_paths;
+use super::ksp::revisit_penalty::RevisitPenalty;
 use super::ksp::route_similarity_function::RouteSimilarityFunction;
+use super::ksp::yens_algorithm;
+use super::lexicographic::lexicographic_algorithm;
 use super::search_algorithm_result::SearchAlgorithmResult;
 use super::search_error::SearchError;
 use super::search_instance::SearchInstance;
+use super::search_metadata::SearchMetadata;
 use super::search_tree_branch::SearchTreeBranch;
 use super::{a_star::a_star_algorithm, direction::Direction};
 use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
 
 use crate::model::unit::Cost;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
@@ -26,6 +32,30 @@ pub enum SearchAlgorithm {
         underlying: Box<SearchAlgorithm>,
         similarity: RouteSimilarityFunction,
     },
+    KspYens {
+        k: usize,
+        max_candidates: Option<usize>,
+        /// optional penalty ranking alternatives lower when they reuse the
+        /// vertices of recently accepted routes
+        revisit_penalty: Option<RevisitPenalty>,
+        underlying: Box<SearchAlgorithm>,
+    },
+    /// minimizes state features in strict priority order, such as
+    /// fewest toll roads first, then least time
+    Lexicographic {
+        features: Vec<String>,
+    },
+    /// routes over a contraction hierarchy, which settles far fewer vertices
+    /// than A* on large graphs. requires edge costs that do not depend on the
+    /// search state, see [`contraction_hierarchy::ContractionHierarchy`].
+    ContractionHierarchy {
+        #[serde(flatten)]
+        config: ContractionHierarchyConfig,
+        /// the hierarchy of the most recent query, reused by later queries
+        /// while their edge costs are unchanged
+        #[serde(skip)]
+        loaded: Mutex<Option<Arc<contraction_hierarchy::ContractionHierarchy>>>,
+    },
 }
 
 impl SearchAlgorithm {
@@ -34,19 +64,21 @@ impl SearchAlgorithm {
         src_id: VertexId,
         dst_id_opt: Option<VertexId>,
         direction: &Direction,
+        avoid_edges: Option<&HashSet<EdgeId>>,
         si: &SearchInstance,
     ) -> Result<SearchAlgorithmResult, SearchError> {
         match self {
             SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
                 weight_factor: Some(Cost::ZERO),
             }
-            .run_vertex_oriented(src_id, dst_id_opt, direction, si),
+            .run_vertex_oriented(src_id, dst_id_opt, direction, avoid_edges, si),
             SearchAlgorithm::AStarAlgorithm { weight_factor } => {
                 let search_result = a_star_algorithm::run_a_star(
                     src_id,
                     dst_id_opt,
                     direction,
                     *weight_factor,
+                    avoid_edges,
                     si,
                 )?;
                 let routes = match dst_id_opt {
@@ -61,6 +93,7 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    metadata: search_result.metadata,
                 })
             }
             SearchAlgorithm::KspSingleVia {
@@ -68,33 +101,374 @@ impl SearchAlgorithm {
                 underlying,
                 similarity,
             } => match dst_id_opt {
-                Some(dst_id) => {
-                    ksp_single_via_paths::run(src_id, dst_id, *k, similarity, si, underlying)
-                }
+                Some(dst_id) => ksp_single_via_paths::run(
+                    src_id,
+                    dst_id,
+                    *k,
+                    similarity,
+                    avoid_edges,
+                    si,
+                    underlying,
+                ),
+                None => Err(SearchError::BuildError(String::from(
+                    "request has source but no destination which is invalid for k-shortest paths",
+                ))),
+            },
+            SearchAlgorithm::KspYens {
+                k,
+                max_candidates,
+                revisit_penalty,
+                underlying,
+            } => match dst_id_opt {
+                Some(dst_id) => yens_algorithm::run(
+                    src_id,
+                    dst_id,
+                    *k,
+                    yens_algorithm::CandidateOptions {
+                        max_candidates: *max_candidates,
+                        revisit_penalty: revisit_penalty.as_ref(),
+                    },
+                    avoid_edges,
+                    si,
+                    underlying,
+                ),
                 None => Err(SearchError::BuildError(String::from(
                     "request has source but no destination which is invalid for k-shortest paths",
                 ))),
             },
+            SearchAlgorithm::Lexicographic { features } => {
+                let search_result = lexicographic_algorithm::run_lexicographic(
+                    src_id,
+                    dst_id_opt,
+                    direction,
+                    features,
+                    avoid_edges,
+                    si,
+                )?;
+                let routes = match dst_id_opt {
+                    None => vec![],
+                    Some(dst_id) => {
+                        let route =
+                            backtrack::vertex_oriented_route(src_id, dst_id, &search_result.tree)?;
+                        vec![route]
+                    }
+                };
+                Ok(SearchAlgorithmResult {
+                    trees: vec![search_result.tree],
+                    routes,
+                    iterations: search_result.iterations,
+                    metadata: search_result.metadata,
+                })
+            }
+            SearchAlgorithm::ContractionHierarchy { config, loaded } => contraction_hierarchy::run(
+                src_id,
+                dst_id_opt,
+                direction,
+                avoid_edges,
+                config,
+                loaded,
+                si,
+            ),
         }
     }
+
+    /// finds a single route from the origin to the destination that passes
+    /// through each via vertex in order. runs one search per leg between
+    /// consecutive anchors, where each leg continues from the last edge
+    /// traversal of the previous leg. accumulated features such as elapsed
+    /// time keep growing over the whole trip, and turn restrictions, turn
+    /// costs and u-turn checks apply at each via vertex. the legs are
+    /// concatenated into one route whose cost is the sum of the leg costs.
+    ///
+    /// only A* and Dijkstra can continue a search from a previous leg.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - vertex where the route begins
+    /// * `vias` - ordered waypoints the route must pass through
+    /// * `destination` - vertex where the route ends
+    /// * `avoid_edges` - optional edges to treat as impassable on every leg
+    /// * `si` - the search instance
+    ///
+    /// # Returns
+    ///
+    /// a result with one tree per leg and the stitched route, or an error
+    /// naming the first leg that failed
+    pub fn run_via(
+        &self,
+        origin: VertexId,
+        vias: &[VertexId],
+        destination: VertexId,
+        avoid_edges: Option<&HashSet<EdgeId>>,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        let stops = vias
+            .iter()
+            .chain(std::iter::once(&destination))
+            .map(|vertex_id| (*vertex_id, None))
+            .collect::<Vec<_>>();
+        self.run_stitched(origin, &stops, avoid_edges, si, |leg, src, dst, e| {
+            SearchError::WaypointLegFailed(leg, src, dst, Box::new(e))
+        })
+    }
+
+    /// finds a single route from the origin to the destination that
+    /// traverses each required edge in order, such as a mandated weigh
+    /// station or a ferry. searches from the origin to the start of the
+    /// first required edge, traverses that edge, then searches from its end
+    /// to the start of the next required edge, and so on until the
+    /// destination. as in [`SearchAlgorithm::run_via`], each segment
+    /// continues from the last edge traversal before it, and the segments
+    /// and required edges are concatenated into one route. each required
+    /// edge is checked with the frontier model, so a closed or restricted
+    /// required edge fails its segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - vertex where the route begins
+    /// * `required_edges` - ordered edges the route must traverse
+    /// * `destination` - vertex where the route ends
+    /// * `avoid_edges` - optional edges to treat as impassable on every segment
+    /// * `si` - the search instance
+    ///
+    /// # Returns
+    ///
+    /// a result with one tree per segment and the stitched route, or an
+    /// error naming the first segment that failed
+    pub fn run_required_edges(
+        &self,
+        origin: VertexId,
+        required_edges: &[EdgeId],
+        destination: VertexId,
+        avoid_edges: Option<&HashSet<EdgeId>>,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        if let Some(avoided) = required_edges
+            .iter()
+            .find(|e| avoid_edges.is_some_and(|avoid| avoid.contains(e)))
+        {
+            return Err(SearchError::BuildError(format!(
+                "edge {} is both required and avoided",
+                avoided
+            )));
+        }
+
+        // each segment runs to the start of a required edge and then traverses it
+        let mut stops = vec![];
+        for edge_id in required_edges.iter() {
+            let edge = si.directed_graph.get_edge(*edge_id)?;
+            stops.push((edge.src_vertex_id, Some(*edge_id)));
+        }
+        stops.push((destination, None));
+        self.run_stitched(origin, &stops, avoid_edges, si, |segment, src, dst, e| {
+            SearchError::RequiredEdgeSegmentFailed(segment, src, dst, Box::new(e))
+        })
+    }
+
+    /// finds a single route from the origin through a sequence of stops by
+    /// running one search per leg and concatenating the legs. a stop is a
+    /// vertex to search to, optionally followed by an edge to traverse from
+    /// that vertex, and the next leg begins where the stop ends. each leg
+    /// continues from the last edge traversal of the route so far, so the
+    /// state keeps accumulating over the whole trip, and turn restrictions,
+    /// turn costs and u-turn checks apply where the legs meet. a stop edge
+    /// is checked with the frontier model like any edge of a search.
+    ///
+    /// only A* and Dijkstra can continue a search from a previous leg.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - vertex where the route begins
+    /// * `stops` - each vertex the route reaches in order, with the edge traversed from it, if any
+    /// * `avoid_edges` - optional edges to treat as impassable on every leg
+    /// * `si` - the search instance
+    /// * `leg_error` - wraps the error of a failed leg with its index and vertices
+    ///
+    /// # Returns
+    ///
+    /// a result with one tree per leg and the stitched route, or the wrapped
+    /// error of the first leg that failed
+    fn run_stitched(
+        &self,
+        origin: VertexId,
+        stops: &[(VertexId, Option<EdgeId>)],
+        avoid_edges: Option<&HashSet<EdgeId>>,
+        si: &SearchInstance,
+        leg_error: impl Fn(usize, VertexId, VertexId, SearchError) -> SearchError,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        let weight_factor = self.continuing_weight_factor(
+            "routes through waypoints or required edges are only supported by a* and dijkstra",
+        )?;
+        let mut src_id = origin;
+        let mut result = SearchAlgorithmResult {
+            routes: vec![vec![]],
+            ..Default::default()
+        };
+        for (leg, (dst_id, stop_edge)) in stops.iter().enumerate() {
+            let dst_id = *dst_id;
+            let wrap = |e: SearchError| leg_error(leg, src_id, dst_id, e);
+            let search_result = a_star_algorithm::run_a_star_from_arrival(
+                src_id,
+                Some(dst_id),
+                &Direction::Forward,
+                weight_factor,
+                avoid_edges,
+                result.routes[0].last(),
+                si,
+            )
+            .map_err(wrap)?;
+            if src_id != dst_id {
+                let leg_route =
+                    backtrack::vertex_oriented_route(src_id, dst_id, &search_result.tree)
+                        .map_err(wrap)?;
+                result.routes[0].extend(leg_route);
+            }
+            result.trees.push(search_result.tree);
+            result.iterations += search_result.iterations;
+            result.metadata = result.metadata.combine(&search_result.metadata);
+
+            src_id = match stop_edge {
+                None => dst_id,
+                Some(edge_id) => {
+                    let traversal =
+                        traverse_stop_edge(*edge_id, result.routes[0].last(), si).map_err(wrap)?;
+                    result.routes[0].push(traversal);
+                    si.directed_graph.dst_vertex_id(*edge_id)?
+                }
+            };
+        }
+        Ok(result)
+    }
+
+    /// finds a route that starts partway along the origin edge and ends
+    /// partway along the destination edge. the origin offset is the fraction
+    /// of the origin edge already behind the traveler, so the search begins
+    /// after traversing the remaining `1 - origin_offset` of it, carrying its
+    /// state and treating it as the previous edge of the first expansion. the
+    /// destination offset is the fraction of the destination edge traveled
+    /// before arriving, which is traversed from the state the search arrives
+    /// with. a trip along a single edge is one partial traversal, and a trip
+    /// between adjacent edges needs no search.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - edge where the trip begins
+    /// * `origin_offset` - position of the origin along its edge, in the range [0, 1]
+    /// * `destination` - edge where the trip ends
+    /// * `destination_offset` - position of the destination along its edge, in the range [0, 1]
+    /// * `avoid_edges` - optional edges to treat as impassable
+    /// * `si` - the search instance
+    ///
+    /// # Returns
+    ///
+    /// a result with the search tree and the route with partial first and
+    /// last edge traversals, or an error if an offset is out of range or the
+    /// search fails
+    pub fn run_between_edge_offsets(
+        &self,
+        origin: EdgeId,
+        origin_offset: f64,
+        destination: EdgeId,
+        destination_offset: f64,
+        avoid_edges: Option<&HashSet<EdgeId>>,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        let weight_factor =
+            self.continuing_weight_factor("edge offsets are only supported by a* and dijkstra")?;
+        let initial_state = si.state_model.initial_state()?;
+        if origin == destination && origin_offset <= destination_offset {
+            let et = EdgeTraversal::partial_forward_traversal(
+                origin,
+                None,
+                destination_offset - origin_offset,
+                &initial_state,
+                si,
+            )?;
+            return Ok(SearchAlgorithmResult {
+                routes: vec![vec![et]],
+                ..Default::default()
+            });
+        }
+
+        let origin_et = EdgeTraversal::partial_forward_traversal(
+            origin,
+            None,
+            1.0 - origin_offset,
+            &initial_state,
+            si,
+        )?;
+        let src_id = si.directed_graph.dst_vertex_id(origin)?;
+        let dst_id = si.directed_graph.src_vertex_id(destination)?;
+        let search_result = a_star_algorithm::run_a_star_from_arrival(
+            src_id,
+            Some(dst_id),
+            &Direction::Forward,
+            weight_factor,
+            avoid_edges,
+            Some(&origin_et),
+            si,
+        )?;
+        let mut route = vec![origin_et];
+        if src_id != dst_id {
+            route.extend(backtrack::vertex_oriented_route(
+                src_id,
+                dst_id,
+                &search_result.tree,
+            )?);
+        }
+        let arrival = &route[route.len() - 1];
+        let destination_et = EdgeTraversal::partial_forward_traversal(
+            destination,
+            Some(arrival.edge_id),
+            destination_offset,
+            &arrival.result_state,
+            si,
+        )?;
+        route.push(destination_et);
+        Ok(SearchAlgorithmResult {
+            routes: vec![route],
+            trees: vec![search_result.tree],
+            iterations: search_result.iterations,
+            metadata: search_result.metadata,
+        })
+    }
+
+    /// the weight factor of an algorithm that can continue a search from an
+    /// earlier traversal, which only A* and Dijkstra can do.
+    fn continuing_weight_factor(&self, unsupported: &str) -> Result<Option<Cost>, SearchError> {
+        match self {
+            SearchAlgorithm::Dijkstra => Ok(Some(Cost::ZERO)),
+            SearchAlgorithm::AStarAlgorithm { weight_factor } => Ok(*weight_factor),
+            _ => Err(SearchError::BuildError(String::from(unsupported))),
+        }
+    }
+
     pub fn run_edge_oriented(
         &self,
         src_id: EdgeId,
         dst_id_opt: Option<EdgeId>,
         direction: &Direction,
+        avoid_edges: Option<&HashSet<EdgeId>>,
         search_instance: &SearchInstance,
     ) -> Result<SearchAlgorithmResult, SearchError> {
         match self {
             SearchAlgorithm::Dijkstra => SearchAlgorithm::AStarAlgorithm {
                 weight_factor: Some(Cost::ZERO),
             }
-            .run_edge_oriented(src_id, dst_id_opt, direction, search_instance),
+            .run_edge_oriented(
+                src_id,
+                dst_id_opt,
+                direction,
+                avoid_edges,
+                search_instance,
+            ),
             SearchAlgorithm::AStarAlgorithm { weight_factor } => {
                 let search_result = a_star_algorithm::run_a_star_edge_oriented(
                     src_id,
                     dst_id_opt,
                     direction,
                     *weight_factor,
+                    avoid_edges,
                     search_instance,
                 )?;
                 let routes = match dst_id_opt {
@@ -113,27 +487,68 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    metadata: search_result.metadata,
                 })
             }
-            SearchAlgorithm::KspSingleVia {
-                k: _,
-                underlying: _,
-                similarity: _,
-            } => run_edge_oriented(src_id, dst_id_opt, direction, self, search_instance),
+            SearchAlgorithm::KspSingleVia { .. }
+            | SearchAlgorithm::KspYens { .. }
+            | SearchAlgorithm::Lexicographic { .. }
+            | SearchAlgorithm::ContractionHierarchy { .. } => run_edge_oriented(
+                src_id,
+                dst_id_opt,
+                direction,
+                avoid_edges,
+                self,
+                search_instance,
+            ),
         }
     }
 }
 
+/// traverses an edge the route must take, continuing from the traversal that
+/// arrived at its start, if any. the edge is checked with the frontier model
+/// before and after the traversal, as a search would check it.
+fn traverse_stop_edge(
+    edge_id: EdgeId,
+    arrival: Option<&EdgeTraversal>,
+    si: &SearchInstance,
+) -> Result<EdgeTraversal, SearchError> {
+    let edge = si.directed_graph.get_edge(edge_id)?;
+    let (prev_edge_id, state) = match arrival {
+        Some(et) => (Some(et.edge_id), et.result_state.clone()),
+        None => (None, si.state_model.initial_state()?),
+    };
+    let prev_edge = prev_edge_id
+        .map(|id| si.directed_graph.get_edge(id))
+        .transpose()?;
+    let valid_frontier =
+        si.frontier_model
+            .valid_frontier(edge, &state, prev_edge, &si.state_model)?;
+    if !valid_frontier {
+        return Err(SearchError::RequiredEdgeRejected(edge_id));
+    }
+    let traversal = EdgeTraversal::forward_traversal(edge_id, prev_edge_id, &state, si)?;
+    let valid_traversal =
+        si.frontier_model
+            .valid_traversal(edge, &traversal.result_state, &si.state_model)?;
+    if !valid_traversal {
+        return Err(SearchError::RequiredEdgeRejected(edge_id));
+    }
+    Ok(traversal)
+}
+
 // convenience method when origin and destination are specified using
 /// edge ids instead of vertex ids. invokes a vertex-oriented search
 /// from the out-vertex of the source edge to the in-vertex of the
 /// target edge. composes the result with the source and target.
+/// edges in the optional avoid set are skipped during expansion.
 ///
 /// not tested.
 pub fn run_edge_oriented(
     source: EdgeId,
     target: Option<EdgeId>,
     direction: &Direction,
+    avoid_edges: Option<&HashSet<EdgeId>>,
     alg: &SearchAlgorithm,
     si: &SearchInstance,
 ) -> Result<SearchAlgorithmResult, SearchError> {
@@ -157,7 +572,8 @@ pub fn run_edge_oriented(
                 mut trees,
                 mut routes,
                 iterations,
-            } = alg.run_vertex_oriented(e1_dst, None, direction, si)?;
+                metadata,
+            } = alg.run_vertex_oriented(e1_dst, None, direction, avoid_edges, si)?;
             for tree in trees.iter_mut() {
                 if !tree.contains_key(&e1_dst) {
                     tree.extend([(e1_dst, src_branch.clone())]);
@@ -170,6 +586,7 @@ pub fn run_edge_oriented(
                 trees,
                 routes,
                 iterations: iterations + 1,
+                metadata,
             };
             Ok(updated)
         }
@@ -203,6 +620,7 @@ pub fn run_edge_oriented(
                     trees: vec![tree],
                     routes: vec![route],
                     iterations: 1,
+                    metadata: SearchMetadata::default(),
                 };
                 return Ok(result);
             } else {
@@ -211,10 +629,11 @@ pub fn run_edge_oriented(
                     trees,
                     mut routes,
                     iterations,
-                } = alg.run_vertex_oriented(e1_dst, Some(e2_src), direction, si)?;
+                    metadata,
+                } = alg.run_vertex_oriented(e1_dst, Some(e2_src), direction, avoid_edges, si)?;
 
                 if trees.is_empty() {
-                    return Err(SearchError::NoPathExists(e1_dst, e2_src));
+                    return Err(SearchError::NoPathExists(e1_dst, e2_src, metadata));
                 }
 
                 // it is possible that the search already found these vertices. one major edge
@@ -238,6 +657,7 @@ pub fn run_edge_oriented(
                     trees,
                     routes,
                     iterations: iterations + 2,
+                    metadata,
                 };
                 Ok(result)
             }
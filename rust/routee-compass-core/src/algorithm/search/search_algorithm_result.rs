@@ -1,4 +1,7 @@
-use super::{edge_traversal::EdgeTraversal, search_tree_branch::SearchTreeBranch};
+use super::{
+    edge_traversal::EdgeTraversal, search_metadata::SearchMetadata,
+    search_tree_branch::SearchTreeBranch,
+};
 use crate::model::road_network::vertex_id::VertexId;
 use std::collections::HashMap;
 
@@ -7,4 +10,5 @@ pub struct SearchAlgorithmResult {
     pub trees: Vec<HashMap<VertexId, SearchTreeBranch>>,
     pub routes: Vec<Vec<EdgeTraversal>>,
     pub iterations: u64,
+    pub metadata: SearchMetadata,
 }
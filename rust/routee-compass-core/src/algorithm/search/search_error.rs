@@ -1,3 +1,4 @@
+use super::search_metadata::SearchMetadata;
 use crate::model::{
     access::access_model_error::AccessModelError,
     cost::cost_error::CostError,
@@ -6,6 +7,7 @@ use crate::model::{
     state::state_error::StateError,
     termination::termination_model_error::TerminationModelError,
     traversal::traversal_model_error::TraversalModelError,
+    unit::Cost,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -26,12 +28,31 @@ pub enum SearchError {
     FrontierModelFailure(#[from] FrontierModelError),
     #[error(transparent)]
     CostError(#[from] CostError),
+    #[error("route is disconnected, edge {0} does not arrive at the start of edge {1}")]
+    DisconnectedRoute(EdgeId, EdgeId),
     #[error("loop in search result revisits edge {0}")]
     LoopInSearchResult(EdgeId),
     #[error("query terminated due to {0}")]
     QueryTerminated(String),
+    #[error("query cancelled after {0} iterations")]
+    QueryCancelled(u64),
+    /// the destination is unreachable. carries the metadata of the search
+    /// that exhausted its frontier, as failed searches are the ones most
+    /// worth inspecting
     #[error("no path exists between vertices {0} and {1}")]
-    NoPathExists(VertexId, VertexId),
+    NoPathExists(VertexId, VertexId, SearchMetadata),
+    #[error("leg {0} from vertex {1} to vertex {2} of the route through waypoints failed: {3}")]
+    WaypointLegFailed(usize, VertexId, VertexId, Box<SearchError>),
+    #[error(
+        "segment {0} from vertex {1} to vertex {2} of the route through required edges failed: {3}"
+    )]
+    RequiredEdgeSegmentFailed(usize, VertexId, VertexId, Box<SearchError>),
+    #[error("required edge {0} is rejected by the frontier model")]
+    RequiredEdgeRejected(EdgeId),
+    #[error("route cost {0} exceeds the query max_cost of {1}")]
+    MaxCostExceeded(Cost, Cost),
+    #[error("no route costs at most the query max_cost of {0}")]
+    NoPathWithinMaxCost(Cost),
     #[error("search tree is missing linked vertex {0}")]
     VertexMissingFromSearchTree(VertexId),
     #[error("error accessing shared read-only dataset: {0}")]
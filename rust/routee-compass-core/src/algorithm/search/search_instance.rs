@@ -1,15 +1,25 @@
-use super::search_error::SearchError;
+use super::{
+    cost_estimate_function::CostEstimateFunction, direction::Direction,
+    edge_traversal::EdgeTraversal, search_error::SearchError,
+};
 use crate::model::{
     access::access_model::AccessModel,
     cost::cost_model::CostModel,
     frontier::frontier_model::FrontierModel,
-    road_network::{graph::Graph, vertex_id::VertexId},
+    property::edge::Edge,
+    road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId},
     state::state_model::StateModel,
     termination::termination_model::TerminationModel,
     traversal::{state::state_variable::StateVar, traversal_model::TraversalModel},
     unit::Cost,
 };
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// an edge leaving a vertex, the vertex it leads to, and the result of traversing it
+pub type Successor = (EdgeId, VertexId, EdgeTraversal);
 
 /// instances of read-only objects used for a search that have
 /// been prepared for a specific query.
@@ -21,24 +31,108 @@ pub struct SearchInstance {
     pub cost_model: CostModel,
     pub frontier_model: Arc<dyn FrontierModel>,
     pub termination_model: Arc<TerminationModel>,
+    /// optional cap on the number of edges in any branch of the search tree.
+    /// labels exceeding the cap are pruned, which may produce a suboptimal route.
+    pub max_edges: Option<usize>,
+    /// optional cap on the cost of any branch of the search tree. A* and
+    /// Dijkstra drop branches exceeding the cap, so the search never expands
+    /// beyond it and a destination that costs more is not reached.
+    pub max_cost: Option<Cost>,
+    /// when true, among labels of equal cost the search prefers the one
+    /// reached with fewer edges, which returns simpler routes without
+    /// changing the optimal cost
+    pub prefer_fewer_edges: bool,
+    /// optional flag set by the caller to abandon this search, such as when
+    /// the client that requested it has disconnected
+    pub cancellation: Option<Arc<AtomicBool>>,
+    /// estimates the cost remaining to the destination, see [`CostEstimateFunction`]
+    pub cost_estimate: Arc<dyn CostEstimateFunction>,
 }
 
 impl SearchInstance {
-    /// approximates the traversal state delta between two vertices and uses
-    /// the result to compute a cost estimate.
+    /// true if the caller has requested that this search be cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|cancelled| cancelled.load(Ordering::Relaxed))
+    }
+
+    /// estimates the cost remaining from `src` to `dst` with the cost
+    /// estimate function of this search.
     pub fn estimate_traversal_cost(
         &self,
         src: VertexId,
         dst: VertexId,
         state: &[StateVar],
     ) -> Result<Cost, SearchError> {
-        let src = self.directed_graph.get_vertex(src)?;
-        let dst = self.directed_graph.get_vertex(dst)?;
-        let mut dst_state = state.to_vec();
+        self.cost_estimate.estimate_cost(src, dst, state, self)
+    }
+
+    /// iterates over the edges incident to a vertex in the given direction,
+    /// skipping any edge rejected by the frontier model and traversing the
+    /// rest with the traversal, access and cost models. this is the same
+    /// expansion step used by the search algorithms in this crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_id` - vertex to expand
+    /// * `last_edge_id` - edge traversed to reach `vertex_id`, if any
+    /// * `state` - search state at `vertex_id`
+    /// * `direction` - expand out edges (Forward) or in edges (Reverse)
+    ///
+    /// # Returns
+    ///
+    /// An iterator of successors, where the vertex in each successor is the
+    /// next vertex reached in the given direction, or an error if the vertex
+    /// or last edge is missing from the graph.
+    pub fn successors<'a>(
+        &'a self,
+        vertex_id: VertexId,
+        last_edge_id: Option<EdgeId>,
+        state: &'a [StateVar],
+        direction: &'a Direction,
+    ) -> Result<Box<dyn Iterator<Item = Result<Successor, SearchError>> + 'a>, SearchError> {
+        let last_edge = match last_edge_id {
+            Some(id) => Some(self.directed_graph.get_edge(id)?),
+            None => None,
+        };
+        let edges = direction.get_incident_edges(&vertex_id, self)?;
+        let iter = edges.filter_map(move |edge_id| {
+            self.expand_edge(*edge_id, last_edge, state, direction)
+                .transpose()
+        });
+        Ok(Box::new(iter))
+    }
 
-        self.traversal_model
-            .estimate_traversal((src, dst), &mut dst_state, &self.state_model)?;
-        let cost_estimate = self.cost_model.cost_estimate(state, &dst_state)?;
-        Ok(cost_estimate)
+    /// traverses a single edge, returning None if the frontier model rejects
+    /// the edge or the result of traversing it
+    fn expand_edge(
+        &self,
+        edge_id: EdgeId,
+        last_edge: Option<&Edge>,
+        state: &[StateVar],
+        direction: &Direction,
+    ) -> Result<Option<Successor>, SearchError> {
+        let edge = self.directed_graph.get_edge(edge_id)?;
+        let valid = self.frontier_model.valid_frontier_in_direction(
+            edge,
+            state,
+            last_edge,
+            direction,
+            &self.state_model,
+        )?;
+        if !valid {
+            return Ok(None);
+        }
+        let last_edge_id = last_edge.map(|e| e.edge_id);
+        let et = direction.perform_edge_traversal(edge_id, last_edge_id, state, self)?;
+        let valid_traversal =
+            self.frontier_model
+                .valid_traversal(edge, &et.result_state, &self.state_model)?;
+        if !valid_traversal {
+            return Ok(None);
+        }
+        let next_vertex_id = direction.tree_key_vertex_id(edge);
+        Ok(Some((edge_id, next_vertex_id, et)))
     }
 }
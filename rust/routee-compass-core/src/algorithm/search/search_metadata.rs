@@ -0,0 +1,76 @@
+use allocative::Allocative;
+use serde::Serialize;
+use std::time::Duration;
+
+/// counters describing how much work a search performed. these are
+/// collected during every search, including searches that produce a
+/// tree but no route.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Allocative)]
+pub struct SearchMetadata {
+    /// number of vertices removed from the open set and expanded
+    pub vertices_settled: u64,
+    /// number of edge traversals evaluated while expanding settled vertices
+    pub edges_relaxed: u64,
+    /// largest size of the open set observed during the search
+    pub peak_open_set_size: usize,
+    /// wall-clock duration of the search
+    pub runtime: Duration,
+    /// number of labels discarded because they exceeded the query `max_edges` limit
+    pub labels_pruned_by_max_edges: u64,
+    /// number of labels discarded because their cost exceeded the bound of
+    /// the search, such as the query `max_cost`
+    pub labels_pruned_by_max_cost: u64,
+    /// number of edge expansions blocked by the frontier model
+    pub frontier_rejections: u64,
+    /// set when the search inflated its heuristic to trade optimality for
+    /// speed. the route then costs at most this factor times the optimal cost.
+    pub suboptimality_bound: Option<f64>,
+}
+
+impl SearchMetadata {
+    /// combines the metadata of two searches that together produced a
+    /// single result, such as the forward and reverse searches of a
+    /// k-shortest paths solver.
+    pub fn combine(&self, other: &SearchMetadata) -> SearchMetadata {
+        SearchMetadata {
+            vertices_settled: self.vertices_settled + other.vertices_settled,
+            edges_relaxed: self.edges_relaxed + other.edges_relaxed,
+            peak_open_set_size: self.peak_open_set_size.max(other.peak_open_set_size),
+            runtime: self.runtime + other.runtime,
+            labels_pruned_by_max_edges: self.labels_pruned_by_max_edges
+                + other.labels_pruned_by_max_edges,
+            labels_pruned_by_max_cost: self.labels_pruned_by_max_cost
+                + other.labels_pruned_by_max_cost,
+            frontier_rejections: self.frontier_rejections + other.frontier_rejections,
+            suboptimality_bound: match (self.suboptimality_bound, other.suboptimality_bound) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    /// true if the `max_edges` limit pruned any label during the search. when
+    /// this is the case, the returned route is not guaranteed to be optimal.
+    pub fn max_edges_binding(&self) -> bool {
+        self.labels_pruned_by_max_edges > 0
+    }
+
+    /// true if the cost bound pruned any label during the search. when a
+    /// search with a destination finds no path, this means a path may exist
+    /// but costs more than the bound.
+    pub fn max_cost_binding(&self) -> bool {
+        self.labels_pruned_by_max_cost > 0
+    }
+
+    /// true if the frontier model blocked any edge expansion during the search,
+    /// which may have forced the route onto a detour.
+    pub fn frontier_binding(&self) -> bool {
+        self.frontier_rejections > 0
+    }
+
+    /// true if the search used an inflated heuristic, so the route is only
+    /// guaranteed to be within the suboptimality bound of the optimal route.
+    pub fn is_approximate(&self) -> bool {
+        self.suboptimality_bound.is_some()
+    }
+}
@@ -0,0 +1,225 @@
+use super::search_algorithm_result::SearchAlgorithmResult;
+use super::search_error::SearchError;
+use super::search_metadata::SearchMetadata;
+use crate::model::road_network::vertex_id::VertexId;
+use crate::model::unit::Cost;
+
+/// the outcome of a single search query. a query that runs correctly but
+/// finds no acceptable route is an outcome rather than an error, so that
+/// errors are reserved for genuine failures such as malformed tables or
+/// missing ids. the found result is a [`SearchAlgorithmResult`] by default,
+/// and callers that build a richer result from it use [`SearchOutcome::try_map`].
+pub enum SearchOutcome<T = SearchAlgorithmResult> {
+    /// the search found its routes, or its tree when there is no destination
+    Found(T),
+    /// the destination cannot be reached from the origin, along with the
+    /// metadata of the search that exhausted its frontier
+    NoPath {
+        source: VertexId,
+        target: VertexId,
+        metadata: SearchMetadata,
+    },
+    /// every route costs more than the query max_cost. the cost is that of
+    /// the route found, or None when the search was bounded by the max_cost
+    /// and stopped before reaching the destination.
+    BudgetExceeded {
+        cost: Option<Cost>,
+        max_cost: Cost,
+        metadata: SearchMetadata,
+    },
+    /// the search was cancelled before it completed
+    Cancelled { iterations: u64 },
+}
+
+impl SearchOutcome {
+    /// sorts the result of a search into an outcome, where the benign
+    /// non-results become outcomes and every other error is returned. a
+    /// search bounded by the query max_cost that finds no path after pruning
+    /// branches over it exceeded the budget, and a route assembled from
+    /// several searches, such as one leg per waypoint, is checked against
+    /// the max_cost as a whole.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - the result of running a search algorithm
+    /// * `max_cost` - the optional upper bound on route cost from the query
+    ///
+    /// # Returns
+    ///
+    /// the outcome of the search, or the error if the search failed
+    pub fn from_search_result(
+        result: Result<SearchAlgorithmResult, SearchError>,
+        max_cost: Option<Cost>,
+    ) -> Result<SearchOutcome, SearchError> {
+        let result = match result {
+            Ok(result) => result,
+            Err(SearchError::NoPathExists(source, target, metadata)) => {
+                return match max_cost {
+                    Some(max_cost) if metadata.max_cost_binding() => {
+                        Ok(SearchOutcome::BudgetExceeded {
+                            cost: None,
+                            max_cost,
+                            metadata,
+                        })
+                    }
+                    _ => Ok(SearchOutcome::NoPath {
+                        source,
+                        target,
+                        metadata,
+                    }),
+                };
+            }
+            Err(SearchError::QueryCancelled(iterations)) => {
+                return Ok(SearchOutcome::Cancelled { iterations })
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(max_cost) = max_cost {
+            for route in result.routes.iter() {
+                let cost: Cost = route.iter().map(|e| e.total_cost()).sum();
+                if cost > max_cost {
+                    return Ok(SearchOutcome::BudgetExceeded {
+                        cost: Some(cost),
+                        max_cost,
+                        metadata: result.metadata,
+                    });
+                }
+            }
+        }
+        Ok(SearchOutcome::Found(result))
+    }
+}
+
+impl<T> SearchOutcome<T> {
+    /// converts the found result, leaving the other outcomes unchanged.
+    pub fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<SearchOutcome<U>, E> {
+        match self {
+            SearchOutcome::Found(result) => Ok(SearchOutcome::Found(f(result)?)),
+            SearchOutcome::NoPath {
+                source,
+                target,
+                metadata,
+            } => Ok(SearchOutcome::NoPath {
+                source,
+                target,
+                metadata,
+            }),
+            SearchOutcome::BudgetExceeded {
+                cost,
+                max_cost,
+                metadata,
+            } => Ok(SearchOutcome::BudgetExceeded {
+                cost,
+                max_cost,
+                metadata,
+            }),
+            SearchOutcome::Cancelled { iterations } => Ok(SearchOutcome::Cancelled { iterations }),
+        }
+    }
+
+    /// a short snake_case name for this outcome, as reported in responses
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchOutcome::Found(_) => "found",
+            SearchOutcome::NoPath { .. } => "no_path",
+            SearchOutcome::BudgetExceeded { .. } => "budget_exceeded",
+            SearchOutcome::Cancelled { .. } => "cancelled",
+        }
+    }
+
+    /// the metadata of a search that ran to completion without an acceptable route
+    pub fn metadata(&self) -> Option<&SearchMetadata> {
+        match self {
+            SearchOutcome::NoPath { metadata, .. } => Some(metadata),
+            SearchOutcome::BudgetExceeded { metadata, .. } => Some(metadata),
+            SearchOutcome::Found(_) | SearchOutcome::Cancelled { .. } => None,
+        }
+    }
+
+    /// the error describing this outcome for callers that report every
+    /// non-result as an error, None when the search found its result.
+    pub fn error(&self) -> Option<SearchError> {
+        match self {
+            SearchOutcome::Found(_) => None,
+            SearchOutcome::NoPath {
+                source,
+                target,
+                metadata,
+            } => Some(SearchError::NoPathExists(*source, *target, *metadata)),
+            SearchOutcome::BudgetExceeded { cost, max_cost, .. } => match cost {
+                Some(cost) => Some(SearchError::MaxCostExceeded(*cost, *max_cost)),
+                None => Some(SearchError::NoPathWithinMaxCost(*max_cost)),
+            },
+            SearchOutcome::Cancelled { iterations } => {
+                Some(SearchError::QueryCancelled(*iterations))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::edge_traversal::EdgeTraversal;
+    use crate::model::road_network::edge_id::EdgeId;
+
+    fn result_with_route_cost(cost: f64) -> SearchAlgorithmResult {
+        let route = vec![EdgeTraversal {
+            edge_id: EdgeId(0),
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::new(cost),
+            result_state: vec![],
+        }];
+        SearchAlgorithmResult {
+            routes: vec![route],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_benign_non_results_are_outcomes() {
+        let settled = SearchMetadata {
+            vertices_settled: 7,
+            ..Default::default()
+        };
+        let no_path = Err(SearchError::NoPathExists(VertexId(0), VertexId(1), settled));
+        assert!(matches!(
+            SearchOutcome::from_search_result(no_path, None),
+            Ok(SearchOutcome::NoPath { metadata, .. }) if metadata.vertices_settled == 7
+        ));
+        let cancelled = Err(SearchError::QueryCancelled(10));
+        assert!(matches!(
+            SearchOutcome::from_search_result(cancelled, None),
+            Ok(SearchOutcome::Cancelled { iterations: 10 })
+        ));
+        let mut over_budget = result_with_route_cost(20.0);
+        over_budget.metadata.vertices_settled = 4;
+        assert!(matches!(
+            SearchOutcome::from_search_result(Ok(over_budget), Some(Cost::new(10.0))),
+            Ok(SearchOutcome::BudgetExceeded { metadata, .. }) if metadata.vertices_settled == 4
+        ));
+        let pruned = SearchMetadata {
+            labels_pruned_by_max_cost: 2,
+            ..Default::default()
+        };
+        let bounded = Err(SearchError::NoPathExists(VertexId(0), VertexId(1), pruned));
+        assert!(matches!(
+            SearchOutcome::from_search_result(bounded, Some(Cost::new(10.0))),
+            Ok(SearchOutcome::BudgetExceeded { cost: None, .. })
+        ));
+        let within_budget = Ok(result_with_route_cost(5.0));
+        assert!(matches!(
+            SearchOutcome::from_search_result(within_budget, Some(Cost::new(10.0))),
+            Ok(SearchOutcome::Found(_))
+        ));
+    }
+
+    #[test]
+    fn test_failures_remain_errors() {
+        let failure = Err(SearchError::LoopInSearchResult(EdgeId(3)));
+        assert!(matches!(
+            SearchOutcome::from_search_result(failure, None),
+            Err(SearchError::LoopInSearchResult(_))
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::model::{
+    road_network::vertex_id::VertexId,
+    unit::{cost::ReverseCost, Cost},
+};
+use allocative::Allocative;
+use std::cmp::Reverse;
+
+/// priority of a vertex in the search frontier. the vertex with the lowest
+/// cost is popped first, and ties in cost are broken by popping the lower
+/// VertexId first, so that the search order does not depend on the order
+/// vertices were pushed or on the internals of the priority queue.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct SearchPriority {
+    cost: ReverseCost,
+    vertex_id: Reverse<VertexId>,
+}
+
+impl SearchPriority {
+    pub fn new(cost: Cost, vertex_id: VertexId) -> SearchPriority {
+        SearchPriority {
+            cost: cost.into(),
+            vertex_id: Reverse(vertex_id),
+        }
+    }
+}
+
+impl Allocative for SearchPriority {
+    fn visit<'a, 'b: 'a>(&self, visitor: &'a mut allocative::Visitor<'b>) {
+        let _visitor = visitor.enter_self_sized::<Self>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::priority_queue::InternalPriorityQueue;
+
+    #[test]
+    fn test_ties_pop_lower_vertex_id_first() {
+        let mut queue: InternalPriorityQueue<VertexId, SearchPriority> =
+            InternalPriorityQueue::default();
+        for (vertex_id, cost) in [(3, 1.0), (1, 2.0), (2, 1.0), (0, 2.0)] {
+            queue.push(
+                VertexId(vertex_id),
+                SearchPriority::new(Cost::new(cost), VertexId(vertex_id)),
+            );
+        }
+        let order = std::iter::from_fn(|| queue.pop().map(|(v, _)| v)).collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![VertexId(2), VertexId(3), VertexId(0), VertexId(1)]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::search_tree_branch::SearchTreeBranch;
+use super::{search_metadata::SearchMetadata, search_tree_branch::SearchTreeBranch};
 use crate::model::road_network::vertex_id::VertexId;
 use std::collections::HashMap;
 
@@ -6,10 +6,19 @@ use std::collections::HashMap;
 pub struct SearchResult {
     pub tree: HashMap<VertexId, SearchTreeBranch>,
     pub iterations: u64,
+    pub metadata: SearchMetadata,
 }
 
 impl SearchResult {
-    pub fn new(tree: HashMap<VertexId, SearchTreeBranch>, iterations: u64) -> SearchResult {
-        SearchResult { tree, iterations }
+    pub fn new(
+        tree: HashMap<VertexId, SearchTreeBranch>,
+        iterations: u64,
+        metadata: SearchMetadata,
+    ) -> SearchResult {
+        SearchResult {
+            tree,
+            iterations,
+            metadata,
+        }
     }
 }
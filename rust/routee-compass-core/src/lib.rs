@@ -3,3 +3,6 @@
 pub mod algorithm;
 pub mod model;
 pub mod util;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_util;
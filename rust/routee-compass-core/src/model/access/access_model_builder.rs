@@ -1,4 +1,5 @@
 use super::{access_model_error::AccessModelError, access_model_service::AccessModelService};
+use crate::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 /// A [`AccessModelBuilder`] takes a JSON object describing the configuration of a
@@ -12,6 +13,7 @@ pub trait AccessModelBuilder {
     /// # Arguments
     ///
     /// * `parameters` - the contents of the "traversal" TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
     ///
     /// # Returns
     ///
@@ -19,5 +21,6 @@ pub trait AccessModelBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn AccessModelService>, AccessModelError>;
 }
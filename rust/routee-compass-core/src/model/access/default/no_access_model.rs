@@ -1,3 +1,4 @@
+use crate::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use crate::model::access::{
@@ -46,6 +47,7 @@ impl AccessModelBuilder for NoAccessModel {
     fn build(
         &self,
         _parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<
         Arc<dyn AccessModelService>,
         crate::model::access::access_model_error::AccessModelError,
@@ -1,11 +1,18 @@
-use serde::Deserialize;
+use serde::{de, Deserialize, Deserializer};
+
+/// raw headings beyond this magnitude are treated as bad input rather than
+/// as angles that wrap around the compass many times.
+pub const MAX_RAW_HEADING: i16 = 10000;
 
 /// simplifies the representation of directionality for a linestring
 /// to just the headings of the start and end points, using cardinal angles [0, 360).
 /// if the start and end have the same heading, the edge heading is None.
+/// headings are normalized into [0, 360) when deserialized.
 #[derive(Copy, Clone, Deserialize)]
 pub struct EdgeHeading {
+    #[serde(deserialize_with = "deserialize_heading")]
     arrival_heading: i16,
+    #[serde(deserialize_with = "deserialize_optional_heading")]
     departure_heading: Option<i16>,
 }
 
@@ -30,6 +37,16 @@ impl EdgeHeading {
             None => self.arrival_heading,
         }
     }
+    /// the headings of this edge when traversed in the opposite direction,
+    /// used for the synthetic reverse edges of a bidirectional edge list.
+    pub fn reversed(&self) -> EdgeHeading {
+        let flip = |heading: i16| (heading + 180).rem_euclid(360);
+        EdgeHeading {
+            arrival_heading: flip(self.end_heading()),
+            departure_heading: self.departure_heading.map(|_| flip(self.arrival_heading)),
+        }
+    }
+
     /// Compute the angle between this edge and some destination edge.
     pub fn bearing_to_destination(&self, destination: &EdgeHeading) -> i16 {
         let angle = destination.start_heading() - self.end_heading();
@@ -43,10 +60,57 @@ impl EdgeHeading {
     }
 }
 
+/// normalizes a raw heading into the range [0, 360).
+///
+/// # Arguments
+///
+/// * `heading` - raw heading in degrees, possibly negative or >= 360
+///
+/// # Returns
+///
+/// the equivalent heading in [0, 360), or an error if the raw heading
+/// magnitude exceeds MAX_RAW_HEADING
+pub fn normalize_heading(heading: i16) -> Result<i16, String> {
+    // i16::MIN has no positive i16 magnitude, so compare unsigned
+    if heading.unsigned_abs() > MAX_RAW_HEADING.unsigned_abs() {
+        return Err(format!(
+            "heading {} is out of range, expected a magnitude of at most {}",
+            heading, MAX_RAW_HEADING
+        ));
+    }
+    Ok(heading.rem_euclid(360))
+}
+
+fn deserialize_heading<'de, D>(deserializer: D) -> Result<i16, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let heading = i16::deserialize(deserializer)?;
+    normalize_heading(heading).map_err(de::Error::custom)
+}
+
+fn deserialize_optional_heading<'de, D>(deserializer: D) -> Result<Option<i16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<i16>::deserialize(deserializer)?
+        .map(|heading| normalize_heading(heading).map_err(de::Error::custom))
+        .transpose()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn read_headings(csv_rows: &str) -> Result<Vec<EdgeHeading>, csv::Error> {
+        let input = format!("arrival_heading,departure_heading\n{}", csv_rows);
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::Fields)
+            .from_reader(input.as_bytes())
+            .into_deserialize::<EdgeHeading>()
+            .collect()
+    }
+
     #[test]
     fn test_simple() {
         let edge_heading = EdgeHeading::new(45, 90);
@@ -64,4 +128,41 @@ mod test {
         let next_edge_heading = EdgeHeading::new(10, 10);
         assert_eq!(edge_heading.bearing_to_destination(&next_edge_heading), 20);
     }
+
+    #[test]
+    fn test_headings_normalized_on_load() {
+        let headings = read_headings("-90,450\n0,\n450,-90").unwrap();
+        let (first, second, third) = (headings[0], headings[1], headings[2]);
+        assert_eq!(first.start_heading(), 270);
+        assert_eq!(first.end_heading(), 90);
+        assert_eq!(second.end_heading(), 0);
+        assert_eq!(third.start_heading(), 90);
+        assert_eq!(third.end_heading(), 270);
+
+        // leaving eastbound (90) and entering northbound (0) is a left turn
+        assert_eq!(first.bearing_to_destination(&second), -90);
+        // leaving eastbound (90) and entering eastbound (90) is straight ahead
+        assert_eq!(first.bearing_to_destination(&third), 0);
+        // leaving westbound (270) and entering westbound (270) is straight ahead
+        assert_eq!(third.bearing_to_destination(&first), 0);
+        // leaving westbound (270) and entering northbound (0) is a right turn
+        assert_eq!(third.bearing_to_destination(&second), 90);
+    }
+
+    #[test]
+    fn test_invalid_headings_rejected_on_load() {
+        assert!(read_headings("10001,0").is_err());
+        assert!(read_headings("0,-20000").is_err());
+        assert!(read_headings("-32768,0").is_err());
+        assert!(read_headings("north,0").is_err());
+        assert!(read_headings("90.5,0").is_err());
+    }
+
+    #[test]
+    fn test_reversed() {
+        // heads north-east then turns east, so the reverse heads west then south-west
+        let reversed = EdgeHeading::new(45, 90).reversed();
+        assert_eq!(reversed.start_heading(), 270);
+        assert_eq!(reversed.end_heading(), 225);
+    }
 }
@@ -4,9 +4,14 @@ use crate::model::{
     property::{edge::Edge, vertex::Vertex},
     state::{state_feature::StateFeature, state_model::StateModel},
     traversal::state::state_variable::StateVar,
+    unit::Time,
 };
 use std::sync::Arc;
 
+/// state feature accumulating only the time spent on turn delays, so that
+/// a route summary can separate maneuvering time from driving time.
+pub const ACCESS_TIME_FEATURE_NAME: &str = "access_time";
+
 pub struct TurnDelayAccessModel {
     pub engine: Arc<TurnDelayAccessModelEngine>,
 }
@@ -20,10 +25,89 @@ impl AccessModel for TurnDelayAccessModel {
     ) -> Result<(), AccessModelError> {
         let (delay, delay_unit) = self.engine.get_delay(traversal)?;
         state_model.add_time(state, &self.engine.time_feature_name, &delay, delay_unit)?;
+        state_model.add_time(state, &ACCESS_TIME_FEATURE_NAME.into(), &delay, delay_unit)?;
         Ok(())
     }
 
     fn state_features(&self) -> Vec<(String, StateFeature)> {
-        vec![]
+        vec![(
+            String::from(ACCESS_TIME_FEATURE_NAME),
+            StateFeature::Time {
+                time_unit: self.engine.turn_delay_model.time_unit(),
+                initial: Time::ZERO,
+            },
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        access::default::turn_delays::{
+            edge_heading::EdgeHeading, turn::Turn,
+            turn_delay_access_model_engine::TurnDelayAccessModelEngine,
+            turn_delay_model::TurnDelayModel,
+        },
+        unit::TimeUnit,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_access_time_tracks_only_turn_delays() {
+        let table = HashMap::from([
+            (Turn::NoTurn, Time::new(0.0)),
+            (Turn::Right, Time::new(5.0)),
+        ]);
+        let engine = TurnDelayAccessModelEngine {
+            // edge 0 heads north, edge 1 heads east
+            edge_headings: vec![EdgeHeading::new(0, 0), EdgeHeading::new(90, 90)]
+                .into_boxed_slice(),
+            turn_delay_model: TurnDelayModel::TabularDiscrete {
+                table,
+                time_unit: TimeUnit::Seconds,
+            },
+            time_feature_name: String::from("time"),
+        };
+        let model = TurnDelayAccessModel {
+            engine: Arc::new(engine),
+        };
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Seconds,
+                    initial: Time::ZERO,
+                },
+            )])
+            .unwrap()
+            .extend(model.state_features())
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+
+        // driving time is added by the traversal model, not the access model
+        state_model
+            .add_time(
+                &mut state,
+                &"time".into(),
+                &Time::new(60.0),
+                &TimeUnit::Seconds,
+            )
+            .unwrap();
+        let v = Vertex::new(0, 0.0, 0.0);
+        let e0 = Edge::new(0, 0, 1, 100.0);
+        let e1 = Edge::new(1, 1, 2, 100.0);
+        model
+            .access_edge((&v, &e0, &v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        let access_time = state_model
+            .get_time(&state, &ACCESS_TIME_FEATURE_NAME.into(), &TimeUnit::Seconds)
+            .unwrap();
+        assert_eq!(time, Time::new(65.0));
+        assert_eq!(access_time, Time::new(5.0));
     }
 }
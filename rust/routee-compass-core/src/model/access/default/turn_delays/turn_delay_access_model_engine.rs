@@ -1,7 +1,7 @@
 use crate::model::access::access_model_error::AccessModelError;
 use crate::model::property::edge::Edge;
 use crate::model::property::vertex::Vertex;
-use crate::model::road_network::edge_id::EdgeId;
+use crate::model::traversal::traversal_model_error::MissingIdContext;
 use crate::model::unit::{Time, TimeUnit};
 
 use super::edge_heading::EdgeHeading;
@@ -20,8 +20,8 @@ impl TurnDelayAccessModelEngine {
         traversal: (&Vertex, &Edge, &Vertex, &Edge, &Vertex),
     ) -> Result<(Time, &'a TimeUnit), AccessModelError> {
         let (_v1, src, _v2, dst, _v3) = traversal;
-        let src_heading = get_headings(&self.edge_headings, src.edge_id)?;
-        let dst_heading = get_headings(&self.edge_headings, dst.edge_id)?;
+        let src_heading = get_headings(&self.edge_headings, src)?;
+        let dst_heading = get_headings(&self.edge_headings, dst)?;
         let angle = src_heading.bearing_to_destination(&dst_heading);
         match &self.turn_delay_model {
             TurnDelayModel::TabularDiscrete { table, time_unit } => {
@@ -40,14 +40,17 @@ impl TurnDelayAccessModelEngine {
 /// lookup up the edge heading from the headings table
 pub fn get_headings(
     headings_table: &[EdgeHeading],
-    edge_id: EdgeId,
+    edge: &Edge,
 ) -> Result<EdgeHeading, AccessModelError> {
-    let heading: &EdgeHeading =
-        headings_table
-            .get(edge_id.as_usize())
-            .ok_or_else(|| AccessModelError::RuntimeError {
-                name: String::from("turn delay access model"),
-                error: format!("missing edge id {} ", edge_id),
-            })?;
+    let heading: &EdgeHeading = headings_table.get(edge.edge_id.as_usize()).ok_or_else(|| {
+        AccessModelError::RuntimeError {
+            name: String::from("turn delay access model"),
+            error: format!(
+                "missing edge id {} ({})",
+                edge.edge_id,
+                MissingIdContext::from(edge)
+            ),
+        }
+    })?;
     Ok(*heading)
 }
@@ -24,3 +24,12 @@ pub enum TurnDelayModel {
     //     time_unit: TimeUnit,
     // },
 }
+
+impl TurnDelayModel {
+    /// the unit of the delays produced by this model
+    pub fn time_unit(&self) -> TimeUnit {
+        match self {
+            TurnDelayModel::TabularDiscrete { time_unit, .. } => *time_unit,
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use super::cost_aggregation::CostAggregation;
 use super::cost_ops;
 use super::network::network_cost_rate::NetworkCostRate;
+use super::road_class_bias::RoadClassBias;
 use super::vehicle::vehicle_cost_rate::VehicleCostRate;
 use crate::model::cost::cost_error::CostError;
 use crate::model::property::edge::Edge;
+use crate::model::state::state_feature::StateFeature;
 use crate::model::state::state_model::StateModel;
 use crate::model::traversal::state::state_variable::StateVar;
-use crate::model::unit::Cost;
+use crate::model::unit::{Cost, CostUnit};
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -20,6 +22,9 @@ pub struct CostModel {
     vehicle_rates: Vec<VehicleCostRate>,
     network_rates: Vec<NetworkCostRate>,
     cost_aggregation: CostAggregation,
+    cost_unit: Option<CostUnit>,
+    /// when present, scales the traversal cost of each edge by its road class
+    pub road_class_bias: Option<RoadClassBias>,
 }
 
 impl CostModel {
@@ -77,21 +82,35 @@ impl CostModel {
         if weights.iter().sum::<f64>() == 0.0 {
             return Err(CostError::InvalidCostVariables);
         }
+        let cost_unit = find_cost_unit(&weights, &vehicle_rates, &network_rates, &state_model);
         Ok(CostModel {
             feature_indices: indices,
             weights,
             vehicle_rates,
             network_rates,
             cost_aggregation,
+            cost_unit,
+            road_class_bias: None,
         })
     }
 
+    /// the unit of the costs produced by this model. a cost is in the unit
+    /// of a state feature when it is the only weighted feature, with a
+    /// weight of one, a raw vehicle rate and no network rate. any other
+    /// combination produces costs in no physical unit, reported as None.
+    pub fn cost_unit(&self) -> Option<CostUnit> {
+        self.cost_unit
+    }
+
     /// Calculates the cost of traversing an edge due to some state transition.
+    /// the road class factor of the edge, if any, scales this cost. the
+    /// transition should not include accessing the edge, whose cost comes
+    /// from [`CostModel::access_cost`] and is never scaled.
     ///
     /// # Arguments
     ///
     /// * `edge` - edge traversed
-    /// * `prev_state` - state of the search at the beginning of this edge
+    /// * `prev_state` - state of the search at the beginning of this edge, after accessing it
     /// * `next_state` - state of the search at the end of this edge
     ///
     /// # Returns
@@ -118,7 +137,10 @@ impl CostModel {
             &self.network_rates,
             &self.cost_aggregation,
         )?;
-        let total_cost = vehicle_cost + network_cost;
+        let total_cost = match &self.road_class_bias {
+            Some(bias) => (vehicle_cost + network_cost) * bias.factor(edge.edge_id),
+            None => vehicle_cost + network_cost,
+        };
         let pos_cost = Cost::enforce_strictly_positive(total_cost);
         Ok(pos_cost)
     }
@@ -162,8 +184,10 @@ impl CostModel {
             &self.network_rates,
             &self.cost_aggregation,
         )?;
+        // the traversal cost of the edge is strictly positive, so accessing it
+        // may be free
         let total_cost = vehicle_cost + network_cost;
-        let pos_cost = Cost::enforce_strictly_positive(total_cost);
+        let pos_cost = Cost::enforce_non_negative(total_cost);
         Ok(pos_cost)
     }
 
@@ -192,7 +216,11 @@ impl CostModel {
             &self.vehicle_rates,
             &self.cost_aggregation,
         )?;
-        let pos_cost = Cost::enforce_non_negative(vehicle_cost);
+        let estimate = match &self.road_class_bias {
+            Some(bias) => vehicle_cost * bias.min_factor(),
+            None => vehicle_cost,
+        };
+        let pos_cost = Cost::enforce_non_negative(estimate);
         Ok(pos_cost)
     }
 
@@ -298,3 +326,26 @@ impl CostModel {
         Ok(json![result])
     }
 }
+
+/// finds the unit of the costs of a cost model, see [`CostModel::cost_unit`]
+fn find_cost_unit(
+    weights: &[f64],
+    vehicle_rates: &[VehicleCostRate],
+    network_rates: &[NetworkCostRate],
+    state_model: &StateModel,
+) -> Option<CostUnit> {
+    let mut weighted = weights.iter().enumerate().filter(|(_, w)| **w != 0.0);
+    let (index, weight) = weighted.next()?;
+    let raw = matches!(vehicle_rates[index], VehicleCostRate::Raw)
+        && matches!(network_rates[index], NetworkCostRate::Zero);
+    if weighted.next().is_some() || *weight != 1.0 || !raw {
+        return None;
+    }
+    let (_, (_, feature)) = state_model.indexed_iter().nth(index)?;
+    match feature {
+        StateFeature::Distance { distance_unit, .. } => Some(CostUnit::Distance(*distance_unit)),
+        StateFeature::Time { time_unit, .. } => Some(CostUnit::Time(*time_unit)),
+        StateFeature::Energy { energy_unit, .. } => Some(CostUnit::Energy(*energy_unit)),
+        StateFeature::Custom { .. } => None,
+    }
+}
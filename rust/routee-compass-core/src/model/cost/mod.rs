@@ -3,4 +3,5 @@ pub mod cost_error;
 pub mod cost_model;
 pub mod cost_ops;
 pub mod network;
+pub mod road_class_bias;
 pub mod vehicle;
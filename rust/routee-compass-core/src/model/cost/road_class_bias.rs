@@ -0,0 +1,82 @@
+use super::cost_error::CostError;
+use crate::model::road_network::edge_id::EdgeId;
+use std::{collections::HashMap, sync::Arc};
+
+/// scales the traversal cost of each edge by a factor for its road class,
+/// which biases the search toward or away from classes of road. a factor
+/// above 1 discourages a class and a factor below 1 encourages it. only the
+/// cost used to order the search is scaled; the traversal state, such as
+/// time and distance, is unaffected.
+#[derive(Clone, Debug)]
+pub struct RoadClassBias {
+    road_classes: Arc<Box<[u8]>>,
+    factors: HashMap<u8, f64>,
+    min_factor: f64,
+}
+
+impl RoadClassBias {
+    /// # Arguments
+    ///
+    /// * `road_classes` - the road class of each edge, indexed by `EdgeId`
+    /// * `factors` - cost multiplier for each road class, classes without a factor use 1
+    ///
+    /// # Returns
+    ///
+    /// the bias, or an error if a factor is not a positive, finite number
+    pub fn new(
+        road_classes: Arc<Box<[u8]>>,
+        factors: HashMap<u8, f64>,
+    ) -> Result<RoadClassBias, CostError> {
+        if let Some((road_class, factor)) =
+            factors.iter().find(|(_, f)| !f.is_finite() || **f <= 0.0)
+        {
+            return Err(CostError::InvalidConfiguration(format!(
+                "road class {} has factor {}, factors must be positive",
+                road_class, factor
+            )));
+        }
+        let min_factor = factors.values().fold(1.0, |a: f64, b| a.min(*b));
+        Ok(RoadClassBias {
+            road_classes,
+            factors,
+            min_factor,
+        })
+    }
+
+    /// the cost multiplier for an edge. edges missing from the road class
+    /// table, or whose class has no factor, are not scaled.
+    pub fn factor(&self, edge_id: EdgeId) -> f64 {
+        self.road_classes
+            .get(edge_id.0)
+            .and_then(|road_class| self.factors.get(road_class))
+            .cloned()
+            .unwrap_or(1.0)
+    }
+
+    /// the smallest multiplier applied to any edge, capped at 1. cost
+    /// estimates are scaled by this value so that they never exceed the
+    /// biased cost of a route, keeping the A* heuristic admissible.
+    pub fn min_factor(&self) -> f64 {
+        self.min_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factors_by_road_class() {
+        let road_classes = Arc::new(vec![1, 2, 3].into_boxed_slice());
+        let bias =
+            RoadClassBias::new(road_classes.clone(), HashMap::from([(1, 0.5), (3, 2.0)])).unwrap();
+        assert_eq!(bias.factor(EdgeId(0)), 0.5);
+        assert_eq!(bias.factor(EdgeId(1)), 1.0);
+        assert_eq!(bias.factor(EdgeId(2)), 2.0);
+        assert_eq!(bias.factor(EdgeId(9)), 1.0);
+        assert_eq!(bias.min_factor(), 0.5);
+
+        let invalid = RoadClassBias::new(road_classes, HashMap::from([(1, 0.0)]));
+        assert!(matches!(invalid, Err(CostError::InvalidConfiguration(_))));
+    }
+}
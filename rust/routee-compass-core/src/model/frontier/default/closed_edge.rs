@@ -0,0 +1,58 @@
+use crate::model::{
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+};
+use std::sync::Arc;
+
+/// rejects any edge flagged as `closed` in the edge list.
+#[derive(Clone)]
+pub struct ClosedEdgeRestriction {}
+
+impl FrontierModel for ClosedEdgeRestriction {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _state: &[StateVar],
+        _previous_edge: Option<&Edge>,
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        Ok(!edge.closed)
+    }
+}
+
+impl FrontierModelService for ClosedEdgeRestriction {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        Ok(Arc::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closed_edge_is_rejected() {
+        let model = ClosedEdgeRestriction {};
+        let state_model = StateModel::empty();
+        let open = Edge::new(0, 0, 1, 10.0);
+        let closed = Edge {
+            closed: true,
+            ..Edge::new(1, 1, 2, 10.0)
+        };
+        assert!(model
+            .valid_frontier(&open, &[], None, &state_model)
+            .unwrap());
+        assert!(!model
+            .valid_frontier(&closed, &[], None, &state_model)
+            .unwrap());
+    }
+}
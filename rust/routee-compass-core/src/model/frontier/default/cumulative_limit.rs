@@ -0,0 +1,155 @@
+use crate::model::{
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+    unit::{Distance, DistanceUnit, Energy, EnergyUnit, Time, TimeUnit},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// the maximum accumulated value of a state feature, in an explicit unit.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum CumulativeLimit {
+    Time {
+        max: Time,
+        time_unit: TimeUnit,
+    },
+    Distance {
+        max: Distance,
+        distance_unit: DistanceUnit,
+    },
+    Energy {
+        max: Energy,
+        energy_unit: EnergyUnit,
+    },
+}
+
+/// rejects any edge whose traversal would push an accumulated state feature
+/// past a limit, such as a legal limit on hours driven before a mandatory rest.
+/// the check uses the state after traversing the edge, so a route may reach
+/// the limit exactly but never exceed it.
+#[derive(Clone)]
+pub struct CumulativeLimitFrontierModel {
+    /// name of the state feature to limit
+    pub feature: String,
+    pub limit: CumulativeLimit,
+}
+
+impl FrontierModel for CumulativeLimitFrontierModel {
+    fn valid_traversal(
+        &self,
+        _edge: &Edge,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        let state_err = |e: crate::model::state::state_error::StateError| {
+            FrontierModelError::StateError(e.to_string())
+        };
+        let within_limit = match &self.limit {
+            CumulativeLimit::Time { max, time_unit } => {
+                state_model
+                    .get_time(state, &self.feature, time_unit)
+                    .map_err(state_err)?
+                    <= *max
+            }
+            CumulativeLimit::Distance { max, distance_unit } => {
+                state_model
+                    .get_distance(state, &self.feature, distance_unit)
+                    .map_err(state_err)?
+                    <= *max
+            }
+            CumulativeLimit::Energy { max, energy_unit } => {
+                state_model
+                    .get_energy(state, &self.feature, energy_unit)
+                    .map_err(state_err)?
+                    <= *max
+            }
+        };
+        Ok(within_limit)
+    }
+}
+
+impl FrontierModelService for CumulativeLimitFrontierModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        if !state_model.contains_key(&self.feature) {
+            return Err(FrontierModelError::BuildError(format!(
+                "cumulative limit feature '{}' is not in the state model, found: {}",
+                self.feature,
+                state_model.get_names()
+            )));
+        }
+        Ok(Arc::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::state::state_feature::StateFeature;
+
+    #[test]
+    fn test_rejects_traversal_exceeding_limit() {
+        let model = CumulativeLimitFrontierModel {
+            feature: String::from("time"),
+            limit: CumulativeLimit::Time {
+                max: Time::new(11.0),
+                time_unit: TimeUnit::Hours,
+            },
+        };
+        // time accumulates in minutes while the limit is in hours
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                },
+            )])
+            .unwrap();
+        let edge = Edge::new(0, 0, 1, 1000.0);
+        let mut state = state_model.initial_state().unwrap();
+        let traverse = |state: &mut Vec<StateVar>, minutes: f64| {
+            state_model
+                .add_time(
+                    state,
+                    &"time".into(),
+                    &Time::new(minutes),
+                    &TimeUnit::Minutes,
+                )
+                .unwrap();
+            model.valid_traversal(&edge, state, &state_model).unwrap()
+        };
+
+        // 10.5 hours, then 10 hours and 59 minutes, are within the limit
+        assert!(traverse(&mut state, 630.0));
+        assert!(traverse(&mut state, 29.0));
+        // the pre-traversal frontier check cannot see the next edge, so it passes
+        assert!(model
+            .valid_frontier(&edge, &state, None, &state_model)
+            .unwrap());
+        // two more minutes would exceed the limit
+        assert!(!traverse(&mut state, 2.0));
+    }
+
+    #[test]
+    fn test_build_requires_feature_in_state_model() {
+        let model = CumulativeLimitFrontierModel {
+            feature: String::from("time"),
+            limit: CumulativeLimit::Time {
+                max: Time::new(11.0),
+                time_unit: TimeUnit::Hours,
+            },
+        };
+        let result = model.build(&serde_json::json!({}), Arc::new(StateModel::empty()));
+        assert!(result.is_err());
+    }
+}
@@ -1 +1,3 @@
+pub mod closed_edge;
+pub mod cumulative_limit;
 pub mod no_restriction;
@@ -1,4 +1,5 @@
 use super::frontier_model_error::FrontierModelError;
+use crate::algorithm::search::direction::Direction;
 use crate::model::{
     property::edge::Edge, state::state_model::StateModel,
     traversal::state::state_variable::StateVar,
@@ -11,7 +12,10 @@ use crate::model::{
 ///
 /// [TraversalModel]: crate::model::traversal::traversal_model::TraversalModel
 pub trait FrontierModel: Send + Sync {
-    /// Validates an edge before allowing it to be added to the frontier.
+    /// Validates an edge before allowing it to be added to the frontier. This is
+    /// called before the edge is traversed, so the state does not yet include
+    /// the edge. Use [`FrontierModel::valid_traversal`] for checks that depend
+    /// on the state after traversing the edge.
     ///
     /// # Arguments
     ///
@@ -31,4 +35,65 @@ pub trait FrontierModel: Send + Sync {
     ) -> Result<bool, FrontierModelError> {
         Ok(true)
     }
+
+    /// Validates an edge for a search running in a given direction. In a
+    /// reverse search, edges are expanded from the destination, so the
+    /// previous edge follows this edge along the route and the first edge
+    /// expanded is the last edge of the route. The default treats both
+    /// directions alike; override it when a check only holds going forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the edge to traverse
+    /// * `state` - the state of the traversal at the beginning of this edge
+    /// * `previous_edge` - the edge that was expanded before this edge
+    /// * `direction` - the direction of the search
+    ///
+    /// # Returns
+    ///
+    /// True if the edge is valid, false otherwise; Or, an error from processing
+    fn valid_frontier_in_direction(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        _direction: &Direction,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        self.valid_frontier(edge, state, previous_edge, state_model)
+    }
+
+    /// Validates the result of traversing an edge before it is added to the
+    /// frontier. This is called after [`FrontierModel::valid_frontier`] accepts
+    /// the edge and the edge has been traversed, which allows limits on
+    /// accumulated state such as the total time driven.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the edge that was traversed
+    /// * `state` - the state of the traversal at the end of this edge
+    /// * `state_model` - provides access to the state vector
+    ///
+    /// # Returns
+    ///
+    /// True if the traversal is valid, false otherwise; Or, an error from processing
+    fn valid_traversal(
+        &self,
+        _edge: &Edge,
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        Ok(true)
+    }
+
+    /// Describes the edges this model has rejected for the current query, such as
+    /// which restriction types were triggered. Models that do not track their
+    /// rejections return None.
+    ///
+    /// # Returns
+    ///
+    /// A JSON description of the rejections observed so far, if tracked
+    fn rejection_summary(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
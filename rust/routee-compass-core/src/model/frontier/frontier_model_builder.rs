@@ -1,3 +1,4 @@
+use crate::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use super::{
@@ -17,6 +18,7 @@ pub trait FrontierModelBuilder {
     /// # Arguments
     ///
     /// * `parameters` - the contents of the "frontier" TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
     ///
     /// # Returns
     ///
@@ -26,5 +28,6 @@ pub trait FrontierModelBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError>;
 }
@@ -4,4 +4,6 @@ pub enum FrontierModelError {
     BuildError(String),
     #[error("edge id {0} missing from frontier model file")]
     MissingIndex(String),
+    #[error("failure reading search state in frontier model: {0}")]
+    StateError(String),
 }
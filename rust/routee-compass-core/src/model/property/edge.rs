@@ -5,13 +5,21 @@ use serde::{Deserialize, Serialize};
 
 /// represents a single edge in a Graph.
 /// this struct implements Serialize and Deserialize to support reading
-/// edge records from CSV files.
+/// edge records from CSV files. common names for the vertex id and distance
+/// columns used by third-party edge lists are accepted as aliases.
 #[derive(Copy, Clone, Serialize, Deserialize, Debug, Allocative)]
 pub struct Edge {
     pub edge_id: EdgeId,
+    #[serde(alias = "from", alias = "src", alias = "u")]
     pub src_vertex_id: VertexId,
+    #[serde(alias = "to", alias = "dst", alias = "v")]
     pub dst_vertex_id: VertexId,
+    #[serde(alias = "length_m", alias = "length", alias = "dist")]
     pub distance: Distance,
+    /// true if the edge is closed to traffic, such as for construction.
+    /// edge lists without a `closed` column load every edge as open.
+    #[serde(default)]
+    pub closed: bool,
 }
 
 impl Edge {
@@ -21,6 +29,7 @@ impl Edge {
             src_vertex_id: VertexId(src_vertex_id),
             dst_vertex_id: VertexId(dst_vertex_id),
             distance: Distance::new(distance),
+            closed: false,
         }
     }
 }
@@ -32,6 +41,7 @@ impl Default for Edge {
             src_vertex_id: VertexId(0),
             dst_vertex_id: VertexId(1),
             distance: Distance::ONE,
+            closed: false,
         }
     }
 }
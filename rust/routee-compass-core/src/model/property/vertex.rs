@@ -102,7 +102,13 @@ impl<'de> de::Deserialize<'de> for Vertex {
                     match vertex_id_result.zip(x_result).zip(y_result) {
                         Some(((vertex_id, x), y)) => {
                             // we're done; build the vertex and short-circuit the loop
-                            vertex_result = Some(Vertex::new(vertex_id, x, y));
+                            let coordinate = InternalCoord::new(x, y).map_err(|e| {
+                                de::Error::custom(format!("invalid vertex {}: {}", vertex_id, e))
+                            })?;
+                            vertex_result = Some(Vertex {
+                                vertex_id: VertexId(vertex_id),
+                                coordinate,
+                            });
                             next = None;
                         }
                         None => {
@@ -187,4 +193,13 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_deserialize_rejects_swapped_coordinates() {
+        let input = "vertex_id,x,y\n0,39.71221,-105.20423\n";
+        let mut reader = csv::Reader::from_reader(input.as_bytes());
+        let result = reader.deserialize::<Vertex>().next().unwrap();
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("invalid vertex 0"), "{}", error);
+    }
 }
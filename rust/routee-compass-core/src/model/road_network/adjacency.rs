@@ -0,0 +1,140 @@
+use super::{csr_adjacency::CsrAdjacency, edge_id::EdgeId, vertex_id::VertexId};
+use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use allocative::Allocative;
+
+/// the adjacency list of one direction of a `Graph`, indexed by `VertexId`.
+/// see [`super::adjacency_representation::AdjacencyRepresentation`] for
+/// choosing between the two representations.
+#[derive(Debug, Allocative)]
+pub enum Adjacency {
+    HashMap(Box<[CompactOrderedHashMap<EdgeId, VertexId>]>),
+    Csr(CsrAdjacency),
+}
+
+impl Adjacency {
+    /// number of vertices in the adjacency list
+    pub fn n_vertices(&self) -> usize {
+        match self {
+            Adjacency::HashMap(maps) => maps.len(),
+            Adjacency::Csr(csr) => csr.n_vertices(),
+        }
+    }
+
+    /// the `EdgeId`s adjacent to a vertex, or None if the vertex is missing
+    pub fn edge_ids(&self, vertex_id: VertexId) -> Option<Box<dyn Iterator<Item = &EdgeId> + '_>> {
+        match self {
+            Adjacency::HashMap(maps) => maps.get(vertex_id.0).map(|m| m.keys()),
+            Adjacency::Csr(csr) => {
+                csr.get(vertex_id)
+                    .map(|(edge_ids, _)| -> Box<dyn Iterator<Item = &EdgeId>> {
+                        Box::new(edge_ids.iter())
+                    })
+            }
+        }
+    }
+
+    /// the (`EdgeId`, adjacent `VertexId`) pairs of a vertex, or None if the
+    /// vertex is missing
+    pub fn entries(
+        &self,
+        vertex_id: VertexId,
+    ) -> Option<Box<dyn Iterator<Item = (EdgeId, VertexId)> + '_>> {
+        match self {
+            Adjacency::HashMap(maps) => {
+                maps.get(vertex_id.0)
+                    .map(|m| -> Box<dyn Iterator<Item = (EdgeId, VertexId)>> {
+                        Box::new(m.iter().map(|(e, v)| (*e, *v)))
+                    })
+            }
+            Adjacency::Csr(csr) => csr.get(vertex_id).map(
+                |(edge_ids, vertex_ids)| -> Box<dyn Iterator<Item = (EdgeId, VertexId)>> {
+                    Box::new(edge_ids.iter().copied().zip(vertex_ids.iter().copied()))
+                },
+            ),
+        }
+    }
+
+    /// the vertex adjacent to `vertex_id` along `edge_id`, if the edge is in
+    /// the adjacency list of the vertex
+    pub fn get(&self, vertex_id: VertexId, edge_id: EdgeId) -> Option<VertexId> {
+        match self {
+            Adjacency::HashMap(maps) => maps.get(vertex_id.0)?.get(&edge_id).copied(),
+            Adjacency::Csr(csr) => {
+                let (edge_ids, vertex_ids) = csr.get(vertex_id)?;
+                let idx = edge_ids.iter().position(|e| *e == edge_id)?;
+                Some(vertex_ids[idx])
+            }
+        }
+    }
+
+    /// true if `edge_id` is in the adjacency list of `vertex_id`
+    pub fn contains(&self, vertex_id: VertexId, edge_id: EdgeId) -> bool {
+        self.get(vertex_id, edge_id).is_some()
+    }
+}
+
+impl From<Box<[CompactOrderedHashMap<EdgeId, VertexId>]>> for Adjacency {
+    fn from(maps: Box<[CompactOrderedHashMap<EdgeId, VertexId>]>) -> Self {
+        Adjacency::HashMap(maps)
+    }
+}
+
+impl From<CsrAdjacency> for Adjacency {
+    fn from(csr: CsrAdjacency) -> Self {
+        Adjacency::Csr(csr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// forward adjacency of a grid where each vertex links to its right and
+    /// lower neighbors, as (src, edge, dst) entries
+    fn grid_entries(n: usize) -> Vec<(VertexId, EdgeId, VertexId)> {
+        let mut entries = vec![];
+        for row in 0..n {
+            for col in 0..n {
+                let src = row * n + col;
+                if col + 1 < n {
+                    entries.push((VertexId(src), EdgeId(entries.len()), VertexId(src + 1)));
+                }
+                if row + 1 < n {
+                    entries.push((VertexId(src), EdgeId(entries.len()), VertexId(src + n)));
+                }
+            }
+        }
+        entries
+    }
+
+    #[test]
+    fn test_representations_agree_and_csr_is_smaller() {
+        let n_vertices = 100 * 100;
+        let entries = grid_entries(100);
+        let mut maps = vec![CompactOrderedHashMap::empty(); n_vertices];
+        for (src, edge_id, dst) in entries.iter() {
+            maps[src.0].insert(*edge_id, *dst);
+        }
+        let hash_map = Adjacency::from(maps.into_boxed_slice());
+        let csr = Adjacency::from(
+            CsrAdjacency::from_entries(n_vertices, || entries.iter().copied()).unwrap(),
+        );
+
+        for vertex_id in (0..n_vertices).map(VertexId) {
+            let expected = hash_map.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            let actual = csr.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            assert_eq!(expected, actual);
+        }
+        assert_eq!(csr.get(VertexId(0), EdgeId(1)), Some(VertexId(100)));
+        assert!(!csr.contains(VertexId(1), EdgeId(0)));
+
+        let hash_map_bytes = allocative::size_of_unique(&hash_map);
+        let csr_bytes = allocative::size_of_unique(&csr);
+        assert!(
+            csr_bytes < hash_map_bytes,
+            "csr: {} bytes, hash map: {} bytes",
+            csr_bytes,
+            hash_map_bytes
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// describes how the graph loader stores the adjacency lists. the hash map
+/// representation supports fast membership checks on small graphs, while
+/// the compressed sparse row (CSR) representation stores each direction in
+/// three flat arrays, avoiding the per-vertex overhead of a map when
+/// loading very large edge lists.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjacencyRepresentation {
+    /// one ordered hash map of out edges per vertex
+    #[default]
+    HashMap,
+    /// compressed sparse row arrays, see [`super::csr_adjacency::CsrAdjacency`]
+    Csr,
+}
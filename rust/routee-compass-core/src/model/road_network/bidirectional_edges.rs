@@ -0,0 +1,96 @@
+use super::edge_id::EdgeId;
+use crate::model::property::edge::Edge;
+use std::collections::HashMap;
+
+/// assigns the synthetic id of the reverse of an edge row when an edge list
+/// is loaded as bidirectional. the edge list keeps its ids `0..n_rows`, and
+/// the reverse of edge `i` is appended as edge `n_rows + i`, so the graph
+/// holds `2 * n_rows` edges and every id remains a valid index.
+///
+/// # Arguments
+///
+/// * `edge_id` - id of the edge row as it appears in the edge list
+/// * `n_rows` - number of rows in the edge list
+///
+/// # Returns
+///
+/// the id of the reverse edge
+pub fn reverse_edge_id(edge_id: EdgeId, n_rows: usize) -> EdgeId {
+    EdgeId(edge_id.0 + n_rows)
+}
+
+/// creates the reverse of an edge row, with swapped vertices, the same
+/// distance and closure, and a synthetic id from [`reverse_edge_id`].
+pub fn reverse_edge(edge: &Edge, n_rows: usize) -> Edge {
+    Edge {
+        edge_id: reverse_edge_id(edge.edge_id, n_rows),
+        src_vertex_id: edge.dst_vertex_id,
+        dst_vertex_id: edge.src_vertex_id,
+        distance: edge.distance,
+        closed: edge.closed,
+    }
+}
+
+/// extends a table with one row per edge, such as a speed or grade table,
+/// so that it covers the synthetic reverse edges. the reverse rows are
+/// appended in the same order as the edge list, matching [`reverse_edge_id`].
+///
+/// # Arguments
+///
+/// * `rows` - table rows for the edges as they appear in the edge list
+/// * `reverse` - creates the row of the reverse edge, for example by negating a grade
+///
+/// # Returns
+///
+/// a table with `2 * rows.len()` rows
+pub fn mirror_table<T: Clone>(rows: &[T], reverse: impl Fn(&T) -> T) -> Box<[T]> {
+    let mut mirrored = Vec::with_capacity(rows.len() * 2);
+    mirrored.extend(rows.iter().cloned());
+    mirrored.extend(rows.iter().map(reverse));
+    mirrored.into_boxed_slice()
+}
+
+/// extends a table keyed by edge id, such as a set of per-edge overrides, so
+/// that every entry also applies to the reverse of its edge.
+///
+/// # Arguments
+///
+/// * `entries` - table entries for the edges as they appear in the edge list
+/// * `n_rows` - number of rows in the edge list
+///
+/// # Returns
+///
+/// a table holding each entry under both the edge id and its reverse edge id
+pub fn mirror_edge_map<T: Clone>(
+    entries: &HashMap<EdgeId, T>,
+    n_rows: usize,
+) -> HashMap<EdgeId, T> {
+    let mut mirrored = HashMap::with_capacity(entries.len() * 2);
+    for (edge_id, value) in entries.iter() {
+        mirrored.insert(*edge_id, value.clone());
+        mirrored.insert(reverse_edge_id(*edge_id, n_rows), value.clone());
+    }
+    mirrored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_table() {
+        let grades: Box<[f64]> = vec![0.05, -0.02, 0.0].into_boxed_slice();
+        let mirrored = mirror_table(&grades, |g| -g);
+        assert_eq!(&mirrored[..], &[0.05, -0.02, 0.0, -0.05, 0.02, -0.0]);
+        assert_eq!(reverse_edge_id(EdgeId(1), 3), EdgeId(4));
+    }
+
+    #[test]
+    fn test_mirror_edge_map() {
+        let overrides = HashMap::from([(EdgeId(1), "a")]);
+        let mirrored = mirror_edge_map(&overrides, 3);
+        assert_eq!(mirrored.len(), 2);
+        assert_eq!(mirrored.get(&EdgeId(1)), Some(&"a"));
+        assert_eq!(mirrored.get(&EdgeId(4)), Some(&"a"));
+    }
+}
@@ -0,0 +1,184 @@
+use super::{edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId};
+use allocative::Allocative;
+
+/// an adjacency list in compressed sparse row (CSR) form. the entries of
+/// every vertex are stored contiguously, and `offsets[v]..offsets[v + 1]`
+/// is the range of entries for `VertexId(v)`. entries of a vertex keep the
+/// order in which they were provided.
+#[derive(Debug, Allocative)]
+pub struct CsrAdjacency {
+    offsets: Box<[usize]>,
+    edge_ids: Box<[EdgeId]>,
+    vertex_ids: Box<[VertexId]>,
+}
+
+impl CsrAdjacency {
+    /// builds a CSR adjacency list in two passes over its entries: the first
+    /// counts the entries of each vertex and the second writes them in place,
+    /// so no per-vertex collections are allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_vertices` - number of vertices in the graph
+    /// * `entries` - produces an iterator of (vertex, edge, adjacent vertex)
+    ///   entries, called once per pass
+    ///
+    /// # Returns
+    ///
+    /// the adjacency list, or an error if an entry refers to a vertex outside
+    /// of the graph
+    pub fn from_entries<F, I>(n_vertices: usize, entries: F) -> Result<CsrAdjacency, GraphError>
+    where
+        F: Fn() -> I,
+        I: Iterator<Item = (VertexId, EdgeId, VertexId)>,
+    {
+        let mut builder = CsrAdjacencyBuilder::new(n_vertices);
+        for (vertex_id, _, _) in entries() {
+            builder.count(vertex_id)?;
+        }
+        builder.allocate();
+        for (vertex_id, edge_id, adjacent) in entries() {
+            builder.insert(vertex_id, edge_id, adjacent)?;
+        }
+        Ok(builder.build())
+    }
+
+    /// number of vertices in the adjacency list
+    pub fn n_vertices(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// the edges and adjacent vertices of a vertex, or None if the vertex
+    /// is not part of the adjacency list
+    pub fn get(&self, vertex_id: VertexId) -> Option<(&[EdgeId], &[VertexId])> {
+        let start = *self.offsets.get(vertex_id.0)?;
+        let end = *self.offsets.get(vertex_id.0 + 1)?;
+        Some((&self.edge_ids[start..end], &self.vertex_ids[start..end]))
+    }
+}
+
+/// builds a [`CsrAdjacency`] from two passes over a stream of entries, such
+/// as the rows of an edge list as they are read. the first pass calls
+/// [`CsrAdjacencyBuilder::count`] for each entry, [`CsrAdjacencyBuilder::allocate`]
+/// sizes the arrays, and the second pass calls [`CsrAdjacencyBuilder::insert`]
+/// with the same entries in the same order.
+pub struct CsrAdjacencyBuilder {
+    offsets: Vec<usize>,
+    cursors: Vec<usize>,
+    edge_ids: Vec<EdgeId>,
+    vertex_ids: Vec<VertexId>,
+}
+
+impl CsrAdjacencyBuilder {
+    pub fn new(n_vertices: usize) -> CsrAdjacencyBuilder {
+        CsrAdjacencyBuilder {
+            offsets: vec![0; n_vertices + 1],
+            cursors: vec![],
+            edge_ids: vec![],
+            vertex_ids: vec![],
+        }
+    }
+
+    /// counts an entry of a vertex during the first pass
+    pub fn count(&mut self, vertex_id: VertexId) -> Result<(), GraphError> {
+        let n_vertices = self.offsets.len() - 1;
+        if vertex_id.0 >= n_vertices {
+            return Err(GraphError::AdjacencyVertexMissing(vertex_id));
+        }
+        self.offsets[vertex_id.0 + 1] += 1;
+        Ok(())
+    }
+
+    /// ends the first pass, allocating room for every counted entry
+    pub fn allocate(&mut self) {
+        for idx in 1..self.offsets.len() {
+            self.offsets[idx] += self.offsets[idx - 1];
+        }
+        let n_vertices = self.offsets.len() - 1;
+        let n_entries = self.offsets[n_vertices];
+        self.cursors = self.offsets[..n_vertices].to_vec();
+        self.edge_ids = vec![EdgeId::default(); n_entries];
+        self.vertex_ids = vec![VertexId::default(); n_entries];
+    }
+
+    /// writes an entry of a vertex during the second pass
+    pub fn insert(
+        &mut self,
+        vertex_id: VertexId,
+        edge_id: EdgeId,
+        adjacent: VertexId,
+    ) -> Result<(), GraphError> {
+        let end = *self
+            .offsets
+            .get(vertex_id.0 + 1)
+            .ok_or(GraphError::AdjacencyVertexMissing(vertex_id))?;
+        let cursor = self
+            .cursors
+            .get_mut(vertex_id.0)
+            .ok_or(GraphError::AdjacencyVertexMissing(vertex_id))?;
+        if *cursor >= end {
+            return Err(GraphError::AdjacencyEntriesExceeded(vertex_id));
+        }
+        self.edge_ids[*cursor] = edge_id;
+        self.vertex_ids[*cursor] = adjacent;
+        *cursor += 1;
+        Ok(())
+    }
+
+    pub fn build(self) -> CsrAdjacency {
+        CsrAdjacency {
+            offsets: self.offsets.into_boxed_slice(),
+            edge_ids: self.edge_ids.into_boxed_slice(),
+            vertex_ids: self.vertex_ids.into_boxed_slice(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_grouped_by_vertex() {
+        let entries = [
+            (VertexId(2), EdgeId(0), VertexId(0)),
+            (VertexId(0), EdgeId(1), VertexId(1)),
+            (VertexId(2), EdgeId(2), VertexId(1)),
+        ];
+        let csr = CsrAdjacency::from_entries(3, || entries.iter().copied()).unwrap();
+        assert_eq!(csr.n_vertices(), 3);
+        assert_eq!(
+            csr.get(VertexId(0)),
+            Some((&[EdgeId(1)][..], &[VertexId(1)][..]))
+        );
+        assert_eq!(csr.get(VertexId(1)), Some((&[][..], &[][..])));
+        assert_eq!(
+            csr.get(VertexId(2)),
+            Some((&[EdgeId(0), EdgeId(2)][..], &[VertexId(0), VertexId(1)][..]))
+        );
+        assert_eq!(csr.get(VertexId(3)), None);
+    }
+
+    #[test]
+    fn test_vertex_out_of_range() {
+        let entries = [(VertexId(5), EdgeId(0), VertexId(0))];
+        let result = CsrAdjacency::from_entries(3, || entries.iter().copied());
+        assert!(matches!(
+            result,
+            Err(GraphError::AdjacencyVertexMissing(VertexId(5)))
+        ));
+    }
+
+    #[test]
+    fn test_insert_beyond_count() {
+        let mut builder = CsrAdjacencyBuilder::new(2);
+        builder.count(VertexId(0)).unwrap();
+        builder.allocate();
+        builder.insert(VertexId(0), EdgeId(0), VertexId(1)).unwrap();
+        let result = builder.insert(VertexId(0), EdgeId(1), VertexId(1));
+        assert!(matches!(
+            result,
+            Err(GraphError::AdjacencyEntriesExceeded(VertexId(0)))
+        ));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// describes how the graph loader treats degenerate edges, which are edges
+/// that form a self-loop (`src_vertex_id == dst_vertex_id`) or that have a
+/// distance of zero.
+///
+/// in both cases, the edge remains in the `edges` collection so that
+/// `EdgeId`s can still be used as indices, and the number of degenerate
+/// edges found is always reported.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DegenerateEdgePolicy {
+    /// keep degenerate edges in the adjacency lists and log a warning
+    #[default]
+    Flag,
+    /// exclude degenerate edges from the adjacency lists so that they
+    /// can never be traversed during a search
+    Drop,
+}
@@ -0,0 +1,59 @@
+use super::{degenerate_edge_policy::DegenerateEdgePolicy, edge_id::EdgeId};
+use crate::model::{property::edge::Edge, unit::Distance};
+use serde::Serialize;
+
+/// summary of the degenerate edges found while loading a graph.
+/// an edge that is both a self-loop and zero-distance is counted once
+/// in each category.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DegenerateEdgeReport {
+    pub policy: DegenerateEdgePolicy,
+    pub self_loops: Vec<EdgeId>,
+    pub zero_distance: Vec<EdgeId>,
+}
+
+impl DegenerateEdgeReport {
+    pub fn new(policy: DegenerateEdgePolicy) -> DegenerateEdgeReport {
+        DegenerateEdgeReport {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// inspects an edge, recording it if it is degenerate.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the edge to inspect
+    ///
+    /// # Returns
+    ///
+    /// true if the edge is a self-loop or has zero distance
+    pub fn inspect(&mut self, edge: &Edge) -> bool {
+        let is_self_loop = edge.src_vertex_id == edge.dst_vertex_id;
+        let is_zero_distance = edge.distance == Distance::ZERO;
+        if is_self_loop {
+            self.self_loops.push(edge.edge_id);
+        }
+        if is_zero_distance {
+            self.zero_distance.push(edge.edge_id);
+        }
+        is_self_loop || is_zero_distance
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.self_loops.is_empty() && self.zero_distance.is_empty()
+    }
+}
+
+impl std::fmt::Display for DegenerateEdgeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "found {} self-loop edges and {} zero-distance edges (policy: {:?})",
+            self.self_loops.len(),
+            self.zero_distance.len(),
+            self.policy
+        )
+    }
+}
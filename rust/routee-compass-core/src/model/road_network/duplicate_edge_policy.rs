@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// describes how the graph loader treats an `edge_id` that appears on more
+/// than one row of the edge list. since `EdgeId`s are used as indices, a
+/// duplicate id means one of the rows cannot be addressed, which almost
+/// always indicates a broken export.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateEdgePolicy {
+    /// fail to load the graph
+    #[default]
+    Error,
+    /// load the graph and log a warning
+    Warn,
+}
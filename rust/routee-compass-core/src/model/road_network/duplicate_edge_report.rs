@@ -0,0 +1,86 @@
+use super::edge_id::EdgeId;
+
+/// number of duplicate `EdgeId`s listed in a report
+const MAX_REPORTED_DUPLICATES: usize = 10;
+
+/// tracks the `EdgeId`s seen while loading an edge list in a bitset, so
+/// that detecting duplicates costs one bit per edge.
+#[derive(Clone, Debug, Default)]
+pub struct DuplicateEdgeReport {
+    seen: Vec<u64>,
+    /// the first duplicate ids found, in file order
+    pub duplicates: Vec<EdgeId>,
+    /// total number of rows with an id that was already seen
+    pub n_duplicates: usize,
+}
+
+impl DuplicateEdgeReport {
+    pub fn new(n_edges: usize) -> DuplicateEdgeReport {
+        DuplicateEdgeReport {
+            seen: vec![0; n_edges.div_ceil(64)],
+            ..Default::default()
+        }
+    }
+
+    /// records an edge id, returning true if it was already seen.
+    pub fn inspect(&mut self, edge_id: EdgeId) -> bool {
+        let (word, bit) = (edge_id.0 / 64, edge_id.0 % 64);
+        if word >= self.seen.len() {
+            self.seen.resize(word + 1, 0);
+        }
+        let mask = 1_u64 << bit;
+        let is_duplicate = self.seen[word] & mask != 0;
+        self.seen[word] |= mask;
+        if is_duplicate {
+            self.n_duplicates += 1;
+            if self.duplicates.len() < MAX_REPORTED_DUPLICATES {
+                self.duplicates.push(edge_id);
+            }
+        }
+        is_duplicate
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n_duplicates == 0
+    }
+}
+
+impl std::fmt::Display for DuplicateEdgeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ids = self
+            .duplicates
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = if self.n_duplicates > self.duplicates.len() {
+            ", ..."
+        } else {
+            ""
+        };
+        write!(
+            f,
+            "found {} rows with a duplicate edge_id: [{}{}]",
+            self.n_duplicates, ids, more
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_edge_report() {
+        let mut report = DuplicateEdgeReport::new(4);
+        for edge_id in [0, 1, 2, 1, 70, 70, 1] {
+            report.inspect(EdgeId(edge_id));
+        }
+        assert_eq!(report.n_duplicates, 3);
+        assert_eq!(report.duplicates, vec![EdgeId(1), EdgeId(70), EdgeId(1)]);
+        assert_eq!(
+            report.to_string(),
+            "found 3 rows with a duplicate edge_id: [1, 70, 1]"
+        );
+    }
+}
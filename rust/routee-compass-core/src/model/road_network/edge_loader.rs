@@ -1,73 +1,500 @@
+use crate::model::unit::{DistanceUnit, BASE_DISTANCE_UNIT};
 use crate::{
     model::{
         property::edge::Edge,
-        road_network::{edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId},
+        road_network::{
+            adjacency::Adjacency, adjacency_representation::AdjacencyRepresentation,
+            bidirectional_edges, csr_adjacency::CsrAdjacencyBuilder,
+            degenerate_edge_policy::DegenerateEdgePolicy,
+            degenerate_edge_report::DegenerateEdgeReport,
+            duplicate_edge_policy::DuplicateEdgePolicy, duplicate_edge_report::DuplicateEdgeReport,
+            edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId,
+        },
+    },
+    util::{
+        compact_ordered_hash_map::CompactOrderedHashMap,
+        fs::read_utils,
+        progress::{LoadProgress, ProgressCallback},
     },
-    util::{compact_ordered_hash_map::CompactOrderedHashMap, fs::read_utils},
 };
-use kdam::Bar;
-use kdam::BarExt;
 use std::{collections::HashSet, path::PathBuf};
 
 pub struct EdgeLoader {
     pub edges: Box<[Edge]>,
-    pub adj: Box<[CompactOrderedHashMap<EdgeId, VertexId>]>,
-    pub rev: Box<[CompactOrderedHashMap<EdgeId, VertexId>]>,
+    pub adj: Adjacency,
+    pub rev: Adjacency,
+    pub degenerate_edge_report: DegenerateEdgeReport,
+    pub duplicate_edge_report: DuplicateEdgeReport,
 }
 
 pub struct EdgeLoaderConfig {
     pub edge_list_csv: PathBuf,
     pub n_edges: usize,
     pub n_vertices: usize,
+    pub degenerate_edge_policy: DegenerateEdgePolicy,
+    /// unit of the distance column in the edge list, converted to the base
+    /// distance unit during load
+    pub distance_unit: DistanceUnit,
+    /// if true, each row is also loaded as its reverse edge with a synthetic
+    /// id, see [`bidirectional_edges::reverse_edge_id`]
+    pub bidirectional: bool,
+    pub duplicate_edge_policy: DuplicateEdgePolicy,
+    /// how the adjacency lists are stored
+    pub adjacency: AdjacencyRepresentation,
+    /// draw a progress bar while loading, unless a progress callback is provided
+    pub verbose: bool,
+    /// receives (rows loaded, n_edges) as the edge list is read
+    pub progress: Option<ProgressCallback>,
 }
 
 impl TryFrom<EdgeLoaderConfig> for EdgeLoader {
     type Error = GraphError;
 
     fn try_from(c: EdgeLoaderConfig) -> Result<Self, Self::Error> {
-        let mut adj: Vec<CompactOrderedHashMap<EdgeId, VertexId>> =
-            vec![CompactOrderedHashMap::empty(); c.n_vertices];
-        let mut rev: Vec<CompactOrderedHashMap<EdgeId, VertexId>> =
-            vec![CompactOrderedHashMap::empty(); c.n_vertices];
-
-        let mut pb = Bar::builder()
-            .total(c.n_edges)
-            .animation("fillup")
-            .desc("edge list")
-            .build()
+        let rows = read_utils::iterator_from_csv::<_, Edge>(&c.edge_list_csv, true, None)?;
+        EdgeLoader::from_rows(c, rows)
+    }
+}
+
+impl EdgeLoader {
+    /// loads the edge list in two passes. the first streams the rows into the
+    /// edge array of the graph, recording degenerate and duplicate edges and
+    /// counting the adjacency entries of each vertex. the second replays the
+    /// accepted rows to write the adjacency lists, so both representations
+    /// hold the same entries: under [`DuplicateEdgePolicy::Warn`], only the
+    /// first row with an edge id is traversable.
+    ///
+    /// the edge array is sized once for every row and, for a bidirectional
+    /// graph, every reverse edge, which are appended in place. no other copy
+    /// of the edge list is made, so with the CSR representation the memory
+    /// used while loading is the edge array and adjacency arrays of the
+    /// finished graph, along with a count per vertex and the ids of any
+    /// dropped edges.
+    pub fn from_rows<I>(c: EdgeLoaderConfig, rows: I) -> Result<EdgeLoader, GraphError>
+    where
+        I: Iterator<Item = Result<Edge, csv::Error>>,
+    {
+        let mut adj = AdjacencyBuilder::new(c.adjacency, c.n_vertices);
+        let mut rev = AdjacencyBuilder::new(c.adjacency, c.n_vertices);
+        let mut dropped_edges: HashSet<EdgeId> = HashSet::new();
+
+        let mut pb = LoadProgress::new(c.progress, c.verbose, "edge list", c.n_edges, 0)
             .map_err(|e| GraphError::ProgressBarBuildError(String::from("edge list"), e))?;
 
-        let mut missing_vertices: HashSet<VertexId> = HashSet::new();
-        let cb = Box::new(|edge: &Edge| {
-            // the Edge provides us with all id information to build our adjacency lists as well
-            match adj.get_mut(edge.src_vertex_id.0) {
-                None => {
-                    missing_vertices.insert(edge.src_vertex_id);
-                }
-                Some(out_links) => {
-                    out_links.insert(edge.edge_id, edge.dst_vertex_id);
-                }
+        let mut degenerate_edge_report = DegenerateEdgeReport::new(c.degenerate_edge_policy);
+        let mut duplicate_edge_report = DuplicateEdgeReport::new(c.n_edges);
+        let capacity = if c.bidirectional {
+            2 * c.n_edges
+        } else {
+            c.n_edges
+        };
+        let mut edges: Vec<Edge> = Vec::with_capacity(capacity);
+        for row in rows {
+            let mut edge = row?;
+            pb.update();
+            if c.distance_unit != BASE_DISTANCE_UNIT {
+                edge.distance = c.distance_unit.convert(&edge.distance, &BASE_DISTANCE_UNIT);
             }
-            match rev.get_mut(edge.dst_vertex_id.0) {
-                None => {
-                    missing_vertices.insert(edge.dst_vertex_id);
-                }
-                Some(in_links) => {
-                    in_links.insert(edge.edge_id, edge.src_vertex_id);
-                }
+            edges.push(edge);
+            let is_duplicate = duplicate_edge_report.inspect(edge.edge_id);
+            // degenerate edges are always recorded, but only added to the
+            // adjacency lists if the policy allows it
+            let is_degenerate = degenerate_edge_report.inspect(&edge);
+            if is_duplicate {
+                continue;
             }
-            let _ = pb.update(1);
-        });
-
-        let edges = read_utils::from_csv(&c.edge_list_csv, true, Some(cb))?;
+            if is_degenerate && c.degenerate_edge_policy == DegenerateEdgePolicy::Drop {
+                dropped_edges.insert(edge.edge_id);
+                continue;
+            }
+            // reverse ids are assigned once the number of rows is known, and
+            // are not needed to count entries
+            let reverse_id = c.bidirectional.then_some(EdgeId::default());
+            for (vertex_id, _, _) in row_entries(&edge, reverse_id, c.n_vertices, true) {
+                adj.count(vertex_id)?;
+            }
+            for (vertex_id, _, _) in row_entries(&edge, reverse_id, c.n_vertices, false) {
+                rev.count(vertex_id)?;
+            }
+        }
+        if !duplicate_edge_report.is_empty()
+            && c.duplicate_edge_policy == DuplicateEdgePolicy::Error
+        {
+            return Err(GraphError::DuplicateEdgeIds(
+                duplicate_edge_report.to_string(),
+            ));
+        }
+        adj.allocate();
+        rev.allocate();
+        let n_rows = edges.len();
+        let mut seen = DuplicateEdgeReport::new(n_rows);
+        for edge in edges.iter() {
+            if seen.inspect(edge.edge_id) || dropped_edges.contains(&edge.edge_id) {
+                continue;
+            }
+            let reverse_id = c
+                .bidirectional
+                .then(|| bidirectional_edges::reverse_edge_id(edge.edge_id, n_rows));
+            for (vertex_id, edge_id, adjacent) in row_entries(edge, reverse_id, c.n_vertices, true)
+            {
+                adj.insert(vertex_id, edge_id, adjacent)?;
+            }
+            for (vertex_id, edge_id, adjacent) in row_entries(edge, reverse_id, c.n_vertices, false)
+            {
+                rev.insert(vertex_id, edge_id, adjacent)?;
+            }
+        }
+        if c.bidirectional {
+            for idx in 0..n_rows {
+                let reverse = bidirectional_edges::reverse_edge(&edges[idx], n_rows);
+                edges.push(reverse);
+            }
+        }
 
-        println!();
         let result = EdgeLoader {
-            edges,
-            adj: adj.into_boxed_slice(),
-            rev: rev.into_boxed_slice(),
+            edges: edges.into_boxed_slice(),
+            adj: adj.build(),
+            rev: rev.build(),
+            degenerate_edge_report,
+            duplicate_edge_report,
         };
 
         Ok(result)
     }
 }
+
+/// the entries that an edge row adds to one direction of the adjacency
+/// lists, as (vertex, edge, adjacent vertex). the reverse of the row is
+/// included if a reverse id is provided, and only when both of its
+/// vertices are in the graph.
+///
+/// # Arguments
+///
+/// * `edge` - the edge row
+/// * `reverse_id` - id of the reverse of the row, for bidirectional edge lists
+/// * `n_vertices` - number of vertices in the graph
+/// * `outgoing` - true for the out adjacency lists, false for the in adjacency lists
+fn row_entries(
+    edge: &Edge,
+    reverse_id: Option<EdgeId>,
+    n_vertices: usize,
+    outgoing: bool,
+) -> impl Iterator<Item = (VertexId, EdgeId, VertexId)> {
+    let (src, dst) = (edge.src_vertex_id, edge.dst_vertex_id);
+    let (vertex_id, adjacent) = if outgoing { (src, dst) } else { (dst, src) };
+    let forward = (vertex_id.0 < n_vertices).then_some((vertex_id, edge.edge_id, adjacent));
+    let reverse = reverse_id
+        .filter(|_| src.0 < n_vertices && dst.0 < n_vertices)
+        .map(|reverse_id| (adjacent, reverse_id, vertex_id));
+    forward.into_iter().chain(reverse)
+}
+
+/// one direction of the adjacency lists while the edge list is loaded. the
+/// CSR representation is sized by counting entries before they are written,
+/// while the hash map representation ignores the counts.
+enum AdjacencyBuilder {
+    HashMap(Vec<CompactOrderedHashMap<EdgeId, VertexId>>),
+    Csr(CsrAdjacencyBuilder),
+}
+
+impl AdjacencyBuilder {
+    fn new(representation: AdjacencyRepresentation, n_vertices: usize) -> AdjacencyBuilder {
+        match representation {
+            AdjacencyRepresentation::HashMap => {
+                AdjacencyBuilder::HashMap(vec![CompactOrderedHashMap::empty(); n_vertices])
+            }
+            AdjacencyRepresentation::Csr => {
+                AdjacencyBuilder::Csr(CsrAdjacencyBuilder::new(n_vertices))
+            }
+        }
+    }
+
+    fn count(&mut self, vertex_id: VertexId) -> Result<(), GraphError> {
+        match self {
+            AdjacencyBuilder::HashMap(_) => Ok(()),
+            AdjacencyBuilder::Csr(csr) => csr.count(vertex_id),
+        }
+    }
+
+    fn allocate(&mut self) {
+        if let AdjacencyBuilder::Csr(csr) = self {
+            csr.allocate();
+        }
+    }
+
+    fn insert(
+        &mut self,
+        vertex_id: VertexId,
+        edge_id: EdgeId,
+        adjacent: VertexId,
+    ) -> Result<(), GraphError> {
+        match self {
+            AdjacencyBuilder::HashMap(maps) => {
+                let links = maps
+                    .get_mut(vertex_id.0)
+                    .ok_or(GraphError::AdjacencyVertexMissing(vertex_id))?;
+                links.insert(edge_id, adjacent);
+                Ok(())
+            }
+            AdjacencyBuilder::Csr(csr) => csr.insert(vertex_id, edge_id, adjacent),
+        }
+    }
+
+    fn build(self) -> Adjacency {
+        match self {
+            AdjacencyBuilder::HashMap(maps) => maps.into_boxed_slice().into(),
+            AdjacencyBuilder::Csr(csr) => csr.build().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::unit::as_f64::AsF64;
+    use std::sync::{Arc, Mutex};
+
+    fn filepath(filename: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+            .join(filename)
+    }
+
+    fn load(policy: DegenerateEdgePolicy) -> EdgeLoader {
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("degenerate_edges.csv"),
+            n_edges: 4,
+            n_vertices: 3,
+            degenerate_edge_policy: policy,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        EdgeLoader::try_from(conf).unwrap()
+    }
+
+    #[test]
+    fn test_flag_degenerate_edges() {
+        // edge 1 is a self-loop on vertex 1, edge 2 has zero distance
+        let loader = load(DegenerateEdgePolicy::Flag);
+        let report = &loader.degenerate_edge_report;
+        assert_eq!(report.self_loops, vec![EdgeId(1)]);
+        assert_eq!(report.zero_distance, vec![EdgeId(2)]);
+        assert_eq!(loader.edges.len(), 4);
+        assert!(loader.adj.contains(VertexId(1), EdgeId(1)));
+        assert!(loader.adj.contains(VertexId(1), EdgeId(2)));
+        assert!(loader.rev.contains(VertexId(2), EdgeId(2)));
+    }
+
+    #[test]
+    fn test_drop_degenerate_edges() {
+        let loader = load(DegenerateEdgePolicy::Drop);
+        let report = &loader.degenerate_edge_report;
+        assert_eq!(report.self_loops, vec![EdgeId(1)]);
+        assert_eq!(report.zero_distance, vec![EdgeId(2)]);
+        // dropped edges remain addressable by id but are not traversable
+        assert_eq!(loader.edges.len(), 4);
+        assert!(!loader.adj.contains(VertexId(1), EdgeId(1)));
+        assert!(!loader.rev.contains(VertexId(1), EdgeId(1)));
+        assert!(!loader.adj.contains(VertexId(1), EdgeId(2)));
+        assert!(!loader.rev.contains(VertexId(2), EdgeId(2)));
+        assert!(loader.adj.contains(VertexId(0), EdgeId(0)));
+        assert!(loader.adj.contains(VertexId(2), EdgeId(3)));
+    }
+
+    #[test]
+    fn test_load_feet_distances() {
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("feet_edges.csv"),
+            n_edges: 2,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+            distance_unit: DistanceUnit::Feet,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        let loader = EdgeLoader::try_from(conf).unwrap();
+        // 1000 feet is 304.8 meters, 3280.84 feet is 1000 meters
+        let meters = loader
+            .edges
+            .iter()
+            .map(|e| e.distance.as_f64())
+            .collect::<Vec<_>>();
+        assert!((meters[0] - 304.8).abs() < 0.01);
+        assert!((meters[1] - 1000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_load_closed_flag() {
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("closed_edges.csv"),
+            n_edges: 3,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        let loader = EdgeLoader::try_from(conf).unwrap();
+        let closed = loader.edges.iter().map(|e| e.closed).collect::<Vec<_>>();
+        assert_eq!(closed, vec![false, true, false]);
+
+        // edge lists without the column load every edge as open
+        let open = load(DegenerateEdgePolicy::Flag);
+        assert!(open.edges.iter().all(|e| !e.closed));
+    }
+
+    #[test]
+    fn test_load_aliased_columns() {
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("aliased_edges.csv"),
+            n_edges: 2,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        let loader = EdgeLoader::try_from(conf).unwrap();
+        let edges = loader
+            .edges
+            .iter()
+            .map(|e| (e.src_vertex_id, e.dst_vertex_id, e.distance.as_f64()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                (VertexId(0), VertexId(1), 10.0),
+                (VertexId(1), VertexId(2), 20.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_bidirectional_edges() {
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("degenerate_edges.csv"),
+            n_edges: 4,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Drop,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: true,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        let loader = EdgeLoader::try_from(conf).unwrap();
+        assert_eq!(loader.edges.len(), 8);
+
+        // edge 0 is (0) -> (1), so its reverse, edge 4, is (1) -> (0)
+        let reverse = loader.edges[4];
+        assert_eq!(reverse.edge_id, EdgeId(4));
+        assert_eq!(reverse.src_vertex_id, VertexId(1));
+        assert_eq!(reverse.dst_vertex_id, VertexId(0));
+        assert_eq!(reverse.distance, loader.edges[0].distance);
+        assert_eq!(loader.adj.get(VertexId(1), EdgeId(4)), Some(VertexId(0)));
+        assert_eq!(loader.rev.get(VertexId(0), EdgeId(4)), Some(VertexId(1)));
+
+        // reverses of dropped degenerate edges are dropped too
+        assert!(!loader.adj.contains(VertexId(1), EdgeId(5)));
+        assert!(!loader.adj.contains(VertexId(2), EdgeId(6)));
+    }
+
+    #[test]
+    fn test_duplicate_edge_ids() {
+        let conf = |policy| EdgeLoaderConfig {
+            edge_list_csv: filepath("duplicate_edges.csv"),
+            n_edges: 4,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: policy,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: false,
+            progress: None,
+        };
+        match EdgeLoader::try_from(conf(DuplicateEdgePolicy::Error)) {
+            Err(GraphError::DuplicateEdgeIds(msg)) => assert!(msg.contains("[1]"), "{}", msg),
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("expected duplicate edge ids to fail"),
+        }
+        let loader = EdgeLoader::try_from(conf(DuplicateEdgePolicy::Warn)).unwrap();
+        assert_eq!(loader.duplicate_edge_report.duplicates, vec![EdgeId(1)]);
+        assert_eq!(loader.edges.len(), 4);
+    }
+
+    #[test]
+    fn test_duplicate_edge_ids_agree_across_representations() {
+        // edge 1 is listed as (1) -> (2) and then as (2) -> (0)
+        let load = |adjacency| {
+            let conf = EdgeLoaderConfig {
+                edge_list_csv: filepath("duplicate_edges.csv"),
+                n_edges: 4,
+                n_vertices: 3,
+                degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+                distance_unit: BASE_DISTANCE_UNIT,
+                bidirectional: true,
+                duplicate_edge_policy: DuplicateEdgePolicy::Warn,
+                adjacency,
+                verbose: false,
+                progress: None,
+            };
+            EdgeLoader::try_from(conf).unwrap()
+        };
+        let hash_map = load(AdjacencyRepresentation::HashMap);
+        let csr = load(AdjacencyRepresentation::Csr);
+        for loader in [&hash_map, &csr] {
+            assert_eq!(loader.adj.get(VertexId(1), EdgeId(1)), Some(VertexId(2)));
+            assert!(!loader.adj.contains(VertexId(2), EdgeId(1)));
+            assert!(!loader.rev.contains(VertexId(0), EdgeId(1)));
+        }
+        for vertex_id in (0..3).map(VertexId) {
+            let expected = hash_map.adj.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            let actual = csr.adj.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            assert_eq!(expected, actual);
+            let expected = hash_map.rev.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            let actual = csr.rev.entries(vertex_id).unwrap().collect::<Vec<_>>();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        let updates = Arc::new(Mutex::new(vec![]));
+        let recorder = updates.clone();
+        let conf = EdgeLoaderConfig {
+            edge_list_csv: filepath("degenerate_edges.csv"),
+            n_edges: 4,
+            n_vertices: 3,
+            degenerate_edge_policy: DegenerateEdgePolicy::Flag,
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::Error,
+            adjacency: AdjacencyRepresentation::HashMap,
+            verbose: true,
+            progress: Some(Box::new(move |done, total| {
+                recorder.lock().unwrap().push((done, total));
+            })),
+        };
+        EdgeLoader::try_from(conf).unwrap();
+        let updates = updates.lock().unwrap();
+        assert_eq!(*updates, vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+    }
+}
@@ -0,0 +1,178 @@
+use super::{bidirectional_edges, edge_id::EdgeId, graph_error::GraphError};
+use allocative::Allocative;
+use std::collections::HashMap;
+
+/// describes how the rows of a per-edge table, such as a speed or grade table
+/// with one row per row of the edge list, map to the edges of a loaded graph.
+/// a regional graph keeps a subset of the rows under dense ids, and a
+/// bidirectional graph appends the reverse of every kept row, see
+/// [`bidirectional_edges::reverse_edge_id`].
+#[derive(Clone, Debug, Default, Allocative)]
+pub struct EdgeTableLayout {
+    /// number of edge list rows loaded as forward edges
+    pub n_rows: usize,
+    /// for each forward `EdgeId` of a regional graph, its row in the edge
+    /// list. None if every row was loaded
+    pub rows: Option<Box<[EdgeId]>>,
+    /// true if the reverse of every row was appended as an edge
+    pub bidirectional: bool,
+}
+
+impl EdgeTableLayout {
+    /// arranges a table with one row per edge list row so that it is
+    /// indexed by the `EdgeId`s of the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - table rows for the edges as they appear in the edge list
+    /// * `reverse` - creates the row of a reverse edge, for example by negating a grade
+    ///
+    /// # Returns
+    ///
+    /// one row per edge of the graph, or an error if the table is missing a
+    /// row of a regional graph
+    pub fn align<T: Clone>(
+        &self,
+        table: &[T],
+        reverse: impl Fn(&T) -> T,
+    ) -> Result<Box<[T]>, GraphError> {
+        let forward: Box<[T]> = match &self.rows {
+            None => table.into(),
+            Some(rows) => rows
+                .iter()
+                .map(|row| {
+                    table
+                        .get(row.0)
+                        .cloned()
+                        .ok_or(GraphError::EdgeAttributeNotFound { edge_id: *row })
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        if self.bidirectional {
+            Ok(bidirectional_edges::mirror_table(&forward, reverse))
+        } else {
+            Ok(forward)
+        }
+    }
+
+    /// re-keys a table keyed by edge list row, such as a set of per-edge
+    /// overrides, by the `EdgeId`s of the graph. entries of rows outside of a
+    /// regional graph are left out, and the entries of a bidirectional graph
+    /// also apply to the reverse edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - table entries keyed by edge list row
+    ///
+    /// # Returns
+    ///
+    /// the table keyed by graph `EdgeId`
+    pub fn align_edge_map<T: Clone>(&self, entries: &HashMap<EdgeId, T>) -> HashMap<EdgeId, T> {
+        let forward: HashMap<EdgeId, T> = match &self.rows {
+            None => entries.clone(),
+            Some(rows) => rows
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, row)| entries.get(row).map(|v| (EdgeId(idx), v.clone())))
+                .collect(),
+        };
+        if self.bidirectional {
+            bidirectional_edges::mirror_edge_map(&forward, self.n_rows)
+        } else {
+            forward
+        }
+    }
+
+    /// maps edge list rows to the forward `EdgeId`s of the graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - edge list rows, such as the edges of a set of turn restrictions
+    ///
+    /// # Returns
+    ///
+    /// the forward `EdgeId` of each row, or None for rows outside of a regional graph
+    pub fn forward_edge_ids(&self, rows: &[EdgeId]) -> Vec<Option<EdgeId>> {
+        match &self.rows {
+            None => rows.iter().map(|row| Some(*row)).collect(),
+            Some(kept) => {
+                let lookup: HashMap<EdgeId, EdgeId> = kept
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, row)| (*row, EdgeId(idx)))
+                    .collect();
+                rows.iter().map(|row| lookup.get(row).copied()).collect()
+            }
+        }
+    }
+
+    /// the reverse of a forward edge, if the graph is bidirectional
+    pub fn reverse_edge_id(&self, edge_id: EdgeId) -> Option<EdgeId> {
+        if self.bidirectional {
+            Some(bidirectional_edges::reverse_edge_id(edge_id, self.n_rows))
+        } else {
+            None
+        }
+    }
+
+    /// maps edge list rows, such as a list of closed edges, to the `EdgeId`s
+    /// of the graph. rows outside of a regional graph are left out, and the
+    /// rows of a bidirectional graph map to both of their edges.
+    pub fn align_edge_ids(&self, rows: &[EdgeId]) -> Vec<EdgeId> {
+        let forward: Vec<EdgeId> = self.forward_edge_ids(rows).into_iter().flatten().collect();
+        let reverse: Vec<EdgeId> = forward
+            .iter()
+            .filter_map(|edge_id| self.reverse_edge_id(*edge_id))
+            .collect();
+        forward.into_iter().chain(reverse).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regional_layout() -> EdgeTableLayout {
+        // rows 1 and 3 of a four row edge list, loaded in both directions
+        EdgeTableLayout {
+            n_rows: 2,
+            rows: Some(vec![EdgeId(1), EdgeId(3)].into_boxed_slice()),
+            bidirectional: true,
+        }
+    }
+
+    #[test]
+    fn test_align_subsets_and_mirrors() {
+        let grades = [0.1, 0.2, 0.3, 0.4];
+        let aligned = regional_layout().align(&grades, |g| -g).unwrap();
+        assert_eq!(&aligned[..], &[0.2, 0.4, -0.2, -0.4]);
+        assert_eq!(
+            &EdgeTableLayout::default().align(&grades, |g| -g).unwrap()[..],
+            &grades[..]
+        );
+    }
+
+    #[test]
+    fn test_align_short_table() {
+        let result = regional_layout().align(&[0.1, 0.2], |g| -g);
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAttributeNotFound { edge_id: EdgeId(3) })
+        ));
+    }
+
+    #[test]
+    fn test_align_edge_map() {
+        let entries = HashMap::from([(EdgeId(0), "a"), (EdgeId(3), "b")]);
+        let aligned = regional_layout().align_edge_map(&entries);
+        assert_eq!(aligned.len(), 2);
+        assert_eq!(aligned.get(&EdgeId(1)), Some(&"b"));
+        assert_eq!(aligned.get(&EdgeId(3)), Some(&"b"));
+    }
+
+    #[test]
+    fn test_align_edge_ids() {
+        let aligned = regional_layout().align_edge_ids(&[EdgeId(0), EdgeId(3)]);
+        assert_eq!(aligned, vec![EdgeId(1), EdgeId(3)]);
+    }
+}
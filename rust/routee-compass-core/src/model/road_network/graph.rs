@@ -3,10 +3,16 @@ use crate::model::property::edge::Edge;
 use crate::model::property::vertex::Vertex;
 use crate::model::road_network::graph_error::GraphError;
 use crate::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
-use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
-use std::path::Path;
+use crate::model::unit::Distance;
+use geo::Coord;
+use std::collections::HashMap;
 
-use super::graph_loader::graph_from_files;
+use super::adjacency::Adjacency;
+use super::edge_table_layout::EdgeTableLayout;
+use super::graph_loader::{graph_from_files, GraphLoadConfig, GraphLoadReport};
+use super::graph_merge::{merge_graph, GraphMergeReport};
+use super::graph_stats::GraphStats;
+use super::incident_edges::IncidentEdges;
 
 use allocative::Allocative;
 
@@ -16,10 +22,12 @@ use allocative::Allocative;
 ///
 /// # Arguments
 ///
-/// * `adj` - the forward-oriented adjacency list
+/// * `adj` - the forward-oriented adjacency list, see [`Adjacency`]
 /// * `rev` - the reverse-oriented adjacency list
 /// * `edges` - for each `EdgeId`, the corresponding `Edge` record
 /// * `vertices` - for each `VertexId`, the corresponding `Vertex` record
+/// * `vertex_pair_index` - optional lookup from a (src, dst) `VertexId` pair to the connecting `EdgeId`s
+/// * `edge_table_layout` - how per-edge tables read from files map to the `EdgeId`s, see [`EdgeTableLayout`]
 ///
 /// # Performance
 ///
@@ -31,10 +39,12 @@ use allocative::Allocative;
 
 #[derive(Debug, Allocative)]
 pub struct Graph {
-    pub adj: Box<[CompactOrderedHashMap<EdgeId, VertexId>]>,
-    pub rev: Box<[CompactOrderedHashMap<EdgeId, VertexId>]>,
+    pub adj: Adjacency,
+    pub rev: Adjacency,
     pub edges: Box<[Edge]>,
     pub vertices: Box<[Vertex]>,
+    pub vertex_pair_index: Option<HashMap<(VertexId, VertexId), Vec<EdgeId>>>,
+    pub edge_table_layout: EdgeTableLayout,
 }
 
 impl Graph {
@@ -45,25 +55,38 @@ impl Graph {
     ///
     /// # Arguments
     ///
-    /// * `edge_list_csv` - path to the CSV file containing edge attributes
-    /// * `vertex_list_csv` - path to the CSV file containing vertex attributes
-    /// * `n_edges` - number of edges in the graph
-    /// * `n_vertices` - number of vertices in the graph
-    /// * `verbose` - whether to print progress information to the console
+    /// * `config` - the files to load and how to load them, see [`GraphLoadConfig`]
     ///
     /// # Returns
     ///
-    /// A graph instance, or an error if an IO error occurred.
+    /// A graph instance and the degenerate and duplicate edges found in the
+    /// edge list, or an error if an IO error occurred.
     ///
-    pub fn from_files<P: AsRef<Path>>(
-        edge_list_csv: &P,
-        vertex_list_csv: &P,
-        n_edges: Option<usize>,
-        n_vertices: Option<usize>,
-        verbose: Option<bool>,
-    ) -> Result<Graph, GraphError> {
-        graph_from_files(edge_list_csv, vertex_list_csv, n_edges, n_vertices, verbose)
+    pub fn from_files(config: GraphLoadConfig) -> Result<(Graph, GraphLoadReport), GraphError> {
+        graph_from_files(config)
     }
+
+    /// merges a separately loaded graph, such as an overlay of private roads,
+    /// into this graph. see [`merge_graph`] for how ids are assigned.
+    ///
+    /// # Arguments
+    ///
+    /// * `overlay` - the graph to merge into this graph
+    /// * `vertex_tolerance` - optional distance in meters within which an overlay
+    ///   vertex is replaced by an existing vertex, reconnecting its edges
+    ///
+    /// # Returns
+    ///
+    /// where the overlay ids were placed in this graph, or an error if the ids
+    /// of either graph do not match their positions
+    pub fn merge(
+        &mut self,
+        overlay: &Graph,
+        vertex_tolerance: Option<Distance>,
+    ) -> Result<GraphMergeReport, GraphError> {
+        merge_graph(self, overlay, vertex_tolerance)
+    }
+
     /// number of edges in the Graph
     pub fn n_edges(&self) -> usize {
         self.edges.len()
@@ -74,6 +97,16 @@ impl Graph {
         self.vertices.len()
     }
 
+    /// summary statistics of this Graph, such as degree and edge distances.
+    /// see [`GraphStats`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `road_classes` - optional road class of each edge, indexed by `EdgeId`
+    pub fn stats(&self, road_classes: Option<&[u8]>) -> Result<GraphStats, GraphError> {
+        GraphStats::new(self, road_classes)
+    }
+
     /// helper function for creating a range of all edge ids in the graph.
     /// uses the knowledge that all ids are unique and consecutive integers
     /// beginning at zero.
@@ -133,12 +166,9 @@ impl Graph {
     /// A list of `EdgeIds` for outbound edges that leave this `VertexId`, or an error
     /// if the vertex is missing from the Graph adjacency matrix.
     pub fn out_edges(&self, src: VertexId) -> Result<Vec<EdgeId>, GraphError> {
-        match self.adj.get(src.0) {
+        match self.adj.edge_ids(src) {
             None => Err(GraphError::VertexWithoutOutEdges { vertex_id: src }),
-            Some(out_map) => {
-                let edge_ids = out_map.keys().cloned().collect();
-                Ok(edge_ids)
-            }
+            Some(edge_ids) => Ok(edge_ids.cloned().collect()),
         }
     }
 
@@ -146,13 +176,9 @@ impl Graph {
         &'a self,
         src: VertexId,
     ) -> Result<Box<dyn Iterator<Item = &EdgeId> + 'a>, GraphError> {
-        match self.adj.get(src.0) {
-            None => Err(GraphError::VertexWithoutOutEdges { vertex_id: src }),
-            Some(out_map) => {
-                let edge_ids = out_map.keys();
-                Ok(edge_ids)
-            }
-        }
+        self.adj
+            .edge_ids(src)
+            .ok_or(GraphError::VertexWithoutOutEdges { vertex_id: src })
     }
 
     /// retrieve a list of `EdgeId`s for edges that arrive at the given `VertexId`
@@ -166,12 +192,9 @@ impl Graph {
     /// A list of `EdgeIds` for inbound edges that arrive at this `VertexId`, or an error
     /// if the vertex is missing from the Graph adjacency matrix.
     pub fn in_edges(&self, dst: VertexId) -> Result<Vec<EdgeId>, GraphError> {
-        match self.rev.get(dst.0) {
+        match self.rev.edge_ids(dst) {
             None => Err(GraphError::VertexWithoutInEdges { vertex_id: dst }),
-            Some(in_map) => {
-                let edge_ids = in_map.keys().cloned().collect();
-                Ok(edge_ids)
-            }
+            Some(edge_ids) => Ok(edge_ids.cloned().collect()),
         }
     }
 
@@ -179,13 +202,49 @@ impl Graph {
         &'a self,
         dst: VertexId,
     ) -> Result<Box<dyn Iterator<Item = &EdgeId> + 'a>, GraphError> {
-        match self.rev.get(dst.0) {
-            None => Err(GraphError::VertexWithoutInEdges { vertex_id: dst }),
-            Some(in_map) => {
-                let edge_ids = in_map.keys();
-                Ok(edge_ids)
+        self.rev
+            .edge_ids(dst)
+            .ok_or(GraphError::VertexWithoutInEdges { vertex_id: dst })
+    }
+
+    /// builds an index from each (src, dst) `VertexId` pair to the `EdgeId`s
+    /// that connect them, making `edges_between` _O(1)_. only edges present
+    /// in the forward adjacency list are indexed.
+    pub fn build_vertex_pair_index(&mut self) {
+        let mut index: HashMap<(VertexId, VertexId), Vec<EdgeId>> = HashMap::new();
+        for src in (0..self.adj.n_vertices()).map(VertexId) {
+            for (edge_id, dst) in self.adj.entries(src).into_iter().flatten() {
+                index.entry((src, dst)).or_default().push(edge_id);
             }
         }
+        self.vertex_pair_index = Some(index);
+    }
+
+    /// retrieve all `EdgeId`s for edges that connect a source vertex to a
+    /// destination vertex. parallel edges are all returned. if the vertex
+    /// pair index has been built this is a hash lookup, otherwise the out
+    /// edges of `src` are scanned.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - the `VertexId` the edges depart from
+    /// * `dst` - the `VertexId` the edges arrive at
+    ///
+    /// # Returns
+    ///
+    /// The connecting `EdgeId`s, which is empty if the vertices are not adjacent,
+    /// or an error if `src` is missing from the Graph adjacency matrix.
+    pub fn edges_between(&self, src: VertexId, dst: VertexId) -> Result<Vec<EdgeId>, GraphError> {
+        // the index only holds adjacent pairs, so a missing src is checked
+        // against the adjacency either way
+        let entries = self
+            .adj
+            .entries(src)
+            .ok_or(GraphError::VertexWithoutOutEdges { vertex_id: src })?;
+        match &self.vertex_pair_index {
+            Some(index) => Ok(index.get(&(src, dst)).cloned().unwrap_or_default()),
+            None => Ok(entries.filter(|(_, v)| *v == dst).map(|(e, _)| e).collect()),
+        }
     }
 
     /// retrieve the source vertex id of an edge
@@ -236,6 +295,33 @@ impl Graph {
         }
     }
 
+    /// collects every edge that arrives at or departs from a vertex, each
+    /// paired with the vertex at its other end, as a building block for
+    /// intersection-level logic such as enumerating turns.
+    ///
+    /// # Arguments
+    ///
+    /// * `vertex_id` - the vertex to find edges which connect to it
+    ///
+    /// # Returns
+    ///
+    /// The incoming and outgoing edges of the vertex, which are both empty
+    /// for an isolated vertex, or an error if the vertex is not in the graph.
+    pub fn all_incident_edges(&self, vertex_id: VertexId) -> Result<IncidentEdges, GraphError> {
+        self.get_vertex(vertex_id)?;
+        let incoming = self
+            .rev
+            .entries(vertex_id)
+            .map(|entries| entries.collect())
+            .unwrap_or_default();
+        let outgoing = self
+            .adj
+            .entries(vertex_id)
+            .map(|entries| entries.collect())
+            .unwrap_or_default();
+        Ok(IncidentEdges { incoming, outgoing })
+    }
+
     /// helper function to give the incident vertex to an edge based on a
     /// traversal direction.
     ///
@@ -338,4 +424,135 @@ impl Graph {
             })
             .collect()
     }
+
+    /// builds the polyline of a route from the coordinates of the vertices
+    /// it visits, beginning with the source vertex of the first edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_ids` - the edges of the route, in the order they are traversed
+    ///
+    /// # Returns
+    ///
+    /// The coordinates of the route, with one more coordinate than edges, or an
+    /// error if an edge does not begin at the vertex where the previous edge ends.
+    pub fn route_coordinates(&self, edge_ids: &[EdgeId]) -> Result<Vec<Coord<f32>>, GraphError> {
+        let mut coordinates = Vec::with_capacity(edge_ids.len() + 1);
+        let mut prev_edge: Option<&Edge> = None;
+        for edge_id in edge_ids.iter() {
+            let edge = self.get_edge(*edge_id)?;
+            match prev_edge {
+                None => {
+                    let src = self.get_vertex(edge.src_vertex_id)?;
+                    coordinates.push(*src.coordinate);
+                }
+                Some(prev) if prev.dst_vertex_id != edge.src_vertex_id => {
+                    return Err(GraphError::DisconnectedRoute {
+                        prev_edge_id: prev.edge_id,
+                        prev_dst_vertex_id: prev.dst_vertex_id,
+                        next_edge_id: edge.edge_id,
+                        next_src_vertex_id: edge.src_vertex_id,
+                    });
+                }
+                Some(_) => {}
+            }
+            let dst = self.get_vertex(edge.dst_vertex_id)?;
+            coordinates.push(*dst.coordinate);
+            prev_edge = Some(edge);
+        }
+        Ok(coordinates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::graph_fixture::graph_from_edges;
+
+    /// three vertices with two parallel edges from 0 to 1 and one edge from 1 to 2,
+    /// plus a fourth vertex without any edges
+    fn build_parallel_edge_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 1.0, 0.0),
+            Vertex::new(2, 1.0, 1.0),
+            Vertex::new(3, 2.0, 2.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 0, 1, 12.0),
+            Edge::new(2, 1, 2, 5.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    fn assert_edges_between(graph: &Graph) {
+        let mut parallel = graph.edges_between(VertexId(0), VertexId(1)).unwrap();
+        parallel.sort_by_key(|e| e.0);
+        assert_eq!(parallel, vec![EdgeId(0), EdgeId(1)]);
+        let single = graph.edges_between(VertexId(1), VertexId(2)).unwrap();
+        assert_eq!(single, vec![EdgeId(2)]);
+        let wrong_direction = graph.edges_between(VertexId(1), VertexId(0)).unwrap();
+        assert!(wrong_direction.is_empty());
+    }
+
+    #[test]
+    fn test_edges_between_without_index() {
+        let graph = build_parallel_edge_graph();
+        assert_edges_between(&graph);
+        assert!(graph.edges_between(VertexId(5), VertexId(0)).is_err());
+    }
+
+    #[test]
+    fn test_edges_between_with_index() {
+        let mut graph = build_parallel_edge_graph();
+        graph.build_vertex_pair_index();
+        assert!(graph.vertex_pair_index.is_some());
+        assert_edges_between(&graph);
+        assert!(graph.edges_between(VertexId(5), VertexId(0)).is_err());
+    }
+
+    #[test]
+    fn test_all_incident_edges() {
+        let graph = build_parallel_edge_graph();
+        let middle = graph.all_incident_edges(VertexId(1)).unwrap();
+        let mut incoming = middle.incoming.clone();
+        incoming.sort_by_key(|(e, _)| e.0);
+        assert_eq!(
+            incoming,
+            vec![(EdgeId(0), VertexId(0)), (EdgeId(1), VertexId(0))]
+        );
+        assert_eq!(middle.outgoing, vec![(EdgeId(2), VertexId(2))]);
+
+        let end = graph.all_incident_edges(VertexId(2)).unwrap();
+        assert_eq!(end.incoming, vec![(EdgeId(2), VertexId(1))]);
+        assert!(end.outgoing.is_empty());
+
+        // a vertex with no edges is isolated rather than an error
+        assert!(graph.all_incident_edges(VertexId(3)).unwrap().is_isolated());
+        assert!(graph.all_incident_edges(VertexId(4)).is_err());
+    }
+
+    #[test]
+    fn test_route_coordinates() {
+        let graph = build_parallel_edge_graph();
+        let coordinates = graph.route_coordinates(&[EdgeId(1), EdgeId(2)]).unwrap();
+        let xy = coordinates.iter().map(|c| (c.x, c.y)).collect::<Vec<_>>();
+        assert_eq!(xy, vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]);
+        assert!(graph.route_coordinates(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_route_coordinates_disconnected() {
+        let graph = build_parallel_edge_graph();
+        let result = graph.route_coordinates(&[EdgeId(2), EdgeId(0)]);
+        assert!(matches!(
+            result,
+            Err(GraphError::DisconnectedRoute {
+                prev_edge_id: EdgeId(2),
+                next_edge_id: EdgeId(0),
+                ..
+            })
+        ));
+    }
 }
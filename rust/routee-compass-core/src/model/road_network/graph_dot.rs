@@ -0,0 +1,163 @@
+use super::{edge_id::EdgeId, graph::Graph, graph_error::GraphError, vertex_id::VertexId};
+use crate::model::property::edge::Edge;
+use crate::model::unit::{as_f64::AsF64, BASE_DISTANCE_UNIT};
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// writes the graph in the Graphviz DOT format for inspecting its topology
+/// while onboarding data. every vertex becomes a node, and every edge in
+/// the adjacency list becomes a directed edge labeled with its `EdgeId`
+/// and distance. DOT rendering is only practical for small graphs, so a
+/// graph with more vertices than the limit is rejected.
+///
+/// # Arguments
+///
+/// * `graph` - the graph to write
+/// * `max_vertices` - the largest number of vertices that may be written
+/// * `writer` - the destination of the DOT output
+///
+/// # Returns
+///
+/// an error if the graph exceeds the vertex limit or the write fails
+pub fn write_dot<W: Write>(
+    graph: &Graph,
+    max_vertices: usize,
+    writer: &mut W,
+) -> Result<(), GraphError> {
+    let vertices = graph.vertex_ids().collect::<Vec<_>>();
+    let mut edges: Vec<&Edge> = vec![];
+    for src in (0..graph.adj.n_vertices()).map(VertexId) {
+        for (edge_id, _) in graph.adj.entries(src).into_iter().flatten() {
+            let edge = graph.get_edge(edge_id)?;
+            if edge.src_vertex_id != src {
+                return Err(GraphError::AdjacencyVertexMissing(src));
+            }
+            edges.push(edge);
+        }
+    }
+    write_digraph(&vertices, &edges, max_vertices, writer)
+}
+
+/// writes part of the graph, such as a route, in the Graphviz DOT format.
+/// only the listed edges and the vertices they connect are written.
+///
+/// # Arguments
+///
+/// * `graph` - the graph the edges belong to
+/// * `edge_ids` - the edges of the subgraph
+/// * `max_vertices` - the largest number of vertices that may be written
+/// * `writer` - the destination of the DOT output
+///
+/// # Returns
+///
+/// an error if an edge is missing, the subgraph exceeds the vertex limit,
+/// or the write fails
+pub fn write_subgraph_dot<W: Write>(
+    graph: &Graph,
+    edge_ids: &[EdgeId],
+    max_vertices: usize,
+    writer: &mut W,
+) -> Result<(), GraphError> {
+    let edges = edge_ids
+        .iter()
+        .map(|edge_id| graph.get_edge(*edge_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let vertices = edges
+        .iter()
+        .flat_map(|e| [e.src_vertex_id, e.dst_vertex_id])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    write_digraph(&vertices, &edges, max_vertices, writer)
+}
+
+fn write_digraph<W: Write>(
+    vertices: &[VertexId],
+    edges: &[&Edge],
+    max_vertices: usize,
+    writer: &mut W,
+) -> Result<(), GraphError> {
+    if vertices.len() > max_vertices {
+        return Err(GraphError::DotVertexLimitExceeded {
+            n_vertices: vertices.len(),
+            max_vertices,
+        });
+    }
+    writeln!(writer, "digraph compass {{")?;
+    for vertex_id in vertices.iter() {
+        writeln!(writer, "  {};", vertex_id)?;
+    }
+    for edge in edges.iter() {
+        writeln!(
+            writer,
+            "  {} -> {} [label=\"{}: {} {}\"];",
+            edge.src_vertex_id,
+            edge.dst_vertex_id,
+            edge.edge_id,
+            edge.distance.as_f64(),
+            BASE_DISTANCE_UNIT
+        )?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::graph_fixture::graph_from_links;
+
+    /// a triangle (0) -[0]-> (1) -[1]-> (2) -[2]-> (0)
+    fn build_graph() -> Graph {
+        graph_from_links(3, &[(0, 1, 10.0), (1, 2, 20.0), (2, 0, 30.0)])
+    }
+
+    fn assert_valid_dot(dot: &str) {
+        assert!(dot.starts_with("digraph compass {"));
+        let mut depth = 0;
+        for c in dot.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            assert!(depth >= 0, "unbalanced braces in {}", dot);
+        }
+        assert_eq!(depth, 0, "unbalanced braces in {}", dot);
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let mut buffer = vec![];
+        write_dot(&build_graph(), 10, &mut buffer).unwrap();
+        let dot = String::from_utf8(buffer).unwrap();
+        assert_valid_dot(&dot);
+        assert_eq!(dot.matches(" -> ").count(), 3);
+        assert!(dot.contains("1 -> 2 [label=\"1: 20 meters\"];"));
+    }
+
+    #[test]
+    fn test_write_subgraph_dot() {
+        let mut buffer = vec![];
+        write_subgraph_dot(&build_graph(), &[EdgeId(0), EdgeId(1)], 10, &mut buffer).unwrap();
+        let dot = String::from_utf8(buffer).unwrap();
+        assert_valid_dot(&dot);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(!dot.contains("2 -> 0"));
+    }
+
+    #[test]
+    fn test_vertex_limit() {
+        let mut buffer = vec![];
+        let result = write_dot(&build_graph(), 2, &mut buffer);
+        assert!(matches!(
+            result,
+            Err(GraphError::DotVertexLimitExceeded {
+                n_vertices: 3,
+                max_vertices: 2
+            })
+        ));
+        assert!(buffer.is_empty());
+    }
+}
@@ -22,7 +22,7 @@ pub enum GraphError {
     AttributeError(String, String),
     #[error("{filename} file source was empty")]
     EmptyFileSource { filename: PathBuf },
-    #[error("failure reading TomTom graph: {source}")]
+    #[error("failure reading graph: {source}")]
     IOError {
         #[from]
         source: std::io::Error,
@@ -34,6 +34,29 @@ pub enum GraphError {
     },
     #[error("internal error: adjacency list missing vertex {0}")]
     AdjacencyVertexMissing(VertexId),
+    #[error("internal error: adjacency list of vertex {0} has more entries than were counted")]
+    AdjacencyEntriesExceeded(VertexId),
     #[error("error creating progress bar for {0}: {1}")]
     ProgressBarBuildError(String, String),
+    #[error("failure loading {filename}: {source}")]
+    FileLoadError {
+        filename: PathBuf,
+        source: Box<GraphError>,
+    },
+    #[error("route is disconnected: edge {prev_edge_id} ends at vertex {prev_dst_vertex_id} but the next edge {next_edge_id} starts at vertex {next_src_vertex_id}")]
+    DisconnectedRoute {
+        prev_edge_id: EdgeId,
+        prev_dst_vertex_id: VertexId,
+        next_edge_id: EdgeId,
+        next_src_vertex_id: VertexId,
+    },
+    #[error("{0}. set the graph duplicate_edge_policy to \"warn\" to load the graph anyway")]
+    DuplicateEdgeIds(String),
+    #[error("graph has {n_vertices} vertices, more than the DOT export limit of {max_vertices}")]
+    DotVertexLimitExceeded {
+        n_vertices: usize,
+        max_vertices: usize,
+    },
+    #[error("loader thread for {0} panicked")]
+    LoaderThreadPanic(PathBuf),
 }
@@ -1,24 +1,132 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use log::warn;
 
-use crate::{model::property::vertex::Vertex, util::fs::fs_utils::line_count};
+use crate::{
+    model::{
+        property::{edge::Edge, vertex::Vertex},
+        unit::{DistanceUnit, BASE_DISTANCE_UNIT},
+    },
+    util::{fs::fs_utils::line_count, geo::crs::Crs, progress::ProgressCallback},
+};
 
 use super::{
+    adjacency::Adjacency,
+    adjacency_representation::AdjacencyRepresentation,
+    degenerate_edge_policy::DegenerateEdgePolicy,
+    degenerate_edge_report::DegenerateEdgeReport,
+    duplicate_edge_policy::DuplicateEdgePolicy,
+    duplicate_edge_report::DuplicateEdgeReport,
+    edge_id::EdgeId,
     edge_loader::{EdgeLoader, EdgeLoaderConfig},
+    edge_table_layout::EdgeTableLayout,
     graph::Graph,
     graph_error::GraphError,
+    graph_region::{select_region, GraphRegion, GraphRegionReport},
     vertex_loader::VertexLoaderConfig,
 };
 
-pub fn graph_from_files<P: AsRef<Path>>(
-    edge_list_csv: &P,
-    vertex_list_csv: &P,
-    n_edges: Option<usize>,
-    n_vertices: Option<usize>,
-    verbose: Option<bool>,
-) -> Result<Graph, GraphError> {
-    let verbose = verbose.unwrap_or(false);
+/// optional callbacks which receive the progress of loading the edge and
+/// vertex lists. the lists are loaded on separate threads, so each has its
+/// own callback. without a callback, a list's progress is drawn as a
+/// progress bar when loading is verbose.
+#[derive(Default)]
+pub struct GraphLoadProgress {
+    pub edges: Option<ProgressCallback>,
+    pub vertices: Option<ProgressCallback>,
+}
+
+/// settings for loading a [`Graph`] from an edge list and a vertex list.
+/// only the file paths are required, see [`GraphLoadConfig::new`] for the
+/// defaults of the remaining settings.
+///
+/// # Arguments
+///
+/// * `edge_list_csv` - path to the CSV file containing edge attributes
+/// * `vertex_list_csv` - path to the CSV file containing vertex attributes
+/// * `n_edges` - number of edge rows in the edge list, found by scanning the file if not provided
+/// * `n_vertices` - number of vertices in the graph, found by scanning the file if not provided
+/// * `verbose` - whether to report progress while loading
+/// * `degenerate_edge_policy` - how to treat self-loop and zero-distance edges
+/// * `distance_unit` - unit of edge distances in the edge list
+/// * `bidirectional` - if true, also loads the reverse of each edge row with a synthetic id
+/// * `duplicate_edge_policy` - whether an `edge_id` found on more than one row is an error
+/// * `vertex_crs` - coordinate reference system of the vertex list
+/// * `adjacency` - how the adjacency lists are stored
+/// * `region` - optional part of the graph to load, see [`GraphRegion`]
+/// * `progress` - optional callbacks that receive load progress in place of the progress bars
+pub struct GraphLoadConfig {
+    pub edge_list_csv: PathBuf,
+    pub vertex_list_csv: PathBuf,
+    pub n_edges: Option<usize>,
+    pub n_vertices: Option<usize>,
+    pub verbose: bool,
+    pub degenerate_edge_policy: DegenerateEdgePolicy,
+    pub distance_unit: DistanceUnit,
+    pub bidirectional: bool,
+    pub duplicate_edge_policy: DuplicateEdgePolicy,
+    pub vertex_crs: Crs,
+    pub adjacency: AdjacencyRepresentation,
+    pub region: Option<GraphRegion>,
+    pub progress: GraphLoadProgress,
+}
+
+impl GraphLoadConfig {
+    /// settings for loading the given edge and vertex lists quietly as
+    /// one-way edges in the base distance unit and WGS84 coordinates, with
+    /// the default policies and adjacency representation and no region.
+    pub fn new<P: AsRef<Path>>(edge_list_csv: &P, vertex_list_csv: &P) -> GraphLoadConfig {
+        GraphLoadConfig {
+            edge_list_csv: edge_list_csv.as_ref().to_path_buf(),
+            vertex_list_csv: vertex_list_csv.as_ref().to_path_buf(),
+            n_edges: None,
+            n_vertices: None,
+            verbose: false,
+            degenerate_edge_policy: DegenerateEdgePolicy::default(),
+            distance_unit: BASE_DISTANCE_UNIT,
+            bidirectional: false,
+            duplicate_edge_policy: DuplicateEdgePolicy::default(),
+            vertex_crs: Crs::default(),
+            adjacency: AdjacencyRepresentation::default(),
+            region: None,
+            progress: GraphLoadProgress::default(),
+        }
+    }
+}
+
+/// the degenerate and duplicate edges found while loading an edge list.
+/// both are also logged as warnings when not empty. degenerate edges are
+/// listed by graph `EdgeId`, and duplicates by their id in the edge list.
+#[derive(Clone, Debug, Default)]
+pub struct GraphLoadReport {
+    pub degenerate_edges: DegenerateEdgeReport,
+    pub duplicate_edges: DuplicateEdgeReport,
+    /// where the edges and vertices of a regional graph came from
+    pub region: Option<GraphRegionReport>,
+}
+
+/// loads a graph from the files named in the config.
+///
+/// # Returns
+///
+/// the graph and what was found in its edge list, or an error if either file
+/// could not be loaded
+pub fn graph_from_files(config: GraphLoadConfig) -> Result<(Graph, GraphLoadReport), GraphError> {
+    let GraphLoadConfig {
+        edge_list_csv,
+        vertex_list_csv,
+        n_edges,
+        n_vertices,
+        verbose,
+        degenerate_edge_policy,
+        distance_unit,
+        bidirectional,
+        duplicate_edge_policy,
+        vertex_crs,
+        adjacency,
+        region,
+        progress,
+    } = config;
     let n_edges = match n_edges {
         Some(n) => n,
         None => {
@@ -38,40 +146,157 @@ pub fn graph_from_files<P: AsRef<Path>>(
             get_n_vertices(&vertex_list_csv)?
         }
     };
+    if let Some(region) = region {
+        let v_conf = VertexLoaderConfig {
+            vertex_list_csv,
+            n_vertices,
+            crs: vertex_crs,
+            verbose,
+            progress: progress.vertices,
+        };
+        let all_vertices = load_vertices(v_conf)?;
+        let selection = select_region(
+            &edge_list_csv,
+            &all_vertices,
+            &region,
+            n_edges,
+            duplicate_edge_policy,
+        )?;
+        let e_conf = EdgeLoaderConfig {
+            edge_list_csv,
+            n_edges: selection.edges.len(),
+            n_vertices: selection.vertices.len(),
+            degenerate_edge_policy,
+            distance_unit,
+            bidirectional,
+            duplicate_edge_policy,
+            adjacency,
+            verbose,
+            progress: progress.edges,
+        };
+        let e_result = EdgeLoader::from_rows(e_conf, selection.edges.into_iter().map(Ok))?;
+        let rows = selection.report.edge_ids.clone().into_boxed_slice();
+        let report = GraphLoadReport {
+            degenerate_edges: e_result.degenerate_edge_report,
+            duplicate_edges: selection.duplicate_edges,
+            region: Some(selection.report),
+        };
+        return Ok(finish_graph(
+            e_result.edges,
+            e_result.adj,
+            e_result.rev,
+            selection.vertices,
+            Some(rows),
+            bidirectional,
+            report,
+        ));
+    }
+
     let e_conf = EdgeLoaderConfig {
-        edge_list_csv: edge_list_csv.as_ref().to_path_buf(),
+        edge_list_csv,
         n_edges,
         n_vertices,
+        degenerate_edge_policy,
+        distance_unit,
+        bidirectional,
+        duplicate_edge_policy,
+        adjacency,
+        verbose,
+        progress: progress.edges,
     };
-
-    let e_result = EdgeLoader::try_from(e_conf)?;
-
     let v_conf = VertexLoaderConfig {
-        vertex_list_csv: vertex_list_csv.as_ref().to_path_buf(),
+        vertex_list_csv: vertex_list_csv.clone(),
         n_vertices,
+        crs: vertex_crs,
+        verbose,
+        progress: progress.vertices,
     };
 
-    let vertices: Box<[Vertex]> = v_conf.try_into()?;
+    // the edge and vertex lists are independent files, so the vertex list is
+    // parsed on its own thread while the edge list and adjacencies are built
+    let (e_result, v_result) = std::thread::scope(|s| {
+        let vertex_handle = s.spawn(|| load_vertices(v_conf));
+        let e_result = load_edges(e_conf);
+        let v_result = vertex_handle
+            .join()
+            .unwrap_or_else(|_| Err(GraphError::LoaderThreadPanic(vertex_list_csv.clone())));
+        (e_result, v_result)
+    });
+
+    let e_result = e_result?;
+    let vertices = v_result?;
+    let report = GraphLoadReport {
+        degenerate_edges: e_result.degenerate_edge_report,
+        duplicate_edges: e_result.duplicate_edge_report,
+        region: None,
+    };
+    Ok(finish_graph(
+        e_result.edges,
+        e_result.adj,
+        e_result.rev,
+        vertices,
+        None,
+        bidirectional,
+        report,
+    ))
+}
 
+/// assembles a loaded graph and logs what was found in its edge list
+fn finish_graph(
+    edges: Box<[Edge]>,
+    adj: Adjacency,
+    rev: Adjacency,
+    vertices: Box<[Vertex]>,
+    rows: Option<Box<[EdgeId]>>,
+    bidirectional: bool,
+    report: GraphLoadReport,
+) -> (Graph, GraphLoadReport) {
+    if !report.degenerate_edges.is_empty() {
+        warn!("{}", report.degenerate_edges);
+    }
+    if !report.duplicate_edges.is_empty() {
+        warn!("{}", report.duplicate_edges);
+    }
+    let n_rows = if bidirectional {
+        edges.len() / 2
+    } else {
+        edges.len()
+    };
     let graph = Graph {
-        adj: e_result.adj,
-        rev: e_result.rev,
-        edges: e_result.edges,
+        adj,
+        rev,
+        edges,
         vertices,
+        vertex_pair_index: None,
+        edge_table_layout: EdgeTableLayout {
+            n_rows,
+            rows,
+            bidirectional,
+        },
     };
+    (graph, report)
+}
 
-    Ok(graph)
+/// loads the edge list, attaching the file name to any failure
+fn load_edges(conf: EdgeLoaderConfig) -> Result<EdgeLoader, GraphError> {
+    let filename = conf.edge_list_csv.clone();
+    EdgeLoader::try_from(conf).map_err(|e| GraphError::FileLoadError {
+        filename,
+        source: Box::new(e),
+    })
+}
+
+/// loads the vertex list, attaching the file name to any failure
+pub(super) fn load_vertices(conf: VertexLoaderConfig) -> Result<Box<[Vertex]>, GraphError> {
+    let filename = conf.vertex_list_csv.clone();
+    conf.try_into().map_err(|e| GraphError::FileLoadError {
+        filename,
+        source: Box::new(e),
+    })
 }
 
 fn get_n_edges<P: AsRef<Path>>(edge_list_csv: &P) -> Result<usize, GraphError> {
-    // check if the extension is .gz
-    let is_gzip = edge_list_csv
-        .as_ref()
-        .to_path_buf()
-        .extension()
-        .map(|ext| ext.to_str() == Some("gz"))
-        .unwrap_or(false);
-    let n = line_count(edge_list_csv, is_gzip)?;
+    let n = line_count(edge_list_csv)?;
     if n < 1 {
         return Err(GraphError::EmptyFileSource {
             filename: edge_list_csv.as_ref().to_path_buf(),
@@ -80,14 +305,8 @@ fn get_n_edges<P: AsRef<Path>>(edge_list_csv: &P) -> Result<usize, GraphError> {
     Ok(n - 1) // drop count of header line
 }
 
-fn get_n_vertices<P: AsRef<Path>>(vertex_list_csv: &P) -> Result<usize, GraphError> {
-    let is_gzip = vertex_list_csv
-        .as_ref()
-        .to_path_buf()
-        .extension()
-        .map(|ext| ext.to_str() == Some("gz"))
-        .unwrap_or(false);
-    let n = line_count(vertex_list_csv, is_gzip)?;
+pub(super) fn get_n_vertices<P: AsRef<Path>>(vertex_list_csv: &P) -> Result<usize, GraphError> {
+    let n = line_count(vertex_list_csv)?;
     if n < 1 {
         return Err(GraphError::EmptyFileSource {
             filename: vertex_list_csv.as_ref().to_path_buf(),
@@ -95,3 +314,184 @@ fn get_n_vertices<P: AsRef<Path>>(vertex_list_csv: &P) -> Result<usize, GraphErr
     }
     Ok(n - 1) // drop count of header line
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::{adjacency::Adjacency, edge_id::EdgeId, vertex_id::VertexId};
+    use std::path::PathBuf;
+
+    fn test_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+    }
+
+    #[test]
+    fn test_load_edges_and_vertices_concurrently() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("degenerate_vertices.csv");
+        let (graph, _) = graph_from_files(GraphLoadConfig::new(&edges, &vertices)).unwrap();
+        assert_eq!(graph.n_edges(), 4);
+        assert_eq!(graph.n_vertices(), 3);
+    }
+
+    #[test]
+    fn test_missing_vertex_file_is_named_in_error() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("missing_vertices.csv");
+        let result = graph_from_files(GraphLoadConfig {
+            n_edges: Some(4),
+            n_vertices: Some(3),
+            ..GraphLoadConfig::new(&edges, &vertices)
+        });
+        match result {
+            Err(GraphError::FileLoadError { filename, .. }) => assert_eq!(filename, vertices),
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("expected missing vertex file to fail"),
+        }
+    }
+
+    #[test]
+    fn test_compression_detected_from_content_not_extension() {
+        // a plain csv named .gz and a gzipped csv named .csv
+        let edges = test_dir().join("plain_edges.csv.gz");
+        let vertices = test_dir().join("gzipped_vertices.csv");
+        let (graph, _) = graph_from_files(GraphLoadConfig::new(&edges, &vertices)).unwrap();
+        assert_eq!(graph.n_edges(), 4);
+        assert_eq!(graph.n_vertices(), 3);
+        assert_eq!(graph.get_vertex(VertexId(2)).unwrap().x(), -105.1);
+    }
+
+    #[test]
+    fn test_projected_vertices_reprojected_to_wgs84() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("utm_vertices.csv");
+        let crs = Crs::Utm {
+            zone: 13,
+            north: true,
+        };
+        let (graph, _) = graph_from_files(GraphLoadConfig {
+            vertex_crs: crs,
+            ..GraphLoadConfig::new(&edges, &vertices)
+        })
+        .unwrap();
+        let vertex = graph.get_vertex(VertexId(0)).unwrap();
+        assert!((vertex.x() - -105.0).abs() < 1e-5);
+        assert!((vertex.y() - 40.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_planar_vertices_skip_range_check() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("utm_vertices.csv");
+        assert!(graph_from_files(GraphLoadConfig::new(&edges, &vertices)).is_err());
+        let (graph, _) = graph_from_files(GraphLoadConfig {
+            vertex_crs: Crs::Planar,
+            ..GraphLoadConfig::new(&edges, &vertices)
+        })
+        .unwrap();
+        let vertex = graph.get_vertex(VertexId(0)).unwrap();
+        assert_eq!(vertex.x(), 500_000.0);
+    }
+
+    #[test]
+    fn test_csr_adjacency_matches_hash_map() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("degenerate_vertices.csv");
+        let load = |adjacency| {
+            graph_from_files(GraphLoadConfig {
+                degenerate_edge_policy: DegenerateEdgePolicy::Drop,
+                bidirectional: true,
+                adjacency,
+                ..GraphLoadConfig::new(&edges, &vertices)
+            })
+            .unwrap()
+            .0
+        };
+        let hash_map = load(AdjacencyRepresentation::HashMap);
+        let csr = load(AdjacencyRepresentation::Csr);
+        assert!(matches!(csr.adj, Adjacency::Csr(_)));
+        for vertex_id in hash_map.vertex_ids() {
+            assert_eq!(
+                hash_map.out_edges(vertex_id).unwrap(),
+                csr.out_edges(vertex_id).unwrap()
+            );
+            assert_eq!(
+                hash_map.in_edges(vertex_id).unwrap(),
+                csr.in_edges(vertex_id).unwrap()
+            );
+        }
+    }
+
+    /// compares the memory held by each adjacency representation for a
+    /// 100 x 100 grid of one-way and of two-way streets. run with
+    /// `--nocapture` to print the sizes.
+    #[test]
+    fn test_adjacency_memory_comparison() {
+        let grid_size = 100;
+        let dir = tempfile::tempdir().unwrap();
+        let vertices = dir.path().join("vertices.csv");
+        let edges = dir.path().join("edges.csv");
+        let mut vertex_rows = String::from("vertex_id,x,y\n");
+        let mut edge_rows = String::from("edge_id,src_vertex_id,dst_vertex_id,distance\n");
+        let mut n_edges = 0;
+        for row in 0..grid_size {
+            for col in 0..grid_size {
+                let v = row * grid_size + col;
+                let (x, y) = (-105.0 + col as f64 * 0.001, 39.0 + row as f64 * 0.001);
+                vertex_rows.push_str(&format!("{},{},{}\n", v, x, y));
+                if col + 1 < grid_size {
+                    edge_rows.push_str(&format!("{},{},{},100.0\n", n_edges, v, v + 1));
+                    n_edges += 1;
+                }
+                if row + 1 < grid_size {
+                    edge_rows.push_str(&format!("{},{},{},100.0\n", n_edges, v, v + grid_size));
+                    n_edges += 1;
+                }
+            }
+        }
+        std::fs::write(&vertices, vertex_rows).unwrap();
+        std::fs::write(&edges, edge_rows).unwrap();
+
+        let adjacency_bytes = |adjacency, bidirectional| {
+            let (graph, _) = graph_from_files(GraphLoadConfig {
+                bidirectional,
+                adjacency,
+                ..GraphLoadConfig::new(&edges, &vertices)
+            })
+            .unwrap();
+            allocative::size_of_unique_allocated_data(&graph.adj)
+                + allocative::size_of_unique_allocated_data(&graph.rev)
+        };
+        for bidirectional in [false, true] {
+            let hash_map = adjacency_bytes(AdjacencyRepresentation::HashMap, bidirectional);
+            let csr = adjacency_bytes(AdjacencyRepresentation::Csr, bidirectional);
+            println!(
+                "{} grid adjacency bytes: hash_map {}, csr {}",
+                if bidirectional { "two-way" } else { "one-way" },
+                hash_map,
+                csr
+            );
+            // the hash map holds up to four edges of a vertex inline, so csr
+            // saves the most where vertices have fewer
+            if bidirectional {
+                assert!(csr <= hash_map);
+            } else {
+                assert!((csr as f64) < 0.6 * hash_map as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_degenerate_edges_are_reported() {
+        let edges = test_dir().join("degenerate_edges.csv");
+        let vertices = test_dir().join("degenerate_vertices.csv");
+        let (_, report) = graph_from_files(GraphLoadConfig::new(&edges, &vertices)).unwrap();
+        assert_eq!(report.degenerate_edges.self_loops, vec![EdgeId(1)]);
+        assert_eq!(report.degenerate_edges.zero_distance, vec![EdgeId(2)]);
+        assert_eq!(report.duplicate_edges.n_duplicates, 0);
+    }
+}
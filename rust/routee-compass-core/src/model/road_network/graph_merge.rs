@@ -0,0 +1,349 @@
+use super::adjacency::Adjacency;
+use super::csr_adjacency::CsrAdjacency;
+use super::graph::Graph;
+use super::graph_error::GraphError;
+use super::{edge_id::EdgeId, vertex_id::VertexId};
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::unit::{as_f64::AsF64, Distance};
+use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
+use crate::util::geo::haversine;
+use std::collections::HashMap;
+
+/// approximate length of a degree of latitude, used to size the lookup grid
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// smallest grid cell, so that a zero tolerance matches identical coordinates
+const MIN_CELL_METERS: f64 = 0.001;
+
+/// describes where the ids of a merged overlay graph ended up.
+#[derive(Debug, Clone)]
+pub struct GraphMergeReport {
+    /// added to each overlay `EdgeId` to find its id in the merged graph
+    pub edge_id_offset: usize,
+    /// for each overlay `VertexId`, its id in the merged graph
+    pub vertex_ids: Vec<VertexId>,
+    /// number of overlay vertices matched to an existing vertex
+    pub n_shared_vertices: usize,
+}
+
+impl GraphMergeReport {
+    /// the id of an overlay edge in the merged graph
+    pub fn edge_id(&self, overlay_edge_id: EdgeId) -> EdgeId {
+        EdgeId(overlay_edge_id.0 + self.edge_id_offset)
+    }
+
+    /// the id of an overlay vertex in the merged graph, if it exists
+    pub fn vertex_id(&self, overlay_vertex_id: VertexId) -> Option<VertexId> {
+        self.vertex_ids.get(overlay_vertex_id.0).copied()
+    }
+}
+
+/// appends the edges and vertices of `overlay` to `graph`. overlay ids are
+/// offset past the ids of `graph` so they never collide. when a vertex
+/// tolerance is given, each overlay vertex within that distance of a vertex
+/// of `graph` is replaced by it, reconnecting the overlay edges to the
+/// existing vertex. the adjacency lists are rebuilt in the representation
+/// already used by `graph`, and the vertex pair index is rebuilt if present.
+///
+/// # Arguments
+///
+/// * `graph` - the graph to extend
+/// * `overlay` - the graph to merge into `graph`
+/// * `vertex_tolerance` - optional distance in meters within which vertices are shared
+///
+/// # Returns
+///
+/// where the overlay ids were placed in the merged graph, or an error if the
+/// ids of either graph do not match their positions
+pub fn merge_graph(
+    graph: &mut Graph,
+    overlay: &Graph,
+    vertex_tolerance: Option<Distance>,
+) -> Result<GraphMergeReport, GraphError> {
+    validate_dense_ids(graph, "graph")?;
+    validate_dense_ids(overlay, "overlay")?;
+    let edge_id_offset = graph.n_edges();
+    let n_base_vertices = graph.n_vertices();
+
+    // assign each overlay vertex to a shared or a new vertex
+    let grid = vertex_tolerance.map(|tolerance| VertexGrid::new(&graph.vertices, tolerance));
+    let mut vertex_ids = Vec::with_capacity(overlay.n_vertices());
+    let mut vertices = graph.vertices.to_vec();
+    let mut n_shared_vertices = 0;
+    for vertex in overlay.vertices.iter() {
+        match grid.as_ref().and_then(|g| g.nearest(vertex)) {
+            Some(shared) => {
+                n_shared_vertices += 1;
+                vertex_ids.push(shared);
+            }
+            None => {
+                let vertex_id = VertexId(vertices.len());
+                vertices.push(Vertex {
+                    vertex_id,
+                    coordinate: vertex.coordinate,
+                });
+                vertex_ids.push(vertex_id);
+            }
+        }
+    }
+    let remap = |v: VertexId| -> Result<VertexId, GraphError> {
+        vertex_ids
+            .get(v.0)
+            .copied()
+            .ok_or(GraphError::VertexIdNotFound { vertex_id: v })
+    };
+
+    let mut edges = graph.edges.to_vec();
+    for edge in overlay.edges.iter() {
+        edges.push(Edge {
+            edge_id: EdgeId(edge.edge_id.0 + edge_id_offset),
+            src_vertex_id: remap(edge.src_vertex_id)?,
+            dst_vertex_id: remap(edge.dst_vertex_id)?,
+            ..*edge
+        });
+    }
+
+    // only edges already in an adjacency list are carried over, so edges
+    // dropped at load time stay untraversable
+    let mut adj_entries = adjacency_entries(&graph.adj, n_base_vertices);
+    let mut rev_entries = adjacency_entries(&graph.rev, n_base_vertices);
+    for (src, edge_id, dst) in adjacency_entries(&overlay.adj, overlay.n_vertices()) {
+        adj_entries.push((remap(src)?, EdgeId(edge_id.0 + edge_id_offset), remap(dst)?));
+    }
+    for (dst, edge_id, src) in adjacency_entries(&overlay.rev, overlay.n_vertices()) {
+        rev_entries.push((remap(dst)?, EdgeId(edge_id.0 + edge_id_offset), remap(src)?));
+    }
+    let use_csr = matches!(graph.adj, Adjacency::Csr(_));
+    graph.adj = build_adjacency(vertices.len(), &adj_entries, use_csr)?;
+    graph.rev = build_adjacency(vertices.len(), &rev_entries, use_csr)?;
+    graph.edges = edges.into_boxed_slice();
+    graph.vertices = vertices.into_boxed_slice();
+    if graph.vertex_pair_index.is_some() {
+        graph.build_vertex_pair_index();
+    }
+
+    Ok(GraphMergeReport {
+        edge_id_offset,
+        vertex_ids,
+        n_shared_vertices,
+    })
+}
+
+/// ids are offset by position, so each edge and vertex id must match its index
+fn validate_dense_ids(graph: &Graph, name: &str) -> Result<(), GraphError> {
+    if let Some((idx, edge)) = graph
+        .edges
+        .iter()
+        .enumerate()
+        .find(|(idx, edge)| edge.edge_id.0 != *idx)
+    {
+        return Err(GraphError::AttributeError(
+            format!("{} edge_id", name),
+            format!("edge at index {} has id {}", idx, edge.edge_id),
+        ));
+    }
+    if let Some((idx, vertex)) = graph
+        .vertices
+        .iter()
+        .enumerate()
+        .find(|(idx, vertex)| vertex.vertex_id.0 != *idx)
+    {
+        return Err(GraphError::AttributeError(
+            format!("{} vertex_id", name),
+            format!("vertex at index {} has id {}", idx, vertex.vertex_id),
+        ));
+    }
+    Ok(())
+}
+
+fn adjacency_entries(
+    adjacency: &Adjacency,
+    n_vertices: usize,
+) -> Vec<(VertexId, EdgeId, VertexId)> {
+    (0..n_vertices)
+        .map(VertexId)
+        .flat_map(|v| {
+            adjacency
+                .entries(v)
+                .into_iter()
+                .flatten()
+                .map(move |(e, u)| (v, e, u))
+        })
+        .collect()
+}
+
+fn build_adjacency(
+    n_vertices: usize,
+    entries: &[(VertexId, EdgeId, VertexId)],
+    use_csr: bool,
+) -> Result<Adjacency, GraphError> {
+    if use_csr {
+        let csr = CsrAdjacency::from_entries(n_vertices, || entries.iter().copied())?;
+        return Ok(csr.into());
+    }
+    let mut maps = vec![CompactOrderedHashMap::empty(); n_vertices];
+    for (v, e, u) in entries.iter() {
+        maps[v.0].insert(*e, *u);
+    }
+    Ok(maps.into_boxed_slice().into())
+}
+
+/// buckets vertices into square cells the size of the tolerance, so that
+/// matching a vertex only compares the vertices of nearby cells
+struct VertexGrid<'a> {
+    vertices: &'a [Vertex],
+    cells: HashMap<(i64, i64), Vec<VertexId>>,
+    cell_degrees: f64,
+    tolerance_meters: f64,
+}
+
+impl<'a> VertexGrid<'a> {
+    fn new(vertices: &'a [Vertex], tolerance: Distance) -> VertexGrid<'a> {
+        let tolerance_meters = tolerance.as_f64().max(0.0);
+        let cell_degrees = tolerance_meters.max(MIN_CELL_METERS) / METERS_PER_DEGREE;
+        let mut cells: HashMap<(i64, i64), Vec<VertexId>> = HashMap::new();
+        for vertex in vertices.iter() {
+            let key = Self::cell(vertex, cell_degrees);
+            cells.entry(key).or_default().push(vertex.vertex_id);
+        }
+        VertexGrid {
+            vertices,
+            cells,
+            cell_degrees,
+            tolerance_meters,
+        }
+    }
+
+    fn cell(vertex: &Vertex, cell_degrees: f64) -> (i64, i64) {
+        (
+            (vertex.x() as f64 / cell_degrees).floor() as i64,
+            (vertex.y() as f64 / cell_degrees).floor() as i64,
+        )
+    }
+
+    /// the closest vertex within the tolerance, if any
+    fn nearest(&self, vertex: &Vertex) -> Option<VertexId> {
+        let (cx, cy) = Self::cell(vertex, self.cell_degrees);
+        // a degree of longitude shrinks toward the poles, covering more cells
+        let cos_lat = (vertex.y() as f64).to_radians().cos().max(0.01);
+        let dx = (1.0 / cos_lat).ceil() as i64;
+        let mut nearest: Option<(VertexId, f64)> = None;
+        for x in cx - dx..=cx + dx {
+            for y in cy - 1..=cy + 1 {
+                for candidate in self.cells.get(&(x, y)).into_iter().flatten() {
+                    let other = &self.vertices[candidate.0];
+                    let distance =
+                        haversine::coord_distance_meters(&vertex.coordinate, &other.coordinate)
+                            .map(|d| d.as_f64())
+                            .unwrap_or(f64::INFINITY);
+                    let is_nearer = nearest.is_none_or(|(_, d)| distance < d);
+                    if distance <= self.tolerance_meters && is_nearer {
+                        nearest = Some((*candidate, distance));
+                    }
+                }
+            }
+        }
+        nearest.map(|(vertex_id, _)| vertex_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::search::direction::Direction;
+    use crate::algorithm::search::search_algorithm::SearchAlgorithm;
+    use crate::algorithm::search::search_error::SearchError;
+    use crate::algorithm::search::search_instance::SearchInstance;
+    use crate::model::road_network::adjacency_representation::AdjacencyRepresentation;
+    use crate::model::road_network::edge_table_layout::EdgeTableLayout;
+    use crate::model::termination::termination_model::TerminationModel;
+    use crate::test_util::search_instance_builder::SearchInstanceBuilder;
+
+    /// a graph with one edge from each vertex to the next
+    fn build_path_graph(xs: &[f32], adjacency: AdjacencyRepresentation) -> Graph {
+        let vertices = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Vertex::new(i, *x, 0.0))
+            .collect::<Vec<_>>();
+        let edges = (0..xs.len() - 1)
+            .map(|i| Edge::new(i, i, i + 1, 100.0))
+            .collect::<Vec<_>>();
+        let fwd = edges
+            .iter()
+            .map(|e| (e.src_vertex_id, e.edge_id, e.dst_vertex_id))
+            .collect::<Vec<_>>();
+        let bwd = edges
+            .iter()
+            .map(|e| (e.dst_vertex_id, e.edge_id, e.src_vertex_id))
+            .collect::<Vec<_>>();
+        let use_csr = adjacency == AdjacencyRepresentation::Csr;
+        Graph {
+            adj: build_adjacency(vertices.len(), &fwd, use_csr).unwrap(),
+            rev: build_adjacency(vertices.len(), &bwd, use_csr).unwrap(),
+            edges: edges.into_boxed_slice(),
+            vertices: vertices.into_boxed_slice(),
+            vertex_pair_index: None,
+            edge_table_layout: EdgeTableLayout::default(),
+        }
+    }
+
+    fn search_instance(graph: Graph) -> SearchInstance {
+        SearchInstanceBuilder::new(graph)
+            .termination_model(TerminationModel::IterationsLimit { limit: 100 })
+            .build()
+    }
+
+    fn route(graph: Graph, origin: usize, destination: usize) -> Result<Vec<EdgeId>, SearchError> {
+        let si = search_instance(graph);
+        let result = SearchAlgorithm::Dijkstra.run_vertex_oriented(
+            VertexId(origin),
+            Some(VertexId(destination)),
+            &Direction::Forward,
+            None,
+            &si,
+        )?;
+        Ok(result.routes[0].iter().map(|e| e.edge_id).collect())
+    }
+
+    #[test]
+    fn test_route_across_shared_vertex() {
+        for adjacency in [
+            AdjacencyRepresentation::HashMap,
+            AdjacencyRepresentation::Csr,
+        ] {
+            let mut graph = build_path_graph(&[0.0, 0.001], adjacency);
+            // the overlay begins about 1 centimeter from where the base ends
+            let overlay = build_path_graph(&[0.0010001, 0.002, 0.003], adjacency);
+            let report = merge_graph(&mut graph, &overlay, Some(Distance::new(1.0))).unwrap();
+
+            assert_eq!(report.n_shared_vertices, 1);
+            assert_eq!(report.vertex_id(VertexId(0)), Some(VertexId(1)));
+            assert_eq!(report.vertex_id(VertexId(2)), Some(VertexId(3)));
+            assert_eq!(graph.n_vertices(), 4);
+            assert_eq!(graph.n_edges(), 3);
+            assert_eq!(graph.edges[1].src_vertex_id, VertexId(1));
+
+            let expected = vec![
+                EdgeId(0),
+                report.edge_id(EdgeId(0)),
+                report.edge_id(EdgeId(1)),
+            ];
+            assert_eq!(route(graph, 0, 3).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_merge_without_tolerance_keeps_graphs_apart() {
+        let mut graph = build_path_graph(&[0.0, 0.001], AdjacencyRepresentation::HashMap);
+        let overlay = build_path_graph(&[0.001, 0.002], AdjacencyRepresentation::HashMap);
+        let report = merge_graph(&mut graph, &overlay, None).unwrap();
+        assert_eq!(report.n_shared_vertices, 0);
+        assert_eq!(report.vertex_ids, vec![VertexId(2), VertexId(3)]);
+        assert_eq!(graph.n_vertices(), 4);
+        assert!(matches!(
+            route(graph, 0, 3),
+            Err(SearchError::NoPathExists(..))
+        ));
+    }
+}
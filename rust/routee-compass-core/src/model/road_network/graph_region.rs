@@ -0,0 +1,295 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    model::property::{edge::Edge, vertex::Vertex},
+    util::fs::read_utils,
+};
+
+use super::{
+    duplicate_edge_policy::DuplicateEdgePolicy, duplicate_edge_report::DuplicateEdgeReport,
+    edge_id::EdgeId, graph_error::GraphError, vertex_id::VertexId,
+};
+
+/// a rectangle in the WGS84 coordinates of the graph vertices
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl BoundingBox {
+    /// true if the vertex falls inside or on the edge of this box
+    pub fn contains(&self, vertex: &Vertex) -> bool {
+        self.min_x <= vertex.x()
+            && vertex.x() <= self.max_x
+            && self.min_y <= vertex.y()
+            && vertex.y() <= self.max_y
+    }
+}
+
+/// how to treat an edge with one endpoint inside the bounding box of a
+/// regional graph and the other outside
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryEdgePolicy {
+    /// drop the edge, so the regional graph only has edges inside the box
+    #[default]
+    Drop,
+    /// keep the edge along with its vertex outside the box, so that routes
+    /// may begin or end just past the boundary
+    Keep,
+}
+
+/// the part of a graph to load, which saves memory when routing in one city
+/// of a continental dataset. only edges with both endpoints inside the box
+/// are kept, along with edges crossing the boundary when the policy keeps
+/// them, and the kept edges and their vertices are renumbered to dense ids
+/// in the order of the input files.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct GraphRegion {
+    pub bounding_box: BoundingBox,
+    #[serde(default)]
+    pub boundary_edge_policy: BoundaryEdgePolicy,
+}
+
+/// describes where the edges and vertices of a regional graph came from.
+/// regional ids are dense, so any table with one row per edge of the full
+/// graph, such as a speed or grade table, must be subset with the
+/// [`super::edge_table_layout::EdgeTableLayout`] of the graph before it is used.
+#[derive(Debug, Clone)]
+pub struct GraphRegionReport {
+    /// for each regional `EdgeId`, its id in the full edge list
+    pub edge_ids: Vec<EdgeId>,
+    /// for each regional `VertexId`, its id in the full vertex list
+    pub vertex_ids: Vec<VertexId>,
+}
+
+/// the edge rows and vertices that fall in a region, renumbered to dense ids
+pub(super) struct RegionSelection {
+    pub edges: Vec<Edge>,
+    pub vertices: Box<[Vertex]>,
+    pub report: GraphRegionReport,
+    pub duplicate_edges: DuplicateEdgeReport,
+}
+
+/// selects the part of a graph within a region. the full vertex list is
+/// loaded first to find the vertices inside the box, then the edge list is
+/// streamed so that edges outside the region are never stored. a row whose
+/// id was already seen is left out, as the loader would for the full graph.
+///
+/// # Arguments
+///
+/// * `edge_list_csv` - path to the CSV file containing edge attributes
+/// * `all_vertices` - every vertex of the full graph
+/// * `region` - the region to select
+/// * `n_edges` - number of rows in the edge list
+/// * `duplicate_edge_policy` - whether a duplicate `edge_id` is an error
+///
+/// # Returns
+///
+/// the regional edge rows and vertices, or an error if the edge list could
+/// not be read or refers to a missing vertex
+pub(super) fn select_region<P: AsRef<Path>>(
+    edge_list_csv: &P,
+    all_vertices: &[Vertex],
+    region: &GraphRegion,
+    n_edges: usize,
+    duplicate_edge_policy: DuplicateEdgePolicy,
+) -> Result<RegionSelection, GraphError> {
+    let bounding_box = &region.bounding_box;
+    let inside = |vertex_id: VertexId| -> Result<bool, GraphError> {
+        all_vertices
+            .get(vertex_id.0)
+            .map(|v| bounding_box.contains(v))
+            .ok_or(GraphError::VertexIdNotFound { vertex_id })
+    };
+
+    let mut duplicate_edges = DuplicateEdgeReport::new(n_edges);
+    let mut full_edges: Vec<Edge> = vec![];
+    let rows = read_utils::iterator_from_csv::<_, Edge>(edge_list_csv, true, None)?;
+    for row in rows {
+        let edge = row?;
+        if duplicate_edges.inspect(edge.edge_id) {
+            continue;
+        }
+        let keep = match (inside(edge.src_vertex_id)?, inside(edge.dst_vertex_id)?) {
+            (true, true) => true,
+            (false, false) => false,
+            _ => region.boundary_edge_policy == BoundaryEdgePolicy::Keep,
+        };
+        if keep {
+            full_edges.push(edge);
+        }
+    }
+    if !duplicate_edges.is_empty() && duplicate_edge_policy == DuplicateEdgePolicy::Error {
+        return Err(GraphError::DuplicateEdgeIds(duplicate_edges.to_string()));
+    }
+
+    // vertices keep the order of the vertex list, and edges the order of the edge list
+    let mut is_endpoint = vec![false; all_vertices.len()];
+    for edge in full_edges.iter() {
+        is_endpoint[edge.src_vertex_id.0] = true;
+        is_endpoint[edge.dst_vertex_id.0] = true;
+    }
+    let mut regional_vertex_ids: Vec<Option<VertexId>> = vec![None; all_vertices.len()];
+    let mut vertices = vec![];
+    let mut vertex_ids = vec![];
+    for (idx, vertex) in all_vertices.iter().enumerate() {
+        if is_endpoint[idx] || bounding_box.contains(vertex) {
+            let vertex_id = VertexId(vertices.len());
+            regional_vertex_ids[idx] = Some(vertex_id);
+            vertices.push(Vertex {
+                vertex_id,
+                coordinate: vertex.coordinate,
+            });
+            vertex_ids.push(vertex.vertex_id);
+        }
+    }
+    let remap = |vertex_id: VertexId| -> Result<VertexId, GraphError> {
+        regional_vertex_ids
+            .get(vertex_id.0)
+            .copied()
+            .flatten()
+            .ok_or(GraphError::VertexIdNotFound { vertex_id })
+    };
+
+    let mut edges = Vec::with_capacity(full_edges.len());
+    let mut edge_ids = Vec::with_capacity(full_edges.len());
+    for edge in full_edges.iter() {
+        edges.push(Edge {
+            edge_id: EdgeId(edges.len()),
+            src_vertex_id: remap(edge.src_vertex_id)?,
+            dst_vertex_id: remap(edge.dst_vertex_id)?,
+            distance: edge.distance,
+            closed: edge.closed,
+        });
+        edge_ids.push(edge.edge_id);
+    }
+
+    Ok(RegionSelection {
+        edges,
+        vertices: vertices.into_boxed_slice(),
+        report: GraphRegionReport {
+            edge_ids,
+            vertex_ids,
+        },
+        duplicate_edges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::{
+        adjacency_representation::AdjacencyRepresentation,
+        graph::Graph,
+        graph_loader::{graph_from_files, GraphLoadConfig},
+    };
+    use std::path::PathBuf;
+
+    fn test_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+    }
+
+    /// the south-west 2x2 corner of the 3x3 grid in the region test files
+    fn south_west() -> BoundingBox {
+        BoundingBox {
+            min_x: -105.05,
+            min_y: 38.95,
+            max_x: -104.85,
+            max_y: 39.15,
+        }
+    }
+
+    fn load(
+        policy: BoundaryEdgePolicy,
+        bidirectional: bool,
+        adjacency: AdjacencyRepresentation,
+    ) -> (Graph, GraphRegionReport) {
+        let edges = test_dir().join("region_edges.csv");
+        let vertices = test_dir().join("region_vertices.csv");
+        let config = GraphLoadConfig {
+            region: Some(GraphRegion {
+                bounding_box: south_west(),
+                boundary_edge_policy: policy,
+            }),
+            bidirectional,
+            adjacency,
+            ..GraphLoadConfig::new(&edges, &vertices)
+        };
+        let (graph, report) = graph_from_files(config).unwrap();
+        (graph, report.region.unwrap())
+    }
+
+    #[test]
+    fn test_load_region_of_grid() {
+        let (graph, report) = load(
+            BoundaryEdgePolicy::Drop,
+            false,
+            AdjacencyRepresentation::HashMap,
+        );
+        // the corner holds 4 vertices joined by 4 roads in both directions
+        assert_eq!(graph.n_vertices(), 4);
+        assert_eq!(graph.n_edges(), 8);
+        assert_eq!(
+            report.vertex_ids,
+            vec![VertexId(0), VertexId(1), VertexId(3), VertexId(4)]
+        );
+        for (idx, edge) in graph.edges.iter().enumerate() {
+            assert_eq!(edge.edge_id, EdgeId(idx));
+            let out_edges = graph.out_edges(edge.src_vertex_id).unwrap();
+            assert!(out_edges.contains(&edge.edge_id));
+            let full_src = report.vertex_ids[edge.src_vertex_id.0];
+            let full_dst = report.vertex_ids[edge.dst_vertex_id.0];
+            assert!([0, 1, 3, 4].contains(&full_src.0));
+            assert!([0, 1, 3, 4].contains(&full_dst.0));
+        }
+
+        // per-edge tables of the full graph are subset to the regional edges
+        let full_table = (0..24).map(|i| i * 10).collect::<Vec<_>>();
+        let table = graph.edge_table_layout.align(&full_table, |v| *v).unwrap();
+        assert_eq!(table.len(), graph.n_edges());
+        for (regional, full) in report.edge_ids.iter().enumerate() {
+            assert_eq!(table[regional], full.0 * 10);
+        }
+    }
+
+    #[test]
+    fn test_keep_edges_crossing_boundary() {
+        let (graph, report) = load(
+            BoundaryEdgePolicy::Keep,
+            false,
+            AdjacencyRepresentation::HashMap,
+        );
+        // 4 more roads leave the corner for the 4 neighboring vertices
+        assert_eq!(graph.n_vertices(), 8);
+        assert_eq!(graph.n_edges(), 16);
+        assert!(!report.vertex_ids.contains(&VertexId(8)));
+    }
+
+    #[test]
+    fn test_bidirectional_csr_region() {
+        let (graph, report) = load(BoundaryEdgePolicy::Drop, true, AdjacencyRepresentation::Csr);
+        assert_eq!(graph.n_edges(), 16);
+        let reverse = graph.edges[8];
+        assert_eq!(reverse.src_vertex_id, graph.edges[0].dst_vertex_id);
+        assert!(graph
+            .out_edges(reverse.src_vertex_id)
+            .unwrap()
+            .contains(&reverse.edge_id));
+
+        // per-edge tables are subset and then mirrored onto the reverse edges
+        let full_table = (0..24).collect::<Vec<_>>();
+        let table = graph.edge_table_layout.align(&full_table, |v| -v).unwrap();
+        assert_eq!(table.len(), 16);
+        assert_eq!(table[8], -(report.edge_ids[0].0 as i32));
+    }
+}
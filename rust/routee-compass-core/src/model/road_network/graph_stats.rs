@@ -0,0 +1,143 @@
+use super::{graph::Graph, graph_error::GraphError, vertex_id::VertexId};
+use crate::model::unit::{as_f64::AsF64, DistanceUnit, BASE_DISTANCE_UNIT};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// summary statistics of a loaded graph, used for data QA.
+/// an edge is two-way if some other edge connects its vertices in the
+/// opposite direction, and one-way otherwise.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct GraphStats {
+    pub n_edges: usize,
+    pub n_vertices: usize,
+    pub mean_out_degree: f64,
+    pub distance_unit: DistanceUnit,
+    pub min_edge_distance: f64,
+    pub max_edge_distance: f64,
+    pub mean_edge_distance: f64,
+    pub one_way_edges: usize,
+    pub two_way_edges: usize,
+    /// number of edges of each road class, if road classes were provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub road_classes: Option<BTreeMap<u8, usize>>,
+}
+
+impl GraphStats {
+    /// computes the statistics of a graph in one pass over its edges and
+    /// adjacency lists.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the graph to summarize
+    /// * `road_classes` - optional road class of each edge, indexed by `EdgeId`
+    ///
+    /// # Returns
+    ///
+    /// the graph statistics, or an error if an edge has no road class
+    pub fn new(graph: &Graph, road_classes: Option<&[u8]>) -> Result<GraphStats, GraphError> {
+        let mut min_distance = f64::INFINITY;
+        let mut max_distance = f64::NEG_INFINITY;
+        let mut total_distance = 0.0;
+        let mut histogram: Option<BTreeMap<u8, usize>> = road_classes.map(|_| BTreeMap::new());
+        for edge in graph.edges.iter() {
+            let distance = edge.distance.as_f64();
+            min_distance = min_distance.min(distance);
+            max_distance = max_distance.max(distance);
+            total_distance += distance;
+            if let (Some(lookup), Some(histogram)) = (road_classes, histogram.as_mut()) {
+                let road_class =
+                    lookup
+                        .get(edge.edge_id.0)
+                        .ok_or(GraphError::EdgeAttributeNotFound {
+                            edge_id: edge.edge_id,
+                        })?;
+                *histogram.entry(*road_class).or_default() += 1;
+            }
+        }
+
+        let mut n_out_edges = 0;
+        let mut two_way_edges = 0;
+        for src in (0..graph.adj.n_vertices()).map(VertexId) {
+            for (edge_id, dst) in graph.adj.entries(src).into_iter().flatten() {
+                n_out_edges += 1;
+                let reversed = graph
+                    .adj
+                    .entries(dst)
+                    .map(|mut dst_out| dst_out.any(|(e, v)| v == src && e != edge_id))
+                    .unwrap_or(false);
+                if reversed {
+                    two_way_edges += 1;
+                }
+            }
+        }
+
+        let n_edges = graph.n_edges();
+        let n_vertices = graph.n_vertices();
+        let (min_edge_distance, max_edge_distance, mean_edge_distance) = if n_edges == 0 {
+            (0.0, 0.0, 0.0)
+        } else {
+            (min_distance, max_distance, total_distance / n_edges as f64)
+        };
+        let mean_out_degree = if n_vertices == 0 {
+            0.0
+        } else {
+            n_out_edges as f64 / n_vertices as f64
+        };
+
+        Ok(GraphStats {
+            n_edges,
+            n_vertices,
+            mean_out_degree,
+            distance_unit: BASE_DISTANCE_UNIT,
+            min_edge_distance,
+            max_edge_distance,
+            mean_edge_distance,
+            one_way_edges: n_out_edges - two_way_edges,
+            two_way_edges,
+            road_classes: histogram,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::graph_fixture::graph_from_links;
+
+    /// a two-way street between vertices 0 and 1, and one-way edges 1 -> 2 and 2 -> 0
+    fn build_graph() -> Graph {
+        graph_from_links(3, &[(0, 1, 10.0), (1, 0, 10.0), (1, 2, 20.0), (2, 0, 40.0)])
+    }
+
+    #[test]
+    fn test_graph_stats() {
+        let graph = build_graph();
+        let road_classes = [1, 1, 2, 3];
+        let stats = GraphStats::new(&graph, Some(&road_classes)).unwrap();
+        assert_eq!(stats.n_edges, 4);
+        assert_eq!(stats.n_vertices, 3);
+        assert!((stats.mean_out_degree - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.min_edge_distance, 10.0);
+        assert_eq!(stats.max_edge_distance, 40.0);
+        assert_eq!(stats.mean_edge_distance, 20.0);
+        assert_eq!(stats.two_way_edges, 2);
+        assert_eq!(stats.one_way_edges, 2);
+        assert_eq!(
+            stats.road_classes,
+            Some(BTreeMap::from([(1, 2), (2, 1), (3, 1)]))
+        );
+    }
+
+    #[test]
+    fn test_graph_stats_missing_road_class() {
+        let graph = build_graph();
+        let result = GraphStats::new(&graph, Some(&[1, 1]));
+        assert!(matches!(
+            result,
+            Err(GraphError::EdgeAttributeNotFound { .. })
+        ));
+        let stats = GraphStats::new(&graph, None).unwrap();
+        let stats_json = serde_json::to_value(stats).unwrap();
+        assert!(stats_json.get("road_classes").is_none());
+    }
+}
@@ -0,0 +1,18 @@
+use super::{edge_id::EdgeId, vertex_id::VertexId};
+use serde::Serialize;
+
+/// every edge that touches a vertex, as (`EdgeId`, other `VertexId`) pairs.
+/// incoming edges pair each edge with its source vertex and outgoing edges
+/// pair each edge with its destination vertex. a self-loop appears in both.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IncidentEdges {
+    pub incoming: Vec<(EdgeId, VertexId)>,
+    pub outgoing: Vec<(EdgeId, VertexId)>,
+}
+
+impl IncidentEdges {
+    /// true if no edge arrives at or departs from the vertex
+    pub fn is_isolated(&self) -> bool {
+        self.incoming.is_empty() && self.outgoing.is_empty()
+    }
+}
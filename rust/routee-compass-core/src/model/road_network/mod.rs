@@ -1,7 +1,22 @@
+pub mod adjacency;
+pub mod adjacency_representation;
+pub mod bidirectional_edges;
+pub mod csr_adjacency;
+pub mod degenerate_edge_policy;
+pub mod degenerate_edge_report;
+pub mod duplicate_edge_policy;
+pub mod duplicate_edge_report;
 pub mod edge_id;
 pub mod edge_loader;
+pub mod edge_table_layout;
 pub mod graph;
+pub mod graph_dot;
 pub mod graph_error;
 pub mod graph_loader;
+pub mod graph_merge;
+pub mod graph_region;
+pub mod graph_stats;
+pub mod incident_edges;
+pub mod road_class_table;
 pub mod vertex_id;
 pub mod vertex_loader;
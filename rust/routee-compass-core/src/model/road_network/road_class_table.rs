@@ -0,0 +1,132 @@
+use super::{edge_table_layout::EdgeTableLayout, graph_error::GraphError};
+use crate::util::fs::{read_decoders, read_utils};
+use std::{collections::HashMap, path::Path};
+
+/// reads the road class of each edge from a file with one integer per line.
+/// without a mapping, each line holds the road class itself (0-255). with a
+/// mapping, each line holds a code, such as a numeric highway type from an
+/// OSM export, which is translated to its road class.
+///
+/// # Arguments
+///
+/// * `path` - file with one road class or code per edge
+/// * `codes` - optional mapping from the code in the file to a road class
+///
+/// # Returns
+///
+/// the road class of each edge, or an error if a row is not an integer or
+/// its code is missing from the mapping
+pub fn read_road_classes<P: AsRef<Path>>(
+    path: &P,
+    codes: Option<&HashMap<i64, u8>>,
+) -> Result<Box<[u8]>, std::io::Error> {
+    match codes {
+        None => read_utils::read_raw_file(path, read_decoders::u8, None),
+        Some(codes) => read_utils::read_raw_file(
+            path,
+            |idx, row| {
+                let code = row.trim().parse::<i64>().map_err(|e| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("failure decoding row {} due to: {}", row, e),
+                    )
+                })?;
+                codes.get(&code).cloned().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!(
+                            "road class code {} on row {} has no entry in road_class_codes",
+                            code, idx
+                        ),
+                    )
+                })
+            },
+            None,
+        ),
+    }
+}
+
+/// reads the road class of each edge, as in [`read_road_classes`], and
+/// arranges the rows by the `EdgeId`s of the graph. this is shared by the
+/// models and plugins that read a road class file, so that a regional or
+/// bidirectional graph finds the road class of each of its edges.
+///
+/// # Arguments
+///
+/// * `path` - file with one road class or code per edge list row
+/// * `codes` - optional mapping from the code in the file to a road class
+/// * `layout` - the edge table layout of the graph
+///
+/// # Returns
+///
+/// the road class of each graph edge, or an error if the file could not be
+/// read or is missing a row of the graph
+pub fn read_edge_road_classes<P: AsRef<Path>>(
+    path: &P,
+    codes: Option<&HashMap<i64, u8>>,
+    layout: &EdgeTableLayout,
+) -> Result<Box<[u8]>, GraphError> {
+    let load_error = |source: GraphError| GraphError::FileLoadError {
+        filename: path.as_ref().to_path_buf(),
+        source: Box::new(source),
+    };
+    let road_classes = read_road_classes(path, codes).map_err(|e| load_error(e.into()))?;
+    layout
+        .align(&road_classes, |road_class| *road_class)
+        .map_err(load_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_file(filename: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("road_network")
+            .join("test")
+            .join(filename)
+    }
+
+    #[test]
+    fn test_integer_codes_are_mapped_to_road_classes() {
+        let codes = HashMap::from([(101, 1), (105, 3), (113, 5)]);
+        let road_classes =
+            read_road_classes(&test_file("road_class_codes.txt"), Some(&codes)).unwrap();
+        assert_eq!(road_classes.to_vec(), vec![1, 3, 1, 5]);
+    }
+
+    #[test]
+    fn test_unmapped_code_is_an_error() {
+        let codes = HashMap::from([(101, 1)]);
+        let error = read_road_classes(&test_file("road_class_codes_unmapped.txt"), Some(&codes))
+            .unwrap_err();
+        assert!(error.to_string().contains("999"), "{}", error);
+    }
+
+    #[test]
+    fn test_without_mapping_rows_are_road_classes() {
+        // codes above 255 are not valid road classes
+        assert!(read_road_classes(&test_file("road_class_codes.txt"), None).is_ok());
+        assert!(read_road_classes(&test_file("road_class_codes_unmapped.txt"), None).is_err());
+    }
+
+    #[test]
+    fn test_road_classes_arranged_by_graph_edge() {
+        let codes = HashMap::from([(101, 1), (105, 3), (113, 5)]);
+        // the reverse of each of the 4 rows is appended as another edge
+        let layout = EdgeTableLayout {
+            n_rows: 4,
+            rows: None,
+            bidirectional: true,
+        };
+        let road_classes =
+            read_edge_road_classes(&test_file("road_class_codes.txt"), Some(&codes), &layout)
+                .unwrap();
+        assert_eq!(road_classes.to_vec(), vec![1, 3, 1, 5, 1, 3, 1, 5]);
+        let error = read_edge_road_classes(&test_file("missing.txt"), None, &layout).unwrap_err();
+        assert!(error.to_string().contains("missing.txt"), "{}", error);
+    }
+}
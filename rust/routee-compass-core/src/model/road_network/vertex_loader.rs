@@ -2,33 +2,89 @@ use std::path::PathBuf;
 
 use crate::model::property::vertex::Vertex;
 use crate::model::road_network::graph_error::GraphError;
+use crate::model::road_network::vertex_id::VertexId;
 use crate::util::fs::read_utils;
-use kdam::{Bar, BarExt};
+use crate::util::geo::coord::InternalCoord;
+use crate::util::geo::crs::Crs;
+use crate::util::progress::{LoadProgress, ProgressCallback};
+use serde::Deserialize;
 
+/// configuration for loading the vertex list. the vertex list progress bar is
+/// drawn on the second terminal row so that it can be loaded alongside the
+/// edge list. vertex coordinates in a projected `crs` are reprojected to WGS84,
+/// and planar coordinates are kept as is.
 pub struct VertexLoaderConfig {
     pub vertex_list_csv: PathBuf,
     pub n_vertices: usize,
+    pub crs: Crs,
+    /// draw a progress bar while loading, unless a progress callback is provided
+    pub verbose: bool,
+    /// receives (rows loaded, n_vertices) as the vertex list is read
+    pub progress: Option<ProgressCallback>,
 }
 
 impl TryFrom<VertexLoaderConfig> for Box<[Vertex]> {
     type Error = GraphError;
 
     fn try_from(conf: VertexLoaderConfig) -> Result<Self, Self::Error> {
-        let mut processed: usize = 0;
-        let mut pb = Bar::builder()
-            .total(conf.n_vertices)
-            .animation("fillup")
-            .desc("vertex list")
-            .build()
-            .map_err(|e| GraphError::ProgressBarBuildError(String::from("vertex list"), e))?;
+        let mut pb = LoadProgress::new(
+            conf.progress,
+            conf.verbose,
+            "vertex list",
+            conf.n_vertices,
+            1,
+        )
+        .map_err(|e| GraphError::ProgressBarBuildError(String::from("vertex list"), e))?;
 
-        let cb = Box::new(|_v: &Vertex| {
-            let _ = pb.update(1);
-            processed += 1;
-        });
-        let result: Box<[Vertex]> = read_utils::from_csv(&conf.vertex_list_csv, true, Some(cb))?;
+        if conf.crs == Crs::Wgs84 {
+            let cb = Box::new(|_v: &Vertex| {
+                pb.update();
+            });
+            let result: Box<[Vertex]> =
+                read_utils::from_csv(&conf.vertex_list_csv, true, Some(cb))?;
+            return Ok(result);
+        }
 
-        println!();
-        Ok(result)
+        // projected coordinates are read at full precision and are only
+        // validated as longitude and latitude once reprojected. planar
+        // coordinates are not longitude and latitude, so they are not validated
+        let cb = Box::new(|_v: &ProjectedVertexRow| {
+            pb.update();
+        });
+        let rows: Box<[ProjectedVertexRow]> =
+            read_utils::from_csv(&conf.vertex_list_csv, true, Some(cb))?;
+        if conf.crs == Crs::Planar {
+            let vertices = rows
+                .iter()
+                .map(|row| Vertex {
+                    vertex_id: VertexId(row.vertex_id),
+                    coordinate: InternalCoord::from((row.x as f32, row.y as f32)),
+                })
+                .collect();
+            return Ok(vertices);
+        }
+        rows.iter()
+            .map(|row| {
+                let (x, y) = conf.crs.to_wgs84(row.x, row.y);
+                let coordinate = InternalCoord::new(x as f32, y as f32).map_err(|e| {
+                    GraphError::AttributeError(
+                        format!("vertex {} reprojected from {}", row.vertex_id, conf.crs),
+                        e,
+                    )
+                })?;
+                Ok(Vertex {
+                    vertex_id: VertexId(row.vertex_id),
+                    coordinate,
+                })
+            })
+            .collect()
     }
 }
+
+/// a vertex list row with coordinates in a projected CRS
+#[derive(Deserialize)]
+struct ProjectedVertexRow {
+    vertex_id: usize,
+    x: f64,
+    y: f64,
+}
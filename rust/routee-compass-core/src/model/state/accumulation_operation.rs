@@ -0,0 +1,37 @@
+use super::update_operation::UpdateOperation;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// declares how the values assigned to a state feature combine as edges are
+/// traversed. distance, time, and energy features always accumulate by
+/// summation. custom features default to summation but may instead keep the
+/// maximum or minimum value observed, such as the steepest grade along a route.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccumulationOperation {
+    #[default]
+    Sum,
+    Max,
+    Min,
+}
+
+impl AccumulationOperation {
+    pub(crate) fn update_operation(&self) -> UpdateOperation {
+        match self {
+            AccumulationOperation::Sum => UpdateOperation::Add,
+            AccumulationOperation::Max => UpdateOperation::Max,
+            AccumulationOperation::Min => UpdateOperation::Min,
+        }
+    }
+}
+
+impl Display for AccumulationOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AccumulationOperation::Sum => "sum",
+            AccumulationOperation::Max => "max",
+            AccumulationOperation::Min => "min",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -1,7 +1,9 @@
+pub mod accumulation_operation;
 pub mod custom_feature_format;
 pub mod indexed_state_feature;
 pub mod state_error;
 pub mod state_feature;
 pub mod state_model;
+pub mod state_model_builder;
 pub mod unit_codec_name;
 pub mod update_operation;
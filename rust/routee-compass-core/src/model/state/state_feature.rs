@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-use super::{custom_feature_format::CustomFeatureFormat, state_error::StateError};
+use super::{
+    accumulation_operation::AccumulationOperation, custom_feature_format::CustomFeatureFormat,
+    state_error::StateError,
+};
 use crate::model::{traversal::state::state_variable::StateVar, unit};
 use serde::{Deserialize, Serialize};
 
@@ -22,7 +25,8 @@ use serde::{Deserialize, Serialize};
 /// state = [
 ///   { distance_unit = "kilometers", initial = 0.0 },
 ///   { time_unit = "minutes", initial = 0.0 },
-///   { name = "soc", unit = "percent", format = { type = "floating_point", initial = 0.0 } }
+///   { name = "soc", unit = "percent", format = { type = "floating_point", initial = 0.0 } },
+///   { name = "grade", unit = "decimal", format = { type = "floating_point", initial = 0.0 }, accumulation = "max" }
 /// ]
 ///
 /// NOTE: deserialization is "untagged" so each variant must have a unique set of
@@ -48,6 +52,8 @@ pub enum StateFeature {
         r#type: String,
         unit: String,
         format: CustomFeatureFormat,
+        #[serde(default)]
+        accumulation: AccumulationOperation,
     },
 }
 
@@ -98,13 +104,15 @@ impl PartialEq for StateFeature {
                     r#type: a_name,
                     unit: a_unit,
                     format: _,
+                    accumulation: a_accumulation,
                 },
                 StateFeature::Custom {
                     r#type: b_name,
                     unit: b_unit,
                     format: _,
+                    accumulation: b_accumulation,
                 },
-            ) => a_name == b_name && a_unit == b_unit,
+            ) => a_name == b_name && a_unit == b_unit && a_accumulation == b_accumulation,
             _ => false,
         }
     }
@@ -128,8 +136,13 @@ impl Display for StateFeature {
                 r#type: name,
                 unit,
                 format,
+                accumulation,
             } => {
-                write!(f, "name: {} unit: {}, repr: {}", name, unit, format)
+                write!(
+                    f,
+                    "name: {} unit: {}, repr: {}, accumulation: {}",
+                    name, unit, format, accumulation
+                )
             }
         }
     }
@@ -154,6 +167,7 @@ impl StateFeature {
                 r#type,
                 unit: _,
                 format: _,
+                accumulation: _,
             } => r#type.clone(),
         }
     }
@@ -176,6 +190,7 @@ impl StateFeature {
                 r#type: _,
                 unit,
                 format: _,
+                accumulation: _,
             } => unit.clone(),
         }
     }
@@ -190,6 +205,7 @@ impl StateFeature {
                 r#type: _,
                 unit: _,
                 format,
+                accumulation: _,
             } => *format,
             _ => CustomFeatureFormat::default(),
         }
@@ -213,6 +229,7 @@ impl StateFeature {
                 r#type: _,
                 unit: _,
                 format,
+                accumulation: _,
             } => format.initial(),
         }
     }
@@ -256,12 +273,27 @@ impl StateFeature {
         }
     }
 
+    /// the operation used to accumulate values for this feature. distance,
+    /// time, and energy features always use summation.
+    pub fn get_accumulation_operation(&self) -> AccumulationOperation {
+        match self {
+            StateFeature::Custom {
+                r#type: _,
+                unit: _,
+                format: _,
+                accumulation,
+            } => *accumulation,
+            _ => AccumulationOperation::Sum,
+        }
+    }
+
     pub fn get_custom_feature_format(&self) -> Result<&CustomFeatureFormat, StateError> {
         match self {
             StateFeature::Custom {
                 r#type: _,
                 unit: _,
                 format,
+                accumulation: _,
             } => Ok(format),
             _ => Err(StateError::UnexpectedFeatureUnit(
                 self.get_feature_unit_name(),
@@ -83,6 +83,11 @@ impl StateModel {
         self.0.contains_key(k)
     }
 
+    /// the index of a feature in the state vector, if the feature is in this model
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.0.get_index(&String::from(name))
+    }
+
     /// collects the state model tuples and clones them so they can
     /// be used to build other collections
     pub fn to_vec(&self) -> Vec<(String, IndexedEntry<StateFeature>)> {
@@ -279,6 +284,42 @@ impl StateModel {
         Ok(next_val - prev_val)
     }
 
+    /// gets the raw value of a feature from a state vector by name, in the
+    /// feature's own unit. prefer this over indexing the state vector
+    /// directly, since indices shift when features are added to the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - state vector to inspect
+    /// * `name`  - feature name to extract
+    ///
+    /// # Result
+    ///
+    /// the state variable for this feature, or an error if the name is not in the model
+    pub fn get_value(&self, state: &[StateVar], name: &str) -> Result<StateVar, StateError> {
+        self.get_state_variable(state, &String::from(name))
+    }
+
+    /// replaces the raw value of a feature in a state vector by name, in the feature's own unit
+    pub fn set_value(
+        &self,
+        state: &mut [StateVar],
+        name: &str,
+        value: &StateVar,
+    ) -> Result<(), StateError> {
+        self.update_state(state, &String::from(name), value, UpdateOperation::Replace)
+    }
+
+    /// adds to the raw value of a feature in a state vector by name, in the feature's own unit
+    pub fn add_value(
+        &self,
+        state: &mut [StateVar],
+        name: &str,
+        value: &StateVar,
+    ) -> Result<(), StateError> {
+        self.update_state(state, &String::from(name), value, UpdateOperation::Add)
+    }
+
     /// adds a distance value with distance unit to this feature vector
     pub fn add_distance(
         &self,
@@ -405,6 +446,36 @@ impl StateModel {
         self.update_state(state, name, &encoded_value, UpdateOperation::Replace)
     }
 
+    /// accumulates a custom floating point value into this feature vector using
+    /// the accumulation operation declared on the feature (sum, max, or min).
+    pub fn accumulate_custom_f64(
+        &self,
+        state: &mut [StateVar],
+        name: &String,
+        value: &f64,
+    ) -> Result<(), StateError> {
+        let feature = self.get_feature(name)?;
+        let format = feature.get_custom_feature_format()?;
+        let encoded_value = format.encode_f64(value)?;
+        let op = feature.get_accumulation_operation().update_operation();
+        self.update_state(state, name, &encoded_value, op)
+    }
+
+    /// accumulates a custom unsigned integer value into this feature vector using
+    /// the accumulation operation declared on the feature (sum, max, or min).
+    pub fn accumulate_custom_u64(
+        &self,
+        state: &mut [StateVar],
+        name: &String,
+        value: &u64,
+    ) -> Result<(), StateError> {
+        let feature = self.get_feature(name)?;
+        let format = feature.get_custom_feature_format()?;
+        let encoded_value = format.encode_u64(value)?;
+        let op = feature.get_accumulation_operation().update_operation();
+        self.update_state(state, name, &encoded_value, op)
+    }
+
     /// uses the state model to pretty print a state instance as a JSON object
     ///
     /// # Arguments
@@ -546,3 +617,93 @@ impl From<Vec<(String, StateFeature)>> for StateModel {
         StateModel::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::state::accumulation_operation::AccumulationOperation;
+    use ordered_float::OrderedFloat;
+
+    fn custom_feature(accumulation: AccumulationOperation) -> StateFeature {
+        StateFeature::Custom {
+            r#type: String::from("grade"),
+            unit: String::from("decimal"),
+            format: CustomFeatureFormat::FloatingPoint {
+                initial: OrderedFloat(0.0),
+            },
+            accumulation,
+        }
+    }
+
+    fn accumulate_grades(accumulation: AccumulationOperation) -> f64 {
+        let name = String::from("grade");
+        let state_model = StateModel::new(vec![(name.clone(), custom_feature(accumulation))]);
+        let mut state = state_model.initial_state().unwrap();
+        for grade in [0.02, 0.05, -0.03] {
+            state_model
+                .accumulate_custom_f64(&mut state, &name, &grade)
+                .unwrap();
+        }
+        state_model.get_custom_f64(&state, &name).unwrap()
+    }
+
+    #[test]
+    fn test_accumulate_custom_features() {
+        assert!((accumulate_grades(AccumulationOperation::Sum) - 0.04).abs() < 1e-9);
+        assert_eq!(accumulate_grades(AccumulationOperation::Max), 0.05);
+        assert_eq!(accumulate_grades(AccumulationOperation::Min), -0.03);
+    }
+
+    #[test]
+    fn test_accumulation_defaults_to_sum() {
+        let json = serde_json::json!({
+            "grade": {
+                "type": "grade",
+                "unit": "decimal",
+                "format": { "floating_point": { "initial": 0.0 } }
+            },
+            "max_grade": {
+                "type": "grade",
+                "unit": "decimal",
+                "format": { "floating_point": { "initial": 0.0 } },
+                "accumulation": "max"
+            },
+            "time": { "time_unit": "minutes", "initial": 0.0 }
+        });
+        let state_model = StateModel::try_from(&json).unwrap();
+        let op = |name: &str| {
+            state_model
+                .get_feature(&String::from(name))
+                .unwrap()
+                .get_accumulation_operation()
+        };
+        assert_eq!(op("grade"), AccumulationOperation::Sum);
+        assert_eq!(op("max_grade"), AccumulationOperation::Max);
+        assert_eq!(op("time"), AccumulationOperation::Sum);
+    }
+
+    #[test]
+    fn test_name_based_access_survives_feature_order() {
+        let json = serde_json::json!({
+            "distance": { "distance_unit": "kilometers", "initial": 0.0 },
+            "time": { "time_unit": "minutes", "initial": 0.0 }
+        });
+        let state_model = StateModel::try_from(&json).unwrap();
+        let extended = state_model
+            .extend(vec![(
+                String::from("grade"),
+                custom_feature(AccumulationOperation::Sum),
+            )])
+            .unwrap();
+        for model in [state_model, extended] {
+            let mut state = model.initial_state().unwrap();
+            model.set_value(&mut state, "time", &StateVar(5.0)).unwrap();
+            model.add_value(&mut state, "time", &StateVar(2.5)).unwrap();
+            assert_eq!(model.get_value(&state, "time").unwrap(), StateVar(7.5));
+            assert_eq!(model.get_value(&state, "distance").unwrap(), StateVar(0.0));
+        }
+        let state_model = StateModel::try_from(&json).unwrap();
+        let state = state_model.initial_state().unwrap();
+        assert!(state_model.get_value(&state, "energy").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use super::{state_error::StateError, state_feature::StateFeature, state_model::StateModel};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// builds a [`StateModel`] one named feature at a time. each feature is
+/// assigned the next state vector index in the order it is added, and a
+/// feature name may only be added once.
+///
+/// # Example
+///
+/// ```
+/// use routee_compass_core::model::state::{
+///     state_feature::StateFeature, state_model_builder::StateModelBuilder,
+/// };
+/// use routee_compass_core::model::unit::{Distance, DistanceUnit, Time, TimeUnit};
+///
+/// let state_model = StateModelBuilder::new()
+///     .with_feature(
+///         "distance",
+///         StateFeature::Distance {
+///             distance_unit: DistanceUnit::Kilometers,
+///             initial: Distance::new(0.0),
+///         },
+///     )
+///     .with_feature(
+///         "time",
+///         StateFeature::Time {
+///             time_unit: TimeUnit::Minutes,
+///             initial: Time::new(0.0),
+///         },
+///     )
+///     .build()
+///     .unwrap();
+/// assert_eq!(state_model.index_of("time"), Some(1));
+/// ```
+#[derive(Default)]
+pub struct StateModelBuilder {
+    features: Vec<(String, StateFeature)>,
+    indices: HashMap<String, usize>,
+    duplicates: Vec<String>,
+}
+
+impl StateModelBuilder {
+    pub fn new() -> StateModelBuilder {
+        StateModelBuilder::default()
+    }
+
+    /// adds a feature at the next state vector index. a name that was
+    /// already added is recorded and reported as an error by [`StateModelBuilder::build`].
+    pub fn with_feature(mut self, name: &str, feature: StateFeature) -> StateModelBuilder {
+        if self.indices.contains_key(name) {
+            self.duplicates.push(String::from(name));
+        } else {
+            self.indices.insert(String::from(name), self.features.len());
+            self.features.push((String::from(name), feature));
+        }
+        self
+    }
+
+    /// the state vector index assigned to a feature added to this builder
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).cloned()
+    }
+
+    /// builds the state model, or fails if any feature name was added more than once.
+    pub fn build(self) -> Result<StateModel, StateError> {
+        if self.duplicates.is_empty() {
+            Ok(StateModel::new(self.features))
+        } else {
+            let names = self.duplicates.iter().unique().join(", ");
+            Err(StateError::BuildError(format!(
+                "state model features must have unique names, found duplicates: {}",
+                names
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::unit::{Distance, DistanceUnit, Time, TimeUnit};
+
+    fn distance() -> StateFeature {
+        StateFeature::Distance {
+            distance_unit: DistanceUnit::Kilometers,
+            initial: Distance::new(0.0),
+        }
+    }
+
+    fn time() -> StateFeature {
+        StateFeature::Time {
+            time_unit: TimeUnit::Minutes,
+            initial: Time::new(0.0),
+        }
+    }
+
+    #[test]
+    fn test_indices_follow_insertion_order() {
+        let builder = StateModelBuilder::new()
+            .with_feature("time", time())
+            .with_feature("distance", distance());
+        assert_eq!(builder.index_of("distance"), Some(1));
+        let state_model = builder.build().unwrap();
+        assert_eq!(state_model.index_of("time"), Some(0));
+        assert_eq!(state_model.index_of("distance"), Some(1));
+        assert_eq!(state_model.index_of("energy"), None);
+    }
+
+    #[test]
+    fn test_duplicate_names_are_an_error() {
+        let result = StateModelBuilder::new()
+            .with_feature("distance", distance())
+            .with_feature("time", time())
+            .with_feature("distance", distance())
+            .build();
+        match result {
+            Err(StateError::BuildError(msg)) => assert!(msg.contains("distance"), "{}", msg),
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("expected duplicate feature names to fail"),
+        }
+    }
+}
@@ -10,22 +10,22 @@ use crate::model::traversal::state::state_variable::StateVar;
 /// the StateModel exposes these operations through it's interface.
 pub(crate) enum UpdateOperation {
     Replace,
-    // Add,
+    Add,
     // Multiply,
-    // Max,
-    // Min,
+    Max,
+    Min,
     // AddBounded(StateVar, StateVar),
     // Function(GenericStateUpdateOp),
 }
 
 impl UpdateOperation {
-    pub fn perform_operation(&self, _prev: &StateVar, next: &StateVar) -> StateVar {
+    pub fn perform_operation(&self, prev: &StateVar, next: &StateVar) -> StateVar {
         match self {
             UpdateOperation::Replace => *next,
-            // UpdateOperation::Add => *prev + *next,
+            UpdateOperation::Add => *prev + *next,
             // UpdateOperation::Multiply => StateVar(prev.0 * next.0),
-            // UpdateOperation::Max => StateVar(prev.0.max(next.0)),
-            // UpdateOperation::Min => StateVar(prev.0.min(next.0)),
+            UpdateOperation::Max => StateVar(prev.0.max(next.0)),
+            UpdateOperation::Min => StateVar(prev.0.min(next.0)),
             // UpdateOperation::AddBounded(min, max) => {
             //     StateVar(min.0.max(max.0.min(prev.0 + next.0)))
             // }
@@ -0,0 +1,384 @@
+use super::state::state_variable::StateVar;
+use super::traversal_model::TraversalModel;
+use super::traversal_model_error::TraversalModelError;
+use crate::model::property::{edge::Edge, vertex::Vertex};
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::state::accumulation_operation::AccumulationOperation;
+use crate::model::state::state_feature::StateFeature;
+use crate::model::state::state_model::StateModel;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+type EdgeDeltas = Arc<RwLock<HashMap<EdgeId, Vec<StateVar>>>>;
+
+/// stores the change to the search state made by traversing each edge, for
+/// traversal models that advertise a [`TraversalModel::cache_key`]. entries
+/// are partitioned by the model cache key and the state model, so queries
+/// only share traversals when their models and state vectors match. the
+/// cache must be cleared whenever the data behind a model changes, such as
+/// after updating speeds.
+///
+/// a traversal is replayed by adding its recorded change to the state, which
+/// is only correct when every feature is accumulated by summation. the
+/// change made to a max or min feature depends on its prior value, so
+/// state models with such features are never cached.
+#[derive(Default)]
+pub struct TraversalCache {
+    partitions: RwLock<HashMap<String, EdgeDeltas>>,
+}
+
+impl TraversalCache {
+    /// wraps a traversal model so that its edge traversals are served from
+    /// this cache. models without a cache key, or queries whose state model
+    /// has a feature that is not accumulated by summation, are returned
+    /// unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - the traversal model built for a query
+    /// * `state_model` - the state model of the query
+    ///
+    /// # Returns
+    ///
+    /// the model to use for the query
+    pub fn wrap(
+        &self,
+        model: Arc<dyn TraversalModel>,
+        state_model: &StateModel,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model_key = match model.cache_key() {
+            None => return Ok(model),
+            Some(model_key) => model_key,
+        };
+        let summed = state_model
+            .iter()
+            .all(|(_, feature)| feature.get_accumulation_operation() == AccumulationOperation::Sum);
+        if !summed {
+            return Ok(model);
+        }
+        let key = format!("{}|{}", model_key, state_model.get_names());
+        let mut partitions = self.partitions.write().map_err(|e| {
+            TraversalModelError::InternalError(format!("traversal cache is poisoned: {}", e))
+        })?;
+        let deltas = partitions.entry(key).or_default().clone();
+        Ok(Arc::new(CachedTraversalModel { model, deltas }))
+    }
+
+    /// removes every cached traversal
+    pub fn clear(&self) -> Result<(), TraversalModelError> {
+        let mut partitions = self.partitions.write().map_err(|e| {
+            TraversalModelError::InternalError(format!("traversal cache is poisoned: {}", e))
+        })?;
+        partitions.clear();
+        Ok(())
+    }
+
+    /// the number of edge traversals stored across all partitions
+    pub fn len(&self) -> usize {
+        self.partitions
+            .read()
+            .map(|partitions| {
+                partitions
+                    .values()
+                    .map(|deltas| deltas.read().map(|d| d.len()).unwrap_or_default())
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// a state-independent traversal model whose edge traversals are memoized.
+/// the first traversal of an edge records the change it made to the state,
+/// and later traversals of the edge apply that change without running the
+/// underlying model. partial edges share the edge id of the full edge, so
+/// their traversals bypass the cache.
+pub struct CachedTraversalModel {
+    model: Arc<dyn TraversalModel>,
+    deltas: EdgeDeltas,
+}
+
+impl TraversalModel for CachedTraversalModel {
+    fn state_features(&self) -> Vec<(String, StateFeature)> {
+        self.model.state_features()
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        if let Some(delta) = self
+            .deltas
+            .read()
+            .ok()
+            .and_then(|deltas| deltas.get(&edge.edge_id).cloned())
+        {
+            for (value, change) in state.iter_mut().zip(delta) {
+                *value = *value + change;
+            }
+            return Ok(());
+        }
+
+        let prev = state.clone();
+        self.model.traverse_edge(trajectory, state, state_model)?;
+        let delta = state
+            .iter()
+            .zip(prev.iter())
+            .map(|(next, prev)| *next - *prev)
+            .collect::<Vec<_>>();
+        if let Ok(mut deltas) = self.deltas.write() {
+            deltas.insert(edge.edge_id, delta);
+        }
+        Ok(())
+    }
+
+    fn traverse_partial_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        fraction: f64,
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.model
+            .traverse_partial_edge(trajectory, fraction, state, state_model)
+    }
+
+    fn estimate_traversal(
+        &self,
+        od: (&Vertex, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.model.estimate_traversal(od, state, state_model)
+    }
+
+    fn summary(
+        &self,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        self.model.summary(state, state_model)
+    }
+
+    fn edge_details(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        self.model.edge_details(trajectory, state, state_model)
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        self.model.cache_key()
+    }
+
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        self.model.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::state::custom_feature_format::CustomFeatureFormat;
+    use crate::model::traversal::default::distance_traversal_model::DistanceTraversalModel;
+    use crate::model::unit::as_f64::AsF64;
+    use crate::model::unit::{Distance, DistanceUnit};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// counts the edges it traverses, each adding one unit of distance
+    struct CountingModel {
+        traversals: AtomicUsize,
+        cache_key: Option<String>,
+    }
+
+    impl TraversalModel for CountingModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+        fn traverse_edge(
+            &self,
+            _trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            self.traversals.fetch_add(1, Ordering::SeqCst);
+            state_model.add_distance(
+                state,
+                &"distance".into(),
+                &Distance::ONE,
+                &DistanceUnit::Meters,
+            )?;
+            Ok(())
+        }
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVar>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+        fn cache_key(&self) -> Option<String> {
+            self.cache_key.clone()
+        }
+    }
+
+    fn state_model() -> StateModel {
+        StateModel::empty()
+            .extend(vec![(
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Meters,
+                    initial: Distance::ZERO,
+                },
+            )])
+            .unwrap()
+    }
+
+    fn traverse(model: &Arc<dyn TraversalModel>, edge_id: usize, state_model: &StateModel) -> f64 {
+        let v = Vertex::new(0, 0.0, 0.0);
+        let edge = Edge::new(edge_id, 0, 1, 1.0);
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &edge, &v), &mut state, state_model)
+            .unwrap();
+        state_model
+            .get_distance(&state, &"distance".into(), &DistanceUnit::Meters)
+            .unwrap()
+            .as_f64()
+    }
+
+    #[test]
+    fn test_cached_traversals_skip_the_model() {
+        let cache = TraversalCache::default();
+        let state_model = state_model();
+        let counting = Arc::new(CountingModel {
+            traversals: AtomicUsize::new(0),
+            cache_key: Some(String::from("counting")),
+        });
+        let model = cache.wrap(counting.clone(), &state_model).unwrap();
+        // a second query builds a new model with the same key, sharing the cache
+        let model_2 = cache.wrap(counting.clone(), &state_model).unwrap();
+
+        assert_eq!(traverse(&model, 0, &state_model), 1.0);
+        assert_eq!(traverse(&model_2, 0, &state_model), 1.0);
+        assert_eq!(traverse(&model_2, 1, &state_model), 1.0);
+        assert_eq!(counting.traversals.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.len(), 2);
+
+        cache.clear().unwrap();
+        assert!(cache.is_empty());
+        let model_3 = cache.wrap(counting.clone(), &state_model).unwrap();
+        traverse(&model_3, 0, &state_model);
+        assert_eq!(counting.traversals.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_state_dependent_models_are_not_cached() {
+        let cache = TraversalCache::default();
+        let state_model = state_model();
+        let counting = Arc::new(CountingModel {
+            traversals: AtomicUsize::new(0),
+            cache_key: None,
+        });
+        let model = cache.wrap(counting.clone(), &state_model).unwrap();
+        traverse(&model, 0, &state_model);
+        traverse(&model, 0, &state_model);
+        assert_eq!(counting.traversals.load(Ordering::SeqCst), 2);
+        assert!(cache.is_empty());
+
+        // the distance model is always cacheable
+        let distance = Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters));
+        assert!(distance.cache_key().is_some());
+    }
+
+    /// records the steepest grade of the route, taking the edge id as the
+    /// grade of each edge
+    struct MaxGradeModel {
+        traversals: AtomicUsize,
+    }
+
+    impl TraversalModel for MaxGradeModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            vec![]
+        }
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            let (_, edge, _) = trajectory;
+            self.traversals.fetch_add(1, Ordering::SeqCst);
+            state_model.accumulate_custom_f64(
+                state,
+                &"max_grade".into(),
+                &(edge.edge_id.0 as f64),
+            )?;
+            Ok(())
+        }
+        fn estimate_traversal(
+            &self,
+            _od: (&Vertex, &Vertex),
+            _state: &mut Vec<StateVar>,
+            _state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            Ok(())
+        }
+        fn cache_key(&self) -> Option<String> {
+            Some(String::from("max_grade"))
+        }
+    }
+
+    #[test]
+    fn test_max_accumulated_features_are_not_cached() {
+        let cache = TraversalCache::default();
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("max_grade"),
+                StateFeature::Custom {
+                    r#type: String::from("grade"),
+                    unit: String::from("decimal"),
+                    format: CustomFeatureFormat::FloatingPoint {
+                        initial: 0.0.into(),
+                    },
+                    accumulation: AccumulationOperation::Max,
+                },
+            )])
+            .unwrap();
+        let max_grade = Arc::new(MaxGradeModel {
+            traversals: AtomicUsize::new(0),
+        });
+        let model = cache.wrap(max_grade.clone(), &state_model).unwrap();
+        let v = Vertex::new(0, 0.0, 0.0);
+        let edge = Edge::new(3, 0, 1, 1.0);
+
+        // the same edge from states with a lower and a higher prior max grade
+        let mut results = vec![];
+        for prior in [0.0, 5.0] {
+            let mut state = state_model.initial_state().unwrap();
+            state_model
+                .set_custom_f64(&mut state, &"max_grade".into(), &prior)
+                .unwrap();
+            model
+                .traverse_edge((&v, &edge, &v), &mut state, &state_model)
+                .unwrap();
+            results.push(
+                state_model
+                    .get_custom_f64(&state, &"max_grade".into())
+                    .unwrap(),
+            );
+        }
+        assert_eq!(results, vec![3.0, 5.0]);
+        assert_eq!(max_grade.traversals.load(Ordering::SeqCst), 2);
+        assert!(cache.is_empty());
+    }
+}
@@ -1,22 +1,67 @@
 use crate::model::state::state_feature::StateFeature;
 use crate::model::state::state_model::StateModel;
-use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model::{TraversalModel, DEFAULT_SUMMARY_PRECISION};
 use crate::model::unit::DistanceUnit;
 use crate::model::unit::BASE_DISTANCE_UNIT;
 use crate::model::{
     property::{edge::Edge, vertex::Vertex},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
 };
-use crate::util::geo::haversine;
+use crate::util::geo::distance_function::{DistanceFunction, HaversineDistance};
+use crate::util::serde::serde_json_extension::SerdeJsonExtension;
+use serde_json::json;
+use std::sync::Arc;
 
 /// A simple traversal model that uses the edge distance as the cost of traversal.
+/// The route distance may optionally be summarized in a second display unit.
+/// The remaining distance to the destination is estimated with the haversine
+/// distance unless another [`DistanceFunction`] is set.
 pub struct DistanceTraversalModel {
     distance_unit: DistanceUnit,
+    display_distance_unit: Option<DistanceUnit>,
+    summary_precision: u32,
+    distance_function: Arc<dyn DistanceFunction>,
 }
 
 impl DistanceTraversalModel {
     pub fn new(distance_unit: DistanceUnit) -> DistanceTraversalModel {
-        DistanceTraversalModel { distance_unit }
+        DistanceTraversalModel {
+            distance_unit,
+            display_distance_unit: None,
+            summary_precision: DEFAULT_SUMMARY_PRECISION,
+            distance_function: Arc::new(HaversineDistance::default()),
+        }
+    }
+
+    pub fn new_with_display_unit(
+        distance_unit: DistanceUnit,
+        display_distance_unit: Option<DistanceUnit>,
+    ) -> DistanceTraversalModel {
+        DistanceTraversalModel {
+            distance_unit,
+            display_distance_unit,
+            summary_precision: DEFAULT_SUMMARY_PRECISION,
+            distance_function: Arc::new(HaversineDistance::default()),
+        }
+    }
+
+    /// sets the number of decimal places of the distances in the route summary
+    pub fn with_summary_precision(self, summary_precision: u32) -> DistanceTraversalModel {
+        DistanceTraversalModel {
+            summary_precision,
+            ..self
+        }
+    }
+
+    /// sets the function used to estimate the distance between two vertices
+    pub fn with_distance_function(
+        self,
+        distance_function: Arc<dyn DistanceFunction>,
+    ) -> DistanceTraversalModel {
+        DistanceTraversalModel {
+            distance_function,
+            ..self
+        }
     }
     const DISTANCE: &'static str = "distance";
 }
@@ -47,9 +92,10 @@ impl TraversalModel for DistanceTraversalModel {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (src, dst) = od;
-        let distance =
-            haversine::coord_distance(&src.coordinate, &dst.coordinate, self.distance_unit)
-                .map_err(TraversalModelError::NumericError)?;
+        let distance = self
+            .distance_function
+            .distance(&src.coordinate, &dst.coordinate, self.distance_unit)
+            .map_err(TraversalModelError::NumericError)?;
         state_model.add_distance(
             state,
             &Self::DISTANCE.into(),
@@ -63,4 +109,133 @@ impl TraversalModel for DistanceTraversalModel {
     fn state_features(&self) -> Vec<(String, StateFeature)> {
         vec![]
     }
+
+    /// reports the route distance in the configured distance unit and, if
+    /// set, the display distance unit, rounded to the summary precision
+    fn summary(
+        &self,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        let distance =
+            state_model.get_distance(state, &Self::DISTANCE.into(), &self.distance_unit)?;
+        let mut summary = json!({
+            "distance": distance,
+            "distance_unit": self.distance_unit,
+        });
+        if let Some(display_unit) = &self.display_distance_unit {
+            let display_distance = self.distance_unit.convert(&distance, display_unit);
+            summary["display_distance"] = json!(display_distance);
+            summary["display_distance_unit"] = json!(display_unit);
+        }
+        Ok(Some(summary.round_floats(self.summary_precision)))
+    }
+
+    /// edge distances never depend on the state
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("distance:{}", self.distance_unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::property::vertex::Vertex;
+    use crate::model::traversal::default::distance_traversal_service::DistanceTraversalService;
+    use crate::model::traversal::traversal_model_service::TraversalModelService;
+    use crate::model::unit::Distance;
+    use crate::util::geo::distance_function::DistanceFunctionType;
+
+    #[test]
+    fn test_summary_with_display_unit() {
+        let model = DistanceTraversalModel::new_with_display_unit(
+            DistanceUnit::Kilometers,
+            Some(DistanceUnit::Miles),
+        );
+        // the state accumulates in meters, which the summary must convert
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Meters,
+                    initial: Distance::new(0.0),
+                },
+            )])
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = Vertex::new(0, 0.0, 0.0);
+        let edge = Edge::new(0, 0, 1, 1609.344);
+        model
+            .traverse_edge((&v, &edge, &v), &mut state, &state_model)
+            .unwrap();
+
+        let summary = model.summary(&state, &state_model).unwrap().unwrap();
+        assert_eq!(summary["distance_unit"], json!("kilometers"));
+        assert_eq!(summary["display_distance_unit"], json!("miles"));
+        let km = summary["distance"].as_f64().unwrap();
+        let miles = summary["display_distance"].as_f64().unwrap();
+        assert_eq!(km, 1.6093);
+        assert!((miles - 1.0).abs() < 1e-3, "{}", miles);
+
+        let no_display = DistanceTraversalModel::new(DistanceUnit::Kilometers)
+            .summary(&state, &state_model)
+            .unwrap()
+            .unwrap();
+        assert!(no_display.get("display_distance").is_none());
+        assert_eq!(no_display["distance"].as_f64(), Some(km));
+
+        let precise = DistanceTraversalModel::new(DistanceUnit::Kilometers)
+            .with_summary_precision(6)
+            .summary(&state, &state_model)
+            .unwrap()
+            .unwrap();
+        assert_eq!(precise["distance"].as_f64(), Some(1.609344));
+
+        // the query precision overrides the configured precision
+        let service = DistanceTraversalService {
+            distance_unit: DistanceUnit::Kilometers,
+            display_distance_unit: None,
+            summary_precision: Some(1),
+            distance_function: DistanceFunctionType::Haversine,
+            haversine_radius: None,
+        };
+        let summarize = |query: serde_json::Value| {
+            let model = service.build(&query).unwrap();
+            model.summary(&state, &state_model).unwrap().unwrap()["distance"].as_f64()
+        };
+        assert_eq!(summarize(json!({})), Some(1.6));
+        assert_eq!(summarize(json!({ "summary_precision": 6 })), Some(1.609344));
+    }
+
+    #[test]
+    fn test_euclidean_estimate_on_unit_square() {
+        let model = DistanceTraversalModel::new(DistanceUnit::Meters)
+            .with_distance_function(DistanceFunctionType::Euclidean.build());
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("distance"),
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Meters,
+                    initial: Distance::new(0.0),
+                },
+            )])
+            .unwrap();
+        let corners = [
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 1.0, 0.0),
+            Vertex::new(2, 1.0, 1.0),
+            Vertex::new(3, 0.0, 1.0),
+        ];
+        let estimate = |src: usize, dst: usize| {
+            let mut state = state_model.initial_state().unwrap();
+            model
+                .estimate_traversal((&corners[src], &corners[dst]), &mut state, &state_model)
+                .unwrap();
+            state_model.get_value(&state, "distance").unwrap().0
+        };
+        assert_eq!(estimate(0, 1), 1.0);
+        assert_eq!(estimate(0, 3), 1.0);
+        assert_eq!(estimate(0, 2), 2f64.sqrt());
+        assert_eq!(estimate(3, 1), 2f64.sqrt());
+    }
 }
@@ -1,20 +1,38 @@
 use crate::model::traversal::default::distance_traversal_model::DistanceTraversalModel;
-use crate::model::traversal::traversal_model::TraversalModel;
+use crate::model::traversal::traversal_model::{summary_precision, TraversalModel};
 use crate::model::traversal::traversal_model_error::TraversalModelError;
 use crate::model::traversal::traversal_model_service::TraversalModelService;
 use crate::model::unit::DistanceUnit;
+use crate::util::geo::distance_function::DistanceFunctionType;
 use std::sync::Arc;
 
 pub struct DistanceTraversalService {
     pub distance_unit: DistanceUnit,
+    pub display_distance_unit: Option<DistanceUnit>,
+    /// decimal places of the route summary, which the `summary_precision`
+    /// query parameter overrides. defaults to DEFAULT_SUMMARY_PRECISION
+    pub summary_precision: Option<u32>,
+    /// how the distance between two vertices is estimated
+    pub distance_function: DistanceFunctionType,
+    /// radius in meters of the sphere for haversine distances, defaults to the earth
+    pub haversine_radius: Option<f32>,
 }
 
 impl TraversalModelService for DistanceTraversalService {
     fn build(
         &self,
-        _parameters: &serde_json::Value,
+        parameters: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
-        let m: Arc<dyn TraversalModel> = Arc::new(DistanceTraversalModel::new(self.distance_unit));
+        let model = DistanceTraversalModel::new_with_display_unit(
+            self.distance_unit,
+            self.display_distance_unit,
+        )
+        .with_distance_function(
+            self.distance_function
+                .build_with_haversine_radius(self.haversine_radius),
+        )
+        .with_summary_precision(summary_precision(parameters, self.summary_precision)?);
+        let m: Arc<dyn TraversalModel> = Arc::new(model);
         Ok(m)
     }
 }
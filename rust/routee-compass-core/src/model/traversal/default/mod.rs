@@ -1,5 +1,8 @@
 pub mod distance_traversal_model;
 pub mod distance_traversal_service;
+pub mod speed_grade_table;
+pub mod speed_profile;
+pub mod speed_smoothing;
 pub mod speed_traversal_engine;
 pub mod speed_traversal_model;
 pub mod speed_traversal_service;
@@ -0,0 +1,98 @@
+use crate::model::road_network::edge_id::EdgeId;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::unit::{Grade, Speed};
+use crate::util::fs::read_utils;
+use serde::Deserialize;
+use std::path::Path;
+
+/// a row of a combined speed and grade file
+#[derive(Deserialize)]
+struct SpeedGradeRow {
+    edge_id: EdgeId,
+    speed: Speed,
+    grade: Grade,
+}
+
+/// per-edge speeds and grades read together from a single CSV file with
+/// `edge_id`, `speed` and `grade` columns. since both tables come from the
+/// same rows, they always agree on the number of edges.
+pub struct SpeedGradeTable {
+    pub speeds: Box<[Speed]>,
+    pub grades: Box<[Grade]>,
+}
+
+impl SpeedGradeTable {
+    /// reads the speed and grade tables in a single pass. rows must be
+    /// ordered by edge id so that each row index matches its edge id.
+    /// values are kept in the units of the file, which are declared in
+    /// the configuration of the models that use them.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - CSV file with `edge_id`, `speed` and `grade` columns
+    ///
+    /// # Returns
+    ///
+    /// the speed and grade tables, or an error if a column is missing or
+    /// the rows are out of order
+    pub fn from_csv<P: AsRef<Path>>(path: &P) -> Result<SpeedGradeTable, TraversalModelError> {
+        let read_error =
+            |e: String| TraversalModelError::FileReadError(path.as_ref().to_path_buf(), e);
+        let rows = read_utils::iterator_from_csv::<_, SpeedGradeRow>(path, true, None)
+            .map_err(|e| read_error(e.to_string()))?;
+        let mut speeds = vec![];
+        let mut grades = vec![];
+        for (idx, row) in rows.enumerate() {
+            let row = row.map_err(|e| read_error(e.to_string()))?;
+            if row.edge_id != EdgeId(idx) {
+                return Err(read_error(format!(
+                    "row {} has edge_id {}, rows must be ordered by edge_id",
+                    idx, row.edge_id
+                )));
+            }
+            speeds.push(row.speed);
+            grades.push(row.grade);
+        }
+        if speeds.is_empty() {
+            return Err(read_error(String::from("parsed 0 entries")));
+        }
+        Ok(SpeedGradeTable {
+            speeds: speeds.into_boxed_slice(),
+            grades: grades.into_boxed_slice(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_file(filename: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join(filename)
+    }
+
+    #[test]
+    fn test_reads_aligned_tables() {
+        let table = SpeedGradeTable::from_csv(&test_file("speeds_grades.csv")).unwrap();
+        assert_eq!(table.speeds.len(), 3);
+        assert_eq!(table.grades.len(), 3);
+        assert_eq!(table.speeds[1], Speed::new(25.0));
+        assert_eq!(table.grades[1], Grade::new(-2.0));
+    }
+
+    #[test]
+    fn test_missing_column_errors() {
+        let result = SpeedGradeTable::from_csv(&test_file("speeds_no_grades.csv"));
+        assert!(matches!(
+            result,
+            Err(TraversalModelError::FileReadError(_, msg)) if msg.contains("grade")
+        ));
+    }
+}
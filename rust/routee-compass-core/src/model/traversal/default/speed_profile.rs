@@ -0,0 +1,134 @@
+use crate::model::property::edge::Edge;
+use crate::model::traversal::traversal_model_error::TraversalModelError;
+use crate::model::unit::{as_f64::AsF64, Speed, Time, TimeUnit};
+use crate::util::fs::read_utils;
+use std::path::Path;
+
+/// a table of speeds for each edge indexed by time-of-day bucket. bucket `i`
+/// covers the time range `[i * bucket_duration, (i + 1) * bucket_duration)`
+/// and lookups wrap around to the first bucket after the last bucket.
+pub struct SpeedProfile {
+    pub profiles: Box<[Box<[Speed]>]>,
+    pub bucket_duration: Time,
+    pub time_unit: TimeUnit,
+    pub max_speed: Speed,
+}
+
+impl SpeedProfile {
+    /// loads a speed profile file where each row is a comma-delimited list of
+    /// bucket speeds for the edge at that row index. every row must contain
+    /// the same number of buckets.
+    ///
+    /// # Arguments
+    ///
+    /// * `speed_profile_path` - file containing one row of bucket speeds per edge
+    /// * `bucket_duration` - duration of each bucket, in `time_unit`
+    /// * `time_unit` - time unit for bucket duration and departure times
+    ///
+    /// # Returns
+    ///
+    /// the loaded speed profile, or an error if the file is malformed
+    pub fn new<P: AsRef<Path>>(
+        speed_profile_path: &P,
+        bucket_duration: Time,
+        time_unit: TimeUnit,
+    ) -> Result<SpeedProfile, TraversalModelError> {
+        if bucket_duration <= Time::ZERO {
+            return Err(TraversalModelError::BuildError(format!(
+                "speed profile bucket duration must be positive, found {}",
+                bucket_duration
+            )));
+        }
+        let profiles: Box<[Box<[Speed]>]> =
+            read_utils::read_raw_file(speed_profile_path, decode_profile_row, None).map_err(
+                |e| {
+                    TraversalModelError::FileReadError(
+                        speed_profile_path.as_ref().to_path_buf(),
+                        e.to_string(),
+                    )
+                },
+            )?;
+
+        let n_buckets = match profiles.first() {
+            None => {
+                return Err(TraversalModelError::BuildError(String::from(
+                    "parsed 0 entries for speed profile",
+                )))
+            }
+            Some(row) => row.len(),
+        };
+        if let Some((idx, row)) = profiles
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != n_buckets)
+        {
+            return Err(TraversalModelError::BuildError(format!(
+                "speed profile row {} has {} buckets, expected {}",
+                idx,
+                row.len(),
+                n_buckets
+            )));
+        }
+
+        let max_speed = profiles
+            .iter()
+            .flat_map(|row| row.iter())
+            .fold(Speed::ZERO, |acc, s| if acc > *s { acc } else { *s });
+        if max_speed == Speed::ZERO {
+            return Err(TraversalModelError::BuildError(String::from(
+                "max speed was zero in speed profile",
+            )));
+        }
+
+        Ok(SpeedProfile {
+            profiles,
+            bucket_duration,
+            time_unit,
+            max_speed,
+        })
+    }
+
+    /// look up the speed for an edge at some clock time
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - edge to look up
+    /// * `road_class` - road class of the edge, if known, reported when the edge is missing
+    /// * `clock_time` - time of day, in the profile time unit
+    ///
+    /// # Returns
+    ///
+    /// the speed in the bucket containing the clock time
+    pub fn get_speed(
+        &self,
+        edge: &Edge,
+        road_class: Option<u8>,
+        clock_time: Time,
+    ) -> Result<Speed, TraversalModelError> {
+        let edge_id = edge.edge_id;
+        let row = self
+            .profiles
+            .get(edge_id.as_usize())
+            .ok_or_else(|| TraversalModelError::missing_edge("speed profile", edge, road_class))?;
+        let bucket = (clock_time.as_f64() / self.bucket_duration.as_f64()).floor() as i64;
+        let bucket_idx = bucket.rem_euclid(row.len() as i64) as usize;
+        let speed = row.get(bucket_idx).ok_or_else(|| {
+            TraversalModelError::InternalError(format!(
+                "speed profile bucket {} out of range for edge {}",
+                bucket_idx, edge_id
+            ))
+        })?;
+        Ok(*speed)
+    }
+}
+
+fn decode_profile_row(idx: usize, row: String) -> Result<Box<[Speed]>, std::io::Error> {
+    row.split(',')
+        .map(|s| {
+            s.trim().parse::<Speed>().map_err(|e| {
+                let msg = format!("failure decoding speed profile row {} due to: {}", idx, e);
+                std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,129 @@
+use crate::model::{
+    road_network::graph::Graph,
+    traversal::traversal_model_error::TraversalModelError,
+    unit::{as_f64::AsF64, Speed},
+};
+
+/// blends the speed of each edge with the speeds of its topological
+/// neighbors, which are the edges that end where it starts and the edges
+/// that start where it ends, found in the adjacency of the graph. an edge
+/// leading straight back along the same road, such as the reverse edge of
+/// a bidirectional graph, is not a neighbor. this evens out unrealistic
+/// jumps between adjacent edges in raw speed data. the transform is applied
+/// once to the whole table, using the original speeds of the neighbors, and
+/// it changes the travel times of every edge with neighbors of a different
+/// speed.
+///
+/// # Arguments
+///
+/// * `speeds` - speed of each edge, indexed by `EdgeId`
+/// * `graph` - the road network, used to find the neighbors of each edge
+/// * `weight` - share of the smoothed speed taken from the mean neighbor speed, in `[0, 1]`
+///
+/// # Returns
+///
+/// the smoothed speed table, or an error if the weight is out of range or
+/// an edge has no speed
+pub fn smooth_speeds(
+    speeds: &[Speed],
+    graph: &Graph,
+    weight: f64,
+) -> Result<Box<[Speed]>, TraversalModelError> {
+    if !(0.0..=1.0).contains(&weight) {
+        return Err(TraversalModelError::BuildError(format!(
+            "speed smoothing weight must be between 0 and 1, found {}",
+            weight
+        )));
+    }
+    if graph.n_edges() > speeds.len() {
+        return Err(TraversalModelError::BuildError(format!(
+            "cannot smooth speeds, graph has {} edges but speed table has {} rows",
+            graph.n_edges(),
+            speeds.len()
+        )));
+    }
+
+    let mut smoothed = speeds.to_vec();
+    for edge in graph.edges.iter() {
+        let idx = edge.edge_id.0;
+        let upstream = graph
+            .in_edges_iter(edge.src_vertex_id)?
+            .filter(|n| graph.src_vertex_id(**n).ok() != Some(edge.dst_vertex_id));
+        let downstream = graph
+            .out_edges_iter(edge.dst_vertex_id)?
+            .filter(|n| graph.dst_vertex_id(**n).ok() != Some(edge.src_vertex_id));
+        let neighbor_speeds = upstream
+            .chain(downstream)
+            .filter(|n| n.0 != idx)
+            .map(|n| speeds[n.0].as_f64())
+            .collect::<Vec<_>>();
+        if neighbor_speeds.is_empty() {
+            continue;
+        }
+        let neighbor_mean = neighbor_speeds.iter().sum::<f64>() / neighbor_speeds.len() as f64;
+        let speed = speeds[idx].as_f64();
+        smoothed[idx] = Speed::new((1.0 - weight) * speed + weight * neighbor_mean);
+    }
+    Ok(smoothed.into_boxed_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::road_network::edge_table_layout::EdgeTableLayout;
+    use crate::test_util::graph_fixture::graph_from_links;
+
+    #[test]
+    fn test_spike_pulled_toward_neighbors() {
+        // a chain of edges (0) -> (1) -> (2) -> (3) where the middle edge is a spike
+        let graph = graph_from_links(4, &[(0, 1, 100.0), (1, 2, 100.0), (2, 3, 100.0)]);
+        let speeds = vec![Speed::new(40.0), Speed::new(100.0), Speed::new(40.0)];
+        let smoothed = smooth_speeds(&speeds, &graph, 0.5).unwrap();
+        // the spike is halfway to its neighbors' mean of 40
+        assert_eq!(smoothed[1], Speed::new(70.0));
+        // each end edge has only the spike as a neighbor
+        assert_eq!(smoothed[0], Speed::new(70.0));
+        assert_eq!(smoothed[2], Speed::new(70.0));
+
+        let unchanged = smooth_speeds(&speeds, &graph, 0.0).unwrap();
+        assert_eq!(&unchanged[..], &speeds[..]);
+    }
+
+    #[test]
+    fn test_reverse_edges_are_not_neighbors() {
+        // the same chain with a reverse edge for each road, whose speeds
+        // are mirrored from the forward edges
+        let mut graph = graph_from_links(
+            4,
+            &[
+                (0, 1, 100.0),
+                (1, 2, 100.0),
+                (2, 3, 100.0),
+                (1, 0, 100.0),
+                (2, 1, 100.0),
+                (3, 2, 100.0),
+            ],
+        );
+        graph.edge_table_layout = EdgeTableLayout {
+            n_rows: 3,
+            rows: None,
+            bidirectional: true,
+        };
+        let rows = vec![Speed::new(40.0), Speed::new(100.0), Speed::new(40.0)];
+        let speeds = graph.edge_table_layout.align(&rows, |s| *s).unwrap();
+        let smoothed = smooth_speeds(&speeds, &graph, 0.5).unwrap();
+        // each direction smooths as the one-way chain does
+        assert_eq!(smoothed[1], Speed::new(70.0));
+        assert_eq!(smoothed[4], Speed::new(70.0));
+        assert_eq!(smoothed[0], Speed::new(70.0));
+        assert_eq!(smoothed[5], Speed::new(70.0));
+    }
+
+    #[test]
+    fn test_invalid_weight() {
+        let graph = graph_from_links(2, &[(0, 1, 100.0)]);
+        let speeds = vec![Speed::new(40.0)];
+        assert!(smooth_speeds(&speeds, &graph, 1.5).is_err());
+        assert!(smooth_speeds(&speeds, &graph, -0.1).is_err());
+    }
+}
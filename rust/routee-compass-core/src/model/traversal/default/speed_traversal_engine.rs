@@ -1,18 +1,64 @@
+use crate::model::road_network::edge_id::EdgeId;
 use crate::model::unit::DistanceUnit;
-use crate::model::unit::{SpeedUnit, TimeUnit, BASE_DISTANCE_UNIT, BASE_TIME_UNIT};
+use crate::model::unit::{SpeedUnit, Time, TimeUnit, BASE_DISTANCE_UNIT, BASE_TIME_UNIT};
+use crate::util::departure_time::GraphTimezone;
 use crate::util::fs::read_decoders;
+use crate::util::geo::distance_function::{DistanceFunction, HaversineDistance};
 use crate::{
     model::{traversal::traversal_model_error::TraversalModelError, unit::Speed},
     util::fs::read_utils,
 };
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+
+use super::speed_profile::SpeedProfile;
+
+/// default speeds by road class, which stand in for the speed of an edge
+/// that is missing from the speed table or has a speed of zero there
+pub struct RoadClassSpeeds {
+    /// road class of each edge, indexed by `EdgeId`
+    pub road_classes: Box<[u8]>,
+    /// default speed of each road class, in the speed unit of the engine
+    pub speeds: HashMap<u8, Speed>,
+}
+
+impl RoadClassSpeeds {
+    /// the default speed of the road class of an edge, if it has one
+    pub fn get_speed(&self, edge_id: EdgeId) -> Option<Speed> {
+        let road_class = self.road_classes.get(edge_id.as_usize())?;
+        self.speeds.get(road_class).copied()
+    }
+
+    /// the road class of an edge
+    pub fn get_road_class(&self, edge_id: EdgeId) -> Option<u8> {
+        self.road_classes.get(edge_id.as_usize()).copied()
+    }
+}
 
 pub struct SpeedTraversalEngine {
     pub speed_table: Box<[Speed]>,
+    pub speed_profile: Option<SpeedProfile>,
     pub speed_unit: SpeedUnit,
     pub time_unit: TimeUnit,
     pub distance_unit: DistanceUnit,
     pub max_speed: Speed,
+    /// optional floor applied to every speed lookup, protecting against
+    /// near-zero speeds in the source data
+    pub min_speed: Option<Speed>,
+    /// estimates the distance between two vertices, haversine by default
+    pub distance_function: Arc<dyn DistanceFunction>,
+    /// optional fixed delay added when traversing each edge, such as for a
+    /// traffic signal or stop sign at the end of the edge, in `time_unit`
+    pub edge_delays: Option<Box<[Time]>>,
+    /// timezone of the road network, used to read timestamped departure times
+    pub timezone: GraphTimezone,
+    /// optional default speeds by road class for edges without a table speed,
+    /// see [`SpeedTraversalEngine::set_road_class_speeds`]
+    pub road_class_speeds: Option<RoadClassSpeeds>,
+    /// decimal places of the route summary, which the `summary_precision`
+    /// query parameter overrides. defaults to DEFAULT_SUMMARY_PRECISION
+    pub summary_precision: Option<u32>,
 }
 
 impl SpeedTraversalEngine {
@@ -21,6 +67,8 @@ impl SpeedTraversalEngine {
         speed_unit: SpeedUnit,
         distance_unit_opt: Option<DistanceUnit>,
         time_unit_opt: Option<TimeUnit>,
+        speed_profile: Option<SpeedProfile>,
+        min_speed: Option<Speed>,
     ) -> Result<SpeedTraversalEngine, TraversalModelError> {
         let speed_table: Box<[Speed]> =
             read_utils::read_raw_file(speed_table_path, read_decoders::default, None).map_err(
@@ -31,18 +79,158 @@ impl SpeedTraversalEngine {
                     )
                 },
             )?;
-        let max_speed = get_max_speed(&speed_table)?;
+        SpeedTraversalEngine::from_table(
+            speed_table,
+            speed_unit,
+            distance_unit_opt,
+            time_unit_opt,
+            speed_profile,
+            min_speed,
+        )
+    }
+
+    /// builds the engine from a speed table that has already been loaded,
+    /// such as the speeds of a combined speed and grade file.
+    pub fn from_table(
+        speed_table: Box<[Speed]>,
+        speed_unit: SpeedUnit,
+        distance_unit_opt: Option<DistanceUnit>,
+        time_unit_opt: Option<TimeUnit>,
+        speed_profile: Option<SpeedProfile>,
+        min_speed: Option<Speed>,
+    ) -> Result<SpeedTraversalEngine, TraversalModelError> {
+        // the heuristic must remain admissible, so use the fastest speed found
+        // in either the static table or any bucket of the speed profile
+        let table_max_speed = get_max_speed(&speed_table)?;
+        let max_speed = match &speed_profile {
+            Some(profile) if profile.max_speed > table_max_speed => profile.max_speed,
+            _ => table_max_speed,
+        };
+        if let Some(min_speed) = min_speed {
+            if min_speed <= Speed::ZERO || min_speed > max_speed {
+                let msg = format!(
+                    "min speed {} must be positive and no greater than the max speed {}",
+                    min_speed, max_speed
+                );
+                return Err(TraversalModelError::BuildError(msg));
+            }
+        }
         let time_unit = time_unit_opt.unwrap_or(BASE_TIME_UNIT);
         let distance_unit = distance_unit_opt.unwrap_or(BASE_DISTANCE_UNIT);
         let model = SpeedTraversalEngine {
             speed_table,
+            speed_profile,
             distance_unit,
             time_unit,
             speed_unit,
             max_speed,
+            min_speed,
+            distance_function: Arc::new(HaversineDistance::default()),
+            edge_delays: None,
+            timezone: GraphTimezone::default(),
+            road_class_speeds: None,
+            summary_precision: None,
         };
         Ok(model)
     }
+
+    /// loads a table of fixed delays, one row per edge like the speed table,
+    /// and converts them into this engine's time unit. edges past the end
+    /// of the table have no delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_delay_path` - file with the delay of each edge
+    /// * `delay_time_unit` - time unit of the delays in the file
+    ///
+    /// # Returns
+    ///
+    /// the delays in the engine time unit, or an error reading the file
+    pub fn read_edge_delays<P: AsRef<Path>>(
+        &self,
+        edge_delay_path: &P,
+        delay_time_unit: TimeUnit,
+    ) -> Result<Box<[Time]>, TraversalModelError> {
+        let delays: Box<[Time]> =
+            read_utils::read_raw_file(edge_delay_path, read_decoders::default, None).map_err(
+                |e| {
+                    TraversalModelError::FileReadError(
+                        edge_delay_path.as_ref().to_path_buf(),
+                        e.to_string(),
+                    )
+                },
+            )?;
+        let delays = delays
+            .iter()
+            .map(|delay| delay_time_unit.convert(delay, &self.time_unit))
+            .collect();
+        Ok(delays)
+    }
+
+    /// the fixed delay of an edge in `time_unit`, which is zero for edges
+    /// without a delay
+    pub fn get_edge_delay(&self, edge_id: EdgeId) -> Time {
+        self.edge_delays
+            .as_ref()
+            .and_then(|delays| delays.get(edge_id.as_usize()).copied())
+            .unwrap_or(Time::ZERO)
+    }
+
+    /// sets the default speeds by road class, raising the max speed used by
+    /// the heuristic when a default is faster than every table speed.
+    pub fn set_road_class_speeds(&mut self, road_class_speeds: RoadClassSpeeds) {
+        if let Some(fastest) =
+            road_class_speeds
+                .speeds
+                .values()
+                .copied()
+                .reduce(|a, b| if a > b { a } else { b })
+        {
+            if fastest > self.max_speed {
+                self.max_speed = fastest;
+            }
+        }
+        self.road_class_speeds = Some(road_class_speeds);
+    }
+
+    /// the default speed for an edge from its road class, if configured
+    pub fn get_road_class_speed(&self, edge_id: EdgeId) -> Option<Speed> {
+        self.road_class_speeds
+            .as_ref()
+            .and_then(|speeds| speeds.get_speed(edge_id))
+    }
+
+    /// the road class of an edge, known when road class speeds are configured
+    pub fn get_road_class(&self, edge_id: EdgeId) -> Option<u8> {
+        self.road_class_speeds
+            .as_ref()
+            .and_then(|speeds| speeds.get_road_class(edge_id))
+    }
+
+    /// raises a speed up to the minimum speed, if one is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge_id` - the edge the speed was found for, used for logging
+    /// * `speed` - the speed found in the speed table or speed profile
+    ///
+    /// # Returns
+    ///
+    /// the floored speed, or None if the speed was already at or above the floor
+    pub fn apply_min_speed(&self, edge_id: EdgeId, speed: Speed) -> Option<Speed> {
+        match self.min_speed {
+            Some(min_speed) if speed < min_speed => {
+                log::trace!(
+                    "edge {} speed {} is below the min speed, using {}",
+                    edge_id,
+                    speed,
+                    min_speed
+                );
+                Some(min_speed)
+            }
+            _ => None,
+        }
+    }
 }
 
 pub fn get_max_speed(speed_table: &[Speed]) -> Result<Speed, TraversalModelError> {
@@ -1,46 +1,166 @@
 use super::speed_traversal_engine::SpeedTraversalEngine;
-use crate::model::road_network::edge_id::EdgeId;
 use crate::model::state::state_feature::StateFeature;
 use crate::model::state::state_model::StateModel;
-use crate::model::traversal::traversal_model::TraversalModel;
-use crate::model::unit::{Distance, Time, BASE_DISTANCE_UNIT};
+use crate::model::traversal::traversal_model::{TraversalModel, DEFAULT_SUMMARY_PRECISION};
+use crate::model::unit::as_f64::AsF64;
+use crate::model::unit::{Distance, SpeedUnit, Time, BASE_DISTANCE_UNIT};
 use crate::model::{
     property::{edge::Edge, vertex::Vertex},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
     unit::Speed,
 };
-use crate::util::geo::haversine;
+use crate::util::serde::serde_json_extension::SerdeJsonExtension;
+use serde::Serialize;
+use serde_json::json;
 use std::sync::Arc;
 
+/// where the speed used to traverse an edge came from
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedSource {
+    /// the static speed table
+    Tabular,
+    /// the default speed of the edge's road class, for an edge missing from
+    /// the speed table or with a speed of zero there
+    RoadClassDefault,
+    /// the time-of-day speed profile
+    Profile,
+    /// the engine's minimum speed, which replaced a slower speed from the table or profile
+    MinimumSpeedFloor,
+}
+
+/// the speed used to traverse an edge, recorded for debugging
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct ResolvedSpeed {
+    pub speed: Speed,
+    pub speed_unit: SpeedUnit,
+    pub source: SpeedSource,
+}
+
 pub struct SpeedTraversalModel {
     engine: Arc<SpeedTraversalEngine>,
+    departure_time: Option<Time>,
+    /// when true, [`TraversalModel::edge_details`] reports the resolved speed of each edge
+    report_resolved_speeds: bool,
+    /// decimal places of the times and distances in the route summary
+    summary_precision: u32,
 }
 
 impl SpeedTraversalModel {
     pub fn new(engine: Arc<SpeedTraversalEngine>) -> SpeedTraversalModel {
-        SpeedTraversalModel { engine }
+        SpeedTraversalModel {
+            engine,
+            departure_time: None,
+            report_resolved_speeds: false,
+            summary_precision: DEFAULT_SUMMARY_PRECISION,
+        }
     }
-    const DISTANCE: &'static str = "distance";
-    const TIME: &'static str = "time";
-}
 
-impl TraversalModel for SpeedTraversalModel {
-    fn traverse_edge(
+    /// creates a model which selects speeds from the engine's speed profile
+    /// using the clock time, which is the departure time plus the travel time
+    /// accumulated in the search state. the departure time is expressed in
+    /// the time unit of the speed profile.
+    pub fn new_with_departure_time(
+        engine: Arc<SpeedTraversalEngine>,
+        departure_time: Time,
+    ) -> SpeedTraversalModel {
+        SpeedTraversalModel {
+            engine,
+            departure_time: Some(departure_time),
+            report_resolved_speeds: false,
+            summary_precision: DEFAULT_SUMMARY_PRECISION,
+        }
+    }
+
+    /// enables reporting the speed and speed source of each edge of a route
+    /// through [`TraversalModel::edge_details`]. intended for debugging, as
+    /// the speeds are resolved again for every route edge.
+    pub fn with_resolved_speed_reporting(self) -> SpeedTraversalModel {
+        SpeedTraversalModel {
+            report_resolved_speeds: true,
+            ..self
+        }
+    }
+
+    /// sets the number of decimal places of the times and distances in the route summary
+    pub fn with_summary_precision(self, summary_precision: u32) -> SpeedTraversalModel {
+        SpeedTraversalModel {
+            summary_precision,
+            ..self
+        }
+    }
+
+    /// finds the speed for this edge. when a speed profile and departure time
+    /// are present, the speed comes from the profile bucket for the current
+    /// clock time, otherwise it comes from the static speed table, or from the
+    /// road class default when the table has no positive speed for the edge.
+    /// the result is raised to the engine's minimum speed when one is configured.
+    fn get_edge_speed(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<(Speed, SpeedSource), TraversalModelError> {
+        let (speed, source) = match (&self.engine.speed_profile, self.departure_time) {
+            (Some(profile), Some(departure_time)) => {
+                let elapsed =
+                    state_model.get_time(state, &Self::TIME.into(), &profile.time_unit)?;
+                let road_class = self.engine.get_road_class(edge.edge_id);
+                let speed = profile.get_speed(edge, road_class, departure_time + elapsed)?;
+                (speed, SpeedSource::Profile)
+            }
+            _ => {
+                let table_speed = self
+                    .engine
+                    .speed_table
+                    .get(edge.edge_id.as_usize())
+                    .copied()
+                    .filter(|speed| *speed > Speed::ZERO);
+                match (table_speed, self.engine.get_road_class_speed(edge.edge_id)) {
+                    (Some(speed), _) => (speed, SpeedSource::Tabular),
+                    (None, Some(speed)) => (speed, SpeedSource::RoadClassDefault),
+                    (None, None) => {
+                        let road_class = self.engine.get_road_class(edge.edge_id);
+                        let speed = get_speed(&self.engine.speed_table, edge, road_class)?;
+                        (speed, SpeedSource::Tabular)
+                    }
+                }
+            }
+        };
+        match self.engine.apply_min_speed(edge.edge_id, speed) {
+            Some(floored) => Ok((floored, SpeedSource::MinimumSpeedFloor)),
+            None => Ok((speed, source)),
+        }
+    }
+
+    /// adds the distance of the edge and the time to traverse it, charging
+    /// `delay_fraction` of any fixed delay on the edge
+    fn traverse(
         &self,
         trajectory: (&Vertex, &Edge, &Vertex),
-        state: &mut Vec<StateVar>,
+        delay_fraction: f64,
+        state: &mut [StateVar],
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (_, edge, _) = trajectory;
         let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, &self.engine.distance_unit);
-        let speed = get_speed(&self.engine.speed_table, edge.edge_id)?;
-        let edge_time = Time::create(
-            &speed,
-            &self.engine.speed_unit,
-            &distance,
-            &self.engine.distance_unit,
-            &self.engine.time_unit,
-        )?;
+        // a zero-distance edge takes zero time regardless of speed, which avoids
+        // dividing by a speed that may itself be zero
+        let edge_time = if distance == Distance::ZERO {
+            Time::ZERO
+        } else {
+            let (speed, _) = self.get_edge_speed(edge, state, state_model)?;
+            Time::create(
+                &speed,
+                &self.engine.speed_unit,
+                &distance,
+                &self.engine.distance_unit,
+                &self.engine.time_unit,
+            )?
+        };
+        // fixed delays only add time, so the estimate ignores them and stays admissible
+        let delay = self.engine.get_edge_delay(edge.edge_id);
+        let edge_time = edge_time + Time::new(delay.as_f64() * delay_fraction);
 
         state_model.add_time(
             state,
@@ -57,6 +177,32 @@ impl TraversalModel for SpeedTraversalModel {
         Ok(())
     }
 
+    const DISTANCE: &'static str = "distance";
+    const TIME: &'static str = "time";
+}
+
+impl TraversalModel for SpeedTraversalModel {
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.traverse(trajectory, 1.0, state, state_model)
+    }
+
+    /// the edge delay is charged in proportion to the fraction traversed,
+    /// the same as the free-flow time of the partial edge
+    fn traverse_partial_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        fraction: f64,
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.traverse(trajectory, fraction, state, state_model)
+    }
+
     fn estimate_traversal(
         &self,
         od: (&Vertex, &Vertex),
@@ -64,9 +210,11 @@ impl TraversalModel for SpeedTraversalModel {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (src, dst) = od;
-        let distance =
-            haversine::coord_distance(&src.coordinate, &dst.coordinate, self.engine.distance_unit)
-                .map_err(TraversalModelError::NumericError)?;
+        let distance = self
+            .engine
+            .distance_function
+            .distance(&src.coordinate, &dst.coordinate, self.engine.distance_unit)
+            .map_err(TraversalModelError::NumericError)?;
 
         if distance == Distance::ZERO {
             return Ok(());
@@ -94,6 +242,96 @@ impl TraversalModel for SpeedTraversalModel {
 
         Ok(())
     }
+    /// reports the travel time and distance of the route in the units of the engine
+    fn summary(
+        &self,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        let time = state_model.get_time(state, &Self::TIME.into(), &self.engine.time_unit)?;
+        let distance =
+            state_model.get_distance(state, &Self::DISTANCE.into(), &self.engine.distance_unit)?;
+        let summary = json!({
+            "time": time,
+            "time_unit": self.engine.time_unit,
+            "distance": distance,
+            "distance_unit": self.engine.distance_unit,
+        });
+        Ok(Some(summary.round_floats(self.summary_precision)))
+    }
+
+    /// reports the speed and its source for a route edge when enabled. the
+    /// speed is resolved from the state before the edge, as in `traverse_edge`
+    fn edge_details(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        if !self.report_resolved_speeds {
+            return Ok(None);
+        }
+        let (_, edge, _) = trajectory;
+        let (speed, source) = self.get_edge_speed(edge, state, state_model)?;
+        let resolved = ResolvedSpeed {
+            speed,
+            speed_unit: self.engine.speed_unit,
+            source,
+        };
+        let details = serde_json::to_value(resolved).map_err(|e| {
+            TraversalModelError::InternalError(format!("failed to serialize speed: {}", e))
+        })?;
+        Ok(Some(details))
+    }
+
+    /// speeds from the static table do not depend on the state, so these
+    /// traversals are cacheable unless the speed comes from the time of day.
+    /// the key identifies the shared engine, so models built from different
+    /// speed tables never share cached traversals.
+    fn cache_key(&self) -> Option<String> {
+        let time_of_day = self.engine.speed_profile.is_some() && self.departure_time.is_some();
+        if time_of_day {
+            None
+        } else {
+            Some(format!("speed_table:{:p}", Arc::as_ptr(&self.engine)))
+        }
+    }
+
+    /// checks that every speed in the table is a finite, non-negative number
+    /// and that every edge delay is non-negative
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        if self.engine.speed_table.is_empty() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "speed table is empty",
+            )));
+        }
+        let invalid_speed = self
+            .engine
+            .speed_table
+            .iter()
+            .enumerate()
+            .find(|(_, speed)| !speed.as_f64().is_finite() || speed.as_f64() < 0.0);
+        if let Some((edge_id, speed)) = invalid_speed {
+            return Err(TraversalModelError::BuildError(format!(
+                "speed table has invalid speed {} {} for edge {}",
+                speed, self.engine.speed_unit, edge_id
+            )));
+        }
+        let invalid_delay = self.engine.edge_delays.as_ref().and_then(|delays| {
+            delays
+                .iter()
+                .enumerate()
+                .find(|(_, delay)| !delay.as_f64().is_finite() || delay.as_f64() < 0.0)
+        });
+        if let Some((edge_id, delay)) = invalid_delay {
+            return Err(TraversalModelError::BuildError(format!(
+                "edge delays have invalid delay {} {} for edge {}",
+                delay, self.engine.time_unit, edge_id
+            )));
+        }
+        Ok(())
+    }
+
     /// track the time state feature
     fn state_features(&self) -> Vec<(String, StateFeature)> {
         vec![
@@ -115,28 +353,35 @@ impl TraversalModel for SpeedTraversalModel {
     }
 }
 
-/// look up a speed from the speed table
-pub fn get_speed(speed_table: &[Speed], edge_id: EdgeId) -> Result<Speed, TraversalModelError> {
-    let speed: &Speed = speed_table.get(edge_id.as_usize()).ok_or_else(|| {
-        TraversalModelError::MissingIdInTabularCostFunction(
-            format!("{}", edge_id),
-            String::from("EdgeId"),
-            String::from("speed table"),
-        )
-    })?;
+/// look up a speed from the speed table. a missing speed reports the
+/// vertices and road class of the edge so it can be found in the source data.
+pub fn get_speed(
+    speed_table: &[Speed],
+    edge: &Edge,
+    road_class: Option<u8>,
+) -> Result<Speed, TraversalModelError> {
+    let speed: &Speed = speed_table
+        .get(edge.edge_id.as_usize())
+        .ok_or_else(|| TraversalModelError::missing_edge("speed table", edge, road_class))?;
     Ok(*speed)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::unit::{Distance, DistanceUnit, SpeedUnit, TimeUnit};
+    use crate::model::state::state_model_builder::StateModelBuilder;
+    use crate::model::traversal::default::speed_profile::SpeedProfile;
+    use crate::model::traversal::default::speed_traversal_engine::RoadClassSpeeds;
+    use crate::model::unit::as_f64::AsF64;
+    use crate::model::unit::{CostUnit, Distance, DistanceUnit, SpeedUnit, TimeUnit};
     use crate::model::{
         property::{edge::Edge, vertex::Vertex},
         road_network::{edge_id::EdgeId, vertex_id::VertexId},
     };
+    use crate::test_util::search_instance_builder::raw_cost_model;
     use crate::util::geo::coord::InternalCoord;
     use geo::coord;
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
     fn mock_vertex() -> Vertex {
@@ -151,6 +396,7 @@ mod tests {
             src_vertex_id: VertexId(0),
             dst_vertex_id: VertexId(1),
             distance: Distance::new(100.0),
+            closed: false,
         }
     }
     fn filepath() -> PathBuf {
@@ -163,6 +409,26 @@ mod tests {
             .join("velocities.txt")
     }
 
+    fn zero_speed_filepath() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join("velocities_with_zero.txt")
+    }
+
+    fn profile_filepath() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join("speed_profile.txt")
+    }
+
     fn approx_eq(a: f64, b: f64, error: f64) {
         let result = match (a, b) {
             (c, d) if c < d => d - c < error,
@@ -184,26 +450,27 @@ mod tests {
             SpeedUnit::KilometersPerHour,
             None,
             Some(TimeUnit::Seconds),
+            None,
+            None,
         )
         .unwrap();
         let state_model = Arc::new(
-            StateModel::empty()
-                .extend(vec![
-                    (
-                        String::from("distance"),
-                        StateFeature::Distance {
-                            distance_unit: DistanceUnit::Kilometers,
-                            initial: Distance::new(0.0),
-                        },
-                    ),
-                    (
-                        String::from("time"),
-                        StateFeature::Time {
-                            time_unit: TimeUnit::Seconds,
-                            initial: Time::new(0.0),
-                        },
-                    ),
-                ])
+            StateModelBuilder::new()
+                .with_feature(
+                    "distance",
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Kilometers,
+                        initial: Distance::new(0.0),
+                    },
+                )
+                .with_feature(
+                    "time",
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Seconds,
+                        initial: Time::new(0.0),
+                    },
+                )
+                .build()
                 .unwrap(),
         );
         let model: SpeedTraversalModel = SpeedTraversalModel::new(Arc::new(engine));
@@ -216,9 +483,97 @@ mod tests {
             .unwrap();
 
         let expected = 36.0;
-        // approx_eq(result.total_cost.into(), expected, 0.001);
-        // approx_eq(result.updated_state[1].into(), expected, 0.001);
-        approx_eq(state[1].into(), expected, 0.001);
+        let time = state_model.get_value(&state, "time").unwrap();
+        approx_eq(time.into(), expected, 0.001);
+    }
+
+    #[test]
+    fn test_cost_unit_matches_configured_time_unit() {
+        for time_unit in [TimeUnit::Seconds, TimeUnit::Minutes, TimeUnit::Hours] {
+            let engine = SpeedTraversalEngine::new(
+                &filepath(),
+                SpeedUnit::KilometersPerHour,
+                None,
+                Some(time_unit),
+                None,
+                None,
+            )
+            .unwrap();
+            let model = SpeedTraversalModel::new(Arc::new(engine));
+            let state_model = Arc::new(StateModel::empty().extend(model.state_features()).unwrap());
+            let cost_model = raw_cost_model(&["time"], state_model);
+            assert_eq!(cost_model.cost_unit(), Some(CostUnit::Time(time_unit)));
+        }
+    }
+
+    #[test]
+    fn test_signalized_edge_takes_longer_than_free_flow() {
+        let file = filepath();
+        let mut engine = SpeedTraversalEngine::new(
+            &file,
+            SpeedUnit::KilometersPerHour,
+            None,
+            Some(TimeUnit::Seconds),
+            None,
+            None,
+        )
+        .unwrap();
+        let delay_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("model")
+            .join("traversal")
+            .join("default")
+            .join("test")
+            .join("edge_delays.txt");
+        // the delay file is in minutes, with a half-minute signal on edge 0
+        let delays = engine
+            .read_edge_delays(&delay_file, TimeUnit::Minutes)
+            .unwrap();
+        engine.edge_delays = Some(delays);
+        let state_model = StateModelBuilder::new()
+            .with_feature(
+                "distance",
+                StateFeature::Distance {
+                    distance_unit: DistanceUnit::Kilometers,
+                    initial: Distance::new(0.0),
+                },
+            )
+            .with_feature(
+                "time",
+                StateFeature::Time {
+                    time_unit: TimeUnit::Seconds,
+                    initial: Time::new(0.0),
+                },
+            )
+            .build()
+            .unwrap();
+        let model = SpeedTraversalModel::new(Arc::new(engine));
+        let v = mock_vertex();
+        let traverse = |edge_id: usize| {
+            let mut state = state_model.initial_state().unwrap();
+            model
+                .traverse_edge((&v, &mock_edge(edge_id), &v), &mut state, &state_model)
+                .unwrap();
+            state_model.get_value(&state, "time").unwrap().into()
+        };
+
+        // 100 meters @ 10kph takes 36 seconds of free-flow time, plus 30 seconds at the signal
+        approx_eq(traverse(0), 36.0 + 30.0, 0.001);
+        // edge 1 has no delay, and edges past the end of the table have none
+        approx_eq(traverse(1), 18.0, 0.001);
+        approx_eq(traverse(3), 9.0, 0.001);
+
+        // half of edge 0 takes half of its free-flow time and half of its delay
+        let mut state = state_model.initial_state().unwrap();
+        let half_edge = Edge {
+            distance: Distance::new(50.0),
+            ..mock_edge(0)
+        };
+        model
+            .traverse_partial_edge((&v, &half_edge, &v), 0.5, &mut state, &state_model)
+            .unwrap();
+        let time: f64 = state_model.get_value(&state, "time").unwrap().into();
+        approx_eq(time, 18.0 + 15.0, 0.001);
     }
 
     #[test]
@@ -229,26 +584,27 @@ mod tests {
             SpeedUnit::KilometersPerHour,
             None,
             Some(TimeUnit::Milliseconds),
+            None,
+            None,
         )
         .unwrap();
         let state_model = Arc::new(
-            StateModel::empty()
-                .extend(vec![
-                    (
-                        String::from("distance"),
-                        StateFeature::Distance {
-                            distance_unit: DistanceUnit::Kilometers,
-                            initial: Distance::new(0.0),
-                        },
-                    ),
-                    (
-                        String::from("time"),
-                        StateFeature::Time {
-                            time_unit: TimeUnit::Milliseconds,
-                            initial: Time::new(0.0),
-                        },
-                    ),
-                ])
+            StateModelBuilder::new()
+                .with_feature(
+                    "distance",
+                    StateFeature::Distance {
+                        distance_unit: DistanceUnit::Kilometers,
+                        initial: Distance::new(0.0),
+                    },
+                )
+                .with_feature(
+                    "time",
+                    StateFeature::Time {
+                        time_unit: TimeUnit::Milliseconds,
+                        initial: Time::new(0.0),
+                    },
+                )
+                .build()
                 .unwrap(),
         );
         let model = SpeedTraversalModel::new(Arc::new(engine));
@@ -260,8 +616,285 @@ mod tests {
             .traverse_edge((&v, &e1, &v), &mut state, &state_model)
             .unwrap();
         let expected = 36000.0;
-        // approx_eq(result.total_cost.into(), expected, 0.001);
-        // approx_eq(result.updated_state[1].into(), expected, 0.001);
-        approx_eq(state[1].into(), expected, 0.001);
+        let time = state_model.get_value(&state, "time").unwrap();
+        approx_eq(time.into(), expected, 0.001);
+    }
+
+    #[test]
+    fn test_edge_cost_lookup_with_speed_profile() {
+        // 4 buckets of one hour each with speeds 10, 20, 40, 20 kph
+        let profile =
+            SpeedProfile::new(&profile_filepath(), Time::new(3600.0), TimeUnit::Seconds).unwrap();
+        let engine = Arc::new(
+            SpeedTraversalEngine::new(
+                &filepath(),
+                SpeedUnit::KilometersPerHour,
+                None,
+                Some(TimeUnit::Seconds),
+                Some(profile),
+                None,
+            )
+            .unwrap(),
+        );
+        // heuristic speed must be the max across all buckets
+        assert_eq!(engine.max_speed, Speed::new(40.0));
+
+        let state_model = StateModel::empty()
+            .extend(SpeedTraversalModel::new(engine.clone()).state_features())
+            .unwrap();
+        let v = mock_vertex();
+        let e1 = mock_edge(0);
+
+        // departing at 01:00 falls in the 20kph bucket: 100 meters takes 18 seconds
+        let model = SpeedTraversalModel::new_with_departure_time(engine.clone(), Time::new(3600.0));
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.as_f64(), 18.0, 0.001);
+
+        // accumulated travel time moves the clock into the 40kph bucket
+        let model = SpeedTraversalModel::new_with_departure_time(engine.clone(), Time::new(7199.0));
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.as_f64(), 18.0 + 9.0, 0.001);
+
+        // without a departure time, the static speed table is used: 10kph takes 36 seconds
+        let model = SpeedTraversalModel::new(engine);
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.as_f64(), 36.0, 0.001);
+    }
+
+    #[test]
+    fn test_zero_distance_edge_takes_zero_time() {
+        let engine = SpeedTraversalEngine::new(
+            &filepath(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            Some(TimeUnit::Seconds),
+            None,
+            None,
+        )
+        .unwrap();
+        let model = SpeedTraversalModel::new(Arc::new(engine));
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = mock_vertex();
+        let e1 = Edge {
+            distance: Distance::ZERO,
+            ..mock_edge(0)
+        };
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        assert_eq!(time, Time::ZERO);
+        assert!(!time.as_f64().is_nan());
+    }
+
+    #[test]
+    fn test_resolved_speed_reporting() {
+        let engine = Arc::new(
+            SpeedTraversalEngine::new(
+                &filepath(),
+                SpeedUnit::KilometersPerHour,
+                None,
+                Some(TimeUnit::Seconds),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        let v = mock_vertex();
+        let e1 = mock_edge(0);
+
+        let reporting = SpeedTraversalModel::new(engine.clone()).with_resolved_speed_reporting();
+        let state_model = StateModel::empty()
+            .extend(reporting.state_features())
+            .unwrap();
+        let state = state_model.initial_state().unwrap();
+        let details = reporting
+            .edge_details((&v, &e1, &v), &state, &state_model)
+            .unwrap()
+            .unwrap();
+        assert_eq!(details["source"], serde_json::json!("tabular"));
+        assert_eq!(
+            details["speed_unit"],
+            serde_json::json!("kilometers_per_hour")
+        );
+        assert_eq!(details["speed"], serde_json::json!(10.0));
+        // reporting does not prevent caching, as traversals have no side effects
+        assert!(reporting.cache_key().is_some());
+
+        // without the debug flag, nothing is reported
+        let silent = SpeedTraversalModel::new(engine);
+        let details = silent
+            .edge_details((&v, &e1, &v), &state, &state_model)
+            .unwrap();
+        assert!(details.is_none());
+    }
+
+    #[test]
+    fn test_road_class_default_speed() {
+        // edge 1 of this speed table has a speed of zero, and edge 9 is past its end
+        let mut engine = SpeedTraversalEngine::new(
+            &zero_speed_filepath(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            Some(TimeUnit::Seconds),
+            None,
+            None,
+        )
+        .unwrap();
+        engine.set_road_class_speeds(RoadClassSpeeds {
+            road_classes: vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2].into_boxed_slice(),
+            speeds: HashMap::from([(1, Speed::new(200.0))]),
+        });
+        assert_eq!(engine.max_speed, Speed::new(200.0));
+        let model = SpeedTraversalModel::new(Arc::new(engine)).with_resolved_speed_reporting();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let v = mock_vertex();
+        for (edge_id, source) in [
+            (0, "tabular"),
+            (1, "road_class_default"),
+            (9, "road_class_default"),
+        ] {
+            let state = state_model.initial_state().unwrap();
+            let details = model
+                .edge_details((&v, &mock_edge(edge_id), &v), &state, &state_model)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                details["source"],
+                serde_json::json!(source),
+                "edge {}",
+                edge_id
+            );
+        }
+        // 100 meters @ 200kph takes 1.8 seconds
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &mock_edge(1), &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.as_f64(), 1.8, 0.001);
+
+        // edge 10 is missing and road class 2 has no default, so the error names its road class
+        let mut state = state_model.initial_state().unwrap();
+        let error = model
+            .traverse_edge((&v, &mock_edge(10), &v), &mut state, &state_model)
+            .unwrap_err();
+        assert!(error.to_string().contains("road class 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_min_speed_floor_clamps_zero_speed() {
+        // edge 1 of this speed table has a speed of zero
+        let v = mock_vertex();
+        let e1 = mock_edge(1);
+        let engine = SpeedTraversalEngine::new(
+            &zero_speed_filepath(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            Some(TimeUnit::Seconds),
+            None,
+            Some(Speed::new(5.0)),
+        )
+        .unwrap();
+        let model = SpeedTraversalModel::new(Arc::new(engine)).with_resolved_speed_reporting();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let details = model
+            .edge_details((&v, &e1, &v), &state, &state_model)
+            .unwrap()
+            .unwrap();
+        assert_eq!(details["source"], serde_json::json!("minimum_speed_floor"));
+        model
+            .traverse_edge((&v, &e1, &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        // 100 meters @ 5kph should take 72 seconds ((0.1/5) * 3600)
+        assert!(time.as_f64().is_finite());
+        approx_eq(time.as_f64(), 72.0, 0.001);
+
+        // speeds above the floor are unchanged: 10kph takes 36 seconds
+        let mut state = state_model.initial_state().unwrap();
+        model
+            .traverse_edge((&v, &mock_edge(0), &v), &mut state, &state_model)
+            .unwrap();
+        let time = state_model
+            .get_time(&state, &"time".into(), &TimeUnit::Seconds)
+            .unwrap();
+        approx_eq(time.as_f64(), 36.0, 0.001);
+    }
+
+    #[test]
+    fn test_min_speed_must_be_positive_and_below_max() {
+        for min_speed in [Speed::ZERO, Speed::new(1000.0)] {
+            let result = SpeedTraversalEngine::new(
+                &filepath(),
+                SpeedUnit::KilometersPerHour,
+                None,
+                None,
+                None,
+                Some(min_speed),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_speeds() {
+        let engine = SpeedTraversalEngine::new(
+            &filepath(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(SpeedTraversalModel::new(Arc::new(engine))
+            .validate()
+            .is_ok());
+
+        let speeds = vec![Speed::new(10.0), Speed::new(-5.0)].into_boxed_slice();
+        let engine = SpeedTraversalEngine::from_table(
+            speeds,
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let result = SpeedTraversalModel::new(Arc::new(engine)).validate();
+        assert!(matches!(
+            result,
+            Err(TraversalModelError::BuildError(msg)) if msg.contains("edge 1")
+        ));
     }
 }
@@ -2,9 +2,12 @@ use super::{
     speed_traversal_engine::SpeedTraversalEngine, speed_traversal_model::SpeedTraversalModel,
 };
 use crate::model::traversal::{
-    traversal_model::TraversalModel, traversal_model_error::TraversalModelError,
+    traversal_model::{summary_precision, TraversalModel},
+    traversal_model_error::TraversalModelError,
     traversal_model_service::TraversalModelService,
 };
+use crate::model::unit::Time;
+use crate::util::departure_time::DepartureTime;
 use std::sync::Arc;
 
 pub struct SpeedLookupService {
@@ -14,8 +17,46 @@ pub struct SpeedLookupService {
 impl TraversalModelService for SpeedLookupService {
     fn build(
         &self,
-        _parameters: &serde_json::Value,
+        parameters: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
-        Ok(Arc::new(SpeedTraversalModel::new(self.e.clone())))
+        let model = match parameters.get("departure_time") {
+            None => SpeedTraversalModel::new(self.e.clone()),
+            Some(serde_json::Value::String(timestamp)) => {
+                // a timestamp selects its local time of day in the road network timezone
+                let departure = DepartureTime::parse(timestamp, self.e.timezone)
+                    .map_err(TraversalModelError::BuildError)?;
+                let time_unit = match &self.e.speed_profile {
+                    Some(profile) => profile.time_unit,
+                    None => self.e.time_unit,
+                };
+                let departure_time = departure.time_of_day(&time_unit);
+                SpeedTraversalModel::new_with_departure_time(self.e.clone(), departure_time)
+            }
+            Some(value) => {
+                let departure_time = value.as_f64().map(Time::new).ok_or_else(|| {
+                    TraversalModelError::BuildError(format!(
+                        "expected departure_time to be a number or timestamp, found {}",
+                        value
+                    ))
+                })?;
+                SpeedTraversalModel::new_with_departure_time(self.e.clone(), departure_time)
+            }
+        };
+        let model =
+            model.with_summary_precision(summary_precision(parameters, self.e.summary_precision)?);
+        let debug_speeds = match parameters.get("debug_speeds") {
+            None => false,
+            Some(value) => value.as_bool().ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "expected debug_speeds to be a boolean, found {}",
+                    value
+                ))
+            })?,
+        };
+        if debug_speeds {
+            Ok(Arc::new(model.with_resolved_speed_reporting()))
+        } else {
+            Ok(Arc::new(model))
+        }
     }
 }
@@ -1,3 +1,4 @@
+pub mod cached_traversal_model;
 pub mod default;
 pub mod state;
 pub mod traversal_model;
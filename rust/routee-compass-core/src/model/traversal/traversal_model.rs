@@ -12,6 +12,37 @@ use crate::model::state::state_model::StateModel;
 ///
 /// [DistanceModel]: super::default::distance::DistanceModel
 /// [SpeedLookupModel]: super::default::speed_lookup_model::SpeedLookupModel
+/// default number of decimal places used by traversal models when rounding
+/// the numbers in their route summaries.
+pub const DEFAULT_SUMMARY_PRECISION: u32 = 4;
+
+/// resolves the number of decimal places of a route summary. the
+/// `summary_precision` query parameter wins over the precision configured
+/// for the traversal model, which wins over [`DEFAULT_SUMMARY_PRECISION`].
+///
+/// # Arguments
+///
+/// * `parameters` - the query used to build the traversal model
+/// * `configured` - the precision set in the traversal model configuration, if any
+///
+/// # Returns
+///
+/// the number of decimal places, or an error if the query value is not a whole number
+pub fn summary_precision(
+    parameters: &serde_json::Value,
+    configured: Option<u32>,
+) -> Result<u32, TraversalModelError> {
+    match parameters.get("summary_precision") {
+        None => Ok(configured.unwrap_or(DEFAULT_SUMMARY_PRECISION)),
+        Some(value) => serde_json::from_value::<u32>(value.clone()).map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "invalid 'summary_precision' value {}: {}",
+                value, e
+            ))
+        }),
+    }
+}
+
 pub trait TraversalModel: Send + Sync {
     /// lists the state variables expected by this traversal model that are not
     /// defined on the base configuration. for example, if this traversal model
@@ -38,6 +69,31 @@ pub trait TraversalModel: Send + Sync {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
 
+    /// Updates the traversal state by traversing part of an edge, such as the
+    /// remainder of an origin edge after a query offset. The edge of the
+    /// trajectory is a copy whose distance is already scaled by the fraction,
+    /// while its edge id is that of the full edge. By default, the partial
+    /// edge is traversed like any other edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `trajectory` - source vertex, partial edge and destination vertex
+    /// * `fraction` - the portion of the edge traversed, in the range [0, 1]
+    /// * `state` - state of the search at the beginning of this edge
+    ///
+    /// # Returns
+    ///
+    /// Either a traversal result or an error.
+    fn traverse_partial_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        _fraction: f64,
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.traverse_edge(trajectory, state, state_model)
+    }
+
     /// Estimates the traversal state by traversing between two vertices without
     /// performing any graph traversals.
     ///
@@ -56,4 +112,74 @@ pub trait TraversalModel: Send + Sync {
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
+
+    /// Summarizes the final state of a route from the perspective of this model,
+    /// such as accumulated values reported in alternative display units.
+    /// Models should round the numbers in their summaries, by default to
+    /// [`DEFAULT_SUMMARY_PRECISION`] decimal places, to keep responses compact.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - state at the end of the route
+    /// * `state_model` - provides access to the state vector
+    ///
+    /// # Returns
+    ///
+    /// A JSON summary, None if this model does not provide one, or an error.
+    fn summary(
+        &self,
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        Ok(None)
+    }
+
+    /// Describes how this model traverses an edge of a route, such as the
+    /// inputs it resolves for that edge, to help debug the route. Models that
+    /// do not report these details return None.
+    ///
+    /// # Arguments
+    ///
+    /// * `trajectory` - source vertex, edge, and destination vertex of the route edge
+    /// * `state` - state of the route before the edge was traversed
+    /// * `state_model` - provides access to the state vector
+    ///
+    /// # Returns
+    ///
+    /// A JSON description of the traversal, None if not reported, or an error
+    fn edge_details(
+        &self,
+        _trajectory: (&Vertex, &Edge, &Vertex),
+        _state: &[StateVar],
+        _state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        Ok(None)
+    }
+
+    /// Advertises whether this model is state-independent, meaning it changes
+    /// the state by the same amount on an edge no matter what state it
+    /// arrives with, so that its edge traversals can be cached by edge id.
+    /// The key identifies the model's configuration and data, so that the
+    /// models built for two queries share cached traversals only when they
+    /// have the same key. Models that depend on the state, such as time of
+    /// day speeds or a battery state of charge, return None, which is the default.
+    ///
+    /// # Returns
+    ///
+    /// A key for the cache of this model's traversals, or None if it is not cacheable
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Checks that this model is configured coherently before it serves any
+    /// query, such as a speed table with valid speeds or an energy model that
+    /// can make a prediction, so that misconfiguration is reported at startup
+    /// instead of partway through a batch. By default, no checks are made.
+    ///
+    /// # Returns
+    ///
+    /// Nothing if the model is valid, otherwise an error describing the problem
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
 }
@@ -1,6 +1,8 @@
+use super::default::speed_grade_table::SpeedGradeTable;
 use super::{
     traversal_model_error::TraversalModelError, traversal_model_service::TraversalModelService,
 };
+use crate::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 /// A [`TraversalModelBuilder`] takes a JSON object describing the configuration of a
@@ -14,6 +16,7 @@ pub trait TraversalModelBuilder {
     /// # Arguments
     ///
     /// * `parameters` - the contents of the "traversal" TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
     ///
     /// # Returns
     ///
@@ -21,5 +24,31 @@ pub trait TraversalModelBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError>;
+
+    /// Builds a [`TraversalModelService`] whose speeds come from a combined speed
+    /// and grade table that was already read by the caller, such as the energy
+    /// model, so that the file is parsed once. By default, a model does not
+    /// read speeds from such a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - the contents of the model's TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
+    /// * `speed_grade_table` - speeds and grades by edge list row, in the units of the file
+    ///
+    /// # Returns
+    ///
+    /// A [`TraversalModelService`] designed to persist the duration of the CompassApp.
+    fn build_with_speed_grade_table(
+        &self,
+        _parameters: &serde_json::Value,
+        _graph: &Graph,
+        _speed_grade_table: &SpeedGradeTable,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        Err(TraversalModelError::BuildError(String::from(
+            "this traversal model does not read speeds from a speed and grade table",
+        )))
+    }
 }
@@ -1,5 +1,8 @@
 use super::state::traversal_state::TraversalState;
+use crate::model::property::edge::Edge;
+use crate::model::road_network::edge_id::EdgeId;
 use crate::model::road_network::graph_error::GraphError;
+use crate::model::road_network::vertex_id::VertexId;
 use crate::model::state::state_error::StateError;
 use crate::model::unit::UnitError;
 use crate::util::cache_policy::cache_error::CacheError;
@@ -17,6 +20,12 @@ pub enum TraversalModelError {
     StateVectorIndexOutOfBounds(usize, String, TraversalState),
     #[error("id {0} for id type {1} not found in tabular edge cost function {2}")]
     MissingIdInTabularCostFunction(String, String, String),
+    #[error("edge {edge_id} not found in tabular edge cost function {table} ({context})")]
+    MissingEdgeInTabularCostFunction {
+        edge_id: EdgeId,
+        table: String,
+        context: Box<MissingIdContext>,
+    },
     #[error("internal error: {0}")]
     InternalError(String),
     #[error(transparent)]
@@ -30,3 +39,58 @@ pub enum TraversalModelError {
     #[error("prediction model failed with error {0}")]
     PredictionModel(String),
 }
+
+/// where a missing tabular lookup happened in the road network, to help locate
+/// the offending edge in the source data. each field is set when known.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MissingIdContext {
+    pub src_vertex_id: Option<VertexId>,
+    pub dst_vertex_id: Option<VertexId>,
+    pub road_class: Option<u8>,
+}
+
+impl MissingIdContext {
+    /// adds the road class of the edge, when it is known
+    pub fn with_road_class(self, road_class: Option<u8>) -> MissingIdContext {
+        MissingIdContext { road_class, ..self }
+    }
+}
+
+impl TraversalModelError {
+    /// creates the error for an edge that is missing from a per-edge table
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - name of the table, such as "speed table"
+    /// * `edge` - the edge that was looked up
+    /// * `road_class` - road class of the edge, if known
+    pub fn missing_edge(table: &str, edge: &Edge, road_class: Option<u8>) -> TraversalModelError {
+        TraversalModelError::MissingEdgeInTabularCostFunction {
+            edge_id: edge.edge_id,
+            table: String::from(table),
+            context: Box::new(MissingIdContext::from(edge).with_road_class(road_class)),
+        }
+    }
+}
+
+impl From<&Edge> for MissingIdContext {
+    fn from(edge: &Edge) -> Self {
+        MissingIdContext {
+            src_vertex_id: Some(edge.src_vertex_id),
+            dst_vertex_id: Some(edge.dst_vertex_id),
+            road_class: None,
+        }
+    }
+}
+
+impl std::fmt::Display for MissingIdContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = [
+            self.src_vertex_id.map(|v| format!("src vertex {}", v)),
+            self.dst_vertex_id.map(|v| format!("dst vertex {}", v)),
+            self.road_class.map(|c| format!("road class {}", c)),
+        ];
+        let description = fields.into_iter().flatten().collect::<Vec<_>>().join(", ");
+        write!(f, "{}", description)
+    }
+}
@@ -29,4 +29,18 @@ pub trait TraversalModelService: Send + Sync {
         &self,
         query: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError>;
+
+    /// Validates the models of this service at startup. By default, builds a model
+    /// for an empty query and calls [TraversalModel::validate] on it. Services whose
+    /// models require query parameters should override this to validate a model for
+    /// each configuration they can build, such as each vehicle of an energy model.
+    ///
+    /// # Returns
+    ///
+    /// Nothing if the models are valid, otherwise an error describing the problem
+    ///
+    /// [TraversalModel::validate]: compass_core::model::traversal::traversal_model::TraversalModel::validate
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        self.build(&serde_json::json!({}))?.validate()
+    }
 }
@@ -0,0 +1,62 @@
+use super::{DistanceUnit, EnergyUnit, TimeUnit, UnitError};
+use serde::{Deserialize, Serialize};
+
+/// the unit of the cost scalar accumulated by a traversal model, so that a
+/// [`super::Cost`] can be reported and compared unambiguously. a cost does
+/// not carry its unit, which is instead reported by the model that produced it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CostUnit {
+    Distance(DistanceUnit),
+    Time(TimeUnit),
+    Energy(EnergyUnit),
+}
+
+impl CostUnit {
+    /// confirms that costs in this unit may be combined with costs in
+    /// another unit, which is only the case when the units are the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the unit of the other cost
+    ///
+    /// # Returns
+    ///
+    /// nothing if the units match, otherwise an error naming both units
+    pub fn ensure_compatible(&self, other: &CostUnit) -> Result<(), UnitError> {
+        if self == other {
+            Ok(())
+        } else {
+            Err(UnitError::IncompatibleCostUnits(*self, *other))
+        }
+    }
+}
+
+impl std::fmt::Display for CostUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CostUnit::Distance(unit) => write!(f, "{}", unit),
+            CostUnit::Time(unit) => write!(f, "{}", unit),
+            CostUnit::Energy(unit) => write!(f, "{}", unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_cost_units_are_incompatible() {
+        let seconds = CostUnit::Time(TimeUnit::Seconds);
+        assert!(seconds.ensure_compatible(&seconds).is_ok());
+        let result = seconds.ensure_compatible(&CostUnit::Time(TimeUnit::Minutes));
+        assert!(matches!(
+            result,
+            Err(UnitError::IncompatibleCostUnits(_, _))
+        ));
+        let result = seconds.ensure_compatible(&CostUnit::Distance(DistanceUnit::Meters));
+        assert!(result.is_err());
+        assert_eq!(seconds.to_string(), "seconds");
+    }
+}
@@ -1,10 +1,11 @@
+use super::unit_name::parse_unit_name;
 use super::Distance;
-use crate::util::serde::serde_ops::string_deserialize;
+use super::UnitError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", try_from = "String")]
 pub enum DistanceUnit {
     Meters,
     Kilometers,
@@ -55,11 +56,42 @@ impl std::fmt::Display for DistanceUnit {
     }
 }
 
+/// canonical names of each distance unit, as written by serialization
+const NAMES: [(&str, DistanceUnit); 5] = [
+    ("meters", DistanceUnit::Meters),
+    ("kilometers", DistanceUnit::Kilometers),
+    ("miles", DistanceUnit::Miles),
+    ("inches", DistanceUnit::Inches),
+    ("feet", DistanceUnit::Feet),
+];
+
+/// common abbreviations accepted in addition to the canonical names
+const ALIASES: [(&str, DistanceUnit); 10] = [
+    ("m", DistanceUnit::Meters),
+    ("meter", DistanceUnit::Meters),
+    ("km", DistanceUnit::Kilometers),
+    ("kilometer", DistanceUnit::Kilometers),
+    ("mi", DistanceUnit::Miles),
+    ("mile", DistanceUnit::Miles),
+    ("in", DistanceUnit::Inches),
+    ("inch", DistanceUnit::Inches),
+    ("ft", DistanceUnit::Feet),
+    ("foot", DistanceUnit::Feet),
+];
+
 impl FromStr for DistanceUnit {
-    type Err = serde_json::Error;
+    type Err = UnitError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        string_deserialize(s)
+        parse_unit_name(s, "distance unit", &NAMES, &ALIASES)
+    }
+}
+
+impl TryFrom<String> for DistanceUnit {
+    type Error = UnitError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        DistanceUnit::from_str(&value)
     }
 }
 
@@ -84,6 +116,18 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_parse_names_and_aliases() {
+        use std::str::FromStr;
+        assert_eq!(D::from_str("kilometers").unwrap(), D::Kilometers);
+        assert_eq!(D::from_str("KM").unwrap(), D::Kilometers);
+        assert_eq!(D::from_str("ft").unwrap(), D::Feet);
+        let error = D::from_str("furlongs").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("expected one of: meters, kilometers, miles, inches, feet"));
+    }
+
     #[test]
     fn test_conversions() {
         assert_approx_eq(
@@ -1,4 +1,4 @@
-use super::{DistanceUnit, EnergyUnit};
+use super::{energy_unit::EnergyFamily, DistanceUnit, EnergyUnit};
 use crate::util::serde::serde_ops::string_deserialize;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -27,6 +27,11 @@ impl EnergyRateUnit {
         }
     }
 
+    /// whether this rate measures liquid fuel or electricity
+    pub fn family(&self) -> EnergyFamily {
+        self.associated_energy_unit().family()
+    }
+
     pub fn associated_energy_unit(&self) -> EnergyUnit {
         use EnergyRateUnit as ERU;
         use EnergyUnit as EU;
@@ -3,7 +3,7 @@ use crate::util::serde::serde_ops::string_deserialize;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum EnergyUnit {
     GallonsGasoline,
@@ -11,7 +11,32 @@ pub enum EnergyUnit {
     KilowattHours,
 }
 
+/// the kind of energy measured by an energy unit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum EnergyFamily {
+    LiquidFuel,
+    Electric,
+}
+
+impl EnergyFamily {
+    /// short label for energy of this family, used in route summaries
+    pub fn label(&self) -> &'static str {
+        match self {
+            EnergyFamily::LiquidFuel => "gallons",
+            EnergyFamily::Electric => "kWh",
+        }
+    }
+}
+
 impl EnergyUnit {
+    pub fn family(&self) -> EnergyFamily {
+        match self {
+            EnergyUnit::GallonsGasoline | EnergyUnit::GallonsDiesel => EnergyFamily::LiquidFuel,
+            EnergyUnit::KilowattHours => EnergyFamily::Electric,
+        }
+    }
+
     // see https://epact.energy.gov/fuel-conversion-factors
     pub fn convert(&self, value: &Energy, target: &EnergyUnit) -> Energy {
         use EnergyUnit as S;
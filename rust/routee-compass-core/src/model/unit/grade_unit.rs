@@ -1,20 +1,34 @@
-use super::Grade;
+use super::{as_f64::AsF64, Grade};
 use crate::util::serde::serde_ops::string_deserialize;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+/// unit of a road grade. the canonical grade is a Decimal ratio of rise over run,
+/// which may also be written as "ratio". Degrees is the angle of the incline.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum GradeUnit {
     Percent,
+    #[serde(alias = "ratio")]
     Decimal,
     Millis,
+    Degrees,
 }
 
 impl GradeUnit {
     pub fn convert(&self, value: &Grade, target: &GradeUnit) -> Grade {
         use GradeUnit as G;
         match (self, target) {
+            (G::Degrees, G::Degrees) => *value,
+            // angles are not proportional to ratios, so go through the tangent
+            (G::Degrees, _) => {
+                let ratio = Grade::new(value.as_f64().to_radians().tan());
+                G::Decimal.convert(&ratio, target)
+            }
+            (_, G::Degrees) => {
+                let ratio = self.convert(value, &G::Decimal);
+                Grade::new(ratio.as_f64().atan().to_degrees())
+            }
             (G::Percent, G::Percent) => *value,
             (G::Decimal, G::Decimal) => *value,
             (G::Millis, G::Millis) => *value,
@@ -98,5 +112,22 @@ mod test {
             Grade::new(0.1),
             0.001,
         );
+        // a 45 degree incline rises one unit per unit of run
+        assert_approx_eq(
+            G::Degrees.convert(&Grade::new(45.0), &G::Percent),
+            Grade::new(100.0),
+            0.001,
+        );
+        assert_approx_eq(
+            G::Decimal.convert(&Grade::new(1.0), &G::Degrees),
+            Grade::new(45.0),
+            0.001,
+        );
+    }
+
+    #[test]
+    fn test_ratio_alias() {
+        let unit: G = serde_json::from_str("\"ratio\"").unwrap();
+        assert_eq!(unit, G::Decimal);
     }
 }
@@ -1,6 +1,7 @@
 pub mod as_f64;
 pub mod builders;
 pub mod cost;
+pub mod cost_unit;
 pub mod distance;
 pub mod distance_unit;
 pub mod energy;
@@ -15,17 +16,19 @@ pub mod speed_unit;
 pub mod time;
 pub mod time_unit;
 pub mod unit_error;
+mod unit_name;
 pub mod untyped;
 pub mod weight;
 pub mod weight_unit;
 pub use builders::{BASE_DISTANCE_UNIT, BASE_SPEED_UNIT, BASE_TIME_UNIT};
 pub use cost::Cost;
+pub use cost_unit::CostUnit;
 pub use distance::Distance;
 pub use distance_unit::DistanceUnit;
 pub use energy::Energy;
 pub use energy_rate::EnergyRate;
 pub use energy_rate_unit::EnergyRateUnit;
-pub use energy_unit::EnergyUnit;
+pub use energy_unit::{EnergyFamily, EnergyUnit};
 pub use grade::Grade;
 pub use grade_unit::GradeUnit;
 pub use speed::Speed;
@@ -1,11 +1,12 @@
+use super::unit_name::parse_unit_name;
 use super::Speed;
+use super::UnitError;
 use super::{DistanceUnit, TimeUnit};
-use crate::util::serde::serde_ops::string_deserialize;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", try_from = "String")]
 pub enum SpeedUnit {
     KilometersPerHour,
     MilesPerHour,
@@ -21,11 +22,36 @@ impl std::fmt::Display for SpeedUnit {
     }
 }
 
+/// canonical names of each speed unit, as written by serialization
+const NAMES: [(&str, SpeedUnit); 3] = [
+    ("kilometers_per_hour", SpeedUnit::KilometersPerHour),
+    ("miles_per_hour", SpeedUnit::MilesPerHour),
+    ("meters_per_second", SpeedUnit::MetersPerSecond),
+];
+
+/// common abbreviations accepted in addition to the canonical names
+const ALIASES: [(&str, SpeedUnit); 6] = [
+    ("kph", SpeedUnit::KilometersPerHour),
+    ("km/h", SpeedUnit::KilometersPerHour),
+    ("kmh", SpeedUnit::KilometersPerHour),
+    ("mph", SpeedUnit::MilesPerHour),
+    ("mps", SpeedUnit::MetersPerSecond),
+    ("m/s", SpeedUnit::MetersPerSecond),
+];
+
 impl FromStr for SpeedUnit {
-    type Err = serde_json::Error;
+    type Err = UnitError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        string_deserialize(s)
+        parse_unit_name(s, "speed unit", &NAMES, &ALIASES)
+    }
+}
+
+impl TryFrom<String> for SpeedUnit {
+    type Error = UnitError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        SpeedUnit::from_str(&value)
     }
 }
 
@@ -125,6 +151,41 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_parse_names_and_aliases() {
+        assert!(matches!(
+            SpeedUnit::from_str("kilometers_per_hour"),
+            Ok(S::KilometersPerHour)
+        ));
+        assert!(matches!(
+            SpeedUnit::from_str("Miles Per Hour"),
+            Ok(S::MilesPerHour)
+        ));
+        assert!(matches!(
+            SpeedUnit::from_str("KPH"),
+            Ok(S::KilometersPerHour)
+        ));
+        assert!(matches!(SpeedUnit::from_str(" mph "), Ok(S::MilesPerHour)));
+        let from_config: SpeedUnit = serde_json::from_value(serde_json::json!("m/s")).unwrap();
+        assert!(matches!(from_config, S::MetersPerSecond));
+        // serialization still writes the canonical name
+        assert_eq!(S::KilometersPerHour.to_string(), "kilometers_per_hour");
+    }
+
+    #[test]
+    fn test_parse_invalid_name_lists_valid_names() {
+        let error = SpeedUnit::from_str("kilometres_per_hr").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown speed unit 'kilometres_per_hr', expected one of: kilometers_per_hour, miles_per_hour, meters_per_second"
+        );
+        let from_config = serde_json::from_value::<SpeedUnit>(serde_json::json!("knots"));
+        assert!(from_config
+            .unwrap_err()
+            .to_string()
+            .contains("expected one of: kilometers_per_hour"));
+    }
+
     #[test]
     fn test_conversions() {
         assert_approx_eq(
@@ -1,7 +1,7 @@
 use allocative::Allocative;
 use derive_more::{Add, Div, Mul, Neg, Sub, Sum};
 use serde::{Deserialize, Serialize};
-use std::{cmp::Ordering, fmt::Display};
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
 
 use crate::model::traversal::state::state_variable::StateVar;
 
@@ -60,6 +60,24 @@ impl Ord for Time {
     }
 }
 
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s
+            .parse::<f64>()
+            .map_err(|_| format!("could not parse {} as a number", s))?;
+        if value < 0.0 {
+            Err(format!(
+                "time value {} invalid, must be non-negative [0, +inf]",
+                value
+            ))
+        } else {
+            Ok(Time::new(value))
+        }
+    }
+}
+
 impl Display for Time {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.0)
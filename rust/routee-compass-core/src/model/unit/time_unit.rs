@@ -1,10 +1,11 @@
+use super::unit_name::parse_unit_name;
 use super::Time;
-use crate::util::serde::serde_ops::string_deserialize;
+use super::UnitError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", try_from = "String")]
 pub enum TimeUnit {
     Hours,
     #[default]
@@ -46,11 +47,41 @@ impl std::fmt::Display for TimeUnit {
     }
 }
 
+/// canonical names of each time unit, as written by serialization
+const NAMES: [(&str, TimeUnit); 4] = [
+    ("hours", TimeUnit::Hours),
+    ("minutes", TimeUnit::Minutes),
+    ("seconds", TimeUnit::Seconds),
+    ("milliseconds", TimeUnit::Milliseconds),
+];
+
+/// common abbreviations accepted in addition to the canonical names
+const ALIASES: [(&str, TimeUnit); 10] = [
+    ("h", TimeUnit::Hours),
+    ("hr", TimeUnit::Hours),
+    ("hour", TimeUnit::Hours),
+    ("min", TimeUnit::Minutes),
+    ("minute", TimeUnit::Minutes),
+    ("s", TimeUnit::Seconds),
+    ("sec", TimeUnit::Seconds),
+    ("second", TimeUnit::Seconds),
+    ("ms", TimeUnit::Milliseconds),
+    ("millisecond", TimeUnit::Milliseconds),
+];
+
 impl FromStr for TimeUnit {
-    type Err = serde_json::Error;
+    type Err = UnitError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        string_deserialize(s)
+        parse_unit_name(s, "time unit", &NAMES, &ALIASES)
+    }
+}
+
+impl TryFrom<String> for TimeUnit {
+    type Error = UnitError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TimeUnit::from_str(&value)
     }
 }
 
@@ -72,6 +103,18 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_parse_names_and_aliases() {
+        assert_eq!(TimeUnit::from_str("seconds").unwrap(), T::Seconds);
+        assert_eq!(TimeUnit::from_str("Hours").unwrap(), T::Hours);
+        assert_eq!(TimeUnit::from_str("sec").unwrap(), T::Seconds);
+        assert_eq!(TimeUnit::from_str("ms").unwrap(), T::Milliseconds);
+        let error = TimeUnit::from_str("fortnights").unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("expected one of: hours, minutes, seconds, milliseconds"));
+    }
+
     #[test]
     fn test_conversions() {
         assert_approx_eq(T::Hours.convert(&Time::ONE, &T::Hours), Time::ONE, 0.001);
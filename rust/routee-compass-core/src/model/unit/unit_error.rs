@@ -1,4 +1,4 @@
-use super::{Distance, DistanceUnit, Speed, SpeedUnit, Time};
+use super::{CostUnit, Distance, DistanceUnit, Speed, SpeedUnit, Time};
 
 #[derive(thiserror::Error, Debug)]
 pub enum UnitError {
@@ -10,4 +10,12 @@ pub enum UnitError {
     SpeedFromTimeAndDistanceError(Time, Distance),
     #[error("cannot create time from speed {0} {1} and distance {2} {3}")]
     TimeFromSpeedAndDistanceError(Speed, SpeedUnit, Distance, DistanceUnit),
+    #[error("cannot combine costs in {0} with costs in {1}")]
+    IncompatibleCostUnits(CostUnit, CostUnit),
+    #[error("unknown {unit_type} '{value}', expected one of: {valid}")]
+    UnknownUnit {
+        unit_type: String,
+        value: String,
+        valid: String,
+    },
 }
@@ -0,0 +1,40 @@
+use super::UnitError;
+
+/// parses a unit from its name in a config file or query. names are
+/// matched ignoring case and surrounding whitespace, with spaces and dashes
+/// treated as underscores, so "Kilometers Per Hour" matches
+/// "kilometers_per_hour". an unknown name produces an error listing the
+/// accepted names.
+///
+/// # Arguments
+///
+/// * `value` - the unit name to parse
+/// * `unit_type` - the kind of unit, used in the error message
+/// * `names` - the canonical name of each unit
+/// * `aliases` - additional accepted names, such as abbreviations
+///
+/// # Returns
+///
+/// the matching unit, or an error listing the canonical names
+pub(crate) fn parse_unit_name<T: Copy>(
+    value: &str,
+    unit_type: &str,
+    names: &[(&str, T)],
+    aliases: &[(&str, T)],
+) -> Result<T, UnitError> {
+    let normalized = value.trim().to_lowercase().replace([' ', '-'], "_");
+    names
+        .iter()
+        .chain(aliases.iter())
+        .find(|(name, _)| *name == normalized)
+        .map(|(_, unit)| *unit)
+        .ok_or_else(|| UnitError::UnknownUnit {
+            unit_type: String::from(unit_type),
+            value: String::from(value),
+            valid: names
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+}
@@ -0,0 +1,54 @@
+use crate::{
+    model::{
+        property::{edge::Edge, vertex::Vertex},
+        road_network::{edge_table_layout::EdgeTableLayout, graph::Graph},
+    },
+    util::compact_ordered_hash_map::CompactOrderedHashMap,
+};
+
+/// builds a graph from its vertices and edges. the id of each vertex and
+/// edge must be its position in the list.
+pub fn graph_from_edges(vertices: Vec<Vertex>, edges: Vec<Edge>) -> Graph {
+    let mut adj = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    let mut rev = vec![CompactOrderedHashMap::empty(); vertices.len()];
+    for edge in &edges {
+        adj[edge.src_vertex_id.0].insert(edge.edge_id, edge.dst_vertex_id);
+        rev[edge.dst_vertex_id.0].insert(edge.edge_id, edge.src_vertex_id);
+    }
+    let edge_table_layout = EdgeTableLayout {
+        n_rows: edges.len(),
+        ..Default::default()
+    };
+    Graph {
+        adj: adj.into_boxed_slice().into(),
+        rev: rev.into_boxed_slice().into(),
+        edges: edges.into_boxed_slice(),
+        vertices: vertices.into_boxed_slice(),
+        vertex_pair_index: None,
+        edge_table_layout,
+    }
+}
+
+/// builds a graph of `n_vertices` vertices placed at the origin, with one
+/// edge per (src, dst, distance) link whose id is its position in `links`.
+/// with every vertex at the same place, straight-line estimates are zero.
+pub fn graph_from_links(n_vertices: usize, links: &[(usize, usize, f64)]) -> Graph {
+    let vertices = (0..n_vertices)
+        .map(|i| Vertex::new(i, 0.0, 0.0))
+        .collect::<Vec<_>>();
+    let edges = links
+        .iter()
+        .enumerate()
+        .map(|(idx, (src, dst, distance))| Edge::new(idx, *src, *dst, *distance))
+        .collect::<Vec<_>>();
+    graph_from_edges(vertices, edges)
+}
+
+/// a copy of each link running in the opposite direction is placed directly
+/// after it, so link `i` becomes edges `2 * i` and `2 * i + 1`
+pub fn two_way_links(links: &[(usize, usize, f64)]) -> Vec<(usize, usize, f64)> {
+    links
+        .iter()
+        .flat_map(|(a, b, d)| [(*a, *b, *d), (*b, *a, *d)])
+        .collect()
+}
@@ -0,0 +1,5 @@
+//! fixtures shared by the unit tests of this crate and of the crates that
+//! depend on it, which enable the `test-utils` feature to use them.
+
+pub mod graph_fixture;
+pub mod search_instance_builder;
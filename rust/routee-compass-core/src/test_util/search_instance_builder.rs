@@ -0,0 +1,191 @@
+use crate::{
+    algorithm::search::{
+        cost_estimate_function::{CostEstimateFunction, TraversalModelCostEstimate},
+        search_instance::SearchInstance,
+    },
+    model::{
+        access::{access_model::AccessModel, default::no_access_model::NoAccessModel},
+        cost::{
+            cost_aggregation::CostAggregation, cost_model::CostModel,
+            vehicle::vehicle_cost_rate::VehicleCostRate,
+        },
+        frontier::{default::no_restriction::NoRestriction, frontier_model::FrontierModel},
+        road_network::graph::Graph,
+        state::{state_feature::StateFeature, state_model::StateModel},
+        termination::termination_model::TerminationModel,
+        traversal::{
+            default::distance_traversal_model::DistanceTraversalModel,
+            traversal_model::TraversalModel,
+        },
+        unit::{Cost, Distance, DistanceUnit},
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+/// builds a [`SearchInstance`] for tests. by default the search minimizes
+/// distance in meters with no access costs, no frontier restrictions and a
+/// limit of 1000 iterations, and each model can be replaced.
+pub struct SearchInstanceBuilder {
+    graph: Arc<Graph>,
+    state_model: Option<Arc<StateModel>>,
+    cost_model: Option<CostModel>,
+    traversal_model: Arc<dyn TraversalModel>,
+    access_model: Arc<dyn AccessModel>,
+    frontier_model: Arc<dyn FrontierModel>,
+    termination_model: TerminationModel,
+    max_edges: Option<usize>,
+    max_cost: Option<Cost>,
+    prefer_fewer_edges: bool,
+    cancellation: Option<Arc<AtomicBool>>,
+    cost_estimate: Arc<dyn CostEstimateFunction>,
+}
+
+impl SearchInstanceBuilder {
+    pub fn new(graph: impl Into<Arc<Graph>>) -> SearchInstanceBuilder {
+        SearchInstanceBuilder {
+            graph: graph.into(),
+            state_model: None,
+            cost_model: None,
+            traversal_model: Arc::new(DistanceTraversalModel::new(DistanceUnit::Meters)),
+            access_model: Arc::new(NoAccessModel {}),
+            frontier_model: Arc::new(NoRestriction {}),
+            termination_model: TerminationModel::IterationsLimit { limit: 1000 },
+            max_edges: None,
+            max_cost: None,
+            prefer_fewer_edges: false,
+            cancellation: None,
+            cost_estimate: Arc::new(TraversalModelCostEstimate),
+        }
+    }
+
+    /// replaces the state model, which otherwise holds a distance in meters
+    /// along with the state features of the traversal and access models
+    pub fn state_model(mut self, state_model: Arc<StateModel>) -> Self {
+        self.state_model = Some(state_model);
+        self
+    }
+
+    /// replaces the cost model, which otherwise sums the raw distance
+    pub fn cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = Some(cost_model);
+        self
+    }
+
+    pub fn traversal_model(mut self, traversal_model: Arc<dyn TraversalModel>) -> Self {
+        self.traversal_model = traversal_model;
+        self
+    }
+
+    pub fn access_model(mut self, access_model: Arc<dyn AccessModel>) -> Self {
+        self.access_model = access_model;
+        self
+    }
+
+    pub fn frontier_model(mut self, frontier_model: Arc<dyn FrontierModel>) -> Self {
+        self.frontier_model = frontier_model;
+        self
+    }
+
+    pub fn termination_model(mut self, termination_model: TerminationModel) -> Self {
+        self.termination_model = termination_model;
+        self
+    }
+
+    pub fn max_edges(mut self, max_edges: usize) -> Self {
+        self.max_edges = Some(max_edges);
+        self
+    }
+
+    pub fn max_cost(mut self, max_cost: Cost) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    pub fn prefer_fewer_edges(mut self, prefer_fewer_edges: bool) -> Self {
+        self.prefer_fewer_edges = prefer_fewer_edges;
+        self
+    }
+
+    pub fn cancellation(mut self, cancellation: Arc<AtomicBool>) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn cost_estimate(mut self, cost_estimate: Arc<dyn CostEstimateFunction>) -> Self {
+        self.cost_estimate = cost_estimate;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// if the default state or cost model cannot be built from the models
+    pub fn build(self) -> SearchInstance {
+        let state_model = self.state_model.unwrap_or_else(|| {
+            let features = self
+                .traversal_model
+                .state_features()
+                .into_iter()
+                .chain(self.access_model.state_features());
+            Arc::new(distance_state_model().extend(features.collect()).unwrap())
+        });
+        let cost_model = self
+            .cost_model
+            .unwrap_or_else(|| distance_cost_model(state_model.clone()));
+        SearchInstance {
+            directed_graph: self.graph,
+            state_model,
+            traversal_model: self.traversal_model,
+            access_model: self.access_model,
+            cost_model,
+            frontier_model: self.frontier_model,
+            termination_model: Arc::new(self.termination_model),
+            max_edges: self.max_edges,
+            max_cost: self.max_cost,
+            prefer_fewer_edges: self.prefer_fewer_edges,
+            cancellation: self.cancellation,
+            cost_estimate: self.cost_estimate,
+        }
+    }
+}
+
+/// a state model holding only a distance feature in meters
+pub fn distance_state_model() -> Arc<StateModel> {
+    let feature = StateFeature::Distance {
+        distance_unit: DistanceUnit::Meters,
+        initial: Distance::new(0.0),
+    };
+    Arc::new(
+        StateModel::empty()
+            .extend(vec![(String::from("distance"), feature)])
+            .unwrap(),
+    )
+}
+
+/// a cost model which sums the raw value of each of the named features
+///
+/// # Panics
+///
+/// if a feature is missing from the state model
+pub fn raw_cost_model(features: &[&str], state_model: Arc<StateModel>) -> CostModel {
+    let weights = features.iter().map(|f| (f.to_string(), 1.0)).collect();
+    let rates = features
+        .iter()
+        .map(|f| (f.to_string(), VehicleCostRate::Raw))
+        .collect();
+    CostModel::new(
+        Arc::new(weights),
+        Arc::new(rates),
+        Arc::new(HashMap::new()),
+        CostAggregation::Sum,
+        state_model,
+    )
+    .unwrap()
+}
+
+/// a cost model which sums the raw distance
+pub fn distance_cost_model(state_model: Arc<StateModel>) -> CostModel {
+    raw_cost_model(&["distance"], state_model)
+}
@@ -380,7 +380,7 @@ impl<K: Hash + Ord + PartialEq + Clone, V: Clone> CompactOrderedHashMap<K, V> {
                 }
             }
             CompactOrderedHashMap::NEntries(map) => {
-                let index = map.get(&k).map(|e| e.index).unwrap_or(map.len() + 1);
+                let index = map.get(&k).map(|e| e.index).unwrap_or(map.len());
                 let result = map.insert(k, IndexedEntry::new(v, index));
                 result.map(|r| r.v)
             }
@@ -792,4 +792,17 @@ mod test {
         assert_eq!(Some(&v1), insert_2.as_ref());
         assert_eq!(Some(&v2), stored);
     }
+
+    #[test]
+    fn test_indices_stay_dense_past_five_entries() {
+        let mut map: CompactOrderedHashMap<String, usize> = CompactOrderedHashMap::empty();
+        for i in 0..7 {
+            let _ = map.insert(format!("k{}", i), i);
+        }
+        for i in 0..7 {
+            assert_eq!(map.get_index(&format!("k{}", i)), Some(i));
+        }
+        let values = map.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        assert_eq!(values, (0..7).collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,326 @@
+use crate::model::unit::{Time, TimeUnit};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    Weekday,
+};
+use serde::{Deserialize, Serialize};
+
+/// the rule used to move the clocks forward one hour for daylight saving
+/// time. only these two rules are supported, each as currently defined, so
+/// southern hemisphere timezones and departures in years before a rule took
+/// effect cannot be represented. see [`DaylightSavingRule::first_year`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DaylightSavingRule {
+    /// from 02:00 local time on the second Sunday of March until 02:00 local
+    /// time on the first Sunday of November
+    UnitedStates,
+    /// from 01:00 UTC on the last Sunday of March until 01:00 UTC on the
+    /// last Sunday of October
+    EuropeanUnion,
+}
+
+impl DaylightSavingRule {
+    /// the first year the rule was in effect. a departure in an earlier
+    /// year followed other rules and is rejected.
+    pub fn first_year(&self) -> i32 {
+        match self {
+            DaylightSavingRule::UnitedStates => 2007,
+            DaylightSavingRule::EuropeanUnion => 1996,
+        }
+    }
+}
+
+/// the timezone of a road network, used to convert a departure instant into
+/// the local time of day that selects time-dependent speeds and closures.
+/// the timezone is described by its standard offset from UTC and an optional
+/// daylight saving rule, which avoids depending on a timezone database. a
+/// named timezone such as "America/Denver" is rejected, except for "UTC".
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(try_from = "GraphTimezoneConfig")]
+pub struct GraphTimezone {
+    /// offset of local standard time from UTC, in minutes east of UTC
+    pub utc_offset_minutes: i32,
+    /// optional daylight saving rule, which adds one hour while in effect
+    #[serde(default)]
+    pub daylight_saving: Option<DaylightSavingRule>,
+}
+
+/// the ways a timezone may be written in a configuration file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GraphTimezoneConfig {
+    Name(String),
+    Offset {
+        utc_offset_minutes: i32,
+        #[serde(default)]
+        daylight_saving: Option<DaylightSavingRule>,
+    },
+}
+
+impl TryFrom<GraphTimezoneConfig> for GraphTimezone {
+    type Error = String;
+
+    fn try_from(config: GraphTimezoneConfig) -> Result<Self, Self::Error> {
+        match config {
+            GraphTimezoneConfig::Name(name) if name.eq_ignore_ascii_case("utc") => {
+                Ok(GraphTimezone::default())
+            }
+            GraphTimezoneConfig::Name(name) => Err(format!(
+                "named timezone '{}' is not supported, expected {{ utc_offset_minutes, daylight_saving }} with a daylight_saving rule of 'united_states' or 'european_union'",
+                name
+            )),
+            GraphTimezoneConfig::Offset {
+                utc_offset_minutes,
+                daylight_saving,
+            } => {
+                if utc_offset_minutes.abs() > 14 * 60 {
+                    return Err(format!(
+                        "utc_offset_minutes {} is outside of the range -840 to 840",
+                        utc_offset_minutes
+                    ));
+                }
+                Ok(GraphTimezone {
+                    utc_offset_minutes,
+                    daylight_saving,
+                })
+            }
+        }
+    }
+}
+
+impl GraphTimezone {
+    /// the offset of local time from UTC at some instant, including any
+    /// daylight saving time in effect
+    pub fn offset_at(&self, instant: DateTime<Utc>) -> Duration {
+        let standard = Duration::minutes(self.utc_offset_minutes as i64);
+        let rule = match self.daylight_saving {
+            None => return standard,
+            Some(rule) => rule,
+        };
+        let year = (instant + standard).year();
+        let (start, end) = daylight_saving_interval(rule, year, standard);
+        if start <= instant && instant < end {
+            standard + Duration::hours(1)
+        } else {
+            standard
+        }
+    }
+
+    /// converts an instant to the local time of the road network
+    pub fn to_local(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        instant.naive_utc() + self.offset_at(instant)
+    }
+
+    /// converts a local time of the road network to an instant. around a
+    /// daylight saving transition, a local time may occur twice or not at
+    /// all. a repeated local time, when the clocks fall back, resolves to
+    /// its first occurrence, which is in daylight time. a skipped local
+    /// time, when the clocks spring forward, is read in standard time, so
+    /// that 02:30 on the morning the clocks move from 02:00 to 03:00 is
+    /// treated as 03:30 daylight time.
+    ///
+    /// # Arguments
+    ///
+    /// * `local` - a local date and time
+    ///
+    /// # Returns
+    ///
+    /// the instant of that local time
+    pub fn from_local(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let standard = Duration::minutes(self.utc_offset_minutes as i64);
+        let daylight = standard + Duration::hours(1);
+        let as_standard = Utc.from_utc_datetime(&(local - standard));
+        let as_daylight = Utc.from_utc_datetime(&(local - daylight));
+        if self.daylight_saving.is_some() && self.offset_at(as_daylight) == daylight {
+            as_daylight
+        } else {
+            as_standard
+        }
+    }
+}
+
+/// a query departure time, stored as an instant along with the timezone of
+/// the road network so that it can be converted to local time of day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DepartureTime {
+    pub instant: DateTime<Utc>,
+    pub timezone: GraphTimezone,
+}
+
+impl DepartureTime {
+    /// reads a departure time from a query. a timestamp with an offset, such
+    /// as `2024-03-10T09:30:00Z` or `2024-03-10T09:30:00-07:00`, names an
+    /// instant. a timestamp without an offset, such as `2024-03-10T09:30:00`,
+    /// is a local time of the road network, resolved as in
+    /// [`GraphTimezone::from_local`].
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the departure timestamp
+    /// * `timezone` - the timezone of the road network
+    ///
+    /// # Returns
+    ///
+    /// the departure time, or an error if the timestamp cannot be parsed or
+    /// falls in a year before the daylight saving rule of the timezone
+    pub fn parse(value: &str, timezone: GraphTimezone) -> Result<DepartureTime, String> {
+        let instant = match DateTime::parse_from_rfc3339(value) {
+            Ok(instant) => instant.with_timezone(&Utc),
+            Err(_) => {
+                let local =
+                    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").map_err(|e| {
+                        format!(
+                            "expected departure_time to be an RFC 3339 timestamp, found '{}': {}",
+                            value, e
+                        )
+                    })?;
+                timezone.from_local(local)
+            }
+        };
+        let departure = DepartureTime { instant, timezone };
+        if let Some(rule) = timezone.daylight_saving {
+            let year = departure.local_time().year();
+            if year < rule.first_year() {
+                return Err(format!(
+                    "departure_time '{}' is in {}, before the {:?} daylight saving rule took effect in {}",
+                    value,
+                    year,
+                    rule,
+                    rule.first_year()
+                ));
+            }
+        }
+        Ok(departure)
+    }
+
+    /// the local date and time of departure
+    pub fn local_time(&self) -> NaiveDateTime {
+        self.timezone.to_local(self.instant)
+    }
+
+    /// the local time elapsed since midnight at departure, which is the
+    /// clock time used to select time of day buckets
+    pub fn time_of_day(&self, time_unit: &TimeUnit) -> Time {
+        let seconds = self.local_time().num_seconds_from_midnight() as f64;
+        TimeUnit::Seconds.convert(&Time::new(seconds), time_unit)
+    }
+}
+
+/// the instants that daylight saving time begins and ends in some year
+fn daylight_saving_interval(
+    rule: DaylightSavingRule,
+    year: i32,
+    standard: Duration,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let at = |date: NaiveDate, hour: u32| {
+        let time = NaiveTime::from_hms_opt(hour, 0, 0).unwrap_or_default();
+        Utc.from_utc_datetime(&date.and_time(time))
+    };
+    match rule {
+        DaylightSavingRule::UnitedStates => {
+            // 02:00 standard time, and 02:00 daylight time which is 01:00 standard time
+            let start = at(nth_sunday(year, 3, 2), 2) - standard;
+            let end = at(nth_sunday(year, 11, 1), 1) - standard;
+            (start, end)
+        }
+        DaylightSavingRule::EuropeanUnion => {
+            let start = at(last_sunday(year, 3), 1);
+            let end = at(last_sunday(year, 10), 1);
+            (start, end)
+        }
+    }
+}
+
+/// the nth Sunday of a month, counting from 1
+fn nth_sunday(year: i32, month: u32, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n).unwrap_or_default()
+}
+
+/// the last Sunday of a month
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, 5)
+        .unwrap_or_else(|| nth_sunday(year, month, 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::unit::as_f64::AsF64;
+
+    /// mountain time, seven hours behind UTC in winter and six in summer
+    fn denver() -> GraphTimezone {
+        GraphTimezone {
+            utc_offset_minutes: -7 * 60,
+            daylight_saving: Some(DaylightSavingRule::UnitedStates),
+        }
+    }
+
+    fn local_hour(value: &str, timezone: GraphTimezone) -> f64 {
+        let departure = DepartureTime::parse(value, timezone).unwrap();
+        departure.time_of_day(&TimeUnit::Hours).as_f64().floor()
+    }
+
+    #[test]
+    fn test_local_hour_across_dst_boundary() {
+        // clocks spring forward at 02:00 MST on 2024-03-10, which is 09:00 UTC.
+        // an hour before the change is 01:30 local, an hour after is 03:30 local
+        assert_eq!(local_hour("2024-03-10T08:30:00Z", denver()), 1.0);
+        assert_eq!(local_hour("2024-03-10T09:30:00Z", denver()), 3.0);
+        // the same UTC time of day lands in different local hours across the season
+        assert_eq!(local_hour("2024-01-15T15:00:00Z", denver()), 8.0);
+        assert_eq!(local_hour("2024-07-15T15:00:00Z", denver()), 9.0);
+        // without daylight saving the offset never changes
+        let arizona = GraphTimezone {
+            utc_offset_minutes: -7 * 60,
+            daylight_saving: None,
+        };
+        assert_eq!(local_hour("2024-07-15T15:00:00Z", arizona), 8.0);
+    }
+
+    #[test]
+    fn test_ambiguous_and_skipped_local_times() {
+        // 02:30 does not exist on 2024-03-10 and is read in standard time
+        let skipped = DepartureTime::parse("2024-03-10T02:30:00", denver()).unwrap();
+        assert_eq!(skipped.local_time().hour(), 3);
+        // 01:30 occurs twice on 2024-11-03 and resolves to the first, in daylight time
+        let repeated = DepartureTime::parse("2024-11-03T01:30:00", denver()).unwrap();
+        assert_eq!(repeated.instant.to_rfc3339(), "2024-11-03T07:30:00+00:00");
+        assert_eq!(repeated.local_time().hour(), 1);
+        // an hour later in UTC is the second 01:30, in standard time
+        let later = DepartureTime::parse("2024-11-03T08:30:00Z", denver()).unwrap();
+        assert_eq!(later.local_time().hour(), 1);
+    }
+
+    #[test]
+    fn test_european_rule_changes_at_utc() {
+        let berlin = GraphTimezone {
+            utc_offset_minutes: 60,
+            daylight_saving: Some(DaylightSavingRule::EuropeanUnion),
+        };
+        // the last Sunday of March 2024 is the 31st, changing at 01:00 UTC
+        assert_eq!(local_hour("2024-03-31T00:30:00Z", berlin), 1.0);
+        assert_eq!(local_hour("2024-03-31T01:30:00Z", berlin), 3.0);
+        assert!(DepartureTime::parse("half past nine", berlin).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_timezones_rejected() {
+        // the current rules did not apply before they took effect
+        assert!(DepartureTime::parse("2006-07-15T15:00:00Z", denver()).is_err());
+        assert!(DepartureTime::parse("2007-07-15T15:00:00Z", denver()).is_ok());
+        // named timezones need a timezone database, except for UTC
+        let named = serde_json::from_value::<GraphTimezone>(serde_json::json!("America/Denver"));
+        assert!(named.is_err());
+        let utc = serde_json::from_value::<GraphTimezone>(serde_json::json!("UTC")).unwrap();
+        assert_eq!(utc, GraphTimezone::default());
+        let offset =
+            serde_json::json!({ "utc_offset_minutes": -420, "daylight_saving": "united_states" });
+        assert_eq!(
+            serde_json::from_value::<GraphTimezone>(offset).unwrap(),
+            denver()
+        );
+        let out_of_range = serde_json::json!({ "utc_offset_minutes": 1500 });
+        assert!(serde_json::from_value::<GraphTimezone>(out_of_range).is_err());
+    }
+}
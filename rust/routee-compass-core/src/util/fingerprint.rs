@@ -0,0 +1,24 @@
+/// a stable FNV-1a hash of a sequence of values, used to detect when a
+/// preprocessing cache file no longer matches the graph or edge costs it was
+/// built from. unlike the standard library hasher, the result does not change
+/// between runs or Rust versions, so it may be written to disk.
+///
+/// # Arguments
+///
+/// * `values` - the values to hash, such as lengths and the bits of floats
+///
+/// # Returns
+///
+/// the fingerprint of the values
+pub fn fingerprint(values: impl IntoIterator<Item = u64>) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut hash = FNV_OFFSET;
+    for value in values {
+        for byte in value.to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
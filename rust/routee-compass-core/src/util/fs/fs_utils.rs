@@ -1,45 +1,57 @@
 use std::{
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, Read},
     path::Path,
 };
 
 use flate2::read::GzDecoder;
 
-/// The output is wrapped in a Result to allow matching on errors
-/// Returns an Iterator to the Reader of the lines of the file.
-/// based on https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
+/// the first two bytes of every gzip stream
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+/// opens a file for buffered reading, transparently decoding it if it is
+/// gzip-compressed. compression is detected from the gzip magic bytes at the
+/// start of the file rather than the file extension, so a gzipped file
+/// without a `.gz` suffix, or a plain file with one, is still read correctly.
+///
+/// # Arguments
+///
+/// * `filepath` - file to open
 ///
-pub fn line_count<P>(filename: P, is_gzip: bool) -> std::io::Result<usize>
+/// # Returns
+///
+/// a buffered reader over the decoded file contents, or an io error
+pub fn open_file<P>(filepath: P) -> io::Result<Box<dyn BufRead>>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-
-    if is_gzip {
-        let reader = BufReader::new(GzDecoder::new(file));
-        Ok(reader.lines().count())
+    let mut reader = BufReader::new(File::open(filepath)?);
+    let gzipped = reader.fill_buf()?.starts_with(&GZIP_MAGIC_BYTES);
+    if gzipped {
+        Ok(Box::new(BufReader::new(GzDecoder::new(reader))))
     } else {
-        let reader = BufReader::new(file);
-        let count = reader.lines().count();
-        Ok(count)
+        Ok(Box::new(reader))
     }
 }
 
-/// attempts to read a gzip header from the file. if it is found,
-/// then returns true. some inefficiency here due to throwing out the
-/// stream object that could have been used later, but in typical Compass
-/// settings, this isn't a real bottleneck.
+/// counts the lines of a file, decoding it first if it is gzip-compressed.
+pub fn line_count<P>(filename: P) -> std::io::Result<usize>
+where
+    P: AsRef<Path>,
+{
+    let reader = open_file(filename)?;
+    Ok(reader.lines().count())
+}
+
+/// tests whether a file begins with the gzip magic bytes. returns false
+/// if the file cannot be read.
 pub fn is_gzip<P>(filepath: P) -> bool
 where
     P: AsRef<Path>,
 {
-    let file_result = File::open(filepath);
-    match file_result {
-        Err(_) => false,
-        Ok(file) => {
-            let gz = GzDecoder::new(io::BufReader::new(file));
-            gz.header().is_some()
-        }
-    }
+    let mut magic = [0u8; 2];
+    File::open(filepath)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .map(|_| magic == GZIP_MAGIC_BYTES)
+        .unwrap_or(false)
 }
@@ -14,6 +14,18 @@ pub fn string(_idx: usize, row: String) -> Result<String, std::io::Error> {
     Ok(row)
 }
 
+/// decodes a flag written as `true`/`false` or `1`/`0`
+pub fn bool(_idx: usize, row: String) -> Result<bool, std::io::Error> {
+    match row.trim() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        other => {
+            let msg = format!("failure decoding row {} as a true/false flag", other);
+            Err(std::io::Error::new(std::io::ErrorKind::InvalidData, msg))
+        }
+    }
+}
+
 pub fn u8(_idx: usize, row: String) -> Result<u8, std::io::Error> {
     row.parse::<u8>().map_err(|e| {
         let msg = format!("failure decoding row {} due to: {:}", row, e);
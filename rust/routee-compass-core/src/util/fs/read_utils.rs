@@ -1,10 +1,8 @@
 use super::fs_utils;
 use csv::ReaderBuilder;
-use flate2::read::GzDecoder;
 
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead},
     path::Path,
 };
 
@@ -23,12 +21,7 @@ where
     F: AsRef<Path>,
     T: serde::de::DeserializeOwned + 'a,
 {
-    let f = File::open(filepath.as_ref())?;
-    let r: Box<dyn io::Read> = if fs_utils::is_gzip(filepath) {
-        Box::new(BufReader::new(GzDecoder::new(f)))
-    } else {
-        Box::new(f)
-    };
+    let r = fs_utils::open_file(filepath)?;
     let reader = ReaderBuilder::new()
         .has_headers(has_headers)
         .trim(csv::Trim::Fields)
@@ -71,21 +64,6 @@ where
 /// the row index (starting from zero) is passed to the deserialization op
 /// as in most cases, the row number is an id.
 pub fn read_raw_file<'a, F: AsRef<Path>, T>(
-    filepath: F,
-    op: impl Fn(usize, String) -> Result<T, io::Error>,
-    row_callback: Option<Box<dyn FnMut() + 'a>>,
-) -> Result<Box<[T]>, io::Error>
-where
-    F: AsRef<Path>,
-{
-    if fs_utils::is_gzip(filepath.as_ref()) {
-        Ok(read_gzip(filepath, op, row_callback)?)
-    } else {
-        Ok(read_regular(filepath, op, row_callback)?)
-    }
-}
-
-fn read_regular<'a, F, T>(
     filepath: F,
     op: impl Fn(usize, String) -> Result<T, io::Error>,
     mut row_callback: Option<Box<dyn FnMut() + 'a>>,
@@ -93,8 +71,7 @@ fn read_regular<'a, F, T>(
 where
     F: AsRef<Path>,
 {
-    let file = File::open(filepath)?;
-    let reader = BufReader::new(file);
+    let reader = fs_utils::open_file(filepath)?;
     let result: Result<Box<[T]>, std::io::Error> = reader
         .lines()
         .enumerate()
@@ -110,28 +87,6 @@ where
     result
 }
 
-fn read_gzip<'a, F, T>(
-    filepath: F,
-    op: impl Fn(usize, String) -> Result<T, io::Error>,
-    mut row_callback: Option<Box<dyn FnMut() + 'a>>,
-) -> Result<Box<[T]>, io::Error>
-where
-    F: AsRef<Path>,
-{
-    let file = File::open(filepath)?;
-    let mut result = vec![];
-    let reader = BufReader::new(GzDecoder::new(file));
-    for (idx, row) in reader.lines().enumerate() {
-        let parsed = row?;
-        let deserialized = op(idx, parsed)?;
-        if let Some(cb) = &mut row_callback {
-            cb();
-        }
-        result.push(deserialized);
-    }
-    Ok(result.into_boxed_slice())
-}
-
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -6,6 +6,52 @@ use geo::{Coord, CoordNum};
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 pub struct InternalCoord<T: CoordNum>(pub Coord<T>);
 
+impl<T: CoordNum + Into<f64>> InternalCoord<T> {
+    /// creates a coordinate from a longitude and latitude, confirming that
+    /// both fall within valid WGS84 ranges. this catches coordinates with
+    /// swapped x and y values, which most often appear as a latitude
+    /// outside of [-90, 90].
+    ///
+    /// # Arguments
+    ///
+    /// * `lon` - longitude (x) in degrees, within [-180, 180]
+    /// * `lat` - latitude (y) in degrees, within [-90, 90]
+    ///
+    /// # Returns
+    ///
+    /// the coordinate, or an error if either value is out of range
+    pub fn new(lon: T, lat: T) -> Result<InternalCoord<T>, String> {
+        let (lon_f64, lat_f64): (f64, f64) = (lon.into(), lat.into());
+        if !(-180.0..=180.0).contains(&lon_f64) {
+            return Err(format!(
+                "longitude {} is outside of [-180, 180], is the coordinate ({}, {}) in (lon, lat) order?",
+                lon_f64, lon_f64, lat_f64
+            ));
+        }
+        if !(-90.0..=90.0).contains(&lat_f64) {
+            return Err(format!(
+                "latitude {} is outside of [-90, 90], is the coordinate ({}, {}) in (lon, lat) order?",
+                lat_f64, lon_f64, lat_f64
+            ));
+        }
+        Ok(InternalCoord(Coord { x: lon, y: lat }))
+    }
+}
+
+/// builds a coordinate from an (x, y) tuple without any range validation,
+/// matching the behavior of `geo::Coord::from`.
+impl<T: CoordNum> From<(T, T)> for InternalCoord<T> {
+    fn from(xy: (T, T)) -> Self {
+        InternalCoord(Coord::from(xy))
+    }
+}
+
+impl<T: CoordNum> From<Coord<T>> for InternalCoord<T> {
+    fn from(coord: Coord<T>) -> Self {
+        InternalCoord(coord)
+    }
+}
+
 impl<T: CoordNum> Allocative for InternalCoord<T> {
     fn visit<'a, 'b: 'a>(&self, visitor: &'a mut allocative::Visitor<'b>) {
         visitor.enter_self_sized::<Self>().exit();
@@ -39,4 +85,18 @@ mod tests {
         // should only have two f64s at 8 bytes each
         assert_eq!(memory_bytes, 16);
     }
+
+    #[test]
+    fn test_new_validates_ranges() {
+        let golden = InternalCoord::new(-105.22, 39.75).unwrap();
+        assert_eq!(golden, InternalCoord(coord! {x: -105.22, y: 39.75}));
+        assert_eq!(InternalCoord::from((-105.22, 39.75)), golden);
+
+        // swapped lat/lon places the longitude in the latitude position
+        let swapped = InternalCoord::new(39.75, -105.22);
+        assert!(swapped.unwrap_err().contains("latitude -105.22"));
+        assert!(InternalCoord::new(180.5_f32, 0.0).is_err());
+        assert!(InternalCoord::new(f64::NAN, 0.0).is_err());
+        assert!(InternalCoord::new(-180.0, -90.0).is_ok());
+    }
 }
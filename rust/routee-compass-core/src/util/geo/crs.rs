@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// WGS84 ellipsoid semi-major axis, in meters
+const WGS84_A: f64 = 6_378_137.0;
+/// WGS84 ellipsoid flattening
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+/// UTM scale factor on the central meridian
+const UTM_K0: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// coordinate reference system of a coordinate file, declared with an EPSG
+/// code such as `"EPSG:4326"`. the graph stores coordinates in WGS84, since
+/// the haversine distance math assumes longitude and latitude degrees, so
+/// coordinates in a projected system are reprojected on load.
+///
+/// supported systems:
+///   - `EPSG:4326` - WGS84 longitude/latitude, the default, which is not transformed
+///   - `EPSG:326zz` - WGS84 UTM zone zz north, in meters
+///   - `EPSG:327zz` - WGS84 UTM zone zz south, in meters
+///   - `planar` - coordinates in meters on a plane, for synthetic or indoor graphs
+///     that are not on the earth. these are neither transformed nor range checked,
+///     and pair with the euclidean distance function
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Crs {
+    #[default]
+    Wgs84,
+    Utm {
+        zone: u8,
+        north: bool,
+    },
+    Planar,
+}
+
+impl Crs {
+    /// reprojects a coordinate in this system to WGS84.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - easting, or longitude for WGS84
+    /// * `y` - northing, or latitude for WGS84
+    ///
+    /// # Returns
+    ///
+    /// the (longitude, latitude) of the coordinate in degrees. planar
+    /// coordinates have no position on the earth and are returned as is
+    pub fn to_wgs84(&self, x: f64, y: f64) -> (f64, f64) {
+        match self {
+            Crs::Wgs84 | Crs::Planar => (x, y),
+            Crs::Utm { zone, north } => utm_to_wgs84(x, y, *zone, *north),
+        }
+    }
+}
+
+/// inverse transverse mercator projection using the third-order series of
+/// Krüger, as given by Karney (2011), which is accurate to well under a
+/// millimeter within a UTM zone.
+fn utm_to_wgs84(easting: f64, northing: f64, zone: u8, north: bool) -> (f64, f64) {
+    let n = WGS84_F / (2.0 - WGS84_F);
+    let (n2, n3) = (n * n, n * n * n);
+    let a = WGS84_A / (1.0 + n) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0);
+    let beta = [
+        n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0,
+        n2 / 48.0 + n3 / 15.0,
+        17.0 * n3 / 480.0,
+    ];
+    let delta = [
+        2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3,
+        7.0 * n2 / 3.0 - 8.0 * n3 / 5.0,
+        56.0 * n3 / 15.0,
+    ];
+
+    let false_northing = if north { 0.0 } else { UTM_FALSE_NORTHING_SOUTH };
+    let xi = (northing - false_northing) / (UTM_K0 * a);
+    let eta = (easting - UTM_FALSE_EASTING) / (UTM_K0 * a);
+
+    let (mut xi_prime, mut eta_prime) = (xi, eta);
+    for (j, b) in beta.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi_prime -= b * (k * xi).sin() * (k * eta).cosh();
+        eta_prime -= b * (k * xi).cos() * (k * eta).sinh();
+    }
+    let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+    let mut lat = chi;
+    for (j, d) in delta.iter().enumerate() {
+        lat += d * (2.0 * (j + 1) as f64 * chi).sin();
+    }
+    let central_meridian = zone as f64 * 6.0 - 183.0;
+    let lon = central_meridian + eta_prime.sinh().atan2(xi_prime.cos()).to_degrees();
+    (lon, lat.to_degrees())
+}
+
+impl FromStr for Crs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unsupported = || {
+            format!(
+                "unsupported CRS '{}', must be one of EPSG:4326 (WGS84), EPSG:326zz (UTM zone zz north), EPSG:327zz (UTM zone zz south) or planar",
+                s
+            )
+        };
+        if s.trim().eq_ignore_ascii_case("planar") {
+            return Ok(Crs::Planar);
+        }
+        let code = s
+            .trim()
+            .to_uppercase()
+            .strip_prefix("EPSG:")
+            .ok_or_else(unsupported)?
+            .parse::<u32>()
+            .map_err(|_| unsupported())?;
+        match code {
+            4326 => Ok(Crs::Wgs84),
+            32601..=32660 => Ok(Crs::Utm {
+                zone: (code - 32600) as u8,
+                north: true,
+            }),
+            32701..=32760 => Ok(Crs::Utm {
+                zone: (code - 32700) as u8,
+                north: false,
+            }),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+impl TryFrom<String> for Crs {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Crs::from_str(&value)
+    }
+}
+
+impl From<Crs> for String {
+    fn from(value: Crs) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for Crs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Crs::Wgs84 => write!(f, "EPSG:4326"),
+            Crs::Utm { zone, north: true } => write!(f, "EPSG:326{:02}", zone),
+            Crs::Utm { zone, north: false } => write!(f, "EPSG:327{:02}", zone),
+            Crs::Planar => write!(f, "planar"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utm_to_wgs84() {
+        // 40°N, 105°W lies on the central meridian of UTM zone 13
+        let crs = Crs::from_str("EPSG:32613").unwrap();
+        let (lon, lat) = crs.to_wgs84(500_000.0, 4_427_757.22);
+        assert!((lon - -105.0).abs() < 1e-6, "{}", lon);
+        assert!((lat - 40.0).abs() < 1e-6, "{}", lat);
+
+        // the same distance south of the equator in the southern zone
+        let crs = Crs::from_str("EPSG:32713").unwrap();
+        let (lon, lat) = crs.to_wgs84(500_000.0, 10_000_000.0 - 4_427_757.22);
+        assert!((lon - -105.0).abs() < 1e-6, "{}", lon);
+        assert!((lat - -40.0).abs() < 1e-6, "{}", lat);
+        assert_eq!(crs.to_string(), "EPSG:32713");
+    }
+
+    #[test]
+    fn test_unsupported_crs() {
+        assert_eq!(Crs::from_str("epsg:4326"), Ok(Crs::Wgs84));
+        assert_eq!(Crs::from_str("Planar"), Ok(Crs::Planar));
+        assert!(Crs::from_str("EPSG:3857").is_err());
+        assert!(Crs::from_str("EPSG:32661").is_err());
+        assert!(Crs::from_str("UTM13").is_err());
+    }
+}
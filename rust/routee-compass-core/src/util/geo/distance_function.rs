@@ -0,0 +1,113 @@
+use super::haversine;
+use crate::model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT};
+use geo::Coord;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// computes the straight-line distance between two vertex coordinates, which
+/// models use to estimate the remaining cost of a search.
+pub trait DistanceFunction: Send + Sync {
+    /// distance between two coordinates in the requested distance unit
+    fn distance(
+        &self,
+        src: &Coord<f32>,
+        dst: &Coord<f32>,
+        distance_unit: DistanceUnit,
+    ) -> Result<Distance, String>;
+}
+
+/// great circle distance between WGS84 longitude/latitude coordinates
+#[derive(Default)]
+pub struct HaversineDistance {
+    /// radius of the sphere in meters, defaults to the mean radius of the earth
+    pub radius_meters: Option<f32>,
+}
+
+impl DistanceFunction for HaversineDistance {
+    fn distance(
+        &self,
+        src: &Coord<f32>,
+        dst: &Coord<f32>,
+        distance_unit: DistanceUnit,
+    ) -> Result<Distance, String> {
+        haversine::coord_distance_with_radius(src, dst, distance_unit, self.radius_meters)
+    }
+}
+
+/// planar distance between coordinates in the base distance unit (meters),
+/// for synthetic or indoor graphs that are not on the surface of the earth
+pub struct EuclideanDistance;
+
+impl DistanceFunction for EuclideanDistance {
+    fn distance(
+        &self,
+        src: &Coord<f32>,
+        dst: &Coord<f32>,
+        distance_unit: DistanceUnit,
+    ) -> Result<Distance, String> {
+        let dx = dst.x as f64 - src.x as f64;
+        let dy = dst.y as f64 - src.y as f64;
+        let distance = Distance::new(dx.hypot(dy));
+        Ok(BASE_DISTANCE_UNIT.convert(&distance, &distance_unit))
+    }
+}
+
+/// configuration name of a [`DistanceFunction`], which defaults to haversine.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceFunctionType {
+    #[default]
+    Haversine,
+    Euclidean,
+}
+
+impl DistanceFunctionType {
+    pub fn build(&self) -> Arc<dyn DistanceFunction> {
+        self.build_with_haversine_radius(None)
+    }
+
+    /// builds the distance function, using a sphere of the given radius in
+    /// meters for haversine distances. the radius is ignored by other types.
+    pub fn build_with_haversine_radius(
+        &self,
+        radius_meters: Option<f32>,
+    ) -> Arc<dyn DistanceFunction> {
+        match self {
+            DistanceFunctionType::Haversine => Arc::new(HaversineDistance { radius_meters }),
+            DistanceFunctionType::Euclidean => Arc::new(EuclideanDistance),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::unit::as_f64::AsF64;
+    use geo::coord;
+
+    #[test]
+    fn test_euclidean_unit_square() {
+        let f = DistanceFunctionType::Euclidean.build();
+        let corners = [
+            coord! {x: 0.0, y: 0.0},
+            coord! {x: 1.0, y: 0.0},
+            coord! {x: 1.0, y: 1.0},
+            coord! {x: 0.0, y: 1.0},
+        ];
+        let d = |a: usize, b: usize| {
+            f.distance(&corners[a], &corners[b], DistanceUnit::Meters)
+                .unwrap()
+                .as_f64()
+        };
+        assert_eq!(d(0, 1), 1.0);
+        assert_eq!(d(1, 2), 1.0);
+        assert_eq!(d(0, 3), 1.0);
+        assert_eq!(d(0, 2), 2f64.sqrt());
+        assert_eq!(d(1, 3), 2f64.sqrt());
+        assert_eq!(d(2, 2), 0.0);
+        let km = f
+            .distance(&corners[0], &corners[1], DistanceUnit::Kilometers)
+            .unwrap();
+        assert_eq!(km.as_f64(), 0.001);
+    }
+}
@@ -1,27 +1,34 @@
-use crate::util::fs::{fs_utils, read_utils};
+use crate::util::{
+    fs::{fs_utils, read_utils},
+    progress::{LoadProgress, ProgressCallback},
+};
 use geo::{LineString, Point};
-use kdam::{Bar, BarExt};
 use std::path::Path;
 use wkt::TryFromWkt;
 
 /// reads a collection of LINESTRINGS
+///
+/// # Arguments
+///
+/// * `file` - a file with one WKT LINESTRING per row
+/// * `progress` - optional callback receiving the number of rows read, see [`LoadProgress`]
+/// * `verbose` - whether to draw a progress bar when no callback is provided
+///
+/// # Returns
+///
+/// the linestrings in file order, or an error if the file could not be read
 pub fn read_linestring_text_file<F: AsRef<Path>>(
     file: F,
+    progress: Option<ProgressCallback>,
+    verbose: bool,
 ) -> Result<Box<[LineString<f32>]>, std::io::Error> {
     let filepath: &Path = file.as_ref();
-    let is_gzip = fs_utils::is_gzip(filepath);
-    let count = fs_utils::line_count(filepath, is_gzip)?;
+    let count = fs_utils::line_count(filepath)?;
 
-    let mut pb = Bar::builder()
-        .total(count)
-        .animation("fillup")
-        .desc("geometry file")
-        .build()
+    let mut pb = LoadProgress::new(progress, verbose, "geometry file", count, 0)
         .map_err(|s| std::io::Error::new(std::io::ErrorKind::Interrupted, s.as_str()))?;
 
-    let cb = Box::new(|| {
-        let _ = pb.update(1);
-    });
+    let cb = Box::new(|| pb.update());
     let geoms: Box<[LineString<f32>]> =
         read_utils::read_raw_file(filepath, parse_linestring, Some(cb))?;
     Ok(geoms)
@@ -1,5 +1,6 @@
+use crate::model::unit::as_f64::AsF64;
 use crate::model::unit::{Distance, DistanceUnit};
-use geo::Coord;
+use geo::{Coord, LineString};
 // pub const APPROX_EARTH_RADIUS_KM: f64 = 6372.8;
 pub const APPROX_EARTH_RADIUS_M: f32 = 6_371_000.0;
 
@@ -19,7 +20,28 @@ pub fn coord_distance(
     dst: &Coord<f32>,
     distance_unit: DistanceUnit,
 ) -> Result<Distance, String> {
-    let distance_meters = haversine_distance_meters(src.x, src.y, dst.x, dst.y)?;
+    coord_distance_with_radius(src, dst, distance_unit, None)
+}
+
+/// get the distance between two coordinates on a sphere of the given radius
+/// and return the value in the requested distance unit.
+/// coordinates are assumed to be longitude/latitude in degrees.
+///
+/// # Arguments
+///
+/// * `src` - the first coordinate
+/// * `dst` - the second coordinate
+/// * `distance_unit` - unit of the result
+/// * `radius_meters` - radius of the sphere, defaults to [`APPROX_EARTH_RADIUS_M`]
+pub fn coord_distance_with_radius(
+    src: &Coord<f32>,
+    dst: &Coord<f32>,
+    distance_unit: DistanceUnit,
+    radius_meters: Option<f32>,
+) -> Result<Distance, String> {
+    let radius_meters = radius_meters.unwrap_or(APPROX_EARTH_RADIUS_M);
+    let distance_meters =
+        haversine_distance_with_radius(src.x, src.y, dst.x, dst.y, radius_meters)?;
     Ok(DistanceUnit::Meters.convert(&distance_meters, &distance_unit))
 }
 
@@ -33,6 +55,22 @@ pub fn haversine_distance_meters(
     dst_x: f32,
     dst_y: f32,
 ) -> Result<Distance, String> {
+    haversine_distance_with_radius(src_x, src_y, dst_x, dst_y, APPROX_EARTH_RADIUS_M)
+}
+
+/// haversine distance formula on a sphere of the given radius, such as a
+/// unit sphere in tests. the result is in the unit of the radius, which is
+/// meters for the earth.
+pub fn haversine_distance_with_radius(
+    src_x: f32,
+    src_y: f32,
+    dst_x: f32,
+    dst_y: f32,
+    radius_meters: f32,
+) -> Result<Distance, String> {
+    if !radius_meters.is_finite() || radius_meters <= 0.0 {
+        return Err(format!("radius must be positive, found {}", radius_meters));
+    }
     if !(-180.0..=180.0).contains(&src_x) {
         return Err(format!("src x value not in range [-180, 180]: {}", src_x));
     }
@@ -53,6 +91,145 @@ pub fn haversine_distance_meters(
 
     let a = (d_lat / 2.0).sin().powi(2) + (d_lon / 2.0).sin().powi(2) * lat1.cos() * lat2.cos();
     let c = 2.0 * a.sqrt().asin();
-    let distance_meters = APPROX_EARTH_RADIUS_M * c;
+    let distance_meters = radius_meters * c;
     Ok(Distance::new(distance_meters.into()))
 }
+
+/// intermediate points along the great circle between two coordinates,
+/// evenly spaced so that no two consecutive points are further apart than
+/// `spacing`. the endpoints themselves are not included.
+/// coordinates are assumed to be in the WGS84 Coordinate System.
+///
+/// # Arguments
+///
+/// * `src` - start of the segment
+/// * `dst` - end of the segment
+/// * `spacing` - largest distance between points, in meters
+///
+/// # Returns
+///
+/// the intermediate points from `src` to `dst`, or an error if a coordinate
+/// is out of range or the spacing is not positive
+pub fn great_circle_points(
+    src: &Coord<f32>,
+    dst: &Coord<f32>,
+    spacing: Distance,
+) -> Result<Vec<Coord<f32>>, String> {
+    if spacing.as_f64() <= 0.0 {
+        return Err(format!("spacing must be positive, found {}", spacing));
+    }
+    let distance = coord_distance_meters(src, dst)?.as_f64();
+    let n_segments = (distance / spacing.as_f64()).ceil() as usize;
+    // angular distance between the endpoints
+    let delta = distance / APPROX_EARTH_RADIUS_M as f64;
+    if n_segments < 2 || delta.sin() == 0.0 {
+        return Ok(vec![]);
+    }
+    let (lat1, lon1) = ((src.y as f64).to_radians(), (src.x as f64).to_radians());
+    let (lat2, lon2) = ((dst.y as f64).to_radians(), (dst.x as f64).to_radians());
+    let points = (1..n_segments)
+        .map(|i| {
+            let fraction = i as f64 / n_segments as f64;
+            let a = ((1.0 - fraction) * delta).sin() / delta.sin();
+            let b = (fraction * delta).sin() / delta.sin();
+            let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+            let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+            let z = a * lat1.sin() + b * lat2.sin();
+            let lat = z.atan2((x * x + y * y).sqrt());
+            let lon = y.atan2(x);
+            Coord {
+                x: lon.to_degrees() as f32,
+                y: lat.to_degrees() as f32,
+            }
+        })
+        .collect();
+    Ok(points)
+}
+
+/// adds great circle points between each pair of consecutive coordinates
+/// of a linestring, see [`great_circle_points`].
+///
+/// # Arguments
+///
+/// * `linestring` - the WGS84 linestring to densify
+/// * `spacing` - largest distance between points, in meters
+///
+/// # Returns
+///
+/// a linestring with the original coordinates and the added points
+pub fn densify_linestring(
+    linestring: &LineString<f32>,
+    spacing: Distance,
+) -> Result<LineString<f32>, String> {
+    let mut coords = vec![];
+    for line in linestring.lines() {
+        coords.push(line.start);
+        coords.extend(great_circle_points(&line.start, &line.end, spacing)?);
+    }
+    if let Some(last) = linestring.0.last() {
+        coords.push(*last);
+    }
+    Ok(LineString::new(coords))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_sphere_distances() {
+        let origin = Coord { x: 0.0, y: 0.0 };
+        let quarter = |dst: Coord<f32>| {
+            coord_distance_with_radius(&origin, &dst, DistanceUnit::Meters, Some(1.0))
+                .unwrap()
+                .as_f64()
+        };
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        assert!((quarter(Coord { x: 90.0, y: 0.0 }) - half_pi).abs() < 1e-6);
+        assert!((quarter(Coord { x: 0.0, y: 90.0 }) - half_pi).abs() < 1e-6);
+
+        // the default radius is the earth's
+        let dst = Coord { x: 1.0, y: 1.0 };
+        assert_eq!(
+            coord_distance(&origin, &dst, DistanceUnit::Meters).unwrap(),
+            coord_distance_with_radius(&origin, &dst, DistanceUnit::Meters, None).unwrap()
+        );
+        assert!(haversine_distance_with_radius(0.0, 0.0, 1.0, 1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_long_edge_densified_along_great_circle() {
+        // 10 degrees of longitude along the equator is about 1112 km, which
+        // takes 12 segments of at most 100 km, so 11 intermediate points
+        let src = Coord { x: 0.0, y: 0.0 };
+        let dst = Coord { x: 10.0, y: 0.0 };
+        let points = great_circle_points(&src, &dst, Distance::new(100_000.0)).unwrap();
+        assert_eq!(points.len(), 11);
+        for (i, point) in points.iter().enumerate() {
+            let expected_x = 10.0 * (i + 1) as f32 / 12.0;
+            assert!((point.x - expected_x).abs() < 1e-4, "{:?}", point);
+            assert!(point.y.abs() < 1e-4, "{:?}", point);
+        }
+
+        // off the equator the great circle bows toward the pole
+        let src = Coord { x: -100.0, y: 40.0 };
+        let dst = Coord { x: -80.0, y: 40.0 };
+        let points = great_circle_points(&src, &dst, Distance::new(100_000.0)).unwrap();
+        assert!(points.iter().all(|p| p.y > 40.0));
+    }
+
+    #[test]
+    fn test_densify_linestring_keeps_vertices() {
+        let linestring = LineString::new(vec![
+            Coord { x: 0.0, y: 0.0 },
+            Coord { x: 10.0, y: 0.0 },
+            Coord { x: 10.0, y: 0.001 },
+        ]);
+        let dense = densify_linestring(&linestring, Distance::new(100_000.0)).unwrap();
+        // the short second segment adds no points
+        assert_eq!(dense.0.len(), 3 + 11);
+        assert_eq!(dense.0.first(), linestring.0.first());
+        assert_eq!(dense.0.last(), linestring.0.last());
+        assert!(densify_linestring(&linestring, Distance::ZERO).is_err());
+    }
+}
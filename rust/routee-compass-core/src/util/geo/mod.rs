@@ -1,3 +1,6 @@
 pub mod coord;
+pub mod crs;
+pub mod distance_function;
 pub mod geo_io_utils;
 pub mod haversine;
+pub mod simplify;
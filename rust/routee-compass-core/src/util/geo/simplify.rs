@@ -0,0 +1,120 @@
+use super::haversine::APPROX_EARTH_RADIUS_M;
+use crate::model::unit::{as_f64::AsF64, Distance};
+use geo::{Coord, LineString};
+
+/// simplifies a WGS84 linestring with the Douglas-Peucker algorithm, removing
+/// the points that lie within `tolerance` of the line between the points kept
+/// around them. the first and last points are always kept.
+///
+/// # Arguments
+///
+/// * `linestring` - the WGS84 linestring to simplify
+/// * `tolerance` - largest distance a removed point may lie from the
+///   simplified line, in meters. zero disables simplification.
+///
+/// # Returns
+///
+/// the simplified linestring
+pub fn simplify_linestring(linestring: &LineString<f32>, tolerance: Distance) -> LineString<f32> {
+    let coords = &linestring.0;
+    if tolerance.as_f64() <= 0.0 || coords.len() < 3 {
+        return linestring.clone();
+    }
+    let mut keep = vec![false; coords.len()];
+    keep[0] = true;
+    keep[coords.len() - 1] = true;
+    let mut stack = vec![(0, coords.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let farthest = (start + 1..end)
+            .map(|idx| {
+                let distance = segment_distance_meters(&coords[idx], &coords[start], &coords[end]);
+                (idx, distance)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((idx, distance)) = farthest {
+            if distance > tolerance.as_f64() {
+                keep[idx] = true;
+                stack.push((start, idx));
+                stack.push((idx, end));
+            }
+        }
+    }
+    let simplified = coords
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(coord, _)| *coord)
+        .collect();
+    LineString::new(simplified)
+}
+
+/// distance in meters from a point to the segment between `start` and `end`,
+/// measured on an equirectangular projection centered on `start`, which is
+/// accurate over the short segments of a route geometry.
+fn segment_distance_meters(point: &Coord<f32>, start: &Coord<f32>, end: &Coord<f32>) -> f64 {
+    let meters_per_degree = (APPROX_EARTH_RADIUS_M as f64).to_radians();
+    let cos_lat = (start.y as f64).to_radians().cos();
+    let project = |c: &Coord<f32>| {
+        (
+            (c.x - start.x) as f64 * cos_lat * meters_per_degree,
+            (c.y - start.y) as f64 * meters_per_degree,
+        )
+    };
+    let (px, py) = project(point);
+    let (ex, ey) = project(end);
+    let length_squared = ex * ex + ey * ey;
+    let fraction = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((px * ex + py * ey) / length_squared).clamp(0.0, 1.0)
+    };
+    let (dx, dy) = (px - fraction * ex, py - fraction * ey);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(x: f32, y: f32) -> Coord<f32> {
+        Coord { x, y }
+    }
+
+    #[test]
+    fn test_zigzag_keeps_sharp_corners() {
+        // a zigzag along the equator that wanders about 0.1 meters from a
+        // straight line, then turns north at a right angle
+        let linestring = LineString::new(vec![
+            coord(0.0, 0.0),
+            coord(0.001, 0.000001),
+            coord(0.002, -0.000001),
+            coord(0.003, 0.000001),
+            coord(0.004, 0.0),
+            coord(0.004, 0.001),
+            coord(0.004, 0.002),
+        ]);
+        let simplified = simplify_linestring(&linestring, Distance::new(1.0));
+        assert_eq!(
+            simplified.0,
+            vec![coord(0.0, 0.0), coord(0.004, 0.0), coord(0.004, 0.002)]
+        );
+
+        // below the size of the wiggles they are kept, while the midpoint of
+        // the straight leg north is still removed
+        let simplified = simplify_linestring(&linestring, Distance::new(0.01));
+        let mut expected = linestring.0.clone();
+        expected.remove(5);
+        assert_eq!(simplified.0, expected);
+    }
+
+    #[test]
+    fn test_zero_tolerance_is_disabled() {
+        let linestring =
+            LineString::new(vec![coord(0.0, 0.0), coord(0.001, 0.0), coord(0.002, 0.0)]);
+        assert_eq!(simplify_linestring(&linestring, Distance::ZERO), linestring);
+        assert_eq!(
+            simplify_linestring(&linestring, Distance::new(1.0)).0,
+            vec![coord(0.0, 0.0), coord(0.002, 0.0)]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 pub mod cache_policy;
 pub mod compact_ordered_hash_map;
 pub mod conversion;
+pub mod departure_time;
 pub mod duration_extension;
+pub mod fingerprint;
 pub mod fs;
 pub mod geo;
 pub mod io_utils;
 pub mod multiset;
 pub mod priority_queue;
+pub mod progress;
 pub mod read_only_lock;
 pub mod serde;
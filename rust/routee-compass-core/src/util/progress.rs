@@ -0,0 +1,75 @@
+use kdam::{Bar, BarExt};
+
+/// receives the number of rows processed so far and the expected total as a
+/// file is loaded, so that progress can be sent to a logger instead of the terminal.
+pub type ProgressCallback = Box<dyn FnMut(usize, usize) + Send>;
+
+/// reports the progress of loading a file. progress goes to a callback when
+/// one is provided, otherwise to a `kdam` progress bar when verbose, and
+/// otherwise nowhere.
+pub enum LoadProgress {
+    Callback {
+        callback: ProgressCallback,
+        done: usize,
+        total: usize,
+    },
+    Bar(Bar),
+    Silent,
+}
+
+impl LoadProgress {
+    /// # Arguments
+    ///
+    /// * `callback` - optional progress callback, which takes priority over the progress bar
+    /// * `verbose` - whether to draw a progress bar when no callback is provided
+    /// * `desc` - description shown on the progress bar
+    /// * `total` - expected number of rows
+    /// * `position` - terminal row of the progress bar, so concurrent loads do not overlap
+    ///
+    /// # Returns
+    ///
+    /// the progress reporter, or an error message if the progress bar could not be built
+    pub fn new(
+        callback: Option<ProgressCallback>,
+        verbose: bool,
+        desc: &str,
+        total: usize,
+        position: u16,
+    ) -> Result<LoadProgress, String> {
+        match callback {
+            Some(callback) => Ok(LoadProgress::Callback {
+                callback,
+                done: 0,
+                total,
+            }),
+            None if verbose => {
+                let bar = Bar::builder()
+                    .total(total)
+                    .animation("fillup")
+                    .desc(desc)
+                    .position(position)
+                    .build()?;
+                Ok(LoadProgress::Bar(bar))
+            }
+            None => Ok(LoadProgress::Silent),
+        }
+    }
+
+    /// records that one more row was processed
+    pub fn update(&mut self) {
+        match self {
+            LoadProgress::Callback {
+                callback,
+                done,
+                total,
+            } => {
+                *done += 1;
+                callback(*done, *total);
+            }
+            LoadProgress::Bar(bar) => {
+                let _ = bar.update(1);
+            }
+            LoadProgress::Silent => {}
+        }
+    }
+}
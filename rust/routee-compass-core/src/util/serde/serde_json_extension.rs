@@ -4,6 +4,11 @@ use std::any::Any;
 
 pub trait SerdeJsonExtension {
     fn merge(&self, that: &serde_json::Value) -> Result<Value, serde_json::Error>;
+
+    /// rounds every floating point number in this JSON, including those nested
+    /// in arrays and objects, to a number of decimal places. integers and
+    /// non-finite values are left unchanged.
+    fn round_floats(&self, decimal_places: u32) -> Value;
 }
 
 impl SerdeJsonExtension for Value {
@@ -28,4 +33,45 @@ impl SerdeJsonExtension for Value {
             ))),
         }
     }
+
+    fn round_floats(&self, decimal_places: u32) -> Value {
+        match self {
+            Value::Number(n) if n.is_f64() => {
+                let scale = 10f64.powi(decimal_places as i32);
+                let rounded = n.as_f64().map(|f| (f * scale).round() / scale);
+                match rounded.and_then(serde_json::Number::from_f64) {
+                    Some(rounded) => Value::Number(rounded),
+                    None => self.clone(),
+                }
+            }
+            Value::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|v| v.round_floats(decimal_places))
+                    .collect(),
+            ),
+            Object(map) => Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), v.round_floats(decimal_places)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_floats() {
+        let value = json!({"distance": 1.609344, "legs": [0.123456789, 2], "unit": "km"});
+        let rounded = value.round_floats(4);
+        assert_eq!(
+            rounded,
+            json!({"distance": 1.6093, "legs": [0.1235, 2], "unit": "km"})
+        );
+    }
 }
@@ -1,41 +1,366 @@
-use routee_compass_core::model::{
-    access::default::turn_delays::edge_heading::EdgeHeading, road_network::edge_id::EdgeId,
-    traversal::traversal_model_error::TraversalModelError, unit::Grade,
+use super::grade_sign_convention::GradeSignConvention;
+use super::missing_grade_policy::MissingGradePolicy;
+use log::debug;
+use routee_compass_core::{
+    model::{
+        access::default::turn_delays::edge_heading::EdgeHeading,
+        property::edge::Edge,
+        road_network::edge_id::EdgeId,
+        traversal::traversal_model_error::TraversalModelError,
+        unit::{as_f64::AsF64, EnergyRateUnit, Grade, GradeUnit},
+    },
+    util::fs::read_utils,
 };
+use serde::Deserialize;
+use std::{collections::HashMap, path::Path};
 
 pub const ZERO_ENERGY: f64 = 1e-9;
 
-/// look up the grade from the grade table
+/// grades steeper than this ratio (40%) are implausible for a road and most
+/// likely come from a grade table declared in the wrong unit.
+pub const MAX_PLAUSIBLE_GRADE: f64 = 0.4;
+
+/// converts a grade table from its input unit into the canonical Decimal
+/// (ratio) grade unit used by the energy model.
+///
+/// # Arguments
+///
+/// * `grade_table` - grades as read from the grade table file
+/// * `grade_unit` - unit of the grades in the file
+///
+/// # Returns
+///
+/// the grades as ratios of rise over run
+pub fn to_canonical_grades(grade_table: Box<[Grade]>, grade_unit: &GradeUnit) -> Box<[Grade]> {
+    if *grade_unit == GradeUnit::Decimal {
+        return grade_table;
+    }
+    grade_table
+        .iter()
+        .map(|grade| grade_unit.convert(grade, &GradeUnit::Decimal))
+        .collect()
+}
+
+/// counts the canonical grades whose magnitude exceeds [`MAX_PLAUSIBLE_GRADE`].
+/// a large count suggests the grade table unit is misconfigured, for example
+/// a table in percent declared as a ratio.
+pub fn count_implausible_grades(grade_table: &[Grade]) -> usize {
+    grade_table
+        .iter()
+        .filter(|grade| grade.as_f64().abs() > MAX_PLAUSIBLE_GRADE)
+        .count()
+}
+
+/// converts grades read from `source` into canonical grades, warning when
+/// many of them are implausibly steep.
+///
+/// # Arguments
+///
+/// * `raw_grades` - grades as read from the file
+/// * `grade_unit` - unit of the grades in the file
+/// * `source` - the file the grades were read from, for logging
+///
+/// # Returns
+///
+/// the grades as ratios of rise over run
+pub fn canonical_grade_table(
+    raw_grades: Box<[Grade]>,
+    grade_unit: &GradeUnit,
+    source: &Path,
+) -> Box<[Grade]> {
+    let grades = to_canonical_grades(raw_grades, grade_unit);
+    let n_implausible = count_implausible_grades(&grades);
+    if n_implausible > 0 {
+        log::warn!(
+            "{} of {} grades in {:?} are steeper than {}% after converting from {}, check that grade_table_grade_unit is correct",
+            n_implausible,
+            grades.len(),
+            source,
+            MAX_PLAUSIBLE_GRADE * 100.0,
+            grade_unit
+        );
+    }
+    grades
+}
+
+/// look up the grade from the grade table. if the table exists but has no
+/// entry for this edge, the missing grade policy decides whether to fail or
+/// substitute a fallback grade. grades from the table are oriented by the
+/// grade sign convention so that a positive grade is uphill in the direction
+/// of travel, using the digitized direction flag of the edge for segment
+/// grades.
 pub fn get_grade(
     grade_table: &Option<Box<[Grade]>>,
-    edge_id: EdgeId,
+    edge: &Edge,
+    missing_grade_policy: &MissingGradePolicy,
+    grade_sign_convention: &GradeSignConvention,
+    digitized_direction: &Option<Box<[bool]>>,
 ) -> Result<Grade, TraversalModelError> {
+    let edge_id = edge.edge_id;
     match grade_table {
         None => Ok(Grade::ZERO),
-        Some(gt) => {
-            let grade: &Grade = gt.get(edge_id.as_usize()).ok_or_else(|| {
-                TraversalModelError::MissingIdInTabularCostFunction(
-                    format!("{}", edge_id),
-                    String::from("EdgeId"),
-                    String::from("grade table"),
-                )
-            })?;
-            Ok(*grade)
+        Some(gt) => match (gt.get(edge_id.as_usize()), missing_grade_policy) {
+            (Some(grade), _) => match grade_sign_convention {
+                GradeSignConvention::DirectionOfTravel => Ok(*grade),
+                GradeSignConvention::Segment => {
+                    let along_digitized = digitized_direction
+                        .as_ref()
+                        .and_then(|flags| flags.get(edge_id.as_usize()))
+                        .ok_or_else(|| {
+                            TraversalModelError::missing_edge(
+                                "digitized direction table",
+                                edge,
+                                None,
+                            )
+                        })?;
+                    Ok(grade_sign_convention.directed_grade(*grade, *along_digitized))
+                }
+            },
+            (None, MissingGradePolicy::Error) => {
+                Err(TraversalModelError::missing_edge("grade table", edge, None))
+            }
+            (None, MissingGradePolicy::Zero) => {
+                debug!(
+                    "edge {} missing from grade table, using zero grade",
+                    edge_id
+                );
+                Ok(Grade::ZERO)
+            }
+            (None, MissingGradePolicy::Default { grade }) => {
+                debug!(
+                    "edge {} missing from grade table, using default grade {}",
+                    edge_id, grade
+                );
+                Ok(*grade)
+            }
+        },
+    }
+}
+
+/// sums the elevation gained and lost along a sequence of edges, using the
+/// grades the energy model would use. on a closed loop the climb and the
+/// descent should be about equal, so a large gap between them suggests that
+/// the grade sign convention is set backwards.
+///
+/// # Arguments
+///
+/// * `edges` - the edges of the route, in the order they are traversed
+/// * `grade_table` - the grade table of the energy model, as ratios
+/// * `missing_grade_policy` - how to treat edges missing from the grade table
+/// * `grade_sign_convention` - which way a positive grade in the table points
+/// * `digitized_direction` - for segment grades, whether each edge runs along its segment's digitized direction
+///
+/// # Returns
+///
+/// the total climb and the total descent, both positive, in the distance unit of the edges
+pub fn climb_and_descent(
+    edges: &[&Edge],
+    grade_table: &Option<Box<[Grade]>>,
+    missing_grade_policy: &MissingGradePolicy,
+    grade_sign_convention: &GradeSignConvention,
+    digitized_direction: &Option<Box<[bool]>>,
+) -> Result<(f64, f64), TraversalModelError> {
+    let mut climb = 0.0;
+    let mut descent = 0.0;
+    for edge in edges.iter() {
+        let grade = get_grade(
+            grade_table,
+            edge,
+            missing_grade_policy,
+            grade_sign_convention,
+            digitized_direction,
+        )?;
+        let rise = grade.as_f64() * edge.distance.as_f64();
+        if rise > 0.0 {
+            climb += rise;
+        } else {
+            descent -= rise;
         }
     }
+    Ok((climb, descent))
 }
 
 /// lookup up the edge heading from the headings table
 pub fn get_headings(
     headings_table: &[EdgeHeading],
-    edge_id: EdgeId,
+    edge: &Edge,
 ) -> Result<EdgeHeading, TraversalModelError> {
-    let heading: &EdgeHeading = headings_table.get(edge_id.as_usize()).ok_or_else(|| {
-        TraversalModelError::MissingIdInTabularCostFunction(
-            format!("{}", edge_id),
-            String::from("EdgeId"),
-            String::from("headings table"),
-        )
-    })?;
+    let heading: &EdgeHeading = headings_table
+        .get(edge.edge_id.as_usize())
+        .ok_or_else(|| TraversalModelError::missing_edge("headings table", edge, None))?;
     Ok(*heading)
 }
+
+/// a row of the energy rate unit override file
+#[derive(Deserialize)]
+struct EnergyRateUnitOverrideRow {
+    edge_id: EdgeId,
+    energy_rate_unit: EnergyRateUnit,
+}
+
+/// reads the per-edge energy rate unit overrides from a CSV file with
+/// columns `edge_id,energy_rate_unit`. edges not listed in the file use
+/// the energy rate unit of the vehicle's prediction model.
+pub fn read_energy_rate_unit_overrides(
+    path: &Path,
+) -> Result<HashMap<EdgeId, EnergyRateUnit>, TraversalModelError> {
+    let rows: Box<[EnergyRateUnitOverrideRow]> = read_utils::from_csv(&path, true, None)
+        .map_err(|e| TraversalModelError::FileReadError(path.to_path_buf(), e.to_string()))?;
+    let mut overrides = HashMap::with_capacity(rows.len());
+    for row in rows.iter() {
+        if overrides
+            .insert(row.edge_id, row.energy_rate_unit)
+            .is_some()
+        {
+            return Err(TraversalModelError::FileReadError(
+                path.to_path_buf(),
+                format!("edge {} has more than one energy rate unit", row.edge_id),
+            ));
+        }
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::road_network::vertex_id::VertexId;
+
+    #[test]
+    fn test_missing_grade_policies() {
+        let travel = GradeSignConvention::DirectionOfTravel;
+        let table = Some(vec![Grade::new(1.0), Grade::new(2.0)].into_boxed_slice());
+        let present = get_grade(
+            &table,
+            &Edge::new(1, 0, 1, 1.0),
+            &MissingGradePolicy::Error,
+            &travel,
+            &None,
+        );
+        assert_eq!(present.unwrap(), Grade::new(2.0));
+
+        let missing = Edge::new(5, 3, 4, 1.0);
+        let strict = get_grade(&table, &missing, &MissingGradePolicy::Error, &travel, &None);
+        match strict {
+            Err(TraversalModelError::MissingEdgeInTabularCostFunction { context, .. }) => {
+                assert_eq!(context.src_vertex_id, Some(VertexId(3)));
+                assert_eq!(context.dst_vertex_id, Some(VertexId(4)));
+            }
+            other => panic!("expected missing id error with context, found {:?}", other),
+        }
+        let message = get_grade(&table, &missing, &MissingGradePolicy::Error, &travel, &None)
+            .unwrap_err()
+            .to_string();
+        assert!(message.ends_with("(src vertex 3, dst vertex 4)"));
+        let zero = get_grade(&table, &missing, &MissingGradePolicy::Zero, &travel, &None).unwrap();
+        assert_eq!(zero, Grade::ZERO);
+        let default_policy = MissingGradePolicy::Default {
+            grade: Grade::new(3.0),
+        };
+        let default = get_grade(&table, &missing, &default_policy, &travel, &None).unwrap();
+        assert_eq!(default, Grade::new(3.0));
+    }
+
+    #[test]
+    fn test_grade_units_convert_to_same_canonical_grade() {
+        // a 5% grade in each supported input unit
+        let degrees = 0.05_f64.atan().to_degrees();
+        let tables = [
+            (vec![Grade::new(5.0)], GradeUnit::Percent),
+            (vec![Grade::new(0.05)], GradeUnit::Decimal),
+            (vec![Grade::new(50.0)], GradeUnit::Millis),
+            (vec![Grade::new(degrees)], GradeUnit::Degrees),
+        ];
+        for (table, unit) in tables {
+            let canonical = to_canonical_grades(table.into_boxed_slice(), &unit);
+            let ratio = canonical[0].as_f64();
+            assert!(
+                (ratio - 0.05).abs() < 1e-9,
+                "{} converted to {}",
+                unit,
+                ratio
+            );
+            assert_eq!(count_implausible_grades(&canonical), 0);
+        }
+    }
+
+    #[test]
+    fn test_misconfigured_grade_unit_is_implausible() {
+        // percent grades declared as ratios become 500% and 300% hills
+        let table = vec![Grade::new(5.0), Grade::new(-3.0), Grade::new(0.0)];
+        let canonical = to_canonical_grades(table.into_boxed_slice(), &GradeUnit::Decimal);
+        assert_eq!(count_implausible_grades(&canonical), 2);
+    }
+
+    #[test]
+    fn test_no_grade_table_is_flat() {
+        let grade = get_grade(
+            &None,
+            &Edge::new(5, 3, 4, 1.0),
+            &MissingGradePolicy::Error,
+            &GradeSignConvention::DirectionOfTravel,
+            &None,
+        )
+        .unwrap();
+        assert_eq!(grade, Grade::ZERO);
+    }
+
+    #[test]
+    fn test_grade_sign_convention_on_loop() {
+        // vertex elevations 0, 5 and 7 meters. both directed edges of each segment
+        // share the grade measured along its digitized direction, which runs from
+        // vertex 0 to 1, from 2 to 1 and from 2 to 0
+        let edges = [
+            Edge::new(0, 0, 1, 100.0),
+            Edge::new(1, 1, 0, 100.0),
+            Edge::new(2, 1, 2, 100.0),
+            Edge::new(3, 2, 1, 100.0),
+            Edge::new(4, 2, 0, 140.0),
+            Edge::new(5, 0, 2, 140.0),
+        ];
+        let grades = [0.05, 0.05, -0.02, -0.02, -0.05, -0.05]
+            .into_iter()
+            .map(Grade::new)
+            .collect::<Vec<_>>();
+        let table = Some(grades.into_boxed_slice());
+        let digitized = Some(Box::from([true, false, false, true, true, false]));
+        let route = |edge_ids: &[usize]| edge_ids.iter().map(|i| &edges[*i]).collect::<Vec<_>>();
+        let policy = MissingGradePolicy::Error;
+
+        // with the segment convention, either direction around the loop is balanced
+        for edge_ids in [[0, 2, 4], [5, 3, 1]] {
+            let (climb, descent) = climb_and_descent(
+                &route(&edge_ids),
+                &table,
+                &policy,
+                &GradeSignConvention::Segment,
+                &digitized,
+            )
+            .unwrap();
+            assert!((climb - 7.0).abs() < 1e-9);
+            assert!((climb - descent).abs() < 1e-9);
+        }
+
+        // reading the same table as direction of travel descends the 1 to 2 climb
+        let (climb, descent) = climb_and_descent(
+            &route(&[0, 2, 4]),
+            &table,
+            &policy,
+            &GradeSignConvention::DirectionOfTravel,
+            &None,
+        )
+        .unwrap();
+        assert!((climb - 5.0).abs() < 1e-9);
+        assert!((descent - 9.0).abs() < 1e-9);
+
+        // segment grades without digitized direction flags are an error
+        let missing_flags = climb_and_descent(
+            &route(&[0]),
+            &table,
+            &policy,
+            &GradeSignConvention::Segment,
+            &None,
+        );
+        assert!(missing_flags.is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use super::energy_model_ops::{canonical_grade_table, read_energy_rate_unit_overrides};
 use super::energy_traversal_model::EnergyTraversalModel;
+use super::grade_sign_convention::GradeSignConvention;
+use super::missing_grade_policy::MissingGradePolicy;
 use super::vehicle::VehicleType;
+use routee_compass_core::model::road_network::edge_id::EdgeId;
 use routee_compass_core::model::traversal::traversal_model::TraversalModel;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
@@ -16,9 +20,34 @@ pub struct EnergyModelService {
     pub time_model_speed_unit: SpeedUnit,
     pub grade_table: Arc<Option<Box<[Grade]>>>,
     pub grade_table_grade_unit: GradeUnit,
+    pub missing_grade_policy: MissingGradePolicy,
+    /// which way a positive grade in the grade table points
+    pub grade_sign_convention: GradeSignConvention,
+    /// for segment grades, true for each edge that runs along the digitized
+    /// direction of its road segment
+    pub digitized_direction: Arc<Option<Box<[bool]>>>,
     pub time_unit: TimeUnit,
     pub distance_unit: DistanceUnit,
     pub vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
+    /// per-edge overrides of the energy rate unit of the vehicle prediction model
+    pub energy_rate_unit_overrides: Arc<HashMap<EdgeId, EnergyRateUnit>>,
+    /// radius in meters of the sphere for haversine distance estimates,
+    /// defaults to the earth
+    pub haversine_radius: Option<f32>,
+    /// kilograms of CO2-equivalent emitted per unit of energy, by energy unit.
+    /// units without a factor emit nothing, and no CO2-equivalent is reported
+    /// when every factor is zero.
+    pub emissions_factors: HashMap<EnergyUnit, f64>,
+    /// price paid per unit of energy, by energy unit. when any price is set,
+    /// the energy cost of each edge is accumulated in an `energy_cost` state
+    /// feature that the cost model can weight.
+    pub energy_prices: HashMap<EnergyUnit, f64>,
+    /// price of one hour of travel time, used to report the time cost of a
+    /// route alongside its energy cost
+    pub value_of_time: f64,
+    /// decimal places of the route summary, which the `summary_precision`
+    /// query parameter overrides. defaults to DEFAULT_SUMMARY_PRECISION
+    pub summary_precision: Option<u32>,
 }
 
 impl EnergyModelService {
@@ -28,30 +57,61 @@ impl EnergyModelService {
         time_model_speed_unit: SpeedUnit,
         grade_table_path_option: &Option<P>,
         grade_table_grade_unit: GradeUnit,
+        missing_grade_policy_option: Option<MissingGradePolicy>,
         output_time_unit_option: Option<TimeUnit>,
         output_distance_unit_option: Option<DistanceUnit>,
         vehicle_library: HashMap<String, Arc<dyn VehicleType>>,
+        energy_rate_unit_override_path_option: &Option<P>,
     ) -> Result<Self, TraversalModelError> {
         let output_time_unit = output_time_unit_option.unwrap_or(BASE_TIME_UNIT);
         let output_distance_unit = output_distance_unit_option.unwrap_or(BASE_DISTANCE_UNIT);
 
+        // grades are converted to the canonical ratio on load
         let grade_table: Arc<Option<Box<[Grade]>>> = match grade_table_path_option {
-            Some(gtp) => Arc::new(Some(
-                read_utils::read_raw_file(gtp, read_decoders::default, None).map_err(|e| {
-                    TraversalModelError::FileReadError(gtp.as_ref().to_path_buf(), e.to_string())
-                })?,
-            )),
+            Some(gtp) => {
+                let raw_grades: Box<[Grade]> =
+                    read_utils::read_raw_file(gtp, read_decoders::default, None).map_err(|e| {
+                        TraversalModelError::FileReadError(
+                            gtp.as_ref().to_path_buf(),
+                            e.to_string(),
+                        )
+                    })?;
+                let grades =
+                    canonical_grade_table(raw_grades, &grade_table_grade_unit, gtp.as_ref());
+                Arc::new(Some(grades))
+            }
             None => Arc::new(None),
         };
+        // the missing grade default is given in the grade table unit
+        let missing_grade_policy = match missing_grade_policy_option.unwrap_or_default() {
+            MissingGradePolicy::Default { grade } => MissingGradePolicy::Default {
+                grade: grade_table_grade_unit.convert(&grade, &GradeUnit::Decimal),
+            },
+            policy => policy,
+        };
+
+        let energy_rate_unit_overrides = match energy_rate_unit_override_path_option {
+            Some(path) => read_energy_rate_unit_overrides(path.as_ref())?,
+            None => HashMap::new(),
+        };
 
         Ok(EnergyModelService {
             time_model_service,
             time_model_speed_unit,
             grade_table,
-            grade_table_grade_unit,
+            grade_table_grade_unit: GradeUnit::Decimal,
+            missing_grade_policy,
+            grade_sign_convention: GradeSignConvention::default(),
+            digitized_direction: Arc::new(None),
             time_unit: output_time_unit,
             distance_unit: output_distance_unit,
             vehicle_library,
+            energy_rate_unit_overrides: Arc::new(energy_rate_unit_overrides),
+            haversine_radius: None,
+            emissions_factors: HashMap::new(),
+            energy_prices: HashMap::new(),
+            value_of_time: 0.0,
+            summary_precision: None,
         })
     }
 }
@@ -65,4 +125,19 @@ impl TraversalModelService for EnergyModelService {
         let model = EnergyTraversalModel::new(arc_self, parameters)?;
         Ok(Arc::new(model))
     }
+
+    /// validates the model built for each vehicle in the library
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        for model_name in self.vehicle_library.keys() {
+            self.build(&serde_json::json!({ "model_name": model_name }))?
+                .validate()
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "vehicle {} failed validation: {}",
+                        model_name, e
+                    ))
+                })?;
+        }
+        Ok(())
+    }
 }
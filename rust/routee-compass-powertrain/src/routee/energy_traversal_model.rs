@@ -3,19 +3,35 @@ use super::energy_model_service::EnergyModelService;
 use super::vehicle::vehicle_type::VehicleType;
 use routee_compass_core::model::property::edge::Edge;
 use routee_compass_core::model::property::vertex::Vertex;
+use routee_compass_core::model::state::accumulation_operation::AccumulationOperation;
+use routee_compass_core::model::state::custom_feature_format::CustomFeatureFormat;
 use routee_compass_core::model::state::state_feature::StateFeature;
 use routee_compass_core::model::state::state_model::StateModel;
 use routee_compass_core::model::traversal::state::state_variable::StateVar;
-use routee_compass_core::model::traversal::traversal_model::TraversalModel;
+use routee_compass_core::model::traversal::traversal_model::{summary_precision, TraversalModel};
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
+use routee_compass_core::model::unit::as_f64::AsF64;
 use routee_compass_core::model::unit::*;
 use routee_compass_core::util::geo::haversine;
+use routee_compass_core::util::serde::serde_json_extension::SerdeJsonExtension;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub struct EnergyTraversalModel {
     pub energy_model_service: Arc<EnergyModelService>,
     pub time_model: Arc<dyn TraversalModel>,
     pub vehicle: Arc<dyn VehicleType>,
+    /// optional energy unit used to report the total energy of a route
+    pub summary_energy_unit: Option<EnergyUnit>,
+    /// decimal places of the energy values in the route summary
+    pub summary_precision: u32,
+    /// price paid per unit of energy for this query, see [`EnergyModelService::energy_prices`]
+    pub energy_prices: HashMap<EnergyUnit, f64>,
+    /// each energy feature of the vehicle with a nonzero price, along with
+    /// its unit and price, found once when the model is built
+    pub priced_energy_features: Vec<(String, EnergyUnit, f64)>,
+    /// price of one hour of travel time for this query
+    pub value_of_time: f64,
 }
 
 impl TraversalModel for EnergyTraversalModel {
@@ -23,6 +39,23 @@ impl TraversalModel for EnergyTraversalModel {
     fn state_features(&self) -> Vec<(String, StateFeature)> {
         let mut features = self.vehicle.state_features();
         features.extend(self.time_model.state_features());
+        features.push((
+            String::from(Self::MAX_GRADE),
+            StateFeature::Custom {
+                r#type: String::from("grade"),
+                unit: String::from("decimal"),
+                format: CustomFeatureFormat::FloatingPoint {
+                    initial: 0.0.into(),
+                },
+                accumulation: AccumulationOperation::Max,
+            },
+        ));
+        if self.has_energy_prices() {
+            features.push((String::from(Self::ENERGY_COST), Self::monetary_feature()));
+        }
+        if self.has_value_of_time() {
+            features.push((String::from(Self::TIME_COST), Self::monetary_feature()));
+        }
         features
     }
 
@@ -32,52 +65,19 @@ impl TraversalModel for EnergyTraversalModel {
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
-        let (_, edge, _) = trajectory;
-        let distance =
-            BASE_DISTANCE_UNIT.convert(&edge.distance, &self.energy_model_service.distance_unit);
-        let prev = state.to_vec();
-
-        // perform time traversal
-        self.time_model
-            .traverse_edge(trajectory, state, state_model)?;
-        let prev_time = state_model.get_time(
-            &prev,
-            &Self::TIME.into(),
-            &self
-                .energy_model_service
-                .time_model_speed_unit
-                .associated_time_unit(),
-        )?;
-        let current_time = state_model.get_time(
-            state,
-            &Self::TIME.into(),
-            &self
-                .energy_model_service
-                .time_model_speed_unit
-                .associated_time_unit(),
-        )?;
-        let time_delta = current_time - prev_time;
-
-        // perform vehicle energy traversal
-        let grade = get_grade(&self.energy_model_service.grade_table, edge.edge_id)?;
-
-        let distance_in_time_model_unit = BASE_DISTANCE_UNIT.convert(
-            &edge.distance,
-            &self
-                .energy_model_service
-                .time_model_speed_unit
-                .associated_distance_unit(),
-        );
-        let speed = Speed::from((distance_in_time_model_unit, time_delta));
-        self.vehicle.consume_energy(
-            (speed, self.energy_model_service.time_model_speed_unit),
-            (grade, self.energy_model_service.grade_table_grade_unit),
-            (distance, self.energy_model_service.distance_unit),
-            state,
-            state_model,
-        )?;
+        self.traverse(trajectory, None, state, state_model)
+    }
 
-        Ok(())
+    /// the time model is told the fraction, so any fixed delay it adds is
+    /// charged on the same terms as a partial edge of the time model alone
+    fn traverse_partial_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        fraction: f64,
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        self.traverse(trajectory, Some(fraction), state, state_model)
     }
 
     fn estimate_traversal(
@@ -87,10 +87,11 @@ impl TraversalModel for EnergyTraversalModel {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (src, dst) = od;
-        let distance = haversine::coord_distance(
+        let distance = haversine::coord_distance_with_radius(
             &src.coordinate,
             &dst.coordinate,
             self.energy_model_service.distance_unit,
+            self.energy_model_service.haversine_radius,
         )
         .map_err(TraversalModelError::NumericError)?;
 
@@ -107,10 +108,188 @@ impl TraversalModel for EnergyTraversalModel {
 
         Ok(())
     }
+
+    /// reports each energy feature of the vehicle in its native unit. when the
+    /// query requests a `summary_energy_unit`, the energy features are also
+    /// converted to that unit and summed, which allows comparing liquid fuel
+    /// and electric vehicles, or totaling both sources of a hybrid. otherwise,
+    /// a vehicle with a single energy unit reports its total in that unit.
+    /// the total is labeled by its family, "gallons" or "kWh", and when the
+    /// service has emissions factors, the CO2-equivalent in kilograms is
+    /// included, along with the steepest grade climbed as a decimal
+    /// `max_grade`. when energy prices or a value of time are set, the monetary
+    /// cost of the route is broken down into the `energy_cost` and
+    /// `time_cost` state features, which sum to `monetary_cost`. energy
+    /// values are rounded to the summary precision.
+    fn summary(
+        &self,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        let mut summary = serde_json::Map::new();
+        let mut energies = vec![];
+        for (name, feature) in self.vehicle.state_features() {
+            if let StateFeature::Energy { energy_unit, .. } = feature {
+                let energy = state_model.get_energy(state, &name, &energy_unit)?;
+                summary.insert(name.clone(), serde_json::json!(energy));
+                summary.insert(format!("{}_unit", name), serde_json::json!(energy_unit));
+                energies.push((energy, energy_unit));
+            }
+        }
+        let total_unit = match (&self.summary_energy_unit, energies.as_slice()) {
+            (Some(summary_unit), _) => Some(*summary_unit),
+            (None, [(_, unit), rest @ ..]) if rest.iter().all(|(_, u)| u == unit) => Some(*unit),
+            _ => None,
+        };
+        if let Some(total_unit) = total_unit {
+            let total_energy: Energy = energies
+                .iter()
+                .map(|(energy, unit)| unit.convert(energy, &total_unit))
+                .sum();
+            summary.insert(
+                String::from("total_energy"),
+                serde_json::json!(total_energy),
+            );
+            summary.insert(
+                String::from("total_energy_unit"),
+                serde_json::json!(total_unit),
+            );
+            summary.insert(
+                String::from("total_energy_label"),
+                serde_json::json!(total_unit.family().label()),
+            );
+        }
+        let emissions_factors = &self.energy_model_service.emissions_factors;
+        if emissions_factors.values().any(|factor| *factor != 0.0) {
+            let co2e: f64 = energies
+                .iter()
+                .map(|(energy, unit)| {
+                    energy.as_f64() * emissions_factors.get(unit).copied().unwrap_or_default()
+                })
+                .sum();
+            summary.insert(String::from("co2e"), serde_json::json!(co2e));
+            summary.insert(String::from("co2e_unit"), serde_json::json!("kilograms"));
+        }
+        let max_grade = state_model.get_custom_f64(state, &Self::MAX_GRADE.into())?;
+        summary.insert(String::from(Self::MAX_GRADE), serde_json::json!(max_grade));
+        if self.has_energy_prices() || self.has_value_of_time() {
+            let energy_cost = if self.has_energy_prices() {
+                state_model.get_custom_f64(state, &Self::ENERGY_COST.into())?
+            } else {
+                0.0
+            };
+            let time_cost = if self.has_value_of_time() {
+                state_model.get_custom_f64(state, &Self::TIME_COST.into())?
+            } else {
+                0.0
+            };
+            summary.insert(
+                String::from(Self::ENERGY_COST),
+                serde_json::json!(energy_cost),
+            );
+            summary.insert(String::from(Self::TIME_COST), serde_json::json!(time_cost));
+            summary.insert(
+                String::from("monetary_cost"),
+                serde_json::json!(energy_cost + time_cost),
+            );
+        }
+        let summary = serde_json::Value::Object(summary).round_floats(self.summary_precision);
+        Ok(Some(summary))
+    }
+
+    /// forwards the details reported by the underlying time model
+    fn edge_details(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<Option<serde_json::Value>, TraversalModelError> {
+        self.time_model.edge_details(trajectory, state, state_model)
+    }
+
+    /// cacheable when both the time model and the vehicle are. the grade
+    /// table is shared by every query of this service, so it identifies the
+    /// service in the key. the traversal cache declines to wrap this model
+    /// while the max grade feature is accumulated by max.
+    fn cache_key(&self) -> Option<String> {
+        let time_key = self.time_model.cache_key()?;
+        let vehicle_key = self.vehicle.cache_key()?;
+        Some(format!(
+            "energy:{:p}:{}:{}",
+            Arc::as_ptr(&self.energy_model_service.grade_table),
+            time_key,
+            vehicle_key
+        ))
+        .map(|key| {
+            if !self.has_energy_prices() && !self.has_value_of_time() {
+                return key;
+            }
+            // monetary costs depend on the query prices, which must also match
+            let prices = self
+                .priced_energy_features
+                .iter()
+                .map(|(name, _, price)| format!("{}={}", name, price))
+                .collect::<Vec<_>>();
+            format!("{}:{}:{}", key, prices.join(","), self.value_of_time)
+        })
+    }
+
+    /// validates the time model, then predicts the energy of a short flat
+    /// edge to confirm that the vehicle model loaded and returns finite values
+    fn validate(&self) -> Result<(), TraversalModelError> {
+        self.time_model.validate()?;
+        let state_model = StateModel::empty().extend(self.state_features())?;
+        let mut state = state_model.initial_state()?;
+        let speed_unit = self.energy_model_service.time_model_speed_unit;
+        let test_speed = SpeedUnit::MilesPerHour.convert(&Speed::new(30.0), &speed_unit);
+        self.vehicle.consume_energy(
+            (test_speed, speed_unit),
+            (Grade::ZERO, GradeUnit::Decimal),
+            (Distance::ONE, self.energy_model_service.distance_unit),
+            None,
+            &mut state,
+            &state_model,
+        )?;
+        if state.iter().any(|value| !value.0.is_finite()) {
+            return Err(TraversalModelError::BuildError(format!(
+                "vehicle {} predicted a non-finite energy for a test edge",
+                self.vehicle.name()
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl EnergyTraversalModel {
     const TIME: &'static str = "time";
+    const ENERGY_COST: &'static str = "energy_cost";
+    const TIME_COST: &'static str = "time_cost";
+    const MAX_GRADE: &'static str = "max_grade";
+
+    /// true when any energy feature of the vehicle has a price, in which
+    /// case the model tracks the `energy_cost` state feature
+    fn has_energy_prices(&self) -> bool {
+        !self.priced_energy_features.is_empty()
+    }
+
+    /// true when travel time has a price, in which case the model tracks
+    /// the `time_cost` state feature
+    fn has_value_of_time(&self) -> bool {
+        self.value_of_time != 0.0
+    }
+
+    /// a state feature accumulating a monetary cost, which the cost model
+    /// may weight to minimize the operating cost of a route
+    fn monetary_feature() -> StateFeature {
+        StateFeature::Custom {
+            r#type: String::from("monetary_cost"),
+            unit: String::from("currency"),
+            format: CustomFeatureFormat::FloatingPoint {
+                initial: 0.0.into(),
+            },
+            accumulation: AccumulationOperation::Sum,
+        }
+    }
 
     pub fn new(
         energy_model_service: Arc<EnergyModelService>,
@@ -147,26 +326,188 @@ impl EnergyTraversalModel {
         }?
         .update_from_query(conf)?;
 
+        let summary_energy_unit = match conf.get("summary_energy_unit") {
+            None => None,
+            Some(value) => {
+                let unit = serde_json::from_value::<EnergyUnit>(value.clone()).map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "invalid 'summary_energy_unit' value {}: {}",
+                        value, e
+                    ))
+                })?;
+                Some(unit)
+            }
+        };
+
+        let summary_precision = summary_precision(conf, energy_model_service.summary_precision)?;
+
+        let energy_prices = match conf.get("energy_prices") {
+            None => energy_model_service.energy_prices.clone(),
+            Some(value) => serde_json::from_value::<HashMap<EnergyUnit, f64>>(value.clone())
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "invalid 'energy_prices' value {}: {}",
+                        value, e
+                    ))
+                })?,
+        };
+
+        let value_of_time = match conf.get("value_of_time") {
+            None => energy_model_service.value_of_time,
+            Some(value) => serde_json::from_value::<f64>(value.clone()).map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "invalid 'value_of_time' value {}: {}",
+                    value, e
+                ))
+            })?,
+        };
+
+        let priced_energy_features = vehicle
+            .state_features()
+            .into_iter()
+            .filter_map(|(name, feature)| match feature {
+                StateFeature::Energy { energy_unit, .. } => {
+                    let price = energy_prices.get(&energy_unit).copied()?;
+                    (price != 0.0).then_some((name, energy_unit, price))
+                }
+                _ => None,
+            })
+            .collect();
+
         Ok(EnergyTraversalModel {
             energy_model_service,
             time_model,
             vehicle,
+            summary_energy_unit,
+            summary_precision,
+            energy_prices,
+            priced_energy_features,
+            value_of_time,
         })
     }
+
+    /// traverses an edge, or the part of it given by `fraction`, updating
+    /// the time, max grade, energy and any monetary features of the state
+    fn traverse(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        fraction: Option<f64>,
+        state: &mut Vec<StateVar>,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let distance =
+            BASE_DISTANCE_UNIT.convert(&edge.distance, &self.energy_model_service.distance_unit);
+        let prev = state.to_vec();
+
+        // perform time traversal
+        match fraction {
+            None => self
+                .time_model
+                .traverse_edge(trajectory, state, state_model)?,
+            Some(fraction) => {
+                self.time_model
+                    .traverse_partial_edge(trajectory, fraction, state, state_model)?
+            }
+        }
+        let prev_time = state_model.get_time(
+            &prev,
+            &Self::TIME.into(),
+            &self
+                .energy_model_service
+                .time_model_speed_unit
+                .associated_time_unit(),
+        )?;
+        let current_time = state_model.get_time(
+            state,
+            &Self::TIME.into(),
+            &self
+                .energy_model_service
+                .time_model_speed_unit
+                .associated_time_unit(),
+        )?;
+        let time_delta = current_time - prev_time;
+
+        // perform vehicle energy traversal
+        let grade = get_grade(
+            &self.energy_model_service.grade_table,
+            edge,
+            &self.energy_model_service.missing_grade_policy,
+            &self.energy_model_service.grade_sign_convention,
+            &self.energy_model_service.digitized_direction,
+        )?;
+
+        // the steepest climb of the route is kept by the max accumulation of its feature
+        state_model.accumulate_custom_f64(state, &Self::MAX_GRADE.into(), &grade.as_f64())?;
+
+        let distance_in_time_model_unit = BASE_DISTANCE_UNIT.convert(
+            &edge.distance,
+            &self
+                .energy_model_service
+                .time_model_speed_unit
+                .associated_distance_unit(),
+        );
+        let speed = Speed::from((distance_in_time_model_unit, time_delta));
+        let energy_rate_unit = self
+            .energy_model_service
+            .energy_rate_unit_overrides
+            .get(&edge.edge_id);
+        self.vehicle.consume_energy(
+            (speed, self.energy_model_service.time_model_speed_unit),
+            (grade, self.energy_model_service.grade_table_grade_unit),
+            (distance, self.energy_model_service.distance_unit),
+            energy_rate_unit,
+            state,
+            state_model,
+        )?;
+
+        if self.has_energy_prices() {
+            let mut energy_cost = 0.0;
+            for (name, energy_unit, price) in self.priced_energy_features.iter() {
+                let used = state_model.get_energy(state, name, energy_unit)?
+                    - state_model.get_energy(&prev, name, energy_unit)?;
+                energy_cost += used.as_f64() * price;
+            }
+            state_model.accumulate_custom_f64(state, &Self::ENERGY_COST.into(), &energy_cost)?;
+        }
+        if self.has_value_of_time() {
+            let time_model_time_unit = self
+                .energy_model_service
+                .time_model_speed_unit
+                .associated_time_unit();
+            let hours = time_model_time_unit.convert(&time_delta, &TimeUnit::Hours);
+            let time_cost = hours.as_f64() * self.value_of_time;
+            state_model.accumulate_custom_f64(state, &Self::TIME_COST.into(), &time_cost)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::routee::{
-        prediction::load_prediction_model, prediction::model_type::ModelType,
-        vehicle::default::ice::ICE,
+        prediction::load_prediction_model,
+        prediction::model_type::ModelType,
+        vehicle::default::{bev::BEV, ice::ICE},
     };
     use geo::coord;
+    use routee_compass_core::algorithm::search::{
+        a_star::a_star_algorithm::run_a_star, backtrack::vertex_oriented_route,
+        direction::Direction,
+    };
+    use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
+    use routee_compass_core::test_util::{
+        graph_fixture::graph_from_links,
+        search_instance_builder::{distance_state_model, raw_cost_model, SearchInstanceBuilder},
+    };
     use routee_compass_core::{
         model::{
             property::{edge::Edge, vertex::Vertex},
-            road_network::{edge_id::EdgeId, vertex_id::VertexId},
+            road_network::{
+                edge_id::EdgeId, edge_table_layout::EdgeTableLayout, vertex_id::VertexId,
+            },
             traversal::default::{
                 speed_traversal_engine::SpeedTraversalEngine,
                 speed_traversal_service::SpeedLookupService,
@@ -203,6 +544,7 @@ mod tests {
                 src_vertex_id: VertexId(0),
                 dst_vertex_id: VertexId(1),
                 distance: Distance::new(100.0),
+                closed: false,
             }
         }
         let model_record = load_prediction_model(
@@ -225,8 +567,15 @@ mod tests {
         model_library.insert("Toyota_Camry".to_string(), Arc::new(camry));
 
         let time_engine = Arc::new(
-            SpeedTraversalEngine::new(&speed_file_path, SpeedUnit::KilometersPerHour, None, None)
-                .unwrap(),
+            SpeedTraversalEngine::new(
+                &speed_file_path,
+                SpeedUnit::KilometersPerHour,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
         );
         let time_service = SpeedLookupService { e: time_engine };
 
@@ -239,7 +588,9 @@ mod tests {
             GradeUnit::Millis,
             None,
             None,
+            None,
             model_library,
+            &None,
         )
         .unwrap();
         let arc_service = Arc::new(service);
@@ -257,4 +608,531 @@ mod tests {
             .unwrap();
         println!("{:?}", state);
     }
+
+    fn test_file(filename: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("routee")
+            .join("test")
+            .join(filename)
+    }
+
+    /// builds an energy traversal model for a single vehicle, traverses one
+    /// edge and returns the route summary
+    fn summarize_one_edge(
+        vehicle: Arc<dyn VehicleType>,
+        query: serde_json::Value,
+    ) -> serde_json::Value {
+        summarize_one_edge_with_overrides(vehicle, query, None)
+    }
+
+    fn summarize_one_edge_with_overrides(
+        vehicle: Arc<dyn VehicleType>,
+        query: serde_json::Value,
+        energy_rate_unit_overrides: Option<PathBuf>,
+    ) -> serde_json::Value {
+        summarize_one_edge_with_emissions(
+            vehicle,
+            query,
+            energy_rate_unit_overrides,
+            HashMap::new(),
+        )
+    }
+
+    fn summarize_one_edge_with_emissions(
+        vehicle: Arc<dyn VehicleType>,
+        query: serde_json::Value,
+        energy_rate_unit_overrides: Option<PathBuf>,
+        emissions_factors: HashMap<EnergyUnit, f64>,
+    ) -> serde_json::Value {
+        let time_engine = Arc::new(
+            SpeedTraversalEngine::new(
+                &test_file("velocities.txt"),
+                SpeedUnit::KilometersPerHour,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        let time_service = SpeedLookupService { e: time_engine };
+        let mut model_library: HashMap<String, Arc<dyn VehicleType>> = HashMap::new();
+        model_library.insert(vehicle.name(), vehicle);
+        let mut service = EnergyModelService::new(
+            Arc::new(time_service),
+            SpeedUnit::MilesPerHour,
+            &Some(test_file("grades.txt")),
+            GradeUnit::Millis,
+            None,
+            None,
+            None,
+            model_library,
+            &energy_rate_unit_overrides,
+        )
+        .unwrap();
+        service.emissions_factors = emissions_factors;
+        let model = EnergyTraversalModel::new(Arc::new(service), &query).unwrap();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+        let mut state = state_model.initial_state().unwrap();
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(1),
+            distance: Distance::new(1000.0),
+            closed: false,
+        };
+        model
+            .traverse_edge((&v, &e, &v), &mut state, &state_model)
+            .unwrap();
+        model.summary(&state, &state_model).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_summary_converts_gasoline_to_requested_energy_unit() {
+        let model_record = load_prediction_model(
+            "Toyota_Camry".to_string(),
+            &test_file("Toyota_Camry.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let camry = Arc::new(ICE::new("Toyota_Camry".to_string(), model_record).unwrap());
+
+        // without a requested unit, the total is reported in the native unit
+        let native = summarize_one_edge(
+            camry.clone(),
+            serde_json::json!({"model_name": "Toyota_Camry"}),
+        );
+        assert_eq!(
+            native["energy_liquid_unit"],
+            serde_json::json!("gallons_gasoline")
+        );
+        assert_eq!(native["total_energy"], native["energy_liquid"]);
+        assert_eq!(native["total_energy_label"], serde_json::json!("gallons"));
+        assert!(native.get("co2e").is_none());
+
+        let camry_default_precision = camry.clone();
+        let summary = summarize_one_edge(
+            camry,
+            serde_json::json!({"model_name": "Toyota_Camry", "summary_energy_unit": "kilowatt_hours", "summary_precision": 12}),
+        );
+        let gallons = summary["energy_liquid"].as_f64().unwrap();
+        let kwh = summary["total_energy"].as_f64().unwrap();
+        assert!(gallons > 0.0);
+        assert!((kwh - gallons * 32.26).abs() < 1e-9);
+
+        // by default, energy is rounded to 4 decimal places
+        let rounded = summarize_one_edge(
+            camry_default_precision,
+            serde_json::json!({"model_name": "Toyota_Camry", "summary_energy_unit": "kilowatt_hours"}),
+        );
+        let rounded_gallons = rounded["energy_liquid"].as_f64().unwrap();
+        assert_eq!(rounded_gallons, (gallons * 1e4).round() / 1e4);
+        assert_eq!(
+            summary["total_energy_unit"],
+            serde_json::json!("kilowatt_hours")
+        );
+    }
+
+    #[test]
+    fn test_ice_summary_is_labeled_gallons_with_co2e() {
+        let model_record = load_prediction_model(
+            "Toyota_Camry".to_string(),
+            &test_file("Toyota_Camry.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let camry = Arc::new(ICE::new("Toyota_Camry".to_string(), model_record).unwrap());
+        let factors = HashMap::from([
+            (EnergyUnit::GallonsGasoline, 8.887),
+            (EnergyUnit::KilowattHours, 0.4),
+        ]);
+        let summary = summarize_one_edge_with_emissions(
+            camry,
+            serde_json::json!({"model_name": "Toyota_Camry", "summary_precision": 12}),
+            None,
+            factors,
+        );
+        let gallons = summary["total_energy"].as_f64().unwrap();
+        assert!(gallons > 0.0);
+        assert_eq!(summary["total_energy_label"], serde_json::json!("gallons"));
+        let co2e = summary["co2e"].as_f64().unwrap();
+        assert!((co2e - gallons * 8.887).abs() < 1e-9);
+        assert_eq!(summary["co2e_unit"], serde_json::json!("kilograms"));
+    }
+
+    #[test]
+    fn test_ev_summary_is_labeled_kwh_with_co2e() {
+        let model_record = load_prediction_model(
+            "Chevy_Bolt".to_string(),
+            &test_file("2017_CHEVROLET_Bolt.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::KilowattHoursPerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            EnergyRateUnit::KilowattHoursPerMile.family(),
+            EnergyFamily::Electric
+        );
+        let bolt = Arc::new(BEV::new(
+            "Chevy_Bolt".to_string(),
+            model_record,
+            Energy::new(60.0),
+            Energy::new(60.0),
+            EnergyUnit::KilowattHours,
+        ));
+        let factors = HashMap::from([
+            (EnergyUnit::GallonsGasoline, 8.887),
+            (EnergyUnit::KilowattHours, 0.4),
+        ]);
+        let summary = summarize_one_edge_with_emissions(
+            bolt,
+            serde_json::json!({"model_name": "Chevy_Bolt", "summary_precision": 12}),
+            None,
+            factors,
+        );
+        let kwh = summary["total_energy"].as_f64().unwrap();
+        assert!(kwh > 0.0);
+        assert_eq!(summary["total_energy"], summary["energy_electric"]);
+        assert_eq!(summary["total_energy_label"], serde_json::json!("kWh"));
+        let co2e = summary["co2e"].as_f64().unwrap();
+        assert!((co2e - kwh * 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summary_converts_electric_to_requested_energy_unit() {
+        let model_record = load_prediction_model(
+            "Chevy_Bolt".to_string(),
+            &test_file("2017_CHEVROLET_Bolt.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::KilowattHoursPerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let bolt = Arc::new(BEV::new(
+            "Chevy_Bolt".to_string(),
+            model_record,
+            Energy::new(60.0),
+            Energy::new(60.0),
+            EnergyUnit::KilowattHours,
+        ));
+        let summary = summarize_one_edge(
+            bolt,
+            serde_json::json!({"model_name": "Chevy_Bolt", "summary_energy_unit": "gallons_gasoline", "summary_precision": 12}),
+        );
+        let kwh = summary["energy_electric"].as_f64().unwrap();
+        let gge = summary["total_energy"].as_f64().unwrap();
+        assert_eq!(
+            summary["energy_electric_unit"],
+            serde_json::json!("kilowatt_hours")
+        );
+        assert!((gge - kwh * 0.031).abs() < 1e-9);
+        assert_eq!(
+            summary["total_energy_unit"],
+            serde_json::json!("gallons_gasoline")
+        );
+    }
+
+    #[test]
+    fn test_energy_rate_unit_override_is_converted_to_feature_unit() {
+        let model_record = load_prediction_model(
+            "Toyota_Camry".to_string(),
+            &test_file("Toyota_Camry.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let camry = Arc::new(ICE::new("Toyota_Camry".to_string(), model_record).unwrap());
+        let query = serde_json::json!({"model_name": "Toyota_Camry", "summary_energy_unit": "kilowatt_hours", "summary_precision": 12});
+
+        let default = summarize_one_edge(camry.clone(), query.clone());
+        // edge 0 reinterprets the predicted rate as kilowatt-hours per mile
+        let overridden = summarize_one_edge_with_overrides(
+            camry,
+            query,
+            Some(test_file("energy_rate_unit_overrides.csv")),
+        );
+
+        // the accumulator keeps the vehicle's native unit
+        assert_eq!(
+            overridden["energy_liquid_unit"],
+            serde_json::json!("gallons_gasoline")
+        );
+        let default_gallons = default["energy_liquid"].as_f64().unwrap();
+        let overridden_gallons = overridden["energy_liquid"].as_f64().unwrap();
+        assert!((overridden_gallons - default_gallons * 0.031).abs() < 1e-9);
+        // and the total is reported in a single coherent unit
+        let total_kwh = overridden["total_energy"].as_f64().unwrap();
+        assert!((total_kwh - overridden_gallons * 32.26).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validate_predicts_with_each_vehicle() {
+        let model_record = load_prediction_model(
+            "Toyota_Camry".to_string(),
+            &test_file("Toyota_Camry.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::GallonsGasolinePerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let camry: Arc<dyn VehicleType> =
+            Arc::new(ICE::new("Toyota_Camry".to_string(), model_record).unwrap());
+        let service_with_speeds = |speeds: Vec<Speed>| {
+            let time_engine = SpeedTraversalEngine::from_table(
+                speeds.into_boxed_slice(),
+                SpeedUnit::KilometersPerHour,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            let time_service = SpeedLookupService {
+                e: Arc::new(time_engine),
+            };
+            EnergyModelService::new(
+                Arc::new(time_service),
+                SpeedUnit::KilometersPerHour,
+                &None::<PathBuf>,
+                GradeUnit::Decimal,
+                None,
+                None,
+                None,
+                HashMap::from([(camry.name(), camry.clone())]),
+                &None,
+            )
+            .unwrap()
+        };
+
+        let valid = service_with_speeds(vec![Speed::new(40.0), Speed::new(60.0)]);
+        assert!(valid.validate().is_ok());
+
+        // a bad time model fails the validation of the energy model that wraps it
+        let invalid = service_with_speeds(vec![Speed::new(40.0), Speed::new(f64::NAN)]);
+        let message = invalid.validate().unwrap_err().to_string();
+        assert!(message.contains("Toyota_Camry"), "{}", message);
+    }
+
+    #[test]
+    fn test_reverse_edge_descends_the_forward_climb() {
+        let model_record = load_prediction_model(
+            "Chevy_Bolt".to_string(),
+            &test_file("2017_CHEVROLET_Bolt.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::KilowattHoursPerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let bolt: Arc<dyn VehicleType> = Arc::new(BEV::new(
+            "Chevy_Bolt".to_string(),
+            model_record,
+            Energy::new(60.0),
+            Energy::new(60.0),
+            EnergyUnit::KilowattHours,
+        ));
+        let time_engine = SpeedTraversalEngine::from_table(
+            vec![Speed::new(60.0); 2].into_boxed_slice(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let mut service = EnergyModelService::new(
+            Arc::new(SpeedLookupService {
+                e: Arc::new(time_engine),
+            }),
+            SpeedUnit::KilometersPerHour,
+            &None::<PathBuf>,
+            GradeUnit::Decimal,
+            None,
+            None,
+            None,
+            HashMap::from([(bolt.name(), bolt.clone())]),
+            &None,
+        )
+        .unwrap();
+        // a 5% climb from vertex 0 to vertex 1, mirrored onto its reverse edge
+        // from vertex 1 to vertex 0 as the energy model builder does for a
+        // bidirectional graph
+        let layout = EdgeTableLayout {
+            n_rows: 1,
+            rows: None,
+            bidirectional: true,
+        };
+        let grades = layout.align(&[Grade::new(0.05)], |grade| -*grade).unwrap();
+        service.grade_table = Arc::new(Some(grades));
+        let query = serde_json::json!({ "model_name": "Chevy_Bolt" });
+        let model = EnergyTraversalModel::new(Arc::new(service), &query).unwrap();
+        let state_model = StateModel::empty().extend(model.state_features()).unwrap();
+
+        let v0 = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let v1 = Vertex {
+            vertex_id: VertexId(1),
+            coordinate: InternalCoord(coord! {x: -86.66, y: 36.12}),
+        };
+        let energy = |src: &Vertex, edge: &Edge, dst: &Vertex| {
+            let mut state = state_model.initial_state().unwrap();
+            model
+                .traverse_edge((src, edge, dst), &mut state, &state_model)
+                .unwrap();
+            state_model.get_value(&state, "energy_electric").unwrap().0
+        };
+        let uphill = energy(&v0, &Edge::new(0, 0, 1, 1000.0), &v1);
+        let downhill = energy(&v1, &Edge::new(1, 1, 0, 1000.0), &v0);
+        // the return trip descends, recovering energy with regenerative braking
+        assert!(
+            downhill < 0.0 && downhill < uphill,
+            "returning downhill used {} but climbing used {}",
+            downhill,
+            uphill
+        );
+    }
+
+    #[test]
+    fn test_energy_price_reroutes_onto_flatter_path() {
+        let model_record = load_prediction_model(
+            "Chevy_Bolt".to_string(),
+            &test_file("2017_CHEVROLET_Bolt.bin"),
+            ModelType::Smartcore,
+            SpeedUnit::MilesPerHour,
+            GradeUnit::Decimal,
+            EnergyRateUnit::KilowattHoursPerMile,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let bolt: Arc<dyn VehicleType> = Arc::new(BEV::new(
+            "Chevy_Bolt".to_string(),
+            model_record,
+            Energy::new(60.0),
+            Energy::new(60.0),
+            EnergyUnit::KilowattHours,
+        ));
+        let time_engine = SpeedTraversalEngine::from_table(
+            vec![Speed::new(60.0); 3].into_boxed_slice(),
+            SpeedUnit::KilometersPerHour,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let mut service = EnergyModelService::new(
+            Arc::new(SpeedLookupService {
+                e: Arc::new(time_engine),
+            }),
+            SpeedUnit::KilometersPerHour,
+            &None::<PathBuf>,
+            GradeUnit::Decimal,
+            None,
+            None,
+            None,
+            HashMap::from([(bolt.name(), bolt.clone())]),
+            &None,
+        )
+        .unwrap();
+        // edge 0 climbs 8% directly to the destination, while edges 1 and 2
+        // take a flat detour that is 40% longer
+        service.grade_table = Arc::new(Some(
+            vec![Grade::new(0.08), Grade::ZERO, Grade::ZERO].into_boxed_slice(),
+        ));
+        service.value_of_time = 20.0;
+        let service = Arc::new(service);
+        let graph = graph_from_links(3, &[(0, 2, 1000.0), (0, 1, 700.0), (1, 2, 700.0)]);
+        let graph = Arc::new(graph);
+
+        // the edges of the route found when minimizing the operating cost of
+        // the vehicle, which is the sum of its energy and time costs
+        let cheapest_route = |price: f64| {
+            let query = serde_json::json!({
+                "model_name": "Chevy_Bolt",
+                "energy_prices": { "kilowatt_hours": price },
+            });
+            let model = Arc::new(EnergyTraversalModel::new(service.clone(), &query).unwrap());
+            let state_model = Arc::new(
+                distance_state_model()
+                    .extend(model.state_features())
+                    .unwrap(),
+            );
+            let cost_model = raw_cost_model(&["energy_cost", "time_cost"], state_model.clone());
+            let si = SearchInstanceBuilder::new(graph.clone())
+                .traversal_model(model.clone())
+                .state_model(state_model.clone())
+                .cost_model(cost_model)
+                .build();
+            let tree = run_a_star(
+                VertexId(0),
+                Some(VertexId(2)),
+                &Direction::Forward,
+                None,
+                None,
+                &si,
+            )
+            .unwrap()
+            .tree;
+            let route = vertex_oriented_route(VertexId(0), VertexId(2), &tree).unwrap();
+            let state = &route.last().unwrap().result_state;
+            let summary = model.summary(state, &state_model).unwrap().unwrap();
+            let energy_cost = summary["energy_cost"].as_f64().unwrap();
+            let time_cost = summary["time_cost"].as_f64().unwrap();
+            let total = summary["monetary_cost"].as_f64().unwrap();
+            assert!((energy_cost + time_cost - total).abs() < 1e-3);
+            let route_cost: Cost = route.iter().map(|e| e.total_cost()).sum();
+            assert!((route_cost.as_f64() - total).abs() < 1e-3);
+            // the steepest grade climbed is kept across the edges of the route
+            let steepest = if route.len() == 1 { 0.08 } else { 0.0 };
+            assert_eq!(summary["max_grade"].as_f64(), Some(steepest));
+            route.iter().map(|e| e.edge_id).collect::<Vec<_>>()
+        };
+
+        // cheap electricity favors the shorter, faster climb
+        assert_eq!(cheapest_route(0.1), vec![EdgeId(0)]);
+        // expensive electricity reroutes onto the flatter, longer detour
+        assert_eq!(cheapest_route(5.0), vec![EdgeId(1), EdgeId(2)]);
+    }
 }
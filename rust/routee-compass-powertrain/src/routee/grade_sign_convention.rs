@@ -0,0 +1,40 @@
+use routee_compass_core::model::unit::Grade;
+use serde::{Deserialize, Serialize};
+
+/// declares which way a positive grade in the grade table points. data
+/// providers disagree on this, and reading grades with the wrong convention
+/// inverts the energy spent climbing and regained descending.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GradeSignConvention {
+    /// a positive grade is uphill in the direction of travel of the edge
+    #[default]
+    DirectionOfTravel,
+    /// a positive grade is uphill along the digitized direction of a road
+    /// segment, the direction in which its geometry was drawn. both directed
+    /// edges of a segment carry the same grade, and a per-edge digitized
+    /// direction flag tells whether an edge runs along the digitized direction
+    /// or against it, in which case the grade is negated.
+    Segment,
+}
+
+impl GradeSignConvention {
+    /// orients a grade from the grade table so that a positive grade is
+    /// uphill in the direction of travel of the edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `grade` - the grade of the edge as read from the grade table
+    /// * `along_digitized` - true if the edge runs along the digitized direction of its segment
+    ///
+    /// # Returns
+    ///
+    /// the grade in the direction of travel
+    pub fn directed_grade(&self, grade: Grade, along_digitized: bool) -> Grade {
+        match self {
+            GradeSignConvention::DirectionOfTravel => grade,
+            GradeSignConvention::Segment if along_digitized => grade,
+            GradeSignConvention::Segment => -grade,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use routee_compass_core::model::unit::Grade;
+use serde::{Deserialize, Serialize};
+
+/// describes how to proceed when a grade table exists but does not
+/// contain an entry for some edge.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MissingGradePolicy {
+    /// fail the traversal with a missing id error
+    #[default]
+    Error,
+    /// treat the edge as flat
+    Zero,
+    /// use a global default grade, in the grade table grade unit
+    Default { grade: Grade },
+}
@@ -1,5 +1,7 @@
 pub mod energy_model_ops;
 pub mod energy_model_service;
 pub mod energy_traversal_model;
+pub mod grade_sign_convention;
+pub mod missing_grade_policy;
 pub mod prediction;
 pub mod vehicle;
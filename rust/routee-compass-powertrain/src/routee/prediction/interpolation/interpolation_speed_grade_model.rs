@@ -99,6 +99,7 @@ impl InterpolationSpeedGradeModel {
                         (Speed::new(speed_value), speed_unit),
                         (Grade::new(grade_value), grade_unit),
                         (distance, distance_unit),
+                        None,
                     )
                     .map_err(|e| TraversalModelError::PredictionModel(e.to_string()))?;
                 row.push(energy.as_f64());
@@ -0,0 +1,130 @@
+use routee_compass_core::model::unit::{
+    as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyUnit, Grade, GradeUnit, Weight, WeightUnit,
+};
+use serde::{Deserialize, Serialize};
+
+/// gravitational acceleration in m/s^2
+const GRAVITY: f64 = 9.81;
+/// joules per kilowatt-hour
+const JOULES_PER_KWH: f64 = 3_600_000.0;
+
+/// a physics-based correction for a vehicle carrying a different mass than
+/// the one its energy model was trained at. only the potential energy
+/// component is corrected:
+///
+/// `ΔE = c * (m - m_ref) * g * grade * d / η`
+///
+/// where `m` and `m_ref` are in kilograms, `g` is 9.81 m/s^2, `grade` is a
+/// decimal ratio, `d` is in meters and `η` is the drivetrain efficiency. the
+/// result is computed in joules and converted to the energy unit of the
+/// prediction model. a heavier vehicle uses more energy climbing and less
+/// energy descending than the reference vehicle.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct MassAdjustment {
+    /// mass of the vehicle the energy model was trained with
+    pub reference_mass: Weight,
+    /// unit of the reference mass
+    pub mass_unit: WeightUnit,
+    /// scales the potential energy term, `c` in the formula
+    #[serde(default = "MassAdjustment::default_coefficient")]
+    pub potential_energy_coefficient: f64,
+    /// fraction of stored energy delivered to the wheels, `η` in the formula
+    #[serde(default = "MassAdjustment::default_coefficient")]
+    pub drivetrain_efficiency: f64,
+}
+
+impl MassAdjustment {
+    fn default_coefficient() -> f64 {
+        1.0
+    }
+
+    /// computes the additional energy required to traverse an edge with
+    /// some vehicle mass relative to the reference mass.
+    ///
+    /// # Arguments
+    ///
+    /// * `mass` - mass of the vehicle for this query
+    /// * `grade` - grade of the edge
+    /// * `distance` - length of the edge
+    /// * `energy_unit` - energy unit of the result
+    ///
+    /// # Returns
+    ///
+    /// the energy delta, which is negative for a downhill edge or a vehicle
+    /// lighter than the reference mass
+    pub fn energy_delta(
+        &self,
+        mass: (Weight, WeightUnit),
+        grade: (Grade, GradeUnit),
+        distance: (Distance, DistanceUnit),
+        energy_unit: &EnergyUnit,
+    ) -> Energy {
+        let (mass, mass_unit) = mass;
+        let (grade, grade_unit) = grade;
+        let (distance, distance_unit) = distance;
+        let mass_kg = mass_unit.convert(&mass, &WeightUnit::Kg).as_f64();
+        let reference_kg = self
+            .mass_unit
+            .convert(&self.reference_mass, &WeightUnit::Kg)
+            .as_f64();
+        let grade_ratio = grade_unit.convert(&grade, &GradeUnit::Decimal).as_f64();
+        let distance_m = distance_unit
+            .convert(&distance, &DistanceUnit::Meters)
+            .as_f64();
+
+        let joules = self.potential_energy_coefficient
+            * (mass_kg - reference_kg)
+            * GRAVITY
+            * grade_ratio
+            * distance_m
+            / self.drivetrain_efficiency;
+        let kwh = Energy::new(joules / JOULES_PER_KWH);
+        EnergyUnit::KilowattHours.convert(&kwh, energy_unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjustment() -> MassAdjustment {
+        MassAdjustment {
+            reference_mass: Weight::new(1000.0),
+            mass_unit: WeightUnit::Kg,
+            potential_energy_coefficient: 1.0,
+            drivetrain_efficiency: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_energy_delta() {
+        // an extra 1000 kg climbing 36.7 m (10% grade over 367 m) takes
+        // 1000 * 9.81 * 36.7 J = 360027 J = 0.1 kWh
+        let delta = adjustment().energy_delta(
+            (Weight::new(2000.0), WeightUnit::Kg),
+            (Grade::new(10.0), GradeUnit::Percent),
+            (Distance::new(367.0), DistanceUnit::Meters),
+            &EnergyUnit::KilowattHours,
+        );
+        assert!((delta.as_f64() - 0.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_energy_delta_at_reference_mass_or_flat() {
+        let adj = adjustment();
+        let at_reference = adj.energy_delta(
+            (Weight::new(1000.0), WeightUnit::Kg),
+            (Grade::new(10.0), GradeUnit::Percent),
+            (Distance::new(1.0), DistanceUnit::Miles),
+            &EnergyUnit::KilowattHours,
+        );
+        assert_eq!(at_reference, Energy::ZERO);
+        let flat = adj.energy_delta(
+            (Weight::new(5000.0), WeightUnit::Pounds),
+            (Grade::ZERO, GradeUnit::Percent),
+            (Distance::new(1.0), DistanceUnit::Miles),
+            &EnergyUnit::KilowattHours,
+        );
+        assert_eq!(flat, Energy::ZERO);
+    }
+}
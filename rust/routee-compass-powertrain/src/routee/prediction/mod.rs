@@ -1,4 +1,5 @@
 pub mod interpolation;
+pub mod mass_adjustment;
 pub mod model_type;
 pub mod prediction_model;
 pub mod prediction_model_ops;
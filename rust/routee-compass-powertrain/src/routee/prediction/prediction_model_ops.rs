@@ -92,6 +92,7 @@ pub fn load_prediction_model<P: AsRef<Path>>(
         energy_rate_unit,
         ideal_energy_rate,
         real_world_energy_adjustment,
+        mass_adjustment: None,
         cache,
     })
 }
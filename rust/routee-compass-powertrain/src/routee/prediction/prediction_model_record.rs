@@ -4,12 +4,12 @@ use routee_compass_core::{
     model::traversal::traversal_model_error::TraversalModelError,
     model::unit::{
         as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyRate, EnergyRateUnit, EnergyUnit,
-        Grade, GradeUnit, Speed, SpeedUnit,
+        Grade, GradeUnit, Speed, SpeedUnit, Weight, WeightUnit,
     },
     util::cache_policy::float_cache_policy::FloatCachePolicy,
 };
 
-use super::{model_type::ModelType, PredictionModel};
+use super::{mass_adjustment::MassAdjustment, model_type::ModelType, PredictionModel};
 /// A struct to hold the prediction model and associated metadata
 pub struct PredictionModelRecord {
     pub name: String,
@@ -20,15 +20,21 @@ pub struct PredictionModelRecord {
     pub energy_rate_unit: EnergyRateUnit,
     pub ideal_energy_rate: EnergyRate,
     pub real_world_energy_adjustment: f64,
+    pub mass_adjustment: Option<MassAdjustment>,
     pub cache: Option<FloatCachePolicy>,
 }
 
 impl PredictionModelRecord {
+    /// predicts the energy to traverse some distance at a speed and grade.
+    /// the predicted rate is interpreted in the `energy_rate_unit` override
+    /// when provided, otherwise in this record's energy rate unit. the
+    /// returned energy unit reflects whichever rate unit was used.
     pub fn predict(
         &self,
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
         distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
     ) -> Result<(Energy, EnergyUnit), TraversalModelError> {
         let (distance, distance_unit) = distance;
 
@@ -56,11 +62,32 @@ impl PredictionModelRecord {
 
         let (energy, energy_unit) = Energy::create(
             &energy_rate_real_world,
-            &self.energy_rate_unit,
+            energy_rate_unit.unwrap_or(&self.energy_rate_unit),
             &distance,
             &distance_unit,
         )?;
 
         Ok((energy, energy_unit))
     }
+
+    /// predicts energy as in `predict`, then applies the mass adjustment for
+    /// this record when both the adjustment and a vehicle mass are present.
+    /// otherwise, the result is identical to `predict`.
+    pub fn predict_with_mass(
+        &self,
+        speed: (Speed, SpeedUnit),
+        grade: (Grade, GradeUnit),
+        distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
+        mass: Option<(Weight, WeightUnit)>,
+    ) -> Result<(Energy, EnergyUnit), TraversalModelError> {
+        let (energy, energy_unit) = self.predict(speed, grade, distance, energy_rate_unit)?;
+        match (&self.mass_adjustment, mass) {
+            (Some(adjustment), Some(mass)) => {
+                let delta = adjustment.energy_delta(mass, grade, distance, &energy_unit);
+                Ok((energy + delta, energy_unit))
+            }
+            _ => Ok((energy, energy_unit)),
+        }
+    }
 }
@@ -4,13 +4,13 @@ use crate::routee::{
 };
 use routee_compass_core::model::{
     state::{
-        custom_feature_format::CustomFeatureFormat, state_feature::StateFeature,
-        state_model::StateModel,
+        accumulation_operation::AccumulationOperation, custom_feature_format::CustomFeatureFormat,
+        state_feature::StateFeature, state_model::StateModel,
     },
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
     unit::{
-        as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyUnit, Grade, GradeUnit, Speed,
-        SpeedUnit,
+        as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyRateUnit, EnergyUnit, Grade,
+        GradeUnit, Speed, SpeedUnit, Weight, WeightUnit,
     },
 };
 use std::sync::Arc;
@@ -21,6 +21,7 @@ pub struct BEV {
     pub battery_capacity: Energy,
     pub starting_battery_energy: Energy,
     pub battery_energy_unit: EnergyUnit,
+    pub vehicle_mass: Option<(Weight, WeightUnit)>,
 }
 
 impl BEV {
@@ -40,6 +41,7 @@ impl BEV {
             battery_capacity,
             starting_battery_energy,
             battery_energy_unit,
+            vehicle_mass: None,
         }
     }
 }
@@ -68,6 +70,7 @@ impl VehicleType for BEV {
                     format: CustomFeatureFormat::FloatingPoint {
                         initial: initial_soc.into(),
                     },
+                    accumulation: AccumulationOperation::default(),
                 },
             ),
         ]
@@ -117,12 +120,17 @@ impl VehicleType for BEV {
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
         distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
-        let (predicted_energy, energy_unit) = self
-            .prediction_model_record
-            .predict(speed, grade, distance)?;
+        let (predicted_energy, energy_unit) = self.prediction_model_record.predict_with_mass(
+            speed,
+            grade,
+            distance,
+            energy_rate_unit,
+            self.vehicle_mass,
+        )?;
         let battery_delta = energy_unit.convert(&predicted_energy, &self.battery_energy_unit);
         state_model.add_energy(
             state,
@@ -161,6 +169,7 @@ impl VehicleType for BEV {
 
         let starting_battery_energy =
             Energy::new(starting_soc_percent * self.battery_capacity.as_f64());
+        let vehicle_mass = vehicle_ops::get_vehicle_mass(query)?;
 
         let new_bev = BEV {
             name: self.name.clone(),
@@ -168,6 +177,7 @@ impl VehicleType for BEV {
             battery_capacity: self.battery_capacity,
             starting_battery_energy,
             battery_energy_unit: self.battery_energy_unit,
+            vehicle_mass,
         };
 
         Ok(Arc::new(new_bev))
@@ -177,7 +187,10 @@ impl VehicleType for BEV {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::routee::{prediction::load_prediction_model, prediction::model_type::ModelType};
+    use crate::routee::{
+        prediction::load_prediction_model, prediction::mass_adjustment::MassAdjustment,
+        prediction::model_type::ModelType,
+    };
     use routee_compass_core::model::unit::{as_f64::AsF64, EnergyRate, EnergyRateUnit};
     use std::path::PathBuf;
 
@@ -236,7 +249,7 @@ mod tests {
         let grade = (Grade::new(0.0), GradeUnit::Decimal);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let elec = state_model
@@ -271,7 +284,7 @@ mod tests {
         let grade = (Grade::new(-5.0), GradeUnit::Percent);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let elec = state_model
@@ -308,7 +321,7 @@ mod tests {
         let grade = (Grade::new(-5.0), GradeUnit::Percent);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let battery_percent_soc = state_model
@@ -331,7 +344,7 @@ mod tests {
         let grade = (Grade::new(5.0), GradeUnit::Percent);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let battery_percent_soc = state_model
@@ -339,4 +352,54 @@ mod tests {
             .unwrap();
         assert!(battery_percent_soc >= 0.0);
     }
+
+    #[test]
+    fn test_bev_loaded_vs_empty_on_grade() {
+        let mut loaded = mock_vehicle(100.0);
+        let mut record = Arc::try_unwrap(loaded.prediction_model_record)
+            .ok()
+            .expect("record should not be shared");
+        record.mass_adjustment = Some(MassAdjustment {
+            reference_mass: Weight::new(1600.0),
+            mass_unit: WeightUnit::Kg,
+            potential_energy_coefficient: 1.0,
+            drivetrain_efficiency: 0.9,
+        });
+        let record = Arc::new(record);
+        loaded.prediction_model_record = record.clone();
+        loaded.vehicle_mass = Some((Weight::new(2600.0), WeightUnit::Kg));
+        let mut empty = mock_vehicle(100.0);
+        empty.prediction_model_record = record;
+
+        let state_model = StateModel::empty().extend(loaded.state_features()).unwrap();
+        let distance = (Distance::new(1.0), DistanceUnit::Miles);
+        let speed = (Speed::new(40.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(5.0), GradeUnit::Percent);
+
+        let mut energies = vec![];
+        for vehicle in [&empty, &loaded] {
+            let mut state = state_model.initial_state().unwrap();
+            vehicle
+                .consume_energy(speed, grade, distance, None, &mut state, &state_model)
+                .unwrap();
+            let elec = state_model
+                .get_energy(
+                    &state,
+                    &BEV::ENERGY_FEATURE_NAME.into(),
+                    &EnergyUnit::KilowattHours,
+                )
+                .unwrap();
+            energies.push(elec.as_f64());
+        }
+
+        // an extra 1000 kg climbing 80.47 m at 90% efficiency needs about 0.2437 kWh more
+        let expected_delta = 1000.0 * 9.81 * 0.05 * 1609.34 / 0.9 / 3_600_000.0;
+        let delta = energies[1] - energies[0];
+        assert!(
+            (delta - expected_delta).abs() < 1e-3,
+            "delta {} should be close to {}",
+            delta,
+            expected_delta
+        );
+    }
 }
@@ -1,14 +1,21 @@
-use crate::routee::{prediction::PredictionModelRecord, vehicle::VehicleType};
+use crate::routee::{
+    prediction::PredictionModelRecord,
+    vehicle::{vehicle_ops, VehicleType},
+};
 use routee_compass_core::model::{
     state::{state_feature::StateFeature, state_model::StateModel},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
-    unit::{Distance, DistanceUnit, Energy, EnergyUnit, Grade, GradeUnit, Speed, SpeedUnit},
+    unit::{
+        Distance, DistanceUnit, Energy, EnergyRateUnit, EnergyUnit, Grade, GradeUnit, Speed,
+        SpeedUnit, Weight, WeightUnit,
+    },
 };
 use std::sync::Arc;
 
 pub struct ICE {
     pub name: String,
     pub prediction_model_record: Arc<PredictionModelRecord>,
+    pub vehicle_mass: Option<(Weight, WeightUnit)>,
 }
 
 impl ICE {
@@ -20,6 +27,7 @@ impl ICE {
         Ok(Self {
             name,
             prediction_model_record: Arc::new(prediction_model_record),
+            vehicle_mass: None,
         })
     }
 }
@@ -79,32 +87,45 @@ impl VehicleType for ICE {
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
         distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
-        let (energy, _energy_unit) = self
-            .prediction_model_record
-            .predict(speed, grade, distance)?;
+        let (energy, energy_unit) = self.prediction_model_record.predict_with_mass(
+            speed,
+            grade,
+            distance,
+            energy_rate_unit,
+            self.vehicle_mass,
+        )?;
         state_model.add_energy(
             state,
             &ICE::ENERGY_FEATURE_NAME.into(),
             &energy,
-            &self
-                .prediction_model_record
-                .energy_rate_unit
-                .associated_energy_unit(),
+            &energy_unit,
         )?;
         Ok(())
     }
 
     fn update_from_query(
         &self,
-        _query: &serde_json::Value,
+        query: &serde_json::Value,
     ) -> Result<Arc<dyn VehicleType>, TraversalModelError> {
-        // just return a clone of self
+        let vehicle_mass = vehicle_ops::get_vehicle_mass(query)?;
         Ok(Arc::new(ICE {
             name: self.name.clone(),
             prediction_model_record: self.prediction_model_record.clone(),
+            vehicle_mass,
         }))
     }
+
+    /// liquid fuel consumption does not depend on the state
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "{}:{:p}:{:?}",
+            self.name,
+            Arc::as_ptr(&self.prediction_model_record),
+            self.vehicle_mass
+        ))
+    }
 }
@@ -4,13 +4,13 @@ use crate::routee::{
 };
 use routee_compass_core::model::{
     state::{
-        custom_feature_format::CustomFeatureFormat, state_feature::StateFeature,
-        state_model::StateModel,
+        accumulation_operation::AccumulationOperation, custom_feature_format::CustomFeatureFormat,
+        state_feature::StateFeature, state_model::StateModel,
     },
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
     unit::{
-        as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyUnit, Grade, GradeUnit, Speed,
-        SpeedUnit,
+        as_f64::AsF64, Distance, DistanceUnit, Energy, EnergyRateUnit, EnergyUnit, Grade,
+        GradeUnit, Speed, SpeedUnit, Weight, WeightUnit,
     },
 };
 use std::sync::Arc;
@@ -23,6 +23,7 @@ pub struct PHEV {
     pub starting_battery_energy: Energy,
     pub battery_energy_unit: EnergyUnit,
     pub custom_liquid_fuel_to_kwh: Option<f64>,
+    pub vehicle_mass: Option<(Weight, WeightUnit)>,
 }
 
 impl PHEV {
@@ -47,6 +48,7 @@ impl PHEV {
             starting_battery_energy,
             battery_energy_unit,
             custom_liquid_fuel_to_kwh,
+            vehicle_mass: None,
         })
     }
 }
@@ -79,6 +81,7 @@ impl VehicleType for PHEV {
                     format: CustomFeatureFormat::FloatingPoint {
                         initial: initial_soc.into(),
                     },
+                    accumulation: AccumulationOperation::default(),
                 },
             ),
             (
@@ -135,12 +138,13 @@ impl VehicleType for PHEV {
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
         distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let start_soc = state_model.get_custom_f64(state, &PHEV::SOC_FEATURE_NAME.into())?;
         let (elec_energy, elec_unit, liq_energy, liq_unit) =
-            get_phev_energy(self, start_soc, speed, grade, distance)?;
+            get_phev_energy(self, start_soc, speed, grade, distance, energy_rate_unit)?;
 
         state_model.add_energy(
             state,
@@ -190,6 +194,7 @@ impl VehicleType for PHEV {
         }
         let starting_battery_energy =
             Energy::new(starting_soc_percent * self.battery_capacity.as_f64());
+        let vehicle_mass = vehicle_ops::get_vehicle_mass(query)?;
 
         let new_phev = PHEV {
             name: self.name.clone(),
@@ -199,21 +204,24 @@ impl VehicleType for PHEV {
             starting_battery_energy,
             battery_energy_unit: self.battery_energy_unit,
             custom_liquid_fuel_to_kwh: self.custom_liquid_fuel_to_kwh,
+            vehicle_mass,
         };
 
         Ok(Arc::new(new_phev))
     }
 }
 
-/// Compute the energy for the PHEV by converting liquid_fuel to kWh.
-/// This uses a simplified operation in which we assume that if the battery
-/// SOC is greater than zero we can just operate on battery to traverse a link.
-/// This is not entirely realistic as it's possible to arrive at a link with
-/// 0.001% SOC and still need to use liquid_fuel to traverse the link.
+/// Compute the energy for the PHEV, which operates in one of two regimes.
+/// While the battery has charge, the vehicle runs in charge depleting mode on
+/// electric energy. Once the battery is depleted it runs in charge sustaining
+/// mode on liquid fuel.
 ///
-/// In the future we could make this more sophisticated by calculating
-/// the energy required to traverse the link using the battery and then
-/// finding the point at which we would have to switch to liquid_fuel
+/// When the battery runs out partway along a link, the link is split at the
+/// point where the remaining battery energy is used up: the distance before
+/// that point is electric and the remaining distance is liquid fuel, each
+/// predicted by its own model at the link's speed and grade.
+///
+/// The optional energy rate unit override applies to both models.
 ///
 /// Returns a tuple of (electrical_energy, electrical_energy_unit, liquid_fuel_energy, liquid_fuel_energy_unit)
 fn get_phev_energy(
@@ -222,39 +230,74 @@ fn get_phev_energy(
     speed: (Speed, SpeedUnit),
     grade: (Grade, GradeUnit),
     distance: (Distance, DistanceUnit),
+    energy_rate_unit: Option<&EnergyRateUnit>,
 ) -> Result<(Energy, EnergyUnit, Energy, EnergyUnit), TraversalModelError> {
     let electrical_energy_unit = vehicle
         .charge_depleting_model
         .energy_rate_unit
         .associated_energy_unit();
-    let liquid_fuel_energy_unit = vehicle
-        .charge_sustain_model
-        .energy_rate_unit
-        .associated_energy_unit();
+    let predict_liquid_fuel = |distance: (Distance, DistanceUnit)| {
+        vehicle.charge_sustain_model.predict_with_mass(
+            speed,
+            grade,
+            distance,
+            energy_rate_unit,
+            vehicle.vehicle_mass,
+        )
+    };
 
-    if battery_soc_percent > 0.0 {
-        // assume we can just use the battery
-        let (electrical_energy, electrical_energy_unit) = vehicle
-            .charge_depleting_model
-            .predict(speed, grade, distance)?;
-        Ok((
-            electrical_energy,
+    if battery_soc_percent <= 0.0 {
+        // just use the liquid_fuel engine
+        let (liquid_fuel_energy, liquid_fuel_energy_unit) = predict_liquid_fuel(distance)?;
+        return Ok((
+            Energy::ZERO,
             electrical_energy_unit,
-            Energy::new(0.0),
+            liquid_fuel_energy,
             liquid_fuel_energy_unit,
-        ))
-    } else {
-        // just use the liquid_fuel engine
-        let (liquid_fuel_energy, liquid_fuel_energy_unit) = vehicle
+        ));
+    }
+
+    let (electrical_energy, electrical_energy_unit) =
+        vehicle.charge_depleting_model.predict_with_mass(
+            speed,
+            grade,
+            distance,
+            energy_rate_unit,
+            vehicle.vehicle_mass,
+        )?;
+    let remaining_battery =
+        Energy::new(vehicle.battery_capacity.as_f64() * battery_soc_percent / 100.0);
+    let remaining_battery = vehicle
+        .battery_energy_unit
+        .convert(&remaining_battery, &electrical_energy_unit);
+
+    if electrical_energy <= remaining_battery {
+        // the battery covers the whole link
+        let liquid_fuel_energy_unit = vehicle
             .charge_sustain_model
-            .predict(speed, grade, distance)?;
-        Ok((
-            Energy::new(0.0),
+            .energy_rate_unit
+            .associated_energy_unit();
+        return Ok((
+            electrical_energy,
             electrical_energy_unit,
-            liquid_fuel_energy,
+            Energy::ZERO,
             liquid_fuel_energy_unit,
-        ))
+        ));
     }
+
+    // the battery is depleted partway along the link, so the rest of the
+    // link is traversed on liquid fuel
+    let (distance, distance_unit) = distance;
+    let electric_fraction = remaining_battery.as_f64() / electrical_energy.as_f64();
+    let liquid_fuel_distance = Distance::new(distance.as_f64() * (1.0 - electric_fraction));
+    let (liquid_fuel_energy, liquid_fuel_energy_unit) =
+        predict_liquid_fuel((liquid_fuel_distance, distance_unit))?;
+    Ok((
+        remaining_battery,
+        electrical_energy_unit,
+        liquid_fuel_energy,
+        liquid_fuel_energy_unit,
+    ))
 }
 
 #[cfg(test)]
@@ -340,7 +383,7 @@ mod tests {
         let grade = (Grade::new(0.0), GradeUnit::Decimal);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let elec = state_model
@@ -386,7 +429,7 @@ mod tests {
         let grade = (Grade::new(0.0), GradeUnit::Decimal);
 
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let elec = state_model
@@ -407,13 +450,18 @@ mod tests {
             )
             .unwrap();
 
-        assert!(elec > Energy::ZERO, "elec energy {} should be > 0", elec);
+        // the battery is depleted partway along the link, so the rest of it uses liquid fuel
+        assert!(
+            (elec.as_f64() - 12.0).abs() < 1e-6,
+            "elec energy {} should be the full 12 kWh battery",
+            elec
+        );
         assert!(soc < 1e-9, "soc {} should be miniscule, < {}", soc, 1e-9);
-        assert!(liquid == Energy::ZERO, "should not have used liquid energy");
+        assert!(liquid > Energy::ZERO, "should have used liquid energy");
 
         // and then traverse the same distance but this time we should only use liquid_fuel energy
         vehicle
-            .consume_energy(speed, grade, distance, &mut state, &state_model)
+            .consume_energy(speed, grade, distance, None, &mut state, &state_model)
             .unwrap();
 
         let liquid_energy_2 = state_model
@@ -426,4 +474,79 @@ mod tests {
 
         assert!(liquid_energy_2 > Energy::ZERO);
     }
+    #[test]
+    fn test_phev_energy_model_depletes_partway_along_route() {
+        let vehicle = mock_vehicle();
+        let state_model = StateModel::empty()
+            .extend(vehicle.state_features())
+            .unwrap();
+        let mut state = state_model.initial_state().unwrap();
+
+        // a route of 10 mile links, where the 12 kWh battery runs out on the 4th or 5th link
+        let distance = (Distance::new(10.0), DistanceUnit::Miles);
+        let speed = (Speed::new(60.0), SpeedUnit::MilesPerHour);
+        let grade = (Grade::new(0.0), GradeUnit::Decimal);
+
+        let mut link_energy = vec![];
+        for _ in 0..8 {
+            let elec_before = state_model
+                .get_energy(
+                    &state,
+                    &PHEV::ELECTRIC_FEATURE_NAME.into(),
+                    &EnergyUnit::KilowattHours,
+                )
+                .unwrap();
+            let liquid_before = state_model
+                .get_energy(
+                    &state,
+                    &PHEV::LIQUID_FEATURE_NAME.into(),
+                    &EnergyUnit::GallonsGasoline,
+                )
+                .unwrap();
+            vehicle
+                .consume_energy(speed, grade, distance, None, &mut state, &state_model)
+                .unwrap();
+            let elec_after = state_model
+                .get_energy(
+                    &state,
+                    &PHEV::ELECTRIC_FEATURE_NAME.into(),
+                    &EnergyUnit::KilowattHours,
+                )
+                .unwrap();
+            let liquid_after = state_model
+                .get_energy(
+                    &state,
+                    &PHEV::LIQUID_FEATURE_NAME.into(),
+                    &EnergyUnit::GallonsGasoline,
+                )
+                .unwrap();
+            link_energy.push((
+                (elec_after - elec_before).as_f64(),
+                (liquid_after - liquid_before).as_f64(),
+            ));
+        }
+
+        // links are all electric, then one link uses both, then all liquid fuel
+        let mixed = link_energy
+            .iter()
+            .position(|(_, liquid)| *liquid > 0.0)
+            .expect("battery should deplete along the route");
+        assert!(mixed > 0, "the first link should be all electric");
+        let (elec, _) = link_energy[mixed];
+        let (full_elec, _) = link_energy[0];
+        assert!(
+            elec > 0.0 && elec < full_elec,
+            "depleting link should use part of the electric energy of a full link, {} < {}",
+            elec,
+            full_elec
+        );
+        for (elec, liquid) in link_energy[..mixed].iter() {
+            assert!(*elec > 0.0 && *liquid == 0.0);
+        }
+        for (elec, liquid) in link_energy[mixed + 1..].iter() {
+            assert!(*elec == 0.0 && *liquid > 0.0);
+        }
+        let total_elec: f64 = link_energy.iter().map(|(elec, _)| elec).sum();
+        assert!((total_elec - 12.0).abs() < 1e-6);
+    }
 }
@@ -1,7 +1,7 @@
 use routee_compass_core::model::{
     state::{state_error::StateError, state_model::StateModel},
-    traversal::state::state_variable::StateVar,
-    unit::{as_f64::AsF64, Energy},
+    traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
+    unit::{as_f64::AsF64, Energy, Weight, WeightUnit},
 };
 
 /// updates the SOC feature for a vehicle type with a battery based on the
@@ -72,3 +72,42 @@ pub fn soc_from_battery_and_delta(
     let percent_remaining = (current_energy.as_f64() / max_battery.as_f64()) * 100.0;
     percent_remaining.max(0.0).min(100.0)
 }
+
+/// reads the optional vehicle mass from a query. the mass is given by
+/// `vehicle_mass` in `vehicle_mass_unit`, which defaults to kilograms.
+///
+/// # Arguments
+///
+/// * `query` - the incoming query
+///
+/// # Returns
+///
+/// the vehicle mass if provided, or an error if the fields are malformed
+pub fn get_vehicle_mass(
+    query: &serde_json::Value,
+) -> Result<Option<(Weight, WeightUnit)>, TraversalModelError> {
+    let mass = match query.get("vehicle_mass") {
+        None => return Ok(None),
+        Some(v) => v.as_f64().ok_or_else(|| {
+            TraversalModelError::BuildError(String::from(
+                "Expected 'vehicle_mass' value to be numeric",
+            ))
+        })?,
+    };
+    if mass < 0.0 {
+        return Err(TraversalModelError::BuildError(format!(
+            "Expected 'vehicle_mass' value to be non-negative, found {}",
+            mass
+        )));
+    }
+    let mass_unit = match query.get("vehicle_mass_unit") {
+        None => WeightUnit::Kg,
+        Some(v) => serde_json::from_value::<WeightUnit>(v.clone()).map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "Expected 'vehicle_mass_unit' to be a weight unit: {}",
+                e
+            ))
+        })?,
+    };
+    Ok(Some((Weight::new(mass), mass_unit)))
+}
@@ -1,7 +1,10 @@
 use routee_compass_core::model::{
     state::{state_feature::StateFeature, state_model::StateModel},
     traversal::{state::state_variable::StateVar, traversal_model_error::TraversalModelError},
-    unit::{Distance, DistanceUnit, Energy, EnergyUnit, Grade, GradeUnit, Speed, SpeedUnit},
+    unit::{
+        Distance, DistanceUnit, Energy, EnergyRateUnit, EnergyUnit, Grade, GradeUnit, Speed,
+        SpeedUnit,
+    },
 };
 use std::sync::Arc;
 
@@ -22,6 +25,7 @@ pub trait VehicleType: Send + Sync {
     /// * `speed` - The speed at which the vehicle is traveling
     /// * `grade` - The grade of the road
     /// * `distance` - The distance traveled
+    /// * `energy_rate_unit` - Optional per-edge override of the unit of the predicted energy rate
     /// * `state` - The state of the vehicle
     ///
     /// Returns:
@@ -31,6 +35,7 @@ pub trait VehicleType: Send + Sync {
         speed: (Speed, SpeedUnit),
         grade: (Grade, GradeUnit),
         distance: (Distance, DistanceUnit),
+        energy_rate_unit: Option<&EnergyRateUnit>,
         state: &mut Vec<StateVar>,
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError>;
@@ -74,4 +79,13 @@ pub trait VehicleType: Send + Sync {
         &self,
         query: &serde_json::Value,
     ) -> Result<Arc<dyn VehicleType>, TraversalModelError>;
+
+    /// Identifies this vehicle's energy model when the energy it consumes on
+    /// an edge does not depend on the state, such as a battery state of charge.
+    ///
+    /// Returns:
+    /// * `Option<String>` - A key for caching edge traversals, or None if they depend on the state
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
 }
@@ -13,8 +13,22 @@ pub struct CliArgs {
     pub config_file: String,
 
     /// JSON file containing queries. Should be newline-delimited if chunksize is set
-    #[arg(short, long, value_name = "*.json")]
-    pub query_file: String,
+    #[arg(short, long, value_name = "*.json", required_unless_present_any = ["origin", "queries", "validate"])]
+    pub query_file: Option<String>,
+
+    /// run a single query from this origin EdgeId instead of reading a query file
+    #[arg(long, value_name = "EdgeId", conflicts_with = "query_file")]
+    pub origin: Option<usize>,
+
+    /// destination EdgeId for a single query. requires --origin. if omitted,
+    /// a shortest path tree is computed from the origin.
+    #[arg(long, value_name = "EdgeId", requires = "origin")]
+    pub destination: Option<usize>,
+
+    /// CSV file with origin_edge,destination_edge columns, where each row is run
+    /// as a query. use `-` to read the CSV from stdin.
+    #[arg(long, value_name = "*.csv", conflicts_with_all = ["query_file", "origin"])]
+    pub queries: Option<String>,
 
     /// Size of batches to load into memory at a time
     #[arg(long)]
@@ -23,10 +37,47 @@ pub struct CliArgs {
     /// Format of JSON queries file, if regular JSON or newline-delimited JSON
     #[arg(short, long)]
     pub newline_delimited: bool,
+
+    /// check the configuration file for problems, such as missing input files or
+    /// invalid units, and exit without loading the graph or running any queries
+    #[arg(long)]
+    pub validate: bool,
 }
 
 impl CliArgs {
     pub fn validate(&self) -> Result<(), CompassAppError> {
+        if self.validate {
+            return Ok(());
+        }
+        let n_sources = [
+            self.query_file.is_some(),
+            self.origin.is_some(),
+            self.queries.is_some(),
+        ]
+        .iter()
+        .filter(|present| **present)
+        .count();
+        if n_sources == 0 {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "must provide either a query file, a queries CSV or an origin edge",
+                )),
+            ));
+        }
+        if n_sources > 1 {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "only one of a query file, a queries CSV or an origin edge may be provided",
+                )),
+            ));
+        }
+        if self.origin.is_none() && self.destination.is_some() {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "destination edge provided without an origin edge",
+                )),
+            ));
+        }
         match (self.chunksize, self.newline_delimited) {
             (Some(_), false) => Err(CompassAppError::CompassConfigurationError(
                 CompassConfigurationError::UserConfigurationError(String::from(
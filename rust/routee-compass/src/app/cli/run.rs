@@ -1,14 +1,17 @@
 use super::cli_args::CliArgs;
 use crate::app::compass::compass_app_ops as ops;
 use crate::app::compass::config::compass_configuration_error::CompassConfigurationError;
+use crate::app::compass::config::config_validator::validate_config;
 use crate::app::compass::{
     compass_app::CompassApp, compass_app_error::CompassAppError,
     compass_json_extensions::CompassJsonExtensions, config::compass_app_builder::CompassAppBuilder,
 };
+use config::Config;
 use itertools::{Either, Itertools};
 use log::{debug, error};
+use serde::Deserialize;
 use serde_json::{json, Value};
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 use std::{fs::File, io::BufReader, path::Path};
 
 /// runs CompassApp from the command line using the provided app builder and optional
@@ -33,6 +36,9 @@ pub fn command_line_runner(
     let builder_or_default = builder.unwrap_or_default();
     let config_path = Path::new(&args.config_file);
     let config = ops::read_config_from_file(config_path)?;
+    if args.validate {
+        return validate_config_file(&config, config_path, &builder_or_default);
+    }
     let compass_app = match CompassApp::try_from((&config, &builder_or_default)) {
         Ok(app) => app,
         Err(e) => {
@@ -41,9 +47,41 @@ pub fn command_line_runner(
         }
     };
 
+    // a CSV of origin/destination pairs bypasses the query file
+    if let Some(queries_path) = &args.queries {
+        let n_edges = compass_app.search_app.directed_graph.n_edges();
+        let queries = if queries_path == "-" {
+            read_od_pairs(std::io::stdin().lock(), n_edges)?
+        } else {
+            let file = File::open(queries_path).map_err(|_e| {
+                CompassAppError::NoInputFile(format!(
+                    "Could not find queries file {}",
+                    queries_path
+                ))
+            })?;
+            read_od_pairs(BufReader::new(file), n_edges)?
+        };
+        return run_queries(queries, &compass_app, run_config);
+    }
+
+    // a single origin/destination pair from the command line bypasses the query file
+    let query_file_path = match (&args.query_file, args.origin) {
+        (_, Some(origin)) => {
+            let n_edges = compass_app.search_app.directed_graph.n_edges();
+            let query = single_od_query(origin, args.destination, n_edges)?;
+            return run_queries(vec![query], &compass_app, run_config);
+        }
+        (Some(path), None) => path,
+        (None, None) => {
+            return Err(CompassAppError::InternalError(String::from(
+                "missing query file should have been caught during CLI validation",
+            )))
+        }
+    };
+
     // read user file containing JSON query/queries
-    let query_file = File::open(args.query_file.clone()).map_err(|_e| {
-        CompassAppError::NoInputFile(format!("Could not find query file {}", args.query_file))
+    let query_file = File::open(query_file_path).map_err(|_e| {
+        CompassAppError::NoInputFile(format!("Could not find query file {}", query_file_path))
     })?;
 
     // execute queries on app
@@ -62,6 +100,134 @@ pub fn command_line_runner(
     }
 }
 
+/// checks the configuration for problems without building CompassApp, so that
+/// no graph or model input file is loaded. every problem found is logged.
+///
+/// # Arguments
+/// * `config`      - the configuration read from the config file
+/// * `config_path` - path of the config file, used to resolve relative input files
+/// * `builder`     - the builder that would construct CompassApp from this configuration
+///
+/// # Returns
+/// Nothing if the configuration is valid, otherwise an error listing every problem.
+fn validate_config_file(
+    config: &Config,
+    config_path: &Path,
+    builder: &CompassAppBuilder,
+) -> Result<(), CompassAppError> {
+    let config_json = config
+        .clone()
+        .try_deserialize::<Value>()
+        .map_err(CompassAppError::ConfigError)?;
+    let problems = validate_config(&config_json, config_path, builder);
+    if problems.is_empty() {
+        println!("configuration {} is valid", config_path.display());
+        return Ok(());
+    }
+    for problem in problems.iter() {
+        error!("{}", problem);
+    }
+    let msg = format!(
+        "found {} problem(s) in configuration {}:\n{}",
+        problems.len(),
+        config_path.display(),
+        problems.iter().map(|p| p.to_string()).join("\n")
+    );
+    Err(CompassAppError::CompassConfigurationError(
+        CompassConfigurationError::UserConfigurationError(msg),
+    ))
+}
+
+/// builds a query for a single origin/destination pair of edges, confirming that
+/// each EdgeId exists in a graph with `n_edges` edges before any search is run.
+///
+/// # Arguments
+/// * `origin`      - origin EdgeId
+/// * `destination` - optional destination EdgeId
+/// * `n_edges`     - number of edges in the graph
+///
+/// # Returns
+/// A query JSON object, or an error if either EdgeId is out of range.
+fn single_od_query(
+    origin: usize,
+    destination: Option<usize>,
+    n_edges: usize,
+) -> Result<Value, CompassAppError> {
+    let check = |name: &str, edge_id: usize| {
+        if edge_id >= n_edges {
+            Err(CompassAppError::InvalidInput(format!(
+                "{} edge {} is out of range, graph has {} edges (valid ids are 0 to {})",
+                name,
+                edge_id,
+                n_edges,
+                n_edges.saturating_sub(1)
+            )))
+        } else {
+            Ok(())
+        }
+    };
+    check("origin", origin)?;
+    let mut query = json!({ "origin_edge": origin });
+    if let Some(destination) = destination {
+        check("destination", destination)?;
+        query["destination_edge"] = json!(destination);
+    }
+    Ok(query)
+}
+
+/// a row of a CSV file of origin/destination pairs
+#[derive(Deserialize)]
+struct OdPairRow {
+    origin_edge: usize,
+    destination_edge: usize,
+}
+
+/// reads origin/destination pairs from CSV with `origin_edge` and `destination_edge`
+/// columns, building one query per row.
+///
+/// # Arguments
+/// * `reader`  - source of the CSV, such as a file or stdin
+/// * `n_edges` - number of edges in the graph
+///
+/// # Returns
+/// The queries in file order, or an error naming the first malformed or out-of-range line.
+fn read_od_pairs<R: Read>(reader: R, n_edges: usize) -> Result<Vec<Value>, CompassAppError> {
+    let mut csv_reader = csv::Reader::from_reader(reader);
+    let mut queries = vec![];
+    for (idx, row) in csv_reader.deserialize::<OdPairRow>().enumerate() {
+        // line 1 is the header
+        let line = idx + 2;
+        let row = row.map_err(|e| {
+            CompassAppError::InvalidInput(format!(
+                "malformed origin/destination pair on line {}: {}",
+                line, e
+            ))
+        })?;
+        let query = single_od_query(row.origin_edge, Some(row.destination_edge), n_edges)
+            .map_err(|e| CompassAppError::InvalidInput(format!("line {}: {}", line, e)))?;
+        queries.push(query);
+    }
+    if queries.is_empty() {
+        return Err(CompassAppError::InvalidInput(String::from(
+            "no origin/destination pairs found in queries CSV",
+        )));
+    }
+    Ok(queries)
+}
+
+/// executes queries built from command line arguments.
+fn run_queries(
+    queries: Vec<Value>,
+    compass_app: &CompassApp,
+    run_config: Option<&Value>,
+) -> Result<(), CompassAppError> {
+    let results = compass_app.run(queries, run_config)?;
+    for result in results.iter() {
+        log_error(result);
+    }
+    Ok(())
+}
+
 /// parses a file as a valid JSON object and executes it as queries against
 /// the CompassApp.run command.
 fn run_json(
@@ -114,14 +280,20 @@ fn run_newline_json(
             log_error(result)
         }
 
-        // report JSON parsing errors
-        for error in errors {
-            let error_json = json!({
-                "request": "failed to parse",
-                "error": error.to_string()
-            });
-            log_error(&error_json)
+        // report JSON parsing errors, writing each as its own response
+        let mut error_responses = errors
+            .into_iter()
+            .map(|error| {
+                json!({
+                    "request": "failed to parse",
+                    "error": error.to_string()
+                })
+            })
+            .collect::<Vec<_>>();
+        for error_json in error_responses.iter() {
+            log_error(error_json)
         }
+        compass_app.write_responses(&mut error_responses, run_config)?;
     }
 
     Ok(())
@@ -133,3 +305,56 @@ fn log_error(result: &Value) {
         error!("Error: {}", error_string);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_od_query() {
+        let query = single_od_query(0, Some(3), 4).unwrap();
+        assert_eq!(query, json!({ "origin_edge": 0, "destination_edge": 3 }));
+        let tree_query = single_od_query(2, None, 4).unwrap();
+        assert_eq!(tree_query, json!({ "origin_edge": 2 }));
+    }
+
+    #[test]
+    fn test_single_od_query_out_of_range() {
+        let origin_err = single_od_query(4, Some(0), 4);
+        assert!(matches!(origin_err, Err(CompassAppError::InvalidInput(_))));
+        let destination_err = single_od_query(0, Some(10), 4);
+        assert!(matches!(
+            destination_err,
+            Err(CompassAppError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_od_pairs() {
+        let csv = "origin_edge,destination_edge\n0,3\n2,1\n";
+        let queries = read_od_pairs(csv.as_bytes(), 4).unwrap();
+        assert_eq!(
+            queries,
+            vec![
+                json!({ "origin_edge": 0, "destination_edge": 3 }),
+                json!({ "origin_edge": 2, "destination_edge": 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_od_pairs_reports_bad_line() {
+        let malformed = "origin_edge,destination_edge\n0,3\n2,abc\n";
+        match read_od_pairs(malformed.as_bytes(), 4) {
+            Err(CompassAppError::InvalidInput(msg)) => assert!(msg.contains("line 3"), "{}", msg),
+            other => panic!("expected invalid input, found {:?}", other),
+        }
+        let out_of_range = "origin_edge,destination_edge\n9,3\n";
+        match read_od_pairs(out_of_range.as_bytes(), 4) {
+            Err(CompassAppError::InvalidInput(msg)) => assert!(msg.contains("line 2"), "{}", msg),
+            other => panic!("expected invalid input, found {:?}", other),
+        }
+        let empty = "origin_edge,destination_edge\n";
+        assert!(read_od_pairs(empty.as_bytes(), 4).is_err());
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
                 cost_model::cost_model_builder::CostModelBuilder,
                 graph_builder::DefaultGraphBuilder,
                 termination_model_builder::TerminationModelBuilder,
+                vehicle_profile::VehicleProfileConfig,
             },
         },
         search::{search_app::SearchApp, search_app_result::SearchAppResult},
@@ -31,14 +32,26 @@ use itertools::{Either, Itertools};
 use kdam::{Bar, BarExt};
 use rayon::{current_num_threads, prelude::*};
 use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::algorithm::search::search_outcome::SearchOutcome;
 use routee_compass_core::model::state::state_model::StateModel;
+use routee_compass_core::model::traversal::cached_traversal_model::TraversalCache;
 use routee_compass_core::{
-    algorithm::search::search_algorithm::SearchAlgorithm,
-    util::duration_extension::DurationExtension,
+    algorithm::search::{
+        alt::{
+            landmark_config::LandmarkConfig, landmark_cost_estimate::LandmarkCostEstimate,
+            landmark_heuristic::LandmarkHeuristic,
+        },
+        search_algorithm::SearchAlgorithm,
+    },
+    util::{
+        duration_extension::DurationExtension,
+        fs::{read_decoders, read_utils},
+    },
 };
 use serde_json::Value;
 use std::rc::Rc;
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
@@ -152,11 +165,97 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             None => Arc::new(StateModel::empty()),
         };
 
+        // the graph is loaded first, so that the models can align their per-edge tables
+        // with a regional or bidirectional graph through its edge table layout
+        let graph_params =
+            config_json.get_config_section(CompassConfigurationField::Graph, &"TOML")?;
+        // build graph
+        let graph_start = Local::now();
+        let graph = DefaultGraphBuilder::build(&graph_params)?;
+        let graph_duration = (Local::now() - graph_start)
+            .to_std()
+            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
+        log::info!(
+            "finished reading graph with duration {}",
+            graph_duration.hhmmss()
+        );
+
+        // summarize the graph for QA, including a road class histogram if provided
+        let road_class_file =
+            graph_params.get_config_path_optional(&"road_class_input_file", &"graph")?;
+        let road_classes = match road_class_file {
+            None => None,
+            Some(path) => {
+                let table = read_utils::read_raw_file(&path, read_decoders::u8, None)?;
+                Some(
+                    graph
+                        .edge_table_layout
+                        .align(&table, |road_class| *road_class)?,
+                )
+            }
+        };
+        let graph_stats = graph.stats(road_classes.as_deref())?;
+        log::info!("graph stats: {}", serde_json::to_string(&graph_stats)?);
+
+        // optionally precompute ALT landmark tables for the A* heuristic
+        let landmark_config: Option<LandmarkConfig> =
+            graph_params.get_config_serde_optional(&"landmarks", &"graph")?;
+        let landmarks = match landmark_config {
+            None => None,
+            Some(conf) => {
+                let landmarks_start = Local::now();
+                let heuristic = LandmarkHeuristic::load_or_build(&graph, &conf)?;
+                let landmarks_duration = to_std(Local::now() - landmarks_start)?;
+                log::info!(
+                    "finished preparing {} ALT landmarks with duration {}",
+                    heuristic.landmarks().len(),
+                    landmarks_duration.hhmmss()
+                );
+                Some(heuristic)
+            }
+        };
+
+        let graph_bytes = allocative::size_of_unique_allocated_data(&graph);
+        log::info!("graph size: {} GB", graph_bytes as f64 / 1e9);
+
+        #[cfg(debug_assertions)]
+        {
+            use std::io::Write;
+
+            log::debug!("Building flamegraph for graph memory usage..");
+
+            let mut flamegraph = allocative::FlameGraphBuilder::default();
+            flamegraph.visit_root(&graph);
+            let output = flamegraph.finish_and_write_flame_graph();
+
+            let outdir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join("target")
+                .join("flamegraph");
+
+            if !outdir.exists() {
+                std::fs::create_dir(&outdir).unwrap();
+            }
+
+            let outfile = outdir.join("graph_memory_flamegraph.out");
+
+            log::debug!("writing graph flamegraph to {:?}", outfile);
+
+            let mut output_file = std::fs::File::create(outfile).unwrap();
+            output_file.write_all(output.as_bytes()).unwrap();
+        }
+
+        // models that read timestamped departure times convert them to local time
+        // of day in the timezone of the road network
+        let timezone = graph_params.get("timezone").cloned();
+
         // build traversal model
         let traversal_start = Local::now();
-        let traversal_params =
+        let mut traversal_params =
             config_json.get_config_section(CompassConfigurationField::Traversal, &"TOML")?;
-        let traversal_model_service = builder.build_traversal_model_service(&traversal_params)?;
+        inherit_graph_settings(&mut traversal_params, timezone.as_ref());
+        let traversal_model_service =
+            builder.build_traversal_model_service(&traversal_params, &graph)?;
         let traversal_duration = (Local::now() - traversal_start)
             .to_std()
             .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
@@ -167,9 +266,10 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
 
         // build access model
         let access_start = Local::now();
-        let access_params =
+        let mut access_params =
             config_json.get_config_section(CompassConfigurationField::Access, &"TOML")?;
-        let access_model_service = builder.build_access_model_service(&access_params)?;
+        inherit_graph_settings(&mut access_params, timezone.as_ref());
+        let access_model_service = builder.build_access_model_service(&access_params, &graph)?;
         let access_duration = (Local::now() - access_start)
             .to_std()
             .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
@@ -179,16 +279,19 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
         );
 
         // build utility model
-        let cost_params =
+        let mut cost_params =
             config_json.get_config_section(CompassConfigurationField::Cost, &"TOML")?;
-        let cost_model_service = CostModelBuilder {}.build(&cost_params)?;
+        inherit_graph_settings(&mut cost_params, timezone.as_ref());
+        let cost_model_service = CostModelBuilder {}.build(&cost_params, &graph)?;
 
         // build frontier model
         let frontier_start = Local::now();
-        let frontier_params =
+        let mut frontier_params =
             config_json.get_config_section(CompassConfigurationField::Frontier, &"TOML")?;
+        inherit_graph_settings(&mut frontier_params, timezone.as_ref());
 
-        let frontier_model_service = builder.build_frontier_model_service(&frontier_params)?;
+        let frontier_model_service =
+            builder.build_frontier_model_service(&frontier_params, &graph)?;
 
         let frontier_duration = (Local::now() - frontier_start)
             .to_std()
@@ -198,56 +301,28 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             frontier_duration.hhmmss()
         );
 
+        // build vehicle profiles, which bundle alternative traversal and frontier models
+        let profile_configs: HashMap<String, VehicleProfileConfig> = config_json
+            .get_config_serde_optional(&CompassConfigurationField::Profiles, &"TOML")?
+            .unwrap_or_default();
+        let mut profiles = HashMap::new();
+        for (name, mut profile_config) in profile_configs.into_iter() {
+            inherit_graph_settings(&mut profile_config.traversal, timezone.as_ref());
+            for frontier in profile_config.frontier.iter_mut() {
+                inherit_graph_settings(frontier, timezone.as_ref());
+            }
+            let profile = builder.build_vehicle_profile(&profile_config, &graph)?;
+            log::info!("finished reading vehicle profile '{}'", name);
+            profiles.insert(name, profile);
+        }
+
         // build termination model
         let termination_model_json =
             config_json.get_config_section(CompassConfigurationField::Termination, &"TOML")?;
         let termination_model = TerminationModelBuilder::build(&termination_model_json, None)?;
 
-        // build graph
-        let graph_start = Local::now();
-        let graph_params =
-            config_json.get_config_section(CompassConfigurationField::Graph, &"TOML")?;
-        let graph = DefaultGraphBuilder::build(&graph_params)?;
-        let graph_duration = (Local::now() - graph_start)
-            .to_std()
-            .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
-        log::info!(
-            "finished reading graph with duration {}",
-            graph_duration.hhmmss()
-        );
-
-        let graph_bytes = allocative::size_of_unique_allocated_data(&graph);
-        log::info!("graph size: {} GB", graph_bytes as f64 / 1e9);
-
-        #[cfg(debug_assertions)]
-        {
-            use std::io::Write;
-
-            log::debug!("Building flamegraph for graph memory usage..");
-
-            let mut flamegraph = allocative::FlameGraphBuilder::default();
-            flamegraph.visit_root(&graph);
-            let output = flamegraph.finish_and_write_flame_graph();
-
-            let outdir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                .join("..")
-                .join("target")
-                .join("flamegraph");
-
-            if !outdir.exists() {
-                std::fs::create_dir(&outdir).unwrap();
-            }
-
-            let outfile = outdir.join("graph_memory_flamegraph.out");
-
-            log::debug!("writing graph flamegraph to {:?}", outfile);
-
-            let mut output_file = std::fs::File::create(outfile).unwrap();
-            output_file.write_all(output.as_bytes()).unwrap();
-        }
-
         // build search app
-        let search_app: SearchApp = SearchApp::new(
+        let mut search_app: SearchApp = SearchApp::new(
             search_algorithm,
             graph,
             state_model,
@@ -256,15 +331,26 @@ impl TryFrom<(&Config, &CompassAppBuilder)> for CompassApp {
             cost_model_service,
             frontier_model_service,
             termination_model,
-        );
+            profiles,
+        )?;
+        if let Some(landmarks) = landmarks {
+            let cost_estimate = LandmarkCostEstimate::new(Arc::new(landmarks));
+            search_app = search_app.with_cost_estimate(Arc::new(cost_estimate));
+        }
+        let edge_cache = config.get::<bool>(CompassConfigurationField::EdgeCache.to_str())?;
+        if edge_cache {
+            search_app.traversal_cache = Some(Arc::new(TraversalCache::default()));
+        }
 
         // build plugins
         let plugins_start = Local::now();
         let plugins_config =
             config_json.get_config_section(CompassConfigurationField::Plugins, &"TOML")?;
 
-        let input_plugins = builder.build_input_plugins(&plugins_config)?;
-        let output_plugins = builder.build_output_plugins(&plugins_config)?;
+        let input_plugins =
+            builder.build_input_plugins(&plugins_config, &search_app.directed_graph)?;
+        let output_plugins =
+            builder.build_output_plugins(&plugins_config, &search_app.directed_graph)?;
 
         let plugins_duration = to_std(Local::now() - plugins_start)?;
         log::info!(
@@ -338,13 +424,12 @@ impl CompassApp {
             config,
         )?
         .unwrap_or(self.response_persistence_policy);
-        let response_output_policy: ResponseOutputPolicy = get_optional_run_config(
-            &CompassConfigurationField::ResponseOutputPolicy.to_str(),
-            &"run configuration",
-            config,
-        )?
-        .unwrap_or_else(|| self.response_output_policy.clone());
-        let response_writer = response_output_policy.build()?;
+        let response_writer = self.build_response_sink(config)?;
+
+        // cached edge traversals are only shared within a batch
+        if let Some(cache) = &self.search_app.traversal_cache {
+            cache.clear()?;
+        }
 
         let input_pb = Bar::builder()
             .total(queries.len())
@@ -378,7 +463,8 @@ impl CompassApp {
             })
             .unzip();
 
-        println!();
+        // progress bars are drawn on stderr, which keeps stdout free for streamed responses
+        eprintln!();
 
         // unpack input plugin results
         let (processed_inputs_nested, error_inputs_nested) = input_plugin_result;
@@ -389,7 +475,12 @@ impl CompassApp {
             .collect();
         let load_balanced_inputs =
             ops::apply_load_balancing_policy(&processed_inputs, parallelism, 1.0)?;
-        let error_inputs: Vec<Value> = error_inputs_nested.into_iter().flatten().collect();
+        let mut error_inputs: Vec<Value> = error_inputs_nested.into_iter().flatten().collect();
+        // queries that failed input processing are still written, so that each
+        // query appears in the output stream
+        for error_input in error_inputs.iter_mut() {
+            response_writer.write_response(error_input)?;
+        }
         if load_balanced_inputs.is_empty() {
             return Ok(error_inputs);
         }
@@ -443,6 +534,41 @@ impl CompassApp {
         let run_result = run_query_result.chain(error_inputs).collect();
         Ok(run_result)
     }
+
+    /// writes responses that were created outside of [`CompassApp::run`], such as
+    /// error responses for queries that could not be parsed, to the response output
+    /// policy, so that every query of a stream appears in the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `responses` - responses to write
+    /// * `config` - configuration for this run batch which may override the response output policy
+    pub fn write_responses(
+        &self,
+        responses: &mut [serde_json::Value],
+        config: Option<&serde_json::Value>,
+    ) -> Result<(), CompassAppError> {
+        let response_writer = self.build_response_sink(config)?;
+        for response in responses.iter_mut() {
+            response_writer.write_response(response)?;
+        }
+        Ok(())
+    }
+
+    /// builds the sink for the response output policy of a run, which the
+    /// run configuration may override
+    fn build_response_sink(
+        &self,
+        config: Option<&serde_json::Value>,
+    ) -> Result<ResponseSink, CompassAppError> {
+        let response_output_policy: ResponseOutputPolicy = get_optional_run_config(
+            &CompassConfigurationField::ResponseOutputPolicy.to_str(),
+            &"run configuration",
+            config,
+        )?
+        .unwrap_or_else(|| self.response_output_policy.clone());
+        response_output_policy.build()
+    }
 }
 
 pub fn get_optional_run_config<'a, K, T>(
@@ -465,7 +591,8 @@ where
 /// Helper function that runs CompassApp on a single query.
 /// It is assumed that all pre-processing from InputPlugins have been applied.
 /// This function runs a vertex-oriented search and feeds the result into the
-/// OutputPlugins for post-processing, returning the result as JSON.
+/// OutputPlugins for post-processing, returning the result as JSON. A search
+/// that finds no acceptable route skips the OutputPlugins and reports its outcome.
 ///
 /// # Arguments
 ///
@@ -480,11 +607,32 @@ pub fn run_single_query(
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
 ) -> Result<serde_json::Value, CompassAppError> {
-    let search_result = search_app.run(query, search_orientation);
-    let output = apply_output_processing(query, search_result, search_app, output_plugins);
+    let output = match search_app.run(query, search_orientation) {
+        Ok((SearchOutcome::Found(result), si)) => {
+            apply_output_processing(query, Ok((result, si)), search_app, output_plugins)
+        }
+        Ok((outcome, _)) => out_ops::package_outcome(query, &outcome),
+        Err(e) => apply_output_processing(query, Err(e), search_app, output_plugins),
+    };
     Ok(output)
 }
 
+/// passes the graph's `timezone` setting down to a model configuration
+/// section, unless that section sets it explicitly. the nested `models` of a
+/// combined frontier model inherit it as well.
+fn inherit_graph_settings(params: &mut serde_json::Value, timezone: Option<&serde_json::Value>) {
+    if let Some(obj) = params.as_object_mut() {
+        if let Some(timezone) = timezone {
+            obj.entry("timezone").or_insert(timezone.clone());
+        }
+        if let Some(models) = obj.get_mut("models").and_then(|m| m.as_array_mut()) {
+            for model in models.iter_mut() {
+                inherit_graph_settings(model, timezone);
+            }
+        }
+    }
+}
+
 /// helper for handling conversion from Chrono Duration to std Duration
 fn to_std(dur: Duration) -> Result<std::time::Duration, CompassAppError> {
     dur.to_std().map_err(|e| {
@@ -612,7 +760,32 @@ mod tests {
         config::compass_configuration_error::CompassConfigurationError,
     };
 
-    use super::CompassApp;
+    use super::{inherit_graph_settings, Arc, CompassApp, TraversalCache};
+    use crate::app::compass::config::compass_app_builder::CompassAppBuilder;
+    use crate::plugin::output::default::traversal::{
+        builder::TraversalPluginBuilder, plugin::EdgeAttributesFn,
+    };
+    use routee_compass_core::model::road_network::edge_id::EdgeId;
+    use routee_compass_core::model::road_network::vertex_id::VertexId;
+    use routee_compass_core::model::unit::as_f64::AsF64;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_combined_frontier_models_inherit_graph_settings() {
+        let mut frontier = serde_json::json!({
+            "type": "combined",
+            "models": [
+                { "type": "time_window" },
+                { "type": "time_window", "timezone": "UTC" }
+            ]
+        });
+        let timezone = serde_json::json!({ "utc_offset_minutes": -420 });
+        inherit_graph_settings(&mut frontier, Some(&timezone));
+        assert_eq!(frontier["timezone"], timezone);
+        assert_eq!(frontier["models"][0]["timezone"], timezone);
+        assert_eq!(frontier["models"][1]["timezone"], serde_json::json!("UTC"));
+    }
 
     #[test]
     fn test_speeds() {
@@ -666,6 +839,292 @@ mod tests {
         // path [1] is distance-optimal; path [0, 2] is time-optimal
         let expected = serde_json::json!(vec![0, 2]);
         assert_eq!(path_0, &expected);
+        assert!(result[0].get("route_coordinates").is_none());
+
+        let coordinates_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "include_route_coordinates": true
+        });
+        let result = app.run(vec![coordinates_query], None).unwrap();
+        let route_coordinates = result[0].get("route_coordinates").unwrap();
+        let route_0 = route_coordinates[0].as_array().unwrap();
+        // two edges visit three vertices
+        assert_eq!(route_0.len(), 3);
+    }
+
+    #[test]
+    fn test_registered_edge_attributes() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let street_names: EdgeAttributesFn = Arc::new(|edge_id| match edge_id {
+            EdgeId(2) => serde_json::json!({ "name": "Main St" }),
+            _ => serde_json::Value::Null,
+        });
+        let mut builder = CompassAppBuilder::default();
+        builder.add_output_plugin(
+            String::from("traversal"),
+            Rc::new(TraversalPluginBuilder::with_edge_attributes(street_names)),
+        );
+        let config_string = std::fs::read_to_string(&conf_file).unwrap();
+        let app = CompassApp::try_from_config_toml_string(
+            config_string,
+            conf_file.to_str().unwrap().to_string(),
+            &builder,
+        )
+        .unwrap();
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let result = app.run(vec![query], None).unwrap();
+        // edge 0 has no attributes, so only edge 2 is listed
+        let expected = serde_json::json!([
+            { "edge_id": 2, "attributes": { "name": "Main St" } }
+        ]);
+        assert_eq!(result[0]["route"]["edge_attributes"], expected);
+    }
+
+    #[test]
+    fn test_edge_cache() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let mut app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let cache = Arc::new(TraversalCache::default());
+        app.search_app.traversal_cache = Some(cache.clone());
+        let query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        });
+        let result = app.run(vec![query.clone(), query], None).unwrap();
+        for response in result.iter() {
+            assert_eq!(response["route"]["path"], serde_json::json!([0, 2]));
+        }
+        assert!(!cache.is_empty());
+    }
+
+    #[test]
+    fn test_score_route() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let query = serde_json::json!({});
+        let time_optimal = app
+            .search_app
+            .score_route(&query, &[EdgeId(0), EdgeId(2)])
+            .unwrap();
+        let direct = app.search_app.score_route(&query, &[EdgeId(1)]).unwrap();
+        assert_eq!(time_optimal.route.len(), 2);
+        assert!(time_optimal.total_cost < direct.total_cost);
+
+        let disconnected = app.search_app.score_route(&query, &[EdgeId(1), EdgeId(0)]);
+        assert!(disconnected.is_err());
+    }
+
+    #[test]
+    fn test_is_reachable() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let route_cost = app
+            .search_app
+            .score_route(&serde_json::json!({}), &[EdgeId(0), EdgeId(2)])
+            .unwrap()
+            .total_cost
+            .as_f64();
+        let within_budget = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "max_cost": route_cost
+        });
+        assert!(app.search_app.is_reachable(&within_budget).unwrap());
+        let over_budget = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "max_cost": route_cost / 2.0
+        });
+        assert!(!app.search_app.is_reachable(&over_budget).unwrap());
+        let no_budget = serde_json::json!({ "origin_vertex": 0, "destination_vertex": 2 });
+        assert!(app.search_app.is_reachable(&no_budget).is_err());
+    }
+
+    #[test]
+    fn test_run_k_nearest() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let query = serde_json::json!({ "origin_vertex": 0 });
+        let targets = HashSet::from([VertexId(1), VertexId(2)]);
+        let result = app.search_app.run_k_nearest(&query, &targets, 1).unwrap();
+        assert_eq!(result.targets.len(), 1);
+        let route = result.targets[0]
+            .route
+            .iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        let expected = app
+            .run(
+                vec![serde_json::json!({
+                    "origin_vertex": 0,
+                    "destination_vertex": result.targets[0].vertex_id.0
+                })],
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            expected[0]["route"]["path"],
+            serde_json::json!(route.iter().map(|e| e.0).collect::<Vec<_>>())
+        );
+        assert!(result.metadata.vertices_settled > 0);
+    }
+
+    #[test]
+    fn test_road_class_bias() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_road_class_bias.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let run = |query: serde_json::Value| {
+            let result = app.run(vec![query], None).unwrap();
+            result[0]["route"].clone()
+        };
+
+        // without the bias, [0, 2] is time-optimal. discouraging road class 3
+        // reroutes onto edge 1, which is road class 1 for its whole length
+        let biased = run(serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        }));
+        assert_eq!(biased["path"], serde_json::json!([1]));
+        // the traversal state is not biased: edge 1 takes 12 hours
+        let time = biased["traversal_summary"]["time"].as_f64().unwrap();
+        assert!((time - 43_200.0).abs() < 1.0, "{}", time);
+
+        // the query may replace the configured factors
+        let unbiased = run(serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "road_class_factors": {}
+        }));
+        assert_eq!(unbiased["path"], serde_json::json!([0, 2]));
+    }
+
+    #[test]
+    fn test_vehicle_profiles() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("profiles_test")
+            .join("profiles_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let path = |query: serde_json::Value| {
+            let result = app.run(vec![query], None).unwrap();
+            result[0]["route"]["path"].clone()
+        };
+
+        // the road class restriction only belongs to the truck profile
+        let car_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "profile": "car",
+            "road_classes": [1]
+        });
+        assert_eq!(path(car_query), serde_json::json!([0, 2]));
+
+        let truck_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "profile": "truck"
+        });
+        assert_eq!(path(truck_query), serde_json::json!([0, 2]));
+
+        let restricted_truck_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "profile": "truck",
+            "road_classes": [1]
+        });
+        assert_eq!(path(restricted_truck_query), serde_json::json!([1]));
+
+        let unknown_query = serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "profile": "bicycle"
+        });
+        let result = app.run(vec![unknown_query], None).unwrap();
+        assert!(result[0]["error"].to_string().contains("bicycle"));
+    }
+
+    #[test]
+    fn test_newline_delimited_output_keeps_failed_queries() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let output_file =
+            std::env::temp_dir().join(format!("compass_ndjson_output_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&output_file);
+        let run_config = serde_json::json!({
+            "response_persistence_policy": "discard_response_from_memory",
+            "response_output_policy": {
+                "type": "file",
+                "filename": output_file.to_str().unwrap(),
+                "format": { "type": "json", "newline_delimited": true }
+            }
+        });
+        let queries = vec![
+            serde_json::json!({ "origin_vertex": 0, "destination_vertex": 2 }),
+            serde_json::json!({ "origin_vertex": 0, "destination_vertex": 99 }),
+        ];
+        let result = app.run(queries, Some(&run_config)).unwrap();
+        assert!(result.is_empty());
+
+        let contents = std::fs::read_to_string(&output_file).unwrap();
+        std::fs::remove_file(&output_file).unwrap();
+        let rows = contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        let n_errors = rows.iter().filter(|row| row.get("error").is_some()).count();
+        assert_eq!(n_errors, 1);
+        assert!(rows.iter().any(|row| row.get("route").is_some()));
     }
 
     // #[test]
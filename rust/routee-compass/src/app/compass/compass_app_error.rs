@@ -5,7 +5,7 @@ use super::{
 use crate::plugin::plugin_error::PluginError;
 use config::ConfigError;
 use routee_compass_core::{
-    algorithm::search::search_error::SearchError,
+    algorithm::search::{alt::landmark_error::LandmarkError, search_error::SearchError},
     model::{
         frontier::frontier_model_error::FrontierModelError, road_network::graph_error::GraphError,
         state::state_error::StateError, traversal::traversal_model_error::TraversalModelError,
@@ -27,11 +27,15 @@ pub enum CompassAppError {
     #[error(transparent)]
     CodecError(#[from] serde_json::Error),
     #[error(transparent)]
+    CsvError(#[from] csv::Error),
+    #[error(transparent)]
     ConfigError(#[from] ConfigError),
     #[error(transparent)]
     GraphError(#[from] GraphError),
     #[error(transparent)]
     StateError(#[from] StateError),
+    #[error(transparent)]
+    LandmarkError(#[from] LandmarkError),
     #[error("Input file {0} missing")]
     NoInputFile(String),
     #[error(transparent)]
@@ -47,3 +51,65 @@ pub enum CompassAppError {
     #[error("error decoding input:\n{0}")]
     InvalidInput(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::road_network::vertex_id::VertexId;
+    use std::error::Error;
+
+    fn read_graph_file() -> Result<(), GraphError> {
+        let source = std::io::Error::new(std::io::ErrorKind::NotFound, "edges.csv.gz");
+        Err(GraphError::from(source))
+    }
+
+    fn find_vertex() -> Result<(), GraphError> {
+        Err(GraphError::VertexIdNotFound {
+            vertex_id: VertexId(7),
+        })
+    }
+
+    fn load() -> Result<(), CompassAppError> {
+        read_graph_file()?;
+        Ok(())
+    }
+
+    fn lookup() -> Result<(), CompassAppError> {
+        find_vertex()?;
+        Ok(())
+    }
+
+    fn parse_csv() -> Result<(), CompassAppError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader("a,b\nc".as_bytes());
+        for record in reader.records() {
+            record?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_errors_convert_with_context() {
+        let error = load().unwrap_err();
+        assert!(matches!(
+            error,
+            CompassAppError::GraphError(GraphError::IOError { .. })
+        ));
+        assert!(error.to_string().contains("edges.csv.gz"));
+        assert!(error.source().is_some());
+
+        let error = lookup().unwrap_err();
+        assert!(matches!(
+            error,
+            CompassAppError::GraphError(GraphError::VertexIdNotFound { .. })
+        ));
+        assert_eq!(error.to_string(), "vertex 7 not found");
+    }
+
+    #[test]
+    fn test_csv_errors_convert() {
+        let error = parse_csv().unwrap_err();
+        assert!(matches!(error, CompassAppError::CsvError(_)));
+    }
+}
@@ -4,6 +4,7 @@ use routee_compass_core::model::access::{
     access_model_builder::AccessModelBuilder, access_model_error::AccessModelError,
     access_model_service::AccessModelService, default::combined_model::CombinedAccessModelService,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use std::{collections::HashMap, rc::Rc, sync::Arc};
 
 pub struct CombinedAccessModelBuilder {
@@ -14,6 +15,7 @@ impl AccessModelBuilder for CombinedAccessModelBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn AccessModelService>, AccessModelError> {
         let model_params = parameters
             .get_config_array(&"access_models", &"combined")
@@ -41,7 +43,7 @@ impl AccessModelBuilder for CombinedAccessModelBuilder {
                         model_type, alts
                     ))
                 })?;
-                builder.build(params)
+                builder.build(params, graph)
             })
             .collect::<Result<_, _>>()?;
         Ok(Arc::new(CombinedAccessModelService { services }))
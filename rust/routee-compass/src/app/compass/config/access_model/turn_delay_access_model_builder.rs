@@ -1,4 +1,5 @@
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use routee_compass_core::model::road_network::graph::Graph;
 use routee_compass_core::{
     model::access::{
         access_model_builder::AccessModelBuilder,
@@ -20,6 +21,7 @@ impl AccessModelBuilder for TurnDelayAccessModelBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn AccessModelService>, AccessModelError> {
         let file_path = parameters
             .get_config_path(&"edge_heading_input_file", &"turn delay access model")
@@ -36,6 +38,10 @@ impl AccessModelBuilder for TurnDelayAccessModelBuilder {
                 file_path, e
             ))
         })?;
+        let edge_headings = graph
+            .edge_table_layout
+            .align(&edge_headings, |heading| heading.reversed())
+            .map_err(|e| AccessModelError::BuildError(e.to_string()))?;
         let turn_delay_model = parameters
             .get_config_serde::<TurnDelayModel>(&"turn_delay_model", &"turn delay access model")
             .map_err(|e| {
@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use super::compass_configuration_error::CompassConfigurationError;
@@ -16,6 +17,7 @@ pub trait InputPluginBuilder {
     /// # Arguments
     ///
     /// * `parameters` - the contents of an element in the "input_plugin" array TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
     ///
     /// # Returns
     ///
@@ -25,6 +27,7 @@ pub trait InputPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError>;
 }
 
@@ -41,6 +44,7 @@ pub trait OutputPluginBuilder {
     /// # Arguments
     ///
     /// * `parameters` - the contents of an element in the "output_plugin" array TOML config section
+    /// * `graph` - the road network, whose `edge_table_layout` aligns any per-edge tables read by the builder
     ///
     /// # Returns
     ///
@@ -50,5 +54,6 @@ pub trait OutputPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError>;
 }
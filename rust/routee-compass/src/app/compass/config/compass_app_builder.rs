@@ -8,8 +8,15 @@ use super::{
     compass_configuration_field::CompassConfigurationField,
     config_json_extension::ConfigJsonExtensions,
     frontier_model::{
-        combined::combined_builder::CombinedBuilder, no_restriction_builder::NoRestrictionBuilder,
+        closed_edge_builder::ClosedEdgeBuilder,
+        combined::{combined_builder::CombinedBuilder, combined_service::CombinedFrontierService},
+        cumulative_limit_builder::CumulativeLimitBuilder,
+        departure_bearing::departure_bearing_builder::DepartureBearingBuilder,
+        local_only::local_only_builder::LocalOnlyBuilder,
+        no_restriction_builder::NoRestrictionBuilder,
         road_class::road_class_builder::RoadClassBuilder,
+        speed_limit::speed_limit_builder::SpeedLimitBuilder,
+        time_window::time_window_builder::TimeWindowBuilder,
         turn_restrictions::turn_restriction_builder::TurnRestrictionBuilder,
         vehicle_restrictions::vehicle_restriction_builder::VehicleRestrictionBuilder,
     },
@@ -17,6 +24,7 @@ use super::{
         distance_traversal_builder::DistanceTraversalBuilder,
         energy_model_builder::EnergyModelBuilder, speed_lookup_builder::SpeedLookupBuilder,
     },
+    vehicle_profile::{VehicleProfile, VehicleProfileConfig},
 };
 use crate::plugin::{
     input::{
@@ -24,7 +32,9 @@ use crate::plugin::{
             debug::debug_builder::DebugInputPluginBuilder,
             edge_rtree::edge_rtree_input_plugin_builder::EdgeRtreeInputPluginBuilder,
             grid_search::builder::GridSearchBuilder, inject::inject_builder::InjectPluginBuilder,
-            load_balancer::builder::LoadBalancerBuilder, vertex_rtree::builder::VertexRTreeBuilder,
+            load_balancer::builder::LoadBalancerBuilder,
+            structured_query::builder::StructuredQueryBuilder,
+            vertex_rtree::builder::VertexRTreeBuilder,
         },
         input_plugin::InputPlugin,
     },
@@ -37,6 +47,7 @@ use crate::plugin::{
     },
 };
 use itertools::Itertools;
+use routee_compass_core::model::road_network::graph::Graph;
 use routee_compass_core::model::{
     access::{
         access_model_builder::AccessModelBuilder, access_model_service::AccessModelService,
@@ -160,16 +171,28 @@ impl CompassAppBuilder {
 
         // Frontier model builders
         let no_restriction: Rc<dyn FrontierModelBuilder> = Rc::new(NoRestrictionBuilder {});
+        let closed_edge: Rc<dyn FrontierModelBuilder> = Rc::new(ClosedEdgeBuilder {});
+        let cumulative_limit: Rc<dyn FrontierModelBuilder> = Rc::new(CumulativeLimitBuilder {});
         let road_class: Rc<dyn FrontierModelBuilder> = Rc::new(RoadClassBuilder {});
         let turn_restriction: Rc<dyn FrontierModelBuilder> = Rc::new(TurnRestrictionBuilder {});
         let vehicle_restriction: Rc<dyn FrontierModelBuilder> =
             Rc::new(VehicleRestrictionBuilder {});
+        let local_only: Rc<dyn FrontierModelBuilder> = Rc::new(LocalOnlyBuilder {});
+        let time_window: Rc<dyn FrontierModelBuilder> = Rc::new(TimeWindowBuilder {});
+        let speed_limit: Rc<dyn FrontierModelBuilder> = Rc::new(SpeedLimitBuilder {});
+        let departure_bearing: Rc<dyn FrontierModelBuilder> = Rc::new(DepartureBearingBuilder {});
         let base_frontier_builders: HashMap<String, Rc<dyn FrontierModelBuilder>> =
             HashMap::from([
                 (String::from("no_restriction"), no_restriction),
+                (String::from("closed_edge"), closed_edge),
+                (String::from("cumulative_limit"), cumulative_limit),
                 (String::from("road_class"), road_class),
                 (String::from("turn_restriction"), turn_restriction),
                 (String::from("vehicle_restriction"), vehicle_restriction),
+                (String::from("local_only"), local_only),
+                (String::from("time_window"), time_window),
+                (String::from("speed_limit"), speed_limit),
+                (String::from("departure_bearing"), departure_bearing),
             ]);
         let combined = Rc::new(CombinedBuilder {
             builders: base_frontier_builders.clone(),
@@ -184,6 +207,7 @@ impl CompassAppBuilder {
         let load_balancer: Rc<dyn InputPluginBuilder> = Rc::new(LoadBalancerBuilder {});
         let inject: Rc<dyn InputPluginBuilder> = Rc::new(InjectPluginBuilder {});
         let debug: Rc<dyn InputPluginBuilder> = Rc::new(DebugInputPluginBuilder {});
+        let structured_query: Rc<dyn InputPluginBuilder> = Rc::new(StructuredQueryBuilder {});
         let input_plugin_builders = HashMap::from([
             (String::from("grid_search"), grid_search),
             (String::from("vertex_rtree"), vertex_tree),
@@ -191,10 +215,11 @@ impl CompassAppBuilder {
             (String::from("load_balancer"), load_balancer),
             (String::from("inject"), inject),
             (String::from("debug"), debug),
+            (String::from("structured_query"), structured_query),
         ]);
 
         // Output plugin builders
-        let traversal: Rc<dyn OutputPluginBuilder> = Rc::new(TraversalPluginBuilder {});
+        let traversal: Rc<dyn OutputPluginBuilder> = Rc::new(TraversalPluginBuilder::default());
         let summary: Rc<dyn OutputPluginBuilder> = Rc::new(SummaryOutputPluginBuilder {});
         let uuid: Rc<dyn OutputPluginBuilder> = Rc::new(UUIDOutputPluginBuilder {});
         let output_plugin_builders = HashMap::from([
@@ -217,6 +242,7 @@ impl CompassAppBuilder {
     pub fn build_traversal_model_service(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn TraversalModelService>, CompassConfigurationError> {
         let tm_type = config.get_config_string(&"type", &"traversal")?;
         let result = self
@@ -230,7 +256,7 @@ impl CompassAppBuilder {
                 )
             })
             .and_then(|b| {
-                b.build(config)
+                b.build(config, graph)
                     .map_err(CompassConfigurationError::TraversalModelError)
             });
         result
@@ -239,6 +265,7 @@ impl CompassAppBuilder {
     pub fn build_access_model_service(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn AccessModelService>, CompassConfigurationError> {
         let tm_type = config.get_config_string(&"type", &"access")?;
         let result = self
@@ -252,7 +279,7 @@ impl CompassAppBuilder {
                 )
             })
             .and_then(|b| {
-                b.build(config)
+                b.build(config, graph)
                     .map_err(CompassConfigurationError::AccessModelError)
             });
         result
@@ -263,6 +290,7 @@ impl CompassAppBuilder {
     pub fn build_frontier_model_service(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, CompassConfigurationError> {
         let fm_type = config.get_config_string(&"type", &"frontier")?;
         self.frontier_builders
@@ -275,14 +303,37 @@ impl CompassAppBuilder {
                 )
             })
             .and_then(|b| {
-                b.build(config)
+                b.build(config, graph)
                     .map_err(CompassConfigurationError::FrontierModelError)
             })
     }
 
+    /// builds the traversal model and frontier models of a vehicle profile.
+    /// the frontier models are combined so that an edge must be valid for all of them.
+    pub fn build_vehicle_profile(
+        &self,
+        config: &VehicleProfileConfig,
+        graph: &Graph,
+    ) -> Result<VehicleProfile, CompassConfigurationError> {
+        let traversal_model_service =
+            self.build_traversal_model_service(&config.traversal_params(), graph)?;
+        let inner_services = config
+            .frontier
+            .iter()
+            .map(|f| self.build_frontier_model_service(f, graph))
+            .collect::<Result<Vec<_>, _>>()?;
+        let frontier_model_service: Arc<dyn FrontierModelService> =
+            Arc::new(CombinedFrontierService { inner_services });
+        Ok(VehicleProfile {
+            traversal_model_service,
+            frontier_model_service,
+        })
+    }
+
     pub fn build_input_plugins(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Vec<Arc<dyn InputPlugin>>, CompassConfigurationError> {
         let input_plugins = config.get_config_array(
             &CompassConfigurationField::InputPlugins,
@@ -302,7 +353,7 @@ impl CompassAppBuilder {
                         self.input_plugin_builders.keys().join(", "),
                     )
                 })?;
-            let input_plugin = builder.build(&plugin_json)?;
+            let input_plugin = builder.build(&plugin_json, graph)?;
             plugins.push(input_plugin);
         }
         Ok(plugins)
@@ -311,6 +362,7 @@ impl CompassAppBuilder {
     pub fn build_output_plugins(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Vec<Arc<dyn OutputPlugin>>, CompassConfigurationError> {
         let output_plugins = config.get_config_array(
             &CompassConfigurationField::OutputPlugins,
@@ -330,7 +382,7 @@ impl CompassAppBuilder {
                         self.output_plugin_builders.keys().join(", "),
                     )
                 })?;
-            let output_plugin = builder.build(&plugin_json)?;
+            let output_plugin = builder.build(&plugin_json, graph)?;
             plugins.push(output_plugin);
         }
         Ok(plugins)
@@ -7,6 +7,7 @@ pub enum CompassConfigurationField {
     Termination,
     State,
     Traversal,
+    Profiles,
     Access,
     Cost,
     Algorithm,
@@ -16,6 +17,7 @@ pub enum CompassConfigurationField {
     Parallelism,
     QueryTimeoutMs,
     IncludeTree,
+    EdgeCache,
     ChargeDepleting,
     ChargeSustaining,
     SearchOrientation,
@@ -28,6 +30,7 @@ impl CompassConfigurationField {
         match self {
             CompassConfigurationField::Graph => "graph",
             CompassConfigurationField::Traversal => "traversal",
+            CompassConfigurationField::Profiles => "profiles",
             CompassConfigurationField::Access => "access",
             CompassConfigurationField::Cost => "cost",
             CompassConfigurationField::State => "state",
@@ -37,6 +40,7 @@ impl CompassConfigurationField {
             CompassConfigurationField::Parallelism => "parallelism",
             CompassConfigurationField::QueryTimeoutMs => "query_timeout_ms",
             CompassConfigurationField::IncludeTree => "include_tree",
+            CompassConfigurationField::EdgeCache => "edge_cache",
             CompassConfigurationField::Plugins => "plugin",
             CompassConfigurationField::InputPlugins => "input_plugins",
             CompassConfigurationField::OutputPlugins => "output_plugins",
@@ -6,7 +6,7 @@ use std::{
     str::FromStr,
 };
 
-const FILE_NORMALIZATION_POSTFIX: &str = "_input_file";
+pub(crate) const FILE_NORMALIZATION_POSTFIX: &str = "_input_file";
 
 pub trait ConfigJsonExtensions {
     fn get_config_section(
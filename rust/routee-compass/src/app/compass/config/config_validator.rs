@@ -0,0 +1,344 @@
+use super::compass_app_builder::CompassAppBuilder;
+use super::compass_configuration_field::CompassConfigurationField;
+use super::config_json_extension::FILE_NORMALIZATION_POSTFIX;
+use routee_compass_core::algorithm::search::search_algorithm::SearchAlgorithm;
+use routee_compass_core::model::unit::{
+    DistanceUnit, EnergyRateUnit, EnergyUnit, GradeUnit, SpeedUnit, TimeUnit,
+};
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+/// a problem found while validating a configuration without loading it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// dot-delimited location of the problem in the configuration, such as `graph.edge_list_input_file`
+    pub key: String,
+    pub message: String,
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// keys that each traversal model type requires, beyond its `type`. a key
+/// written as `a|b` is satisfied by either `a` or `b`, and is reported as `a`.
+const REQUIRED_TRAVERSAL_KEYS: [(&str, &[&str]); 2] = [
+    (
+        "speed_table",
+        &[
+            "speed_table_input_file|speed_grade_table_input_file",
+            "speed_unit",
+        ],
+    ),
+    (
+        "energy_model",
+        &["time_model", "grade_table_grade_unit", "vehicles"],
+    ),
+];
+
+/// checks a configuration for problems without reading the graph or any
+/// model input file. every file referenced by an `*_input_file` key must
+/// exist and be readable, every unit must parse, and each model `type`
+/// must be one the builder can construct. all problems are collected
+/// rather than stopping at the first, so they can be fixed together.
+///
+/// # Arguments
+///
+/// * `config_json` - the configuration, before file path normalization
+/// * `root_config_path` - path of the configuration file, which relative file paths may be relative to
+/// * `builder` - the builder that would construct the app from this configuration
+///
+/// # Returns
+///
+/// every problem found, which is empty for a valid configuration
+pub fn validate_config(
+    config_json: &serde_json::Value,
+    root_config_path: &Path,
+    builder: &CompassAppBuilder,
+) -> Vec<ConfigProblem> {
+    let mut problems = vec![];
+
+    let algorithm_key = CompassConfigurationField::Algorithm.to_string();
+    match config_json.get(&algorithm_key) {
+        None => problems.push(missing(&algorithm_key)),
+        Some(algorithm) => {
+            if let Err(e) = serde_json::from_value::<SearchAlgorithm>(algorithm.clone()) {
+                problems.push(problem(&algorithm_key, format!("invalid algorithm: {}", e)));
+            }
+        }
+    }
+
+    let graph_key = CompassConfigurationField::Graph.to_string();
+    match config_json.get(&graph_key) {
+        None => problems.push(missing(&graph_key)),
+        Some(graph) => {
+            for key in ["edge_list_input_file", "vertex_list_input_file"] {
+                if graph.get(key).is_none() {
+                    problems.push(missing(&format!("{}.{}", graph_key, key)));
+                }
+            }
+        }
+    }
+
+    let traversal_key = CompassConfigurationField::Traversal.to_string();
+    match config_json.get(&traversal_key) {
+        None => problems.push(missing(&traversal_key)),
+        Some(traversal) => {
+            let known = builder.traversal_model_builders.keys();
+            if let Some(tm_type) = check_type(traversal, &traversal_key, known, &mut problems) {
+                let required = REQUIRED_TRAVERSAL_KEYS
+                    .iter()
+                    .find(|(name, _)| *name == tm_type)
+                    .map(|(_, keys)| *keys)
+                    .unwrap_or_default();
+                for key in required {
+                    let mut alternatives = key.split('|');
+                    if alternatives.clone().all(|k| traversal.get(k).is_none()) {
+                        let first = alternatives.next().unwrap_or_default();
+                        problems.push(missing(&format!("{}.{}", traversal_key, first)));
+                    }
+                }
+                if let Some(time_model) = traversal.get("time_model") {
+                    let time_model_key = format!("{}.time_model", traversal_key);
+                    let known = builder.traversal_model_builders.keys();
+                    check_type(time_model, &time_model_key, known, &mut problems);
+                }
+            }
+        }
+    }
+
+    let access_key = CompassConfigurationField::Access.to_string();
+    if let Some(access) = config_json.get(&access_key) {
+        let known = builder.access_model_builders.keys();
+        check_type(access, &access_key, known, &mut problems);
+    }
+    let frontier_key = CompassConfigurationField::Frontier.to_string();
+    if let Some(frontier) = config_json.get(&frontier_key) {
+        let known = builder.frontier_builders.keys();
+        check_type(frontier, &frontier_key, known, &mut problems);
+    }
+
+    let profiles_key = CompassConfigurationField::Profiles.to_string();
+    let profiles = config_json.get(&profiles_key).and_then(|p| p.as_object());
+    for (name, profile) in profiles.into_iter().flatten() {
+        let profile_key = format!("{}.{}", profiles_key, name);
+        match profile.get(&traversal_key) {
+            None => problems.push(missing(&format!("{}.{}", profile_key, traversal_key))),
+            Some(traversal) => {
+                let key = format!("{}.{}", profile_key, traversal_key);
+                let known = builder.traversal_model_builders.keys();
+                check_type(traversal, &key, known, &mut problems);
+            }
+        }
+        let frontiers = profile.get(&frontier_key).and_then(|f| f.as_array());
+        for (idx, frontier) in frontiers.into_iter().flatten().enumerate() {
+            let key = format!("{}.{}[{}]", profile_key, frontier_key, idx);
+            let known = builder.frontier_builders.keys();
+            check_type(frontier, &key, known, &mut problems);
+        }
+    }
+
+    let plugin_key = CompassConfigurationField::Plugins.to_string();
+    let plugin_lists = [
+        (
+            CompassConfigurationField::InputPlugins.to_string(),
+            builder.input_plugin_builders.keys().collect::<Vec<_>>(),
+        ),
+        (
+            CompassConfigurationField::OutputPlugins.to_string(),
+            builder.output_plugin_builders.keys().collect::<Vec<_>>(),
+        ),
+    ];
+    for (list_key, known) in plugin_lists {
+        let plugins = config_json
+            .get(&plugin_key)
+            .and_then(|p| p.get(&list_key))
+            .and_then(|p| p.as_array());
+        for (idx, plugin) in plugins.into_iter().flatten().enumerate() {
+            let key = format!("{}.{}[{}]", plugin_key, list_key, idx);
+            check_type(plugin, &key, known.iter().copied(), &mut problems);
+        }
+    }
+
+    check_values(config_json, "", root_config_path, &mut problems);
+    problems
+}
+
+/// checks that a model configuration has a `type` known to the builder,
+/// returning that type if so.
+fn check_type<'a>(
+    section: &serde_json::Value,
+    key: &str,
+    known: impl Iterator<Item = &'a String>,
+    problems: &mut Vec<ConfigProblem>,
+) -> Option<String> {
+    let type_key = format!("{}.type", key);
+    let model_type = match section.get("type").and_then(|t| t.as_str()) {
+        Some(model_type) => model_type,
+        None => {
+            problems.push(missing(&type_key));
+            return None;
+        }
+    };
+    let mut known = known.map(String::as_str).collect::<Vec<_>>();
+    if known.contains(&model_type) {
+        Some(model_type.to_string())
+    } else {
+        known.sort();
+        problems.push(problem(
+            &type_key,
+            format!(
+                "unknown type '{}', must be one of {}",
+                model_type,
+                known.join(", ")
+            ),
+        ));
+        None
+    }
+}
+
+/// recursively checks the input files and units found anywhere in the configuration.
+fn check_values(
+    value: &serde_json::Value,
+    key: &str,
+    root_config_path: &Path,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (child_key, child) in obj.iter() {
+                let child_path = if key.is_empty() {
+                    child_key.clone()
+                } else {
+                    format!("{}.{}", key, child_key)
+                };
+                if child_key.ends_with(FILE_NORMALIZATION_POSTFIX) {
+                    match child.as_str() {
+                        Some(path) => {
+                            if let Err(message) = check_file(path, root_config_path) {
+                                problems.push(problem(&child_path, message));
+                            }
+                        }
+                        None => problems.push(problem(&child_path, "expected a file path")),
+                    }
+                } else if let Err(message) = check_unit(child_key, child) {
+                    problems.push(problem(&child_path, message));
+                } else {
+                    check_values(child, &child_path, root_config_path, problems);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (idx, child) in values.iter().enumerate() {
+                let child_path = format!("{}[{}]", key, idx);
+                check_values(child, &child_path, root_config_path, problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// confirms that a file exists and is readable, either as given or relative
+/// to the configuration file, matching the app's file path normalization.
+fn check_file(path: &str, root_config_path: &Path) -> Result<(), String> {
+    let as_given = PathBuf::from(path);
+    let relative = root_config_path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(path);
+    let found = [as_given, relative.clone()]
+        .into_iter()
+        .find(|p| p.is_file())
+        .ok_or_else(|| {
+            format!(
+                "file not found, tried '{}' and '{}'",
+                path,
+                relative.display()
+            )
+        })?;
+    std::fs::File::open(&found)
+        .map(|_| ())
+        .map_err(|e| format!("file {:?} is not readable: {}", found, e))
+}
+
+/// parses the value of a key that names a unit. keys that are not units pass.
+fn check_unit(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    fn parse<T: DeserializeOwned>(value: &serde_json::Value) -> Result<(), String> {
+        serde_json::from_value::<T>(value.clone())
+            .map(|_| ())
+            .map_err(|e| format!("invalid unit {}: {}", value, e))
+    }
+    if key.ends_with("energy_rate_unit") {
+        parse::<EnergyRateUnit>(value)
+    } else if key.ends_with("energy_unit") {
+        parse::<EnergyUnit>(value)
+    } else if key.ends_with("distance_unit") {
+        parse::<DistanceUnit>(value)
+    } else if key.ends_with("speed_unit") {
+        parse::<SpeedUnit>(value)
+    } else if key.ends_with("time_unit") {
+        parse::<TimeUnit>(value)
+    } else if key.ends_with("grade_unit") {
+        parse::<GradeUnit>(value)
+    } else {
+        Ok(())
+    }
+}
+
+fn missing(key: &str) -> ConfigProblem {
+    problem(key, "missing required field")
+}
+
+fn problem(key: &str, message: impl Into<String>) -> ConfigProblem {
+    ConfigProblem {
+        key: key.to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::compass_app_ops::read_config_from_file;
+
+    fn validate(filename: &str) -> Vec<ConfigProblem> {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join(filename);
+        let config = read_config_from_file(&conf_file).unwrap();
+        let config_json = config.try_deserialize::<serde_json::Value>().unwrap();
+        validate_config(&config_json, &conf_file, &CompassAppBuilder::default())
+    }
+
+    #[test]
+    fn test_valid_config_has_no_problems() {
+        let problems = validate("speeds_test/speeds_test.toml");
+        assert!(problems.is_empty(), "{:?}", problems);
+    }
+
+    #[test]
+    fn test_reports_all_problems() {
+        let mut keys = validate("validate_test/validate_bad.toml")
+            .into_iter()
+            .map(|p| p.key)
+            .collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "access.type",
+                "graph.edge_list_input_file",
+                "graph.vertex_list_input_file",
+                "plugin.output_plugins[1].type",
+                "traversal.speed_table_input_file",
+                "traversal.speed_unit",
+            ]
+        );
+    }
+}
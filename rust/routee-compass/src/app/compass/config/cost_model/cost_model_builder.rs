@@ -8,6 +8,7 @@ use routee_compass_core::model::cost::{
     cost_aggregation::CostAggregation, network::network_cost_rate::NetworkCostRate,
     vehicle::vehicle_cost_rate::VehicleCostRate,
 };
+use routee_compass_core::model::road_network::{graph::Graph, road_class_table};
 use std::{collections::HashMap, sync::Arc};
 
 pub struct CostModelBuilder {}
@@ -16,6 +17,7 @@ impl CostModelBuilder {
     pub fn build(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<CostModelService, CompassConfigurationError> {
         let parent_key = CompassConfigurationField::Cost.to_string();
         let vehicle_rates: HashMap<String, VehicleCostRate> = config
@@ -36,12 +38,26 @@ impl CostModelBuilder {
             .get_config_serde_optional(&"ignore_unknown_user_provided_weights", &parent_key)?
             .unwrap_or(true);
 
+        let road_class_factors: HashMap<u8, f64> = config
+            .get_config_serde_optional(&"road_class_factors", &parent_key)?
+            .unwrap_or_default();
+        let road_class_file =
+            config.get_config_path_optional(&"road_class_input_file", &parent_key)?;
+        let road_classes = road_class_file
+            .map(|path| {
+                road_class_table::read_edge_road_classes(&path, None, &graph.edge_table_layout)
+            })
+            .transpose()?
+            .map(Arc::new);
+
         let model = CostModelService {
             vehicle_rates: Arc::new(vehicle_rates),
             network_rates: Arc::new(network_rates),
             weights: Arc::new(weights),
             cost_aggregation,
             ignore_unknown_weights,
+            road_classes,
+            road_class_factors: Arc::new(road_class_factors),
         };
         Ok(model)
     }
@@ -3,7 +3,8 @@ use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
 use routee_compass_core::model::{
     cost::{
         cost_aggregation::CostAggregation, cost_model::CostModel,
-        network::network_cost_rate::NetworkCostRate, vehicle::vehicle_cost_rate::VehicleCostRate,
+        network::network_cost_rate::NetworkCostRate, road_class_bias::RoadClassBias,
+        vehicle::vehicle_cost_rate::VehicleCostRate,
     },
     state::state_model::StateModel,
 };
@@ -18,6 +19,10 @@ pub struct CostModelService {
     pub weights: Arc<HashMap<String, f64>>,
     pub cost_aggregation: CostAggregation,
     pub ignore_unknown_weights: bool,
+    /// the road class of each edge, required by road class factors
+    pub road_classes: Option<Arc<Box<[u8]>>>,
+    /// default cost multiplier for each road class, see [`RoadClassBias`]
+    pub road_class_factors: Arc<HashMap<u8, f64>>,
 }
 
 impl CostModelService {
@@ -29,7 +34,8 @@ impl CostModelService {
     /// ```python
     /// {
     ///   "state_variable_names": [],  # list of state variables to convert to costs
-    ///   "cost_aggregation": ''     # operation for combining costs, 'sum' or 'mul'
+    ///   "cost_aggregation": '',    # operation for combining costs, 'sum' or 'mul'
+    ///   "road_class_factors": {}   # cost multiplier for each road class
     /// }
     /// ```
     ///
@@ -98,7 +104,12 @@ impl CostModelService {
             .get_config_serde_optional(&"cost_aggregation", &"cost_model")?
             .unwrap_or(self.cost_aggregation.to_owned());
 
-        let model = CostModel::new(
+        let road_class_factors = query
+            .get_config_serde_optional::<HashMap<u8, f64>>(&"road_class_factors", &"cost_model")?
+            .map(Arc::new)
+            .unwrap_or(self.road_class_factors.clone());
+
+        let mut model = CostModel::new(
             weights,
             vehicle_rates,
             self.network_rates.clone(),
@@ -112,6 +123,17 @@ impl CostModelService {
             ))
         })?;
 
+        if !road_class_factors.is_empty() {
+            let road_classes = self.road_classes.clone().ok_or_else(|| {
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "road_class_factors requires a road_class_input_file in the cost configuration",
+                ))
+            })?;
+            let bias = RoadClassBias::new(road_classes, (*road_class_factors).clone())
+                .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?;
+            model.road_class_bias = Some(bias);
+        }
+
         Ok(model)
     }
 }
@@ -0,0 +1,18 @@
+use routee_compass_core::model::frontier::{
+    default::closed_edge::ClosedEdgeRestriction, frontier_model_builder::FrontierModelBuilder,
+    frontier_model_error::FrontierModelError, frontier_model_service::FrontierModelService,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use std::sync::Arc;
+
+pub struct ClosedEdgeBuilder {}
+
+impl FrontierModelBuilder for ClosedEdgeBuilder {
+    fn build(
+        &self,
+        _parameters: &serde_json::Value,
+        _graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        Ok(Arc::new(ClosedEdgeRestriction {}))
+    }
+}
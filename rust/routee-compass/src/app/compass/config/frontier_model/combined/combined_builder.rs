@@ -8,6 +8,7 @@ use routee_compass_core::model::frontier::{
     frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
     frontier_model_service::FrontierModelService,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use std::{collections::HashMap, rc::Rc, sync::Arc};
 
 use super::combined_service::CombinedFrontierService;
@@ -29,6 +30,7 @@ impl CombinedBuilder {
     fn build_service(
         &self,
         config: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, CompassConfigurationError> {
         let fm_type_obj = config.get("type").ok_or_else(|| {
             CompassConfigurationError::ExpectedFieldForComponent(
@@ -55,7 +57,7 @@ impl CombinedBuilder {
                 )
             })
             .and_then(|b| {
-                b.build(config)
+                b.build(config, graph)
                     .map_err(CompassConfigurationError::FrontierModelError)
             })
     }
@@ -65,6 +67,7 @@ impl FrontierModelBuilder for CombinedBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
         let frontier_key = CompassConfigurationField::Frontier;
         let params = parameters
@@ -73,7 +76,7 @@ impl FrontierModelBuilder for CombinedBuilder {
 
         let inner_services = params
             .iter()
-            .map(|p| self.build_service(p))
+            .map(|p| self.build_service(p, graph))
             .collect::<Result<Vec<Arc<dyn FrontierModelService>>, CompassConfigurationError>>()
             .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
 
@@ -1,3 +1,4 @@
+use routee_compass_core::algorithm::search::direction::Direction;
 use routee_compass_core::model::{
     frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
     property::edge::Edge,
@@ -27,4 +28,53 @@ impl FrontierModel for CombinedFrontierModel {
         }
         Ok(true)
     }
+
+    fn valid_frontier_in_direction(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        direction: &Direction,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        for frontier_model in self.inner_models.iter() {
+            if !frontier_model.valid_frontier_in_direction(
+                edge,
+                state,
+                previous_edge,
+                direction,
+                state_model,
+            )? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn valid_traversal(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        for frontier_model in self.inner_models.iter() {
+            if !frontier_model.valid_traversal(edge, state, state_model)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn rejection_summary(&self) -> Option<serde_json::Value> {
+        let summaries = self
+            .inner_models
+            .iter()
+            .filter_map(|m| m.rejection_summary())
+            .collect::<Vec<_>>();
+        if summaries.is_empty() {
+            None
+        } else {
+            Some(serde_json::Value::Array(summaries))
+        }
+    }
 }
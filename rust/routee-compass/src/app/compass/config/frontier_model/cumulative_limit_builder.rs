@@ -0,0 +1,31 @@
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::frontier::{
+    default::cumulative_limit::{CumulativeLimit, CumulativeLimitFrontierModel},
+    frontier_model_builder::FrontierModelBuilder,
+    frontier_model_error::FrontierModelError,
+    frontier_model_service::FrontierModelService,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use std::sync::Arc;
+
+pub struct CumulativeLimitBuilder {}
+
+impl FrontierModelBuilder for CumulativeLimitBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        _graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let feature = parameters
+            .get_config_string(&"feature", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let limit = parameters
+            .get_config_serde::<CumulativeLimit>(&"limit", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        Ok(Arc::new(CumulativeLimitFrontierModel { feature, limit }))
+    }
+}
@@ -0,0 +1,56 @@
+use super::departure_bearing_service::{
+    DepartureBearingFrontierService, DEFAULT_BEARING_TOLERANCE,
+};
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::{
+    model::{
+        access::default::turn_delays::edge_heading::EdgeHeading,
+        frontier::{
+            frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
+            frontier_model_service::FrontierModelService,
+        },
+    },
+    util::fs::read_utils,
+};
+use std::sync::Arc;
+
+pub struct DepartureBearingBuilder {}
+
+impl FrontierModelBuilder for DepartureBearingBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let heading_file = parameters
+            .get_config_path(&"edge_heading_input_file", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let tolerance = parameters
+            .get_config_serde_optional::<f64>(&"tolerance", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?
+            .unwrap_or(DEFAULT_BEARING_TOLERANCE);
+
+        let headings = read_utils::from_csv::<EdgeHeading>(&heading_file.as_path(), true, None)
+            .map_err(|e| {
+                FrontierModelError::BuildError(format!(
+                    "error reading headings from file {:?}: {}",
+                    heading_file, e
+                ))
+            })?;
+        let headings = graph
+            .edge_table_layout
+            .align(&headings, |heading| heading.reversed())
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+
+        let m: Arc<dyn FrontierModelService> = Arc::new(DepartureBearingFrontierService {
+            headings: Arc::new(headings),
+            tolerance,
+        });
+        Ok(m)
+    }
+}
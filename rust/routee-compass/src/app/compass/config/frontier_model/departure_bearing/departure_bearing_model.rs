@@ -0,0 +1,158 @@
+use super::departure_bearing_service::DepartureBearingFrontierService;
+use routee_compass_core::algorithm::search::direction::Direction;
+use routee_compass_core::model::{
+    frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+};
+use std::sync::Arc;
+
+/// keeps a route from leaving against the current heading of a moving
+/// vehicle, such as with an immediate u-turn. the first edge of a forward
+/// search, which has no previous edge, is rejected when its start heading
+/// differs from the query departure bearing by more than the tolerance. a
+/// reverse search starts from the last edge of the route, so it is not
+/// constrained. queries without a departure bearing may leave in any direction.
+pub struct DepartureBearingFrontierModel {
+    pub service: Arc<DepartureBearingFrontierService>,
+    pub bearing: Option<f64>,
+    pub tolerance: f64,
+}
+
+impl FrontierModel for DepartureBearingFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        let bearing = match (self.bearing, previous_edge) {
+            (Some(bearing), None) => bearing,
+            _ => return Ok(true),
+        };
+        let heading = self
+            .service
+            .headings
+            .get(edge.edge_id.0)
+            .ok_or_else(|| FrontierModelError::MissingIndex(format!("{}", edge.edge_id)))?;
+        Ok(angle_between(bearing, heading.start_heading() as f64) <= self.tolerance)
+    }
+
+    fn valid_frontier_in_direction(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        previous_edge: Option<&Edge>,
+        direction: &Direction,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        match direction {
+            Direction::Forward => self.valid_frontier(edge, state, previous_edge, state_model),
+            Direction::Reverse => Ok(true),
+        }
+    }
+}
+
+/// the smallest angle, in degrees within [0, 180], between two compass headings
+fn angle_between(a: f64, b: f64) -> f64 {
+    let difference = (a - b).rem_euclid(360.0);
+    difference.min(360.0 - difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::test_util::graph_fixture::graph_from_links;
+    use routee_compass_core::test_util::search_instance_builder::{
+        distance_state_model, SearchInstanceBuilder,
+    };
+    use routee_compass_core::{
+        algorithm::search::{direction::Direction, search_algorithm::SearchAlgorithm},
+        model::{
+            access::default::turn_delays::edge_heading::EdgeHeading,
+            frontier::frontier_model_service::FrontierModelService,
+            road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId},
+            termination::termination_model::TerminationModel,
+        },
+    };
+
+    /// two routes from vertex 0 to vertex 3. the shorter leaves heading east
+    /// through vertex 1, the longer leaves heading west through vertex 2.
+    fn build_graph() -> Graph {
+        graph_from_links(4, &[(0, 1, 1.0), (1, 3, 1.0), (0, 2, 2.0), (2, 3, 2.0)])
+    }
+
+    fn route_with_query(query: serde_json::Value) -> Vec<EdgeId> {
+        let service = DepartureBearingFrontierService {
+            headings: Arc::new(
+                vec![
+                    EdgeHeading::new(90, 90),
+                    EdgeHeading::new(0, 0),
+                    EdgeHeading::new(270, 270),
+                    EdgeHeading::new(0, 0),
+                ]
+                .into_boxed_slice(),
+            ),
+            tolerance: 90.0,
+        };
+        let state_model = distance_state_model();
+        let si = SearchInstanceBuilder::new(build_graph())
+            .state_model(state_model.clone())
+            .frontier_model(service.build(&query, state_model).unwrap())
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        let result = SearchAlgorithm::Dijkstra
+            .run_vertex_oriented(
+                VertexId(0),
+                Some(VertexId(3)),
+                &Direction::Forward,
+                None,
+                &si,
+            )
+            .unwrap();
+        result.routes[0].iter().map(|et| et.edge_id).collect()
+    }
+
+    #[test]
+    fn test_opposite_bearing_forces_other_first_edge() {
+        // without a bearing, the route leaves east on the shorter path
+        let route = route_with_query(serde_json::json!({}));
+        assert_eq!(route, vec![EdgeId(0), EdgeId(1)]);
+        // a vehicle heading west may not turn around onto the eastbound edge
+        let route = route_with_query(serde_json::json!({ "departure_bearing": 260 }));
+        assert_eq!(route, vec![EdgeId(2), EdgeId(3)]);
+        // only the first edge is constrained, so later edges may head anywhere
+        let route = route_with_query(serde_json::json!({ "departure_bearing": 100 }));
+        assert_eq!(route, vec![EdgeId(0), EdgeId(1)]);
+    }
+
+    #[test]
+    fn test_reverse_search_does_not_constrain_last_edge() {
+        let model = DepartureBearingFrontierModel {
+            service: Arc::new(DepartureBearingFrontierService {
+                headings: Arc::new(vec![EdgeHeading::new(90, 90)].into_boxed_slice()),
+                tolerance: 90.0,
+            }),
+            bearing: Some(270.0),
+            tolerance: 90.0,
+        };
+        let edge = Edge::new(0, 0, 1, 1.0);
+        let state_model = StateModel::empty();
+        let valid = |direction: &Direction| {
+            model
+                .valid_frontier_in_direction(&edge, &[], None, direction, &state_model)
+                .unwrap()
+        };
+        assert!(!valid(&Direction::Forward));
+        assert!(valid(&Direction::Reverse));
+    }
+
+    #[test]
+    fn test_angle_between_wraps_around_north() {
+        assert_eq!(angle_between(350.0, 10.0), 20.0);
+        assert_eq!(angle_between(90.0, 270.0), 180.0);
+        assert_eq!(angle_between(0.0, 0.0), 0.0);
+    }
+}
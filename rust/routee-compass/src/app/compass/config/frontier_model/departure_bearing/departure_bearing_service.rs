@@ -0,0 +1,65 @@
+use super::departure_bearing_model::DepartureBearingFrontierModel;
+use routee_compass_core::model::{
+    access::default::turn_delays::edge_heading::EdgeHeading,
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    state::state_model::StateModel,
+};
+use std::sync::Arc;
+
+/// default largest difference, in degrees, between the departure bearing of a
+/// query and the start heading of the first edge of a route
+pub const DEFAULT_BEARING_TOLERANCE: f64 = 90.0;
+
+#[derive(Clone)]
+pub struct DepartureBearingFrontierService {
+    /// the start and end headings of each edge
+    pub headings: Arc<Box<[EdgeHeading]>>,
+    /// default largest difference, in degrees, between the departure bearing
+    /// and the start heading of a first edge
+    pub tolerance: f64,
+}
+
+impl DepartureBearingFrontierService {
+    /// reads an optional number of degrees from the query
+    fn read_degrees(
+        query: &serde_json::Value,
+        key: &str,
+    ) -> Result<Option<f64>, FrontierModelError> {
+        match query.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_f64().map(Some).ok_or_else(|| {
+                FrontierModelError::BuildError(format!(
+                    "expected {} to be a number of degrees, found {}",
+                    key, value
+                ))
+            }),
+        }
+    }
+}
+
+impl FrontierModelService for DepartureBearingFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        let bearing = Self::read_degrees(query, "departure_bearing")?;
+        let tolerance =
+            Self::read_degrees(query, "departure_bearing_tolerance")?.unwrap_or(self.tolerance);
+        if !(0.0..=180.0).contains(&tolerance) {
+            return Err(FrontierModelError::BuildError(format!(
+                "departure bearing tolerance must be between 0 and 180 degrees, found {}",
+                tolerance
+            )));
+        }
+        let model = DepartureBearingFrontierModel {
+            service: Arc::new(self.clone()),
+            bearing,
+            tolerance,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod departure_bearing_builder;
+pub mod departure_bearing_model;
+pub mod departure_bearing_service;
@@ -0,0 +1,76 @@
+use super::local_only_service::{LocalOnlyEdgeRow, LocalOnlyFrontierService};
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::{
+    model::frontier::{
+        frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    util::fs::read_utils,
+};
+use std::sync::Arc;
+
+pub struct LocalOnlyBuilder {}
+
+impl FrontierModelBuilder for LocalOnlyBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let local_only_file_key = String::from("local_only_input_file");
+
+        let local_only_file = parameters
+            .get_config_path(&local_only_file_key, &frontier_key)
+            .map_err(|e| {
+                FrontierModelError::BuildError(format!(
+                    "configuration error due to {}: {}",
+                    local_only_file_key.clone(),
+                    e
+                ))
+            })?;
+
+        let rows: Box<[LocalOnlyEdgeRow]> = read_utils::from_csv(&local_only_file, true, None)
+            .map_err(|e| {
+                FrontierModelError::BuildError(format!(
+                    "configuration error due to {}: {}",
+                    local_only_file_key.clone(),
+                    e
+                ))
+            })?;
+
+        // the vertices of each edge are taken from the graph, which renumbers
+        // them for a graph region
+        let edge_ids = graph
+            .edge_table_layout
+            .align_edge_ids(&rows.iter().map(|row| row.edge_id).collect::<Vec<_>>());
+        let rows = edge_ids
+            .into_iter()
+            .map(|edge_id| {
+                let edge = graph
+                    .get_edge(edge_id)
+                    .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+                Ok(LocalOnlyEdgeRow {
+                    edge_id,
+                    src_vertex_id: edge.src_vertex_id,
+                    dst_vertex_id: edge.dst_vertex_id,
+                })
+            })
+            .collect::<Result<Vec<_>, FrontierModelError>>()?;
+
+        let service = LocalOnlyFrontierService::new(&rows);
+        log::debug!(
+            "Loaded {} local-only edges in {} zones from {:?}.",
+            service.edge_zones.len(),
+            service.n_zones(),
+            local_only_file
+        );
+
+        let m: Arc<dyn FrontierModelService> = Arc::new(service);
+        Ok(m)
+    }
+}
@@ -0,0 +1,147 @@
+use super::local_only_service::LocalOnlyFrontierService;
+use routee_compass_core::model::{
+    frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+};
+use std::{collections::HashSet, sync::Arc};
+
+pub struct LocalOnlyFrontierModel {
+    pub service: Arc<LocalOnlyFrontierService>,
+    /// zones containing the origin or destination of this query
+    pub allowed_zones: HashSet<usize>,
+}
+
+impl FrontierModel for LocalOnlyFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _state: &[StateVar],
+        _previous_edge: Option<&Edge>,
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        match self.service.edge_zones.get(&edge.edge_id) {
+            None => Ok(true),
+            Some(zone) => Ok(self.allowed_zones.contains(zone)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::config::frontier_model::local_only::local_only_service::LocalOnlyEdgeRow;
+    use routee_compass_core::test_util::graph_fixture::graph_from_links;
+    use routee_compass_core::test_util::search_instance_builder::{
+        distance_state_model, SearchInstanceBuilder,
+    };
+    use routee_compass_core::{
+        algorithm::search::{a_star::a_star_algorithm::run_a_star, direction::Direction},
+        model::{
+            frontier::frontier_model_service::FrontierModelService,
+            road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId},
+            termination::termination_model::TerminationModel,
+        },
+    };
+    use serde_json::json;
+
+    /// a graph with a residential zone of local-only edges [1], [2] and [3]
+    /// between vertices 0 and 2, and a longer bypass (0) -[4]-> (4) -[5]-> (2).
+    /// vertex 5 leads into vertex 0 over edge 0, vertex 2 leads out to vertex 6
+    /// over edge 6, and vertex 3 is only reachable from inside the zone.
+    fn build_graph() -> Graph {
+        graph_from_links(
+            7,
+            &[
+                (5, 0, 1.0),
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (1, 3, 1.0),
+                (0, 4, 2.0),
+                (4, 2, 2.0),
+                (2, 6, 1.0),
+            ],
+        )
+    }
+
+    fn build_service(local_only: &[(usize, usize, usize)]) -> LocalOnlyFrontierService {
+        let rows = local_only
+            .iter()
+            .map(|(edge_id, src, dst)| LocalOnlyEdgeRow {
+                edge_id: EdgeId(*edge_id),
+                src_vertex_id: VertexId(*src),
+                dst_vertex_id: VertexId(*dst),
+            })
+            .collect::<Vec<_>>();
+        LocalOnlyFrontierService::new(&rows)
+    }
+
+    fn route(service: &LocalOnlyFrontierService, origin: usize, destination: usize) -> Vec<EdgeId> {
+        let state_model = distance_state_model();
+        let query = json!({ "origin_vertex": origin, "destination_vertex": destination });
+        let frontier_model = service.build(&query, state_model.clone()).unwrap();
+        let si = SearchInstanceBuilder::new(build_graph())
+            .state_model(state_model)
+            .frontier_model(frontier_model)
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build();
+        let result = run_a_star(
+            VertexId(origin),
+            Some(VertexId(destination)),
+            &Direction::Forward,
+            None,
+            None,
+            &si,
+        )
+        .unwrap();
+        let mut edges = vec![];
+        let mut vertex_id = VertexId(destination);
+        while let Some(branch) = result.tree.get(&vertex_id) {
+            edges.push(branch.edge_traversal.edge_id);
+            vertex_id = branch.terminal_vertex;
+        }
+        edges.reverse();
+        edges
+    }
+
+    #[test]
+    fn test_zones_group_connected_edges() {
+        let service = build_service(&[(1, 0, 1), (2, 1, 2), (3, 1, 3), (7, 8, 9)]);
+        assert_eq!(service.n_zones(), 2);
+        assert_eq!(
+            service.edge_zones.get(&EdgeId(1)),
+            service.edge_zones.get(&EdgeId(3))
+        );
+        assert_ne!(
+            service.edge_zones.get(&EdgeId(1)),
+            service.edge_zones.get(&EdgeId(7))
+        );
+    }
+
+    #[test]
+    fn test_through_route_avoids_local_zone() {
+        let unrestricted = build_service(&[]);
+        assert_eq!(
+            route(&unrestricted, 5, 6),
+            vec![EdgeId(0), EdgeId(1), EdgeId(2), EdgeId(6)]
+        );
+
+        let local_only = build_service(&[(1, 0, 1), (2, 1, 2), (3, 1, 3)]);
+        assert_eq!(
+            route(&local_only, 5, 6),
+            vec![EdgeId(0), EdgeId(4), EdgeId(5), EdgeId(6)]
+        );
+    }
+
+    #[test]
+    fn test_destination_inside_local_zone_is_reachable() {
+        // vertex 3 is two local-only edges into the zone, so reaching it
+        // requires traversing local-only edges not adjacent to the destination
+        let local_only = build_service(&[(1, 0, 1), (2, 1, 2), (3, 1, 3)]);
+        assert_eq!(
+            route(&local_only, 5, 3),
+            vec![EdgeId(0), EdgeId(1), EdgeId(3)]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::local_only_model::LocalOnlyFrontierModel;
+use crate::plugin::input::input_field::InputField;
+use routee_compass_core::model::{
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    road_network::{edge_id::EdgeId, vertex_id::VertexId},
+    state::state_model::StateModel,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// a row of the local-only edges file. these are the leading columns of the
+/// edge list, so the file may be a filtered copy of it; other columns are ignored.
+#[derive(Deserialize, Clone)]
+pub struct LocalOnlyEdgeRow {
+    pub edge_id: EdgeId,
+    pub src_vertex_id: VertexId,
+    pub dst_vertex_id: VertexId,
+}
+
+/// local-only edges grouped into zones, where a zone is a set of local-only
+/// edges connected through shared vertices, such as the streets of a
+/// residential neighborhood. a zone may only be used by a query that starts
+/// or ends in it, which prevents routes from cutting through it.
+#[derive(Clone)]
+pub struct LocalOnlyFrontierService {
+    /// zone of each local-only edge
+    pub edge_zones: Arc<HashMap<EdgeId, usize>>,
+    /// zone of each vertex at either end of a local-only edge
+    pub vertex_zones: Arc<HashMap<VertexId, usize>>,
+}
+
+impl LocalOnlyFrontierService {
+    /// groups the local-only edges into zones of edges connected by shared vertices.
+    pub fn new(rows: &[LocalOnlyEdgeRow]) -> LocalOnlyFrontierService {
+        // union-find over the vertices touched by local-only edges
+        let mut parents: HashMap<VertexId, VertexId> = HashMap::new();
+        fn find(parents: &mut HashMap<VertexId, VertexId>, v: VertexId) -> VertexId {
+            let parent = *parents.entry(v).or_insert(v);
+            if parent == v {
+                return v;
+            }
+            let root = find(parents, parent);
+            parents.insert(v, root);
+            root
+        }
+        for row in rows.iter() {
+            let src_root = find(&mut parents, row.src_vertex_id);
+            let dst_root = find(&mut parents, row.dst_vertex_id);
+            if src_root != dst_root {
+                parents.insert(src_root, dst_root);
+            }
+        }
+
+        // number the zones in order of first appearance
+        let mut root_zones: HashMap<VertexId, usize> = HashMap::new();
+        let mut vertex_zones: HashMap<VertexId, usize> = HashMap::new();
+        let mut edge_zones: HashMap<EdgeId, usize> = HashMap::new();
+        for row in rows.iter() {
+            let root = find(&mut parents, row.src_vertex_id);
+            let n_zones = root_zones.len();
+            let zone = *root_zones.entry(root).or_insert(n_zones);
+            edge_zones.insert(row.edge_id, zone);
+            vertex_zones.insert(row.src_vertex_id, zone);
+            vertex_zones.insert(row.dst_vertex_id, zone);
+        }
+
+        LocalOnlyFrontierService {
+            edge_zones: Arc::new(edge_zones),
+            vertex_zones: Arc::new(vertex_zones),
+        }
+    }
+
+    pub fn n_zones(&self) -> usize {
+        self.edge_zones.values().collect::<HashSet<_>>().len()
+    }
+
+    /// finds the zones containing the origin or destination of a query, which
+    /// may be given as vertices, edges, or both.
+    pub fn endpoint_zones(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<HashSet<usize>, FrontierModelError> {
+        let mut zones = HashSet::new();
+        for field in [InputField::OriginVertex, InputField::DestinationVertex] {
+            if let Some(vertex_id) = get_endpoint::<VertexId>(query, field)? {
+                zones.extend(self.vertex_zones.get(&vertex_id));
+            }
+        }
+        for field in [InputField::OriginEdge, InputField::DestinationEdge] {
+            if let Some(edge_id) = get_endpoint::<EdgeId>(query, field)? {
+                zones.extend(self.edge_zones.get(&edge_id));
+            }
+        }
+        Ok(zones)
+    }
+}
+
+fn get_endpoint<T: DeserializeOwned>(
+    query: &serde_json::Value,
+    field: InputField,
+) -> Result<Option<T>, FrontierModelError> {
+    match query.get(field.to_str()) {
+        None => Ok(None),
+        Some(value) => serde_json::from_value::<T>(value.clone())
+            .map(Some)
+            .map_err(|e| FrontierModelError::BuildError(format!("invalid {}: {}", field, e))),
+    }
+}
+
+impl FrontierModelService for LocalOnlyFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        let allowed_zones = self.endpoint_zones(query)?;
+        let model = LocalOnlyFrontierModel {
+            service: Arc::new(self.clone()),
+            allowed_zones,
+        };
+        Ok(Arc::new(model))
+    }
+}
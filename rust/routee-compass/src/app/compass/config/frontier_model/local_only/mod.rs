@@ -0,0 +1,3 @@
+pub mod local_only_builder;
+pub mod local_only_model;
+pub mod local_only_service;
@@ -1,5 +1,11 @@
+pub mod closed_edge_builder;
 pub mod combined;
+pub mod cumulative_limit_builder;
+pub mod departure_bearing;
+pub mod local_only;
 pub mod no_restriction_builder;
 pub mod road_class;
+pub mod speed_limit;
+pub mod time_window;
 pub mod turn_restrictions;
 pub mod vehicle_restrictions;
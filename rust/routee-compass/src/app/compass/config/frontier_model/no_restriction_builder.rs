@@ -2,6 +2,7 @@ use routee_compass_core::model::frontier::{
     default::no_restriction::NoRestriction, frontier_model_builder::FrontierModelBuilder,
     frontier_model_error::FrontierModelError, frontier_model_service::FrontierModelService,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 pub struct NoRestrictionBuilder {}
@@ -10,6 +11,7 @@ impl FrontierModelBuilder for NoRestrictionBuilder {
     fn build(
         &self,
         _parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
         Ok(Arc::new(NoRestriction {}))
     }
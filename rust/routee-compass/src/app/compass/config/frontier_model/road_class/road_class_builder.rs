@@ -3,14 +3,12 @@ use crate::app::compass::config::{
     compass_configuration_field::CompassConfigurationField,
     config_json_extension::ConfigJsonExtensions,
 };
-use routee_compass_core::{
-    model::frontier::{
-        frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
-        frontier_model_service::FrontierModelService,
-    },
-    util::fs::{read_decoders, read_utils},
+use routee_compass_core::model::frontier::{
+    frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
+    frontier_model_service::FrontierModelService,
 };
-use std::sync::Arc;
+use routee_compass_core::model::road_network::{graph::Graph, road_class_table};
+use std::{collections::HashMap, sync::Arc};
 
 pub struct RoadClassBuilder {}
 
@@ -18,6 +16,7 @@ impl FrontierModelBuilder for RoadClassBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
         let frontier_key = CompassConfigurationField::Frontier.to_string();
         let road_class_file_key = String::from("road_class_input_file");
@@ -32,15 +31,22 @@ impl FrontierModelBuilder for RoadClassBuilder {
                 ))
             })?;
 
-        let road_class_lookup: Box<[u8]> =
-            read_utils::read_raw_file(&road_class_file, read_decoders::u8, None).map_err(|e| {
+        let road_class_codes = parameters
+            .get_config_serde_optional::<HashMap<i64, u8>>(&"road_class_codes", &frontier_key)
+            .map_err(|e| {
                 FrontierModelError::BuildError(format!(
-                    "failed to load file at {:?}: {}",
-                    road_class_file.clone().to_str(),
+                    "unable to deserialize road_class_codes: {}",
                     e
                 ))
             })?;
 
+        let road_class_lookup = road_class_table::read_edge_road_classes(
+            &road_class_file,
+            road_class_codes.as_ref(),
+            &graph.edge_table_layout,
+        )
+        .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+
         let road_class_parser = parameters
             .get_config_serde_optional::<RoadClassParser>(
                 &"road_class_parser",
@@ -0,0 +1,3 @@
+pub mod speed_limit_builder;
+pub mod speed_limit_model;
+pub mod speed_limit_service;
@@ -0,0 +1,63 @@
+use super::speed_limit_service::SpeedLimitFrontierService;
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::{
+    model::{
+        frontier::{
+            frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
+            frontier_model_service::FrontierModelService,
+        },
+        unit::{Speed, SpeedUnit},
+    },
+    util::fs::{read_decoders, read_utils},
+};
+use std::sync::Arc;
+
+pub struct SpeedLimitBuilder {}
+
+impl FrontierModelBuilder for SpeedLimitBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let speed_limit_file = parameters
+            .get_config_path(&"speed_limit_input_file", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let speed_unit = parameters
+            .get_config_serde::<SpeedUnit>(&"speed_unit", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let min_speed_limit = parameters
+            .get_config_serde_optional::<Speed>(&"min_speed_limit", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let max_speed_limit = parameters
+            .get_config_serde_optional::<Speed>(&"max_speed_limit", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+
+        let speed_limits: Box<[Speed]> =
+            read_utils::read_raw_file(&speed_limit_file, read_decoders::default, None).map_err(
+                |e| {
+                    FrontierModelError::BuildError(format!(
+                        "failed to load file at {:?}: {}",
+                        speed_limit_file, e
+                    ))
+                },
+            )?;
+        let speed_limits = graph
+            .edge_table_layout
+            .align(&speed_limits, |speed_limit| *speed_limit)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+
+        let m: Arc<dyn FrontierModelService> = Arc::new(SpeedLimitFrontierService {
+            speed_limits: Arc::new(speed_limits),
+            speed_unit,
+            min_speed_limit,
+            max_speed_limit,
+        });
+        Ok(m)
+    }
+}
@@ -0,0 +1,121 @@
+use super::speed_limit_service::SpeedLimitFrontierService;
+use routee_compass_core::model::{
+    frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+    unit::Speed,
+};
+use std::sync::Arc;
+
+/// rejects edges whose legal speed limit falls outside the bounds of a
+/// query, such as keeping fast routing off slow streets with a minimum, or
+/// keeping slow vehicles off highways with a maximum.
+pub struct SpeedLimitFrontierModel {
+    pub service: Arc<SpeedLimitFrontierService>,
+    pub min_speed_limit: Option<Speed>,
+    pub max_speed_limit: Option<Speed>,
+}
+
+impl FrontierModel for SpeedLimitFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _state: &[StateVar],
+        _previous_edge: Option<&Edge>,
+        _state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        if self.min_speed_limit.is_none() && self.max_speed_limit.is_none() {
+            return Ok(true);
+        }
+        let speed_limit = self
+            .service
+            .speed_limits
+            .get(edge.edge_id.0)
+            .ok_or_else(|| FrontierModelError::MissingIndex(format!("{}", edge.edge_id)))?;
+        let above_min = self.min_speed_limit.is_none_or(|min| *speed_limit >= min);
+        let below_max = self.max_speed_limit.is_none_or(|max| *speed_limit <= max);
+        Ok(above_min && below_max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::{
+        frontier::frontier_model_service::FrontierModelService, unit::SpeedUnit,
+    };
+
+    fn service() -> SpeedLimitFrontierService {
+        let speed_limits = vec![Speed::new(25.0), Speed::new(55.0), Speed::new(70.0)];
+        SpeedLimitFrontierService {
+            speed_limits: Arc::new(speed_limits.into_boxed_slice()),
+            speed_unit: SpeedUnit::MilesPerHour,
+            min_speed_limit: None,
+            max_speed_limit: None,
+        }
+    }
+
+    fn valid_edges(model: &Arc<dyn FrontierModel>) -> Vec<bool> {
+        let state_model = StateModel::empty();
+        (0..3)
+            .map(|edge_id| {
+                let edge = Edge::new(edge_id, 0, 1, 100.0);
+                model
+                    .valid_frontier(&edge, &[], None, &state_model)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_low_limit_edge_excluded_by_min_speed() {
+        let state_model = Arc::new(StateModel::empty());
+        let unrestricted = service()
+            .build(&serde_json::json!({}), state_model.clone())
+            .unwrap();
+        assert_eq!(valid_edges(&unrestricted), vec![true, true, true]);
+
+        let min_speed = service()
+            .build(
+                &serde_json::json!({"min_speed_limit": 45}),
+                state_model.clone(),
+            )
+            .unwrap();
+        assert_eq!(valid_edges(&min_speed), vec![false, true, true]);
+
+        let max_speed = service()
+            .build(&serde_json::json!({"max_speed_limit": 55}), state_model)
+            .unwrap();
+        assert_eq!(valid_edges(&max_speed), vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_query_overrides_configured_bound() {
+        let state_model = Arc::new(StateModel::empty());
+        let configured = SpeedLimitFrontierService {
+            min_speed_limit: Some(Speed::new(60.0)),
+            ..service()
+        };
+        let model = configured
+            .build(&serde_json::json!({}), state_model.clone())
+            .unwrap();
+        assert_eq!(valid_edges(&model), vec![false, false, true]);
+        let model = configured
+            .build(&serde_json::json!({"min_speed_limit": 0}), state_model)
+            .unwrap();
+        assert_eq!(valid_edges(&model), vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_query_bound_in_other_unit() {
+        // 80 kph is just under 50 mph, so only the 25 mph edge is excluded
+        let model = service()
+            .build(
+                &serde_json::json!({"min_speed_limit": 80, "speed_limit_unit": "kilometers_per_hour"}),
+                Arc::new(StateModel::empty()),
+            )
+            .unwrap();
+        assert_eq!(valid_edges(&model), vec![false, true, true]);
+    }
+}
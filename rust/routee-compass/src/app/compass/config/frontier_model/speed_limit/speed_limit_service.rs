@@ -0,0 +1,77 @@
+use super::speed_limit_model::SpeedLimitFrontierModel;
+use routee_compass_core::model::{
+    frontier::{
+        frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    state::state_model::StateModel,
+    unit::{Speed, SpeedUnit},
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SpeedLimitFrontierService {
+    /// legal speed limit of each edge, which is separate from the speed
+    /// used by the traversal model to compute travel time
+    pub speed_limits: Arc<Box<[Speed]>>,
+    /// unit of the speed limits and of the configured bounds
+    pub speed_unit: SpeedUnit,
+    /// default lowest speed limit allowed, when not set by the query
+    pub min_speed_limit: Option<Speed>,
+    /// default highest speed limit allowed, when not set by the query
+    pub max_speed_limit: Option<Speed>,
+}
+
+impl SpeedLimitFrontierService {
+    /// reads an optional speed bound from the query, falling back to the
+    /// configured default. a query bound is given in the query unit and
+    /// converted to the unit of the speed limits.
+    fn read_bound(
+        &self,
+        query: &serde_json::Value,
+        key: &str,
+        query_unit: &SpeedUnit,
+        default: Option<Speed>,
+    ) -> Result<Option<Speed>, FrontierModelError> {
+        match query.get(key) {
+            None => Ok(default),
+            Some(value) => {
+                let bound = value.as_f64().map(Speed::new).ok_or_else(|| {
+                    FrontierModelError::BuildError(format!(
+                        "expected {} to be a number, found {}",
+                        key, value
+                    ))
+                })?;
+                Ok(Some(query_unit.convert(&bound, &self.speed_unit)))
+            }
+        }
+    }
+}
+
+impl FrontierModelService for SpeedLimitFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        let query_unit = match query.get("speed_limit_unit") {
+            None => self.speed_unit,
+            Some(value) => serde_json::from_value::<SpeedUnit>(value.clone()).map_err(|e| {
+                FrontierModelError::BuildError(format!(
+                    "failed to read speed_limit_unit {}: {}",
+                    value, e
+                ))
+            })?,
+        };
+        let min_speed_limit =
+            self.read_bound(query, "min_speed_limit", &query_unit, self.min_speed_limit)?;
+        let max_speed_limit =
+            self.read_bound(query, "max_speed_limit", &query_unit, self.max_speed_limit)?;
+        let model = SpeedLimitFrontierModel {
+            service: Arc::new(self.clone()),
+            min_speed_limit,
+            max_speed_limit,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,57 @@
+use super::time_window_service::TimeWindowFrontierService;
+use crate::app::compass::config::{
+    compass_configuration_field::CompassConfigurationField,
+    config_json_extension::ConfigJsonExtensions,
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::model::{
+    frontier::{
+        frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
+        frontier_model_service::FrontierModelService,
+    },
+    unit::{Time, TimeUnit, BASE_TIME_UNIT},
+};
+use routee_compass_core::util::departure_time::GraphTimezone;
+use std::sync::Arc;
+
+pub struct TimeWindowBuilder {}
+
+impl FrontierModelBuilder for TimeWindowBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        graph: &Graph,
+    ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
+        let frontier_key = CompassConfigurationField::Frontier.to_string();
+        let closure_file = parameters
+            .get_config_path(&"closure_input_file", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        let time_unit = parameters
+            .get_config_serde_optional::<TimeUnit>(&"time_unit", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?
+            .unwrap_or(BASE_TIME_UNIT);
+        let period = parameters
+            .get_config_serde_optional::<Time>(&"period", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?;
+        if period.is_some_and(|p| p <= Time::ZERO) {
+            return Err(FrontierModelError::BuildError(String::from(
+                "time window period must be positive",
+            )));
+        }
+        let timezone = parameters
+            .get_config_serde_optional::<GraphTimezone>(&"timezone", &frontier_key)
+            .map_err(|e| FrontierModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        // a closed road is closed in both directions
+        let closures = graph
+            .edge_table_layout
+            .align_edge_map(&TimeWindowFrontierService::read_closures(&closure_file)?);
+        let m: Arc<dyn FrontierModelService> = Arc::new(TimeWindowFrontierService {
+            closures: Arc::new(closures),
+            time_unit,
+            period,
+            timezone,
+        });
+        Ok(m)
+    }
+}
@@ -0,0 +1,182 @@
+use super::time_window_service::TimeWindowFrontierService;
+use routee_compass_core::model::{
+    frontier::{frontier_model::FrontierModel, frontier_model_error::FrontierModelError},
+    property::edge::Edge,
+    state::state_model::StateModel,
+    traversal::state::state_variable::StateVar,
+    unit::{as_f64::AsF64, Time},
+};
+use std::sync::Arc;
+
+/// rejects an edge when the search reaches it while it is closed, such as a
+/// school zone during drop-off hours. the clock time at the start of the edge
+/// is the query departure time plus the travel time accumulated in the search
+/// state. queries without a departure time have no clock, so every edge is
+/// open to them.
+pub struct TimeWindowFrontierModel {
+    pub service: Arc<TimeWindowFrontierService>,
+    pub departure_time: Option<Time>,
+}
+
+impl TimeWindowFrontierModel {
+    pub const TIME: &'static str = "time";
+}
+
+impl FrontierModel for TimeWindowFrontierModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        state: &[StateVar],
+        _previous_edge: Option<&Edge>,
+        state_model: &StateModel,
+    ) -> Result<bool, FrontierModelError> {
+        let departure_time = match self.departure_time {
+            None => return Ok(true),
+            Some(departure_time) => departure_time,
+        };
+        let windows = match self.service.closures.get(&edge.edge_id) {
+            None => return Ok(true),
+            Some(windows) => windows,
+        };
+        let elapsed = state_model
+            .get_time(state, &Self::TIME.into(), &self.service.time_unit)
+            .map_err(|e| FrontierModelError::StateError(e.to_string()))?;
+        let mut clock = departure_time + elapsed;
+        if let Some(period) = self.service.period {
+            clock = Time::new(clock.as_f64().rem_euclid(period.as_f64()));
+        }
+        let is_closed = windows
+            .iter()
+            .any(|(start, end)| *start <= clock && clock < *end);
+        Ok(!is_closed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::{
+        frontier::frontier_model_service::FrontierModelService, state::state_feature::StateFeature,
+        unit::TimeUnit,
+    };
+    use routee_compass_core::util::departure_time::GraphTimezone;
+    use std::path::PathBuf;
+
+    fn service() -> TimeWindowFrontierService {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("config")
+            .join("frontier_model")
+            .join("time_window")
+            .join("test")
+            .join("closures.csv");
+        let closures = TimeWindowFrontierService::read_closures(&path).unwrap();
+        TimeWindowFrontierService {
+            closures: Arc::new(closures),
+            time_unit: TimeUnit::Seconds,
+            period: Some(Time::new(86400.0)),
+            timezone: GraphTimezone {
+                utc_offset_minutes: -7 * 60,
+                daylight_saving: None,
+            },
+        }
+    }
+
+    fn state_model() -> Arc<StateModel> {
+        let state_model = StateModel::empty()
+            .extend(vec![(
+                String::from("time"),
+                StateFeature::Time {
+                    time_unit: TimeUnit::Minutes,
+                    initial: Time::ZERO,
+                },
+            )])
+            .unwrap();
+        Arc::new(state_model)
+    }
+
+    #[test]
+    fn test_edge_closed_only_when_reached_during_window() {
+        let state_model = state_model();
+        // depart at 7:30, while edge 1 closes from 8:00 to 9:00
+        let query = serde_json::json!({ "departure_time": 27000 });
+        let model = service().build(&query, state_model.clone()).unwrap();
+        let edge = Edge::new(1, 0, 1, 100.0);
+        let mut state = state_model.initial_state().unwrap();
+        let reach_edge_after = |state: &mut Vec<StateVar>, minutes: f64| {
+            state_model
+                .add_time(
+                    state,
+                    &"time".into(),
+                    &Time::new(minutes),
+                    &TimeUnit::Minutes,
+                )
+                .unwrap();
+            model
+                .valid_frontier(&edge, state, None, &state_model)
+                .unwrap()
+        };
+
+        // reached at 7:50, before the closure
+        assert!(reach_edge_after(&mut state, 20.0));
+        // reached at 8:10, during the closure
+        assert!(!reach_edge_after(&mut state, 20.0));
+        // reached at 9:00, when the closure has ended
+        assert!(reach_edge_after(&mut state, 50.0));
+        // reached at 15:20 on the second window
+        assert!(!reach_edge_after(&mut state, 380.0));
+        // the next day at 8:30 the windows repeat
+        assert!(!reach_edge_after(&mut state, 1030.0));
+
+        // edges without closures are always open
+        let open = Edge::new(0, 0, 1, 100.0);
+        assert!(model
+            .valid_frontier(&open, &state, None, &state_model)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_no_departure_time_leaves_edges_open() {
+        let state_model = state_model();
+        let model = service()
+            .build(&serde_json::json!({}), state_model.clone())
+            .unwrap();
+        let state = state_model.initial_state().unwrap();
+        // edge 2 is closed from midnight to 1:00
+        let edge = Edge::new(2, 0, 1, 100.0);
+        assert!(model
+            .valid_frontier(&edge, &state, None, &state_model)
+            .unwrap());
+        let departing = service()
+            .build(
+                &serde_json::json!({"departure_time": 0}),
+                state_model.clone(),
+            )
+            .unwrap();
+        assert!(!departing
+            .valid_frontier(&edge, &state, None, &state_model)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_timestamp_departure_in_graph_timezone() {
+        let state_model = state_model();
+        let state = state_model.initial_state().unwrap();
+        // edge 1 closes from 8:00 to 9:00 local time, which is 15:00 to 16:00 UTC
+        let edge = Edge::new(1, 0, 1, 100.0);
+        let valid_at = |departure_time: &str| {
+            let query = serde_json::json!({ "departure_time": departure_time });
+            service()
+                .build(&query, state_model.clone())
+                .unwrap()
+                .valid_frontier(&edge, &state, None, &state_model)
+                .unwrap()
+        };
+        assert!(!valid_at("2024-07-15T15:30:00Z"));
+        assert!(valid_at("2024-07-15T08:30:00Z"));
+        // a timestamp without an offset is already local
+        assert!(!valid_at("2024-07-15T08:30:00"));
+    }
+}
@@ -0,0 +1,121 @@
+use super::time_window_model::TimeWindowFrontierModel;
+use routee_compass_core::{
+    model::{
+        frontier::{
+            frontier_model::FrontierModel, frontier_model_error::FrontierModelError,
+            frontier_model_service::FrontierModelService,
+        },
+        road_network::edge_id::EdgeId,
+        state::state_model::StateModel,
+        unit::{Time, TimeUnit},
+    },
+    util::{departure_time::DepartureTime, departure_time::GraphTimezone, fs::read_utils},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+/// a row of the closure file, where the edge is closed from `start_time`
+/// until just before `end_time`
+#[derive(Deserialize)]
+struct TimeWindowRow {
+    edge_id: EdgeId,
+    start_time: Time,
+    end_time: Time,
+}
+
+#[derive(Clone)]
+pub struct TimeWindowFrontierService {
+    /// for each edge with closures, the `[start, end)` windows when it is closed
+    pub closures: Arc<HashMap<EdgeId, Vec<(Time, Time)>>>,
+    /// unit of the closure windows and of the query departure time
+    pub time_unit: TimeUnit,
+    /// optional length of a day, after which the clock wraps so that the
+    /// windows repeat daily
+    pub period: Option<Time>,
+    /// timezone of the road network, used to read timestamped departure times
+    pub timezone: GraphTimezone,
+}
+
+impl TimeWindowFrontierService {
+    /// reads the closure windows of each edge from a CSV file with
+    /// `edge_id`, `start_time` and `end_time` columns. an edge may appear
+    /// on more than one row.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the closure file
+    ///
+    /// # Returns
+    ///
+    /// the windows of each edge, or an error if a row is malformed or a
+    /// window ends before it starts
+    pub fn read_closures<P: AsRef<Path>>(
+        path: &P,
+    ) -> Result<HashMap<EdgeId, Vec<(Time, Time)>>, FrontierModelError> {
+        let read_error = |e: String| {
+            FrontierModelError::BuildError(format!(
+                "failed to load time window file {:?}: {}",
+                path.as_ref(),
+                e
+            ))
+        };
+        let rows = read_utils::iterator_from_csv::<_, TimeWindowRow>(path, true, None)
+            .map_err(|e| read_error(e.to_string()))?;
+        let mut closures: HashMap<EdgeId, Vec<(Time, Time)>> = HashMap::new();
+        for row in rows {
+            let row = row.map_err(|e| read_error(e.to_string()))?;
+            if row.end_time <= row.start_time {
+                return Err(read_error(format!(
+                    "closure of edge {} ends at {} before it starts at {}",
+                    row.edge_id, row.end_time, row.start_time
+                )));
+            }
+            closures
+                .entry(row.edge_id)
+                .or_default()
+                .push((row.start_time, row.end_time));
+        }
+        Ok(closures)
+    }
+}
+
+impl FrontierModelService for TimeWindowFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn FrontierModel>, FrontierModelError> {
+        let departure_time = match query.get("departure_time") {
+            None => None,
+            Some(serde_json::Value::String(timestamp)) => {
+                // a timestamp selects its local time of day in the road network timezone
+                let departure = DepartureTime::parse(timestamp, self.timezone)
+                    .map_err(FrontierModelError::BuildError)?;
+                Some(departure.time_of_day(&self.time_unit))
+            }
+            Some(value) => {
+                let time = value.as_f64().map(Time::new).ok_or_else(|| {
+                    FrontierModelError::BuildError(format!(
+                        "expected departure_time to be a number or timestamp, found {}",
+                        value
+                    ))
+                })?;
+                Some(time)
+            }
+        };
+        if departure_time.is_some()
+            && !state_model.contains_key(&TimeWindowFrontierModel::TIME.to_string())
+        {
+            return Err(FrontierModelError::BuildError(format!(
+                "time window frontier model requires a '{}' feature in the state model, found: {}",
+                TimeWindowFrontierModel::TIME,
+                state_model.get_names()
+            )));
+        }
+        let model = TimeWindowFrontierModel {
+            service: Arc::new(self.clone()),
+            departure_time,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -2,6 +2,7 @@ use crate::app::compass::config::{
     compass_configuration_field::CompassConfigurationField,
     config_json_extension::ConfigJsonExtensions,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use routee_compass_core::{
     model::frontier::{
         frontier_model_builder::FrontierModelBuilder, frontier_model_error::FrontierModelError,
@@ -19,6 +20,7 @@ impl FrontierModelBuilder for TurnRestrictionBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
         let frontier_key = CompassConfigurationField::Frontier.to_string();
         let turn_restriction_file_key = String::from("turn_restriction_input_file");
@@ -33,18 +35,32 @@ impl FrontierModelBuilder for TurnRestrictionBuilder {
                 ))
             })?;
 
-        let restricted_edges: HashSet<RestrictedEdgePair> =
-            read_utils::from_csv(&turn_restriction_file, true, None)
-                .map_err(|e| {
-                    FrontierModelError::BuildError(format!(
-                        "configuration error due to {}: {}",
-                        turn_restriction_file_key.clone(),
-                        e
-                    ))
-                })?
-                .iter()
-                .cloned()
-                .collect();
+        let rows: Box<[RestrictedEdgePair]> =
+            read_utils::from_csv(&turn_restriction_file, true, None).map_err(|e| {
+                FrontierModelError::BuildError(format!(
+                    "configuration error due to {}: {}",
+                    turn_restriction_file_key.clone(),
+                    e
+                ))
+            })?;
+        // a restricted turn is a maneuver in the direction of its edge rows, so only
+        // the forward edges are restricted. turns with an edge outside of the graph
+        // region are dropped
+        let layout = &graph.edge_table_layout;
+        let prev_edge_ids =
+            layout.forward_edge_ids(&rows.iter().map(|r| r.prev_edge_id).collect::<Vec<_>>());
+        let next_edge_ids =
+            layout.forward_edge_ids(&rows.iter().map(|r| r.next_edge_id).collect::<Vec<_>>());
+        let restricted_edges: HashSet<RestrictedEdgePair> = prev_edge_ids
+            .into_iter()
+            .zip(next_edge_ids)
+            .filter_map(|(prev, next)| {
+                Some(RestrictedEdgePair {
+                    prev_edge_id: prev?,
+                    next_edge_id: next?,
+                })
+            })
+            .collect();
 
         log::debug!(
             "Loaded {} turn restrictions from {:?}.",
@@ -35,3 +35,72 @@ impl FrontierModel for TurnRestrictionFrontierModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::test_util::graph_fixture::graph_from_links;
+    use routee_compass_core::test_util::search_instance_builder::SearchInstanceBuilder;
+    use routee_compass_core::{
+        algorithm::search::{
+            a_star::a_star_algorithm::run_a_star, direction::Direction,
+            search_instance::SearchInstance,
+        },
+        model::{
+            road_network::{edge_id::EdgeId, graph::Graph, vertex_id::VertexId},
+            termination::termination_model::TerminationModel,
+        },
+    };
+    use std::collections::HashSet;
+
+    /// a graph with a short route (0) -[0]-> (1) -[1]-> (3) and a longer route
+    /// (0) -[2]-> (2) -[3]-> (3), both arriving at vertex 3.
+    fn build_graph() -> Graph {
+        graph_from_links(4, &[(0, 1, 1.0), (1, 3, 1.0), (0, 2, 1.0), (2, 3, 2.0)])
+    }
+
+    fn build_search_instance(restricted: HashSet<RestrictedEdgePair>) -> SearchInstance {
+        let service = Arc::new(TurnRestrictionFrontierService {
+            restricted_edge_pairs: Arc::new(restricted),
+        });
+        SearchInstanceBuilder::new(build_graph())
+            .frontier_model(Arc::new(TurnRestrictionFrontierModel { service }))
+            .termination_model(TerminationModel::IterationsLimit { limit: 20 })
+            .build()
+    }
+
+    fn route(si: &SearchInstance) -> Vec<EdgeId> {
+        let result = run_a_star(
+            VertexId(0),
+            Some(VertexId(3)),
+            &Direction::Forward,
+            None,
+            None,
+            si,
+        )
+        .unwrap();
+        let mut edges = vec![];
+        let mut vertex_id = VertexId(3);
+        while let Some(branch) = result.tree.get(&vertex_id) {
+            edges.push(branch.edge_traversal.edge_id);
+            vertex_id = branch.terminal_vertex;
+        }
+        edges.reverse();
+        edges
+    }
+
+    #[test]
+    fn test_banned_turn_changes_approach_edge() {
+        let unrestricted = build_search_instance(HashSet::new());
+        assert_eq!(route(&unrestricted), vec![EdgeId(0), EdgeId(1)]);
+
+        // banning the turn from edge 0 onto edge 1 forces the search to
+        // approach vertex 3 over edge 3 instead
+        let banned = HashSet::from([RestrictedEdgePair {
+            prev_edge_id: EdgeId(0),
+            next_edge_id: EdgeId(1),
+        }]);
+        let restricted = build_search_instance(banned);
+        assert_eq!(route(&restricted), vec![EdgeId(2), EdgeId(3)]);
+    }
+}
@@ -15,6 +15,18 @@ pub enum VehicleRestriction {
 }
 
 impl VehicleRestriction {
+    /// the snake_case name of this restriction type
+    pub fn name(&self) -> &'static str {
+        match self {
+            VehicleRestriction::MaximumTotalWeight(_) => "maximum_total_weight",
+            VehicleRestriction::MaximumWeightPerAxle(_) => "maximum_weight_per_axle",
+            VehicleRestriction::MaximumLength(_) => "maximum_length",
+            VehicleRestriction::MaximumWidth(_) => "maximum_width",
+            VehicleRestriction::MaximumHeight(_) => "maximum_height",
+            VehicleRestriction::MaximumTrailerLength(_) => "maximum_trailer_length",
+        }
+    }
+
     /// Returns true if the truck parameters are valid for the restriction.
     /// For example, if the restriction is MaximumTotalWeight(1000.0, "kg"),
     /// and the truck parameters are VehicleParameters { vehicle_total_weight: (500.0, "kg"), ... },
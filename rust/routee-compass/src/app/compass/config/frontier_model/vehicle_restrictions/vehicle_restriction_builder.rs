@@ -2,6 +2,7 @@ use crate::app::compass::config::{
     compass_configuration_field::CompassConfigurationField,
     config_json_extension::ConfigJsonExtensions,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use routee_compass_core::{
     model::{
         frontier::{
@@ -25,6 +26,7 @@ impl FrontierModelBuilder for VehicleRestrictionBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn FrontierModelService>, FrontierModelError> {
         let frontier_key = CompassConfigurationField::Frontier.to_string();
         let vehicle_restriction_input_file_key = String::from("vehicle_restriction_input_file");
@@ -40,7 +42,11 @@ impl FrontierModelBuilder for VehicleRestrictionBuilder {
             })?;
 
         let vehicle_restriction_lookup =
-            vehicle_restriction_lookup_from_file(&vehicle_restriction_input_file)?;
+            graph
+                .edge_table_layout
+                .align_edge_map(&vehicle_restriction_lookup_from_file(
+                    &vehicle_restriction_input_file,
+                )?);
 
         let m = VehicleRestrictionFrontierService {
             vehicle_restriction_lookup: Arc::new(vehicle_restriction_lookup),
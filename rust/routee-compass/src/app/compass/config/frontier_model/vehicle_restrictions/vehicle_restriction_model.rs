@@ -8,11 +8,17 @@ use routee_compass_core::model::{
     state::state_model::StateModel,
     traversal::state::state_variable::StateVar,
 };
-use std::sync::Arc;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 pub struct VehicleRestrictionFrontierModel {
     pub service: Arc<VehicleRestrictionFrontierService>,
     pub vehicle_parameters: VehicleParameters,
+    /// number of times each restriction type rejected an edge for this query
+    pub triggered_restrictions: Mutex<HashMap<&'static str, u64>>,
 }
 
 impl FrontierModel for VehicleRestrictionFrontierModel {
@@ -28,6 +34,10 @@ impl FrontierModel for VehicleRestrictionFrontierModel {
             Some(vehicle_restrictions) => {
                 for restriction in vehicle_restrictions.iter() {
                     if !restriction.valid(&self.vehicle_parameters) {
+                        // recording is best-effort and never fails the search
+                        if let Ok(mut triggered) = self.triggered_restrictions.lock() {
+                            *triggered.entry(restriction.name()).or_insert(0) += 1;
+                        }
                         return Ok(false);
                     }
                 }
@@ -35,4 +45,70 @@ impl FrontierModel for VehicleRestrictionFrontierModel {
             }
         }
     }
+
+    fn rejection_summary(&self) -> Option<serde_json::Value> {
+        let triggered = self.triggered_restrictions.lock().ok()?;
+        Some(json!({ "vehicle_restrictions": *triggered }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::config::frontier_model::vehicle_restrictions::vehicle_restriction::VehicleRestriction;
+    use routee_compass_core::model::{
+        road_network::edge_id::EdgeId,
+        unit::{Distance, DistanceUnit, Weight, WeightUnit},
+    };
+
+    #[test]
+    fn test_records_triggered_restrictions() {
+        let lookup = HashMap::from([
+            (
+                EdgeId(0),
+                vec![VehicleRestriction::MaximumHeight((
+                    Distance::new(3.0),
+                    DistanceUnit::Meters,
+                ))],
+            ),
+            (
+                EdgeId(1),
+                vec![VehicleRestriction::MaximumTotalWeight((
+                    Weight::new(50000.0),
+                    WeightUnit::Kg,
+                ))],
+            ),
+        ]);
+        let model = VehicleRestrictionFrontierModel {
+            service: Arc::new(VehicleRestrictionFrontierService {
+                vehicle_restriction_lookup: Arc::new(lookup),
+            }),
+            vehicle_parameters: VehicleParameters {
+                height: (Distance::new(4.0), DistanceUnit::Meters),
+                width: (Distance::new(2.5), DistanceUnit::Meters),
+                total_length: (Distance::new(20.0), DistanceUnit::Meters),
+                trailer_length: (Distance::new(15.0), DistanceUnit::Meters),
+                total_weight: (Weight::new(30000.0), WeightUnit::Kg),
+                number_of_axles: 5,
+            },
+            triggered_restrictions: Mutex::new(HashMap::new()),
+        };
+        let state_model = StateModel::empty();
+        let low_bridge = Edge::new(0, 0, 1, 10.0);
+        let weight_limit = Edge::new(1, 1, 2, 10.0);
+        for _ in 0..2 {
+            assert!(!model
+                .valid_frontier(&low_bridge, &[], None, &state_model)
+                .unwrap());
+        }
+        assert!(model
+            .valid_frontier(&weight_limit, &[], None, &state_model)
+            .unwrap());
+
+        let summary = model.rejection_summary().unwrap();
+        assert_eq!(
+            summary,
+            json!({ "vehicle_restrictions": { "maximum_height": 2 } })
+        );
+    }
 }
@@ -10,7 +10,10 @@ use routee_compass_core::model::{
     road_network::edge_id::EdgeId,
     state::state_model::StateModel,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 #[derive(Clone)]
 pub struct VehicleRestrictionFrontierService {
@@ -30,6 +33,7 @@ impl FrontierModelService for VehicleRestrictionFrontierService {
         let model = VehicleRestrictionFrontierModel {
             service,
             vehicle_parameters,
+            triggered_restrictions: Mutex::new(HashMap::new()),
         };
 
         Ok(Arc::new(model))
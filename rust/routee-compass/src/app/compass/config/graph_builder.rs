@@ -1,4 +1,4 @@
-use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::model::road_network::{graph::Graph, graph_loader::GraphLoadConfig};
 
 use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
 
@@ -29,17 +29,48 @@ impl DefaultGraphBuilder {
         let graph_key = CompassConfigurationField::Graph.to_string();
         let edge_list_csv = params.get_config_path(&"edge_list_input_file", &graph_key)?;
         let vertex_list_csv = params.get_config_path(&"vertex_list_input_file", &graph_key)?;
-        let n_edges = params.get_config_serde_optional(&"n_edges", &graph_key)?;
-        let n_vertices = params.get_config_serde_optional(&"n_vertices", &graph_key)?;
-        let verbose: Option<bool> = params.get_config_serde_optional(&"verbose", &graph_key)?;
+        let mut config = GraphLoadConfig::new(&edge_list_csv, &vertex_list_csv);
+        config.n_edges = params.get_config_serde_optional(&"n_edges", &graph_key)?;
+        config.n_vertices = params.get_config_serde_optional(&"n_vertices", &graph_key)?;
+        if let Some(verbose) = params.get_config_serde_optional(&"verbose", &graph_key)? {
+            config.verbose = verbose;
+        }
+        if let Some(policy) =
+            params.get_config_serde_optional(&"degenerate_edge_policy", &graph_key)?
+        {
+            config.degenerate_edge_policy = policy;
+        }
+        if let Some(distance_unit) =
+            params.get_config_serde_optional(&"distance_unit", &graph_key)?
+        {
+            config.distance_unit = distance_unit;
+        }
+        if let Some(bidirectional) =
+            params.get_config_serde_optional(&"bidirectional_edges", &graph_key)?
+        {
+            config.bidirectional = bidirectional;
+        }
+        if let Some(policy) =
+            params.get_config_serde_optional(&"duplicate_edge_policy", &graph_key)?
+        {
+            config.duplicate_edge_policy = policy;
+        }
+        if let Some(crs) = params.get_config_serde_optional(&"vertex_crs", &graph_key)? {
+            config.vertex_crs = crs;
+        }
+        if let Some(adjacency) = params.get_config_serde_optional(&"adjacency", &graph_key)? {
+            config.adjacency = adjacency;
+        }
+        config.region = params.get_config_serde_optional(&"region", &graph_key)?;
 
-        let graph = Graph::from_files(
-            &edge_list_csv,
-            &vertex_list_csv,
-            n_edges,
-            n_vertices,
-            verbose,
-        )?;
+        let build_vertex_pair_index: bool = params
+            .get_config_serde_optional(&"build_vertex_pair_index", &graph_key)?
+            .unwrap_or(false);
+
+        let (mut graph, _) = Graph::from_files(config)?;
+        if build_vertex_pair_index {
+            graph.build_vertex_pair_index();
+        }
 
         Ok(graph)
     }
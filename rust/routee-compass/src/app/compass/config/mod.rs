@@ -4,8 +4,10 @@ pub mod compass_app_builder;
 pub mod compass_configuration_error;
 pub mod compass_configuration_field;
 pub mod config_json_extension;
+pub mod config_validator;
 pub mod cost_model;
 pub mod frontier_model;
 pub mod graph_builder;
 pub mod termination_model_builder;
 pub mod traversal_model;
+pub mod vehicle_profile;
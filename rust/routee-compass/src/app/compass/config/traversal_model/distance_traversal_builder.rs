@@ -1,5 +1,6 @@
 use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
+use routee_compass_core::model::road_network::graph::Graph;
 
 use routee_compass_core::model::traversal::default::distance_traversal_service::DistanceTraversalService;
 
@@ -8,6 +9,7 @@ use routee_compass_core::model::traversal::traversal_model_error::TraversalModel
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
 use routee_compass_core::model::unit::DistanceUnit;
 use routee_compass_core::model::unit::BASE_DISTANCE_UNIT;
+use routee_compass_core::util::geo::distance_function::DistanceFunctionType;
 use std::sync::Arc;
 
 pub struct DistanceTraversalBuilder {}
@@ -16,14 +18,33 @@ impl TraversalModelBuilder for DistanceTraversalBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
         let traversal_key = CompassConfigurationField::Traversal.to_string();
         let distance_unit_option = parameters
             .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
         let distance_unit = distance_unit_option.unwrap_or(BASE_DISTANCE_UNIT);
-        let m: Arc<dyn TraversalModelService> =
-            Arc::new(DistanceTraversalService { distance_unit });
+        let display_distance_unit = parameters
+            .get_config_serde_optional::<DistanceUnit>(&"display_distance_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let summary_precision = parameters
+            .get_config_serde_optional::<u32>(&"summary_precision", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let distance_function = parameters
+            .get_config_serde_optional::<DistanceFunctionType>(&"distance_function", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        let haversine_radius = parameters
+            .get_config_serde_optional::<f32>(&"haversine_radius", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let m: Arc<dyn TraversalModelService> = Arc::new(DistanceTraversalService {
+            distance_unit,
+            display_distance_unit,
+            summary_precision,
+            distance_function,
+            haversine_radius,
+        });
         Ok(m)
     }
 }
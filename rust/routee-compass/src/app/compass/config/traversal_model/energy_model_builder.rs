@@ -1,14 +1,20 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
 use itertools::Itertools;
+use routee_compass_core::model::traversal::default::speed_grade_table::SpeedGradeTable;
 use routee_compass_core::model::traversal::traversal_model_builder::TraversalModelBuilder;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
-use routee_compass_core::model::unit::{DistanceUnit, GradeUnit, SpeedUnit, TimeUnit};
+use routee_compass_core::model::unit::{DistanceUnit, EnergyUnit, GradeUnit, SpeedUnit, TimeUnit};
+use routee_compass_core::util::fs::{read_decoders, read_utils};
+use routee_compass_powertrain::routee::energy_model_ops::canonical_grade_table;
 use routee_compass_powertrain::routee::energy_model_service::EnergyModelService;
+use routee_compass_powertrain::routee::grade_sign_convention::GradeSignConvention;
+use routee_compass_powertrain::routee::missing_grade_policy::MissingGradePolicy;
 
 use super::energy_model_vehicle_builders::VehicleBuilder;
 
@@ -26,6 +32,7 @@ impl TraversalModelBuilder for EnergyModelBuilder {
     fn build(
         &self,
         params: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
         let parent_key = String::from("energy traversal model");
 
@@ -39,6 +46,13 @@ impl TraversalModelBuilder for EnergyModelBuilder {
             .get_config_string(&"type", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
+        let mut time_model_params = time_model_params.clone();
+        if let Some(time_model_obj) = time_model_params.as_object_mut() {
+            if let Some(timezone) = params.get("timezone") {
+                time_model_obj.entry("timezone").or_insert(timezone.clone());
+            }
+        }
+
         let time_builder = self.time_models.get(&time_model_type).ok_or_else(|| {
             let valid_models = self.time_models.keys().join(",");
             TraversalModelError::BuildError(format!(
@@ -46,7 +60,6 @@ impl TraversalModelBuilder for EnergyModelBuilder {
                 time_model_type, valid_models
             ))
         })?;
-        let time_model_service = time_builder.build(time_model_params)?;
         let time_model_speed_unit = time_model_params
             .get_config_serde::<SpeedUnit>(&"speed_unit", &"time_model")
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
@@ -57,6 +70,31 @@ impl TraversalModelBuilder for EnergyModelBuilder {
         let grade_table_grade_unit = params
             .get_config_serde::<GradeUnit>(&"grade_table_grade_unit", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let speed_grade_table_path_option = params
+            .get_config_path_optional(&"speed_grade_table_input_file", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        if grade_table_path_option.is_some() && speed_grade_table_path_option.is_some() {
+            return Err(TraversalModelError::BuildError(format!(
+                "{} accepts either grade_table_input_file or speed_grade_table_input_file, not both",
+                parent_key
+            )));
+        }
+        // a combined speed and grade file is read once, providing the grades of this
+        // model and the speeds of the time model
+        let speed_grade_table = match speed_grade_table_path_option.as_ref() {
+            Some(path) => Some(SpeedGradeTable::from_csv(path)?),
+            None => None,
+        };
+        let time_model_service = match speed_grade_table.as_ref() {
+            Some(table) => {
+                time_builder.build_with_speed_grade_table(&time_model_params, graph, table)?
+            }
+            None => time_builder.build(&time_model_params, graph)?,
+        };
+
+        let missing_grade_policy_option = params
+            .get_config_serde_optional::<MissingGradePolicy>(&"missing_grade_policy", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
         let vehicle_configs = params
             .get_config_array(&"vehicles", &parent_key)
@@ -84,15 +122,87 @@ impl TraversalModelBuilder for EnergyModelBuilder {
             .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
-        let service = EnergyModelService::new(
+        let energy_rate_unit_override_path_option = params
+            .get_config_path_optional(&"energy_rate_unit_override_input_file", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+        let mut service = EnergyModelService::new(
             time_model_service,
             time_model_speed_unit,
             &grade_table_path_option,
             grade_table_grade_unit,
+            missing_grade_policy_option,
             time_unit_option,
             distance_unit_option,
             vehicle_library,
+            &energy_rate_unit_override_path_option,
         )?;
+        service.haversine_radius = params
+            .get_config_serde_optional::<f32>(&"haversine_radius", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        service.emissions_factors = params
+            .get_config_serde_optional::<HashMap<EnergyUnit, f64>>(
+                &"emissions_factors",
+                &parent_key,
+            )
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        service.energy_prices = params
+            .get_config_serde_optional::<HashMap<EnergyUnit, f64>>(&"energy_prices", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        service.summary_precision = params
+            .get_config_serde_optional::<u32>(&"summary_precision", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        service.value_of_time = params
+            .get_config_serde_optional::<f64>(&"value_of_time", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        if let (Some(speed_grade_table_path), Some(table)) =
+            (speed_grade_table_path_option, speed_grade_table)
+        {
+            let grades = canonical_grade_table(
+                table.grades,
+                &grade_table_grade_unit,
+                &speed_grade_table_path,
+            );
+            service.grade_table = Arc::new(Some(grades));
+        }
+        service.grade_sign_convention = params
+            .get_config_serde_optional::<GradeSignConvention>(&"grade_sign_convention", &parent_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or_default();
+        let layout = &graph.edge_table_layout;
+        if service.grade_sign_convention == GradeSignConvention::Segment {
+            let digitized_direction_path = params
+                .get_config_path(&"digitized_direction_input_file", &parent_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+            let flags: Box<[bool]> =
+                read_utils::read_raw_file(&digitized_direction_path, read_decoders::bool, None)
+                    .map_err(|e| {
+                        TraversalModelError::FileReadError(
+                            digitized_direction_path.clone(),
+                            e.to_string(),
+                        )
+                    })?;
+            // a reverse edge runs against the digitized direction of its row
+            let aligned = layout.align(&flags, |along| !*along)?;
+            service.digitized_direction = Arc::new(Some(aligned));
+        }
+        // grades are negated on the reverse edges, as uphill becomes downhill. segment
+        // grades are oriented by the digitized direction flags when looked up
+        let negate = service.grade_sign_convention == GradeSignConvention::DirectionOfTravel;
+        let aligned = match service.grade_table.as_ref() {
+            Some(table) => {
+                Some(layout.align(table, |grade| if negate { -*grade } else { *grade })?)
+            }
+            None => None,
+        };
+        service.grade_table = Arc::new(aligned);
+        if !service.energy_rate_unit_overrides.is_empty() {
+            let overrides = layout.align_edge_map(&service.energy_rate_unit_overrides);
+            service.energy_rate_unit_overrides = Arc::new(overrides);
+        }
 
         Ok(Arc::new(service))
     }
@@ -10,7 +10,10 @@ use routee_compass_core::util::cache_policy::float_cache_policy::{
     FloatCachePolicy, FloatCachePolicyConfig,
 };
 use routee_compass_powertrain::routee::{
-    prediction::{load_prediction_model, model_type::ModelType, PredictionModelRecord},
+    prediction::{
+        load_prediction_model, mass_adjustment::MassAdjustment, model_type::ModelType,
+        PredictionModelRecord,
+    },
     vehicle::{
         default::{bev::BEV, ice::ICE, phev::PHEV},
         VehicleType,
@@ -148,7 +151,10 @@ fn get_model_record_from_params(
         None => None,
     };
 
-    let model_record = load_prediction_model(
+    let mass_adjustment =
+        parameters.get_config_serde_optional::<MassAdjustment>(&"mass_adjustment", &parent_key)?;
+
+    let mut model_record = load_prediction_model(
         name.clone(),
         &model_path,
         model_type,
@@ -159,6 +165,7 @@ fn get_model_record_from_params(
         real_world_energy_adjustment_option,
         cache,
     )?;
+    model_record.mass_adjustment = mass_adjustment;
 
     Ok(model_record)
 }
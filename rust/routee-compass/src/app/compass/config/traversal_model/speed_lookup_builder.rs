@@ -1,12 +1,23 @@
 use crate::app::compass::config::compass_configuration_field::CompassConfigurationField;
 use crate::app::compass::config::config_json_extension::ConfigJsonExtensions;
-use routee_compass_core::model::traversal::default::speed_traversal_engine::SpeedTraversalEngine;
+use routee_compass_core::model::road_network::{graph::Graph, road_class_table};
+use routee_compass_core::model::traversal::default::speed_grade_table::SpeedGradeTable;
+use routee_compass_core::model::traversal::default::speed_profile::SpeedProfile;
+use routee_compass_core::model::traversal::default::speed_smoothing::smooth_speeds;
+use routee_compass_core::model::traversal::default::speed_traversal_engine::{
+    RoadClassSpeeds, SpeedTraversalEngine,
+};
 
 use routee_compass_core::model::traversal::default::speed_traversal_service::SpeedLookupService;
 use routee_compass_core::model::traversal::traversal_model_builder::TraversalModelBuilder;
 use routee_compass_core::model::traversal::traversal_model_error::TraversalModelError;
 use routee_compass_core::model::traversal::traversal_model_service::TraversalModelService;
-use routee_compass_core::model::unit::{DistanceUnit, SpeedUnit, TimeUnit};
+use routee_compass_core::model::unit::{
+    DistanceUnit, Speed, SpeedUnit, Time, TimeUnit, BASE_TIME_UNIT,
+};
+use routee_compass_core::util::departure_time::GraphTimezone;
+use routee_compass_core::util::geo::distance_function::DistanceFunctionType;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub struct SpeedLookupBuilder {}
@@ -15,24 +26,157 @@ impl TraversalModelBuilder for SpeedLookupBuilder {
     fn build(
         &self,
         params: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
         let traversal_key = CompassConfigurationField::Traversal.to_string();
-        // todo: optional output time unit
-        let filename = params
-            .get_config_path(&"speed_table_input_file", &traversal_key)
-            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
-        let speed_unit = params
-            .get_config_serde::<SpeedUnit>(&"speed_unit", &traversal_key)
-            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
-        let distance_unit = params
-            .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
-            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
-        let time_unit = params
-            .get_config_serde_optional::<TimeUnit>(&"time_unit", &traversal_key)
+        // speeds come from either a speed table or a combined speed and grade file
+        let speed_grade_filename = params
+            .get_config_path_optional(&"speed_grade_table_input_file", &traversal_key)
             .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let speed_table = match speed_grade_filename {
+            Some(filename) => Some(SpeedGradeTable::from_csv(&filename)?.speeds),
+            None => None,
+        };
+        build_service(params, graph, speed_table)
+    }
+
+    /// builds the service with the speeds of a combined speed and grade table
+    /// read by the energy model, in place of the speed table file
+    fn build_with_speed_grade_table(
+        &self,
+        params: &serde_json::Value,
+        graph: &Graph,
+        speed_grade_table: &SpeedGradeTable,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        build_service(params, graph, Some(speed_grade_table.speeds.clone()))
+    }
+}
+
+/// builds the speed lookup service, reading the `speed_table_input_file` unless
+/// the speeds were already loaded from a combined speed and grade file
+fn build_service(
+    params: &serde_json::Value,
+    graph: &Graph,
+    speed_table: Option<Box<[Speed]>>,
+) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+    let traversal_key = CompassConfigurationField::Traversal.to_string();
+    // todo: optional output time unit
+    let speed_unit = params
+        .get_config_serde::<SpeedUnit>(&"speed_unit", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+    let distance_unit = params
+        .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+    let time_unit = params
+        .get_config_serde_optional::<TimeUnit>(&"time_unit", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
-        let e = SpeedTraversalEngine::new(&filename, speed_unit, distance_unit, time_unit)?;
-        let service = Arc::new(SpeedLookupService { e: Arc::new(e) });
-        Ok(service)
+    let min_speed = params
+        .get_config_serde_optional::<Speed>(&"min_speed", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+    let speed_profile = match params
+        .get_config_path_optional(&"speed_profile_input_file", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+    {
+        None => None,
+        Some(profile_filename) => {
+            let bucket_duration = params
+                .get_config_serde::<Time>(&"speed_profile_bucket_duration", &traversal_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+            let profile_time_unit = params
+                .get_config_serde_optional::<TimeUnit>(&"speed_profile_time_unit", &traversal_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+                .unwrap_or(BASE_TIME_UNIT);
+            let profile = SpeedProfile::new(&profile_filename, bucket_duration, profile_time_unit)?;
+            Some(profile)
+        }
+    };
+
+    let distance_function = params
+        .get_config_serde_optional::<DistanceFunctionType>(&"distance_function", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+        .unwrap_or_default();
+    let haversine_radius = params
+        .get_config_serde_optional::<f32>(&"haversine_radius", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+    let speed_smoothing_weight = params
+        .get_config_serde_optional::<f64>(&"speed_smoothing_weight", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+
+    let mut e = match speed_table {
+        Some(speed_table) => SpeedTraversalEngine::from_table(
+            speed_table,
+            speed_unit,
+            distance_unit,
+            time_unit,
+            speed_profile,
+            min_speed,
+        )?,
+        None => {
+            let filename = params
+                .get_config_path(&"speed_table_input_file", &traversal_key)
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+            SpeedTraversalEngine::new(
+                &filename,
+                speed_unit,
+                distance_unit,
+                time_unit,
+                speed_profile,
+                min_speed,
+            )?
+        }
+    };
+    e.distance_function = distance_function.build_with_haversine_radius(haversine_radius);
+    e.summary_precision = params
+        .get_config_serde_optional::<u32>(&"summary_precision", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+    if let Some(timezone) = params
+        .get_config_serde_optional::<GraphTimezone>(&"timezone", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+    {
+        e.timezone = timezone;
+    }
+    if let Some(edge_delay_filename) = params
+        .get_config_path_optional(&"edge_delay_input_file", &traversal_key)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+    {
+        let edge_delay_time_unit = params
+            .get_config_serde_optional::<TimeUnit>(&"edge_delay_time_unit", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+            .unwrap_or(BASE_TIME_UNIT);
+        e.edge_delays = Some(e.read_edge_delays(&edge_delay_filename, edge_delay_time_unit)?);
+    }
+    // reverse edges travel at the same speeds and delays as their edge row
+    let layout = &graph.edge_table_layout;
+    e.speed_table = layout.align(&e.speed_table, |speed| *speed)?;
+    if let Some(weight) = speed_smoothing_weight {
+        e.speed_table = smooth_speeds(&e.speed_table, graph, weight)?;
+    }
+    if let Some(profile) = e.speed_profile.as_mut() {
+        profile.profiles = layout.align(&profile.profiles, |row| row.clone())?;
+    }
+    if let Some(delays) = e.edge_delays.as_ref() {
+        e.edge_delays = Some(layout.align(delays, |delay| *delay)?);
+    }
+    if let Some(speeds) = params
+        .get_config_serde_optional::<HashMap<u8, Speed>>(
+            &"road_class_default_speeds",
+            &traversal_key,
+        )
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?
+    {
+        let road_class_filename = params
+            .get_config_path(&"road_class_input_file", &traversal_key)
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let road_classes =
+            road_class_table::read_edge_road_classes(&road_class_filename, None, layout)?;
+        e.set_road_class_speeds(RoadClassSpeeds {
+            road_classes,
+            speeds,
+        });
     }
+    let service = Arc::new(SpeedLookupService { e: Arc::new(e) });
+    Ok(service)
 }
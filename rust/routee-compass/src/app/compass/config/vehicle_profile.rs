@@ -0,0 +1,64 @@
+use routee_compass_core::model::{
+    frontier::frontier_model_service::FrontierModelService,
+    traversal::traversal_model_service::TraversalModelService,
+    unit::{DistanceUnit, TimeUnit},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// configuration of a named vehicle class, such as "car" or "truck", which
+/// bundles the traversal model, frontier models and unit preferences that
+/// route that class. a query selects a profile by name with the `profile`
+/// field, and queries without one use the top-level traversal and frontier
+/// models.
+///
+/// ```toml
+/// [profiles.truck]
+/// distance_unit = "miles"
+/// time_unit = "minutes"
+/// frontier = [{ type = "road_class", road_class_input_file = "road_class.txt" }]
+///
+/// [profiles.truck.traversal]
+/// type = "speed_table"
+/// speed_table_input_file = "truck-speeds.txt.gz"
+/// speed_unit = "kilometers_per_hour"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VehicleProfileConfig {
+    /// traversal model configuration, with the same schema as the `[traversal]` section
+    pub traversal: serde_json::Value,
+    /// frontier model configurations, all of which must accept an edge for it to be traversed
+    #[serde(default)]
+    pub frontier: Vec<serde_json::Value>,
+    /// preferred distance unit, applied to the traversal model unless it sets one
+    pub distance_unit: Option<DistanceUnit>,
+    /// preferred time unit, applied to the traversal model unless it sets one
+    pub time_unit: Option<TimeUnit>,
+}
+
+impl VehicleProfileConfig {
+    /// the traversal model configuration with the unit preferences of this
+    /// profile applied, unless the traversal model sets its units explicitly.
+    pub fn traversal_params(&self) -> serde_json::Value {
+        let mut params = self.traversal.clone();
+        if let Some(obj) = params.as_object_mut() {
+            if let Some(distance_unit) = &self.distance_unit {
+                obj.entry("distance_unit")
+                    .or_insert(serde_json::json!(distance_unit));
+            }
+            if let Some(time_unit) = &self.time_unit {
+                obj.entry("time_unit")
+                    .or_insert(serde_json::json!(time_unit));
+            }
+        }
+        params
+    }
+}
+
+/// the loaded models of a [`VehicleProfileConfig`], which the
+/// SearchApp uses in place of its default models for queries naming this profile.
+#[derive(Clone)]
+pub struct VehicleProfile {
+    pub traversal_model_service: Arc<dyn TraversalModelService>,
+    pub frontier_model_service: Arc<dyn FrontierModelService>,
+}
@@ -19,6 +19,9 @@ pub enum ResponseOutputPolicy {
         file_flush_rate: Option<i64>,
         // write_mode: WriteMode,
     },
+    /// streams each response to stdout as one line of JSON as soon as it
+    /// completes, so that output can be piped into another program
+    Stdout,
     Combined {
         policies: Vec<Box<ResponseOutputPolicy>>,
     },
@@ -63,6 +66,7 @@ impl ResponseOutputPolicy {
                     iterations,
                 })
             }
+            ResponseOutputPolicy::Stdout => Ok(ResponseSink::Stdout),
             ResponseOutputPolicy::Combined { policies } => {
                 let policies = policies
                     .iter()
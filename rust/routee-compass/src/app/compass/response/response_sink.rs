@@ -1,4 +1,6 @@
-use super::response_output_format::ResponseOutputFormat;
+use super::{
+    response_output_format::ResponseOutputFormat, response_output_format_json as json_ops,
+};
 use crate::app::compass::compass_app_error::CompassAppError;
 use std::io::prelude::*;
 use std::{
@@ -16,6 +18,7 @@ pub enum ResponseSink {
         iterations_per_flush: u64,
         iterations: Arc<Mutex<u64>>,
     },
+    Stdout,
     Combined(Vec<Box<ResponseSink>>),
 }
 
@@ -48,14 +51,22 @@ impl ResponseSink {
                 })?;
 
                 let output_row = format.format_response(response)?;
-                writeln!(file_attained, "{}", output_row).map_err(CompassAppError::IOError)?;
+                writeln!(file_attained, "{}", output_row)?;
                 *it_attained += 1;
                 if *it_attained % iterations_per_flush == 0 {
-                    file_attained.flush().map_err(CompassAppError::IOError)?;
+                    file_attained.flush()?;
                 }
 
                 Ok(())
             }
+            ResponseSink::Stdout => {
+                // the lock keeps lines from concurrent queries from interleaving
+                let output_row = json_ops::format_response(response, true)?;
+                let mut stdout = std::io::stdout().lock();
+                writeln!(stdout, "{}", output_row)?;
+                stdout.flush()?;
+                Ok(())
+            }
             ResponseSink::Combined(policies) => {
                 for policy in policies {
                     policy.write_response(response)?;
@@ -87,10 +98,11 @@ impl ResponseSink {
                 let final_contents = format
                     .final_file_contents()
                     .unwrap_or_else(|| String::from(""));
-                writeln!(file_attained, "{}", final_contents).map_err(CompassAppError::IOError)?;
+                writeln!(file_attained, "{}", final_contents)?;
 
                 Ok(filename.clone())
             }
+            ResponseSink::Stdout => Ok(String::from("")),
             ResponseSink::Combined(policies) => {
                 let mut out_strs = vec![];
                 for policy in policies {
@@ -22,9 +22,7 @@ impl TryFrom<&GeomAppConfig> for GeomApp {
     /// by their row index, starting from zero, treated as EdgeIds.
     /// the app can then process a file which provides a list of EdgeIds and return the corresponding LINESTRINGs.
     fn try_from(conf: &GeomAppConfig) -> Result<Self, Self::Error> {
-        let count =
-            fs_utils::line_count(conf.edge_file.clone(), fs_utils::is_gzip(&conf.edge_file))
-                .map_err(CompassAppError::IOError)?;
+        let count = fs_utils::line_count(conf.edge_file.clone())?;
 
         let mut pb = Bar::builder()
             .total(count)
@@ -42,8 +40,7 @@ impl TryFrom<&GeomAppConfig> for GeomApp {
             Ok(result)
         };
 
-        let geoms = read_utils::read_raw_file(&conf.edge_file, op, Some(cb))
-            .map_err(CompassAppError::IOError)?;
+        let geoms = read_utils::read_raw_file(&conf.edge_file, op, Some(cb))?;
         println!();
         let app = GeomApp { geoms };
         Ok(app)
@@ -54,8 +51,7 @@ impl GeomApp {
     /// run the GeomApp. reads each line of a file, which is expected to be a number coorelating to
     /// some EdgeId. looks up the geometry for that EdgeId.
     pub fn run(&self, file: String) -> Result<Box<[LineString<f32>]>, CompassAppError> {
-        let count = fs_utils::line_count(file.clone(), fs_utils::is_gzip(&file))
-            .map_err(CompassAppError::IOError)?;
+        let count = fs_utils::line_count(file.clone())?;
 
         let mut pb = Bar::builder()
             .total(count)
@@ -81,8 +77,7 @@ impl GeomApp {
             result
         };
 
-        let result: Box<[LineString<f32>]> =
-            read_utils::read_raw_file(&file, op, Some(cb)).map_err(CompassAppError::IOError)?;
+        let result: Box<[LineString<f32>]> = read_utils::read_raw_file(&file, op, Some(cb))?;
         Ok(result)
     }
 }
@@ -0,0 +1,86 @@
+use routee_compass_core::model::road_network::edge_id::EdgeId;
+use serde::Serialize;
+
+/// whether the frontier model of a query accepts an edge, found without
+/// running a search. see [`super::search_app::SearchApp::check_edge_validity`].
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct EdgeValidity {
+    pub edge_id: EdgeId,
+    pub previous_edge_id: Option<EdgeId>,
+    pub valid: bool,
+    /// when the edge is invalid, the restrictions that rejected it, as
+    /// described by the frontier model. None for valid edges and for models
+    /// that do not track their rejections.
+    pub rejection: Option<serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::{
+        compass_app::CompassApp,
+        config::frontier_model::vehicle_restrictions::{
+            vehicle_restriction::VehicleRestriction,
+            vehicle_restriction_service::VehicleRestrictionFrontierService,
+        },
+    };
+    use routee_compass_core::model::unit::{Distance, DistanceUnit};
+    use serde_json::json;
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+    fn speeds_test_app() -> CompassApp {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        CompassApp::try_from(conf_file.as_path()).unwrap()
+    }
+
+    #[test]
+    fn test_check_edge_against_vehicle_restrictions() {
+        let mut app = speeds_test_app();
+        let lookup = HashMap::from([(
+            EdgeId(1),
+            vec![VehicleRestriction::MaximumHeight((
+                Distance::new(3.0),
+                DistanceUnit::Meters,
+            ))],
+        )]);
+        app.search_app.frontier_model_service = Arc::new(VehicleRestrictionFrontierService {
+            vehicle_restriction_lookup: Arc::new(lookup),
+        });
+        let query = json!({
+            "vehicle_parameters": {
+                "height": [4.0, "meters"],
+                "width": [2.5, "meters"],
+                "total_length": [20.0, "meters"],
+                "trailer_length": [15.0, "meters"],
+                "total_weight": [30000.0, "kg"],
+                "number_of_axles": 5
+            }
+        });
+
+        let open = app
+            .search_app
+            .check_edge_validity(&query, EdgeId(0), None)
+            .unwrap();
+        assert!(open.valid);
+        assert_eq!(open.rejection, None);
+
+        let low_bridge = app
+            .search_app
+            .check_edge_validity(&query, EdgeId(1), Some(EdgeId(0)))
+            .unwrap();
+        assert!(!low_bridge.valid);
+        assert_eq!(
+            low_bridge.rejection,
+            Some(json!({ "vehicle_restrictions": { "maximum_height": 1 } }))
+        );
+
+        let missing = app.search_app.check_edge_validity(&query, EdgeId(99), None);
+        assert!(missing.is_err());
+    }
+}
@@ -1,4 +1,7 @@
+pub mod edge_validity;
+pub mod search_admissibility;
 pub mod search_app;
 pub mod search_app_graph_ops;
 pub mod search_app_ops;
 pub mod search_app_result;
+pub mod search_benchmark;
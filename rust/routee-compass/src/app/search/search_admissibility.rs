@@ -0,0 +1,245 @@
+use super::search_app::SearchApp;
+use crate::app::compass::compass_app_error::CompassAppError;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use routee_compass_core::{
+    algorithm::search::{
+        direction::Direction, edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_error::SearchError, search_instance::SearchInstance,
+    },
+    model::{
+        road_network::vertex_id::VertexId,
+        unit::{as_f64::AsF64, Cost},
+    },
+};
+use serde::Serialize;
+
+/// relative difference in route cost below which A* and Dijkstra agree,
+/// absorbing floating point error from summing costs in different orders.
+const COST_TOLERANCE: f64 = 1e-9;
+
+/// an origin/destination pair where A* found a more expensive route than
+/// Dijkstra, which means the traversal model's estimate is not admissible.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AdmissibilityViolation {
+    pub origin_vertex: VertexId,
+    pub destination_vertex: VertexId,
+    pub a_star_cost: Cost,
+    pub dijkstra_cost: Cost,
+}
+
+/// samples origin/destination vertex pairs uniformly at random, skipping
+/// pairs where the origin and destination are the same vertex. the same
+/// seed always produces the same pairs.
+///
+/// # Arguments
+///
+/// * `n_vertices` - number of vertices in the graph
+/// * `n_samples` - number of origin/destination pairs to draw
+/// * `seed` - seed for the random number generator
+///
+/// # Returns
+///
+/// the sampled origin/destination vertex pairs
+pub fn sample_od_vertices(
+    n_vertices: usize,
+    n_samples: usize,
+    seed: u64,
+) -> Vec<(VertexId, VertexId)> {
+    if n_vertices == 0 {
+        return vec![];
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n_samples)
+        .map(|_| {
+            let o = rng.gen_range(0..n_vertices);
+            let d = rng.gen_range(0..n_vertices);
+            (VertexId(o), VertexId(d))
+        })
+        .filter(|(o, d)| o != d)
+        .collect()
+}
+
+/// checks that the traversal model's `estimate_traversal` never causes A*
+/// to return a suboptimal route. for each sampled origin/destination pair,
+/// runs both A* and Dijkstra, which ignores the estimate, and reports the
+/// pairs where A* found the more expensive route. intended as a guard when
+/// writing a custom traversal model.
+///
+/// # Arguments
+///
+/// * `search_app` - the search application whose traversal model is checked
+/// * `base_query` - query fields shared by every search, such as a vehicle model name
+/// * `n_samples` - number of origin/destination pairs to sample
+/// * `seed` - seed used to sample the origin/destination pairs
+///
+/// # Returns
+///
+/// the pairs where the costs differ, which is empty for an admissible model,
+/// or an error if a search fails for a reason other than a missing path
+pub fn check_admissibility(
+    search_app: &SearchApp,
+    base_query: &serde_json::Value,
+    n_samples: usize,
+    seed: u64,
+) -> Result<Vec<AdmissibilityViolation>, CompassAppError> {
+    let od_pairs = sample_od_vertices(search_app.directed_graph.n_vertices(), n_samples, seed);
+    let a_star = SearchAlgorithm::AStarAlgorithm {
+        weight_factor: None,
+    };
+    let mut violations = vec![];
+    for (origin_vertex, destination_vertex) in od_pairs.into_iter() {
+        let si = search_app.build_search_instance(base_query, None)?;
+        let a_star_cost = route_cost(&a_star, origin_vertex, destination_vertex, &si)?;
+        let dijkstra_cost = route_cost(
+            &SearchAlgorithm::Dijkstra,
+            origin_vertex,
+            destination_vertex,
+            &si,
+        )?;
+        let (a_star_cost, dijkstra_cost) = match (a_star_cost, dijkstra_cost) {
+            (Some(a), Some(d)) => (a, d),
+            _ => continue,
+        };
+        let difference = a_star_cost.as_f64() - dijkstra_cost.as_f64();
+        if difference > COST_TOLERANCE * dijkstra_cost.as_f64().abs().max(1.0) {
+            violations.push(AdmissibilityViolation {
+                origin_vertex,
+                destination_vertex,
+                a_star_cost,
+                dijkstra_cost,
+            });
+        }
+    }
+    Ok(violations)
+}
+
+/// runs a search and sums the cost of its route, or None if no path exists
+fn route_cost(
+    algorithm: &SearchAlgorithm,
+    origin: VertexId,
+    destination: VertexId,
+    si: &SearchInstance,
+) -> Result<Option<Cost>, CompassAppError> {
+    let result = match algorithm.run_vertex_oriented(
+        origin,
+        Some(destination),
+        &Direction::Forward,
+        None,
+        si,
+    ) {
+        Ok(result) => result,
+        Err(SearchError::NoPathExists(..)) => return Ok(None),
+        Err(e) => return Err(CompassAppError::SearchError(e)),
+    };
+    let cost = result
+        .routes
+        .first()
+        .map(|route| route.iter().map(EdgeTraversal::total_cost).sum());
+    Ok(cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::compass_app::CompassApp;
+    use routee_compass_core::model::{
+        property::{edge::Edge, vertex::Vertex},
+        state::{state_feature::StateFeature, state_model::StateModel},
+        traversal::{
+            state::state_variable::StateVar, traversal_model::TraversalModel,
+            traversal_model_error::TraversalModelError,
+            traversal_model_service::TraversalModelService,
+        },
+        unit::{Time, TimeUnit},
+    };
+    use std::{path::PathBuf, sync::Arc};
+
+    /// overestimates the time remaining from vertex 1, so that A* skips the
+    /// time-optimal route through it
+    struct OverestimatingModel(Arc<dyn TraversalModel>);
+
+    impl TraversalModel for OverestimatingModel {
+        fn state_features(&self) -> Vec<(String, StateFeature)> {
+            self.0.state_features()
+        }
+        fn traverse_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            self.0.traverse_edge(trajectory, state, state_model)
+        }
+        fn traverse_partial_edge(
+            &self,
+            trajectory: (&Vertex, &Edge, &Vertex),
+            fraction: f64,
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            self.0
+                .traverse_partial_edge(trajectory, fraction, state, state_model)
+        }
+        fn estimate_traversal(
+            &self,
+            od: (&Vertex, &Vertex),
+            state: &mut Vec<StateVar>,
+            state_model: &StateModel,
+        ) -> Result<(), TraversalModelError> {
+            self.0.estimate_traversal(od, state, state_model)?;
+            if od.0.vertex_id == VertexId(1) {
+                state_model.add_time(
+                    state,
+                    &"time".into(),
+                    &Time::new(1000.0),
+                    &TimeUnit::Hours,
+                )?;
+            }
+            Ok(())
+        }
+    }
+
+    struct OverestimatingService(Arc<dyn TraversalModelService>);
+
+    impl TraversalModelService for OverestimatingService {
+        fn build(
+            &self,
+            query: &serde_json::Value,
+        ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+            let model = self.0.build(query)?;
+            Ok(Arc::new(OverestimatingModel(model)))
+        }
+    }
+
+    fn speeds_test_app() -> CompassApp {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        CompassApp::try_from(conf_file.as_path()).unwrap()
+    }
+
+    #[test]
+    fn test_admissible_model_has_no_violations() {
+        let app = speeds_test_app();
+        let violations =
+            check_admissibility(&app.search_app, &serde_json::json!({}), 20, 7).unwrap();
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn test_overestimate_is_flagged() {
+        let mut app = speeds_test_app();
+        let service = app.search_app.traversal_model_service.clone();
+        app.search_app.traversal_model_service = Arc::new(OverestimatingService(service));
+        let violations =
+            check_admissibility(&app.search_app, &serde_json::json!({}), 20, 7).unwrap();
+        assert!(violations
+            .iter()
+            .any(|v| v.origin_vertex == VertexId(0) && v.destination_vertex == VertexId(2)));
+        assert!(violations.iter().all(|v| v.a_star_cost > v.dijkstra_cost));
+    }
+}
@@ -1,8 +1,10 @@
-use super::{search_app_ops, search_app_result::SearchAppResult};
+use super::{edge_validity::EdgeValidity, search_app_ops, search_app_result::SearchAppResult};
 use crate::{
     app::compass::{
         compass_app_error::CompassAppError,
-        config::cost_model::cost_model_service::CostModelService,
+        config::{
+            cost_model::cost_model_service::CostModelService, vehicle_profile::VehicleProfile,
+        },
         search_orientation::SearchOrientation,
     },
     plugin::input::input_json_extensions::InputJsonExtensions,
@@ -10,20 +12,42 @@ use crate::{
 use chrono::Local;
 use routee_compass_core::{
     algorithm::search::{
-        direction::Direction, search_algorithm::SearchAlgorithm,
-        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        backtrack::{self, RouteScore},
+        cost_estimate_function::{CostEstimateFunction, TraversalModelCostEstimate},
+        direction::Direction,
+        isochrone::isochrone_algorithm,
+        k_nearest::{k_nearest_algorithm, k_nearest_result::KNearestResult},
+        search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult,
+        search_error::SearchError,
         search_instance::SearchInstance,
+        search_outcome::SearchOutcome,
     },
     model::{
         access::access_model_service::AccessModelService,
-        frontier::frontier_model_service::FrontierModelService, road_network::graph::Graph,
-        state::state_model::StateModel, termination::termination_model::TerminationModel,
-        traversal::traversal_model_service::TraversalModelService,
+        frontier::frontier_model_service::FrontierModelService,
+        road_network::{
+            edge_id::EdgeId, graph::Graph, graph_error::GraphError, vertex_id::VertexId,
+        },
+        state::state_model::StateModel,
+        termination::termination_model::TerminationModel,
+        traversal::{
+            cached_traversal_model::TraversalCache, traversal_model_service::TraversalModelService,
+        },
+        unit::Cost,
     },
+    util::geo::coord::InternalCoord,
 };
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{atomic::AtomicBool, Arc};
 use std::time;
 
+/// the traversal and frontier model services that build the models of a query
+type ModelServices<'a> = (
+    &'a Arc<dyn TraversalModelService>,
+    &'a Arc<dyn FrontierModelService>,
+);
+
 /// a configured and loaded application to execute searches.
 pub struct SearchApp {
     pub search_algorithm: SearchAlgorithm,
@@ -34,11 +58,20 @@ pub struct SearchApp {
     pub cost_model_service: Arc<CostModelService>,
     pub frontier_model_service: Arc<dyn FrontierModelService>,
     pub termination_model: Arc<TerminationModel>,
+    pub profiles: HashMap<String, VehicleProfile>,
+    /// when present, caches the edge traversals of state-independent traversal models
+    pub traversal_cache: Option<Arc<TraversalCache>>,
+    /// estimates the cost remaining to the destination for A*
+    pub cost_estimate: Arc<dyn CostEstimateFunction>,
 }
 
 impl SearchApp {
     /// builds a new SearchApp from the required components.
     /// handles all of the specialized boxing that allows for simple parallelization.
+    /// A* is guided by the traversal model estimate, see [`TraversalModelCostEstimate`],
+    /// which [`SearchApp::with_cost_estimate`] replaces. the traversal models of the
+    /// app and of each vehicle profile are validated before the app is returned,
+    /// so that misconfiguration is reported at startup.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         search_algorithm: SearchAlgorithm,
@@ -49,8 +82,18 @@ impl SearchApp {
         cost_model_service: CostModelService,
         frontier_model_service: Arc<dyn FrontierModelService>,
         termination_model: TerminationModel,
-    ) -> Self {
-        SearchApp {
+        profiles: HashMap<String, VehicleProfile>,
+    ) -> Result<Self, CompassAppError> {
+        traversal_model_service.validate()?;
+        for (name, profile) in profiles.iter() {
+            profile.traversal_model_service.validate().map_err(|e| {
+                CompassAppError::InvalidInput(format!(
+                    "traversal model of vehicle profile {} failed validation: {}",
+                    name, e
+                ))
+            })?;
+        }
+        Ok(SearchApp {
             search_algorithm,
             directed_graph: Arc::new(graph),
             state_model,
@@ -59,6 +102,21 @@ impl SearchApp {
             cost_model_service: Arc::new(cost_model_service),
             frontier_model_service,
             termination_model: Arc::new(termination_model),
+            profiles,
+            traversal_cache: None,
+            cost_estimate: Arc::new(TraversalModelCostEstimate),
+        })
+    }
+
+    /// guides the A* searches of this app with the provided cost estimate
+    /// function, such as [`ZeroCostEstimate`] for traversal models without an
+    /// admissible estimate.
+    ///
+    /// [`ZeroCostEstimate`]: routee_compass_core::algorithm::search::cost_estimate_function::ZeroCostEstimate
+    pub fn with_cost_estimate(self, cost_estimate: Arc<dyn CostEstimateFunction>) -> Self {
+        SearchApp {
+            cost_estimate,
+            ..self
         }
     }
 
@@ -75,17 +133,71 @@ impl SearchApp {
     ///
     /// # Results
     ///
-    /// The complete set of trees, routes, and search assets for this run.
+    /// The outcome of the search, holding the complete set of trees and routes
+    /// when it found them, along with the search assets for this run.
     pub fn run(
         &self,
         query: &serde_json::Value,
         search_orientation: &SearchOrientation,
-    ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
+    ) -> Result<(SearchOutcome<SearchAppResult>, SearchInstance), CompassAppError> {
+        self.run_with_cancellation(query, search_orientation, None)
+    }
+
+    /// runs a search which can be abandoned by setting the cancellation flag,
+    /// such as when the client that submitted the query disconnects. a
+    /// cancelled search returns a [`SearchOutcome::Cancelled`] outcome.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON search query provided by the user
+    /// * `search_orientation` - whether to orient by vertex or edge
+    /// * `cancellation` - optional flag which cancels the search when set
+    ///
+    /// # Results
+    ///
+    /// The outcome of the search, holding the complete set of trees and routes
+    /// when it found them, along with the search assets for this run.
+    pub fn run_with_cancellation(
+        &self,
+        query: &serde_json::Value,
+        search_orientation: &SearchOrientation,
+        cancellation: Option<Arc<AtomicBool>>,
+    ) -> Result<(SearchOutcome<SearchAppResult>, SearchInstance), CompassAppError> {
         let search_start_time = Local::now();
-        let (results, si) = match search_orientation {
-            SearchOrientation::Vertex => self.run_vertex_oriented(query),
-            SearchOrientation::Edge => self.run_edge_oriented(query),
-        }?;
+        let (outcome, si) = match search_orientation {
+            SearchOrientation::Vertex => self.run_vertex_oriented(query, cancellation)?,
+            SearchOrientation::Edge => self.run_edge_oriented(query, cancellation)?,
+        };
+        let include_route_coordinates = query
+            .get_include_route_coordinates()
+            .map_err(CompassAppError::PluginError)?;
+        let outcome = outcome.try_map(|results| {
+            self.build_app_result(results, include_route_coordinates, search_start_time)
+        })?;
+        Ok((outcome, si))
+    }
+
+    /// adds the route coordinates and timing of a run to the result of its search
+    fn build_app_result(
+        &self,
+        results: SearchAlgorithmResult,
+        include_route_coordinates: bool,
+        search_start_time: chrono::DateTime<Local>,
+    ) -> Result<SearchAppResult, GraphError> {
+        let route_coordinates = if include_route_coordinates {
+            let coordinates = results
+                .routes
+                .iter()
+                .map(|route| {
+                    let edge_ids = route.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+                    let coords = self.directed_graph.route_coordinates(&edge_ids)?;
+                    Ok(coords.into_iter().map(InternalCoord).collect())
+                })
+                .collect::<Result<Vec<_>, GraphError>>()?;
+            Some(coordinates)
+        } else {
+            None
+        };
 
         let search_end_time = Local::now();
         let search_runtime = (search_end_time - search_start_time)
@@ -99,19 +211,33 @@ impl SearchApp {
 
         let result = SearchAppResult {
             routes: results.routes,
+            route_coordinates,
             trees: results.trees,
             search_executed_time: search_start_time.to_rfc3339(),
             search_runtime,
             iterations: results.iterations,
+            search_metadata: results.metadata,
         };
 
-        Ok((result, si))
+        Ok(result)
     }
 
+    /// runs a vertex-oriented search for a query, sorting its result into a
+    /// [`SearchOutcome`] the same way as [`SearchApp::run_edge_oriented`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON search query provided by the user
+    /// * `cancellation` - optional flag which cancels the search when set
+    ///
+    /// # Results
+    ///
+    /// The outcome of the search along with the assets that ran it.
     pub fn run_vertex_oriented(
         &self,
         query: &serde_json::Value,
-    ) -> Result<(SearchAlgorithmResult, SearchInstance), CompassAppError> {
+        cancellation: Option<Arc<AtomicBool>>,
+    ) -> Result<(SearchOutcome, SearchInstance), CompassAppError> {
         let o = query
             .get_origin_vertex()
             .map_err(CompassAppError::PluginError)?;
@@ -119,28 +245,172 @@ impl SearchApp {
             .get_destination_vertex()
             .map_err(CompassAppError::PluginError)?;
 
-        let search_instance = self.build_search_instance(query)?;
-        self.search_algorithm
-            .run_vertex_oriented(o, d, &Direction::Forward, &search_instance)
-            .map(|search_result| (search_result, search_instance))
-            .map_err(CompassAppError::SearchError)
+        let avoid_edges = query
+            .get_avoid_edges()
+            .map_err(CompassAppError::PluginError)?;
+        let required_edges = query
+            .get_required_edges()
+            .map_err(CompassAppError::PluginError)?
+            .filter(|edges| !edges.is_empty());
+        let via_vertices = query
+            .get_via_vertices()
+            .map_err(CompassAppError::PluginError)?
+            .filter(|vias| !vias.is_empty());
+
+        let weighted_algorithm = self.weighted_search_algorithm(query)?;
+        let search_algorithm = weighted_algorithm
+            .as_ref()
+            .unwrap_or(&self.search_algorithm);
+        let search_instance = self.build_search_instance(query, cancellation)?;
+        let search_result = match (required_edges, via_vertices, d) {
+            (None, None, _) => search_algorithm.run_vertex_oriented(
+                o,
+                d,
+                &Direction::Forward,
+                avoid_edges.as_ref(),
+                &search_instance,
+            ),
+            (Some(required), None, Some(d)) => search_algorithm.run_required_edges(
+                o,
+                &required,
+                d,
+                avoid_edges.as_ref(),
+                &search_instance,
+            ),
+            (None, Some(vias), Some(d)) => {
+                search_algorithm.run_via(o, &vias, d, avoid_edges.as_ref(), &search_instance)
+            }
+            (Some(_), Some(_), _) => {
+                return Err(CompassAppError::InvalidInput(String::from(
+                    "required_edges and via_vertices cannot be combined",
+                )))
+            }
+            (Some(_), None, None) => {
+                return Err(CompassAppError::InvalidInput(String::from(
+                    "required_edges needs a destination",
+                )))
+            }
+            (None, Some(_), None) => {
+                return Err(CompassAppError::InvalidInput(String::from(
+                    "via_vertices needs a destination",
+                )))
+            }
+        };
+        let max_cost = query.get_max_cost().map_err(CompassAppError::PluginError)?;
+        let outcome = SearchOutcome::from_search_result(search_result, max_cost)?;
+        Ok((outcome, search_instance))
     }
 
+    /// runs an edge-oriented search for a query. a search that finds no
+    /// path, finds only routes over the query max_cost, or is cancelled
+    /// returns the matching [`SearchOutcome`], while genuine failures such
+    /// as missing ids are returned as errors.
+    ///
+    /// when the query provides a destination edge and an `origin_edge_offset`
+    /// or `destination_edge_offset`, the route charges only the part of the
+    /// origin edge after the offset and the part of the destination edge
+    /// before it, see [`SearchAlgorithm::run_between_edge_offsets`]. a
+    /// missing offset defaults to the full edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON search query provided by the user
+    /// * `cancellation` - optional flag which cancels the search when set
+    ///
+    /// # Results
+    ///
+    /// The outcome of the search along with the assets that ran it.
     pub fn run_edge_oriented(
         &self,
         query: &serde_json::Value,
-    ) -> Result<(SearchAlgorithmResult, SearchInstance), CompassAppError> {
+        cancellation: Option<Arc<AtomicBool>>,
+    ) -> Result<(SearchOutcome, SearchInstance), CompassAppError> {
         let o = query
             .get_origin_edge()
             .map_err(CompassAppError::PluginError)?;
         let d_opt = query
             .get_destination_edge()
             .map_err(CompassAppError::PluginError)?;
-        let search_instance = self.build_search_instance(query)?;
-        self.search_algorithm
-            .run_edge_oriented(o, d_opt, &Direction::Forward, &search_instance)
-            .map(|search_result| (search_result, search_instance))
-            .map_err(CompassAppError::SearchError)
+        let avoid_edges = query
+            .get_avoid_edges()
+            .map_err(CompassAppError::PluginError)?;
+        if query
+            .get_required_edges()
+            .map_err(CompassAppError::PluginError)?
+            .is_some_and(|edges| !edges.is_empty())
+        {
+            return Err(CompassAppError::InvalidInput(String::from(
+                "required_edges is only supported for vertex-oriented queries",
+            )));
+        }
+        if query
+            .get_via_vertices()
+            .map_err(CompassAppError::PluginError)?
+            .is_some_and(|vias| !vias.is_empty())
+        {
+            return Err(CompassAppError::InvalidInput(String::from(
+                "via_vertices is only supported for vertex-oriented queries",
+            )));
+        }
+        let max_cost = query.get_max_cost().map_err(CompassAppError::PluginError)?;
+        let o_offset = query.get_origin_edge_offset()?;
+        let d_offset = query.get_destination_edge_offset()?;
+        let weighted_algorithm = self.weighted_search_algorithm(query)?;
+        let search_algorithm = weighted_algorithm
+            .as_ref()
+            .unwrap_or(&self.search_algorithm);
+        let search_instance = self.build_search_instance(query, cancellation)?;
+        let search_result = match d_opt {
+            Some(d) if o_offset.is_some() || d_offset.is_some() => search_algorithm
+                .run_between_edge_offsets(
+                    o,
+                    o_offset.unwrap_or(0.0),
+                    d,
+                    d_offset.unwrap_or(1.0),
+                    avoid_edges.as_ref(),
+                    &search_instance,
+                ),
+            _ => search_algorithm.run_edge_oriented(
+                o,
+                d_opt,
+                &Direction::Forward,
+                avoid_edges.as_ref(),
+                &search_instance,
+            ),
+        };
+        let outcome = SearchOutcome::from_search_result(search_result, max_cost)?;
+        Ok((outcome, search_instance))
+    }
+
+    /// when the query sets a `heuristic_weight`, builds the weighted A*
+    /// algorithm that replaces the configured A* search for this query. a
+    /// weight above 1.0 trades optimality for speed, and the search metadata
+    /// of the result reports the weight as its suboptimality bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the user query initiating this search
+    ///
+    /// # Results
+    ///
+    /// the weighted algorithm, None if the query has no heuristic weight, or
+    /// an error if the configured algorithm is not A*
+    fn weighted_search_algorithm(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Option<SearchAlgorithm>, CompassAppError> {
+        let weight = match query.get_heuristic_weight()? {
+            None => return Ok(None),
+            Some(weight) => weight,
+        };
+        match self.search_algorithm {
+            SearchAlgorithm::AStarAlgorithm { .. } => Ok(Some(SearchAlgorithm::AStarAlgorithm {
+                weight_factor: Some(Cost::new(weight)),
+            })),
+            _ => Err(CompassAppError::InvalidInput(String::from(
+                "heuristic_weight requires the a* search algorithm",
+            ))),
+        }
     }
 
     /// builds the assets that will run the search for this query instance.
@@ -148,6 +418,7 @@ impl SearchApp {
     /// # Arguments
     ///
     /// * `query` - the user query initiating this search
+    /// * `cancellation` - optional flag which cancels the search when set
     ///
     /// # Results
     ///
@@ -155,22 +426,35 @@ impl SearchApp {
     pub fn build_search_instance(
         &self,
         query: &serde_json::Value,
+        cancellation: Option<Arc<AtomicBool>>,
     ) -> Result<SearchInstance, SearchError> {
-        let traversal_model = self.traversal_model_service.build(query)?;
+        let (traversal_model_service, frontier_model_service) = self.select_profile(query)?;
+        let traversal_model = traversal_model_service.build(query)?;
         let access_model = self.access_model_service.build(query)?;
 
         let state_features =
             search_app_ops::collect_features(query, traversal_model.clone(), access_model.clone())?;
         let state_model_instance = self.state_model.extend(state_features)?;
         let state_model = Arc::new(state_model_instance);
+        let traversal_model = match &self.traversal_cache {
+            Some(cache) => cache.wrap(traversal_model, &state_model)?,
+            None => traversal_model,
+        };
 
         let cost_model = self
             .cost_model_service
             .build(query, state_model.clone())
             .map_err(|e| SearchError::BuildError(e.to_string()))?;
-        let frontier_model = self
-            .frontier_model_service
-            .build(query, state_model.clone())?;
+        let frontier_model = frontier_model_service.build(query, state_model.clone())?;
+        let max_edges = query
+            .get_max_edges()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let max_cost = query
+            .get_max_cost()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let prefer_fewer_edges = query
+            .get_prefer_fewer_edges()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
 
         let search_assets = SearchInstance {
             directed_graph: self.directed_graph.clone(),
@@ -180,8 +464,179 @@ impl SearchApp {
             cost_model,
             frontier_model,
             termination_model: self.termination_model.clone(),
+            max_edges,
+            max_cost,
+            prefer_fewer_edges,
+            cancellation,
+            cost_estimate: self.cost_estimate.clone(),
         };
 
         Ok(search_assets)
     }
+
+    /// checks whether the frontier model built for a query accepts an edge,
+    /// without running a search. the edge is checked from the initial search
+    /// state, so limits on accumulated state are not considered. a new
+    /// frontier model is built for each check, so its rejection summary
+    /// describes only this edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the query the frontier model is built for, such as a vehicle profile
+    /// * `edge_id` - the edge to check
+    /// * `previous_edge_id` - optional edge traversed before this edge, for turn restrictions
+    ///
+    /// # Returns
+    ///
+    /// an [`EdgeValidity`] holding whether the edge is valid and, if not, the
+    /// rejection summary naming the restriction that rejected it, or an error
+    /// if an edge is missing or the frontier model fails
+    pub fn check_edge_validity(
+        &self,
+        query: &serde_json::Value,
+        edge_id: EdgeId,
+        previous_edge_id: Option<EdgeId>,
+    ) -> Result<EdgeValidity, CompassAppError> {
+        let si = self.build_search_instance(query, None)?;
+        let edge = self.directed_graph.get_edge(edge_id)?;
+        let previous_edge = previous_edge_id
+            .map(|e| self.directed_graph.get_edge(e))
+            .transpose()?;
+        let state = si.state_model.initial_state()?;
+        let valid =
+            si.frontier_model
+                .valid_frontier(edge, &state, previous_edge, &si.state_model)?;
+        let rejection = if valid {
+            None
+        } else {
+            si.frontier_model.rejection_summary()
+        };
+        Ok(EdgeValidity {
+            edge_id,
+            previous_edge_id,
+            valid,
+            rejection,
+        })
+    }
+
+    /// scores a fixed route under the models built for a query, without
+    /// running a search. running the same route with queries that select
+    /// different models, such as another vehicle profile, compares the
+    /// models on the same physical path. see [`backtrack::score_route`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the query the models are built for
+    /// * `edge_ids` - the route in forward (origin to destination) order
+    ///
+    /// # Returns
+    ///
+    /// the traversals, final state and total cost of the route, or an error
+    /// if the route is disconnected or a traversal fails
+    pub fn score_route(
+        &self,
+        query: &serde_json::Value,
+        edge_ids: &[EdgeId],
+    ) -> Result<RouteScore, CompassAppError> {
+        let si = self.build_search_instance(query, None)?;
+        let score = backtrack::score_route(edge_ids, &si)?;
+        Ok(score)
+    }
+
+    /// finds the k candidate targets reachable most cheaply from the origin
+    /// vertex of a query, each with its route, stopping the search once the
+    /// k-th target is settled. see [`k_nearest_algorithm::run_k_nearest`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the query holding the origin and any avoided edges
+    /// * `targets` - the candidate targets, such as the vertices of charging stations
+    /// * `k` - the maximum number of targets to return
+    ///
+    /// # Returns
+    ///
+    /// up to k reachable targets ordered by cost, or an error if the query is
+    /// missing its origin or the search fails
+    pub fn run_k_nearest(
+        &self,
+        query: &serde_json::Value,
+        targets: &HashSet<VertexId>,
+        k: usize,
+    ) -> Result<KNearestResult, SearchError> {
+        let o = query
+            .get_origin_vertex()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let avoid_edges = query
+            .get_avoid_edges()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let si = self.build_search_instance(query, None)?;
+        k_nearest_algorithm::run_k_nearest(
+            o,
+            targets,
+            k,
+            &Direction::Forward,
+            avoid_edges.as_ref(),
+            &si,
+        )
+    }
+
+    /// answers whether the destination vertex of a query can be reached from
+    /// its origin vertex within the query `max_cost`, without building a
+    /// route. see [`isochrone_algorithm::is_reachable`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the query holding the origin, destination and `max_cost`
+    ///
+    /// # Returns
+    ///
+    /// true if the destination is reachable within the budget, or an error
+    /// if the query is missing a field or the search fails
+    pub fn is_reachable(&self, query: &serde_json::Value) -> Result<bool, SearchError> {
+        let o = query
+            .get_origin_vertex()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        let d = query
+            .get_destination_vertex()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?
+            .ok_or_else(|| {
+                SearchError::BuildError(String::from("reachability requires a destination"))
+            })?;
+        let budget = query
+            .get_max_cost()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?
+            .ok_or_else(|| {
+                SearchError::BuildError(String::from("reachability requires a max_cost"))
+            })?;
+        let si = self.build_search_instance(query, None)?;
+        let reachable = isochrone_algorithm::is_reachable(o, d, budget, &si)?;
+        Ok(reachable)
+    }
+
+    /// selects the traversal and frontier model services for a query, which
+    /// come from the vehicle profile named by the query's `profile` field, or
+    /// are the default services when the query names no profile.
+    fn select_profile(&self, query: &serde_json::Value) -> Result<ModelServices<'_>, SearchError> {
+        let profile_name = query
+            .get_profile()
+            .map_err(|e| SearchError::BuildError(e.to_string()))?;
+        match profile_name {
+            None => Ok((&self.traversal_model_service, &self.frontier_model_service)),
+            Some(name) => {
+                let profile = self.profiles.get(&name).ok_or_else(|| {
+                    let mut known = self.profiles.keys().map(String::as_str).collect::<Vec<_>>();
+                    known.sort();
+                    SearchError::BuildError(format!(
+                        "unknown vehicle profile '{}', must be one of [{}]",
+                        name,
+                        known.join(", ")
+                    ))
+                })?;
+                Ok((
+                    &profile.traversal_model_service,
+                    &profile.frontier_model_service,
+                ))
+            }
+        }
+    }
 }
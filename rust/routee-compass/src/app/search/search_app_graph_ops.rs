@@ -23,18 +23,12 @@ pub trait SearchAppGraphOps {
 
 impl SearchAppGraphOps for SearchApp {
     fn get_edge_origin(&self, edge_id: EdgeId) -> Result<VertexId, CompassAppError> {
-        let edge = self
-            .directed_graph
-            .get_edge(edge_id)
-            .map_err(CompassAppError::GraphError)?;
+        let edge = self.directed_graph.get_edge(edge_id)?;
         Ok(edge.src_vertex_id)
     }
 
     fn get_edge_destination(&self, edge_id: EdgeId) -> Result<VertexId, CompassAppError> {
-        let edge = self
-            .directed_graph
-            .get_edge(edge_id)
-            .map_err(CompassAppError::GraphError)?;
+        let edge = self.directed_graph.get_edge(edge_id)?;
         Ok(edge.dst_vertex_id)
     }
 
@@ -43,10 +37,7 @@ impl SearchAppGraphOps for SearchApp {
         edge_id: EdgeId,
         distance_unit: Option<DistanceUnit>,
     ) -> Result<Distance, CompassAppError> {
-        let edge = self
-            .directed_graph
-            .get_edge(edge_id)
-            .map_err(CompassAppError::GraphError)?;
+        let edge = self.directed_graph.get_edge(edge_id)?;
         let result_base = edge.distance;
         let result = match distance_unit {
             Some(du) => DistanceUnit::Meters.convert(&result_base, &du),
@@ -60,10 +51,7 @@ impl SearchAppGraphOps for SearchApp {
         vertex_id: VertexId,
         direction: Direction,
     ) -> Result<Vec<EdgeId>, CompassAppError> {
-        let incident_edges = self
-            .directed_graph
-            .incident_edges(vertex_id, direction)
-            .map_err(CompassAppError::GraphError)?;
+        let incident_edges = self.directed_graph.incident_edges(vertex_id, direction)?;
         Ok(incident_edges)
     }
 }
@@ -1,8 +1,12 @@
 use allocative::Allocative;
 
 use routee_compass_core::{
-    algorithm::search::{edge_traversal::EdgeTraversal, search_tree_branch::SearchTreeBranch},
+    algorithm::search::{
+        edge_traversal::EdgeTraversal, search_metadata::SearchMetadata,
+        search_tree_branch::SearchTreeBranch,
+    },
     model::road_network::vertex_id::VertexId,
+    util::geo::coord::InternalCoord,
 };
 
 use std::{collections::HashMap, time::Duration};
@@ -10,8 +14,12 @@ use std::{collections::HashMap, time::Duration};
 #[derive(Allocative)]
 pub struct SearchAppResult {
     pub routes: Vec<Vec<EdgeTraversal>>,
+    /// for each route, the coordinates of the vertices it visits, if requested
+    /// with the `include_route_coordinates` query flag
+    pub route_coordinates: Option<Vec<Vec<InternalCoord<f32>>>>,
     pub trees: Vec<HashMap<VertexId, SearchTreeBranch>>,
     pub search_executed_time: String,
     pub search_runtime: Duration,
     pub iterations: u64,
+    pub search_metadata: SearchMetadata,
 }
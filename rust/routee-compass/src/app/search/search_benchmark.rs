@@ -0,0 +1,257 @@
+use super::search_app::SearchApp;
+use crate::{
+    app::compass::{compass_app_error::CompassAppError, search_orientation::SearchOrientation},
+    plugin::input::input_json_extensions::InputJsonExtensions,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::prelude::*;
+use routee_compass_core::algorithm::search::search_outcome::SearchOutcome;
+use routee_compass_core::model::road_network::edge_id::EdgeId;
+use serde::Serialize;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// outcome of a single benchmark search between a sampled origin and destination edge.
+#[derive(Serialize, Clone, Debug)]
+pub struct SearchBenchmarkSample {
+    pub origin_edge: EdgeId,
+    pub destination_edge: EdgeId,
+    pub duration_ms: f64,
+    /// number of vertices settled by the search, see [`SearchMetadata::vertices_settled`]
+    ///
+    /// [`SearchMetadata::vertices_settled`]: routee_compass_core::algorithm::search::search_metadata::SearchMetadata::vertices_settled
+    pub explored_vertices: u64,
+    /// the error message if the search failed, such as when no path exists
+    pub error: Option<String>,
+}
+
+/// min/median/p95/max of some benchmark measure, using the nearest-rank percentile.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct BenchmarkStats {
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+/// results of running a search benchmark. statistics are computed over
+/// the successful searches only, and are None if every search failed.
+#[derive(Serialize, Clone, Debug)]
+pub struct SearchBenchmark {
+    pub seed: u64,
+    pub n_searches: usize,
+    pub n_failed: usize,
+    pub duration_ms: Option<BenchmarkStats>,
+    pub explored_vertices: Option<BenchmarkStats>,
+    #[serde(skip)]
+    pub samples: Vec<SearchBenchmarkSample>,
+}
+
+impl SearchBenchmark {
+    /// writes one row per sampled search to a CSV file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - file to write
+    ///
+    /// # Returns
+    ///
+    /// nothing, or an error writing the file
+    pub fn write_csv(&self, path: &Path) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for sample in self.samples.iter() {
+            writer.serialize(sample)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// samples origin/destination edge pairs uniformly at random. the same
+/// seed always produces the same pairs, so benchmarks are comparable
+/// across runs.
+///
+/// # Arguments
+///
+/// * `n_edges` - number of edges in the graph
+/// * `n_samples` - number of origin/destination pairs to sample
+/// * `seed` - seed for the random number generator
+///
+/// # Returns
+///
+/// the sampled origin/destination edge pairs
+pub fn sample_od_edges(n_edges: usize, n_samples: usize, seed: u64) -> Vec<(EdgeId, EdgeId)> {
+    if n_edges == 0 {
+        return vec![];
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n_samples)
+        .map(|_| {
+            let o = rng.gen_range(0..n_edges);
+            let d = rng.gen_range(0..n_edges);
+            (EdgeId(o), EdgeId(d))
+        })
+        .collect()
+}
+
+/// runs edge-oriented searches between randomly-sampled origin/destination
+/// edges in parallel on the rayon thread pool and collects the distribution
+/// of search durations and explored vertex counts. samples are reported in
+/// the order they were drawn.
+///
+/// # Arguments
+///
+/// * `search_app` - the search application to benchmark
+/// * `base_query` - query fields shared by every search, such as cost weights
+/// * `n_samples` - number of searches to run
+/// * `seed` - seed used to sample the origin/destination edges
+///
+/// # Returns
+///
+/// the benchmark results, or an error if the base query is not a JSON object
+pub fn run_search_benchmark(
+    search_app: &SearchApp,
+    base_query: &serde_json::Value,
+    n_samples: usize,
+    seed: u64,
+) -> Result<SearchBenchmark, CompassAppError> {
+    let od_pairs = sample_od_edges(search_app.directed_graph.n_edges(), n_samples, seed);
+    let mut queries = Vec::with_capacity(od_pairs.len());
+    for (origin_edge, destination_edge) in od_pairs.into_iter() {
+        let mut query = base_query.clone();
+        query.add_origin_edge(origin_edge)?;
+        query.add_destination_edge(destination_edge)?;
+        queries.push((origin_edge, destination_edge, query));
+    }
+    let samples = queries
+        .into_par_iter()
+        .map(|(origin_edge, destination_edge, query)| {
+            let start_time = Instant::now();
+            let result = search_app.run(&query, &SearchOrientation::Edge);
+            let duration = Instant::now().duration_since(start_time);
+
+            let (explored_vertices, error) = match result {
+                Ok((SearchOutcome::Found(result), _)) => {
+                    (result.search_metadata.vertices_settled, None)
+                }
+                Ok((outcome, _)) => (
+                    outcome.metadata().map_or(0, |m| m.vertices_settled),
+                    outcome.error().map(|e| e.to_string()),
+                ),
+                Err(e) => (0, Some(e.to_string())),
+            };
+            SearchBenchmarkSample {
+                origin_edge,
+                destination_edge,
+                duration_ms: duration_as_millis(&duration),
+                explored_vertices,
+                error,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let successes = samples.iter().filter(|s| s.error.is_none());
+    let durations = successes.clone().map(|s| s.duration_ms).collect::<Vec<_>>();
+    let explored = successes
+        .map(|s| s.explored_vertices as f64)
+        .collect::<Vec<_>>();
+    Ok(SearchBenchmark {
+        seed,
+        n_searches: samples.len(),
+        n_failed: samples.len() - durations.len(),
+        duration_ms: benchmark_stats(durations),
+        explored_vertices: benchmark_stats(explored),
+        samples,
+    })
+}
+
+/// computes min/median/p95/max using the nearest-rank percentile.
+/// returns None for an empty set of values.
+pub fn benchmark_stats(mut values: Vec<f64>) -> Option<BenchmarkStats> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let nearest_rank = |p: f64| {
+        let rank = (p * values.len() as f64).ceil() as usize;
+        values[rank.clamp(1, values.len()) - 1]
+    };
+    Some(BenchmarkStats {
+        min: values[0],
+        median: nearest_rank(0.5),
+        p95: nearest_rank(0.95),
+        max: values[values.len() - 1],
+    })
+}
+
+fn duration_as_millis(duration: &Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::compass::compass_app::CompassApp;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_fixed_seed_reproduces_samples() {
+        let a = sample_od_edges(1000, 50, 42);
+        let b = sample_od_edges(1000, 50, 42);
+        let c = sample_od_edges(1000, 50, 43);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.iter().all(|(o, d)| o.0 < 1000 && d.0 < 1000));
+    }
+
+    #[test]
+    fn test_benchmark_stats() {
+        let values = (1..=20).rev().map(|v| v as f64).collect::<Vec<_>>();
+        let stats = benchmark_stats(values).unwrap();
+        assert_eq!(
+            stats,
+            BenchmarkStats {
+                min: 1.0,
+                median: 10.0,
+                p95: 19.0,
+                max: 20.0
+            }
+        );
+        assert!(benchmark_stats(vec![]).is_none());
+    }
+
+    #[test]
+    fn test_run_search_benchmark() {
+        let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("speeds_test")
+            .join("speeds_test.toml");
+        let app = CompassApp::try_from(conf_file.as_path()).unwrap();
+        let benchmark =
+            run_search_benchmark(&app.search_app, &serde_json::json!({}), 20, 7).unwrap();
+        assert_eq!(benchmark.n_searches, 20);
+        assert_eq!(benchmark.samples.len(), 20);
+        let succeeded = benchmark.n_searches - benchmark.n_failed;
+        assert_eq!(benchmark.duration_ms.is_some(), succeeded > 0);
+
+        // samples keep the order in which they were drawn
+        let sampled = sample_od_edges(app.search_app.directed_graph.n_edges(), 20, 7);
+        let searched = benchmark
+            .samples
+            .iter()
+            .map(|s| (s.origin_edge, s.destination_edge))
+            .collect::<Vec<_>>();
+        assert_eq!(searched, sampled);
+
+        let csv_dir = tempfile::tempdir().unwrap();
+        let csv_file = csv_dir.path().join("search_benchmark.csv");
+        benchmark.write_csv(&csv_file).unwrap();
+        let rows = csv::Reader::from_path(&csv_file).unwrap().records().count();
+        assert_eq!(rows, 20);
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     },
     plugin::input::input_plugin::InputPlugin,
 };
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use super::debug_plugin::DebugInputPlugin;
@@ -14,6 +15,7 @@ impl InputPluginBuilder for DebugInputPluginBuilder {
     fn build(
         &self,
         _parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         Ok(Arc::new(DebugInputPlugin {}))
     }
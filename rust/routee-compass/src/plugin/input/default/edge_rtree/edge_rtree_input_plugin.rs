@@ -1,4 +1,4 @@
-use super::edge_rtree_record::EdgeRtreeRecord;
+use super::edge_segment_rtree::{EdgeSegmentRTree, NearestEdge};
 use crate::{
     app::compass::config::{
         compass_configuration_error::CompassConfigurationError,
@@ -15,23 +15,24 @@ use crate::{
         plugin_error::PluginError,
     },
 };
-use geo_types::Coord;
+use geo_types::{Coord, LineString};
 use routee_compass_core::{
-    model::road_network::edge_id::EdgeId,
-    model::unit::{as_f64::AsF64, Distance, DistanceUnit, BASE_DISTANCE_UNIT},
+    model::road_network::{
+        edge_id::EdgeId, edge_table_layout::EdgeTableLayout, graph_error::GraphError,
+    },
+    model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT},
     util::{
         fs::{read_decoders, read_utils},
-        geo::geo_io_utils::read_linestring_text_file,
+        geo::{geo_io_utils::read_linestring_text_file, haversine},
     },
 };
-use rstar::RTree;
 use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
 };
 
 pub struct EdgeRtreeInputPlugin {
-    pub rtree: RTree<EdgeRtreeRecord>,
+    pub rtree: EdgeSegmentRTree,
     pub tolerance: Option<(Distance, DistanceUnit)>,
 
     // TODO: instead of having to load the road classes and the truck restrictions
@@ -47,7 +48,8 @@ pub struct EdgeRtreeInputPlugin {
 }
 
 impl InputPlugin for EdgeRtreeInputPlugin {
-    /// finds the nearest edge ids to the user-provided origin and destination coordinates.
+    /// snaps the user-provided origin and destination coordinates to the closest point
+    /// on the nearest edges, adding each edge id and the offset along the edge to the query.
     /// optionally restricts the search to a subset of road classes tagged by the user.
     fn process(&self, query: &mut serde_json::Value) -> Result<(), PluginError> {
         let road_classes = self.road_class_parser.read_query(query).map_err(|e| {
@@ -61,7 +63,7 @@ impl InputPlugin for EdgeRtreeInputPlugin {
         let src_coord = query.get_origin_coordinate()?;
         let dst_coord_option = query.get_destination_coordinate()?;
 
-        let source_edge_id = search(
+        let source_edge = search(
             src_coord,
             &self.rtree,
             self.tolerance,
@@ -71,7 +73,7 @@ impl InputPlugin for EdgeRtreeInputPlugin {
             &vehicle_parameters,
         )?
         .ok_or_else(|| matching_error(&src_coord, self.tolerance))?;
-        let destination_edge_id_option = match dst_coord_option {
+        let destination_edge_option = match dst_coord_option {
             None => Ok(None),
             Some(dst_coord) => search(
                 dst_coord,
@@ -86,11 +88,13 @@ impl InputPlugin for EdgeRtreeInputPlugin {
             .ok_or_else(|| matching_error(&dst_coord, self.tolerance)),
         }?;
 
-        query.add_origin_edge(source_edge_id)?;
-        match destination_edge_id_option {
+        query.add_origin_edge(source_edge.edge_id)?;
+        query.add_origin_edge_offset(source_edge.fraction as f64)?;
+        match destination_edge_option {
             None => {}
-            Some(destination_edge_id) => {
-                query.add_destination_edge(destination_edge_id)?;
+            Some(destination_edge) => {
+                query.add_destination_edge(destination_edge.edge_id)?;
+                query.add_destination_edge_offset(destination_edge.fraction as f64)?;
             }
         }
 
@@ -99,6 +103,8 @@ impl InputPlugin for EdgeRtreeInputPlugin {
 }
 
 impl EdgeRtreeInputPlugin {
+    /// builds the plugin from per-edge files, which are aligned with the
+    /// `EdgeId`s of the graph through its `layout`.
     pub fn new(
         road_class_file: Option<String>,
         vehicle_restriction_file: Option<String>,
@@ -106,6 +112,7 @@ impl EdgeRtreeInputPlugin {
         tolerance_distance: Option<Distance>,
         distance_unit: Option<DistanceUnit>,
         road_class_parser: RoadClassParser,
+        layout: &EdgeTableLayout,
     ) -> Result<Self, CompassConfigurationError> {
         let road_class_lookup: Option<Vec<u8>> = match road_class_file {
             None => Ok(None),
@@ -119,33 +126,46 @@ impl EdgeRtreeInputPlugin {
                 Some(file) => {
                     let path = PathBuf::from(file);
                     let trs = vehicle_restriction_lookup_from_file(&path)?;
-                    Some(trs)
+                    Some(layout.align_edge_map(&trs))
                 }
             };
 
-        let geometries = read_linestring_text_file(linestring_file)
-            .map_err(CompassConfigurationError::IoError)?
-            .into_vec();
+        let geometries = read_linestring_text_file(linestring_file, None, true)
+            .map_err(CompassConfigurationError::IoError)?;
 
-        let rcl_len_opt = road_class_lookup.as_ref().map(|l| l.len());
-        let geo_len = geometries.len();
-        if let Some(rcl_len) = rcl_len_opt {
-            if rcl_len != geo_len {
+        let align_error = |e: GraphError| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "edge_rtree: failed to align edge files with the graph: {}",
+                e
+            ))
+        };
+        let road_class_lookup = match road_class_lookup {
+            None => None,
+            Some(lookup) => Some(
+                layout
+                    .align(&lookup, |road_class| *road_class)
+                    .map_err(align_error)?
+                    .into_vec(),
+            ),
+        };
+        let geometries = layout
+            .align(&geometries, |geom| {
+                LineString::new(geom.0.iter().rev().cloned().collect())
+            })
+            .map_err(align_error)?;
+        // compare the aligned tables, which hold one row per graph edge
+        if let Some(lookup) = road_class_lookup.as_ref() {
+            if lookup.len() != geometries.len() {
                 let msg = format!(
                     "edge_rtree: road class file and geometries file have different lengths ({} != {})",
-                    rcl_len, geo_len
+                    lookup.len(),
+                    geometries.len()
                 );
                 return Err(CompassConfigurationError::UserConfigurationError(msg));
             }
         }
 
-        let records: Vec<EdgeRtreeRecord> = geometries
-            .into_iter()
-            .enumerate()
-            .map(|(idx, geom)| EdgeRtreeRecord::new(EdgeId(idx), geom))
-            .collect();
-
-        let rtree = RTree::bulk_load(records);
+        let rtree = EdgeSegmentRTree::from_geometries(&geometries);
 
         let tolerance = match (tolerance_distance, distance_unit) {
             (None, None) => None,
@@ -164,12 +184,12 @@ impl EdgeRtreeInputPlugin {
     }
 }
 
-/// finds the nearest edge to some coordinate, optionally within some distance tolerance
+/// snaps a coordinate to the nearest edge, optionally within some distance tolerance
 ///
 /// # Arguments
 ///
 /// * `coord` - coordinate from which to find a nearest edge
-/// * `rtree` - search tree containing all road network edges
+/// * `rtree` - search tree containing the segments of all road network edges
 /// * `tolerance` - distance tolerance argument. if provided, result edge must be within this
 ///                 distance/distance unit of the coord provided.
 /// * `road_class_lookup` - optional lookup table for road classes
@@ -179,19 +199,19 @@ impl EdgeRtreeInputPlugin {
 ///
 /// # Result
 ///
-/// the EdgeId of the nearest edge that meets the tolerance requirement, if provided
+/// the nearest edge that meets the tolerance requirement, if provided, and the
+/// offset of the snapped point along it
 fn search(
     coord: Coord<f32>,
-    rtree: &RTree<EdgeRtreeRecord>,
+    rtree: &EdgeSegmentRTree,
     tolerance: Option<(Distance, DistanceUnit)>,
     road_class_lookup: &Option<Vec<u8>>,
     road_classes: &Option<HashSet<u8>>,
     vehicle_restrictions: &Option<HashMap<EdgeId, Vec<VehicleRestriction>>>,
     vehicle_parameters: &Option<VehicleParameters>,
-) -> Result<Option<EdgeId>, PluginError> {
-    let point = geo::Point(coord);
-    for (record, distance_meters) in rtree.nearest_neighbor_iter_with_distance_2(&point) {
-        if !within_tolerance(tolerance, &distance_meters) {
+) -> Result<Option<NearestEdge>, PluginError> {
+    for record in rtree.nearest_edges(coord) {
+        if !within_tolerance(tolerance, &coord, &record.coordinate)? {
             return Ok(None);
         }
         let valid_class = match (road_classes, road_class_lookup) {
@@ -220,7 +240,7 @@ fn search(
             _ => true,
         };
         if valid_class && valid_truck {
-            return Ok(Some(record.edge_id));
+            return Ok(Some(record));
         }
     }
     Ok(None)
@@ -235,16 +255,67 @@ fn matching_error(coord: &Coord<f32>, tolerance: Option<(Distance, DistanceUnit)
     PluginError::PluginFailed(message)
 }
 
-/// helper to test if some distance in meters is within the optionally-provided tolerance
-fn within_tolerance(tolerance: Option<(Distance, DistanceUnit)>, distance_meters: &f32) -> bool {
+/// helper to test if the haversine distance from a coordinate to its snapped point
+/// is within the optionally-provided tolerance
+fn within_tolerance(
+    tolerance: Option<(Distance, DistanceUnit)>,
+    coord: &Coord<f32>,
+    snapped: &Coord<f32>,
+) -> Result<bool, PluginError> {
     match tolerance {
-        None => true,
+        None => Ok(true),
         Some((tolerance, distance_unit)) => {
-            let tolerance_meters = distance_unit
-                .convert(&tolerance, &DistanceUnit::Meters)
-                .as_f64() as f32;
+            let tolerance_meters = distance_unit.convert(&tolerance, &DistanceUnit::Meters);
+            let distance_meters = haversine::coord_distance_meters(coord, snapped)
+                .map_err(PluginError::PluginFailed)?;
+            Ok(distance_meters <= tolerance_meters)
+        }
+    }
+}
 
-            distance_meters <= &tolerance_meters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::input::input_field::InputField;
+
+    /// a plugin over a long east-west edge 0 and a short edge 1 to its north
+    fn build_plugin(tolerance: Option<(Distance, DistanceUnit)>) -> EdgeRtreeInputPlugin {
+        let geometries = vec![
+            LineString::from(vec![(-105.0, 39.0), (-104.9, 39.0)]),
+            LineString::from(vec![(-105.0, 39.01), (-105.0, 39.02)]),
+        ];
+        EdgeRtreeInputPlugin {
+            rtree: EdgeSegmentRTree::from_geometries(&geometries),
+            tolerance,
+            road_class_lookup: None,
+            road_class_parser: RoadClassParser::default(),
+            vehicle_restrictions: None,
         }
     }
+
+    #[test]
+    fn test_snap_to_middle_of_long_edge() {
+        let plugin = build_plugin(Some((Distance::new(500.0), DistanceUnit::Meters)));
+        let mut query = serde_json::json!({
+            "origin_x": -104.96,
+            "origin_y": 39.001,
+            "destination_x": -104.91,
+            "destination_y": 38.999,
+        });
+        plugin.process(&mut query).unwrap();
+        assert_eq!(query[InputField::OriginEdge.to_string()], 0);
+        assert_eq!(query[InputField::DestinationEdge.to_string()], 0);
+        let origin_offset = query.get_origin_edge_offset().unwrap().unwrap();
+        let destination_offset = query.get_destination_edge_offset().unwrap().unwrap();
+        assert!((origin_offset - 0.4).abs() < 1e-3);
+        assert!((destination_offset - 0.9).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_snap_outside_tolerance() {
+        // about 111 meters north of edge 0, and farther from edge 1
+        let plugin = build_plugin(Some((Distance::new(100.0), DistanceUnit::Meters)));
+        let mut query = serde_json::json!({ "origin_x": -104.95, "origin_y": 39.001 });
+        assert!(plugin.process(&mut query).is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use super::edge_rtree_input_plugin::EdgeRtreeInputPlugin;
@@ -17,6 +18,7 @@ impl InputPluginBuilder for EdgeRtreeInputPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         let parent_key = String::from("edge_rtree");
         let linestring_file = parameters.get_config_string(&"geometry_input_file", &parent_key)?;
@@ -43,6 +45,7 @@ impl InputPluginBuilder for EdgeRtreeInputPluginBuilder {
             distance_tolerance_option,
             distance_unit_option,
             road_class_parser,
+            &graph.edge_table_layout,
         )?;
         Ok(Arc::new(plugin))
     }
@@ -0,0 +1,211 @@
+use geo::{Coord, LineString};
+use routee_compass_core::model::road_network::{edge_id::EdgeId, graph::Graph};
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
+
+/// one straight segment of an edge. an edge with a multi-point geometry is
+/// split into several segments, each covering a share of the edge between
+/// `start_fraction` and `end_fraction`.
+pub struct EdgeSegment {
+    pub edge_id: EdgeId,
+    pub start: Coord<f32>,
+    pub end: Coord<f32>,
+    pub start_fraction: f32,
+    pub end_fraction: f32,
+}
+
+/// the result of snapping a coordinate to the nearest edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearestEdge {
+    pub edge_id: EdgeId,
+    /// position of the snapped point along the edge, from 0.0 at its
+    /// start to 1.0 at its end
+    pub fraction: f32,
+    /// the snapped point, which is the point on the edge closest to the query
+    pub coordinate: Coord<f32>,
+}
+
+impl EdgeSegment {
+    /// finds the point on this segment closest to a coordinate, as the
+    /// parameter `t` in `[0, 1]` from `start` to `end`.
+    fn project(&self, point: &Coord<f32>) -> f32 {
+        let d = self.end - self.start;
+        let len_2 = d.x * d.x + d.y * d.y;
+        if len_2 == 0.0 {
+            return 0.0;
+        }
+        let p = *point - self.start;
+        ((p.x * d.x + p.y * d.y) / len_2).clamp(0.0, 1.0)
+    }
+}
+
+impl RTreeObject for EdgeSegment {
+    type Envelope = AABB<Coord<f32>>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_corners(self.start, self.end)
+    }
+}
+
+impl PointDistance for EdgeSegment {
+    /// squared euclidean distance from a point to the closest point on this segment
+    fn distance_2(&self, point: &Coord<f32>) -> f32 {
+        let t = self.project(point);
+        let closest = self.start + (self.end - self.start) * t;
+        let dx = closest.x - point.x;
+        let dy = closest.y - point.y;
+        dx * dx + dy * dy
+    }
+}
+
+/// a search tree over the straight segments of each edge, which snaps a
+/// coordinate to the closest point on the network. unlike the nearest
+/// vertex, this finds the right edge when a coordinate lies along the
+/// middle of a long edge.
+pub struct EdgeSegmentRTree {
+    rtree: RTree<EdgeSegment>,
+}
+
+impl EdgeSegmentRTree {
+    pub fn new(segments: Vec<EdgeSegment>) -> Self {
+        let rtree = RTree::bulk_load(segments);
+        Self { rtree }
+    }
+
+    /// builds segments from the coordinates of the vertices at either end of
+    /// each edge, for graphs without edge geometries.
+    pub fn from_directed_graph(graph: &Graph) -> Self {
+        let segments = graph
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                let src = graph.vertices.get(edge.src_vertex_id.0)?;
+                let dst = graph.vertices.get(edge.dst_vertex_id.0)?;
+                Some(EdgeSegment {
+                    edge_id: edge.edge_id,
+                    start: *src.coordinate,
+                    end: *dst.coordinate,
+                    start_fraction: 0.0,
+                    end_fraction: 1.0,
+                })
+            })
+            .collect();
+        Self::new(segments)
+    }
+
+    /// builds segments from edge geometries, indexed by `EdgeId`. each segment
+    /// covers a share of its edge proportional to its length.
+    pub fn from_geometries(geometries: &[LineString<f32>]) -> Self {
+        let mut segments = vec![];
+        for (idx, geometry) in geometries.iter().enumerate() {
+            let lengths = geometry
+                .lines()
+                .map(|line| {
+                    let d = line.delta();
+                    (d.x * d.x + d.y * d.y).sqrt()
+                })
+                .collect::<Vec<_>>();
+            let total: f32 = lengths.iter().sum();
+            let mut start_fraction = 0.0;
+            for (line, length) in geometry.lines().zip(lengths) {
+                let end_fraction = if total > 0.0 {
+                    start_fraction + length / total
+                } else {
+                    1.0
+                };
+                segments.push(EdgeSegment {
+                    edge_id: EdgeId(idx),
+                    start: line.start,
+                    end: line.end,
+                    start_fraction,
+                    end_fraction,
+                });
+                start_fraction = end_fraction;
+            }
+        }
+        Self::new(segments)
+    }
+
+    /// finds the edge closest to a coordinate and the position along it
+    /// closest to the coordinate.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - the coordinate to snap to the network
+    ///
+    /// # Returns
+    ///
+    /// the nearest edge, or None if the tree is empty
+    pub fn nearest_edge(&self, point: Coord<f32>) -> Option<NearestEdge> {
+        self.nearest_edges(point).next()
+    }
+
+    /// snaps a coordinate to each edge segment in order of distance, so that
+    /// callers may skip edges that are not valid for a query. an edge with
+    /// several segments may appear once per segment.
+    pub fn nearest_edges(&self, point: Coord<f32>) -> impl Iterator<Item = NearestEdge> + '_ {
+        self.rtree
+            .nearest_neighbor_iter(&point)
+            .map(move |segment| {
+                let t = segment.project(&point);
+                let fraction =
+                    segment.start_fraction + t * (segment.end_fraction - segment.start_fraction);
+                NearestEdge {
+                    edge_id: segment.edge_id,
+                    fraction,
+                    coordinate: segment.start + (segment.end - segment.start) * t,
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::coord;
+    use routee_compass_core::model::property::{edge::Edge, vertex::Vertex};
+    use routee_compass_core::test_util::graph_fixture::graph_from_edges;
+
+    /// a long edge 0 from (0, 0) to (10, 0) and short edges 1 and 2 that
+    /// leave vertex 0 and vertex 1 to the north
+    fn build_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 10.0, 0.0),
+            Vertex::new(2, 0.0, 1.0),
+            Vertex::new(3, 10.0, 1.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 1, 10.0),
+            Edge::new(1, 0, 2, 1.0),
+            Edge::new(2, 1, 3, 1.0),
+        ];
+        graph_from_edges(vertices, edges)
+    }
+
+    #[test]
+    fn test_nearest_edge_middle_of_long_edge() {
+        // the nearest vertex to (4, 0.5) is vertex 0, but the point is
+        // closest to the middle of edge 0
+        let rtree = EdgeSegmentRTree::from_directed_graph(&build_graph());
+        let nearest = rtree.nearest_edge(coord! { x: 4.0, y: 0.5 }).unwrap();
+        assert_eq!(nearest.edge_id, EdgeId(0));
+        assert!((nearest.fraction - 0.4).abs() < 1e-6);
+        assert_eq!(nearest.coordinate, coord! { x: 4.0, y: 0.0 });
+    }
+
+    #[test]
+    fn test_nearest_edge_from_geometries() {
+        // edge 0 bends at (3, 4), so its first segment is 5 units long and its second is 5
+        let geometries = vec![
+            LineString::from(vec![(0.0, 0.0), (3.0, 4.0), (6.0, 0.0)]),
+            LineString::from(vec![(0.0, 10.0), (6.0, 10.0)]),
+        ];
+        let rtree = EdgeSegmentRTree::from_geometries(&geometries);
+        let nearest = rtree.nearest_edge(coord! { x: 4.5, y: 2.0 }).unwrap();
+        assert_eq!(nearest.edge_id, EdgeId(0));
+        assert!((nearest.fraction - 0.75).abs() < 1e-6);
+        let nearest = rtree.nearest_edge(coord! { x: 3.0, y: 9.0 }).unwrap();
+        assert_eq!(nearest.edge_id, EdgeId(1));
+        assert!((nearest.fraction - 0.5).abs() < 1e-6);
+    }
+}
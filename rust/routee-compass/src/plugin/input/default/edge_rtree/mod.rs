@@ -1,3 +1,3 @@
 pub mod edge_rtree_input_plugin;
 pub mod edge_rtree_input_plugin_builder;
-pub mod edge_rtree_record;
+pub mod edge_segment_rtree;
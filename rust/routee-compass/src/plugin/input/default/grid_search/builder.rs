@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use crate::{
@@ -15,6 +16,7 @@ impl InputPluginBuilder for GridSearchBuilder {
     fn build(
         &self,
         _parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         Ok(Arc::new(GridSearchPlugin {}))
     }
@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use super::inject_format::InjectFormat;
@@ -15,6 +16,7 @@ impl InputPluginBuilder for InjectPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         let key = parameters.get_config_string(&"key", &"inject")?;
         let value_string = parameters.get_config_string(&"value", &"inject")?;
@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use crate::{
@@ -16,6 +17,7 @@ impl InputPluginBuilder for LoadBalancerBuilder {
     fn build(
         &self,
         params: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         let heuristic =
             params.get_config_serde::<WeightHeuristic>(&"weight_heuristic", &"load_balancer")?;
@@ -3,4 +3,5 @@ pub mod edge_rtree;
 pub mod grid_search;
 pub mod inject;
 pub mod load_balancer;
+pub mod structured_query;
 pub mod vertex_rtree;
@@ -0,0 +1,32 @@
+use super::plugin::StructuredQueryPlugin;
+use crate::{
+    app::compass::config::{
+        builders::InputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
+    },
+    plugin::input::{
+        default::vertex_rtree::{builder::max_snap_distance, plugin::RTreePlugin},
+        input_plugin::InputPlugin,
+    },
+};
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::model::unit::DistanceUnit;
+use std::sync::Arc;
+
+pub struct StructuredQueryBuilder {}
+
+impl InputPluginBuilder for StructuredQueryBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+        graph: &Graph,
+    ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("Structured Query Input Plugin");
+        let tolerance_distance = max_snap_distance(parameters, &parent_key)?;
+        let distance_unit =
+            parameters.get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)?;
+        let rtree = RTreePlugin::new(&graph.vertices, tolerance_distance, distance_unit);
+        let m: Arc<dyn InputPlugin> = Arc::new(StructuredQueryPlugin::new(rtree));
+        Ok(m)
+    }
+}
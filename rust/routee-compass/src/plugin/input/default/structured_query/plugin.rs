@@ -0,0 +1,112 @@
+use super::query::StructuredQuery;
+use crate::plugin::input::{default::vertex_rtree::plugin::RTreePlugin, input_plugin::InputPlugin};
+use crate::plugin::plugin_error::PluginError;
+
+/// input plugin which accepts queries as [`StructuredQuery`] objects,
+/// rejecting unknown fields, and rewrites them into the internal query
+/// format with the origin and destination coordinates snapped to the
+/// nearest graph vertices.
+pub struct StructuredQueryPlugin {
+    rtree_plugin: RTreePlugin,
+}
+
+impl StructuredQueryPlugin {
+    pub fn new(rtree_plugin: RTreePlugin) -> StructuredQueryPlugin {
+        StructuredQueryPlugin { rtree_plugin }
+    }
+}
+
+impl InputPlugin for StructuredQueryPlugin {
+    /// parses the structured query and replaces it with the internal query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - search query in the structured query format
+    ///
+    /// # Returns
+    ///
+    /// * nothing, or an error if the query is malformed, has unknown fields,
+    ///   or a coordinate cannot be matched to a vertex
+    fn process(&self, query: &mut serde_json::Value) -> Result<(), PluginError> {
+        let structured = StructuredQuery::from_json(query)?;
+        let mut internal = structured.to_internal_query()?;
+        self.rtree_plugin.process(&mut internal)?;
+        *query = internal;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::plugin::input::{
+        input_field::InputField, input_json_extensions::InputJsonExtensions,
+    };
+    use routee_compass_core::model::{
+        property::vertex::Vertex,
+        road_network::{edge_id::EdgeId, vertex_id::VertexId},
+        unit::Cost,
+    };
+    use routee_compass_core::util::fs::read_utils;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn test_plugin() -> StructuredQueryPlugin {
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_vertices.csv");
+        let vertices: Box<[Vertex]> = read_utils::from_csv(&vertices_filepath, true, None).unwrap();
+        let rtree_plugin = RTreePlugin::new(&vertices, None, None);
+        StructuredQueryPlugin::new(rtree_plugin)
+    }
+
+    #[test]
+    fn test_structured_query_builds_internal_query() {
+        let mut query = json!({
+            "origin": { "x": 0.1, "y": 0.1 },
+            "destination": { "x": 1.9, "y": 2.1 },
+            "vehicle": "2017_CHEVROLET_Bolt",
+            "options": { "avoid_edges": [3, 4], "max_cost": 100.0 },
+            "parameters": { "weights": { "distance": 1.0 } }
+        });
+        test_plugin().process(&mut query).unwrap();
+
+        assert_eq!(query.get_origin_vertex().unwrap(), VertexId(0));
+        assert_eq!(query.get_destination_vertex().unwrap(), Some(VertexId(2)));
+        assert_eq!(query["model_name"], json!("2017_CHEVROLET_Bolt"));
+        assert_eq!(query["weights"], json!({ "distance": 1.0 }));
+        let avoid = query.get_avoid_edges().unwrap().unwrap();
+        assert!(avoid.contains(&EdgeId(3)) && avoid.contains(&EdgeId(4)));
+        assert_eq!(query.get_max_cost().unwrap(), Some(Cost::new(100.0)));
+        assert!(query.get(InputField::MaxEdges.to_str()).is_none());
+    }
+
+    #[test]
+    fn test_structured_query_rejects_unknown_fields() {
+        let plugin = test_plugin();
+        let mut top_level = json!({
+            "origin": { "x": 0.1, "y": 0.1 },
+            "destinaton": { "x": 1.9, "y": 2.1 }
+        });
+        let error = plugin.process(&mut top_level).unwrap_err().to_string();
+        assert!(error.contains("unknown field `destinaton`"), "{}", error);
+
+        let mut in_options = json!({
+            "origin": { "x": 0.1, "y": 0.1 },
+            "options": { "max_costs": 10.0 }
+        });
+        let error = plugin.process(&mut in_options).unwrap_err().to_string();
+        assert!(error.contains("unknown field `max_costs`"), "{}", error);
+
+        let mut reserved_parameter = json!({
+            "origin": { "x": 0.1, "y": 0.1 },
+            "parameters": { "origin_vertex": 2 }
+        });
+        assert!(plugin.process(&mut reserved_parameter).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::plugin::{input::input_field::InputField, plugin_error::PluginError};
+use routee_compass_core::model::{road_network::edge_id::EdgeId, unit::Cost};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// a query submitted as a typed JSON object. any field not listed here is
+/// rejected, so that typos such as `destinaton` fail loudly instead of
+/// silently producing a single-source tree search.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StructuredQuery {
+    pub origin: QueryCoordinate,
+    pub destination: Option<QueryCoordinate>,
+    /// name of the vehicle model to use, passed to the traversal model as `model_name`
+    pub vehicle: Option<String>,
+    #[serde(default)]
+    pub options: QueryOptions,
+    /// model-specific query parameters, such as cost weights, which are
+    /// copied onto the internal query as-is
+    #[serde(default)]
+    pub parameters: serde_json::Map<String, serde_json::Value>,
+}
+
+/// a coordinate in the same x/y (lon/lat) system as the graph vertices
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QueryCoordinate {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// search options which constrain the route
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QueryOptions {
+    pub avoid_edges: Option<Vec<EdgeId>>,
+    /// edges the route must traverse, in order
+    pub required_edges: Option<Vec<EdgeId>>,
+    pub max_cost: Option<Cost>,
+    pub max_edges: Option<usize>,
+    /// among routes of equal cost, return the one with fewer edges
+    pub prefer_fewer_edges: Option<bool>,
+    /// inflates the a* heuristic for a faster route within this factor of optimal
+    pub heuristic_weight: Option<f64>,
+}
+
+/// fields written by this module which may not be set through `parameters`
+const RESERVED_FIELDS: [InputField; 14] = [
+    InputField::OriginX,
+    InputField::OriginY,
+    InputField::DestinationX,
+    InputField::DestinationY,
+    InputField::OriginVertex,
+    InputField::DestinationVertex,
+    InputField::OriginEdge,
+    InputField::DestinationEdge,
+    InputField::AvoidEdges,
+    InputField::RequiredEdges,
+    InputField::MaxCost,
+    InputField::MaxEdges,
+    InputField::PreferFewerEdges,
+    InputField::HeuristicWeight,
+];
+
+impl StructuredQuery {
+    /// deserializes a structured query, rejecting unknown fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the raw JSON query submitted by the user
+    ///
+    /// # Returns
+    ///
+    /// the parsed query or an error describing the invalid or unknown field
+    pub fn from_json(value: &serde_json::Value) -> Result<StructuredQuery, PluginError> {
+        serde_json::from_value(value.clone())
+            .map_err(|e| PluginError::InputError(format!("invalid structured query: {}", e)))
+    }
+
+    /// builds the internal query object from this structured query. the
+    /// coordinates are written as `origin_x`/`origin_y` (and destination) so
+    /// that they can be snapped to graph vertices.
+    ///
+    /// # Returns
+    ///
+    /// the internal query, or an error if a parameter collides with a
+    /// field reserved for the structured query
+    pub fn to_internal_query(&self) -> Result<serde_json::Value, PluginError> {
+        if let Some(reserved) = RESERVED_FIELDS
+            .iter()
+            .find(|f| self.parameters.contains_key(f.to_str()))
+        {
+            return Err(PluginError::InputError(format!(
+                "invalid structured query: parameter '{}' is reserved, use the structured field instead",
+                reserved
+            )));
+        }
+        let mut query = serde_json::Value::Object(self.parameters.clone());
+        query[InputField::OriginX.to_str()] = json!(self.origin.x);
+        query[InputField::OriginY.to_str()] = json!(self.origin.y);
+        if let Some(destination) = &self.destination {
+            query[InputField::DestinationX.to_str()] = json!(destination.x);
+            query[InputField::DestinationY.to_str()] = json!(destination.y);
+        }
+        if let Some(vehicle) = &self.vehicle {
+            query["model_name"] = json!(vehicle);
+        }
+        if let Some(avoid_edges) = &self.options.avoid_edges {
+            query[InputField::AvoidEdges.to_str()] = json!(avoid_edges);
+        }
+        if let Some(required_edges) = &self.options.required_edges {
+            query[InputField::RequiredEdges.to_str()] = json!(required_edges);
+        }
+        if let Some(max_cost) = &self.options.max_cost {
+            query[InputField::MaxCost.to_str()] = json!(max_cost);
+        }
+        if let Some(max_edges) = &self.options.max_edges {
+            query[InputField::MaxEdges.to_str()] = json!(max_edges);
+        }
+        if let Some(prefer_fewer_edges) = &self.options.prefer_fewer_edges {
+            query[InputField::PreferFewerEdges.to_str()] = json!(prefer_fewer_edges);
+        }
+        if let Some(heuristic_weight) = &self.options.heuristic_weight {
+            query[InputField::HeuristicWeight.to_str()] = json!(heuristic_weight);
+        }
+        Ok(query)
+    }
+}
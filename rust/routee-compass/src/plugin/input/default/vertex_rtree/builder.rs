@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use routee_compass_core::model::unit::{Distance, DistanceUnit};
@@ -18,16 +19,30 @@ impl InputPluginBuilder for VertexRTreeBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         let parent_key = String::from("Vertex RTree Input Plugin");
-        let vertex_path = parameters.get_config_path(&"vertices_input_file", &parent_key)?;
-        let tolerance_distance =
-            parameters.get_config_serde_optional::<Distance>(&"distance_tolerance", &parent_key)?;
+        let tolerance_distance = max_snap_distance(parameters, &parent_key)?;
         let distance_unit =
             parameters.get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)?;
-        let rtree = RTreePlugin::new(&vertex_path, tolerance_distance, distance_unit)
-            .map_err(CompassConfigurationError::PluginError)?;
+        let rtree = RTreePlugin::new(&graph.vertices, tolerance_distance, distance_unit);
         let m: Arc<dyn InputPlugin> = Arc::new(rtree);
         Ok(m)
     }
 }
+
+/// reads the `max_snap_distance` of a plugin that snaps coordinates to
+/// vertices, accepting the older `distance_tolerance` key in its place
+pub fn max_snap_distance(
+    parameters: &serde_json::Value,
+    parent_key: &str,
+) -> Result<Option<Distance>, CompassConfigurationError> {
+    let max_snap_distance =
+        parameters.get_config_serde_optional::<Distance>(&"max_snap_distance", &parent_key)?;
+    match max_snap_distance {
+        Some(distance) => Ok(Some(distance)),
+        None => {
+            parameters.get_config_serde_optional::<Distance>(&"distance_tolerance", &parent_key)
+        }
+    }
+}
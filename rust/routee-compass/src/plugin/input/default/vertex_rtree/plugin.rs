@@ -1,5 +1,3 @@
-use std::path::Path;
-
 use crate::plugin::input::input_json_extensions::InputJsonExtensions;
 use crate::plugin::input::input_plugin::InputPlugin;
 use crate::plugin::plugin_error::PluginError;
@@ -7,10 +5,15 @@ use geo::{coord, Coord};
 use routee_compass_core::{
     model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT},
     model::{property::vertex::Vertex, road_network::graph::Graph},
-    util::{fs::read_utils, geo::haversine},
+    util::geo::haversine,
 };
 use rstar::{PointDistance, RTree, RTreeObject, AABB};
 
+/// snap radius used when none is configured, in kilometers. generous enough
+/// for sparse rural networks, while still rejecting coordinates that are off
+/// the map or mistyped.
+pub const DEFAULT_MAX_SNAP_DISTANCE_KM: f64 = 50.0;
+
 pub struct RTreeVertex {
     vertex: Vertex,
 }
@@ -50,6 +53,32 @@ impl VertexRTree {
         }
     }
 
+    /// finds the nearest vertex to a point, unless it is farther away than
+    /// the snap radius.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - WGS84 coordinate to snap
+    /// * `max_snap_distance` - largest haversine distance to the vertex, in meters
+    ///
+    /// # Returns
+    ///
+    /// the nearest vertex, or None if the graph is empty or the nearest vertex
+    /// is beyond `max_snap_distance`
+    pub fn nearest_vertex_within(
+        &self,
+        point: Coord<f32>,
+        max_snap_distance: Distance,
+    ) -> Option<&Vertex> {
+        let vertex = self.nearest_vertex(point)?;
+        let distance = haversine::coord_distance_meters(&point, &vertex.coordinate).ok()?;
+        if distance <= max_snap_distance {
+            Some(vertex)
+        } else {
+            None
+        }
+    }
+
     pub fn nearest_vertices(&self, point: Coord<f32>, n: usize) -> Vec<&Vertex> {
         self.rtree
             .nearest_neighbor_iter(&point)
@@ -89,7 +118,7 @@ impl PointDistance for RTreeVertex {
 /// * An input plugin that uses an RTree to find the nearest vertex to the origin and destination coordinates.
 pub struct RTreePlugin {
     vertex_rtree: VertexRTree,
-    tolerance: Option<(Distance, DistanceUnit)>,
+    tolerance: (Distance, DistanceUnit),
 }
 
 impl RTreePlugin {
@@ -97,31 +126,33 @@ impl RTreePlugin {
     ///
     /// # Arguments
     ///
-    /// * `vertex_file` - file containing vertices
-    /// * `tolerance_distance` - optional max distance to nearest vertex (assumed infinity if not included)
+    /// * `vertices` - vertices of the graph, so that snapped vertex ids match
+    ///   the graph even when it was reprojected or loaded as a region
+    /// * `tolerance_distance` - optional max distance to nearest vertex, defaults to
+    ///   [`DEFAULT_MAX_SNAP_DISTANCE_KM`] kilometers
     /// * `distance_unit` - distance unit for tolerance, assumed BASE_DISTANCE_UNIT if not provided
     ///
     /// # Returns
     ///
-    /// * a plugin instance or an error from file loading
+    /// * a plugin instance
     pub fn new(
-        vertex_file: &Path,
+        vertices: &[Vertex],
         tolerance_distance: Option<Distance>,
         distance_unit: Option<DistanceUnit>,
-    ) -> Result<Self, PluginError> {
-        let vertices: Box<[Vertex]> =
-            read_utils::from_csv(&vertex_file, true, None).map_err(PluginError::CsvReadError)?;
+    ) -> Self {
         let vertex_rtree = VertexRTree::new(vertices.to_vec());
         let tolerance = match (tolerance_distance, distance_unit) {
-            (None, None) => None,
-            (None, Some(_)) => None,
-            (Some(t), None) => Some((t, BASE_DISTANCE_UNIT)),
-            (Some(t), Some(u)) => Some((t, u)),
+            (None, _) => (
+                Distance::new(DEFAULT_MAX_SNAP_DISTANCE_KM),
+                DistanceUnit::Kilometers,
+            ),
+            (Some(t), None) => (t, BASE_DISTANCE_UNIT),
+            (Some(t), Some(u)) => (t, u),
         };
-        Ok(RTreePlugin {
+        RTreePlugin {
             vertex_rtree,
             tolerance,
-        })
+        }
     }
 }
 
@@ -168,17 +199,16 @@ impl InputPlugin for RTreePlugin {
     }
 }
 
-/// confirms that two coordinates are within some stated distance tolerance.
-/// if no tolerance is provided, the dst coordinate is assumed to be a valid distance.
+/// confirms that two coordinates are within some stated distance tolerance,
+/// which rejects coordinates far from the network rather than snapping them
+/// to a distant vertex.
 ///
 /// # Arguments
 ///
 /// * `src` - source coordinate
 /// * `dst` - destination coordinate that may or may not be within some distance
 ///           tolerance of the src coordinate
-/// * `tolerance` - tolerance parameters set by user for the rtree plugin. if this is None,
-///                 all coordinate pairs are assumed to be within distance tolerance, but this
-///                 may lead to unexpected behavior where far away coordinates are considered "matched".
+/// * `tolerance` - the max snap distance set by user for the rtree plugin
 ///
 /// # Returns
 ///
@@ -186,30 +216,26 @@ impl InputPlugin for RTreePlugin {
 fn validate_tolerance(
     src: &Coord<f32>,
     dst: &Coord<f32>,
-    tolerance: &Option<(Distance, DistanceUnit)>,
+    tolerance: &(Distance, DistanceUnit),
 ) -> Result<(), PluginError> {
-    match tolerance {
-        Some((tolerance_distance, tolerance_distance_unit)) => {
-            let distance_meters =
-                haversine::coord_distance_meters(src, dst).map_err(PluginError::PluginFailed)?;
-            let distance = DistanceUnit::Meters.convert(&distance_meters, tolerance_distance_unit);
-            if &distance >= tolerance_distance {
-                Err(PluginError::PluginFailed(
-                    format!(
-                        "coord {:?} nearest vertex coord is {:?} which is {} {} away, exceeding the distance tolerance of {} {}", 
-                        src,
-                        dst,
-                        distance,
-                        tolerance_distance_unit,
-                        tolerance_distance,
-                        tolerance_distance_unit,
-                    )
-                ))
-            } else {
-                Ok(())
-            }
-        }
-        None => Ok(()),
+    let (tolerance_distance, tolerance_distance_unit) = tolerance;
+    let distance_meters =
+        haversine::coord_distance_meters(src, dst).map_err(PluginError::PluginFailed)?;
+    let distance = DistanceUnit::Meters.convert(&distance_meters, tolerance_distance_unit);
+    if &distance >= tolerance_distance {
+        Err(PluginError::PluginFailed(
+            format!(
+                "coord {:?} nearest vertex coord is {:?} which is {} {} away, exceeding the max snap distance of {} {}",
+                src,
+                dst,
+                distance,
+                tolerance_distance_unit,
+                tolerance_distance,
+                tolerance_distance_unit,
+            )
+        ))
+    } else {
+        Ok(())
     }
 }
 
@@ -222,6 +248,7 @@ mod test {
 
     use super::*;
     use crate::plugin::input::input_field::InputField;
+    use routee_compass_core::util::fs::read_utils;
     use serde_json::json;
 
     #[test]
@@ -244,7 +271,8 @@ mod test {
             .join("test")
             .join("rtree_query.json");
         let query_str = fs::read_to_string(query_filepath).unwrap();
-        let rtree_plugin = RTreePlugin::new(&vertices_filepath, None, None).unwrap();
+        let vertices: Box<[Vertex]> = read_utils::from_csv(&vertices_filepath, true, None).unwrap();
+        let rtree_plugin = RTreePlugin::new(&vertices, None, None);
         let mut query: serde_json::Value = serde_json::from_str(&query_str).unwrap();
         rtree_plugin.process(&mut query).unwrap();
 
@@ -267,4 +295,40 @@ mod test {
             other => panic!("expected object result, found {}", other),
         }
     }
+
+    #[test]
+    fn test_point_outside_graph_is_not_snapped() {
+        let vertices_filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugin")
+            .join("input")
+            .join("default")
+            .join("vertex_rtree")
+            .join("test")
+            .join("rtree_vertices.csv");
+        // the vertices span (0, 0) to (2, 2), and this point is ~1000 km beyond
+        let far_away = coord! {x: 11.0, y: 2.0};
+
+        let vertices: Box<[Vertex]> = read_utils::from_csv(&vertices_filepath, true, None).unwrap();
+        let rtree = VertexRTree::new(vertices.to_vec());
+        assert!(rtree
+            .nearest_vertex_within(far_away, Distance::new(50_000.0))
+            .is_none());
+        let snapped = rtree.nearest_vertex_within(far_away, Distance::new(2_000_000.0));
+        assert_eq!(snapped.map(|v| v.vertex_id.0), Some(2));
+
+        // the plugin rejects the point with its default snap radius
+        let rtree_plugin = RTreePlugin::new(&vertices, None, None);
+        let mut query = json!({
+            InputField::OriginX.to_str(): far_away.x,
+            InputField::OriginY.to_str(): far_away.y,
+        });
+        let result = rtree_plugin.process(&mut query);
+        assert!(
+            matches!(&result, Err(PluginError::PluginFailed(msg)) if msg.contains("max snap distance")),
+            "{:?}",
+            result.err()
+        );
+        assert!(query.get(InputField::OriginVertex.to_str()).is_none());
+    }
 }
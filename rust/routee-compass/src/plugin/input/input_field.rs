@@ -9,8 +9,19 @@ pub enum InputField {
     DestinationVertex,
     OriginEdge,
     DestinationEdge,
+    OriginEdgeOffset,
+    DestinationEdgeOffset,
     GridSearch,
     QueryWeightEstimate,
+    AvoidEdges,
+    RequiredEdges,
+    ViaVertices,
+    MaxEdges,
+    PreferFewerEdges,
+    MaxCost,
+    HeuristicWeight,
+    IncludeRouteCoordinates,
+    Profile,
 }
 
 impl InputField {
@@ -25,8 +36,19 @@ impl InputField {
             I::DestinationVertex => "destination_vertex",
             I::OriginEdge => "origin_edge",
             I::DestinationEdge => "destination_edge",
+            I::OriginEdgeOffset => "origin_edge_offset",
+            I::DestinationEdgeOffset => "destination_edge_offset",
             I::GridSearch => "grid_search",
             I::QueryWeightEstimate => "query_weight_estimate",
+            I::AvoidEdges => "avoid_edges",
+            I::RequiredEdges => "required_edges",
+            I::ViaVertices => "via_vertices",
+            I::MaxEdges => "max_edges",
+            I::PreferFewerEdges => "prefer_fewer_edges",
+            I::MaxCost => "max_cost",
+            I::HeuristicWeight => "heuristic_weight",
+            I::IncludeRouteCoordinates => "include_route_coordinates",
+            I::Profile => "profile",
         }
     }
 }
@@ -1,8 +1,12 @@
 use super::input_field::InputField;
 use crate::plugin::plugin_error::PluginError;
 use geo;
-use routee_compass_core::model::road_network::{edge_id::EdgeId, vertex_id::VertexId};
+use routee_compass_core::model::{
+    road_network::{edge_id::EdgeId, vertex_id::VertexId},
+    unit::Cost,
+};
 use serde_json::{self, json};
+use std::collections::HashSet;
 
 pub trait InputJsonExtensions {
     fn get_origin_coordinate(&self) -> Result<geo::Coord<f32>, PluginError>;
@@ -11,13 +15,26 @@ pub trait InputJsonExtensions {
     fn add_destination_vertex(&mut self, vertex_id: VertexId) -> Result<(), PluginError>;
     fn add_origin_edge(&mut self, edge_id: EdgeId) -> Result<(), PluginError>;
     fn add_destination_edge(&mut self, edge_id: EdgeId) -> Result<(), PluginError>;
+    fn add_origin_edge_offset(&mut self, offset: f64) -> Result<(), PluginError>;
+    fn add_destination_edge_offset(&mut self, offset: f64) -> Result<(), PluginError>;
     fn get_origin_vertex(&self) -> Result<VertexId, PluginError>;
     fn get_destination_vertex(&self) -> Result<Option<VertexId>, PluginError>;
     fn get_origin_edge(&self) -> Result<EdgeId, PluginError>;
     fn get_destination_edge(&self) -> Result<Option<EdgeId>, PluginError>;
+    fn get_origin_edge_offset(&self) -> Result<Option<f64>, PluginError>;
+    fn get_destination_edge_offset(&self) -> Result<Option<f64>, PluginError>;
     fn get_grid_search(&self) -> Option<&serde_json::Value>;
     fn add_query_weight_estimate(&mut self, weight: f64) -> Result<(), PluginError>;
     fn get_query_weight_estimate(&self) -> Result<Option<f64>, PluginError>;
+    fn get_avoid_edges(&self) -> Result<Option<HashSet<EdgeId>>, PluginError>;
+    fn get_required_edges(&self) -> Result<Option<Vec<EdgeId>>, PluginError>;
+    fn get_via_vertices(&self) -> Result<Option<Vec<VertexId>>, PluginError>;
+    fn get_max_edges(&self) -> Result<Option<usize>, PluginError>;
+    fn get_prefer_fewer_edges(&self) -> Result<bool, PluginError>;
+    fn get_max_cost(&self) -> Result<Option<Cost>, PluginError>;
+    fn get_heuristic_weight(&self) -> Result<Option<f64>, PluginError>;
+    fn get_include_route_coordinates(&self) -> Result<bool, PluginError>;
+    fn get_profile(&self) -> Result<Option<String>, PluginError>;
 }
 
 impl InputJsonExtensions for serde_json::Value {
@@ -136,6 +153,12 @@ impl InputJsonExtensions for serde_json::Value {
             }),
         }
     }
+    fn get_origin_edge_offset(&self) -> Result<Option<f64>, PluginError> {
+        get_edge_offset(self, InputField::OriginEdgeOffset)
+    }
+    fn get_destination_edge_offset(&self) -> Result<Option<f64>, PluginError> {
+        get_edge_offset(self, InputField::DestinationEdgeOffset)
+    }
     fn get_grid_search(&self) -> Option<&serde_json::Value> {
         self.get(InputField::GridSearch.to_string())
     }
@@ -170,6 +193,14 @@ impl InputJsonExtensions for serde_json::Value {
         }
     }
 
+    fn add_origin_edge_offset(&mut self, offset: f64) -> Result<(), PluginError> {
+        add_edge_offset(self, InputField::OriginEdgeOffset, offset)
+    }
+
+    fn add_destination_edge_offset(&mut self, offset: f64) -> Result<(), PluginError> {
+        add_edge_offset(self, InputField::DestinationEdgeOffset, offset)
+    }
+
     fn add_query_weight_estimate(&mut self, weight: f64) -> Result<(), PluginError> {
         match self {
             serde_json::Value::Object(map) => {
@@ -193,10 +224,207 @@ impl InputJsonExtensions for serde_json::Value {
             }),
         }
     }
+
+    fn get_avoid_edges(&self) -> Result<Option<HashSet<EdgeId>>, PluginError> {
+        match self.get(InputField::AvoidEdges.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let edge_ids = v.as_array().ok_or_else(|| {
+                    PluginError::ParseError(
+                        InputField::AvoidEdges.to_string(),
+                        String::from("array of u64"),
+                    )
+                })?;
+                let avoid = edge_ids
+                    .iter()
+                    .map(|e| {
+                        e.as_u64().map(|e| EdgeId(e as usize)).ok_or_else(|| {
+                            PluginError::ParseError(
+                                InputField::AvoidEdges.to_string(),
+                                String::from("array of u64"),
+                            )
+                        })
+                    })
+                    .collect::<Result<HashSet<_>, _>>()?;
+                Ok(Some(avoid))
+            }
+        }
+    }
+
+    fn get_required_edges(&self) -> Result<Option<Vec<EdgeId>>, PluginError> {
+        match self.get(InputField::RequiredEdges.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let edge_ids = v.as_array().ok_or_else(|| {
+                    PluginError::ParseError(
+                        InputField::RequiredEdges.to_string(),
+                        String::from("array of u64"),
+                    )
+                })?;
+                let required = edge_ids
+                    .iter()
+                    .map(|e| {
+                        e.as_u64().map(|e| EdgeId(e as usize)).ok_or_else(|| {
+                            PluginError::ParseError(
+                                InputField::RequiredEdges.to_string(),
+                                String::from("array of u64"),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(required))
+            }
+        }
+    }
+
+    fn get_via_vertices(&self) -> Result<Option<Vec<VertexId>>, PluginError> {
+        match self.get(InputField::ViaVertices.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let vertex_ids = v.as_array().ok_or_else(|| {
+                    PluginError::ParseError(
+                        InputField::ViaVertices.to_string(),
+                        String::from("array of u64"),
+                    )
+                })?;
+                let vias = vertex_ids
+                    .iter()
+                    .map(|v| {
+                        v.as_u64().map(|v| VertexId(v as usize)).ok_or_else(|| {
+                            PluginError::ParseError(
+                                InputField::ViaVertices.to_string(),
+                                String::from("array of u64"),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(vias))
+            }
+        }
+    }
+
+    fn get_max_edges(&self) -> Result<Option<usize>, PluginError> {
+        match self.get(InputField::MaxEdges.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let max_edges = v.as_u64().ok_or_else(|| {
+                    PluginError::ParseError(InputField::MaxEdges.to_string(), String::from("u64"))
+                })?;
+                Ok(Some(max_edges as usize))
+            }
+        }
+    }
+
+    fn get_prefer_fewer_edges(&self) -> Result<bool, PluginError> {
+        match self.get(InputField::PreferFewerEdges.to_string()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                PluginError::ParseError(
+                    InputField::PreferFewerEdges.to_string(),
+                    String::from("bool"),
+                )
+            }),
+        }
+    }
+
+    fn get_max_cost(&self) -> Result<Option<Cost>, PluginError> {
+        match self.get(InputField::MaxCost.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let max_cost = v.as_f64().ok_or_else(|| {
+                    PluginError::ParseError(InputField::MaxCost.to_string(), String::from("f64"))
+                })?;
+                Ok(Some(Cost::new(max_cost)))
+            }
+        }
+    }
+
+    fn get_heuristic_weight(&self) -> Result<Option<f64>, PluginError> {
+        let field = InputField::HeuristicWeight.to_string();
+        match self.get(&field) {
+            None => Ok(None),
+            Some(v) => {
+                let weight = v
+                    .as_f64()
+                    .ok_or_else(|| PluginError::ParseError(field.clone(), String::from("f64")))?;
+                if weight < 1.0 {
+                    return Err(PluginError::InputError(format!(
+                        "{} must be at least 1.0, found {}",
+                        field, weight
+                    )));
+                }
+                Ok(Some(weight))
+            }
+        }
+    }
+
+    fn get_include_route_coordinates(&self) -> Result<bool, PluginError> {
+        match self.get(InputField::IncludeRouteCoordinates.to_string()) {
+            None => Ok(false),
+            Some(v) => v.as_bool().ok_or_else(|| {
+                PluginError::ParseError(
+                    InputField::IncludeRouteCoordinates.to_string(),
+                    String::from("bool"),
+                )
+            }),
+        }
+    }
+
+    fn get_profile(&self) -> Result<Option<String>, PluginError> {
+        match self.get(InputField::Profile.to_string()) {
+            None => Ok(None),
+            Some(v) => {
+                let profile = v.as_str().ok_or_else(|| {
+                    PluginError::ParseError(InputField::Profile.to_string(), String::from("String"))
+                })?;
+                Ok(Some(String::from(profile)))
+            }
+        }
+    }
 }
 
 // pub type DecodeOp<T> = Box<dyn Fn(&serde_json::Value) -> Option<T>>;
 
+/// reads an optional fraction along an origin or destination edge, which
+/// must be in the range [0, 1]
+fn get_edge_offset(
+    query: &serde_json::Value,
+    field: InputField,
+) -> Result<Option<f64>, PluginError> {
+    match query.get(field.to_string()) {
+        None => Ok(None),
+        Some(v) => {
+            let offset = v
+                .as_f64()
+                .ok_or_else(|| PluginError::ParseError(field.to_string(), String::from("f64")))?;
+            if !(0.0..=1.0).contains(&offset) {
+                return Err(PluginError::InputError(format!(
+                    "{} must be in the range [0, 1], found {}",
+                    field, offset
+                )));
+            }
+            Ok(Some(offset))
+        }
+    }
+}
+
+/// writes a fraction along an origin or destination edge, clamped to [0, 1]
+fn add_edge_offset(
+    query: &mut serde_json::Value,
+    field: InputField,
+    offset: f64,
+) -> Result<(), PluginError> {
+    match query {
+        serde_json::Value::Object(map) => {
+            map.insert(field.to_string(), json!(offset.clamp(0.0, 1.0)));
+            Ok(())
+        }
+        _ => Err(PluginError::InputError(String::from(
+            "InputQuery is not a JSON object",
+        ))),
+    }
+}
+
 // fn get_from_json<T>(
 //     value: &serde_json::Value,
 //     field: InputField,
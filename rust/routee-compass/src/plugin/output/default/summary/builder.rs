@@ -1,21 +1,55 @@
-use std::sync::Arc;
+use routee_compass_core::model::road_network::{graph::Graph, road_class_table};
+use routee_compass_core::model::unit::{DistanceUnit, BASE_DISTANCE_UNIT};
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     app::compass::config::{
         builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
+        config_json_extension::ConfigJsonExtensions,
     },
     plugin::output::output_plugin::OutputPlugin,
 };
 
 use super::plugin::SummaryOutputPlugin;
 
+/// Builds a plugin that summarizes the search.
+///
+/// # Configuration
+///
+/// This plugin accepts the following optional keys:
+/// * `road_class_input_file` - road class of each edge, which adds the distance
+///   of the route on each road class to the summary
+/// * `road_class_codes` - mapping from the codes in the road class file to road classes
+/// * `distance_unit` - unit of the road class distances, meters by default
 pub struct SummaryOutputPluginBuilder {}
 
 impl OutputPluginBuilder for SummaryOutputPluginBuilder {
     fn build(
         &self,
-        _parameters: &serde_json::Value,
+        parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
-        Ok(Arc::new(SummaryOutputPlugin {}))
+        let parent_key = String::from("summary");
+        let road_class_file =
+            parameters.get_config_path_optional(&"road_class_input_file", &parent_key)?;
+        let road_class_codes = parameters
+            .get_config_serde_optional::<HashMap<i64, u8>>(&"road_class_codes", &parent_key)?;
+        let road_classes = road_class_file
+            .map(|path| {
+                road_class_table::read_edge_road_classes(
+                    &path,
+                    road_class_codes.as_ref(),
+                    &graph.edge_table_layout,
+                )
+            })
+            .transpose()?
+            .map(Arc::new);
+        let distance_unit = parameters
+            .get_config_serde_optional::<DistanceUnit>(&"distance_unit", &parent_key)?
+            .unwrap_or(BASE_DISTANCE_UNIT);
+        Ok(Arc::new(SummaryOutputPlugin {
+            road_classes,
+            distance_unit,
+        }))
     }
 }
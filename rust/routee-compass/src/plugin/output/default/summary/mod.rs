@@ -1,2 +1,3 @@
 pub mod builder;
 pub mod plugin;
+pub mod road_class_composition;
@@ -2,13 +2,24 @@ use crate::app::{
     compass::compass_app_error::CompassAppError, search::search_app_result::SearchAppResult,
 };
 use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::output_plugin_ops::search_metadata_json;
 use crate::plugin::plugin_error::PluginError;
 use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::unit::DistanceUnit;
 use routee_compass_core::util::duration_extension::DurationExtension;
 use serde_json::{self, json};
+use std::sync::Arc;
+
+use super::road_class_composition::road_class_distances;
 
 /// provides metrics for the performance of the search algorithm.
-pub struct SummaryOutputPlugin {}
+pub struct SummaryOutputPlugin {
+    /// when present, the road class of each edge, used to report the
+    /// distance of the route on each road class
+    pub road_classes: Option<Arc<Box<[u8]>>>,
+    /// unit of the road class distances
+    pub distance_unit: DistanceUnit,
+}
 
 impl OutputPlugin for SummaryOutputPlugin {
     /// append "Cost" value to the output JSON
@@ -19,7 +30,7 @@ impl OutputPlugin for SummaryOutputPlugin {
     ) -> Result<(), PluginError> {
         match search_result {
             Err(_e) => Ok(()),
-            Ok((result, _)) => {
+            Ok((result, si)) => {
                 let memory_bytes = allocative::size_of_unique(result) as f64;
                 let memory_mib = memory_bytes / 1_048_576.0;
                 let route_edges = result.routes.iter().map(|r| r.len()).sum::<usize>();
@@ -30,6 +41,22 @@ impl OutputPlugin for SummaryOutputPlugin {
                 output["tree_size_count"] = json![tree_edges];
                 output["search_result_size_mib"] = json![memory_mib];
                 output["iterations"] = json![result.iterations];
+                output["search_metadata"] = search_metadata_json(&result.search_metadata);
+                if let Some(rejections) = si.frontier_model.rejection_summary() {
+                    output["search_metadata"]["frontier_rejection_summary"] = rejections;
+                }
+                if let (Some(road_classes), Some(route)) =
+                    (&self.road_classes, result.routes.first())
+                {
+                    let distances = road_class_distances(
+                        route,
+                        &si.directed_graph,
+                        road_classes,
+                        &self.distance_unit,
+                    )?;
+                    output["route_road_class_distance"] = json![distances];
+                    output["route_road_class_distance_unit"] = json![self.distance_unit];
+                }
                 Ok(())
             }
         }
@@ -0,0 +1,74 @@
+use crate::plugin::plugin_error::PluginError;
+use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT};
+use std::collections::BTreeMap;
+
+/// sums the distance of a route traveled on each road class, which describes
+/// a route as mostly highway or mostly local roads.
+///
+/// # Arguments
+///
+/// * `route` - the edges of the route
+/// * `graph` - the road network, which provides the distance of each edge
+/// * `road_classes` - the road class of each edge, by edge id
+/// * `distance_unit` - unit of the reported distances
+///
+/// # Returns
+///
+/// the distance traveled on each road class found along the route, or an
+/// error if an edge has no road class
+pub fn road_class_distances(
+    route: &[EdgeTraversal],
+    graph: &Graph,
+    road_classes: &[u8],
+    distance_unit: &DistanceUnit,
+) -> Result<BTreeMap<u8, Distance>, PluginError> {
+    let mut distances: BTreeMap<u8, Distance> = BTreeMap::new();
+    for traversal in route.iter() {
+        let edge = graph
+            .get_edge(traversal.edge_id)
+            .map_err(|e| PluginError::PluginFailed(e.to_string()))?;
+        let road_class = road_classes.get(edge.edge_id.0).ok_or_else(|| {
+            PluginError::PluginFailed(format!("road class missing for edge id {}", edge.edge_id))
+        })?;
+        let distance = BASE_DISTANCE_UNIT.convert(&edge.distance, distance_unit);
+        let total = distances.entry(*road_class).or_insert(Distance::ZERO);
+        *total = *total + distance;
+    }
+    Ok(distances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routee_compass_core::model::{
+        road_network::edge_id::EdgeId,
+        unit::{as_f64::AsF64, Cost},
+    };
+    use routee_compass_core::test_util::graph_fixture::graph_from_links;
+
+    #[test]
+    fn test_distance_by_road_class() {
+        // a route that leaves a local road for a highway and exits onto a local road
+        let graph = graph_from_links(4, &[(0, 1, 250.0), (1, 2, 2000.0), (2, 3, 750.0)]);
+        let route = (0..3)
+            .map(|edge_id| EdgeTraversal {
+                edge_id: EdgeId(edge_id),
+                access_cost: Cost::ZERO,
+                traversal_cost: Cost::ZERO,
+                result_state: vec![],
+            })
+            .collect::<Vec<_>>();
+        let road_classes = [5, 1, 5];
+        let distances =
+            road_class_distances(&route, &graph, &road_classes, &DistanceUnit::Kilometers).unwrap();
+        assert_eq!(distances.len(), 2);
+        assert!((distances[&1].as_f64() - 2.0).abs() < 1e-9);
+        assert!((distances[&5].as_f64() - 1.0).abs() < 1e-9);
+
+        // every edge of the route needs a road class
+        let result = road_class_distances(&route, &graph, &[5, 1], &DistanceUnit::Kilometers);
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,11 @@
+use routee_compass_core::model::road_network::graph::Graph;
+use routee_compass_core::model::unit::{Distance, DistanceUnit, BASE_DISTANCE_UNIT};
 use std::sync::Arc;
 
-use super::{plugin::TraversalPlugin, traversal_output_format::TraversalOutputFormat};
+use super::{
+    plugin::{EdgeAttributesFn, TraversalPlugin},
+    traversal_output_format::TraversalOutputFormat,
+};
 use crate::{
     app::compass::config::{
         builders::OutputPluginBuilder, compass_configuration_error::CompassConfigurationError,
@@ -17,6 +22,12 @@ use crate::{
 /// * `geometry_file` - the filename providing edge geometries
 /// * `route` (optional) - traversal output format for the route result
 /// * `tree` (optional) - traversal output format for the search tree result
+/// * `densify_spacing` (optional) - largest distance between great circle points
+///   added to route geometries, where zero disables densification
+/// * `densify_spacing_unit` (optional) - unit of `densify_spacing`, meters by default
+/// * `simplify_tolerance` (optional) - Douglas-Peucker tolerance applied to route
+///   geometries, where zero disables simplification
+/// * `simplify_tolerance_unit` (optional) - unit of `simplify_tolerance`, meters by default
 ///
 /// See [TraversalOutputFormat] for information on the output formats supported.
 ///
@@ -32,7 +43,26 @@ use crate::{
 /// geometry_input_file = "edges-geometries-enumerated.txt.gz"
 /// ```
 ///
-pub struct TraversalPluginBuilder {}
+/// # Edge Attributes
+///
+/// attributes held outside of this crate, such as street names, can be
+/// attached to each traversed edge of a route by replacing the "traversal"
+/// builder of a [`crate::app::compass::config::compass_app_builder::CompassAppBuilder`]
+/// with one made by [`TraversalPluginBuilder::with_edge_attributes`].
+#[derive(Default)]
+pub struct TraversalPluginBuilder {
+    edge_attributes: Option<EdgeAttributesFn>,
+}
+
+impl TraversalPluginBuilder {
+    /// a builder whose plugins call `edge_attributes` for each traversed
+    /// edge of a route, attaching the values that are not null to the output
+    pub fn with_edge_attributes(edge_attributes: EdgeAttributesFn) -> TraversalPluginBuilder {
+        TraversalPluginBuilder {
+            edge_attributes: Some(edge_attributes),
+        }
+    }
+}
 
 impl OutputPluginBuilder for TraversalPluginBuilder {
     /// builds the traversal output plugin, which allows users to configure how they want to
@@ -40,6 +70,7 @@ impl OutputPluginBuilder for TraversalPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        graph: &Graph,
     ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
         let parent_key = String::from("traversal");
 
@@ -49,7 +80,28 @@ impl OutputPluginBuilder for TraversalPluginBuilder {
         let tree: Option<TraversalOutputFormat> =
             parameters.get_config_serde_optional(&"tree", &parent_key)?;
 
-        let geom_plugin = TraversalPlugin::from_file(&geometry_filename, route, tree)?;
+        let densify_spacing: Option<Distance> =
+            parameters.get_config_serde_optional(&"densify_spacing", &parent_key)?;
+        let densify_spacing_unit: Option<DistanceUnit> =
+            parameters.get_config_serde_optional(&"densify_spacing_unit", &parent_key)?;
+        let simplify_tolerance: Option<Distance> =
+            parameters.get_config_serde_optional(&"simplify_tolerance", &parent_key)?;
+        let simplify_tolerance_unit: Option<DistanceUnit> =
+            parameters.get_config_serde_optional(&"simplify_tolerance_unit", &parent_key)?;
+
+        let mut geom_plugin = TraversalPlugin::from_file(&geometry_filename, route, tree)?;
+        geom_plugin.edge_attributes = self.edge_attributes.clone();
+        geom_plugin.align_geometries(&graph.edge_table_layout)?;
+        if let Some(spacing) = densify_spacing {
+            geom_plugin.densify_spacing = densify_spacing_unit
+                .unwrap_or(BASE_DISTANCE_UNIT)
+                .convert(&spacing, &BASE_DISTANCE_UNIT);
+        }
+        if let Some(tolerance) = simplify_tolerance {
+            geom_plugin.simplify_tolerance = simplify_tolerance_unit
+                .unwrap_or(BASE_DISTANCE_UNIT)
+                .convert(&tolerance, &BASE_DISTANCE_UNIT);
+        }
         Ok(Arc::new(geom_plugin))
     }
 }
@@ -1,4 +1,5 @@
 use super::json_extensions::TraversalJsonField;
+use super::traversal_ops::RouteGeometryOptions;
 use super::traversal_output_format::TraversalOutputFormat;
 use crate::app::compass::compass_app_error::CompassAppError;
 use crate::app::search::search_app_result::SearchAppResult;
@@ -9,11 +10,19 @@ use kdam::Bar;
 use kdam::BarExt;
 use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
 use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::model::road_network::edge_id::EdgeId;
+use routee_compass_core::model::road_network::edge_table_layout::EdgeTableLayout;
+use routee_compass_core::model::unit::Distance;
 use routee_compass_core::util::fs::fs_utils;
 use routee_compass_core::util::fs::read_utils::read_raw_file;
 use routee_compass_core::util::geo::geo_io_utils;
 use serde_json::json;
 use std::path::Path;
+use std::sync::Arc;
+
+/// looks up caller-owned attributes for an edge, such as a street name or
+/// speed limit, returning null for edges without attributes
+pub type EdgeAttributesFn = Arc<dyn Fn(EdgeId) -> serde_json::Value + Send + Sync>;
 
 pub struct TraversalPlugin {
     geoms: Box<[LineString<f32>]>,
@@ -21,6 +30,16 @@ pub struct TraversalPlugin {
     tree: Option<TraversalOutputFormat>,
     route_key: String,
     tree_key: String,
+    /// when set, called for each traversed edge of a route to attach
+    /// attributes held outside of this crate to the route output
+    pub edge_attributes: Option<EdgeAttributesFn>,
+    /// Douglas-Peucker tolerance in meters applied to route geometries,
+    /// where zero leaves them unsimplified
+    pub simplify_tolerance: Distance,
+    /// largest distance in meters between the great circle points added to
+    /// route geometries so that long edges follow the curvature of the earth,
+    /// where zero leaves them undensified
+    pub densify_spacing: Distance,
 }
 
 impl TraversalPlugin {
@@ -29,7 +48,7 @@ impl TraversalPlugin {
         route: Option<TraversalOutputFormat>,
         tree: Option<TraversalOutputFormat>,
     ) -> Result<TraversalPlugin, PluginError> {
-        let count = fs_utils::line_count(filename, fs_utils::is_gzip(filename)).map_err(|e| {
+        let count = fs_utils::line_count(filename).map_err(|e| {
             PluginError::FileReadError(filename.as_ref().to_path_buf(), e.to_string())
         })?;
 
@@ -57,8 +76,23 @@ impl TraversalPlugin {
             tree,
             route_key,
             tree_key,
+            edge_attributes: None,
+            simplify_tolerance: Distance::ZERO,
+            densify_spacing: Distance::ZERO,
         })
     }
+
+    /// arranges the geometries by the `EdgeId`s of the graph. the reverse
+    /// edges of a bidirectional graph follow the same path as their edge row
+    /// in the opposite direction.
+    pub fn align_geometries(&mut self, layout: &EdgeTableLayout) -> Result<(), PluginError> {
+        self.geoms = layout
+            .align(&self.geoms, |geom| {
+                LineString::new(geom.0.iter().rev().cloned().collect())
+            })
+            .map_err(|e| PluginError::InternalError(e.to_string()))?;
+        Ok(())
+    }
 }
 
 impl OutputPlugin for TraversalPlugin {
@@ -73,11 +107,22 @@ impl OutputPlugin for TraversalPlugin {
                 match self.route {
                     None => {}
                     Some(route_args) => {
+                        let geometry_options = RouteGeometryOptions {
+                            simplify_tolerance: self.simplify_tolerance,
+                            densify_spacing: self.densify_spacing,
+                        };
                         let routes_serialized = result
                             .routes
                             .iter()
                             .map(|route| {
-                                construct_route_output(route, si, &route_args, &self.geoms)
+                                construct_route_output(
+                                    route,
+                                    si,
+                                    &route_args,
+                                    &self.geoms,
+                                    &geometry_options,
+                                    self.edge_attributes.as_ref(),
+                                )
                             })
                             .collect::<Result<Vec<_>, _>>()
                             .map_err(PluginError::PluginFailed)?;
@@ -123,12 +168,14 @@ fn construct_route_output(
     si: &SearchInstance,
     output_format: &TraversalOutputFormat,
     geoms: &[LineString<f32>],
+    geometry_options: &RouteGeometryOptions,
+    edge_attributes: Option<&EdgeAttributesFn>,
 ) -> Result<serde_json::Value, String> {
     let last_edge = route
         .last()
         .ok_or_else(|| String::from("cannot find result route state when route is empty"))?;
     let path_json = output_format
-        .generate_route_output(route, geoms)
+        .generate_shaped_route_output(route, geoms, geometry_options)
         .map_err(|e| e.to_string())?;
     let traversal_summary = si.state_model.serialize_state(&last_edge.result_state);
     let state_model = si.state_model.serialize_state_model();
@@ -140,19 +187,113 @@ fn construct_route_output(
         .cost_model
         .serialize_cost_info()
         .map_err(|e| e.to_string())?;
-    let result = serde_json::json![{
+    let traversal_model_summary = si
+        .traversal_model
+        .summary(&last_edge.result_state, &si.state_model)
+        .map_err(|e| e.to_string())?;
+    let mut result = serde_json::json![{
         "traversal_summary": traversal_summary,
         "state_model": state_model,
         "cost_model": cost_model,
         "cost": cost,
         "path": path_json
     }];
+    if let Some(summary) = traversal_model_summary {
+        result["traversal_model_summary"] = summary;
+    }
+    if let Some(cost_unit) = si.cost_model.cost_unit() {
+        result["cost_unit"] = serde_json::json!(cost_unit.to_string());
+    }
+    let edge_details = route_edge_details(route, si)?;
+    if !edge_details.is_empty() {
+        result["edge_details"] = serde_json::json!(edge_details);
+    }
+    if let Some(edge_attributes) = edge_attributes {
+        attach_edge_attributes(&mut result, route, output_format, edge_attributes);
+    }
     Ok(result)
 }
 
+/// collects the details the traversal model reports for each edge of a
+/// route, resolved from the state of the route before that edge.
+fn route_edge_details(
+    route: &[EdgeTraversal],
+    si: &SearchInstance,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut state = si.state_model.initial_state().map_err(|e| e.to_string())?;
+    let mut details = vec![];
+    for et in route.iter() {
+        let edge = si
+            .directed_graph
+            .get_edge(et.edge_id)
+            .map_err(|e| e.to_string())?;
+        let src = si
+            .directed_graph
+            .get_vertex(edge.src_vertex_id)
+            .map_err(|e| e.to_string())?;
+        let dst = si
+            .directed_graph
+            .get_vertex(edge.dst_vertex_id)
+            .map_err(|e| e.to_string())?;
+        let edge_details = si
+            .traversal_model
+            .edge_details((src, edge, dst), &state, &si.state_model)
+            .map_err(|e| e.to_string())?;
+        if let Some(edge_details) = edge_details {
+            details.push(json!({ "edge_id": et.edge_id, "details": edge_details }));
+        }
+        state.clone_from(&et.result_state);
+    }
+    Ok(details)
+}
+
+/// attaches the caller-provided attributes of each traversed edge to a route
+/// output. formats with an entry per edge receive an `attributes` field on
+/// that entry, while the other formats list the attributes by edge id under
+/// `edge_attributes`. edges whose attributes are null are left unchanged.
+fn attach_edge_attributes(
+    result: &mut serde_json::Value,
+    route: &[EdgeTraversal],
+    output_format: &TraversalOutputFormat,
+    edge_attributes: &EdgeAttributesFn,
+) {
+    let attributes = route
+        .iter()
+        .map(|et| (et.edge_id, edge_attributes(et.edge_id)));
+    let entries = match output_format {
+        TraversalOutputFormat::Json => result["path"].as_array_mut(),
+        TraversalOutputFormat::GeoJson => result["path"]["features"].as_array_mut(),
+        _ => None,
+    };
+    match entries {
+        Some(entries) => {
+            for (entry, (_, attrs)) in entries.iter_mut().zip(attributes) {
+                if attrs.is_null() {
+                    continue;
+                }
+                match output_format {
+                    TraversalOutputFormat::GeoJson => entry["properties"]["attributes"] = attrs,
+                    _ => entry["attributes"] = attrs,
+                }
+            }
+        }
+        None => {
+            let listed = attributes
+                .filter(|(_, attrs)| !attrs.is_null())
+                .map(|(edge_id, attrs)| json!({ "edge_id": edge_id, "attributes": attrs }))
+                .collect::<Vec<_>>();
+            if !listed.is_empty() {
+                result["edge_attributes"] = json!(listed);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use super::*;
+    use routee_compass_core::model::unit::Cost;
     use routee_compass_core::util::{
         fs::read_utils::read_raw_file, geo::geo_io_utils::parse_linestring,
     };
@@ -175,6 +316,103 @@ mod tests {
         assert_eq!(result.len(), 3);
     }
 
+    #[test]
+    fn test_align_geometries() {
+        let mut plugin = TraversalPlugin::from_file(&mock_geometry_file(), None, None).unwrap();
+        let forward = plugin.geoms.clone();
+        let layout = EdgeTableLayout {
+            n_rows: 3,
+            rows: None,
+            bidirectional: true,
+        };
+        plugin.align_geometries(&layout).unwrap();
+        assert_eq!(plugin.geoms.len(), 6);
+        let mut reversed = plugin.geoms[3].0.clone();
+        reversed.reverse();
+        assert_eq!(reversed, forward[0].0);
+    }
+
+    fn route_output(
+        output_format: TraversalOutputFormat,
+        edge_attributes: &EdgeAttributesFn,
+    ) -> serde_json::Value {
+        let geoms = read_raw_file(mock_geometry_file(), parse_linestring, None).unwrap();
+        let route = (0..3)
+            .map(|edge_id| EdgeTraversal {
+                edge_id: EdgeId(edge_id),
+                access_cost: Cost::ZERO,
+                traversal_cost: Cost::ONE,
+                result_state: vec![],
+            })
+            .collect::<Vec<_>>();
+        let path = output_format.generate_route_output(&route, &geoms).unwrap();
+        let mut result = json!({ "path": path });
+        attach_edge_attributes(&mut result, &route, &output_format, edge_attributes);
+        result
+    }
+
+    /// names edges 0 and 2, with no attributes for edge 1
+    fn street_names() -> EdgeAttributesFn {
+        Arc::new(|edge_id: EdgeId| match edge_id.0 {
+            0 => json!({ "name": "Main St" }),
+            2 => json!({ "name": "Elm St" }),
+            _ => serde_json::Value::Null,
+        })
+    }
+
+    #[test]
+    fn test_edge_attributes_attach_to_each_edge() {
+        let result = route_output(TraversalOutputFormat::Json, &street_names());
+        assert_eq!(result["path"][0]["attributes"]["name"], "Main St");
+        assert!(result["path"][1].get("attributes").is_none());
+        assert_eq!(result["path"][2]["attributes"]["name"], "Elm St");
+
+        let result = route_output(TraversalOutputFormat::GeoJson, &street_names());
+        let features = &result["path"]["features"];
+        assert_eq!(features[0]["properties"]["attributes"]["name"], "Main St");
+        assert!(features[1]["properties"].get("attributes").is_none());
+    }
+
+    #[test]
+    fn test_edge_attributes_listed_for_geometry_formats() {
+        let result = route_output(TraversalOutputFormat::Wkt, &street_names());
+        assert_eq!(
+            result["edge_attributes"],
+            json!([
+                { "edge_id": 0, "attributes": { "name": "Main St" } },
+                { "edge_id": 2, "attributes": { "name": "Elm St" } },
+            ])
+        );
+
+        let no_attributes: EdgeAttributesFn = Arc::new(|_| serde_json::Value::Null);
+        let result = route_output(TraversalOutputFormat::Wkt, &no_attributes);
+        assert!(result.get("edge_attributes").is_none());
+    }
+
+    #[test]
+    fn test_densify_route_output() {
+        let geoms = read_raw_file(mock_geometry_file(), parse_linestring, None).unwrap();
+        let route = vec![EdgeTraversal {
+            edge_id: EdgeId(0),
+            access_cost: Cost::ZERO,
+            traversal_cost: Cost::ONE,
+            result_state: vec![],
+        }];
+        let options = RouteGeometryOptions {
+            simplify_tolerance: Distance::ZERO,
+            densify_spacing: Distance::new(10_000.0),
+        };
+        let path = TraversalOutputFormat::GeoJson
+            .generate_shaped_route_output(&route, &geoms, &options)
+            .unwrap();
+        let coords = path["features"][0]["geometry"]["coordinates"]
+            .as_array()
+            .unwrap();
+        assert!(coords.len() > geoms[0].0.len());
+        assert_eq!(coords.first(), Some(&json!([0.0, 0.0])));
+        assert_eq!(coords.last(), Some(&json!([2.0, 2.0])));
+    }
+
     // TODO:
     //   the API for OutputPlugin now expects a SearchInstance which is non-trivial to instantiate.
     //   the logic for adding geometries should be refactored into a separate function and this test
@@ -1,3 +1,4 @@
+use crate::plugin::output::polyline;
 use crate::plugin::plugin_error::PluginError;
 use geo::{LineString, MultiLineString, Point};
 use geo_types::MultiPoint;
@@ -6,9 +7,34 @@ use geojson::{Feature, FeatureCollection};
 use routee_compass_core::algorithm::search::edge_traversal::EdgeTraversal;
 use routee_compass_core::algorithm::search::search_tree_branch::SearchTreeBranch;
 use routee_compass_core::model::road_network::vertex_id::VertexId;
-use routee_compass_core::util::geo::geo_io_utils;
+use routee_compass_core::model::unit::{as_f64::AsF64, Distance};
+use routee_compass_core::util::geo::{geo_io_utils, haversine, simplify};
 use std::collections::HashMap;
 
+/// how the geometry of a returned route is shaped at output time. only the
+/// edges of the route are shaped, leaving the edge geometries untouched.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RouteGeometryOptions {
+    /// Douglas-Peucker tolerance in meters, zero to disable
+    pub simplify_tolerance: Distance,
+    /// largest distance in meters between great circle points added between
+    /// coordinates, zero to disable
+    pub densify_spacing: Distance,
+}
+
+impl RouteGeometryOptions {
+    /// simplifies a geometry and then densifies it, so that the great circle
+    /// points are not removed again by the simplification.
+    pub fn apply(&self, geometry: &LineString<f32>) -> Result<LineString<f32>, PluginError> {
+        let simplified = simplify::simplify_linestring(geometry, self.simplify_tolerance);
+        if self.densify_spacing.as_f64() <= 0.0 {
+            return Ok(simplified);
+        }
+        haversine::densify_linestring(&simplified, self.densify_spacing)
+            .map_err(PluginError::InternalError)
+    }
+}
+
 pub fn create_tree_geojson(
     tree: &HashMap<VertexId, SearchTreeBranch>,
     geoms: &[LineString<f32>],
@@ -35,21 +61,17 @@ pub fn create_tree_geojson(
     Ok(result)
 }
 
+/// creates a GeoJSON feature for each edge of a route, shaping each edge
+/// geometry with the given options.
 pub fn create_route_geojson(
     route: &[EdgeTraversal],
     geoms: &[LineString<f32>],
+    options: &RouteGeometryOptions,
 ) -> Result<serde_json::Value, PluginError> {
     let features = route
         .iter()
-        .map(|t| {
-            let row_result = geoms
-                .get(t.edge_id.0)
-                .cloned()
-                .ok_or_else(|| PluginError::EdgeGeometryMissing(t.edge_id))
-                .and_then(|g| create_geojson_feature(t, g));
-
-            row_result
-        })
+        .zip(route_edge_geometries(route, geoms)?)
+        .map(|(t, g)| create_geojson_feature(t, options.apply(g)?))
         .collect::<Result<Vec<_>, PluginError>>()?;
     // let result_json = serde_json::to_value(features)?;/
     let feature_collection = FeatureCollection {
@@ -103,26 +125,43 @@ pub fn create_branch_geometry(
     create_edge_geometry(&branch.edge_traversal, geoms)
 }
 
+/// concatenates the edge geometries of a route into a single linestring,
+/// shaped with the given options.
 pub fn create_route_linestring(
     route: &[EdgeTraversal],
     geoms: &[LineString<f32>],
+    options: &RouteGeometryOptions,
 ) -> Result<LineString<f32>, PluginError> {
-    let edge_ids = route
-        .iter()
-        .map(|traversal| traversal.edge_id)
-        .collect::<Vec<_>>();
+    let edge_linestrings = route_edge_geometries(route, geoms)?;
+    let geometry = geo_io_utils::concat_linestrings(edge_linestrings);
+    options.apply(&geometry)
+}
 
-    let edge_linestrings = edge_ids
+/// looks up the geometry of each edge of a route, in route order. shared by
+/// the route outputs so that each reports a missing geometry the same way.
+pub fn route_edge_geometries<'a>(
+    route: &[EdgeTraversal],
+    geoms: &'a [LineString<f32>],
+) -> Result<Vec<&'a LineString<f32>>, PluginError> {
+    route
         .iter()
-        .map(|eid| {
-            let geom = geoms
-                .get(eid.0)
-                .ok_or_else(|| PluginError::EdgeGeometryMissing(*eid));
-            geom
+        .map(|t| {
+            geoms
+                .get(t.edge_id.0)
+                .ok_or(PluginError::EdgeGeometryMissing(t.edge_id))
         })
-        .collect::<Result<Vec<&LineString<f32>>, PluginError>>()?;
-    let geometry = geo_io_utils::concat_linestrings(edge_linestrings);
-    Ok(geometry)
+        .collect()
+}
+
+/// encodes the geometry of a route as a polyline string.
+pub fn create_route_polyline(
+    route: &[EdgeTraversal],
+    geoms: &[LineString<f32>],
+    precision: u32,
+    options: &RouteGeometryOptions,
+) -> Result<String, PluginError> {
+    let route_geometry = create_route_linestring(route, geoms, options)?;
+    Ok(polyline::encode(&route_geometry.0, precision))
 }
 
 pub fn create_tree_multilinestring(
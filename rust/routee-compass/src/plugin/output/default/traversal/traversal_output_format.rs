@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use super::traversal_ops as ops;
+use super::traversal_ops::{self as ops, RouteGeometryOptions};
+use crate::plugin::output::polyline;
 use crate::plugin::plugin_error::PluginError;
 use geo::LineString;
 use routee_compass_core::{
@@ -20,6 +21,10 @@ pub enum TraversalOutputFormat {
     // returns the geometries and properties as GeoJSON
     GeoJson,
     EdgeId,
+    // returns the geometry as an encoded polyline with 5 decimal places of precision
+    Polyline,
+    // returns the geometry as an encoded polyline with 6 decimal places of precision
+    Polyline6,
 }
 
 impl TraversalOutputFormat {
@@ -28,10 +33,28 @@ impl TraversalOutputFormat {
         &self,
         route: &Vec<EdgeTraversal>,
         geoms: &[LineString<f32>],
+    ) -> Result<serde_json::Value, PluginError> {
+        self.generate_shaped_route_output(route, geoms, &RouteGeometryOptions::default())
+    }
+
+    /// generates output for a route, simplifying the route geometry with the
+    /// Douglas-Peucker algorithm and densifying it with great circle points.
+    /// formats without a geometry are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - the route to output
+    /// * `geoms` - the geometry of each edge
+    /// * `options` - how to shape the route geometry
+    pub fn generate_shaped_route_output(
+        &self,
+        route: &Vec<EdgeTraversal>,
+        geoms: &[LineString<f32>],
+        options: &RouteGeometryOptions,
     ) -> Result<serde_json::Value, PluginError> {
         match self {
             TraversalOutputFormat::Wkt => {
-                let route_geometry = ops::create_route_linestring(route, geoms)?;
+                let route_geometry = ops::create_route_linestring(route, geoms, options)?;
                 let route_wkt = route_geometry.wkt_string();
                 Ok(serde_json::Value::String(route_wkt))
             }
@@ -40,7 +63,7 @@ impl TraversalOutputFormat {
                 Ok(result)
             }
             TraversalOutputFormat::GeoJson => {
-                let result = ops::create_route_geojson(route, geoms)?;
+                let result = ops::create_route_geojson(route, geoms, options)?;
                 Ok(result)
             }
             TraversalOutputFormat::EdgeId => {
@@ -48,6 +71,11 @@ impl TraversalOutputFormat {
                 let json = serde_json::json![route_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Polyline | TraversalOutputFormat::Polyline6 => {
+                let precision = self.polyline_precision();
+                let route_polyline = ops::create_route_polyline(route, geoms, precision, options)?;
+                Ok(serde_json::Value::String(route_polyline))
+            }
         }
     }
 
@@ -79,6 +107,25 @@ impl TraversalOutputFormat {
                 let json = serde_json::json![tree_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Polyline | TraversalOutputFormat::Polyline6 => {
+                let precision = self.polyline_precision();
+                let polylines = tree
+                    .values()
+                    .map(|b| {
+                        ops::create_branch_geometry(b, geoms)
+                            .map(|g| polyline::encode(&g.0, precision))
+                    })
+                    .collect::<Result<Vec<_>, PluginError>>()?;
+                Ok(serde_json::json![polylines])
+            }
+        }
+    }
+
+    /// number of decimal places kept by the polyline formats
+    fn polyline_precision(&self) -> u32 {
+        match self {
+            TraversalOutputFormat::Polyline6 => 6,
+            _ => 5,
         }
     }
 }
@@ -90,7 +137,7 @@ mod test {
     use chrono::Local;
     use geo::{coord, LineString};
     use routee_compass_core::{
-        algorithm::search::edge_traversal::EdgeTraversal,
+        algorithm::search::{edge_traversal::EdgeTraversal, search_metadata::SearchMetadata},
         model::{
             road_network::edge_id::EdgeId, traversal::state::state_variable::StateVar, unit::Cost,
         },
@@ -121,10 +168,12 @@ mod test {
         ];
         let result = SearchAppResult {
             routes: vec![route],
+            route_coordinates: None,
             trees: vec![],
             search_executed_time: Local::now().to_rfc3339(),
             search_runtime: Duration::ZERO,
             iterations: 0,
+            search_metadata: SearchMetadata::default(),
         };
 
         let geoms = vec![
@@ -162,5 +211,21 @@ mod test {
                 .generate_route_output(&result.routes[0], &geoms)
                 .map(|r| serde_json::to_string_pretty(&r))
         );
+        let route_polyline = TraversalOutputFormat::Polyline
+            .generate_route_output(&result.routes[0], &geoms)
+            .unwrap();
+        assert_eq!(
+            route_polyline,
+            serde_json::json!(polyline::encode(
+                &ops::create_route_linestring(
+                    &result.routes[0],
+                    &geoms,
+                    &RouteGeometryOptions::default()
+                )
+                .unwrap()
+                .0,
+                5
+            ))
+        );
     }
 }
@@ -1,3 +1,4 @@
+use routee_compass_core::model::road_network::graph::Graph;
 use std::sync::Arc;
 
 use crate::{
@@ -16,6 +17,7 @@ impl OutputPluginBuilder for UUIDOutputPluginBuilder {
     fn build(
         &self,
         parameters: &serde_json::Value,
+        _graph: &Graph,
     ) -> Result<Arc<dyn OutputPlugin>, CompassConfigurationError> {
         let uuid_filename = parameters.get_config_path(&"uuid_input_file", &"uuid")?;
 
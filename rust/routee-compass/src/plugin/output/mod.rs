@@ -1,3 +1,4 @@
 pub mod default;
 pub mod output_plugin;
 pub mod output_plugin_ops;
+pub mod polyline;
@@ -2,7 +2,10 @@ use crate::app::{
     compass::compass_app_error::CompassAppError,
     search::{search_app::SearchApp, search_app_result::SearchAppResult},
 };
-use routee_compass_core::algorithm::search::search_instance::SearchInstance;
+use routee_compass_core::algorithm::search::{
+    search_instance::SearchInstance, search_metadata::SearchMetadata, search_outcome::SearchOutcome,
+};
+use routee_compass_core::util::duration_extension::DurationExtension;
 use serde_json::{json, Value};
 
 /// creates the initial output with summary information from the search app,
@@ -14,7 +17,7 @@ pub fn create_initial_output(
 ) -> Result<Value, Value> {
     match &res {
         Err(e) => Err(package_error(req, e)),
-        Ok((_, _)) => {
+        Ok((result, _)) => {
             let mut init_output = serde_json::json!({
                 "request": req,
             });
@@ -23,6 +26,15 @@ pub fn create_initial_output(
             init_output["output_plugin_executed_time"] =
                 serde_json::json!(output_plugin_executed_time.to_rfc3339());
 
+            // each route as a list of [x, y] pairs
+            if let Some(route_coordinates) = &result.route_coordinates {
+                let routes = route_coordinates
+                    .iter()
+                    .map(|route| route.iter().map(|c| json!([c.x, c.y])).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                init_output["route_coordinates"] = json!(routes);
+            }
+
             Ok(init_output)
         }
     }
@@ -36,3 +48,38 @@ pub fn package_error<E: ToString>(req: &Value, error: E) -> Value {
         "error": error.to_string()
     })
 }
+
+/// returns a search that found no acceptable route as a JSON response object
+/// with the original request, the name of the `outcome`, an error message,
+/// and the `search_metadata` of the search when it ran to completion, as
+/// failed searches are the ones most worth inspecting.
+pub fn package_outcome<T>(req: &Value, outcome: &SearchOutcome<T>) -> Value {
+    let mut result = json!({
+        "request": req,
+        "outcome": outcome.name(),
+    });
+    if let Some(error) = outcome.error() {
+        result["error"] = json!(error.to_string());
+    }
+    if let Some(metadata) = outcome.metadata() {
+        result["search_metadata"] = search_metadata_json(metadata);
+    }
+    result
+}
+
+/// serializes the counters of a search for the `search_metadata` output field
+pub fn search_metadata_json(metadata: &SearchMetadata) -> Value {
+    let mut result = json![{
+        "vertices_settled": metadata.vertices_settled,
+        "edges_relaxed": metadata.edges_relaxed,
+        "peak_open_set_size": metadata.peak_open_set_size,
+        "runtime": metadata.runtime.hhmmss(),
+        "max_edges_binding": metadata.max_edges_binding(),
+        "frontier_rejections": metadata.frontier_rejections,
+    }];
+    if let Some(bound) = metadata.suboptimality_bound {
+        result["approximate"] = json![true];
+        result["suboptimality_bound"] = json![bound];
+    }
+    result
+}
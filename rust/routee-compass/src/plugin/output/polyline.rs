@@ -0,0 +1,72 @@
+use geo::Coord;
+
+/// encodes a sequence of coordinates with the encoded polyline algorithm
+/// used by Google Maps and OSRM. each coordinate is written as a latitude,
+/// longitude pair, rounded to `precision` decimal places (5 is the standard,
+/// 6 is used by OSRM's `polyline6`) and stored as the difference from the
+/// previous coordinate.
+///
+/// # Arguments
+///
+/// * `coordinates` - the coordinates of the line, with x as longitude and y as latitude
+/// * `precision` - number of decimal places to keep
+///
+/// # Returns
+///
+/// the encoded polyline, which is empty if there are no coordinates
+pub fn encode(coordinates: &[Coord<f32>], precision: u32) -> String {
+    let factor = 10_f64.powi(precision as i32);
+    let mut encoded = String::new();
+    let mut prev_lat = 0_i64;
+    let mut prev_lon = 0_i64;
+    for coord in coordinates.iter() {
+        let lat = (coord.y as f64 * factor).round() as i64;
+        let lon = (coord.x as f64 * factor).round() as i64;
+        encode_value(lat - prev_lat, &mut encoded);
+        encode_value(lon - prev_lon, &mut encoded);
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+    encoded
+}
+
+/// appends one signed value, written in 5-bit chunks from the least significant,
+/// each offset by 63 into printable ASCII and flagged with 0x20 if more follow.
+fn encode_value(value: i64, encoded: &mut String) {
+    let mut remaining = if value < 0 { !(value << 1) } else { value << 1 } as u64;
+    while remaining >= 0x20 {
+        let chunk = ((remaining & 0x1f) | 0x20) as u8 + 63;
+        encoded.push(chunk as char);
+        remaining >>= 5;
+    }
+    encoded.push((remaining as u8 + 63) as char);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::coord;
+
+    #[test]
+    fn test_encode_known_polyline() {
+        // the example from the encoded polyline algorithm format documentation
+        let coordinates = vec![
+            coord! { x: -120.2, y: 38.5 },
+            coord! { x: -120.95, y: 40.7 },
+            coord! { x: -126.453, y: 43.252 },
+        ];
+        assert_eq!(encode(&coordinates, 5), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    }
+
+    #[test]
+    fn test_encode_precision_6() {
+        // exactly representable as f32, which cannot hold 6 decimal places of most longitudes
+        let coordinates = vec![coord! { x: -120.25, y: 38.5 }];
+        assert_eq!(encode(&coordinates, 6), "_izlhA~gnjdF");
+    }
+
+    #[test]
+    fn test_encode_empty() {
+        assert_eq!(encode(&[], 5), "");
+    }
+}
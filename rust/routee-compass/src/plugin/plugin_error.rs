@@ -20,8 +20,9 @@ pub enum PluginError {
     FileReadError(PathBuf, String),
     #[error(transparent)]
     JsonError(#[from] serde_json::Error),
+    /// boxed to keep PluginError small, as a geojson error is far larger than the other variants
     #[error(transparent)]
-    GeoJsonError(#[from] geojson::Error),
+    GeoJsonError(Box<geojson::Error>),
     #[error(transparent)]
     CsvReadError(#[from] csv::Error),
     #[error("geometry missing for edge id {0}")]
@@ -35,3 +36,9 @@ pub enum PluginError {
     #[error("unexpected error {0}")]
     InternalError(String),
 }
+
+impl From<geojson::Error> for PluginError {
+    fn from(value: geojson::Error) -> Self {
+        PluginError::GeoJsonError(Box::new(value))
+    }
+}